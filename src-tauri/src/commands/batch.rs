@@ -0,0 +1,28 @@
+use crate::{
+    core::{AppError, AppResult},
+    database::with_db,
+    services::batch_service::{run_batch, BatchRequest, BatchResponse},
+};
+
+/// Most requests a single batch may hold
+const MAX_BATCH_REQUESTS: usize = 1000;
+
+/// Runs several read-only queries (metadata, preview, backlinks) in one round
+/// trip and one transaction, e.g. to fill a page of search results. Responses
+/// come back in request order, each with either a result or an error.
+#[tauri::command]
+pub fn batch_invoke(
+    requests: Vec<BatchRequest>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<BatchResponse>, String> {
+    let result = || -> AppResult<Vec<BatchResponse>> {
+        if requests.len() > MAX_BATCH_REQUESTS {
+            return Err(AppError::validation_error(
+                "requests",
+                &format!("A batch may hold at most {} requests", MAX_BATCH_REQUESTS),
+            ));
+        }
+        with_db(&app_state, |conn| run_batch(conn, &requests))
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,66 @@
+use crate::{
+    commands::note_crud::perform_safe_write_and_update,
+    core::{AppError, AppResult},
+    services::{
+        capture_service::append_text,
+        folder_service::{new_note_folder, note_under_folder},
+    },
+    utilities::{text_encoding::read_note_text, validation::validate_note_name},
+};
+use std::sync::Mutex;
+
+/// Serializes read-append-write cycles so concurrent captures don't drop text.
+static APPEND_LOCK: Mutex<()> = Mutex::new(());
+
+/// Appends `text` on a new line at the end of `note_name`, or of the configured
+/// inbox note when no name is given, creating the note if needed. The inbox
+/// note lives where `[preferences] new_note_location` puts new notes.
+#[tauri::command]
+pub fn append_to_note(
+    note_name: Option<String>,
+    text: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    append_text_to_note(note_name, text, &app_state)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Does what `append_to_note` does, returning the note appended to.
+pub(crate) fn append_text_to_note(
+    note_name: Option<String>,
+    text: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<String> {
+    let (note_name, notes_dir) = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        (
+            note_name.unwrap_or_else(|| {
+                let folder = new_note_folder(
+                    &config.preferences.new_note_location,
+                    &config.preferences.inbox_folder,
+                    None,
+                );
+                note_under_folder(&config.capture.inbox_note, folder.as_deref())
+            }),
+            std::path::PathBuf::from(&config.notes_directory),
+        )
+    };
+    validate_note_name(&note_name)?;
+    let note_path = notes_dir.join(&note_name);
+
+    let _guard = APPEND_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let existing = match read_note_text(&note_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(AppError::FileRead(format!(
+                "Failed to read '{}': {}",
+                note_name, e
+            )))
+        }
+    };
+    let content = append_text(&existing, text)?;
+    perform_safe_write_and_update(&note_path, &content, &note_name, app_state)?;
+    Ok(note_name)
+}
@@ -0,0 +1,97 @@
+use crate::{
+    commands::note_crud::perform_safe_write_and_update,
+    core::{AppError, AppResult},
+    services::reference_service::{self, resolve_doi},
+    utilities::{
+        citations::{active_bibliography, format_inline_citation, format_reference, BibEntry},
+        references::{append_reference_entry, normalize_doi, parse_reference_link, ReferenceLink},
+        text_encoding::read_note_text,
+        validation::validate_note_name,
+    },
+};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedCitation {
+    pub entry: BibEntry,
+    pub inline: String,
+    pub reference: String,
+}
+
+impl ResolvedCitation {
+    fn new(entry: BibEntry) -> Self {
+        Self {
+            inline: format!("({})", format_inline_citation(&entry, None, false)),
+            reference: format_reference(&entry),
+            entry,
+        }
+    }
+}
+
+#[tauri::command]
+pub fn resolve_citation(key: String) -> Result<Option<ResolvedCitation>, String> {
+    let result = || -> AppResult<Option<ResolvedCitation>> {
+        let (bibliography, _) = active_bibliography().ok_or_else(|| {
+            AppError::ConfigLoad(
+                "No readable bibliography configured. Set [citations] bibliography_file."
+                    .to_string(),
+            )
+        })?;
+
+        let key = key.trim().trim_start_matches('@');
+        Ok(bibliography.get(key).cloned().map(ResolvedCitation::new))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Metadata for a DOI (`10.x/y`, `doi:` or doi.org URL) or Better BibTeX
+/// `zotero://select/items/@key` link. DOIs missing from the local cache are
+/// fetched from doi.org and cached; `None` for unknown Zotero keys.
+#[tauri::command]
+pub fn resolve_reference(link: String) -> Result<Option<ResolvedCitation>, String> {
+    let result = || -> AppResult<Option<ResolvedCitation>> {
+        let link = parse_reference_link(link.trim())
+            .or_else(|| normalize_doi(&link).map(ReferenceLink::Doi))
+            .ok_or_else(|| {
+                AppError::validation_error("link", "Expected a DOI or zotero://select link")
+            })?;
+        Ok(reference_service::resolve_reference(&link)?.map(ResolvedCitation::new))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Appends the formatted reference for `doi` to the `[citations]
+/// references_heading` section of `note_name`, creating the section if
+/// needed. Returns the entry.
+#[tauri::command]
+pub fn insert_reference(
+    note_name: &str,
+    doi: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(note_name)?;
+        let (notes_dir, heading) = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            (
+                std::path::PathBuf::from(&config.notes_directory),
+                config.citations.references_heading.clone(),
+            )
+        };
+        let note_path = notes_dir.join(note_name);
+        let existing = read_note_text(&note_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                AppError::FileNotFound(format!("Note not found: {}", note_name))
+            }
+            _ => AppError::FileRead(format!("Failed to read '{}': {}", note_name, e)),
+        })?;
+
+        let entry = format_reference(&resolve_doi(doi)?);
+        let content = append_reference_entry(&existing, &heading, &entry);
+        if content != existing {
+            perform_safe_write_and_update(&note_path, &content, note_name, &app_state)?;
+        }
+        Ok(entry)
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -1,11 +1,17 @@
 use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
 
 use crate::config::{
-    get_available_markdown_themes, get_available_ui_themes, load_config_from_content, EditorConfig,
-    GeneralConfig, InterfaceConfig, PreferencesConfig, ShortcutsConfig,
+    get_available_markdown_themes, get_available_ui_themes, load_config, load_config_from_content,
+    EditorConfig, GeneralConfig, InterfaceConfig, PreferencesConfig, ShortcutsConfig,
 };
 use crate::core::{AppError, AppResult};
-use crate::utilities::paths::get_config_path;
+use crate::services::path_access_service::{
+    add_path_grant, authorize_read, default_read_roots, load_path_grants, remove_path_grant,
+};
+use crate::services::vault_check_service::{self, VaultCheck};
+use crate::services::vault_service::unpicked_vault_paths;
+use crate::utilities::paths::{get_config_path, get_path_grants_path};
 use crate::utilities::validation::validate_config;
 use std::fs;
 
@@ -19,6 +25,21 @@ pub fn get_config_content() -> Result<String, String> {
     }
 }
 
+/// Pre-flight check of a folder before it becomes the notes directory:
+/// whether it can be read, roughly how many notes it holds, and anything that
+/// shouldn't be indexed, like other vaults or the app's own backups.
+#[tauri::command]
+pub fn check_vault(
+    path: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> VaultCheck {
+    let current_vault = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        std::path::PathBuf::from(&config.notes_directory)
+    };
+    vault_check_service::check_vault(path, &current_vault)
+}
+
 #[tauri::command]
 pub fn config_exists(app_state: tauri::State<crate::core::state::AppState>) -> bool {
     !app_state
@@ -34,6 +55,20 @@ pub fn save_config_content(content: &str) -> Result<(), String> {
 
     validate_config(&config).map_err(|e| format!("Configuration validation failed: {}", e))?;
 
+    let current = load_config();
+    let unpicked = unpicked_vault_paths(
+        &current.vaults,
+        &current.notes_directory,
+        &config.vaults,
+        &config.notes_directory,
+    );
+    if !unpicked.is_empty() {
+        return Err(format!(
+            "New vault folders must be added with the folder picker: {}",
+            unpicked.join(", ")
+        ));
+    }
+
     if let Some(parent) = config_path.parent() {
         std::fs::create_dir_all(parent)
             .map_err(|e| format!("Failed to create config directory: {}", e))?;
@@ -180,13 +215,16 @@ pub fn get_preferences_config(
     config.preferences.clone()
 }
 
+/// Resolves a theme path the webview asked to read, allowing only the default
+/// roots and user grants. Checked before anything else so the answer doesn't
+/// reveal whether files outside them exist.
+fn authorize_theme_path(path: &str) -> AppResult<std::path::PathBuf> {
+    authorize_read(path, &default_read_roots(), &get_path_grants_path()?)
+}
+
 #[tauri::command]
 pub fn load_custom_theme_file(path: String) -> AppResult<String> {
-    let theme_path = std::path::Path::new(&path);
-
-    if !theme_path.exists() {
-        return Err(AppError::FileNotFound(path));
-    }
+    let theme_path = &authorize_theme_path(&path)?;
 
     if !theme_path.is_file() {
         return Err(AppError::InvalidPath(format!(
@@ -210,11 +248,11 @@ pub fn load_custom_theme_file(path: String) -> AppResult<String> {
 
 #[tauri::command]
 pub fn validate_theme_path(path: String) -> AppResult<bool> {
-    let theme_path = std::path::Path::new(&path);
-
-    if !theme_path.exists() {
-        return Ok(false);
-    }
+    let theme_path = match authorize_theme_path(&path) {
+        Ok(theme_path) => theme_path,
+        Err(AppError::FileNotFound(_)) => return Ok(false),
+        Err(e) => return Err(e),
+    };
 
     if !theme_path.is_file() {
         return Err(AppError::InvalidPath(
@@ -229,3 +267,47 @@ pub fn validate_theme_path(path: String) -> AppResult<bool> {
         )),
     }
 }
+
+/// Paths outside the notes and app folders the user allowed commands to read.
+#[tauri::command]
+pub fn list_path_grants() -> Result<Vec<String>, String> {
+    let result = || -> AppResult<Vec<String>> { load_path_grants(&get_path_grants_path()?) }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Asks the user to pick a file, or a folder with `folder`, in a native dialog
+/// and persists read access to it. The webview can't name the path itself, so
+/// it can't widen its own access. Returns `None` when the dialog is cancelled.
+#[tauri::command]
+pub async fn choose_path_grant(
+    folder: Option<bool>,
+    app: AppHandle,
+) -> Result<Option<Vec<String>>, String> {
+    let dialog = app.dialog().file().set_title(if folder.unwrap_or(false) {
+        "Allow Symiosis to read a folder"
+    } else {
+        "Allow Symiosis to read a file"
+    });
+    let picked = if folder.unwrap_or(false) {
+        dialog.blocking_pick_folder()
+    } else {
+        dialog.blocking_pick_file()
+    };
+    let Some(picked) = picked else {
+        return Ok(None);
+    };
+
+    let result = || -> AppResult<Vec<String>> {
+        let path = picked
+            .into_path()
+            .map_err(|e| AppError::InvalidPath(format!("Unsupported path: {}", e)))?;
+        add_path_grant(&get_path_grants_path()?, &path.to_string_lossy())
+    }();
+    result.map(Some).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn revoke_path_access(path: &str) -> Result<bool, String> {
+    let result = || -> AppResult<bool> { remove_path_grant(&get_path_grants_path()?, path) }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,46 @@
+use crate::{
+    core::AppResult,
+    database::with_db,
+    services::calendar_service::{notes_by_date_range, CalendarDay},
+    utilities::dates::parse_date_argument,
+};
+use chrono::{DateTime, Local};
+
+/// Resolves a typed date such as "next friday" or "in 2 weeks" to `YYYY-MM-DD`,
+/// so the frontend shares the backend's reading of relative dates.
+#[tauri::command]
+pub fn resolve_date(text: String) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        let today = Local::now().date_naive();
+        Ok(parse_date_argument(&text, "text", today)?.to_string())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Notes grouped by the local day they were created, last modified or are the
+/// daily note for, between `start` and `end` inclusive, for calendar heatmaps
+/// and journal views. Both dates may be natural language such as "last month".
+#[tauri::command]
+pub fn get_notes_by_date_range(
+    start: String,
+    end: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<CalendarDay>, String> {
+    let result = || -> AppResult<Vec<CalendarDay>> {
+        let today = Local::now().date_naive();
+        let start = parse_date_argument(&start, "start", today)?;
+        let end = parse_date_argument(&end, "end", today)?;
+        let daily_notes_folder = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            config.habits.daily_notes_folder.clone()
+        };
+
+        with_db(&app_state, |conn| {
+            notes_by_date_range(conn, start, end, &daily_notes_folder, |seconds| {
+                DateTime::from_timestamp(seconds, 0)
+                    .map(|time| time.with_timezone(&Local).date_naive())
+            })
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
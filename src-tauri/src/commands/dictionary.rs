@@ -0,0 +1,102 @@
+use crate::{
+    config::get_config_notes_dir,
+    core::{
+        events::{emit_event, DictionaryUpdated},
+        AppResult,
+    },
+    logging::log,
+    services::dictionary_service::{self, merge_words},
+    utilities::paths::{get_dictionary_path, get_vault_dictionary_path},
+};
+use serde::Deserialize;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DictionaryScope {
+    Global,
+    Vault,
+}
+
+fn dictionary_path(scope: DictionaryScope) -> AppResult<PathBuf> {
+    match scope {
+        DictionaryScope::Global => get_dictionary_path(),
+        DictionaryScope::Vault => get_vault_dictionary_path(&get_config_notes_dir()),
+    }
+}
+
+fn load_merged_dictionary() -> AppResult<Vec<String>> {
+    let global = dictionary_service::load_words(&dictionary_path(DictionaryScope::Global)?)?;
+    let vault = dictionary_service::load_words(&dictionary_path(DictionaryScope::Vault)?)?;
+    Ok(merge_words(&[global, vault]))
+}
+
+fn notify_dictionary_updated(app: &AppHandle) {
+    let words = match load_merged_dictionary() {
+        Ok(words) => words,
+        Err(e) => {
+            log(
+                "DICTIONARY",
+                "Failed to reload dictionary after update",
+                Some(&e.to_string()),
+            );
+            return;
+        }
+    };
+    emit_event(app, DictionaryUpdated { words });
+}
+
+#[tauri::command]
+pub fn get_dictionary(scope: Option<DictionaryScope>) -> Result<Vec<String>, String> {
+    let result = || -> AppResult<Vec<String>> {
+        match scope {
+            Some(scope) => {
+                let words = dictionary_service::load_words(&dictionary_path(scope)?)?;
+                Ok(merge_words(&[words]))
+            }
+            None => load_merged_dictionary(),
+        }
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn add_word_to_dictionary(
+    app: AppHandle,
+    word: String,
+    scope: Option<DictionaryScope>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        let path = dictionary_path(scope.unwrap_or(DictionaryScope::Global))?;
+        dictionary_service::add_word(&path, &word)?;
+        Ok(())
+    }();
+    if result.is_ok() {
+        notify_dictionary_updated(&app);
+    }
+    result.map_err(|e| e.to_string())
+}
+
+/// Removes a word from the given scope, or from both dictionaries when no scope is given.
+#[tauri::command]
+pub fn remove_word(
+    app: AppHandle,
+    word: String,
+    scope: Option<DictionaryScope>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        let scopes = match scope {
+            Some(scope) => vec![scope],
+            None => vec![DictionaryScope::Global, DictionaryScope::Vault],
+        };
+        for scope in scopes {
+            dictionary_service::remove_word(&dictionary_path(scope)?, &word)?;
+        }
+        Ok(())
+    }();
+    if result.is_ok() {
+        notify_dictionary_updated(&app);
+    }
+    result.map_err(|e| e.to_string())
+}
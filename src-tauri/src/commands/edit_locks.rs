@@ -0,0 +1,88 @@
+use crate::{
+    core::{
+        events::{emit_event, NoteEditLockChanged},
+        AppResult,
+    },
+    utilities::validation::validate_note_name,
+};
+
+/// Takes the advisory edit lock of a note for the window labelled
+/// `window_id`, failing while another window holds it. Saves passing a
+/// `window_id` are refused while another window holds the lock.
+#[tauri::command]
+pub fn acquire_edit_lock<R: tauri::Runtime>(
+    note_name: String,
+    window_id: String,
+    app: tauri::AppHandle<R>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        validate_note_name(&note_name)?;
+        if app_state.edit_locks.acquire(&note_name, &window_id)? {
+            emit_event(
+                &app,
+                NoteEditLockChanged {
+                    note_name,
+                    window_id: Some(window_id),
+                },
+            );
+        }
+        Ok(())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Releases `window_id`'s edit lock of a note; false when it held none.
+#[tauri::command]
+pub fn release_edit_lock<R: tauri::Runtime>(
+    note_name: String,
+    window_id: String,
+    app: tauri::AppHandle<R>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<bool, String> {
+    let result = || -> AppResult<bool> {
+        validate_note_name(&note_name)?;
+        let released = app_state.edit_locks.release(&note_name, &window_id);
+        if released {
+            emit_event(
+                &app,
+                NoteEditLockChanged {
+                    note_name,
+                    window_id: None,
+                },
+            );
+        }
+        Ok(released)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Label of the window holding the edit lock of a note, if any.
+#[tauri::command]
+pub fn get_edit_lock_holder(
+    note_name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Option<String>, String> {
+    let result = || -> AppResult<Option<String>> {
+        validate_note_name(&note_name)?;
+        Ok(app_state.edit_locks.holder(&note_name))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Releases every edit lock of a closed window.
+pub fn release_window_edit_locks<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    app_state: &crate::core::state::AppState,
+    window_id: &str,
+) {
+    for note_name in app_state.edit_locks.release_window(window_id) {
+        emit_event(
+            app,
+            NoteEditLockChanged {
+                note_name,
+                window_id: None,
+            },
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    commands::note_export::{ensure_exportable, load_note_content},
+    core::AppResult,
+    logging::log,
+    services::email_service::{
+        build_note_email, delete_smtp_password, ensure_email_configured, load_smtp_password,
+        send_email, sender_address, store_smtp_password, EmailFormat,
+    },
+    utilities::validation::validate_note_name,
+};
+
+/// Sends `note_name` to `to` through the `[email]` SMTP server, as plain
+/// markdown or rendered HTML. Private notes are refused.
+#[tauri::command]
+pub fn email_note(
+    note_name: &str,
+    to: Vec<String>,
+    format: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        validate_note_name(note_name)?;
+        let format = EmailFormat::parse(format)?;
+        let config = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            config.email.clone()
+        };
+        ensure_email_configured(&config)?;
+
+        let content = load_note_content(&app_state, note_name)?;
+        ensure_exportable(note_name, &content)?;
+        let message = build_note_email(sender_address(&config), &to, note_name, &content, format)?;
+        let password = load_smtp_password(&config.username)?;
+        send_email(&config, &password, &message)?;
+
+        log(
+            "EMAIL",
+            &format!(
+                "Sent {} to {} recipient(s)",
+                note_name,
+                message.envelope().to().len()
+            ),
+            None,
+        );
+        Ok(())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Stores the password for `[email] username` in the system keychain.
+#[tauri::command]
+pub fn set_smtp_password(
+    password: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let username = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        config.email.username.clone()
+    };
+    store_smtp_password(&username, &password).map_err(|e| e.to_string())
+}
+
+/// Removes the stored SMTP password; returns whether one was stored.
+#[tauri::command]
+pub fn clear_smtp_password(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<bool, String> {
+    let username = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        config.email.username.clone()
+    };
+    delete_smtp_password(&username).map_err(|e| e.to_string())
+}
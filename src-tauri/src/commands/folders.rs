@@ -0,0 +1,275 @@
+use crate::{
+    core::{AppError, AppResult},
+    database::with_db,
+    logging::log,
+    services::{
+        folder_service::{is_in_folder, normalize_folder_name, note_in_folder, renamed_note_name},
+        note_created_service::{forget_created, move_created},
+        reading_progress_service::{forget_reading_progress, move_reading_progress},
+        trash_service::{move_to_trash, next_trash_ids, trashed_note_path},
+    },
+    utilities::{
+        dry_run::{DryRunReport, PlannedAction},
+        validation::validate_note_name,
+    },
+};
+use rusqlite::params;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+fn notes_directory(app_state: &crate::core::state::AppState) -> PathBuf {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    PathBuf::from(&config.notes_directory)
+}
+
+fn existing_folder_error(folder: &str) -> AppError {
+    AppError::validation_error("folder", &format!("Folder '{}' already exists", folder))
+}
+
+/// Creates a folder, and any missing parents, in the notes directory. Returns
+/// the folder's normalized name.
+#[tauri::command]
+pub fn create_folder(
+    folder: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        let folder = normalize_folder_name(folder)?;
+        let path = notes_directory(&app_state).join(&folder);
+        if path.exists() {
+            return Err(existing_folder_error(&folder));
+        }
+        fs::create_dir_all(&path)?;
+        Ok(folder)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Renames or moves a folder with everything in it and returns the new names
+/// of the notes it holds. The folder is put back if the database can't be
+/// updated.
+#[tauri::command]
+pub fn rename_folder(
+    old_folder: &str,
+    new_folder: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<String>, String> {
+    let result = || -> AppResult<Vec<String>> {
+        let old_folder = normalize_folder_name(old_folder)?;
+        let new_folder = normalize_folder_name(new_folder)?;
+        if new_folder == old_folder || is_in_folder(&new_folder, &old_folder) {
+            return Err(AppError::validation_error(
+                "folder",
+                "A folder can't be moved into itself",
+            ));
+        }
+
+        let (notes_dir, track_rename_history) = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            (
+                PathBuf::from(&config.notes_directory),
+                config.preferences.track_rename_history,
+            )
+        };
+        let old_path = notes_dir.join(&old_folder);
+        let new_path = notes_dir.join(&new_folder);
+        if !old_path.is_dir() {
+            return Err(AppError::FileNotFound(format!(
+                "Folder not found: {}",
+                old_folder
+            )));
+        }
+        if new_path.exists() {
+            return Err(existing_folder_error(&new_folder));
+        }
+
+        let renames: Vec<(String, String)> = with_db(&app_state, |conn| {
+            let mut stmt = conn.prepare("SELECT filename FROM notes")?;
+            let names = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(names
+                .into_iter()
+                .filter_map(|name| {
+                    renamed_note_name(&name, &old_folder, &new_folder).map(|new| (name, new))
+                })
+                .collect())
+        })?;
+
+        if let Some(parent) = new_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        super::notes::with_programmatic_flag(&app_state, || {
+            fs::rename(&old_path, &new_path).map_err(AppError::from)
+        })?;
+
+        let updated = with_db(&app_state, |conn| {
+            let tx = conn.unchecked_transaction()?;
+            for (old_name, new_name) in &renames {
+                tx.execute(
+                    "UPDATE notes SET filename = ?1 WHERE filename = ?2",
+                    params![new_name, old_name],
+                )?;
+                move_created(&tx, old_name, new_name)?;
+                move_reading_progress(&tx, old_name, new_name)?;
+            }
+            tx.commit()?;
+            Ok(())
+        });
+        if let Err(e) = updated {
+            // Keep the files where the index says they are
+            if let Err(restore_err) = super::notes::with_programmatic_flag(&app_state, || {
+                fs::rename(&new_path, &old_path).map_err(AppError::from)
+            }) {
+                log(
+                    "FILE_OPERATION",
+                    "CRITICAL: Failed to restore folder after failed rename",
+                    Some(&restore_err.to_string()),
+                );
+            }
+            return Err(e);
+        }
+
+        if track_rename_history {
+            for (old_name, new_name) in &renames {
+                super::note_crud::record_rename_history(&app_state, old_name, new_name);
+            }
+        }
+        log(
+            "FILE_OPERATION",
+            &format!(
+                "RENAME_FOLDER: {} -> {} | {} notes | SUCCESS",
+                old_folder,
+                new_folder,
+                renames.len()
+            ),
+            None,
+        );
+        Ok(renames.into_iter().map(|(_, new_name)| new_name).collect())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// What `delete_folder` did: how many files it trashed or, with `dry_run`,
+/// what it would do.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum FolderDeletion {
+    Trashed(usize),
+    Planned(DryRunReport),
+}
+
+/// Moves every file in a folder to the trash, where each can be restored on
+/// its own, then removes the folder. With `dry_run`, nothing is touched and
+/// the planned deletions and trash locations are returned instead.
+#[tauri::command]
+pub fn delete_folder(
+    folder: &str,
+    dry_run: Option<bool>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<FolderDeletion, String> {
+    let result = || -> AppResult<FolderDeletion> {
+        let folder = normalize_folder_name(folder)?;
+        let notes_dir = notes_directory(&app_state);
+        let folder_path = notes_dir.join(&folder);
+        if !folder_path.is_dir() {
+            return Err(AppError::FileNotFound(format!(
+                "Folder not found: {}",
+                folder
+            )));
+        }
+
+        let files: Vec<String> = WalkDir::new(&folder_path)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| {
+                entry
+                    .path()
+                    .strip_prefix(&notes_dir)
+                    .ok()
+                    .map(|path| path.to_string_lossy().to_string())
+            })
+            .collect();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        if dry_run.unwrap_or(false) {
+            let mut report = DryRunReport::default();
+            let trash_ids = next_trash_ids(&notes_dir, files.len(), now)?;
+            for (note_name, trash_id) in files.iter().zip(&trash_ids) {
+                report.plan(PlannedAction::Delete, note_name);
+                report.backups.push(
+                    trashed_note_path(&notes_dir, trash_id, note_name)
+                        .to_string_lossy()
+                        .to_string(),
+                );
+            }
+            return Ok(FolderDeletion::Planned(report));
+        }
+
+        let mut trashed = Vec::new();
+        let moved = super::notes::with_programmatic_flag(&app_state, || {
+            for note_name in &files {
+                move_to_trash(&notes_dir, note_name, now)?;
+                trashed.push(note_name.clone());
+            }
+            // Only empty directories are left
+            fs::remove_dir_all(&folder_path).map_err(AppError::from)
+        });
+
+        // Notes already in the trash leave the index even if a later one failed
+        with_db(&app_state, |conn| {
+            let tx = conn.unchecked_transaction()?;
+            for note_name in &trashed {
+                tx.execute("DELETE FROM notes WHERE filename = ?1", params![note_name])?;
+                forget_created(&tx, note_name)?;
+                forget_reading_progress(&tx, note_name)?;
+            }
+            tx.commit()?;
+            Ok(())
+        })?;
+        moved?;
+
+        log(
+            "FILE_OPERATION",
+            &format!(
+                "DELETE_FOLDER: {} | {} files trashed | SUCCESS",
+                folder,
+                trashed.len()
+            ),
+            None,
+        );
+        Ok(FolderDeletion::Trashed(trashed.len()))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Moves a note into `folder`, or to the top of the notes directory when no
+/// folder is given, keeping its file name. Returns the note's new name.
+#[tauri::command]
+pub fn move_note_to_folder(
+    note_name: &str,
+    folder: Option<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(note_name)?;
+        let folder = folder
+            .filter(|folder| !folder.trim().trim_matches('/').is_empty())
+            .map(|folder| normalize_folder_name(&folder))
+            .transpose()?;
+
+        let new_name = note_in_folder(note_name, folder.as_deref());
+        if new_name != note_name {
+            super::note_crud::rename_note_file(note_name, &new_name, &app_state)?;
+        }
+        Ok(new_name)
+    }();
+    result.map_err(|e| e.to_string())
+}
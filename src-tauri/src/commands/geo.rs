@@ -0,0 +1,106 @@
+use crate::{
+    commands::note_export::prepare_export_path,
+    core::{AppError, AppResult},
+    database::with_db,
+    logging::log,
+    services::location_service::{
+        exportable_locations, geocode_place, locations_geojson, notes_near, pending_places,
+        store_geocode, NearbyNote, GEOCODE_INTERVAL, MAX_GEOCODE_BATCH,
+    },
+    utilities::geo::Coordinates,
+};
+use std::fs;
+
+/// Notes whose `location:` lies within `radius_km` of the given point,
+/// nearest first, up to `max_search_results`.
+#[tauri::command]
+pub fn get_notes_near(
+    lat: f64,
+    lon: f64,
+    radius_km: f64,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<NearbyNote>, String> {
+    let result = || -> AppResult<Vec<NearbyNote>> {
+        let center = Coordinates::new(lat, lon)
+            .ok_or_else(|| AppError::validation_error("lat/lon", "Coordinates are out of range"))?;
+        if !(radius_km.is_finite() && radius_km > 0.0) {
+            return Err(AppError::validation_error(
+                "radius_km",
+                "Radius must be a positive number",
+            ));
+        }
+        let limit = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            config.preferences.max_search_results
+        };
+        with_db(&app_state, |conn| {
+            notes_near(conn, center, radius_km, limit)
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Resolves place names in `location:` front matter that have not been looked
+/// up yet, using the `[geo]` geocoder. Handles at most `MAX_GEOCODE_BATCH`
+/// places per call, so call again while it returns a full batch. Returns the
+/// number of places looked up.
+#[tauri::command]
+pub fn geocode_note_locations(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<usize, String> {
+    let result = || -> AppResult<usize> {
+        let config = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            config.geo.clone()
+        };
+        let places = with_db(&app_state, |conn| pending_places(conn, MAX_GEOCODE_BATCH))?;
+
+        for (index, place) in places.iter().enumerate() {
+            if index > 0 {
+                std::thread::sleep(GEOCODE_INTERVAL);
+            }
+            // Network lookups happen without holding the database
+            let coordinates = geocode_place(&config, place)?;
+            with_db(&app_state, |conn| store_geocode(conn, place, coordinates))?;
+        }
+
+        if !places.is_empty() {
+            log(
+                "GEO",
+                &format!("Geocoded {} place name(s)", places.len()),
+                None,
+            );
+        }
+        Ok(places.len())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Writes every located note to `output_path` as a GeoJSON FeatureCollection
+/// for map tools. Private notes are left out.
+#[tauri::command]
+pub fn export_locations_map(
+    output_path: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        let destination = prepare_export_path(output_path, "geojson")?;
+        let located = with_db(&app_state, exportable_locations)?;
+        let geojson = serde_json::to_string_pretty(&locations_geojson(&located))
+            .map_err(|e| AppError::FileWrite(format!("Failed to serialize map: {}", e)))?;
+        fs::write(&destination, geojson)
+            .map_err(|e| AppError::FileWrite(format!("Failed to write map export: {}", e)))?;
+
+        log(
+            "EXPORT",
+            &format!(
+                "MAP: {} location(s) -> {}",
+                located.len(),
+                destination.display()
+            ),
+            None,
+        );
+        Ok(destination.to_string_lossy().to_string())
+    }();
+    result.map_err(|e| e.to_string())
+}
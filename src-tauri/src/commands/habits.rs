@@ -0,0 +1,42 @@
+use crate::{
+    core::{AppError, AppResult},
+    database::with_db,
+    services::habit_service::{habit_history, HabitHistory},
+    utilities::dates::DateRange,
+};
+use chrono::Local;
+
+/// Day-by-day completion of a configured habit, taken from checkboxes in daily
+/// notes, with streaks and completion rate.
+#[tauri::command]
+pub fn get_habit_history(
+    habit: String,
+    range: Option<DateRange>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<HabitHistory, String> {
+    let result = || -> AppResult<HabitHistory> {
+        let (habit, daily_notes_folder) = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            let habit = config
+                .habits
+                .habits
+                .iter()
+                .find(|configured| configured.eq_ignore_ascii_case(habit.trim()))
+                .cloned()
+                .ok_or_else(|| {
+                    AppError::validation_error(
+                        "habit",
+                        &format!("'{}' is not in the configured habit list", habit.trim()),
+                    )
+                })?;
+            (habit, config.habits.daily_notes_folder.clone())
+        };
+
+        let range = range.unwrap_or_default();
+        let today = Local::now().date_naive();
+        with_db(&app_state, |conn| {
+            habit_history(conn, &habit, &daily_notes_folder, &range, today)
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,139 @@
+use crate::{
+    config::get_config_notes_dir,
+    core::{AppError, AppResult},
+    lan_sync::{self, conflicts_path, paired_peers_path, DiscoveredPeer},
+    logging::log,
+    services::{
+        lan_sync_service::{
+            conflict_copy_name, load_conflicts, load_paired_peers, remove_paired_peer,
+            save_conflicts, ConflictResolution, PairedPeer, PendingPairing, SyncConflict,
+            SyncReport,
+        },
+        note_service::write_note_and_index,
+        share_service::unique_import_name,
+    },
+    utilities::validation::validate_note_name,
+};
+use std::sync::Arc;
+
+#[tauri::command]
+pub fn start_lan_sync(
+    app: tauri::AppHandle,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<u16, String> {
+    lan_sync::start(app, Arc::new(app_state.inner().clone())).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn stop_lan_sync() -> Result<(), String> {
+    lan_sync::stop();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_lan_peers() -> Result<Vec<DiscoveredPeer>, String> {
+    lan_sync::discovered_peers().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_paired_devices() -> Result<Vec<PairedPeer>, String> {
+    let result = || -> AppResult<Vec<PairedPeer>> { load_paired_peers(&paired_peers_path()?) }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Opens a short window in which this device accepts one pairing request. The
+/// request shows up as a `lan-pairing-requested` event with its fingerprint.
+#[tauri::command]
+pub fn start_lan_pairing() -> Result<(), String> {
+    lan_sync::begin_pairing();
+    Ok(())
+}
+
+/// Asks a device in its pairing window to pair. Returns the fingerprint to
+/// compare with the one that device shows before confirming.
+#[tauri::command]
+pub fn pair_lan_device(
+    device_id: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<PendingPairing, String> {
+    lan_sync::pair_with_peer(&app_state, device_id).map_err(|e| e.to_string())
+}
+
+/// Trusts a pending pairing once both devices show the same fingerprint.
+#[tauri::command]
+pub fn confirm_lan_pairing(device_id: &str) -> Result<PairedPeer, String> {
+    lan_sync::confirm_pairing(device_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn cancel_lan_pairing(device_id: &str) -> Result<bool, String> {
+    Ok(lan_sync::cancel_pairing(device_id))
+}
+
+#[tauri::command]
+pub fn unpair_lan_device(device_id: &str) -> Result<bool, String> {
+    let result = || -> AppResult<bool> { remove_paired_peer(&paired_peers_path()?, device_id) }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn sync_with_lan_peer(
+    device_id: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<SyncReport, String> {
+    lan_sync::sync_with_peer(&app_state, device_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_sync_conflicts() -> Result<Vec<SyncConflict>, String> {
+    let result = || -> AppResult<Vec<SyncConflict>> { load_conflicts(&conflicts_path()?) }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Settles a divergent edit found during LAN sync. The chosen content is pushed
+/// to the peer on the next sync.
+#[tauri::command]
+pub fn resolve_sync_conflict(
+    note_name: &str,
+    resolution: ConflictResolution,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Option<String>, String> {
+    let result = || -> AppResult<Option<String>> {
+        validate_note_name(note_name)?;
+        let conflicts_file = conflicts_path()?;
+        let mut conflicts = load_conflicts(&conflicts_file)?;
+        let index = conflicts
+            .iter()
+            .position(|conflict| conflict.note_name == note_name)
+            .ok_or_else(|| {
+                AppError::FileNotFound(format!("No sync conflict for '{}'", note_name))
+            })?;
+        let conflict = conflicts.remove(index);
+
+        let created = match resolution {
+            ConflictResolution::KeepLocal => None,
+            ConflictResolution::KeepRemote => {
+                write_note_and_index(&app_state, note_name, &conflict.remote_content)?;
+                None
+            }
+            ConflictResolution::KeepBoth => {
+                let notes_dir = get_config_notes_dir();
+                let copy_name = unique_import_name(
+                    &conflict_copy_name(note_name, &conflict.peer_name),
+                    |candidate| notes_dir.join(candidate).exists(),
+                );
+                write_note_and_index(&app_state, &copy_name, &conflict.remote_content)?;
+                Some(copy_name)
+            }
+        };
+
+        save_conflicts(&conflicts_file, &conflicts)?;
+        log(
+            "LAN_SYNC",
+            &format!("Resolved conflict for '{}': {:?}", note_name, resolution),
+            None,
+        );
+        Ok(created)
+    }();
+    result.map_err(|e| e.to_string())
+}
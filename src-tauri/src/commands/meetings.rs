@@ -0,0 +1,21 @@
+use crate::{
+    commands::note_export::load_note_content,
+    core::AppResult,
+    utilities::{
+        meetings::{parse_meeting, MeetingNote},
+        validation::validate_note_name,
+    },
+};
+
+#[tauri::command]
+pub fn parse_meeting_note(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<MeetingNote, String> {
+    let result = || -> AppResult<MeetingNote> {
+        validate_note_name(note_name)?;
+        let content = load_note_content(&app_state, note_name)?;
+        Ok(parse_meeting(note_name, &content))
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,13 @@
+use crate::{
+    database::with_db,
+    services::mention_service::{find_mentions, MentionReference},
+};
+
+/// Notes that mention a person via `@handle` or a `[[People/Name]]` link.
+#[tauri::command]
+pub fn get_mentions(
+    person: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<MentionReference>, String> {
+    with_db(&app_state, |conn| find_mentions(conn, person)).map_err(|e| e.to_string())
+}
@@ -1,13 +1,78 @@
+pub mod batch;
+pub mod capture;
+pub mod citations;
 pub mod config;
+pub mod dates;
+pub mod dictionary;
+pub mod edit_locks;
+pub mod email;
+pub mod folders;
+pub mod geo;
+pub mod habits;
+pub mod lan;
+pub mod meetings;
+pub mod mentions;
+pub mod note_appearance;
+pub mod note_crdt;
 pub mod note_crud;
+pub mod note_export;
 pub mod note_external;
+pub mod note_frontmatter;
+pub mod note_lint;
+pub mod note_replace;
 pub mod note_search;
+pub mod note_split;
 pub mod note_versions;
 pub mod notes;
+pub mod projects;
+pub mod publish;
+pub mod query_console;
+pub mod reading;
+pub mod reminders;
+pub mod reviews;
+pub mod scratchpads;
+pub mod share;
+pub mod snippets;
 pub mod system;
+pub mod tag_rules;
+pub mod tags;
+pub mod templates;
+pub mod time_tracking;
+pub mod vault_archive;
+pub mod vaults;
+pub mod webhooks;
 pub mod window;
 
+pub use batch::*;
+pub use capture::*;
+pub use citations::*;
 pub use config::*;
+pub use dates::*;
+pub use dictionary::*;
+pub use edit_locks::*;
+pub use email::*;
+pub use folders::*;
+pub use geo::*;
+pub use habits::*;
+pub use lan::*;
+pub use meetings::*;
+pub use mentions::*;
 pub use notes::*;
+pub use projects::*;
+pub use publish::*;
+pub use query_console::*;
+pub use reading::*;
+pub use reminders::*;
+pub use reviews::*;
+pub use scratchpads::*;
+pub use share::*;
+pub use snippets::*;
 pub use system::*;
+pub use tag_rules::*;
+pub use tags::*;
+pub use templates::*;
+pub use time_tracking::*;
+pub use vault_archive::*;
+pub use vaults::*;
+pub use webhooks::*;
 pub use window::*;
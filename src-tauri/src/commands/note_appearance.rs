@@ -0,0 +1,52 @@
+use crate::{
+    commands::note_crud::perform_safe_write_and_update,
+    core::{AppError, AppResult},
+    services::note_details_service::{normalize_color, normalize_icon, NoteAppearance},
+    utilities::{
+        frontmatter::{set_frontmatter_field, FrontmatterValue},
+        text_encoding::read_note_text,
+        validation::validate_note_name,
+    },
+};
+use std::path::PathBuf;
+
+/// Stores a note's sidebar `icon` and `color` in its front matter, so they
+/// travel with the file through renames, sync and exports. A blank or missing
+/// value removes the field. Returns the appearance now in effect.
+#[tauri::command]
+pub fn set_note_appearance(
+    note_name: &str,
+    icon: Option<String>,
+    color: Option<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<NoteAppearance, String> {
+    let result = || -> AppResult<NoteAppearance> {
+        validate_note_name(note_name)?;
+        let appearance = NoteAppearance {
+            icon: normalize_icon(icon.as_deref().unwrap_or_default())?,
+            color: normalize_color(color.as_deref().unwrap_or_default())?,
+        };
+
+        let note_path = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            PathBuf::from(&config.notes_directory).join(note_name)
+        };
+        let existing = read_note_text(&note_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                AppError::FileNotFound(format!("Note not found: {}", note_name))
+            }
+            _ => AppError::FileRead(format!("Failed to read '{}': {}", note_name, e)),
+        })?;
+
+        let mut content = existing.clone();
+        for (key, value) in [("icon", &appearance.icon), ("color", &appearance.color)] {
+            let value = value.clone().map(FrontmatterValue::Text);
+            content = set_frontmatter_field(&content, key, value.as_ref());
+        }
+        if content != existing {
+            perform_safe_write_and_update(&note_path, &content, note_name, &app_state)?;
+        }
+        Ok(appearance)
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,172 @@
+use crate::{
+    config::get_config_notes_dir,
+    core::{AppError, AppResult},
+    database::with_db,
+    logging::log,
+    services::{
+        crdt_service::{
+            crdt_log_path, crdt_state, device_actor, document_text, genesis_document,
+            load_document, merge_remote, record_local_content, save_document, CrdtState,
+        },
+        note_service::update_note_in_database,
+    },
+    utilities::{
+        file_safety::{create_versioned_backup, safe_write_note, BackupType},
+        paths::get_crdt_dir_for_notes_dir,
+        validation::validate_note_name,
+    },
+};
+use rusqlite::{params, OptionalExtension};
+use std::fs;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Serializes access to change logs between saves and sync commands.
+static CRDT_LOCK: Mutex<()> = Mutex::new(());
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn ensure_crdt_enabled(app_state: &crate::core::state::AppState) -> AppResult<()> {
+    crate::core::safe_mode::ensure_not_safe_mode("The CRDT change log")?;
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    if config.sync.crdt_enabled {
+        Ok(())
+    } else {
+        Err(AppError::ConfigLoad(
+            "CRDT change log is disabled; set crdt_enabled = true in the [sync] section"
+                .to_string(),
+        ))
+    }
+}
+
+fn current_note_content(
+    app_state: &crate::core::state::AppState,
+    note_name: &str,
+) -> AppResult<Option<String>> {
+    with_db(app_state, |conn| {
+        Ok(conn
+            .query_row(
+                "SELECT content FROM notes WHERE filename = ?1",
+                params![note_name],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?)
+    })
+}
+
+/// Appends a saved note's content to its change log when the CRDT layer is
+/// enabled. Failures are logged rather than failing the save.
+pub(crate) fn record_note_crdt_change(
+    app_state: &crate::core::state::AppState,
+    note_name: &str,
+    content: &str,
+) {
+    if ensure_crdt_enabled(app_state).is_err() {
+        return;
+    }
+
+    let result = || -> AppResult<()> {
+        let _guard = CRDT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let crdt_dir = get_crdt_dir_for_notes_dir(&get_config_notes_dir())?;
+        let log_path = crdt_log_path(&crdt_dir, note_name);
+        let mut doc = load_document(&log_path)?.unwrap_or_else(genesis_document);
+        if record_local_content(&mut doc, &device_actor(&crdt_dir)?, content, now_secs())? {
+            save_document(&log_path, &mut doc)?;
+        }
+        Ok(())
+    }();
+
+    if let Err(e) = result {
+        log(
+            "CRDT",
+            &format!("Failed to record change for '{}'", note_name),
+            Some(&e.to_string()),
+        );
+    }
+}
+
+#[tauri::command]
+pub fn get_crdt_state(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<CrdtState, String> {
+    let result = || -> AppResult<CrdtState> {
+        validate_note_name(note_name)?;
+        ensure_crdt_enabled(&app_state)?;
+        let content = current_note_content(&app_state, note_name)?
+            .ok_or_else(|| AppError::FileNotFound(format!("Note not found: {}", note_name)))?;
+
+        let _guard = CRDT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let crdt_dir = get_crdt_dir_for_notes_dir(&get_config_notes_dir())?;
+        let log_path = crdt_log_path(&crdt_dir, note_name);
+        let mut doc = load_document(&log_path)?.unwrap_or_else(genesis_document);
+
+        // Picks up edits made outside the app since the last save
+        if record_local_content(&mut doc, &device_actor(&crdt_dir)?, &content, now_secs())? {
+            save_document(&log_path, &mut doc)?;
+        }
+        crdt_state(note_name, &mut doc)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn apply_remote_changes(
+    note_name: &str,
+    payload: Vec<u8>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<CrdtState, String> {
+    let result = || -> AppResult<CrdtState> {
+        validate_note_name(note_name)?;
+        ensure_crdt_enabled(&app_state)?;
+        let local_content = current_note_content(&app_state, note_name)?;
+
+        let _guard = CRDT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let notes_dir = get_config_notes_dir();
+        let crdt_dir = get_crdt_dir_for_notes_dir(&notes_dir)?;
+        let log_path = crdt_log_path(&crdt_dir, note_name);
+
+        let mut local = load_document(&log_path)?;
+        if let (Some(doc), Some(content)) = (local.as_mut(), local_content.as_deref()) {
+            // Unsynced local edits become part of the history before merging
+            record_local_content(doc, &device_actor(&crdt_dir)?, content, now_secs())?;
+        }
+        let had_history = local.is_some();
+
+        let mut doc = merge_remote(local, &payload)?;
+        let merged = document_text(&doc)?;
+        let note_path = notes_dir.join(note_name);
+
+        if local_content.as_deref() != Some(merged.as_str()) {
+            if !had_history && note_path.exists() {
+                // Without a local log the remote history wins; keep the old file recoverable
+                create_versioned_backup(&note_path, BackupType::ExternalChange, None)?;
+            }
+            if let Some(parent) = note_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            super::notes::with_programmatic_flag(&app_state, || {
+                safe_write_note(&note_path, &merged)
+            })?;
+            update_note_in_database(&app_state, note_name, &merged, now_secs())?;
+        }
+
+        save_document(&log_path, &mut doc)?;
+        log(
+            "CRDT",
+            &format!(
+                "Merged remote changes into '{}' ({} bytes payload)",
+                note_name,
+                payload.len()
+            ),
+            None,
+        );
+        crdt_state(note_name, &mut doc)
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -1,66 +1,256 @@
 use crate::{
-    core::{AppError, AppResult},
-    database::with_db,
+    core::{
+        events::{emit_event, CacheRefreshed},
+        AppError, AppResult,
+    },
+    database::{with_db, with_db_mut},
+    lan_sync::conflicts_path,
     logging::log,
-    services::{database_service::handle_database_recovery, note_service::update_note_in_database},
+    services::{
+        database_service::handle_database_recovery,
+        expiry_service::{expiring_notes, ExpiringNote},
+        folder_service::{new_note_folder, note_under_folder},
+        lan_sync_service::load_conflicts,
+        link_service::{backlinks, linked_mentions_html},
+        note_created_service::{forget_created, move_created, record_created},
+        note_details_service::{
+            list_note_details, list_notes_page, note_stats, NoteDetails, NoteOrder, NotePage,
+            NoteSortKey, NoteStats, SortDirection,
+        },
+        note_service::{update_note_in_database, update_note_row},
+        reading_progress_service::{forget_reading_progress, move_reading_progress},
+        rename_history_service::{record_rename, resolve_renamed_note},
+        render_service::{has_embeds, render_with_embedded_notes},
+        staleness_service::{record_note_opened, stale_notes, StaleNote},
+        trash_service::{move_to_trash, next_trash_id, purge_expired_trash, trashed_note_path},
+    },
     utilities::{
+        dry_run::{DryRunReport, PlannedAction},
         file_safety::{create_versioned_backup, safe_write_note, BackupType},
-        note_renderer::render_note,
+        links::rewrite_wikilinks,
+        note_renderer::{note_outline, render_note, OutlineHeading},
+        strings::{copy_note_name, note_excerpt, untitled_note_name, DEFAULT_EXCERPT_CHARS},
+        text_encoding::read_note_text,
         validation::validate_note_name,
     },
 };
-use rusqlite::params;
+use chrono::Local;
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
+use std::collections::HashSet;
 use std::fs;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Notes shown per page when the caller doesn't ask for a size
+const DEFAULT_PAGE_SIZE: usize = 200;
+/// Largest page a single call returns
+pub const MAX_PAGE_SIZE: usize = 5000;
+/// Highest `copy N` (or `Untitled N`) suffix tried before giving up
+const MAX_COPY_SUFFIX: usize = 1000;
+
+/// One page of the note list with each note's dates and size, so list views
+/// can load huge vaults as they scroll. Newest modified first by default.
 #[tauri::command]
-pub fn list_all_notes(
+pub fn list_notes(
+    offset: Option<usize>,
+    limit: Option<usize>,
+    sort_by: Option<NoteSortKey>,
+    order: Option<SortDirection>,
     app_state: tauri::State<crate::core::state::AppState>,
-) -> Result<Vec<String>, String> {
-    let result = with_db(&app_state, |conn| {
-        let mut stmt = conn.prepare("SELECT filename FROM notes ORDER BY modified DESC")?;
-        let rows = stmt.query_map([], |row| row.get(0))?;
-
-        let mut results = Vec::new();
-        for r in rows {
-            if let Ok(filename) = r {
-                results.push(filename);
-            }
-        }
+) -> Result<NotePage, String> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+    with_db(&app_state, |conn| {
+        list_notes_page(
+            conn,
+            sort_by.unwrap_or_default(),
+            order.unwrap_or_default(),
+            offset.unwrap_or(0),
+            limit,
+        )
+    })
+    .map_err(|e| e.to_string())
+}
 
-        Ok(results)
-    });
+/// Every note with the flags and word count list views badge, in one call.
+/// Newest first by modification or, with `sort_by: "created"`, creation time;
+/// `"natural"` lists them by name with `note2` before `note10`.
+#[tauri::command]
+pub fn list_notes_detailed(
+    sort_by: Option<NoteOrder>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<NoteDetails>, String> {
+    // An unreadable conflicts file shouldn't hide the note list
+    let conflicted: HashSet<String> = conflicts_path()
+        .and_then(|path| load_conflicts(&path))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|conflict| conflict.note_name)
+        .collect();
+    with_db(&app_state, |conn| {
+        list_note_details(conn, &conflicted, sort_by.unwrap_or_default())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Dates, size, counts, tags and link totals for a note's info panel.
+#[tauri::command]
+pub fn get_note_metadata(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<NoteStats, String> {
+    let result = || -> AppResult<NoteStats> {
+        validate_note_name(note_name)?;
+        with_db(&app_state, |conn| note_stats(conn, note_name))
+    }();
     result.map_err(|e| e.to_string())
 }
 
+/// Notes neither modified nor opened in the last `older_than_days`, least
+/// recently touched first, for archiving or refreshing old notes. Notes tagged
+/// with any of `exclude_tags` (or a tag nested under one) are skipped.
+#[tauri::command]
+pub fn get_stale_notes(
+    older_than_days: u32,
+    exclude_tags: Option<Vec<String>>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<StaleNote>, String> {
+    let exclude_tags = exclude_tags.unwrap_or_default();
+    with_db(&app_state, |conn| {
+        stale_notes(conn, older_than_days, &exclude_tags, unix_now())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Notes whose front-matter `expires` date is within the next `days`, and
+/// those already expired, soonest first. Archived notes are left out.
+#[tauri::command]
+pub fn list_expiring_notes(
+    days: u32,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<ExpiringNote>, String> {
+    let today = Local::now().date_naive();
+    with_db(&app_state, |conn| expiring_notes(conn, today, days)).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_note_content(
     note_name: &str,
     app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<String, String> {
-    validate_note_name(note_name)
-        .and_then(|_| {
-            with_db(&app_state, |conn| {
-                let mut stmt = conn.prepare("SELECT content FROM notes WHERE filename = ?1")?;
-                let content = stmt
-                    .query_row(params![note_name], |row| Ok(row.get::<_, String>(0)?))
-                    .map_err(|_| {
-                        AppError::FileNotFound(format!("Note not found: {}", note_name))
-                    })?;
-                Ok(content)
+    read_note_content(&app_state, note_name).map_err(|e| e.to_string())
+}
+
+/// The stored content of `note_name`, as `get_note_content` returns it.
+pub fn read_note_content(
+    app_state: &crate::core::state::AppState,
+    note_name: &str,
+) -> AppResult<String> {
+    validate_note_name(note_name)?;
+    with_db(app_state, |conn| {
+        let mut stmt = conn.prepare("SELECT content FROM notes WHERE filename = ?1")?;
+        let content = stmt
+            .query_row(params![note_name], |row| Ok(row.get::<_, String>(0)?))
+            .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", note_name)))?;
+        Ok(content)
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedNoteContent {
+    /// The note's current name, which differs from the requested one after a redirect
+    pub note_name: String,
+    pub content: String,
+    pub redirected_from: Option<String>,
+}
+
+/// Like `get_note_content`, but a name the note had before being renamed resolves
+/// to the note's current name.
+#[tauri::command]
+pub fn get_note_content_resolved(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<ResolvedNoteContent, String> {
+    let result = || -> AppResult<ResolvedNoteContent> {
+        validate_note_name(note_name)?;
+        with_db(&app_state, |conn| {
+            let content: Option<String> = conn
+                .query_row(
+                    "SELECT content FROM notes WHERE filename = ?1",
+                    params![note_name],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(content) = content {
+                return Ok(ResolvedNoteContent {
+                    note_name: note_name.to_string(),
+                    content,
+                    redirected_from: None,
+                });
+            }
+
+            let current = resolve_renamed_note(conn, note_name)?
+                .ok_or_else(|| AppError::FileNotFound(format!("Note not found: {}", note_name)))?;
+            let content = conn.query_row(
+                "SELECT content FROM notes WHERE filename = ?1",
+                params![current],
+                |row| row.get(0),
+            )?;
+            Ok(ResolvedNoteContent {
+                note_name: current,
+                content,
+                redirected_from: Some(note_name.to_string()),
             })
         })
-        .map_err(|e| e.to_string())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_note_excerpt(
+    note_name: &str,
+    max_chars: Option<usize>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let content = read_note_content(&app_state, note_name).map_err(|e| e.to_string())?;
+    Ok(note_excerpt(
+        &content,
+        max_chars.unwrap_or(DEFAULT_EXCERPT_CHARS).max(1),
+    ))
+}
+
+/// Headings of a note with their level, line and anchor, for an outline panel
+/// and jumping to a heading.
+#[tauri::command]
+pub fn get_note_outline(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<OutlineHeading>, String> {
+    let content = read_note_content(&app_state, note_name).map_err(|e| e.to_string())?;
+    Ok(note_outline(note_name, &content))
 }
 
+/// A note's rendered HTML with `![[note]]` embeds expanded, followed by its
+/// linked mentions when `show_linked_mentions` is enabled.
 #[tauri::command]
 pub fn get_note_html_content(
     note_name: &str,
     app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<String, String> {
-    validate_note_name(note_name).map_err(|e| e.to_string())?;
+    read_note_html(&app_state, note_name).map_err(|e| e.to_string())
+}
 
-    with_db(&app_state, |conn| {
+/// What `get_note_html_content` returns for `note_name`.
+pub fn read_note_html(
+    app_state: &crate::core::state::AppState,
+    note_name: &str,
+) -> AppResult<String> {
+    validate_note_name(note_name)?;
+    let show_linked_mentions = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        config.preferences.show_linked_mentions
+    };
+
+    let html = with_db(app_state, |conn| {
         let mut stmt =
             conn.prepare("SELECT html_render, is_indexed, content FROM notes WHERE filename = ?1")?;
 
@@ -74,7 +264,17 @@ pub fn get_note_html_content(
             })
             .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", note_name)))?;
 
-        if is_indexed {
+        if let Err(e) = record_note_opened(conn, note_name, unix_now()) {
+            log(
+                "NOTE_OPENS",
+                &format!("Failed to record opening '{}'", note_name),
+                Some(&e.to_string()),
+            );
+        }
+
+        if has_embeds(&content) {
+            render_with_embedded_notes(conn, note_name, &content)
+        } else if is_indexed {
             Ok(html_content)
         } else {
             let html_render = render_note(note_name, &content);
@@ -92,8 +292,23 @@ pub fn get_note_html_content(
 
             Ok(html_render)
         }
-    })
-    .map_err(|e| e.to_string())
+    })?;
+
+    if !show_linked_mentions {
+        return Ok(html);
+    }
+    // The note still shows if its mentions can't be listed
+    match with_db(app_state, |conn| linked_mentions_html(conn, note_name)) {
+        Ok(mentions) => Ok(html + &mentions),
+        Err(e) => {
+            log(
+                "LINKED_MENTIONS",
+                &format!("Failed to list linked mentions of '{}'", note_name),
+                Some(&e.to_string()),
+            );
+            Ok(html)
+        }
+    }
 }
 
 #[tauri::command]
@@ -101,117 +316,443 @@ pub fn create_new_note(
     note_name: &str,
     app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<(), String> {
-    let result = || -> AppResult<()> {
-        validate_note_name(note_name)?;
+    create_note_with_content(note_name, "", &app_state).map_err(|e| e.to_string())
+}
+
+/// Creates an empty `Untitled.md` (or `Untitled 2.md`, ...) where
+/// `[preferences] new_note_location` puts new notes, `current_note` being the
+/// note open when it was asked for. Returns the new note's name.
+#[tauri::command]
+pub fn create_untitled_note(
+    current_note: Option<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    create_untitled_note_with_content(current_note.as_deref(), "", &app_state)
+        .map_err(|e| e.to_string())
+}
 
+/// Creates the next free untitled note holding `content`, as
+/// `create_untitled_note` does.
+pub(crate) fn create_untitled_note_with_content(
+    current_note: Option<&str>,
+    content: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<String> {
+    let (notes_dir, folder) = {
         let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
-        let note_path = std::path::PathBuf::from(&config.notes_directory).join(note_name);
+        (
+            std::path::PathBuf::from(&config.notes_directory),
+            new_note_folder(
+                &config.preferences.new_note_location,
+                &config.preferences.inbox_folder,
+                current_note,
+            ),
+        )
+    };
+
+    let note_name = (1..=MAX_COPY_SUFFIX)
+        .map(|n| note_under_folder(&untitled_note_name(n), folder.as_deref()))
+        .find(|name| !notes_dir.join(name).exists())
+        .ok_or_else(|| {
+            AppError::InvalidNoteName("Too many untitled notes already exist".to_string())
+        })?;
+    create_note_with_content(&note_name, content, app_state)?;
+    Ok(note_name)
+}
+
+/// Creates `note_name` holding `content`, failing if the note already exists.
+pub(crate) fn create_note_with_content(
+    note_name: &str,
+    content: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<()> {
+    validate_note_name(note_name)?;
+
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    let note_path = std::path::PathBuf::from(&config.notes_directory).join(note_name);
+
+    if let Some(parent) = note_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
 
-        if let Some(parent) = note_path.parent() {
-            fs::create_dir_all(parent)?;
+    // Atomic file creation - this eliminates TOCTOU by using create_new flag
+    super::notes::with_programmatic_flag(app_state, || -> AppResult<()> {
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true) // This will fail if file already exists
+            .open(&note_path)
+        {
+            Ok(mut file) => {
+                use std::io::Write;
+                file.write_all(content.as_bytes())
+                    .map_err(|e| AppError::FileWrite(e.to_string()))?;
+                Ok(())
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Err(
+                AppError::InvalidNoteName(format!("Note '{}' already exists", note_name)),
+            ),
+            Err(e) => Err(AppError::FileWrite(format!("Failed to create note: {}", e))),
         }
+    })?;
 
-        // Atomic file creation - this eliminates TOCTOU by using create_new flag
-        super::notes::with_programmatic_flag(&app_state, || -> AppResult<()> {
-            match std::fs::OpenOptions::new()
-                .write(true)
-                .create_new(true) // This will fail if file already exists
-                .open(&note_path)
-            {
-                Ok(mut file) => {
-                    // File was created successfully, write empty content
-                    use std::io::Write;
-                    file.write_all(b"")
-                        .map_err(|e| AppError::FileWrite(e.to_string()))?;
-                    Ok(())
-                }
-                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Err(
-                    AppError::InvalidNoteName(format!("Note '{}' already exists", note_name)),
-                ),
-                Err(e) => Err(AppError::FileWrite(format!("Failed to create note: {}", e))),
+    let modified = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    match with_db(app_state, |conn| {
+        let html_render = render_note(note_name, content);
+        conn.execute(
+            "INSERT OR REPLACE INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![note_name, content, html_render, modified, true],
+        )?;
+        record_created(conn, note_name, modified)?;
+        Ok(())
+    }) {
+        Ok(_) => Ok(()),
+        Err(e) => handle_database_recovery(
+            app_state,
+            &format!("'{}'", note_name),
+            &e,
+            "Note created but database rebuild failed",
+            "Database rebuild failed. Note was created but may not be searchable.",
+        ),
+    }
+}
+
+/// Copies a note to `new_name`, or next to it as `Name copy.md` (then `Name
+/// copy 2.md`, ...) when no name is given. Returns the new note's name.
+#[tauri::command]
+pub fn duplicate_note(
+    note_name: &str,
+    new_name: Option<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(note_name)?;
+        let notes_dir = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            std::path::PathBuf::from(&config.notes_directory)
+        };
+        let source = notes_dir.join(note_name);
+        let content = read_note_text(&source).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                AppError::FileNotFound(format!("Note not found: {}", note_name))
             }
+            _ => AppError::FileRead(format!("Failed to read '{}': {}", note_name, e)),
         })?;
 
-        let modified = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_secs() as i64)
-            .unwrap_or(0);
+        let new_name = match new_name.map(|name| name.trim().to_string()) {
+            Some(name) if !name.is_empty() => name,
+            _ => (1..=MAX_COPY_SUFFIX)
+                .map(|n| copy_note_name(note_name, n))
+                .find(|name| !notes_dir.join(name).exists())
+                .ok_or_else(|| {
+                    AppError::InvalidNoteName(format!(
+                        "Too many copies of '{}' already exist",
+                        note_name
+                    ))
+                })?,
+        };
 
-        match with_db(&app_state, |conn| {
-            let html_render = render_note(note_name, "");
-            conn.execute(
-                "INSERT OR REPLACE INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![note_name, "", html_render, modified, true],
-            )?;
-            Ok(())
-        }) {
-            Ok(_) => Ok(()),
-            Err(e) => handle_database_recovery(
-                &app_state,
-                &format!("'{}'", note_name),
-                &e,
-                "Note created but database rebuild failed",
-                "Database rebuild failed. Note was created but may not be searchable.",
-            ),
-        }
+        create_note_with_content(&new_name, &content, &app_state)?;
+        Ok(new_name)
     }();
     result.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn save_note_with_content_check(
+pub fn save_note_with_content_check<R: tauri::Runtime>(
     note_name: &str,
     content: &str,
     original_content: &str,
+    window_id: Option<String>,
+    app: tauri::AppHandle<R>,
     app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<(), String> {
     let result = || -> AppResult<()> {
         validate_note_name(note_name)?;
-        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
-        let note_path = std::path::PathBuf::from(&config.notes_directory).join(note_name);
+        app_state.external_edits.ensure_not_active(note_name)?;
+        if let Some(window_id) = &window_id {
+            app_state.edit_locks.ensure_can_save(note_name, window_id)?;
+        }
+        let note_path = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            std::path::PathBuf::from(&config.notes_directory).join(note_name)
+        };
         validate_content_unchanged(&note_path, note_name, original_content, content)?;
         perform_safe_write_and_update(&note_path, content, note_name, &app_state)?;
+
+        // The editor reloads notes that tag rules changed or moved
+        if let Some(plan) = super::tag_rules::apply_tag_rules(
+            &app_state,
+            note_name,
+            Some(original_content),
+            content,
+        ) {
+            let mut refreshed = CacheRefreshed {
+                changed: vec![plan.final_name().to_string()],
+                removed: Vec::new(),
+            };
+            if plan.new_name.is_some() {
+                refreshed.removed.push(note_name.to_string());
+            }
+            emit_event(&app, refreshed);
+        }
         Ok(())
     }();
     result.map_err(|e| e.to_string())
 }
 
+/// Renames or moves a note. With `update_links`, wikilinks and embeds pointing
+/// at it are rewritten across the vault: every affected note is backed up
+/// first, and if one can't be rewritten the others are restored and the
+/// rename is undone.
 #[tauri::command]
 pub fn rename_note(
     old_name: String,
     new_name: String,
+    update_links: Option<bool>,
     app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<(), String> {
-    let result = || -> AppResult<()> {
-        validate_note_name(&old_name)?;
-        validate_note_name(&new_name)?;
+    if update_links.unwrap_or(false) {
+        rename_note_updating_links(&old_name, &new_name, &app_state)
+    } else {
+        rename_note_file(&old_name, &new_name, &app_state)
+    }
+    .map_err(|e| e.to_string())
+}
 
+/// A note whose wikilinks were rewritten for a rename
+struct LinkUpdate {
+    /// The note's name after the rename
+    filename: String,
+    original: String,
+    rewritten: String,
+}
+
+fn rename_note_updating_links(
+    old_name: &str,
+    new_name: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<()> {
+    validate_note_name(old_name)?;
+    validate_note_name(new_name)?;
+    let notes_dir = {
         let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
-        let notes_dir = std::path::PathBuf::from(&config.notes_directory);
-        let old_path = notes_dir.join(&old_name);
-        let new_path = notes_dir.join(&new_name);
-
-        match create_rename_backup_with_target_check(&old_path, &new_path, &new_name)? {
-            Some(backup_path) => perform_atomic_rename_with_database(
-                &old_path,
-                &new_path,
-                &old_name,
-                &new_name,
-                backup_path,
-                &app_state,
-            ),
-            None => handle_database_only_rename(&old_name, &new_name, &new_path, &app_state),
+        std::path::PathBuf::from(&config.notes_directory)
+    };
+
+    let (mut sources, notes_before) = with_db(app_state, |conn| {
+        let sources: Vec<String> = backlinks(conn, old_name)?
+            .into_iter()
+            .map(|link| link.source)
+            .collect();
+        let notes: Vec<String> = conn
+            .prepare("SELECT filename FROM notes ORDER BY filename")?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        Ok((sources, notes))
+    })?;
+    // The note may link to itself by name
+    sources.push(old_name.to_string());
+    sources.sort();
+    sources.dedup();
+    let mut notes_after: Vec<String> = notes_before
+        .iter()
+        .filter(|note| note.as_str() != old_name)
+        .cloned()
+        .chain(std::iter::once(new_name.to_string()))
+        .collect();
+    notes_after.sort();
+
+    // Read and back up every affected note before anything changes
+    let mut updates = Vec::new();
+    let mut links_updated = 0;
+    for source in &sources {
+        let path = notes_dir.join(source);
+        let original = match read_note_text(&path) {
+            Ok(content) => content,
+            // Left behind in the index by a note deleted outside the app
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                return Err(AppError::FileRead(format!(
+                    "Failed to read '{}': {}",
+                    source, e
+                )))
+            }
+        };
+        let (rewritten, count) =
+            rewrite_wikilinks(&original, old_name, new_name, &notes_before, &notes_after);
+        if count == 0 {
+            continue;
         }
-    }();
-    result.map_err(|e| e.to_string())
+        create_versioned_backup(&path, BackupType::LinkUpdate, Some(&original))?;
+        links_updated += count;
+        updates.push(LinkUpdate {
+            filename: if source == old_name {
+                new_name.to_string()
+            } else {
+                source.clone()
+            },
+            original,
+            rewritten,
+        });
+    }
+
+    rename_note_file(old_name, new_name, app_state)?;
+
+    // Every file is rewritten before the index changes, so a failed write
+    // leaves nothing to roll back in the database
+    for (index, update) in updates.iter().enumerate() {
+        let path = notes_dir.join(&update.filename);
+        if let Err(e) = super::notes::with_programmatic_flag(app_state, || {
+            safe_write_note(&path, &update.rewritten)
+        }) {
+            undo_link_updates(&notes_dir, &updates[..index], old_name, new_name, app_state);
+            return Err(AppError::FileWrite(format!(
+                "Failed to update links in '{}', so the rename was undone: {}",
+                update.filename, e
+            )));
+        }
+    }
+    index_link_updates(&updates, app_state)?;
+
+    log(
+        "RENAME",
+        &format!(
+            "{} -> {}: {} link(s) updated in {} note(s)",
+            old_name,
+            new_name,
+            links_updated,
+            updates.len()
+        ),
+        None,
+    );
+    Ok(())
+}
+
+/// Indexes the rewritten notes in one transaction. If that fails the files
+/// are already right, so the database is rebuilt from them.
+fn index_link_updates(
+    updates: &[LinkUpdate],
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<()> {
+    let modified = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let result = with_db_mut(app_state, |conn| {
+        let tx = conn.transaction()?;
+        for update in updates {
+            update_note_row(&tx, &update.filename, &update.rewritten, modified)?;
+        }
+        tx.commit()?;
+        Ok(())
+    });
+
+    match result {
+        Ok(()) => {
+            for update in updates {
+                super::note_crdt::record_note_crdt_change(
+                    app_state,
+                    &update.filename,
+                    &update.rewritten,
+                );
+            }
+            Ok(())
+        }
+        Err(e) => handle_database_recovery(
+            app_state,
+            "update links after a rename",
+            &e,
+            "Links updated but database rebuild failed",
+            "Critical error: Database rebuild failed",
+        ),
+    }
+}
+
+/// Restores the files already rewritten for a rename and renames the note
+/// back. The index never saw the rewrites, so only the files change. Failures
+/// are logged; the backups taken beforehand still hold every note.
+fn undo_link_updates(
+    notes_dir: &std::path::Path,
+    written: &[LinkUpdate],
+    old_name: &str,
+    new_name: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) {
+    for update in written {
+        let path = notes_dir.join(&update.filename);
+        if let Err(e) = super::notes::with_programmatic_flag(app_state, || {
+            safe_write_note(&path, &update.original)
+        }) {
+            log(
+                "RENAME",
+                &format!(
+                    "Failed to restore '{}' after a failed rename",
+                    update.filename
+                ),
+                Some(&e.to_string()),
+            );
+        }
+    }
+    if let Err(e) = rename_note_file(new_name, old_name, app_state) {
+        log(
+            "RENAME",
+            &format!("Failed to rename '{}' back to '{}'", new_name, old_name),
+            Some(&e.to_string()),
+        );
+    }
+}
+
+/// Renames or moves a note on disk and in the database, recording the old
+/// name in the rename history when enabled.
+pub(crate) fn rename_note_file(
+    old_name: &str,
+    new_name: &str,
+    app_state: &crate::core::state::AppState,
+) -> AppResult<()> {
+    validate_note_name(old_name)?;
+    validate_note_name(new_name)?;
+
+    let (notes_dir, track_rename_history) = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        (
+            std::path::PathBuf::from(&config.notes_directory),
+            config.preferences.track_rename_history,
+        )
+    };
+    let old_path = notes_dir.join(old_name);
+    let new_path = notes_dir.join(new_name);
+
+    match create_rename_backup_with_target_check(&old_path, &new_path, new_name)? {
+        Some(backup_path) => perform_atomic_rename_with_database(
+            &old_path,
+            &new_path,
+            old_name,
+            new_name,
+            backup_path,
+            app_state,
+        ),
+        None => handle_database_only_rename(old_name, new_name, &new_path, app_state),
+    }?;
+
+    if track_rename_history {
+        record_rename_history(app_state, old_name, new_name);
+    }
+    Ok(())
 }
 
+/// Moves a note to the trash. With `dry_run`, nothing is touched and the
+/// planned deletion and trash location are returned instead.
 #[tauri::command]
 pub fn delete_note(
     note_name: &str,
+    dry_run: Option<bool>,
     app_state: tauri::State<crate::core::state::AppState>,
-) -> Result<(), String> {
-    let result = || -> AppResult<()> {
+) -> Result<Option<DryRunReport>, String> {
+    let result = || -> AppResult<Option<DryRunReport>> {
         validate_note_name(note_name)?;
         let config = app_state.config.read().unwrap_or_else(|e| {
             log(
@@ -221,7 +762,13 @@ pub fn delete_note(
             );
             e.into_inner()
         });
-        let note_path = std::path::PathBuf::from(&config.notes_directory).join(note_name);
+        let notes_dir = std::path::PathBuf::from(&config.notes_directory);
+        let note_path = notes_dir.join(note_name);
+        let retention_days = config.preferences.trash_retention_days;
+
+        if dry_run.unwrap_or(false) {
+            return plan_delete(&notes_dir, note_name, &app_state).map(Some);
+        }
 
         log(
             "DELETE_NOTE",
@@ -232,52 +779,79 @@ pub fn delete_note(
             )),
         );
 
-        match perform_backup_and_delete(&note_path, note_name, &app_state)? {
-            true => handle_database_cleanup(note_name, &app_state),
+        match note_path.is_file() {
+            true => {
+                perform_move_to_trash(&notes_dir, note_name, &app_state)?;
+                handle_database_cleanup(note_name, &app_state)
+            }
             false => handle_database_only_delete(note_name, &app_state),
+        }?;
+
+        if let Err(e) = purge_expired_trash(&notes_dir, retention_days, unix_now()) {
+            log(
+                "TRASH",
+                "Failed to purge expired notes from trash",
+                Some(&e.to_string()),
+            );
         }
+        Ok(None)
     }();
     result.map_err(|e| e.to_string())
 }
 
-fn perform_backup_and_delete(
-    note_path: &std::path::PathBuf,
+fn plan_delete(
+    notes_dir: &std::path::Path,
     note_name: &str,
     app_state: &tauri::State<crate::core::state::AppState>,
-) -> AppResult<bool> {
-    let copy_result = create_versioned_backup(note_path, BackupType::Delete, None);
-
-    match copy_result {
-        Ok(backup_path) => {
-            match super::notes::with_programmatic_flag(app_state, || {
-                fs::remove_file(note_path).map_err(AppError::from)
-            }) {
-                Ok(()) => {
-                    log(
-                        "FILE_OPERATION",
-                        &format!(
-                            "DELETE: {} | Backup: {} | SUCCESS",
-                            note_name,
-                            backup_path.display()
-                        ),
-                        None,
-                    );
-                    Ok(true)
-                }
-                Err(e) => {
-                    if let Err(e) = fs::remove_file(&backup_path) {
-                        log(
-                            "BACKUP_CLEANUP",
-                            &format!("Failed to remove backup file: {:?}", backup_path),
-                            Some(&e.to_string()),
-                        );
-                    }
-                    Err(AppError::FileWrite(format!("Failed to delete note: {}", e)))
-                }
-            }
+) -> AppResult<DryRunReport> {
+    let mut report = DryRunReport::default();
+    if notes_dir.join(note_name).is_file() {
+        report.plan(PlannedAction::Delete, note_name);
+        let trash_id = next_trash_id(notes_dir, unix_now())?;
+        report.backups.push(
+            trashed_note_path(notes_dir, &trash_id, note_name)
+                .to_string_lossy()
+                .to_string(),
+        );
+    } else {
+        let indexed = with_db(app_state, |conn| {
+            Ok(conn
+                .query_row(
+                    "SELECT 1 FROM notes WHERE filename = ?1",
+                    params![note_name],
+                    |_| Ok(()),
+                )
+                .optional()?
+                .is_some())
+        })?;
+        if indexed {
+            report.plan(PlannedAction::RemoveFromIndex, note_name);
         }
-        Err(_) => Ok(false),
     }
+    Ok(report)
+}
+
+fn perform_move_to_trash(
+    notes_dir: &std::path::Path,
+    note_name: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<()> {
+    let entry = super::notes::with_programmatic_flag(app_state, || {
+        move_to_trash(notes_dir, note_name, unix_now())
+    })?;
+    log(
+        "FILE_OPERATION",
+        &format!("DELETE: {} | Trash id: {} | SUCCESS", note_name, entry.id),
+        None,
+    );
+    Ok(())
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
 }
 
 fn handle_database_only_delete(
@@ -286,6 +860,8 @@ fn handle_database_only_delete(
 ) -> AppResult<()> {
     match with_db(app_state, |conn| {
         conn.execute("DELETE FROM notes WHERE filename = ?1", params![note_name])?;
+        forget_created(conn, note_name)?;
+        forget_reading_progress(conn, note_name)?;
         Ok(())
     }) {
         Ok(_) => Ok(()),
@@ -308,6 +884,8 @@ fn handle_database_cleanup(
 ) -> AppResult<()> {
     match with_db(app_state, |conn| {
         conn.execute("DELETE FROM notes WHERE filename = ?1", params![note_name])?;
+        forget_created(conn, note_name)?;
+        forget_reading_progress(conn, note_name)?;
         Ok(())
     }) {
         Ok(_) => Ok(()),
@@ -328,7 +906,7 @@ fn validate_content_unchanged(
     content: &str,
 ) -> AppResult<()> {
     let current_content = if note_path.exists() {
-        fs::read_to_string(note_path)?
+        read_note_text(note_path)?
     } else {
         String::new()
     };
@@ -364,7 +942,7 @@ fn validate_content_unchanged(
     Ok(())
 }
 
-fn perform_safe_write_and_update(
+pub(crate) fn perform_safe_write_and_update(
     note_path: &std::path::PathBuf,
     content: &str,
     note_name: &str,
@@ -382,7 +960,10 @@ fn perform_safe_write_and_update(
         .unwrap_or(0);
 
     match update_note_in_database(app_state, note_name, content, modified) {
-        Ok(()) => Ok(()),
+        Ok(()) => {
+            super::note_crdt::record_note_crdt_change(app_state, note_name, content);
+            Ok(())
+        }
         Err(e) => handle_database_recovery(
             app_state,
             &format!("update '{}'", note_name),
@@ -432,7 +1013,7 @@ fn ensure_parent_directory_exists(new_path: &std::path::PathBuf) -> AppResult<()
 }
 
 fn perform_atomic_file_rename(
-    app_state: &tauri::State<crate::core::state::AppState>,
+    app_state: &crate::core::state::AppState,
     old_path: &std::path::PathBuf,
     new_path: &std::path::PathBuf,
 ) -> AppResult<()> {
@@ -442,7 +1023,7 @@ fn perform_atomic_file_rename(
 }
 
 fn handle_successful_rename(
-    app_state: &tauri::State<crate::core::state::AppState>,
+    app_state: &crate::core::state::AppState,
     old_name: &str,
     new_name: &str,
     backup_path: std::path::PathBuf,
@@ -495,7 +1076,7 @@ fn handle_failed_rename(
 }
 
 fn update_database_filename(
-    app_state: &tauri::State<crate::core::state::AppState>,
+    app_state: &crate::core::state::AppState,
     old_name: &str,
     new_name: &str,
 ) -> AppResult<()> {
@@ -504,10 +1085,36 @@ fn update_database_filename(
             "UPDATE notes SET filename = ?1 WHERE filename = ?2",
             params![new_name, old_name],
         )?;
+        move_created(conn, old_name, new_name)?;
+        move_reading_progress(conn, old_name, new_name)?;
         Ok(())
     })
 }
 
+pub(crate) fn record_rename_history(
+    app_state: &crate::core::state::AppState,
+    old_name: &str,
+    new_name: &str,
+) {
+    let renamed_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    // The rename itself already succeeded; a missing redirect only affects stale links
+    if let Err(e) = with_db(app_state, |conn| {
+        record_rename(conn, old_name, new_name, renamed_at)
+    }) {
+        log(
+            "FILE_OPERATION",
+            &format!(
+                "Failed to record rename history: {} -> {}",
+                old_name, new_name
+            ),
+            Some(&e.to_string()),
+        );
+    }
+}
+
 fn cleanup_backup_file(backup_path: &std::path::PathBuf) {
     if let Err(e) = fs::remove_file(backup_path) {
         log(
@@ -542,7 +1149,7 @@ fn perform_atomic_rename_with_database(
     old_name: &str,
     new_name: &str,
     backup_path: std::path::PathBuf,
-    app_state: &tauri::State<crate::core::state::AppState>,
+    app_state: &crate::core::state::AppState,
 ) -> AppResult<()> {
     ensure_parent_directory_exists(new_path)?;
 
@@ -558,7 +1165,7 @@ fn handle_database_only_rename(
     old_name: &str,
     new_name: &str,
     new_path: &std::path::PathBuf,
-    app_state: &tauri::State<crate::core::state::AppState>,
+    app_state: &crate::core::state::AppState,
 ) -> AppResult<()> {
     if new_path.exists() {
         match with_db(app_state, |conn| {
@@ -566,6 +1173,8 @@ fn handle_database_only_rename(
                 "UPDATE notes SET filename = ?1 WHERE filename = ?2",
                 params![new_name, old_name],
             )?;
+            move_created(conn, old_name, new_name)?;
+            move_reading_progress(conn, old_name, new_name)?;
             Ok(())
         }) {
             Ok(_) => return Ok(()),
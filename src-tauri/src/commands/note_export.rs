@@ -0,0 +1,305 @@
+use crate::{
+    config::get_config_notes_dir,
+    core::{AppError, AppResult},
+    database::with_db,
+    logging::log,
+    search::search_notes_hybrid,
+    services::{
+        export_template_service::{
+            self, export_template_context, export_template_path, render_export_template,
+            template_output_extension,
+        },
+        folder_service::normalize_folder_name,
+        outline_service::{folder_outline, DEFAULT_OUTLINE_DEPTH, MAX_OUTLINE_DEPTH},
+        publish_service::is_exportable,
+        search_export_service::{
+            collect_export_rows, render_export, ExportFormat, SearchExportFilters,
+            EXPORT_MAX_RESULTS,
+        },
+    },
+    utilities::{
+        docx_export::build_docx, frontmatter::note_title, note_renderer::note_plain_text,
+        paths::get_export_templates_dir, slides::slides_html, validation::validate_note_name,
+    },
+};
+use chrono::Local;
+use rusqlite::params;
+use std::fs;
+use std::path::PathBuf;
+
+/// Resolves an export destination, appending `extension` when missing and
+/// requiring an absolute path whose parent directory exists.
+pub(crate) fn prepare_export_path(output_path: &str, extension: &str) -> AppResult<PathBuf> {
+    let mut path = PathBuf::from(output_path.trim());
+    if !path.is_absolute() {
+        return Err(AppError::InvalidPath(format!(
+            "Export path must be absolute: {}",
+            output_path
+        )));
+    }
+
+    let has_extension = path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case(extension))
+        .unwrap_or(false);
+    if !has_extension {
+        let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(format!(".{}", extension));
+        path.set_file_name(file_name);
+    }
+
+    match path.parent() {
+        Some(parent) if parent.is_dir() => Ok(path),
+        _ => Err(AppError::InvalidPath(format!(
+            "Export directory does not exist: {}",
+            path.display()
+        ))),
+    }
+}
+
+pub(crate) fn load_note_content(
+    app_state: &crate::core::state::AppState,
+    note_name: &str,
+) -> AppResult<String> {
+    with_db(app_state, |conn| {
+        conn.query_row(
+            "SELECT content FROM notes WHERE filename = ?1",
+            params![note_name],
+            |row| row.get::<_, String>(0),
+        )
+        .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", note_name)))
+    })
+}
+
+pub(crate) fn ensure_exportable(note_name: &str, content: &str) -> AppResult<()> {
+    if is_exportable(content) {
+        Ok(())
+    } else {
+        Err(AppError::FilePermission(format!(
+            "'{}' is marked private (visibility: private or publish: false) and cannot be exported",
+            note_name
+        )))
+    }
+}
+
+#[tauri::command]
+pub fn export_note_docx(
+    note_name: &str,
+    output_path: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(note_name)?;
+        let destination = prepare_export_path(output_path, "docx")?;
+        let content = load_note_content(&app_state, note_name)?;
+        ensure_exportable(note_name, &content)?;
+
+        let notes_dir = get_config_notes_dir();
+        let note_dir = notes_dir
+            .join(note_name)
+            .parent()
+            .map(|dir| dir.to_path_buf())
+            .unwrap_or_else(|| notes_dir.clone());
+
+        let document = build_docx(
+            &content,
+            &note_title(note_name, &content),
+            Some(&note_dir),
+            Some(&notes_dir),
+        )?;
+        fs::write(&destination, &document)
+            .map_err(|e| AppError::FileWrite(format!("Failed to write DOCX: {}", e)))?;
+
+        log(
+            "EXPORT",
+            &format!(
+                "DOCX: {} -> {} | Size: {} bytes",
+                note_name,
+                destination.display(),
+                document.len()
+            ),
+            None,
+        );
+        Ok(destination.to_string_lossy().to_string())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Writes a note as a reveal.js slide deck in one HTML file, starting a new
+/// slide at every `---` and `##` heading.
+#[tauri::command]
+pub fn export_note_slides(
+    note_name: &str,
+    output_path: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(note_name)?;
+        let destination = prepare_export_path(output_path, "html")?;
+        let content = load_note_content(&app_state, note_name)?;
+        ensure_exportable(note_name, &content)?;
+
+        let deck = slides_html(note_name, &note_title(note_name, &content), &content);
+        fs::write(&destination, &deck)
+            .map_err(|e| AppError::FileWrite(format!("Failed to write slides: {}", e)))?;
+
+        log(
+            "EXPORT",
+            &format!(
+                "Slides: {} -> {} | Size: {} bytes",
+                note_name,
+                destination.display(),
+                deck.len()
+            ),
+            None,
+        );
+        Ok(destination.to_string_lossy().to_string())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// The note as plain text for pasting into forms and plain-text fields, with
+/// front matter, markdown syntax and wikilink brackets removed.
+#[tauri::command]
+pub fn export_note_plaintext(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(note_name)?;
+        let content = load_note_content(&app_state, note_name)?;
+        ensure_exportable(note_name, &content)?;
+        Ok(note_plain_text(note_name, &content))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// A numbered markdown outline of the headings in `folder`'s notes, ordered
+/// by their `order` front-matter field, as a report skeleton. `depth` is how
+/// many heading levels to include below each note (default 2, at most 4).
+#[tauri::command]
+pub fn export_outline(
+    folder: &str,
+    depth: Option<usize>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        let folder = normalize_folder_name(folder)?;
+        let depth = depth.unwrap_or(DEFAULT_OUTLINE_DEPTH);
+        if depth > MAX_OUTLINE_DEPTH {
+            return Err(AppError::validation_error(
+                "depth",
+                &format!("Outline depth must be at most {}", MAX_OUTLINE_DEPTH),
+            ));
+        }
+        with_db(&app_state, |conn| folder_outline(conn, &folder, depth))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Writes the notes matching `query` and `filters` to `output_path` as a CSV or
+/// JSON table of metadata (no note bodies). Private notes are left out.
+#[tauri::command]
+pub fn export_search_results(
+    query: &str,
+    filters: Option<SearchExportFilters>,
+    output_path: &str,
+    format: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        let format = ExportFormat::parse(format)?;
+        let destination = prepare_export_path(output_path, format.extension())?;
+        let filters = filters.unwrap_or_default();
+
+        let note_names: Vec<String> = search_notes_hybrid(&app_state, query, EXPORT_MAX_RESULTS)?
+            .into_iter()
+            .map(|result| result.filename)
+            .collect();
+        let rows = with_db(&app_state, |conn| {
+            collect_export_rows(conn, &note_names, &filters)
+        })?;
+        let output = render_export(&rows, format)?;
+        fs::write(&destination, &output)
+            .map_err(|e| AppError::FileWrite(format!("Failed to write export: {}", e)))?;
+
+        log(
+            "EXPORT",
+            &format!(
+                "SEARCH: '{}' -> {} | Rows: {}",
+                query,
+                destination.display(),
+                rows.len()
+            ),
+            None,
+        );
+        Ok(destination.to_string_lossy().to_string())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Handlebars templates (`*.hbs`) in the `export_templates` folder of the app
+/// data directory.
+#[tauri::command]
+pub fn list_export_templates() -> Result<Vec<String>, String> {
+    let result = || -> AppResult<Vec<String>> {
+        export_template_service::list_export_templates(&get_export_templates_dir()?)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Renders a note through Handlebars export template `template` and writes it
+/// to `output_path`. The output extension comes from the template name, so
+/// `letterhead.html.hbs` writes HTML. Private notes can't be exported.
+#[tauri::command]
+pub fn export_note_with_template(
+    note_name: &str,
+    template: &str,
+    output_path: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(note_name)?;
+        let template_path = export_template_path(&get_export_templates_dir()?, template)?;
+        let extension = template_output_extension(&template_path);
+        let destination = prepare_export_path(output_path, &extension)?;
+
+        let template_source = fs::read_to_string(&template_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                AppError::FileNotFound(format!("Export template not found: {}", template))
+            }
+            _ => AppError::FileRead(format!(
+                "Failed to read export template '{}': {}",
+                template, e
+            )),
+        })?;
+        let (content, modified) = with_db(&app_state, |conn| {
+            conn.query_row(
+                "SELECT content, modified FROM notes WHERE filename = ?1",
+                params![note_name],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", note_name)))
+        })?;
+        ensure_exportable(note_name, &content)?;
+
+        let context = export_template_context(note_name, &content, modified, Local::now());
+        let output = render_export_template(&template_source, &extension, &context)?;
+        fs::write(&destination, &output)
+            .map_err(|e| AppError::FileWrite(format!("Failed to write export: {}", e)))?;
+
+        log(
+            "EXPORT",
+            &format!(
+                "Template '{}': {} -> {} | Size: {} bytes",
+                template,
+                note_name,
+                destination.display(),
+                output.len()
+            ),
+            None,
+        );
+        Ok(destination.to_string_lossy().to_string())
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -1,8 +1,14 @@
 use crate::{
     core::{AppError, AppResult},
-    utilities::validation::validate_note_name,
+    services::external_change_service::{
+        acknowledge_external_change, load_external_changes, review_external_change,
+        ExternalChangeReview,
+    },
+    utilities::{paths::get_external_changes_path, validation::validate_note_name},
 };
 
+/// Opens the note in the system's default editor. In-app saves of it are
+/// refused until the watcher sees that editor write it.
 #[tauri::command]
 pub fn open_note_in_editor(
     note_name: &str,
@@ -42,11 +48,27 @@ pub fn open_note_in_editor(
                 .status()
                 .map_err(AppError::from)?;
 
+            app_state.external_edits.begin(note_name);
             Ok(())
         })
         .map_err(|e| e.to_string())
 }
 
+/// Ends the external edit `open_note_in_editor` started, allowing in-app saves
+/// of the note again without waiting for the editor to write it. Returns false
+/// when the note wasn't being edited externally.
+#[tauri::command]
+pub fn end_external_edit(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<bool, String> {
+    let result = || -> AppResult<bool> {
+        validate_note_name(note_name)?;
+        Ok(app_state.external_edits.end(note_name))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn open_note_folder(
     note_name: &str,
@@ -96,3 +118,40 @@ pub fn open_note_folder(
     }();
     result.map_err(|e| e.to_string())
 }
+
+/// Notes changed outside the app since they were last acknowledged, oldest
+/// first, each with a diff against the version before the change. Only filled
+/// while `review_external_changes` is enabled.
+#[tauri::command]
+pub fn get_external_changes(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<ExternalChangeReview>, String> {
+    let result = || -> AppResult<Vec<ExternalChangeReview>> {
+        let notes_dir = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            std::path::PathBuf::from(&config.notes_directory)
+        };
+        let mut changes = load_external_changes(&get_external_changes_path(&notes_dir)?)?;
+        changes.sort_by_key(|change| change.detected_at);
+        Ok(changes
+            .iter()
+            .map(|change| review_external_change(change, &notes_dir))
+            .collect())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Clears `note_name` from the external change review queue.
+#[tauri::command]
+pub fn acknowledge_change(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<bool, String> {
+    let result = || -> AppResult<bool> {
+        validate_note_name(note_name)?;
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        let store_path = get_external_changes_path(std::path::Path::new(&config.notes_directory))?;
+        acknowledge_external_change(&store_path, note_name)
+    }();
+    result.map_err(|e| e.to_string())
+}
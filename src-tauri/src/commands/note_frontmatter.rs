@@ -0,0 +1,77 @@
+use crate::{
+    commands::note_crud::perform_safe_write_and_update,
+    core::{AppError, AppResult},
+    database::with_db,
+    logging::log,
+    services::{derived_index_service::refresh_index, metadata_service::NOTE_METADATA_INDEX},
+    utilities::{
+        frontmatter::{self, parse_frontmatter, Frontmatter, FrontmatterValue},
+        text_encoding::read_note_text,
+        validation::{validate_frontmatter_field, validate_note_name},
+    },
+};
+use rusqlite::params;
+use std::path::PathBuf;
+
+/// A note's front-matter fields in file order; empty when it has none.
+#[tauri::command]
+pub fn get_frontmatter(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Frontmatter, String> {
+    let result = || -> AppResult<Frontmatter> {
+        validate_note_name(note_name)?;
+        let content: String = with_db(&app_state, |conn| {
+            conn.query_row(
+                "SELECT content FROM notes WHERE filename = ?1",
+                params![note_name],
+                |row| row.get(0),
+            )
+            .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", note_name)))
+        })?;
+        Ok(parse_frontmatter(&content).unwrap_or_default())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Sets one front-matter field, or removes it when `value` is `None`, leaving
+/// the rest of the block and the body untouched. A note without front matter
+/// gets a new block. Returns the front matter now in the note.
+#[tauri::command]
+pub fn set_frontmatter_field(
+    note_name: &str,
+    key: &str,
+    value: Option<FrontmatterValue>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Frontmatter, String> {
+    let result = || -> AppResult<Frontmatter> {
+        validate_note_name(note_name)?;
+        validate_frontmatter_field(key, value.as_ref())?;
+
+        let note_path = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            PathBuf::from(&config.notes_directory).join(note_name)
+        };
+        let existing = read_note_text(&note_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                AppError::FileNotFound(format!("Note not found: {}", note_name))
+            }
+            _ => AppError::FileRead(format!("Failed to read '{}': {}", note_name, e)),
+        })?;
+
+        let content = frontmatter::set_frontmatter_field(&existing, key, value.as_ref());
+        if content != existing {
+            perform_safe_write_and_update(&note_path, &content, note_name, &app_state)?;
+            // The write succeeded, so a stale index only delays queries
+            if let Err(e) = with_db(&app_state, |conn| refresh_index(conn, &NOTE_METADATA_INDEX)) {
+                log(
+                    "METADATA_INDEX",
+                    "Failed to refresh metadata index",
+                    Some(&e.to_string()),
+                );
+            }
+        }
+        Ok(parse_frontmatter(&content).unwrap_or_default())
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,84 @@
+use crate::{
+    core::{AppError, AppResult},
+    database::with_db,
+    services::{
+        link_service::{vault_health_report, VaultHealthReport},
+        vault_lint_service::{self, VaultLintReport, VaultLintRules},
+    },
+    utilities::{
+        note_lint::{lint_content, LintDiagnostic},
+        validation::validate_note_name,
+    },
+};
+use rusqlite::params;
+
+#[tauri::command]
+pub fn lint_note(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<LintDiagnostic>, String> {
+    let result = || -> AppResult<Vec<LintDiagnostic>> {
+        validate_note_name(note_name)?;
+
+        let lint_config = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            config.lint.clone()
+        };
+
+        let (content, known_notes) = with_db(&app_state, |conn| {
+            let content = conn
+                .query_row(
+                    "SELECT content FROM notes WHERE filename = ?1",
+                    params![note_name],
+                    |row| row.get::<_, String>(0),
+                )
+                .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", note_name)))?;
+
+            let known_notes = if lint_config.check_broken_links {
+                let mut stmt = conn.prepare("SELECT filename FROM notes")?;
+                let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+                rows.collect::<Result<Vec<_>, _>>()?
+            } else {
+                Vec::new()
+            };
+
+            Ok((content, known_notes))
+        })?;
+
+        Ok(lint_content(
+            note_name,
+            &content,
+            &lint_config,
+            &known_notes,
+        ))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Checks every note's front matter against the `[lint]` schema and naming
+/// patterns, with the issues grouped by rule for cleaning up imported vaults.
+#[tauri::command]
+pub fn lint_vault(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<VaultLintReport, String> {
+    let result = || -> AppResult<VaultLintReport> {
+        let lint_config = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            config.lint.clone()
+        };
+        let rules = VaultLintRules::new(&lint_config)?;
+        with_db(&app_state, |conn| {
+            vault_lint_service::lint_vault(conn, &rules)
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Links pointing to notes that don't exist, and notes with no links to or
+/// from other notes.
+#[tauri::command]
+pub fn get_vault_health_report(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<VaultHealthReport, String> {
+    with_db(&app_state, vault_health_report).map_err(|e| e.to_string())
+}
@@ -0,0 +1,103 @@
+use crate::{
+    commands::note_crud::perform_safe_write_and_update,
+    core::{AppError, AppResult},
+    database::with_db,
+    logging::log,
+    services::replace_service::{
+        candidate_notes, FileReplacement, ReplaceOptions, ReplaceReport, Replacement,
+    },
+    utilities::{
+        file_safety::{create_versioned_backup, BackupType},
+        text_encoding::read_note_text,
+    },
+};
+use std::path::PathBuf;
+
+/// Replaces `pattern` with `replacement` across the vault (or one folder).
+/// With `dry_run`, returns each affected note's diff without touching it;
+/// otherwise each note is backed up and rewritten through `safe_write_note`.
+/// A note that fails is reported with its error and the rest still proceed.
+#[tauri::command]
+pub fn replace_in_notes(
+    pattern: &str,
+    replacement: &str,
+    options: Option<ReplaceOptions>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<ReplaceReport, String> {
+    let result = || -> AppResult<ReplaceReport> {
+        let options = options.unwrap_or_default();
+        let replacer = Replacement::new(pattern, replacement, options.flags)?;
+        let candidates = with_db(&app_state, |conn| {
+            candidate_notes(conn, &replacer, options.folder.as_deref())
+        })?;
+
+        let mut report = ReplaceReport {
+            dry_run: options.dry_run,
+            ..ReplaceReport::default()
+        };
+        if options.dry_run {
+            for (filename, content) in &candidates {
+                if let Some(preview) = replacer.preview(filename, content) {
+                    report.push(preview);
+                }
+            }
+            return Ok(report);
+        }
+
+        let notes_dir = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            PathBuf::from(&config.notes_directory)
+        };
+        for (filename, _) in &candidates {
+            match replace_in_note(&notes_dir, filename, &replacer, &app_state) {
+                Ok(Some(file)) => report.push(file),
+                Ok(None) => {}
+                Err(e) => report.push(FileReplacement {
+                    filename: filename.clone(),
+                    replacements: 0,
+                    diff: None,
+                    backup_path: None,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+
+        log(
+            "REPLACE",
+            &format!(
+                "'{}': {} replacement(s) in {} note(s)",
+                pattern,
+                report.total_replacements,
+                report.files.iter().filter(|f| f.error.is_none()).count()
+            ),
+            None,
+        );
+        Ok(report)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Rewrites one note from its file on disk, which may be newer than the index.
+fn replace_in_note(
+    notes_dir: &std::path::Path,
+    filename: &str,
+    replacer: &Replacement,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<Option<FileReplacement>> {
+    let note_path = notes_dir.join(filename);
+    let content = read_note_text(&note_path)
+        .map_err(|e| AppError::FileRead(format!("Failed to read '{}': {}", filename, e)))?;
+    let Some((replaced, count)) = replacer.apply(&content) else {
+        return Ok(None);
+    };
+
+    let backup_path = create_versioned_backup(&note_path, BackupType::Replace, Some(&content))?;
+    perform_safe_write_and_update(&note_path, &replaced, filename, app_state)?;
+    Ok(Some(FileReplacement {
+        filename: filename.to_string(),
+        replacements: count,
+        diff: None,
+        backup_path: Some(backup_path.to_string_lossy().to_string()),
+        error: None,
+    }))
+}
@@ -1,11 +1,251 @@
-use crate::search::search_notes_hybrid;
+use crate::{
+    core::{
+        events::{emit_event, ClipboardSearch},
+        AppError, AppResult,
+    },
+    database::with_db,
+    logging::log,
+    search::{
+        search_notes_hybrid, search_notes_paged, SearchOptions, SearchResult, SearchScope,
+        SearchSort,
+    },
+    services::{
+        grep_service::{self, build_grep_regex, GrepFlags, GrepNoteMatches},
+        keyword_service::{keyword_cloud, Keyword},
+        metadata_service::{self, query_notes_by_metadata, MetadataQuery, NoteMetadata},
+        quick_switch_service::{
+            self, FilenameMatch, QuickSwitchResult, DEFAULT_QUICK_SWITCH_LIMIT,
+        },
+    },
+    utilities::{
+        property_query::{parse_property_filter, parse_property_sort},
+        strings::clipboard_search_query,
+    },
+};
+use std::collections::HashMap;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
 
+/// One page of search results, optionally limited to a folder or tag. `limit`
+/// defaults to and is capped at `max_search_results`; ask for the next page
+/// with `offset`.
 #[tauri::command]
 pub fn search_notes(
     query: &str,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    sort: Option<SearchSort>,
+    scope: Option<SearchScope>,
     app_state: tauri::State<crate::core::state::AppState>,
-) -> Result<Vec<String>, String> {
-    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
-    search_notes_hybrid(&app_state, query, config.preferences.max_search_results)
-        .map_err(|e| e.to_string())
+) -> Result<Vec<SearchResult>, String> {
+    let max_results = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        config.preferences.max_search_results
+    };
+    let options = SearchOptions {
+        offset: offset.unwrap_or(0),
+        limit: limit.unwrap_or(max_results).min(max_results),
+        sort: sort.unwrap_or_default(),
+        scope: scope.unwrap_or_default(),
+    };
+    search_notes_paged(&app_state, query, &options).map_err(|e| e.to_string())
+}
+
+/// Searches for the text on the clipboard, as the clipboard search shortcut
+/// does, and tells the main window with a `clipboard-search` event. Returns
+/// `None` when the clipboard holds no text.
+#[tauri::command]
+pub fn search_clipboard(
+    app: AppHandle,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Option<ClipboardSearch>, String> {
+    let result = || -> AppResult<Option<ClipboardSearch>> {
+        let text = app.clipboard().read_text().unwrap_or_else(|e| {
+            log(
+                "CLIPBOARD_SEARCH",
+                "Failed to read clipboard text",
+                Some(&e.to_string()),
+            );
+            String::new()
+        });
+        let Some(query) = clipboard_search_query(&text) else {
+            return Ok(None);
+        };
+        let max_results = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            config.preferences.max_search_results
+        };
+        let results = search_notes_hybrid(&app_state, &query, max_results)?
+            .into_iter()
+            .map(|result| result.filename)
+            .collect();
+
+        let search = ClipboardSearch { query, results };
+        emit_event(&app, search.clone());
+        Ok(Some(search))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Terms shown in a keyword cloud when the caller doesn't ask for a number
+const DEFAULT_KEYWORD_LIMIT: usize = 50;
+const MAX_KEYWORD_LIMIT: usize = 500;
+
+/// Most frequent terms in the vault, or in a folder or tag, without stop
+/// words, for a word-cloud overview.
+#[tauri::command]
+pub fn get_keyword_cloud(
+    scope: Option<SearchScope>,
+    limit: Option<usize>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<Keyword>, String> {
+    let result = || -> AppResult<Vec<Keyword>> {
+        let tokenizer = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            config.preferences.fts_tokenizer.clone()
+        };
+        if tokenizer == "trigram" {
+            return Err(AppError::validation_error(
+                "fts_tokenizer",
+                "Keyword clouds need a word tokenizer, the trigram index has no words",
+            ));
+        }
+
+        let limit = limit
+            .unwrap_or(DEFAULT_KEYWORD_LIMIT)
+            .min(MAX_KEYWORD_LIMIT);
+        with_db(&app_state, |conn| {
+            keyword_cloud(conn, &scope.unwrap_or_default(), limit)
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Regex scan of note content for what FTS tokenization can't match, such
+/// as `TODO\(\w+\)` or exact symbols. Returns matching lines per note, for
+/// at most `max_search_results` notes in filename order.
+#[tauri::command]
+pub fn grep_notes(
+    pattern: &str,
+    flags: Option<GrepFlags>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<GrepNoteMatches>, String> {
+    let result = || -> AppResult<Vec<GrepNoteMatches>> {
+        let regex = build_grep_regex(pattern, flags.unwrap_or_default())?;
+        let max_results = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            config.preferences.max_search_results
+        };
+        with_db(&app_state, |conn| {
+            grep_service::grep_notes(conn, &regex, max_results)
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Fuzzy matches filenames, aliases and headings (never note bodies) for the
+/// open-note palette.
+#[tauri::command]
+pub fn quick_switch(
+    query: &str,
+    limit: Option<usize>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<QuickSwitchResult>, String> {
+    let limit = limit.unwrap_or(DEFAULT_QUICK_SWITCH_LIMIT);
+    with_db(&app_state, |conn| {
+        quick_switch_service::quick_switch(conn, query, limit)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// fzf-style fuzzy match over note paths with highlight positions, for a
+/// quick-open palette where FTS matching on filenames is too strict.
+#[tauri::command]
+pub fn fuzzy_match_filenames(
+    query: &str,
+    limit: Option<usize>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<FilenameMatch>, String> {
+    let limit = limit.unwrap_or(DEFAULT_QUICK_SWITCH_LIMIT);
+    with_db(&app_state, |conn| {
+        quick_switch_service::fuzzy_match_filenames(conn, query, limit)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Notes matching `query` (every note when empty), filtered and sorted by the
+/// front-matter fields declared in `[metadata] fields`, with those fields'
+/// values.
+#[tauri::command]
+pub fn search_notes_by_metadata(
+    query: &str,
+    filters: Option<HashMap<String, String>>,
+    sort_by: Option<String>,
+    descending: Option<bool>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<NoteMetadata>, String> {
+    let result = || -> AppResult<Vec<NoteMetadata>> {
+        let (schema, max_results) = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            (
+                config.metadata.fields.clone(),
+                config.preferences.max_search_results,
+            )
+        };
+        let candidates = if query.trim().is_empty() {
+            None
+        } else {
+            // Uncapped so filtering doesn't run on an already truncated list
+            Some(
+                search_notes_hybrid(&app_state, query, usize::MAX)?
+                    .into_iter()
+                    .map(|result| result.filename)
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let filters = filters.unwrap_or_default();
+
+        let mut notes = with_db(&app_state, |conn| {
+            query_notes_by_metadata(
+                conn,
+                &schema,
+                &MetadataQuery {
+                    candidates: candidates.as_deref(),
+                    filters: &filters,
+                    sort_by: sort_by.as_deref(),
+                    descending: descending.unwrap_or(false),
+                },
+            )
+        })?;
+        notes.truncate(max_results);
+        Ok(notes)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Notes whose front matter matches `filter`, e.g.
+/// `status = "active" AND due < 2025-01-01`, ordered by `sort` such as
+/// `"due, priority DESC"`. Any front-matter field can be queried. Comparisons
+/// are numeric when both sides are numbers and case-insensitive otherwise.
+#[tauri::command]
+pub fn query_notes_by_properties(
+    filter: Option<String>,
+    sort: Option<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<NoteMetadata>, String> {
+    let result = || -> AppResult<Vec<NoteMetadata>> {
+        let filter = parse_property_filter(filter.as_deref().unwrap_or_default())?;
+        let sort = parse_property_sort(sort.as_deref().unwrap_or_default())?;
+        let max_results = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            config.preferences.max_search_results
+        };
+
+        let mut notes = with_db(&app_state, |conn| {
+            metadata_service::query_notes_by_properties(conn, filter.as_ref(), &sort)
+        })?;
+        notes.truncate(max_results);
+        Ok(notes)
+    }();
+    result.map_err(|e| e.to_string())
 }
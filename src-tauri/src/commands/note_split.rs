@@ -0,0 +1,73 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::{
+        links::strip_note_extension,
+        note_sections::{section_file_stem, split_sections},
+        text_encoding::read_note_text,
+        validation::validate_note_name,
+    },
+};
+use std::collections::HashSet;
+
+/// Copies each section of a note starting at a heading of `level` into its
+/// own note, in a folder named after the note next to it (`Plan.md` splits
+/// into `Plan/Goals.md`, `Plan/Budget.md`, ...). The original note is left
+/// as it is. Returns the new notes in section order.
+#[tauri::command]
+pub fn split_note_by_heading(
+    note_name: &str,
+    level: u8,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<String>, String> {
+    let result = || -> AppResult<Vec<String>> {
+        validate_note_name(note_name)?;
+        if !(1..=6).contains(&level) {
+            return Err(AppError::validation_error(
+                "level",
+                "Heading level must be between 1 and 6",
+            ));
+        }
+
+        let notes_dir = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            std::path::PathBuf::from(&config.notes_directory)
+        };
+        let content = read_note_text(&notes_dir.join(note_name)).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                AppError::FileNotFound(format!("Note not found: {}", note_name))
+            }
+            _ => AppError::FileRead(format!("Failed to read '{}': {}", note_name, e)),
+        })?;
+
+        let sections = split_sections(&content, level as usize);
+        if sections.is_empty() {
+            return Err(AppError::validation_error(
+                "level",
+                &format!("'{}' has no level {} headings", note_name, level),
+            ));
+        }
+
+        let folder = strip_note_extension(note_name);
+        let extension = &note_name[folder.len()..];
+        let mut used: HashSet<String> = HashSet::new();
+        let mut created = Vec::new();
+        for (index, section) in sections.iter().enumerate() {
+            let stem = section_file_stem(&section.title)
+                .unwrap_or_else(|| format!("Section {}", index + 1));
+            // Sections sharing a title, or notes already in the folder, get
+            // numbered names
+            let mut new_name = format!("{}/{}{}", folder, stem, extension);
+            let mut n = 2;
+            while used.contains(&new_name.to_lowercase()) || notes_dir.join(&new_name).exists() {
+                new_name = format!("{}/{} {}{}", folder, stem, n, extension);
+                n += 1;
+            }
+            used.insert(new_name.to_lowercase());
+
+            super::note_crud::create_note_with_content(&new_name, &section.content, &app_state)?;
+            created.push(new_name);
+        }
+        Ok(created)
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -1,12 +1,18 @@
 use crate::{
     core::{AppError, AppResult},
-    services::note_service::update_note_in_database,
+    services::{
+        note_service::update_note_in_database,
+        trash_service::{self, TrashEntry},
+    },
     utilities::{
         file_safety::safe_write_note,
         strings::{
             format_timestamp_for_humans, parse_backup_filename, parse_deleted_backup_filename,
         },
+        text_encoding::read_note_text,
         validation::validate_note_name,
+        version_labels::{load_version_labels, set_version_label},
+        write_recovery::{self, RecoverableWrite},
     },
 };
 use std::fs;
@@ -19,6 +25,8 @@ pub struct NoteVersion {
     pub timestamp: u64,
     pub size: u64,
     pub formatted_time: String,
+    /// Name given with `label_version`; labeled versions are never pruned
+    pub label: Option<String>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -29,6 +37,11 @@ pub struct DeletedFile {
     pub timestamp: u64,
 }
 
+/// `backup_filename` prefix marking a recently deleted entry that lives in the
+/// trash rather than in the backup folder
+const TRASH_BACKUP_PREFIX: &str = "trash:";
+
+/// Versions of a note, labeled ones first, each group newest first.
 #[tauri::command]
 pub fn get_note_versions(
     note_name: &str,
@@ -50,6 +63,7 @@ pub fn get_note_versions(
             std::borrow::Cow::from(note_name)
         };
 
+        let labels = load_version_labels(&backup_dir)?;
         let mut versions = Vec::new();
 
         if let Ok(entries) = fs::read_dir(&backup_dir) {
@@ -63,6 +77,7 @@ pub fn get_note_versions(
                         let formatted_time = format_timestamp_for_humans(timestamp);
 
                         versions.push(NoteVersion {
+                            label: labels.get(&filename).cloned(),
                             filename: filename.clone(),
                             backup_type,
                             timestamp,
@@ -74,14 +89,53 @@ pub fn get_note_versions(
             }
         }
 
-        // Sort by timestamp (newest first)
-        versions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        versions.sort_by(|a, b| {
+            b.label
+                .is_some()
+                .cmp(&a.label.is_some())
+                .then(b.timestamp.cmp(&a.timestamp))
+        });
 
         Ok(versions)
     }();
     result.map_err(|e| e.to_string())
 }
 
+/// Names a version of a note, such as "before big rewrite", and keeps it from
+/// being pruned. A blank label removes it.
+#[tauri::command]
+pub fn label_version(
+    note_name: &str,
+    version: &str,
+    label: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        validate_note_name(note_name)?;
+
+        let backup_dir = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            let notes_dir = std::path::PathBuf::from(&config.notes_directory);
+            crate::utilities::paths::get_backup_dir_for_notes_path(&notes_dir)?
+        };
+        let base_name = std::path::Path::new(note_name)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| note_name.to_string());
+        if parse_backup_filename(version, &base_name).is_none()
+            || !backup_dir.join(version).is_file()
+        {
+            return Err(AppError::FileNotFound(format!(
+                "Version '{}' of '{}' not found",
+                version, note_name
+            )));
+        }
+
+        set_version_label(&backup_dir, version, label)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_version_content(
     version_filename: &str,
@@ -100,7 +154,7 @@ pub fn get_version_content(
             )));
         }
 
-        let content = fs::read_to_string(&version_path)?;
+        let content = read_note_text(&version_path)?;
         Ok(content)
     }();
     result.map_err(|e| e.to_string())
@@ -129,7 +183,7 @@ pub fn recover_note_version(
         }
 
         // Read the version content
-        let version_content = fs::read_to_string(&version_path)?;
+        let version_content = read_note_text(&version_path)?;
 
         // Use the same programmatic flag and safe write as normal saves
         super::notes::with_programmatic_flag(&app_state, || {
@@ -157,11 +211,16 @@ pub fn get_deleted_files(
         let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
         let notes_dir = std::path::PathBuf::from(&config.notes_directory);
         let backup_dir = crate::utilities::paths::get_backup_dir_for_notes_path(&notes_dir)?;
-        if !backup_dir.exists() {
-            return Ok(Vec::new());
-        }
 
-        let mut deleted_files = Vec::new();
+        let mut deleted_files: Vec<DeletedFile> = trash_service::list_trash(&notes_dir)?
+            .into_iter()
+            .map(|entry| DeletedFile {
+                filename: entry.note_name,
+                backup_filename: format!("{}{}", TRASH_BACKUP_PREFIX, entry.id),
+                deleted_at: format_timestamp_for_humans(entry.deleted_at.max(0) as u64),
+                timestamp: entry.deleted_at.max(0) as u64,
+            })
+            .collect();
 
         if let Ok(entries) = fs::read_dir(&backup_dir) {
             for entry in entries.flatten() {
@@ -199,6 +258,11 @@ pub fn recover_deleted_file(
     let result = || -> AppResult<()> {
         validate_note_name(original_filename)?;
 
+        if let Some(trash_id) = backup_filename.strip_prefix(TRASH_BACKUP_PREFIX) {
+            restore_trashed_note(trash_id, &app_state)?;
+            return Ok(());
+        }
+
         let config = app_state.config.read().unwrap_or_else(|e| {
             crate::logging::log(
                 "RECOVER_FILE",
@@ -240,7 +304,7 @@ pub fn recover_deleted_file(
         }
 
         // Read the backup content
-        let backup_content = fs::read_to_string(&backup_path)?;
+        let backup_content = read_note_text(&backup_path)?;
 
         // Write to the original location
         super::notes::with_programmatic_flag(&app_state, || {
@@ -262,3 +326,90 @@ pub fn recover_deleted_file(
     }();
     result.map_err(|e| e.to_string())
 }
+
+/// Lists the trash, newest first, after purging notes past the retention period.
+#[tauri::command]
+pub fn list_trash(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<TrashEntry>, String> {
+    let result = || -> AppResult<Vec<TrashEntry>> {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        let notes_dir = std::path::PathBuf::from(&config.notes_directory);
+        trash_service::purge_expired_trash(
+            &notes_dir,
+            config.preferences.trash_retention_days,
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0),
+        )?;
+        trash_service::list_trash(&notes_dir)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Restores a trashed note and returns the name it was restored under.
+#[tauri::command]
+pub fn restore_from_trash(
+    id: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    restore_trashed_note(id, &app_state).map_err(|e| e.to_string())
+}
+
+/// Permanently deletes every note in the trash and returns how many were removed.
+#[tauri::command]
+pub fn empty_trash(app_state: tauri::State<crate::core::state::AppState>) -> Result<usize, String> {
+    let result = || -> AppResult<usize> {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        let notes_dir = std::path::PathBuf::from(&config.notes_directory);
+        let removed = super::notes::with_programmatic_flag(&app_state, || {
+            trash_service::empty_trash(&notes_dir)
+        })?;
+        crate::logging::log(
+            "FILE_OPERATION",
+            &format!("EMPTY_TRASH: {} notes removed", removed),
+            None,
+        );
+        Ok(removed)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+fn restore_trashed_note(
+    id: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<String> {
+    let notes_dir = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        std::path::PathBuf::from(&config.notes_directory)
+    };
+
+    let restored_name = super::notes::with_programmatic_flag(app_state, || {
+        trash_service::restore_from_trash(&notes_dir, id)
+    })?;
+    let content = read_note_text(&notes_dir.join(&restored_name))?;
+    let modified = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    update_note_in_database(app_state, &restored_name, &content, modified)?;
+    Ok(restored_name)
+}
+
+/// Text of saves a crash interrupted, newest first. Registered at startup
+/// before the leftover temp files are removed.
+#[tauri::command]
+pub fn get_recoverable_writes() -> Result<Vec<RecoverableWrite>, String> {
+    crate::utilities::paths::get_recovery_dir()
+        .and_then(|dir| write_recovery::list_recoverable_writes(&dir))
+        .map_err(|e| e.to_string())
+}
+
+/// Drops a recoverable write once its text is restored or no longer wanted.
+#[tauri::command]
+pub fn discard_recoverable_write(id: &str) -> Result<(), String> {
+    crate::utilities::paths::get_recovery_dir()
+        .and_then(|dir| write_recovery::discard_recoverable_write(&dir, id))
+        .map_err(|e| e.to_string())
+}
@@ -28,7 +28,14 @@ where
 
 // Re-export all note-related commands from their respective modules
 // This maintains backward compatibility while organizing the code better
+pub use super::note_appearance::*;
+pub use super::note_crdt::*;
 pub use super::note_crud::*;
+pub use super::note_export::*;
 pub use super::note_external::*;
+pub use super::note_frontmatter::*;
+pub use super::note_lint::*;
+pub use super::note_replace::*;
 pub use super::note_search::*;
+pub use super::note_split::*;
 pub use super::note_versions::*;
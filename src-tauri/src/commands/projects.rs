@@ -0,0 +1,21 @@
+use crate::{
+    commands::publish::load_source_notes,
+    core::AppResult,
+    services::project_service::{normalize_project_folder, project_overview, ProjectOverview},
+};
+use chrono::Local;
+
+/// Open tasks, recent notes, upcoming dates and frequent links for a folder,
+/// so a project dashboard can render from one call.
+#[tauri::command]
+pub fn get_project_overview(
+    folder: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<ProjectOverview, String> {
+    let result = || -> AppResult<ProjectOverview> {
+        let folder = normalize_project_folder(folder)?;
+        let notes = load_source_notes(&app_state)?;
+        Ok(project_overview(&folder, &notes, Local::now().date_naive()))
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,79 @@
+use crate::{
+    config::get_config_notes_dir,
+    core::{AppError, AppResult},
+    database::with_db,
+    services::publish_service::{
+        list_publishable, publish_notes, PublishOptions, PublishReport, PublishableNote, SourceNote,
+    },
+};
+use std::path::PathBuf;
+
+fn validate_output_dir(output_dir: &str) -> AppResult<PathBuf> {
+    let path = PathBuf::from(output_dir.trim());
+    if !path.is_absolute() {
+        return Err(AppError::InvalidPath(format!(
+            "Output directory must be absolute: {}",
+            output_dir
+        )));
+    }
+
+    // Publishing into the vault would make the watcher index the generated pages
+    let notes_dir = get_config_notes_dir();
+    let notes_dir = notes_dir.canonicalize().unwrap_or(notes_dir);
+    let resolved = path.canonicalize().unwrap_or_else(|_| {
+        path.parent()
+            .and_then(|parent| parent.canonicalize().ok())
+            .map(|parent| parent.join(path.file_name().unwrap_or_default()))
+            .unwrap_or_else(|| path.clone())
+    });
+    if resolved.starts_with(&notes_dir) || notes_dir.starts_with(&resolved) {
+        return Err(AppError::InvalidPath(
+            "Output directory cannot overlap the notes directory".to_string(),
+        ));
+    }
+
+    Ok(path)
+}
+
+pub(crate) fn load_source_notes(
+    app_state: &crate::core::state::AppState,
+) -> AppResult<Vec<SourceNote>> {
+    with_db(app_state, |conn| {
+        let mut stmt =
+            conn.prepare("SELECT filename, content, modified FROM notes ORDER BY filename")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(SourceNote {
+                filename: row.get(0)?,
+                content: row.get(1)?,
+                modified: row.get(2)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    })
+}
+
+#[tauri::command]
+pub fn publish_vault(
+    output_dir: &str,
+    options: Option<PublishOptions>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<PublishReport, String> {
+    let result = || -> AppResult<PublishReport> {
+        let output_dir = validate_output_dir(output_dir)?;
+        let notes = load_source_notes(&app_state)?;
+        publish_notes(&notes, &output_dir, &options.unwrap_or_default())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_publishable_notes(
+    options: Option<PublishOptions>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<PublishableNote>, String> {
+    let result = || -> AppResult<Vec<PublishableNote>> {
+        let notes = load_source_notes(&app_state)?;
+        Ok(list_publishable(&notes, &options.unwrap_or_default()))
+    }();
+    result.map_err(|e| e.to_string())
+}
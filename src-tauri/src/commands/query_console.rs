@@ -0,0 +1,16 @@
+use crate::{
+    core::AppResult,
+    services::query_console_service::{self, open_readonly, QueryResult, DEFAULT_QUERY_LIMIT},
+    utilities::paths::get_database_path,
+};
+
+/// Runs a single SELECT against the notes index on a separate read-only
+/// connection, for the in-app query console.
+#[tauri::command]
+pub fn run_readonly_query(sql: &str, limit: Option<usize>) -> Result<QueryResult, String> {
+    let result = || -> AppResult<QueryResult> {
+        let conn = open_readonly(&get_database_path()?)?;
+        query_console_service::run_readonly_query(&conn, sql, limit.unwrap_or(DEFAULT_QUERY_LIMIT))
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,198 @@
+use crate::{
+    commands::{note_export::load_note_content, publish::load_source_notes},
+    config::get_config_notes_dir,
+    core::{
+        events::{emit_event, ReadingListFailed, ReadingListUpdated},
+        AppError, AppResult,
+    },
+    database::with_db,
+    logging::log,
+    services::{
+        clipper_service::{
+            clip_html, fetch_page, reading_note_content, reading_note_name, reading_queue,
+            validate_clip_url, ReadingItem,
+        },
+        folder_service::{new_note_folder, note_under_folder},
+        note_service::write_note_and_index,
+        reading_progress_service,
+    },
+    utilities::{
+        highlights::{
+            build_highlights_note, find_highlights, highlights_note_name, highlights_origin,
+            Highlight,
+        },
+        text_encoding::read_note_text,
+        validation::validate_note_name,
+    },
+};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HighlightsReport {
+    /// The consolidated highlights note that was created or refreshed
+    pub note_name: String,
+    pub origin: String,
+    pub highlights: Vec<Highlight>,
+}
+
+/// Queues a page for later reading. The page is fetched and clipped in the
+/// background; `reading-list-updated` carries the URL and new note name once it
+/// is saved and `reading-list-failed` the URL and error message if clipping fails.
+#[tauri::command]
+pub fn add_to_reading_list(
+    url: &str,
+    app: tauri::AppHandle,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let url = validate_clip_url(url).map_err(|e| e.to_string())?;
+    let app_state = Arc::new(app_state.inner().clone());
+    let queued_url = url.clone();
+
+    std::thread::spawn(move || match clip_to_reading_list(&app_state, &url) {
+        Ok(note_name) => {
+            log(
+                "READING_LIST",
+                &format!("Clipped '{}' -> {}", url, note_name),
+                None,
+            );
+            emit_event(&app, ReadingListUpdated { url, note_name });
+        }
+        Err(e) => {
+            log(
+                "READING_LIST",
+                &format!("Failed to clip '{}'", url),
+                Some(&e.to_string()),
+            );
+            emit_event(
+                &app,
+                ReadingListFailed {
+                    url,
+                    message: e.to_string(),
+                },
+            );
+        }
+    });
+
+    Ok(queued_url)
+}
+
+#[tauri::command]
+pub fn list_reading_queue(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<ReadingItem>, String> {
+    let result =
+        || -> AppResult<Vec<ReadingItem>> { Ok(reading_queue(&load_source_notes(&app_state)?)) }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Collects `==marked==` and quoted passages from a note into its highlights note,
+/// replacing the previous extraction for the same note.
+#[tauri::command]
+pub fn extract_highlights(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<HighlightsReport, String> {
+    let result = || -> AppResult<HighlightsReport> {
+        validate_note_name(note_name)?;
+        let content = load_note_content(&app_state, note_name)?;
+        if highlights_origin(&content).is_some() {
+            return Err(AppError::validation_error(
+                "note_name",
+                "Highlights cannot be extracted from a highlights note",
+            ));
+        }
+
+        let highlights = find_highlights(&content);
+        if highlights.is_empty() {
+            return Err(AppError::validation_error(
+                "note_name",
+                &format!("'{}' has no highlighted or quoted passages", note_name),
+            ));
+        }
+
+        let notes_dir = get_config_notes_dir();
+        let highlights_name = highlights_note_name(note_name, |candidate| {
+            read_note_text(&notes_dir.join(candidate)).ok()
+        });
+        let highlights_content = build_highlights_note(note_name, &content, &highlights);
+        write_note_and_index(&app_state, &highlights_name, &highlights_content)?;
+
+        log(
+            "HIGHLIGHTS",
+            &format!(
+                "Extracted {} highlights from '{}' -> {}",
+                highlights.len(),
+                note_name,
+                highlights_name
+            ),
+            None,
+        );
+        Ok(HighlightsReport {
+            note_name: highlights_name,
+            origin: note_name.to_string(),
+            highlights,
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Records how far through a note the user has read, from 0 (unread) to 1
+/// (finished). The note's metadata then estimates the reading time left.
+#[tauri::command]
+pub fn set_reading_progress(
+    note_name: &str,
+    fraction: f64,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        validate_note_name(note_name)?;
+        let now = Utc::now().timestamp();
+        with_db(&app_state, |conn| {
+            let indexed = conn
+                .query_row(
+                    "SELECT 1 FROM notes WHERE filename = ?1",
+                    params![note_name],
+                    |_| Ok(()),
+                )
+                .optional()?
+                .is_some();
+            if !indexed {
+                return Err(AppError::FileNotFound(format!(
+                    "Note not found: {}",
+                    note_name
+                )));
+            }
+            reading_progress_service::set_reading_progress(conn, note_name, fraction, now)
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Saves the clipped page under `Reading/`, inside the folder
+/// `[preferences] new_note_location` puts new notes in.
+fn clip_to_reading_list(app_state: &crate::core::state::AppState, url: &str) -> AppResult<String> {
+    let page = clip_html(url, &fetch_page(url)?);
+    let notes_dir = get_config_notes_dir();
+    let folder = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        new_note_folder(
+            &config.preferences.new_note_location,
+            &config.preferences.inbox_folder,
+            None,
+        )
+    };
+    let note_name = note_under_folder(
+        &reading_note_name(&page.title, |candidate| {
+            notes_dir
+                .join(note_under_folder(candidate, folder.as_deref()))
+                .exists()
+        }),
+        folder.as_deref(),
+    );
+    let content = reading_note_content(url, &page, &chrono::Utc::now().to_rfc3339());
+    write_note_and_index(app_state, &note_name, &content)?;
+    Ok(note_name)
+}
@@ -0,0 +1,59 @@
+use crate::{
+    core::{AppError, AppResult},
+    database::with_db,
+    services::reminder_service::{
+        self, upcoming_reminders, Reminder, DEFAULT_SNOOZE_MINUTES, DEFAULT_UPCOMING_DAYS,
+        MAX_SNOOZE_MINUTES,
+    },
+    utilities::validation::validate_note_name,
+};
+use chrono::Local;
+
+/// Open tasks due within `days` (default `DEFAULT_UPCOMING_DAYS`), overdue
+/// ones first, with when each is snoozed until.
+#[tauri::command]
+pub fn list_upcoming_reminders(
+    days: Option<u32>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<Reminder>, String> {
+    let now = Local::now();
+    with_db(&app_state, |conn| {
+        upcoming_reminders(
+            conn,
+            now.date_naive(),
+            days.unwrap_or(DEFAULT_UPCOMING_DAYS),
+            now.timestamp(),
+        )
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Holds off a reminder for `minutes` (default `DEFAULT_SNOOZE_MINUTES`, at
+/// most 30 days), after which it's notified again. `task` and `due` are as
+/// listed by `list_upcoming_reminders`. Returns when the snooze ends, in Unix
+/// seconds.
+#[tauri::command]
+pub fn snooze_reminder(
+    note_name: String,
+    task: String,
+    due: String,
+    minutes: Option<u32>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<i64, String> {
+    let result = || -> AppResult<i64> {
+        validate_note_name(&note_name)?;
+        let minutes = minutes.unwrap_or(DEFAULT_SNOOZE_MINUTES);
+        if minutes == 0 || minutes > MAX_SNOOZE_MINUTES {
+            return Err(AppError::validation_error(
+                "minutes",
+                &format!("Must be between 1 and {}", MAX_SNOOZE_MINUTES),
+            ));
+        }
+        let until = Local::now().timestamp() + i64::from(minutes) * 60;
+        with_db(&app_state, |conn| {
+            reminder_service::snooze_reminder(conn, &note_name, &task, &due, until)
+        })?;
+        Ok(until)
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,83 @@
+use crate::{
+    commands::publish::load_source_notes,
+    config::get_config_notes_dir,
+    core::AppResult,
+    logging::log,
+    services::{
+        note_service::write_note_and_index,
+        rollup_service::{
+            render_rollup, rollup_note_name, summarize, template_note_name, RollupNote,
+            RollupPeriod, RollupRange, RollupSummary, DEFAULT_ROLLUP_TEMPLATE,
+        },
+    },
+    utilities::{dates::parse_date_argument, text_encoding::read_note_text},
+};
+use chrono::Local;
+use serde::Serialize;
+use std::fs;
+use std::time::UNIX_EPOCH;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RollupReport {
+    pub note_name: String,
+    pub summary: RollupSummary,
+}
+
+/// Writes the review note for the week or month containing `date` (today by default),
+/// replacing an earlier review of the same period.
+#[tauri::command]
+pub fn generate_rollup(
+    period: RollupPeriod,
+    date: Option<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<RollupReport, String> {
+    let result = || -> AppResult<RollupReport> {
+        let today = Local::now().date_naive();
+        let date = match date.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+            Some(date) => parse_date_argument(date, "date", today)?,
+            None => today,
+        };
+        let range = RollupRange::containing(period, date)?;
+
+        let notes_dir = get_config_notes_dir();
+        let notes: Vec<RollupNote> = load_source_notes(&app_state)?
+            .into_iter()
+            .map(|note| {
+                // Filesystems without birth times fall back to the last modification
+                let created = fs::metadata(notes_dir.join(&note.filename))
+                    .and_then(|metadata| metadata.created())
+                    .ok()
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .map(|duration| duration.as_secs() as i64)
+                    .unwrap_or(note.modified);
+                RollupNote {
+                    filename: note.filename,
+                    content: note.content,
+                    created,
+                    modified: note.modified,
+                }
+            })
+            .collect();
+
+        let template = read_note_text(&notes_dir.join(template_note_name(period)))
+            .unwrap_or_else(|_| DEFAULT_ROLLUP_TEMPLATE.to_string());
+        let summary = summarize(&notes, &range);
+        let content = render_rollup(&template, &range, &summary, Local::now());
+
+        let note_name = rollup_note_name(&range);
+        write_note_and_index(&app_state, &note_name, &content)?;
+        log(
+            "ROLLUP",
+            &format!(
+                "Generated {} ({} tasks, {} notes, {} highlights)",
+                note_name,
+                summary.completed_tasks.len(),
+                summary.notes_created.len(),
+                summary.highlights.len()
+            ),
+            None,
+        );
+        Ok(RollupReport { note_name, summary })
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,82 @@
+use crate::{
+    commands::note_crud::{create_note_with_content, create_untitled_note_with_content},
+    core::AppResult,
+    database::with_db,
+    services::scratchpad_service::{self, list_scratchpads, Scratchpad},
+};
+use chrono::Utc;
+
+/// Opens a new empty scratchpad: a note kept only in the database, for
+/// jotting things that may never deserve a file. Scratchpads are discarded
+/// when the app next starts unless promoted with `promote_scratchpad`.
+#[tauri::command]
+pub fn create_scratchpad(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Scratchpad, String> {
+    let now = Utc::now().timestamp();
+    with_db(&app_state, |conn| {
+        scratchpad_service::create_scratchpad(conn, now)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Scratchpads open this session, oldest first.
+#[tauri::command]
+pub fn get_scratchpads(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<Scratchpad>, String> {
+    with_db(&app_state, list_scratchpads).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn save_scratchpad(
+    name: String,
+    content: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let now = Utc::now().timestamp();
+    with_db(&app_state, |conn| {
+        scratchpad_service::save_scratchpad(conn, &name, &content, now)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Turns a scratchpad into a real note named `note_name`, or the next free
+/// untitled note when none is given, and closes the scratchpad. Returns the
+/// new note's name.
+#[tauri::command]
+pub fn promote_scratchpad(
+    name: String,
+    note_name: Option<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        let scratchpad = with_db(&app_state, |conn| {
+            scratchpad_service::get_scratchpad(conn, &name)
+        })?;
+        let note_name = match note_name.map(|name| name.trim().to_string()) {
+            Some(note_name) if !note_name.is_empty() => {
+                create_note_with_content(&note_name, &scratchpad.content, &app_state)?;
+                note_name
+            }
+            _ => create_untitled_note_with_content(None, &scratchpad.content, &app_state)?,
+        };
+        // The note exists now, so losing the scratchpad loses nothing
+        with_db(&app_state, |conn| {
+            scratchpad_service::discard_scratchpad(conn, &name)
+        })?;
+        Ok(note_name)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn discard_scratchpad(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    with_db(&app_state, |conn| {
+        scratchpad_service::discard_scratchpad(conn, &name)
+    })
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,134 @@
+use crate::{
+    commands::note_export::{ensure_exportable, load_note_content, prepare_export_path},
+    config::get_config_notes_dir,
+    core::{AppError, AppResult},
+    logging::log,
+    services::{
+        note_service::update_note_in_database,
+        path_access_service::{authorize_read, default_read_roots},
+        share_service::{
+            build_share_bundle, open_share_bundle, unique_import_name, validate_passphrase,
+            SharedNote, SHARE_BUNDLE_EXTENSION,
+        },
+    },
+    utilities::{
+        file_safety::safe_write_note, paths::get_path_grants_path, validation::validate_note_name,
+    },
+};
+use serde::Serialize;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ShareImportReport {
+    pub created: String,
+    /// Names the notes were saved under, renamed when they clashed with existing notes
+    pub imported: Vec<String>,
+    /// Nothing was written; `imported` lists the notes that would be created
+    pub dry_run: bool,
+}
+
+#[tauri::command]
+pub fn create_share_bundle(
+    note_names: Vec<String>,
+    passphrase: String,
+    output_path: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_passphrase(&passphrase)?;
+        let destination = prepare_export_path(output_path, SHARE_BUNDLE_EXTENSION)?;
+
+        let mut notes = Vec::with_capacity(note_names.len());
+        for note_name in &note_names {
+            validate_note_name(note_name)?;
+            let content = load_note_content(&app_state, note_name)?;
+            ensure_exportable(note_name, &content)?;
+            notes.push(SharedNote {
+                filename: note_name.clone(),
+                content,
+            });
+        }
+
+        let bundle = build_share_bundle(&notes, &passphrase)?;
+        fs::write(&destination, &bundle)
+            .map_err(|e| AppError::FileWrite(format!("Failed to write share bundle: {}", e)))?;
+
+        log(
+            "SHARE",
+            &format!(
+                "Created share bundle with {} notes -> {}",
+                notes.len(),
+                destination.display()
+            ),
+            None,
+        );
+        Ok(destination.to_string_lossy().to_string())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Imports every note of a share bundle, renaming on clashes. With `dry_run`, the
+/// bundle is decrypted and checked but no notes are written. Bundles outside the
+/// vault and app folders need a path grant first.
+#[tauri::command]
+pub fn import_share_bundle(
+    path: &str,
+    passphrase: String,
+    dry_run: Option<bool>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<ShareImportReport, String> {
+    let result = || -> AppResult<ShareImportReport> {
+        let bundle_path = authorize_read(path, &default_read_roots(), &get_path_grants_path()?)?;
+        let bundle = fs::read(bundle_path)
+            .map_err(|e| AppError::FileRead(format!("Failed to read share bundle: {}", e)))?;
+        let (manifest, notes) = open_share_bundle(&bundle, &passphrase)?;
+        let dry_run = dry_run.unwrap_or(false);
+
+        let notes_dir = get_config_notes_dir();
+        let modified = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        let mut imported = Vec::with_capacity(notes.len());
+        for note in notes {
+            let note_name = unique_import_name(&note.filename, |candidate| {
+                notes_dir.join(candidate).exists() || imported.iter().any(|n| n == candidate)
+            });
+            if dry_run {
+                imported.push(note_name);
+                continue;
+            }
+
+            let note_path = notes_dir.join(&note_name);
+            if let Some(parent) = note_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            super::notes::with_programmatic_flag(&app_state, || {
+                safe_write_note(&note_path, &note.content)
+            })?;
+            update_note_in_database(&app_state, &note_name, &note.content, modified)?;
+            imported.push(note_name);
+        }
+
+        if !dry_run {
+            log(
+                "SHARE",
+                &format!(
+                    "Imported {} notes from share bundle {}",
+                    imported.len(),
+                    path
+                ),
+                None,
+            );
+        }
+        Ok(ShareImportReport {
+            created: manifest.created,
+            imported,
+            dry_run,
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
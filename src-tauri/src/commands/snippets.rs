@@ -0,0 +1,59 @@
+use crate::{
+    core::{AppError, AppResult},
+    logging::log,
+    services::snippet_service::{self, expand_template, uses_variable, Snippet},
+    utilities::paths::get_snippets_path,
+};
+use std::collections::HashMap;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+#[tauri::command]
+pub fn list_snippets() -> Result<Vec<Snippet>, String> {
+    let result =
+        || -> AppResult<Vec<Snippet>> { snippet_service::load_snippets(&get_snippets_path()?) }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn save_snippet(trigger: String, body: String) -> Result<Vec<Snippet>, String> {
+    let result = || -> AppResult<Vec<Snippet>> {
+        snippet_service::save_snippet(&get_snippets_path()?, &trigger, &body)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn expand_snippet(
+    app: AppHandle,
+    trigger: String,
+    context_vars: Option<HashMap<String, String>>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        let snippets = snippet_service::load_snippets(&get_snippets_path()?)?;
+        let snippet = snippets
+            .iter()
+            .find(|snippet| snippet.trigger == trigger.trim())
+            .ok_or_else(|| AppError::validation_error("trigger", "no snippet with that trigger"))?;
+
+        let mut variables = context_vars.unwrap_or_default();
+        if !variables.contains_key("clipboard") && uses_variable(&snippet.body, "clipboard") {
+            let clipboard = app.clipboard().read_text().unwrap_or_else(|e| {
+                log(
+                    "SNIPPETS",
+                    "Failed to read clipboard for snippet expansion",
+                    Some(&e.to_string()),
+                );
+                String::new()
+            });
+            variables.insert("clipboard".to_string(), clipboard);
+        }
+
+        Ok(expand_template(
+            &snippet.body,
+            &variables,
+            chrono::Local::now(),
+        ))
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -1,23 +1,43 @@
 use crate::{
-    config::{reload_config, ConfigReloadResult},
-    database::{refresh_database_connection, with_db_mut},
+    config::{get_config_notes_dir, reload_config, ConfigReloadResult},
+    core::{
+        events::{
+            emit_event, DbLoadingComplete, DbLoadingError, DbLoadingProgress, DbLoadingStart,
+            RerenderComplete, RerenderError, RerenderProgress,
+        },
+        safe_mode::{ensure_not_safe_mode, is_safe_mode},
+        service_health::{ServiceName, ServiceStatus, SERVICES},
+        slow_operations::{SlowOperation, SLOW_OPERATIONS},
+        startup_profile::{StartupReport, STARTUP},
+        AppError, AppResult,
+    },
+    database::{refresh_database_connection, with_db, with_db_mut},
+    lan_sync,
     logging::log,
-    services::database_service::{
-        init_db, load_all_notes_into_sqlite, load_all_notes_into_sqlite_with_progress,
-        recreate_database_with_progress,
+    services::{
+        database_service::{
+            configured_fts_tokenizer, init_db, load_all_notes_into_sqlite,
+            load_all_notes_into_sqlite_with_progress, recreate_database_with_progress,
+        },
+        integrity_service::{
+            dismiss_integrity_alert as dismiss_alert, integrity_alerts, verify_notes,
+            IntegrityAlert,
+        },
+        render_service::{
+            audit_renders, mark_renders_current, notes_needing_render, rerender_notes,
+            RenderAuditFinding, RERENDER_BATCH_SIZE,
+        },
+        sync_warning_service::{last_sync_report, sync_warnings, SyncReport, SyncWarning},
     },
+    utilities::validation::validate_note_name,
 };
-use tauri::{AppHandle, Emitter};
+use chrono::Utc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::AppHandle;
 
-fn emit_with_logging<T: serde::Serialize + Clone>(app: &AppHandle, event: &str, payload: T) {
-    if let Err(e) = app.emit(event, payload) {
-        log(
-            "UI_UPDATE",
-            &format!("Failed to emit {}", event),
-            Some(&e.to_string()),
-        );
-    }
-}
+static RERENDER_RUNNING: AtomicBool = AtomicBool::new(false);
+static RERENDER_CANCELLED: AtomicBool = AtomicBool::new(false);
 
 #[tauri::command]
 pub async fn initialize_notes_with_progress(
@@ -43,10 +63,10 @@ async fn perform_notes_initialization(
 ) -> Result<(), crate::core::AppError> {
     std::thread::sleep(std::time::Duration::from_millis(50));
 
-    emit_with_logging(app, "db-loading-start", "Initializing notes database...");
+    emit_event(app, DbLoadingStart::new("Initializing notes database..."));
 
     if !crate::utilities::paths::get_config_path().exists() {
-        emit_with_logging(app, "db-loading-complete", ());
+        emit_event(app, DbLoadingComplete);
         return Ok(());
     }
 
@@ -57,12 +77,14 @@ async fn perform_notes_initialization(
     handle_initialization_result(app, result)
 }
 
-async fn perform_cache_refresh(
+/// Reloads the config, reopens the database when the notes directory changed
+/// and syncs it with the notes on disk.
+pub(crate) async fn perform_cache_refresh(
     app: &AppHandle,
     app_state: &tauri::State<'_, crate::core::state::AppState>,
 ) -> Result<(), crate::core::AppError> {
-    emit_with_logging(app, "db-loading-start", "Refreshing notes...");
-    emit_with_logging(app, "db-loading-progress", "Loading settings...");
+    emit_event(app, DbLoadingStart::new("Refreshing notes..."));
+    emit_event(app, DbLoadingProgress::new("Loading settings..."));
 
     let reload_result = handle_config_reload(app, app_state)?;
     handle_database_connection_refresh(app, app_state, reload_result)?;
@@ -74,11 +96,10 @@ async fn perform_cache_refresh(
 }
 
 fn emit_initialization_progress(app: &AppHandle) {
-    emit_with_logging(app, "db-loading-progress", "Setting up notes database...");
-    emit_with_logging(
+    emit_event(app, DbLoadingProgress::new("Setting up notes database..."));
+    emit_event(
         app,
-        "db-loading-progress",
-        "Loading notes from filesystem...",
+        DbLoadingProgress::new("Loading notes from filesystem..."),
     );
 }
 
@@ -105,12 +126,12 @@ fn handle_initialization_result(
 ) -> Result<(), crate::core::AppError> {
     match result {
         Ok(()) => {
-            emit_with_logging(app, "db-loading-complete", ());
+            emit_event(app, DbLoadingComplete);
             Ok(())
         }
         Err(e) => {
             let error_msg = format!("Failed to initialize notes database: {}", e);
-            emit_with_logging(app, "db-loading-error", &error_msg);
+            emit_event(app, DbLoadingError::new(error_msg));
             Err(e)
         }
     }
@@ -121,10 +142,9 @@ fn handle_config_reload(
     app_state: &tauri::State<'_, crate::core::state::AppState>,
 ) -> Result<ConfigReloadResult, crate::core::AppError> {
     reload_config(&app_state.config, Some(app.clone())).map_err(|e| {
-        emit_with_logging(
+        emit_event(
             app,
-            "db-loading-error",
-            format!("Failed to reload config: {}", e),
+            DbLoadingError::new(format!("Failed to reload config: {}", e)),
         );
         crate::core::AppError::ConfigLoad(e)
     })
@@ -138,24 +158,25 @@ fn handle_database_connection_refresh(
     if reload_result == ConfigReloadResult::NotesDirChanged {
         match refresh_database_connection(app_state) {
             Ok(true) => {
-                emit_with_logging(
+                emit_event(
                     app,
-                    "db-loading-progress",
-                    "Notes directory changed, database connection refreshed",
+                    DbLoadingProgress::new(
+                        "Notes directory changed, database connection refreshed",
+                    ),
                 );
             }
             Ok(false) => {
-                emit_with_logging(
+                emit_event(
                     app,
-                    "db-loading-progress",
-                    "Notes directory unchanged, continuing with existing database",
+                    DbLoadingProgress::new(
+                        "Notes directory unchanged, continuing with existing database",
+                    ),
                 );
             }
             Err(e) => {
-                emit_with_logging(
+                emit_event(
                     app,
-                    "db-loading-error",
-                    format!("Failed to refresh database connection: {}", e),
+                    DbLoadingError::new(format!("Failed to refresh database connection: {}", e)),
                 );
                 return Err(e);
             }
@@ -165,9 +186,9 @@ fn handle_database_connection_refresh(
 }
 
 fn emit_cache_refresh_progress(app: &AppHandle) {
-    emit_with_logging(app, "db-loading-progress", "Preparing notes database...");
-    emit_with_logging(app, "db-loading-progress", "Setting up notes database...");
-    emit_with_logging(app, "db-loading-progress", "Loading notes...");
+    emit_event(app, DbLoadingProgress::new("Preparing notes database..."));
+    emit_event(app, DbLoadingProgress::new("Setting up notes database..."));
+    emit_event(app, DbLoadingProgress::new("Loading notes..."));
 }
 
 async fn execute_cache_refresh_task(
@@ -176,8 +197,9 @@ async fn execute_cache_refresh_task(
     let app_state_clone = app_state.inner().clone();
 
     tokio::task::spawn_blocking(move || {
+        let tokenizer = configured_fts_tokenizer(&app_state_clone);
         with_db_mut(&app_state_clone, |conn| {
-            init_db(conn)?;
+            init_db(conn, &tokenizer)?;
             load_all_notes_into_sqlite(&app_state_clone, conn).map_err(|e| e.into())
         })
     })
@@ -192,7 +214,7 @@ async fn handle_cache_refresh_result(
 ) -> Result<(), crate::core::AppError> {
     match result {
         Ok(()) => {
-            emit_with_logging(app, "db-loading-complete", ());
+            emit_event(app, DbLoadingComplete);
             Ok(())
         }
         Err(e) => handle_cache_refresh_failure(app, app_state, e).await,
@@ -204,10 +226,9 @@ async fn handle_cache_refresh_failure(
     app_state: &tauri::State<'_, crate::core::state::AppState>,
     original_error: crate::core::AppError,
 ) -> Result<(), crate::core::AppError> {
-    emit_with_logging(
+    emit_event(
         app,
-        "db-loading-progress",
-        "Database sync failed, attempting recovery...",
+        DbLoadingProgress::new("Database sync failed, attempting recovery..."),
     );
     log(
         "DATABASE_RECOVERY",
@@ -229,9 +250,250 @@ async fn handle_cache_refresh_failure(
     });
 
     if result.is_ok() {
-        emit_with_logging(app, "db-loading-complete", ());
+        emit_event(app, DbLoadingComplete);
     } else if let Err(ref e) = result {
-        emit_with_logging(app, "db-loading-error", e.to_string());
+        emit_event(app, DbLoadingError::new(e.to_string()));
     }
     result
 }
+
+/// Regenerates stored HTML renders in the background after renderer changes,
+/// without rebuilding the database. Without `force`, only notes never rendered
+/// or rendered by an older renderer are redone. Emits `rerender-progress` after
+/// each batch, then `rerender-complete` or `rerender-error`. Returns the number
+/// of notes queued.
+#[tauri::command]
+pub fn rerender_all_notes(
+    force: bool,
+    app: AppHandle,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<usize, String> {
+    let result = || -> AppResult<usize> {
+        ensure_not_safe_mode("Background re-rendering")?;
+        if RERENDER_RUNNING.swap(true, Ordering::SeqCst) {
+            return Err(AppError::DatabaseRebuild(
+                "A re-render is already in progress".to_string(),
+            ));
+        }
+        RERENDER_CANCELLED.store(false, Ordering::SeqCst);
+
+        let filenames = match with_db(&app_state, |conn| notes_needing_render(conn, force)) {
+            Ok(filenames) => filenames,
+            Err(e) => {
+                RERENDER_RUNNING.store(false, Ordering::SeqCst);
+                return Err(e);
+            }
+        };
+        let total = filenames.len();
+        let app_state = app_state.inner().clone();
+
+        std::thread::spawn(move || {
+            let result = run_rerender(&app, &app_state, &filenames);
+            RERENDER_RUNNING.store(false, Ordering::SeqCst);
+            match result {
+                Ok(progress) => emit_event(&app, RerenderComplete(progress)),
+                Err(e) => {
+                    log(
+                        "RERENDER",
+                        "Re-rendering notes failed",
+                        Some(&e.to_string()),
+                    );
+                    emit_event(
+                        &app,
+                        RerenderError {
+                            message: e.to_string(),
+                        },
+                    );
+                }
+            }
+        });
+        Ok(total)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Stops a running `rerender_all_notes` after its current batch. Returns whether
+/// one was running.
+#[tauri::command]
+pub fn cancel_rerender() -> bool {
+    let running = RERENDER_RUNNING.load(Ordering::SeqCst);
+    if running {
+        RERENDER_CANCELLED.store(true, Ordering::SeqCst);
+    }
+    running
+}
+
+/// Lists notes whose cached HTML contains scripts, event handlers or other markup
+/// outside the renderer's allowlist, e.g. after importing an untrusted vault.
+#[tauri::command]
+pub fn audit_html_renders(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<RenderAuditFinding>, String> {
+    with_db(&app_state, audit_renders).map_err(|e| e.to_string())
+}
+
+fn run_rerender(
+    app: &AppHandle,
+    app_state: &crate::core::state::AppState,
+    filenames: &[String],
+) -> AppResult<RerenderProgress> {
+    let total = filenames.len();
+    let mut rendered = 0;
+    for batch in filenames.chunks(RERENDER_BATCH_SIZE) {
+        if RERENDER_CANCELLED.load(Ordering::SeqCst) {
+            return Ok(RerenderProgress {
+                rendered,
+                total,
+                cancelled: true,
+            });
+        }
+        rendered += with_db(app_state, |conn| rerender_notes(conn, batch))?;
+        emit_event(
+            app,
+            RerenderProgress {
+                rendered,
+                total,
+                cancelled: false,
+            },
+        );
+    }
+
+    with_db(app_state, mark_renders_current)?;
+    log(
+        "RERENDER",
+        &format!("Re-rendered {} of {} notes", rendered, total),
+        None,
+    );
+    Ok(RerenderProgress {
+        rendered,
+        total,
+        cancelled: false,
+    })
+}
+
+/// Whether the app was started with `--safe-mode` or `SYMIOSIS_SAFE_MODE`, so the
+/// UI can say why the watcher and sync are off.
+#[tauri::command]
+pub fn get_safe_mode() -> bool {
+    is_safe_mode()
+}
+
+/// Health of the watcher, sync and the other background services.
+#[tauri::command]
+pub fn get_service_status() -> Vec<ServiceStatus> {
+    SERVICES.statuses()
+}
+
+/// How long each startup phase took, to keep an eye on launch time as
+/// subsystems are added.
+#[tauri::command]
+pub fn get_startup_report() -> StartupReport {
+    STARTUP.report()
+}
+
+/// Problems syncs resolved on their own, like notes renamed because their names
+/// collided with another note's, newest first.
+#[tauri::command]
+pub fn get_sync_warnings(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<SyncWarning>, String> {
+    with_db(&app_state, sync_warnings).map_err(|e| e.to_string())
+}
+
+/// Notes whose files look corrupted or truncated compared with their last
+/// known good checksum, newest first.
+#[tauri::command]
+pub fn get_integrity_alerts(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<IntegrityAlert>, String> {
+    with_db(&app_state, integrity_alerts).map_err(|e| e.to_string())
+}
+
+/// Checks every note against its last known good checksum now instead of
+/// waiting for the scheduler, returning the alerts it raised.
+#[tauri::command]
+pub fn verify_note_integrity(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<IntegrityAlert>, String> {
+    let notes_dir = get_config_notes_dir();
+    let now = Utc::now().timestamp();
+    with_db(&app_state, |conn| verify_notes(conn, &notes_dir, now)).map_err(|e| e.to_string())
+}
+
+/// Accepts a flagged note as it is on disk, e.g. after checking it or
+/// restoring it by hand. False when the note had no alert.
+#[tauri::command]
+pub fn dismiss_integrity_alert(
+    note_name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<bool, String> {
+    let result = || -> AppResult<bool> {
+        validate_note_name(&note_name)?;
+        with_db(&app_state, |conn| dismiss_alert(conn, &note_name))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// What the latest sync of the notes directory did, including files it
+/// couldn't index and why. `None` until the first sync finished.
+#[tauri::command]
+pub fn get_last_sync_report() -> Option<SyncReport> {
+    last_sync_report()
+}
+
+/// Recent database operations that were slower than `[preferences]
+/// slow_query_ms` or timed out, newest first.
+#[tauri::command]
+pub fn get_slow_operations() -> Vec<SlowOperation> {
+    SLOW_OPERATIONS.recent()
+}
+
+/// Stops and starts a background service, e.g. to recover a stuck file watcher
+/// without restarting the app.
+#[tauri::command]
+pub fn restart_service(
+    name: &str,
+    app: AppHandle,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<ServiceStatus, String> {
+    let result = || -> AppResult<ServiceStatus> {
+        let service = ServiceName::parse(name)?;
+        if !service.is_available() {
+            return Err(AppError::validation_error(
+                "name",
+                &format!("Service '{}' is not available in this build", name),
+            ));
+        }
+        ensure_not_safe_mode("Restarting background services")?;
+
+        let app_state = Arc::new(app_state.inner().clone());
+        match service {
+            ServiceName::Watcher => {
+                crate::watcher::stop_notes_watcher();
+                crate::watcher::setup_notes_watcher(app, app_state)
+                    .map_err(|e| AppError::FileRead(format!("Failed to start watcher: {}", e)))?;
+            }
+            ServiceName::Scheduler => {
+                crate::reminders::stop();
+                crate::reminders::start(app);
+            }
+            ServiceName::Sync => {
+                lan_sync::stop();
+                lan_sync::start(app, app_state)?;
+            }
+            _ => {}
+        }
+        log(
+            "SERVICE_RESTART",
+            &format!("Restarted {}", service.as_str()),
+            None,
+        );
+
+        SERVICES
+            .statuses()
+            .into_iter()
+            .find(|status| status.name == service)
+            .ok_or_else(|| AppError::validation_error("name", "Unknown service"))
+    }();
+    result.map_err(|e| e.to_string())
+}
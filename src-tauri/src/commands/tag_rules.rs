@@ -0,0 +1,151 @@
+use crate::{
+    commands::{note_export::load_note_content, templates::configured_templates_dir},
+    core::{AppError, AppResult},
+    logging::log,
+    services::{
+        note_service::update_note_in_database,
+        tag_rule_service::{plan_tag_rules, TagRulePlan},
+        template_service::load_template,
+    },
+    utilities::{file_safety::safe_write_note, validation::validate_note_name},
+};
+use chrono::Local;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The `[automation]` plan for `note_name` saved as `content`, with moves onto
+/// an existing note skipped.
+fn plan_for_note(
+    app_state: &crate::core::state::AppState,
+    note_name: &str,
+    previous: Option<&str>,
+    content: &str,
+) -> TagRulePlan {
+    let (rules, notes_dir) = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        (
+            config.automation.rules.clone(),
+            PathBuf::from(&config.notes_directory),
+        )
+    };
+    if rules.is_empty() {
+        return TagRulePlan {
+            note_name: note_name.to_string(),
+            ..Default::default()
+        };
+    }
+
+    let templates = configured_templates_dir(app_state);
+    let mut plan = plan_tag_rules(
+        &rules,
+        note_name,
+        previous,
+        content,
+        |name| load_template(&templates, name),
+        Local::now(),
+    );
+    if let Some(new_name) = plan.new_name.clone() {
+        if let Err(e) = validate_move_target(&notes_dir, &new_name) {
+            plan.cancel_move(e.to_string());
+        }
+    }
+    plan
+}
+
+fn validate_move_target(notes_dir: &Path, new_name: &str) -> AppResult<()> {
+    validate_note_name(new_name)?;
+    if notes_dir.join(new_name).exists() {
+        return Err(AppError::InvalidNoteName(format!(
+            "Note '{}' already exists",
+            new_name
+        )));
+    }
+    Ok(())
+}
+
+/// Runs the `[automation]` rules for a note that was just saved as `content`
+/// over `previous`. Returns what was done, or `None` when no rule fired.
+/// Failures are logged rather than returned, since the save itself succeeded.
+pub(crate) fn apply_tag_rules(
+    app_state: &crate::core::state::AppState,
+    note_name: &str,
+    previous: Option<&str>,
+    content: &str,
+) -> Option<TagRulePlan> {
+    let plan = plan_for_note(app_state, note_name, previous, content);
+    if plan.rules.is_empty() {
+        return None;
+    }
+
+    match carry_out_plan(app_state, &plan) {
+        Ok(()) => {
+            log(
+                "TAG_RULES",
+                &format!(
+                    "Applied {} rule action(s) to {} -> {}",
+                    plan.rules.len(),
+                    note_name,
+                    plan.final_name()
+                ),
+                None,
+            );
+            Some(plan)
+        }
+        Err(e) => {
+            log(
+                "TAG_RULES",
+                &format!("Failed to apply tag rules to {}", note_name),
+                Some(&e.to_string()),
+            );
+            None
+        }
+    }
+}
+
+fn carry_out_plan(app_state: &crate::core::state::AppState, plan: &TagRulePlan) -> AppResult<()> {
+    if let Some(content) = &plan.content {
+        let path = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            PathBuf::from(&config.notes_directory).join(&plan.note_name)
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        super::notes::with_programmatic_flag(app_state, || safe_write_note(&path, content))?;
+        update_note_in_database(app_state, &plan.note_name, content, now)?;
+    }
+
+    // Moved like any other rename: backed up, never over an existing note,
+    // and rolled back if the index can't follow
+    if let Some(new_name) = &plan.new_name {
+        super::note_crud::rename_note_file(&plan.note_name, new_name, app_state)?;
+    }
+    Ok(())
+}
+
+/// Shows what the `[automation]` rules would do if `note_name` were saved as
+/// `content`, without changing anything. Without `content`, the note's current
+/// content is checked as if the note were new, so every rule for one of its
+/// tags fires.
+#[tauri::command]
+pub fn preview_tag_rules(
+    note_name: &str,
+    content: Option<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<TagRulePlan, String> {
+    let result = || -> AppResult<TagRulePlan> {
+        validate_note_name(note_name)?;
+        let (previous, content) = match content {
+            Some(content) => (load_note_content(&app_state, note_name).ok(), content),
+            None => (None, load_note_content(&app_state, note_name)?),
+        };
+        Ok(plan_for_note(
+            &app_state,
+            note_name,
+            previous.as_deref(),
+            &content,
+        ))
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,150 @@
+use crate::{
+    commands::note_crud::perform_safe_write_and_update,
+    core::{
+        events::{emit_event, CacheRefreshed},
+        AppError, AppResult,
+    },
+    database::with_db,
+    logging::log,
+    services::{
+        derived_index_service::refresh_index,
+        replace_service::{FileReplacement, ReplaceReport},
+        tag_service::{self, notes_with_tags, prepare_retag, TagNode, NOTE_TAGS_INDEX},
+    },
+    utilities::{
+        file_safety::{create_versioned_backup, BackupType},
+        tags::retag_content,
+        text_encoding::read_note_text,
+    },
+};
+use std::path::{Path, PathBuf};
+
+/// Every tag with its note count, for the tag sidebar.
+#[tauri::command]
+pub fn list_all_tags(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<TagNode>, String> {
+    with_db(&app_state, tag_service::list_all_tags).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn search_notes_by_tag(
+    tag: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<String>, String> {
+    with_db(&app_state, |conn| {
+        tag_service::search_notes_by_tag(conn, tag)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Renames `old` to `new` in every note, including tags nested under it
+/// (`#old/sub` becomes `#new/sub`). Renaming onto an existing tag merges them.
+#[tauri::command]
+pub fn rename_tag(
+    old: &str,
+    new: &str,
+    app: tauri::AppHandle,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<ReplaceReport, String> {
+    retag_notes(&[old.to_string()], new, &app, &app_state).map_err(|e| e.to_string())
+}
+
+/// Rewrites each of `tags` (and tags nested under them) to `target` in every
+/// note, dropping the duplicates this leaves in front matter.
+#[tauri::command]
+pub fn merge_tags(
+    tags: Vec<String>,
+    target: &str,
+    app: tauri::AppHandle,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<ReplaceReport, String> {
+    retag_notes(&tags, target, &app, &app_state).map_err(|e| e.to_string())
+}
+
+/// Backs up and rewrites each tagged note, reporting notes that fail while the
+/// rest proceed, then refreshes the tag index and emits one `cache-refreshed`
+/// event for all rewritten notes.
+fn retag_notes(
+    sources: &[String],
+    target: &str,
+    app: &tauri::AppHandle,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<ReplaceReport> {
+    let (sources, target) = prepare_retag(sources, target)?;
+    let candidates = with_db(app_state, |conn| notes_with_tags(conn, &sources))?;
+    let notes_dir = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        PathBuf::from(&config.notes_directory)
+    };
+
+    let mut report = ReplaceReport::default();
+    for filename in &candidates {
+        match retag_note(&notes_dir, filename, &sources, &target, app_state) {
+            Ok(Some(file)) => report.push(file),
+            Ok(None) => {}
+            Err(e) => report.push(FileReplacement {
+                filename: filename.clone(),
+                replacements: 0,
+                diff: None,
+                backup_path: None,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    with_db(app_state, |conn| refresh_index(conn, &NOTE_TAGS_INDEX))?;
+    let changed: Vec<String> = report
+        .files
+        .iter()
+        .filter(|file| file.error.is_none())
+        .map(|file| file.filename.clone())
+        .collect();
+    log(
+        "TAGS",
+        &format!(
+            "#{} -> #{}: {} tag(s) in {} note(s)",
+            sources.join(", #"),
+            target,
+            report.total_replacements,
+            changed.len()
+        ),
+        None,
+    );
+    if !changed.is_empty() {
+        emit_event(
+            app,
+            CacheRefreshed {
+                changed,
+                removed: Vec::new(),
+            },
+        );
+    }
+    Ok(report)
+}
+
+/// Rewrites one note from its file on disk, which may be newer than the index.
+fn retag_note(
+    notes_dir: &Path,
+    filename: &str,
+    sources: &[String],
+    target: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<Option<FileReplacement>> {
+    let note_path = notes_dir.join(filename);
+    let content = read_note_text(&note_path)
+        .map_err(|e| AppError::FileRead(format!("Failed to read '{}': {}", filename, e)))?;
+    let Some((retagged, count)) = retag_content(&content, sources, target) else {
+        return Ok(None);
+    };
+
+    let backup_path = create_versioned_backup(&note_path, BackupType::TagRename, Some(&content))?;
+    perform_safe_write_and_update(&note_path, &retagged, filename, app_state)?;
+    Ok(Some(FileReplacement {
+        filename: filename.to_string(),
+        replacements: count,
+        diff: None,
+        backup_path: Some(backup_path.to_string_lossy().to_string()),
+        error: None,
+    }))
+}
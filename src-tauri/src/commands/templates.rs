@@ -0,0 +1,40 @@
+use crate::{
+    commands::note_crud::create_note_with_content,
+    core::AppResult,
+    services::template_service::{self, load_template, render_template, templates_dir},
+};
+use chrono::Local;
+
+pub(crate) fn configured_templates_dir(
+    app_state: &crate::core::state::AppState,
+) -> std::path::PathBuf {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    templates_dir(
+        std::path::Path::new(&config.notes_directory),
+        &config.templates.directory,
+    )
+}
+
+#[tauri::command]
+pub fn list_templates(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<String>, String> {
+    template_service::list_templates(&configured_templates_dir(&app_state))
+        .map_err(|e| e.to_string())
+}
+
+/// Creates `note_name` from `template`, filling in `{{title}}`, `{{date}}`,
+/// `{{time}}` and `{{datetime}}`.
+#[tauri::command]
+pub fn create_note_from_template(
+    template: &str,
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        let body = load_template(&configured_templates_dir(&app_state), template)?;
+        let content = render_template(&body, note_name, Local::now());
+        create_note_with_content(note_name, &content, &app_state)
+    }();
+    result.map_err(|e| e.to_string())
+}
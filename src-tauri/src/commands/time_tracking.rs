@@ -0,0 +1,17 @@
+use crate::{
+    database::with_db,
+    services::time_tracking_service::{time_report, TimeGroupBy, TimeReport},
+    utilities::dates::DateRange,
+};
+
+/// Totals `⏱ 2024-06-01 09:00-10:30 #tag` entries within a date range, grouped
+/// by tag, project, note or day.
+#[tauri::command]
+pub fn get_time_report(
+    range: Option<DateRange>,
+    group_by: TimeGroupBy,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<TimeReport, String> {
+    let range = range.unwrap_or_default();
+    with_db(&app_state, |conn| time_report(conn, &range, group_by)).map_err(|e| e.to_string())
+}
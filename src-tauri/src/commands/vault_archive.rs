@@ -0,0 +1,170 @@
+use crate::{
+    commands::note_export::prepare_export_path,
+    config::get_config_notes_dir,
+    core::{AppError, AppResult},
+    database::with_db,
+    logging::log,
+    services::{
+        note_service::update_note_in_database,
+        path_access_service::{authorize_read, default_read_roots},
+        share_service::SharedNote,
+        vault_archive_service::{build_vault_archive, open_vault_archive, VAULT_ARCHIVE_EXTENSION},
+    },
+    utilities::{
+        dry_run::{DryRunReport, PlannedAction},
+        file_safety::{
+            create_versioned_backup, safe_write_note, versioned_backup_path, BackupType,
+        },
+        paths::get_path_grants_path,
+        text_encoding::read_note_text,
+    },
+};
+use serde::Serialize;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultRestoreReport {
+    pub created: String,
+    /// Notes written from the archive, overwritten ones backed up first
+    pub restored: Vec<String>,
+    /// Notes whose vault copy already matches the archive
+    pub unchanged: Vec<String>,
+}
+
+/// What `restore_vault_archive` did or, with `dry_run`, what it would do.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum VaultRestore {
+    Restored(VaultRestoreReport),
+    Planned(DryRunReport),
+}
+
+/// Writes every note of the vault to a `.symvault` archive. With a passphrase
+/// the notes are age-encrypted, so the copy can be kept off the device.
+#[tauri::command]
+pub fn export_vault_archive(
+    output_path: &str,
+    passphrase: Option<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        let destination = prepare_export_path(output_path, VAULT_ARCHIVE_EXTENSION)?;
+        let notes = with_db(&app_state, |conn| {
+            let mut stmt = conn.prepare("SELECT filename, content FROM notes ORDER BY filename")?;
+            let rows = stmt.query_map([], |row| {
+                Ok(SharedNote {
+                    filename: row.get(0)?,
+                    content: row.get(1)?,
+                })
+            })?;
+            Ok(rows.collect::<Result<Vec<_>, _>>()?)
+        })?;
+
+        let archive = build_vault_archive(&notes, passphrase.as_deref())?;
+        fs::write(&destination, &archive)
+            .map_err(|e| AppError::FileWrite(format!("Failed to write vault archive: {}", e)))?;
+
+        log(
+            "VAULT_ARCHIVE",
+            &format!(
+                "Exported {} notes{} -> {}",
+                notes.len(),
+                if passphrase.is_some() {
+                    " encrypted"
+                } else {
+                    ""
+                },
+                destination.display()
+            ),
+            None,
+        );
+        Ok(destination.to_string_lossy().to_string())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Restores the notes of a vault archive into the open vault, decrypting it
+/// with `passphrase` when it was exported with one. Notes that differ are
+/// backed up before being overwritten; notes missing from the archive are
+/// left alone. With `dry_run`, the archive is opened and compared but nothing
+/// is written; the notes it would create or overwrite and the backups it would
+/// take are returned instead. Archives outside the vault and app folders need
+/// a path grant.
+#[tauri::command]
+pub fn restore_vault_archive(
+    path: &str,
+    passphrase: Option<String>,
+    dry_run: Option<bool>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<VaultRestore, String> {
+    let result = || -> AppResult<VaultRestore> {
+        let archive_path = authorize_read(path, &default_read_roots(), &get_path_grants_path()?)?;
+        let archive = fs::read(archive_path)
+            .map_err(|e| AppError::FileRead(format!("Failed to read vault archive: {}", e)))?;
+        let (manifest, notes) = open_vault_archive(&archive, passphrase.as_deref())?;
+        let notes_dir = get_config_notes_dir();
+
+        if dry_run.unwrap_or(false) {
+            let mut plan = DryRunReport::default();
+            for note in &notes {
+                let note_path = notes_dir.join(&note.filename);
+                if !note_path.exists() {
+                    plan.plan(PlannedAction::Create, &note.filename);
+                } else if read_note_text(&note_path).ok().as_deref() != Some(note.content.as_str())
+                {
+                    plan.plan(PlannedAction::Overwrite, &note.filename);
+                    plan.backups.push(
+                        versioned_backup_path(&note_path, &BackupType::Restore)?
+                            .to_string_lossy()
+                            .to_string(),
+                    );
+                }
+            }
+            return Ok(VaultRestore::Planned(plan));
+        }
+
+        let modified = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        let mut report = VaultRestoreReport {
+            created: manifest.created,
+            restored: Vec::new(),
+            unchanged: Vec::new(),
+        };
+        for note in notes {
+            let note_path = notes_dir.join(&note.filename);
+            let exists = note_path.exists();
+            if exists && read_note_text(&note_path).ok().as_deref() == Some(note.content.as_str()) {
+                report.unchanged.push(note.filename);
+                continue;
+            }
+
+            if exists {
+                create_versioned_backup(&note_path, BackupType::Restore, None)?;
+            } else if let Some(parent) = note_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            super::notes::with_programmatic_flag(&app_state, || {
+                safe_write_note(&note_path, &note.content)
+            })?;
+            update_note_in_database(&app_state, &note.filename, &note.content, modified)?;
+            report.restored.push(note.filename);
+        }
+
+        log(
+            "VAULT_ARCHIVE",
+            &format!(
+                "Restored {} notes from {} ({} unchanged)",
+                report.restored.len(),
+                path,
+                report.unchanged.len()
+            ),
+            None,
+        );
+        Ok(VaultRestore::Restored(report))
+    }();
+    result.map_err(|e| e.to_string())
+}
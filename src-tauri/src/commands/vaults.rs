@@ -0,0 +1,181 @@
+use crate::{
+    commands::system::perform_cache_refresh,
+    config::{load_config, save_config},
+    core::{
+        events::{emit_event, VaultSwitched},
+        safe_mode::is_safe_mode,
+        AppError, AppResult,
+    },
+    logging::log,
+    services::vault_service::{self, find_vault, known_vaults, Vault},
+    watcher::{setup_notes_watcher, stop_notes_watcher},
+};
+use std::path::Path;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+/// Every vault in `[[vaults]]`, the open notes directory marked `active`.
+#[tauri::command]
+pub fn list_vaults(app_state: tauri::State<crate::core::state::AppState>) -> Vec<Vault> {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    vault_service::list_vaults(&config.vaults, &config.notes_directory)
+}
+
+/// Asks the user to pick a folder in a native dialog and adds it to
+/// `[[vaults]]` without opening it, named `name` or after the folder. The
+/// webview can't name the path itself, so it can't open the app onto a folder
+/// the user never chose. Returns `None` when the dialog is cancelled.
+#[tauri::command]
+pub async fn add_vault(
+    name: Option<String>,
+    app: AppHandle,
+    app_state: tauri::State<'_, crate::core::state::AppState>,
+) -> Result<Option<Vault>, String> {
+    let picked = app
+        .dialog()
+        .file()
+        .set_title("Add a vault")
+        .blocking_pick_folder();
+    let Some(picked) = picked else {
+        return Ok(None);
+    };
+
+    let result = || -> AppResult<Vault> {
+        let path = picked
+            .into_path()
+            .map_err(|e| AppError::InvalidPath(format!("Unsupported path: {}", e)))?;
+        let path = path.to_string_lossy();
+        // Start from the file so edits not yet reloaded aren't lost
+        let mut config = load_config();
+        config.vaults = vault_service::add_vault(
+            &config.vaults,
+            &config.notes_directory,
+            &path,
+            name.as_deref(),
+        )?;
+        save_config(&config)?;
+
+        let added = config
+            .vaults
+            .last()
+            .cloned()
+            .ok_or_else(|| AppError::ConfigSave("Vault was not added".to_string()))?;
+        app_state
+            .config
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .vaults = config.vaults;
+
+        log(
+            "VAULTS",
+            &format!("Added vault '{}' at {}", added.name, added.path),
+            None,
+        );
+        Ok(Vault {
+            name: added.name,
+            path: added.path,
+            active: false,
+        })
+    }();
+    result.map(Some).map_err(|e| e.to_string())
+}
+
+/// Opens the vault called `name`: its notes directory becomes
+/// `notes_directory`, the file watcher and database connection are torn down
+/// and re-created for it, and its notes are synced. Emits the usual
+/// `db-loading-*` progress, then `vault-switched`. When the new vault can't
+/// be opened, the one that was open is restored.
+#[tauri::command]
+pub async fn switch_vault(
+    name: String,
+    app: AppHandle,
+    app_state: tauri::State<'_, crate::core::state::AppState>,
+) -> Result<Vault, String> {
+    let result = perform_vault_switch(&name, &app, &app_state).await;
+    result.map_err(|e| e.to_string())
+}
+
+async fn perform_vault_switch(
+    name: &str,
+    app: &AppHandle,
+    app_state: &tauri::State<'_, crate::core::state::AppState>,
+) -> AppResult<Vault> {
+    let mut config = load_config();
+    let vault = find_vault(&config.vaults, &config.notes_directory, name)?;
+    let path = vault.path;
+
+    let open_dir = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .notes_directory
+        .clone();
+    if Path::new(&open_dir) == Path::new(&path) {
+        return Ok(Vault {
+            name: vault.name,
+            path,
+            active: true,
+        });
+    }
+
+    let previous = config.clone();
+    // The vault being left stays listed so it can be switched back to
+    config.vaults = known_vaults(&config.vaults, &config.notes_directory);
+    config.notes_directory = path.clone();
+    save_config(&config)?;
+
+    // Events from the old vault mustn't reach the new vault's database
+    stop_notes_watcher();
+    if let Err(e) = perform_cache_refresh(app, app_state).await {
+        log(
+            "VAULTS",
+            &format!(
+                "Failed to open vault '{}', going back to {}",
+                vault.name, open_dir
+            ),
+            Some(&e.to_string()),
+        );
+        // Put the config, database and watcher back on the vault that was open
+        save_config(&previous)?;
+        if let Err(restore_error) = perform_cache_refresh(app, app_state).await {
+            log(
+                "VAULTS",
+                &format!("Failed to reopen {}", open_dir),
+                Some(&restore_error.to_string()),
+            );
+        }
+        restart_notes_watcher(app, app_state)?;
+        return Err(e);
+    }
+    restart_notes_watcher(app, app_state)?;
+
+    log(
+        "VAULTS",
+        &format!("Switched to vault '{}' at {}", vault.name, path),
+        None,
+    );
+    emit_event(
+        app,
+        VaultSwitched {
+            name: vault.name.clone(),
+            path: path.clone(),
+        },
+    );
+    Ok(Vault {
+        name: vault.name,
+        path,
+        active: true,
+    })
+}
+
+fn restart_notes_watcher(
+    app: &AppHandle,
+    app_state: &tauri::State<'_, crate::core::state::AppState>,
+) -> AppResult<()> {
+    if is_safe_mode() {
+        return Ok(());
+    }
+    setup_notes_watcher(app.clone(), Arc::new(app_state.inner().clone()))
+        .map_err(|e| AppError::FileRead(format!("Failed to start watcher: {}", e)))
+}
@@ -0,0 +1,44 @@
+use crate::{
+    commands::note_export::{ensure_exportable, load_note_content},
+    core::AppResult,
+    logging::log,
+    services::webhook_service::{format_note_messages, post_messages, ChannelTarget},
+    utilities::validation::validate_note_name,
+};
+
+/// Posts `note_name` to the `slack` or `discord` webhook from `[webhooks]`,
+/// converted to that platform's formatting. Long notes go out as several
+/// messages; private notes are refused. Returns the number of messages sent.
+#[tauri::command]
+pub fn send_note_to_channel(
+    note_name: &str,
+    target: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<usize, String> {
+    let result = || -> AppResult<usize> {
+        validate_note_name(note_name)?;
+        let target = ChannelTarget::parse(target)?;
+        let url = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            target.webhook_url(&config.webhooks)?.to_string()
+        };
+
+        let content = load_note_content(&app_state, note_name)?;
+        ensure_exportable(note_name, &content)?;
+        let messages = format_note_messages(target, note_name, &content);
+        post_messages(target, &url, &messages)?;
+
+        log(
+            "WEBHOOK",
+            &format!(
+                "Sent {} to {} in {} message(s)",
+                note_name,
+                target.name(),
+                messages.len()
+            ),
+            None,
+        );
+        Ok(messages.len())
+    }();
+    result.map_err(|e| e.to_string())
+}
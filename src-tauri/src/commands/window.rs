@@ -49,3 +49,43 @@ pub fn hide_main_window(app: AppHandle) -> Result<(), String> {
     }();
     result.map_err(|e| e.to_string())
 }
+
+pub const CAPTURE_WINDOW_LABEL: &str = "capture";
+
+/// Shows the quick capture window, creating it on first use.
+#[tauri::command]
+pub fn show_capture_window(app: AppHandle) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        let window = match app.get_webview_window(CAPTURE_WINDOW_LABEL) {
+            Some(window) => window,
+            None => WebviewWindowBuilder::new(
+                &app,
+                CAPTURE_WINDOW_LABEL,
+                WebviewUrl::App("capture".into()),
+            )
+            .title("Quick Capture")
+            .inner_size(520.0, 160.0)
+            .center()
+            .resizable(false)
+            .decorations(false)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .build()?,
+        };
+        window.show()?;
+        window.set_focus()?;
+        Ok(())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn hide_capture_window(app: AppHandle) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        if let Some(window) = app.get_webview_window(CAPTURE_WINDOW_LABEL) {
+            window.hide()?;
+        }
+        Ok(())
+    }();
+    result.map_err(|e| e.to_string())
+}
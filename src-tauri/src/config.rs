@@ -1,6 +1,9 @@
+use crate::core::events::{emit_event, ConfigUpdated};
 use crate::core::{AppError, AppResult};
 use crate::logging::log;
-use crate::utilities::config_helpers::{default_global_shortcut, default_window_decorations};
+use crate::utilities::config_helpers::{
+    default_global_shortcut, default_markdown_extension, default_window_decorations,
+};
 
 pub use crate::utilities::config_helpers::{
     get_available_markdown_themes, get_available_ui_themes, load_config_from_content,
@@ -8,9 +11,10 @@ pub use crate::utilities::config_helpers::{
 };
 use crate::utilities::paths::{get_config_path, get_default_notes_dir};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConfigReloadResult {
@@ -38,6 +42,47 @@ pub struct AppConfig {
 
     #[serde(default)]
     pub preferences: PreferencesConfig,
+
+    #[serde(default)]
+    pub lint: LintConfig,
+
+    #[serde(default)]
+    pub citations: CitationsConfig,
+
+    #[serde(default)]
+    pub sync: SyncConfig,
+
+    #[serde(default)]
+    pub habits: HabitsConfig,
+
+    #[serde(default)]
+    pub templates: TemplatesConfig,
+
+    #[serde(default)]
+    pub metadata: MetadataConfig,
+
+    #[serde(default)]
+    pub capture: CaptureConfig,
+
+    #[serde(default)]
+    pub email: EmailConfig,
+
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
+
+    #[serde(default)]
+    pub geo: GeoConfig,
+
+    #[serde(default)]
+    pub automation: AutomationConfig,
+
+    #[serde(default)]
+    pub reminders: RemindersConfig,
+
+    /// `[[vaults]]`, notes directories the app can switch between; the open
+    /// one is `notes_directory`
+    #[serde(default)]
+    pub vaults: Vec<VaultConfig>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -60,6 +105,16 @@ pub struct InterfaceConfig {
     pub window_decorations: bool,
     pub custom_ui_theme_path: Option<String>,
     pub custom_markdown_theme_path: Option<String>,
+    #[serde(default = "default_markdown_extension")]
+    pub markdown_tables: bool,
+    #[serde(default = "default_markdown_extension")]
+    pub markdown_task_lists: bool,
+    #[serde(default = "default_markdown_extension")]
+    pub markdown_footnotes: bool,
+    #[serde(default = "default_markdown_extension")]
+    pub markdown_strikethrough: bool,
+    /// mermaid-cli (`mmdc`) executable that pre-renders ```mermaid blocks
+    pub mermaid_cli: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -92,6 +147,213 @@ pub struct ShortcutsConfig {
 pub struct PreferencesConfig {
     #[serde(default = "default_max_results")]
     pub max_search_results: usize,
+    /// Remember old filenames on rename so stale links still open the note
+    #[serde(default = "default_true")]
+    pub track_rename_history: bool,
+    /// Days deleted notes stay in the trash before being purged; 0 keeps them
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Queue notes changed outside the app for review instead of only
+    /// updating the index silently
+    #[serde(default)]
+    pub review_external_changes: bool,
+    /// FTS5 tokenizer of the search index; changing it rebuilds the index
+    #[serde(default = "default_fts_tokenizer")]
+    pub fts_tokenizer: String,
+    /// Milliseconds a database operation may run before it's interrupted; 0
+    /// disables the timeout
+    #[serde(default = "default_db_timeout_ms")]
+    pub db_timeout_ms: u64,
+    /// Database operations slower than this many milliseconds are logged; 0
+    /// disables logging
+    #[serde(default = "default_slow_query_ms")]
+    pub slow_query_ms: u64,
+    /// Append the notes linking to a note, with the lines mentioning it, below
+    /// its rendered view
+    #[serde(default)]
+    pub show_linked_mentions: bool,
+    /// Where new notes are created: "inbox" (`inbox_folder`), "same_folder"
+    /// (the folder of the open note) or "root"
+    #[serde(default = "default_new_note_location")]
+    pub new_note_location: String,
+    #[serde(default = "default_inbox_folder")]
+    pub inbox_folder: String,
+    /// Line endings notes are saved with: "preserve" (whatever the note
+    /// already uses), "lf" or "crlf"
+    #[serde(default = "default_line_endings")]
+    pub line_endings: String,
+    /// Hours between checks of note files against their last known good
+    /// checksums; 0 disables the checks
+    #[serde(default = "default_integrity_check_hours")]
+    pub integrity_check_hours: u32,
+    /// What the scheduler does with notes past their `expires` date: "flag"
+    /// (notify once) or "archive" (notify and mark them `archived`)
+    #[serde(default = "default_expired_notes")]
+    pub expired_notes: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LintConfig {
+    #[serde(default = "default_true")]
+    pub check_broken_links: bool,
+    #[serde(default = "default_true")]
+    pub check_duplicate_headings: bool,
+    #[serde(default = "default_true")]
+    pub check_trailing_whitespace: bool,
+    /// Lines longer than this are reported; 0 disables the check
+    #[serde(default = "default_max_line_length")]
+    pub max_line_length: usize,
+    #[serde(default)]
+    pub required_frontmatter_fields: Vec<String>,
+    /// Expected type of front-matter fields by key, checked by `lint_vault`:
+    /// "text", "list", "number", "date" or "boolean"
+    #[serde(default)]
+    pub field_types: BTreeMap<String, String>,
+    /// Regex every note's file stem must match; empty disables the check
+    #[serde(default)]
+    pub filename_pattern: String,
+    /// Regex every front-matter key must match; empty disables the check
+    #[serde(default)]
+    pub field_name_pattern: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CitationsConfig {
+    /// BibTeX (.bib) or CSL JSON (.json) file; relative paths resolve against the notes directory
+    #[serde(default)]
+    pub bibliography_file: String,
+    #[serde(default = "default_references_heading")]
+    pub references_heading: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SyncConfig {
+    /// Keep a CRDT change log for every saved note
+    #[serde(default)]
+    pub crdt_enabled: bool,
+    /// Advertise this vault over mDNS and accept sync from paired devices
+    #[serde(default)]
+    pub lan_enabled: bool,
+    #[serde(default = "default_lan_port")]
+    pub lan_port: u16,
+    /// Name shown to other devices; empty uses the host name
+    #[serde(default)]
+    pub device_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HabitsConfig {
+    /// Checkbox texts tracked in daily notes, e.g. "Exercise"
+    #[serde(default)]
+    pub habits: Vec<String>,
+    /// Only notes in this folder count as daily notes; empty allows any folder
+    #[serde(default)]
+    pub daily_notes_folder: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TemplatesConfig {
+    /// Folder holding note templates; relative paths resolve against the notes
+    /// directory and empty uses `.templates` inside it
+    #[serde(default)]
+    pub directory: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MetadataConfig {
+    /// Front-matter fields usable as search filters and sort keys, e.g. "status"
+    #[serde(default)]
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CaptureConfig {
+    /// Global shortcut opening the quick capture window; empty disables it
+    #[serde(default = "default_capture_shortcut")]
+    pub shortcut: String,
+    /// Note captured text is appended to
+    #[serde(default = "default_inbox_note")]
+    pub inbox_note: String,
+    /// Global shortcut searching the notes for the clipboard text; empty
+    /// disables it
+    #[serde(default)]
+    pub clipboard_search_shortcut: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmailConfig {
+    /// SMTP server notes are sent through; empty disables sending
+    #[serde(default)]
+    pub smtp_host: String,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    /// "starttls", "tls" or "none"
+    #[serde(default = "default_smtp_security")]
+    pub security: String,
+    /// SMTP login; its password is kept in the system keychain
+    #[serde(default)]
+    pub username: String,
+    /// Sender address; empty uses `username`
+    #[serde(default)]
+    pub from_address: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WebhooksConfig {
+    /// Slack incoming-webhook URL; empty disables the `slack` target
+    #[serde(default)]
+    pub slack_url: String,
+    /// Discord channel webhook URL; empty disables the `discord` target
+    #[serde(default)]
+    pub discord_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GeoConfig {
+    /// Service used to turn `location:` place names into coordinates: `""`
+    /// (place names are not geocoded) or `"nominatim"`
+    #[serde(default)]
+    pub geocoder: String,
+    /// Self-hosted geocoder base URL; empty uses the public Nominatim server
+    #[serde(default)]
+    pub geocoder_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AutomationConfig {
+    /// `[[automation.rules]]`, applied in order when a note gains a tag
+    #[serde(default)]
+    pub rules: Vec<TagRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemindersConfig {
+    /// Show a desktop notification when an open task comes due
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Local time of day (`HH:MM`) reminders go off on their due date
+    #[serde(default = "default_reminder_time")]
+    pub notify_time: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TagRule {
+    /// Normalized tag without `#`; tags nested under it trigger the rule too
+    pub tag: String,
+    /// Folder the note is moved into; empty leaves it where it is
+    #[serde(default)]
+    pub move_to: String,
+    /// Template appended to the note; empty appends nothing
+    #[serde(default)]
+    pub append_template: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct VaultConfig {
+    /// Unique name shown in the vault switcher
+    pub name: String,
+    /// Notes directory of the vault, with its own database
+    pub path: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -108,10 +370,82 @@ fn default_max_results() -> usize {
     crate::utilities::config_helpers::default_max_results()
 }
 
+fn default_capture_shortcut() -> String {
+    "Ctrl+Alt+N".to_string()
+}
+
+fn default_inbox_note() -> String {
+    "Inbox.md".to_string()
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_security() -> String {
+    "starttls".to_string()
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_integrity_check_hours() -> u32 {
+    24
+}
+
+fn default_expired_notes() -> String {
+    crate::utilities::config_helpers::DEFAULT_EXPIRED_NOTES.to_string()
+}
+
+fn default_fts_tokenizer() -> String {
+    crate::utilities::config_helpers::DEFAULT_FTS_TOKENIZER.to_string()
+}
+
+fn default_new_note_location() -> String {
+    crate::utilities::config_helpers::DEFAULT_NEW_NOTE_LOCATION.to_string()
+}
+
+fn default_inbox_folder() -> String {
+    crate::utilities::config_helpers::DEFAULT_INBOX_FOLDER.to_string()
+}
+
+fn default_line_endings() -> String {
+    crate::utilities::text_encoding::DEFAULT_LINE_ENDING_MODE.to_string()
+}
+
+fn default_db_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_slow_query_ms() -> u64 {
+    500
+}
+
 fn default_scroll_amount() -> f64 {
     0.4
 }
 
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_line_length() -> usize {
+    120
+}
+
+fn default_references_heading() -> String {
+    "References".to_string()
+}
+
+fn default_lan_port() -> u16 {
+    47321
+}
+
+fn default_reminder_time() -> String {
+    "09:00".to_string()
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -122,6 +456,19 @@ impl Default for AppConfig {
             editor: EditorConfig::default(),
             shortcuts: ShortcutsConfig::default(),
             preferences: PreferencesConfig::default(),
+            lint: LintConfig::default(),
+            citations: CitationsConfig::default(),
+            sync: SyncConfig::default(),
+            habits: HabitsConfig::default(),
+            templates: TemplatesConfig::default(),
+            metadata: MetadataConfig::default(),
+            capture: CaptureConfig::default(),
+            email: EmailConfig::default(),
+            webhooks: WebhooksConfig::default(),
+            geo: GeoConfig::default(),
+            automation: AutomationConfig::default(),
+            reminders: RemindersConfig::default(),
+            vaults: Vec::new(),
         }
     }
 }
@@ -148,6 +495,11 @@ impl Default for InterfaceConfig {
             window_decorations: default_window_decorations(),
             custom_ui_theme_path: None,
             custom_markdown_theme_path: None,
+            markdown_tables: default_markdown_extension(),
+            markdown_task_lists: default_markdown_extension(),
+            markdown_footnotes: default_markdown_extension(),
+            markdown_strikethrough: default_markdown_extension(),
+            mermaid_cli: None,
         }
     }
 }
@@ -185,6 +537,84 @@ impl Default for PreferencesConfig {
     fn default() -> Self {
         Self {
             max_search_results: default_max_results(),
+            track_rename_history: true,
+            trash_retention_days: default_trash_retention_days(),
+            review_external_changes: false,
+            fts_tokenizer: default_fts_tokenizer(),
+            db_timeout_ms: default_db_timeout_ms(),
+            slow_query_ms: default_slow_query_ms(),
+            show_linked_mentions: false,
+            new_note_location: default_new_note_location(),
+            inbox_folder: default_inbox_folder(),
+            line_endings: default_line_endings(),
+            integrity_check_hours: default_integrity_check_hours(),
+            expired_notes: default_expired_notes(),
+        }
+    }
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            check_broken_links: true,
+            check_duplicate_headings: true,
+            check_trailing_whitespace: true,
+            max_line_length: default_max_line_length(),
+            required_frontmatter_fields: Vec::new(),
+            field_types: BTreeMap::new(),
+            filename_pattern: String::new(),
+            field_name_pattern: String::new(),
+        }
+    }
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            shortcut: default_capture_shortcut(),
+            inbox_note: default_inbox_note(),
+            clipboard_search_shortcut: String::new(),
+        }
+    }
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            smtp_host: String::new(),
+            smtp_port: default_smtp_port(),
+            security: default_smtp_security(),
+            username: String::new(),
+            from_address: String::new(),
+        }
+    }
+}
+
+impl Default for CitationsConfig {
+    fn default() -> Self {
+        Self {
+            bibliography_file: String::new(),
+            references_heading: default_references_heading(),
+        }
+    }
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            crdt_enabled: false,
+            lan_enabled: false,
+            lan_port: default_lan_port(),
+            device_name: String::new(),
+        }
+    }
+}
+
+impl Default for RemindersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            notify_time: default_reminder_time(),
         }
     }
 }
@@ -308,14 +738,20 @@ pub fn reload_config(
     *config = new_config.clone();
     drop(config);
 
+    crate::core::slow_operations::configure_query_limits(&new_config.preferences);
+    crate::utilities::note_renderer::configure_markdown_extensions(&new_config.interface);
+    crate::utilities::mermaid::configure_mermaid_cli(new_config.interface.mermaid_cli.as_deref());
+    crate::utilities::text_encoding::configure_line_endings(&new_config.preferences.line_endings);
+
+    if !crate::core::safe_mode::is_safe_mode() {
+        crate::utilities::citations::configure_citations(
+            &new_config.citations,
+            &get_config_notes_dir_from_config(&new_config),
+        );
+    }
+
     if let Some(app) = app_handle {
-        if let Err(e) = app.emit("config-updated", &new_config) {
-            log(
-                "CONFIG_EVENT",
-                "Failed to emit config-updated event",
-                Some(&e.to_string()),
-            );
-        }
+        emit_event(&app, ConfigUpdated(new_config.clone()));
     }
     Ok(result)
 }
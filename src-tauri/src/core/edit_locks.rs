@@ -0,0 +1,87 @@
+//! Advisory locks of notes being edited in one of the app's windows, so two
+//! windows editing the same note don't silently overwrite each other. Windows
+//! are identified by their label; a window's locks go when it's closed.
+
+use crate::core::{AppError, AppResult};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[derive(Debug, Default)]
+pub struct EditLocks {
+    /// Note name to the window holding its lock
+    locks: Mutex<HashMap<String, String>>,
+}
+
+impl EditLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Locks `note_name` for `window_id`. True when the lock is new, false
+    /// when the window already held it; fails with [`AppError::EditLocked`]
+    /// while another window holds it.
+    pub fn acquire(&self, note_name: &str, window_id: &str) -> AppResult<bool> {
+        let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        match locks.get(note_name) {
+            Some(holder) if holder == window_id => Ok(false),
+            Some(holder) => Err(locked_by(note_name, holder)),
+            None => {
+                locks.insert(note_name.to_string(), window_id.to_string());
+                Ok(true)
+            }
+        }
+    }
+
+    /// Releases `window_id`'s lock of `note_name`; false when it held none.
+    pub fn release(&self, note_name: &str, window_id: &str) -> bool {
+        let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        if locks
+            .get(note_name)
+            .is_some_and(|holder| holder == window_id)
+        {
+            locks.remove(note_name);
+            return true;
+        }
+        false
+    }
+
+    /// Releases every lock `window_id` holds, returning the notes it held.
+    pub fn release_window(&self, window_id: &str) -> Vec<String> {
+        let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        let mut released: Vec<String> = locks
+            .iter()
+            .filter(|(_, holder)| *holder == window_id)
+            .map(|(note_name, _)| note_name.clone())
+            .collect();
+        released.sort();
+        for note_name in &released {
+            locks.remove(note_name);
+        }
+        released
+    }
+
+    /// Window holding the lock of `note_name`, if any
+    pub fn holder(&self, note_name: &str) -> Option<String> {
+        self.locks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(note_name)
+            .cloned()
+    }
+
+    /// Fails with [`AppError::EditLocked`] while a window other than
+    /// `window_id` holds the lock of `note_name`.
+    pub fn ensure_can_save(&self, note_name: &str, window_id: &str) -> AppResult<()> {
+        match self.holder(note_name) {
+            Some(holder) if holder != window_id => Err(locked_by(note_name, &holder)),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn locked_by(note_name: &str, holder: &str) -> AppError {
+    AppError::EditLocked(format!(
+        "'{}' is being edited in window '{}'",
+        note_name, holder
+    ))
+}
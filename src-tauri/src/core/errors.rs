@@ -30,6 +30,15 @@ pub enum AppError {
 
     // UI/Window errors
     WindowOperation(String),
+
+    // IPC throttling; the frontend may retry after a short delay
+    Busy(String),
+
+    // The note is open in an external editor and saving here would overwrite it
+    ExternallyEdited(String),
+
+    // Another window holds the note's edit lock
+    EditLocked(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,6 +84,14 @@ impl fmt::Display for AppError {
             AppError::SearchQuery(msg) => write!(f, "Search query error: {}", msg),
 
             AppError::WindowOperation(msg) => write!(f, "Window operation error: {}", msg),
+
+            AppError::Busy(msg) => write!(f, "Busy: {}", msg),
+
+            AppError::ExternallyEdited(msg) => {
+                write!(f, "Note is being edited externally: {}", msg)
+            }
+
+            AppError::EditLocked(msg) => write!(f, "Note is locked: {}", msg),
         }
     }
 }
@@ -0,0 +1,276 @@
+//! Events emitted to the frontend.
+//!
+//! Every emission goes through [`emit_event`], so the event names and payload
+//! shapes listed here are the whole contract the UI listens against. Mirrored
+//! in `src/lib/types/events.ts`.
+
+use crate::{config::AppConfig, logging::log, services::lan_sync_service::PendingPairing};
+use serde::Serialize;
+use tauri::{Emitter, Runtime};
+
+pub trait AppEvent: Serialize + Clone {
+    const NAME: &'static str;
+}
+
+/// Emits `event` to every window, logging rather than failing if delivery fails.
+pub fn emit_event<R: Runtime, E: AppEvent>(emitter: &impl Emitter<R>, event: E) {
+    if let Err(e) = emitter.emit(E::NAME, event) {
+        log(
+            "UI_UPDATE",
+            &format!("Failed to emit {}", E::NAME),
+            Some(&e.to_string()),
+        );
+    }
+}
+
+/// Notes changed outside the editor: by the file watcher, a LAN sync, a tag
+/// rule or a bulk rewrite such as a tag rename.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CacheRefreshed {
+    /// Notes created or modified
+    pub changed: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl AppEvent for CacheRefreshed {
+    const NAME: &'static str = "cache-refreshed";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DbLoadingStart {
+    pub message: String,
+}
+
+impl AppEvent for DbLoadingStart {
+    const NAME: &'static str = "db-loading-start";
+}
+
+impl DbLoadingStart {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DbLoadingProgress {
+    pub message: String,
+    /// Share of notes loaded, 0.0 to 1.0; `None` for steps without a count
+    pub fraction: Option<f64>,
+}
+
+impl AppEvent for DbLoadingProgress {
+    const NAME: &'static str = "db-loading-progress";
+}
+
+impl DbLoadingProgress {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            fraction: None,
+        }
+    }
+
+    /// `done` of `total` notes loaded
+    pub fn counted(done: usize, total: usize) -> Self {
+        Self {
+            message: format!("Loading {} of {} notes...", done, total),
+            fraction: Some(if total == 0 {
+                1.0
+            } else {
+                done as f64 / total as f64
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DbLoadingComplete;
+
+impl AppEvent for DbLoadingComplete {
+    const NAME: &'static str = "db-loading-complete";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DbLoadingError {
+    pub message: String,
+}
+
+impl AppEvent for DbLoadingError {
+    const NAME: &'static str = "db-loading-error";
+}
+
+impl DbLoadingError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FirstRunDetected;
+
+impl AppEvent for FirstRunDetected {
+    const NAME: &'static str = "first-run-detected";
+}
+
+/// Sent by the tray menu's Settings entry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpenPreferences;
+
+impl AppEvent for OpenPreferences {
+    const NAME: &'static str = "open-preferences";
+}
+
+/// The full config after a save or reload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(transparent)]
+pub struct ConfigUpdated(pub AppConfig);
+
+impl AppEvent for ConfigUpdated {
+    const NAME: &'static str = "config-updated";
+}
+
+/// The merged spell-check dictionary after a word was added or removed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DictionaryUpdated {
+    pub words: Vec<String>,
+}
+
+impl AppEvent for DictionaryUpdated {
+    const NAME: &'static str = "dictionary-updated";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReadingListUpdated {
+    pub url: String,
+    pub note_name: String,
+}
+
+impl AppEvent for ReadingListUpdated {
+    const NAME: &'static str = "reading-list-updated";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReadingListFailed {
+    pub url: String,
+    pub message: String,
+}
+
+impl AppEvent for ReadingListFailed {
+    const NAME: &'static str = "reading-list-failed";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RerenderProgress {
+    pub rendered: usize,
+    pub total: usize,
+    pub cancelled: bool,
+}
+
+impl AppEvent for RerenderProgress {
+    const NAME: &'static str = "rerender-progress";
+}
+
+/// Final counts of a re-render run, including cancelled ones.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct RerenderComplete(pub RerenderProgress);
+
+impl AppEvent for RerenderComplete {
+    const NAME: &'static str = "rerender-complete";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RerenderError {
+    pub message: String,
+}
+
+impl AppEvent for RerenderError {
+    const NAME: &'static str = "rerender-error";
+}
+
+/// Text selected in another app arrived through the macOS Services menu and
+/// was saved to `note_name`, a new note when `created` is set.
+#[cfg(target_os = "macos")]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SelectionCaptured {
+    pub note_name: String,
+    pub created: bool,
+}
+
+#[cfg(target_os = "macos")]
+impl AppEvent for SelectionCaptured {
+    const NAME: &'static str = "selection-captured";
+}
+
+/// The clipboard search shortcut looked up `query`; `results` are the
+/// matching notes, best first, to show before the search box catches up.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClipboardSearch {
+    pub query: String,
+    pub results: Vec<String>,
+}
+
+impl AppEvent for ClipboardSearch {
+    const NAME: &'static str = "clipboard-search";
+}
+
+/// The periodic integrity check found notes whose files look corrupted or
+/// truncated; `get_integrity_alerts` has the details.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IntegrityAlertsRaised {
+    pub note_names: Vec<String>,
+}
+
+impl AppEvent for IntegrityAlertsRaised {
+    const NAME: &'static str = "integrity-alerts-raised";
+}
+
+/// A window took or released the edit lock of `note_name`; `window_id` is the
+/// holder, `None` once the lock is released.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NoteEditLockChanged {
+    pub note_name: String,
+    pub window_id: Option<String>,
+}
+
+impl AppEvent for NoteEditLockChanged {
+    const NAME: &'static str = "note-edit-lock-changed";
+}
+
+/// The notes directory was switched to another vault, whose notes are now
+/// loaded and watched.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VaultSwitched {
+    pub name: String,
+    pub path: String,
+}
+
+impl AppEvent for VaultSwitched {
+    const NAME: &'static str = "vault-switched";
+}
+
+/// Notes passed their front-matter `expires` date; `archived` when
+/// `[preferences] expired_notes = "archive"` marked them archived too.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NotesExpired {
+    pub note_names: Vec<String>,
+    pub archived: bool,
+}
+
+impl AppEvent for NotesExpired {
+    const NAME: &'static str = "notes-expired";
+}
+
+/// Another device asked to pair over LAN sync. It is trusted once the user
+/// confirms this fingerprint matches the one the other device shows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct LanPairingRequested(pub PendingPairing);
+
+impl AppEvent for LanPairingRequested {
+    const NAME: &'static str = "lan-pairing-requested";
+}
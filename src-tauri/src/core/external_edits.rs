@@ -0,0 +1,53 @@
+//! Notes opened in an external editor with `open_note_in_editor`. In-app saves
+//! of them are refused until the watcher sees the editor write the note, so
+//! the two editors don't overwrite each other.
+
+use crate::core::{AppError, AppResult};
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+#[derive(Debug, Default)]
+pub struct ExternalEdits {
+    notes: Mutex<HashSet<String>>,
+}
+
+impl ExternalEdits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn begin(&self, note_name: &str) {
+        self.notes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(note_name.to_string());
+    }
+
+    /// Ends the external edit of `note_name`; false when there was none.
+    pub fn end(&self, note_name: &str) -> bool {
+        self.notes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(note_name)
+    }
+
+    pub fn is_active(&self, note_name: &str) -> bool {
+        self.notes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(note_name)
+    }
+
+    /// Fails with [`AppError::ExternallyEdited`] while `note_name` is open in
+    /// an external editor.
+    pub fn ensure_not_active(&self, note_name: &str) -> AppResult<()> {
+        if self.is_active(note_name) {
+            return Err(AppError::ExternallyEdited(format!(
+                "Cannot save '{}' while it is open in an external editor. \
+                 Save it there, or end the external edit to save here.",
+                note_name
+            )));
+        }
+        Ok(())
+    }
+}
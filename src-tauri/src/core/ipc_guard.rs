@@ -0,0 +1,163 @@
+//! Throttling for IPC commands a runaway frontend loop can call faster than the
+//! database can answer. Every command passes through [`IPC_GUARD`] in the
+//! invoke handler. Commands listed in [`COMMAND_LIMITS`] run on the blocking
+//! thread pool and keep their permit until they have answered; the rest get
+//! [`DEFAULT_LIMIT`]'s rate limit.
+
+use crate::core::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandLimit {
+    /// Calls allowed to run at the same time
+    pub max_concurrent: usize,
+    /// Calls accepted per second
+    pub max_per_second: usize,
+}
+
+/// Limit for commands not listed in [`COMMAND_LIMITS`]. High enough that no
+/// sane frontend hits it, low enough to stop a render loop.
+pub const DEFAULT_LIMIT: CommandLimit = CommandLimit {
+    max_concurrent: 16,
+    max_per_second: 200,
+};
+
+/// Commands that read through the shared database lock on every keystroke or
+/// scroll. They must be synchronous commands, so the permit the invoke handler
+/// holds covers their work.
+pub const COMMAND_LIMITS: &[(&str, CommandLimit)] = &[
+    (
+        "search_notes",
+        CommandLimit {
+            max_concurrent: 2,
+            max_per_second: 30,
+        },
+    ),
+    (
+        "quick_switch",
+        CommandLimit {
+            max_concurrent: 2,
+            max_per_second: 30,
+        },
+    ),
+    (
+        "get_note_content",
+        CommandLimit {
+            max_concurrent: 4,
+            max_per_second: 60,
+        },
+    ),
+    (
+        "get_note_html_content",
+        CommandLimit {
+            max_concurrent: 4,
+            max_per_second: 60,
+        },
+    ),
+    (
+        "run_readonly_query",
+        CommandLimit {
+            max_concurrent: 1,
+            max_per_second: 5,
+        },
+    ),
+];
+
+#[derive(Debug)]
+struct CommandSlot {
+    running: usize,
+    window_start: Instant,
+    calls_in_window: usize,
+}
+
+/// Per-command counters behind [`COMMAND_LIMITS`].
+#[derive(Debug)]
+pub struct CommandGuard {
+    limits: &'static [(&'static str, CommandLimit)],
+    default_limit: CommandLimit,
+    slots: Mutex<HashMap<String, CommandSlot>>,
+}
+
+/// Held while a command runs; releases its slot when dropped.
+#[derive(Debug)]
+pub struct CommandPermit<'a> {
+    guard: &'a CommandGuard,
+    command: String,
+}
+
+impl CommandGuard {
+    pub fn new(
+        limits: &'static [(&'static str, CommandLimit)],
+        default_limit: CommandLimit,
+    ) -> Self {
+        Self {
+            limits,
+            default_limit,
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `command` has its own entry in the limits table.
+    pub fn is_listed(&self, command: &str) -> bool {
+        self.limits.iter().any(|(name, _)| *name == command)
+    }
+
+    /// Admits a call to `command`, or fails with [`AppError::Busy`] when it is
+    /// over its concurrency or rate limit.
+    pub fn acquire(&self, command: &str, now: Instant) -> AppResult<CommandPermit<'_>> {
+        let limit = self
+            .limits
+            .iter()
+            .find(|(name, _)| *name == command)
+            .map(|(_, limit)| *limit)
+            .unwrap_or(self.default_limit);
+
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = slots.entry(command.to_string()).or_insert(CommandSlot {
+            running: 0,
+            window_start: now,
+            calls_in_window: 0,
+        });
+        if now.duration_since(slot.window_start) >= RATE_WINDOW {
+            slot.window_start = now;
+            slot.calls_in_window = 0;
+        }
+
+        if slot.running >= limit.max_concurrent {
+            return Err(AppError::Busy(format!(
+                "{} already has {} calls running",
+                command, slot.running
+            )));
+        }
+        if slot.calls_in_window >= limit.max_per_second {
+            return Err(AppError::Busy(format!(
+                "{} is limited to {} calls per second",
+                command, limit.max_per_second
+            )));
+        }
+
+        slot.running += 1;
+        slot.calls_in_window += 1;
+        Ok(CommandPermit {
+            guard: self,
+            command: command.to_string(),
+        })
+    }
+}
+
+impl Drop for CommandPermit<'_> {
+    fn drop(&mut self) {
+        let mut slots = self.guard.slots.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(slot) = slots.get_mut(&self.command) {
+            slot.running = slot.running.saturating_sub(1);
+        }
+    }
+}
+
+pub static IPC_GUARD: Lazy<CommandGuard> =
+    Lazy::new(|| CommandGuard::new(COMMAND_LIMITS, DEFAULT_LIMIT));
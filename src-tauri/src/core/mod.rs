@@ -1,4 +1,12 @@
+pub mod edit_locks;
 pub mod errors;
+pub mod events;
+pub mod external_edits;
+pub mod ipc_guard;
+pub mod safe_mode;
+pub mod service_health;
+pub mod slow_operations;
+pub mod startup_profile;
 pub mod state;
 
 pub use errors::*;
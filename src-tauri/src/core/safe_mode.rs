@@ -0,0 +1,39 @@
+use crate::core::{AppError, AppResult};
+use std::sync::OnceLock;
+
+/// Set to `1` or `true` to start in safe mode
+pub const SAFE_MODE_ENV: &str = "SYMIOSIS_SAFE_MODE";
+pub const SAFE_MODE_FLAG: &str = "--safe-mode";
+
+static SAFE_MODE: OnceLock<bool> = OnceLock::new();
+
+/// Whether the environment value or command-line arguments ask for safe mode.
+pub fn safe_mode_requested(env_value: Option<&str>, args: &[String]) -> bool {
+    let from_env = env_value.is_some_and(|value| {
+        let value = value.trim();
+        value == "1" || value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("yes")
+    });
+    from_env || args.iter().any(|arg| arg == SAFE_MODE_FLAG)
+}
+
+/// Safe mode loads only core note browsing: the file watcher, LAN sync, the CRDT
+/// change log and background re-rendering stay off for the whole session.
+pub fn is_safe_mode() -> bool {
+    *SAFE_MODE.get_or_init(|| {
+        let env_value = std::env::var(SAFE_MODE_ENV).ok();
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        safe_mode_requested(env_value.as_deref(), &args)
+    })
+}
+
+/// Rejects starting `feature` while in safe mode.
+pub fn ensure_not_safe_mode(feature: &str) -> AppResult<()> {
+    if is_safe_mode() {
+        Err(AppError::ConfigLoad(format!(
+            "{} is disabled in safe mode; restart without {} or {}",
+            feature, SAFE_MODE_FLAG, SAFE_MODE_ENV
+        )))
+    } else {
+        Ok(())
+    }
+}
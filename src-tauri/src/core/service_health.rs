@@ -0,0 +1,152 @@
+use crate::core::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceName {
+    Watcher,
+    Scheduler,
+    Sync,
+    EmbeddingWorker,
+    HttpApi,
+}
+
+impl ServiceName {
+    pub const ALL: [ServiceName; 5] = [
+        ServiceName::Watcher,
+        ServiceName::Scheduler,
+        ServiceName::Sync,
+        ServiceName::EmbeddingWorker,
+        ServiceName::HttpApi,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ServiceName::Watcher => "watcher",
+            ServiceName::Scheduler => "scheduler",
+            ServiceName::Sync => "sync",
+            ServiceName::EmbeddingWorker => "embedding_worker",
+            ServiceName::HttpApi => "http_api",
+        }
+    }
+
+    pub fn parse(name: &str) -> AppResult<Self> {
+        let name = name.trim().to_lowercase().replace('-', "_");
+        Self::ALL
+            .into_iter()
+            .find(|service| service.as_str() == name)
+            .ok_or_else(|| {
+                AppError::validation_error("name", &format!("Unknown service '{}'", name))
+            })
+    }
+
+    /// Services this build ships; the others always report `Unavailable`
+    pub fn is_available(self) -> bool {
+        matches!(
+            self,
+            ServiceName::Watcher | ServiceName::Scheduler | ServiceName::Sync
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceState {
+    Running,
+    Stopped,
+    /// The service stopped unexpectedly or failed to start
+    Error,
+    /// Not part of this build
+    Unavailable,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServiceStatus {
+    pub name: ServiceName,
+    pub state: ServiceState,
+    /// Unix seconds of the last event the service handled
+    pub last_activity: Option<i64>,
+    pub error: Option<String>,
+}
+
+/// Health of the background services, updated by the services themselves.
+#[derive(Debug, Default)]
+pub struct ServiceRegistry {
+    statuses: Mutex<HashMap<ServiceName, ServiceStatus>>,
+}
+
+impl ServiceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&self, name: ServiceName, apply: impl FnOnce(&mut ServiceStatus)) {
+        let mut statuses = self.statuses.lock().unwrap_or_else(|e| e.into_inner());
+        let status = statuses.entry(name).or_insert(ServiceStatus {
+            name,
+            state: ServiceState::Stopped,
+            last_activity: None,
+            error: None,
+        });
+        apply(status);
+    }
+
+    pub fn mark_running(&self, name: ServiceName) {
+        self.update(name, |status| {
+            status.state = ServiceState::Running;
+            status.error = None;
+            status.last_activity = Some(now());
+        });
+    }
+
+    pub fn mark_stopped(&self, name: ServiceName) {
+        self.update(name, |status| status.state = ServiceState::Stopped);
+    }
+
+    pub fn mark_error(&self, name: ServiceName, error: &str) {
+        self.update(name, |status| {
+            status.state = ServiceState::Error;
+            status.error = Some(error.to_string());
+        });
+    }
+
+    pub fn record_activity(&self, name: ServiceName) {
+        self.update(name, |status| status.last_activity = Some(now()));
+    }
+
+    /// Every service in [`ServiceName::ALL`] order, including ones never started.
+    pub fn statuses(&self) -> Vec<ServiceStatus> {
+        let statuses = self.statuses.lock().unwrap_or_else(|e| e.into_inner());
+        ServiceName::ALL
+            .into_iter()
+            .map(|name| match statuses.get(&name) {
+                _ if !name.is_available() => ServiceStatus {
+                    name,
+                    state: ServiceState::Unavailable,
+                    last_activity: None,
+                    error: None,
+                },
+                Some(status) => status.clone(),
+                None => ServiceStatus {
+                    name,
+                    state: ServiceState::Stopped,
+                    last_activity: None,
+                    error: None,
+                },
+            })
+            .collect()
+    }
+}
+
+pub static SERVICES: Lazy<ServiceRegistry> = Lazy::new(ServiceRegistry::new);
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
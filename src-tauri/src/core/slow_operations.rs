@@ -0,0 +1,193 @@
+use crate::config::PreferencesConfig;
+use crate::logging::log;
+use once_cell::sync::Lazy;
+use rusqlite::trace::TraceEvent;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Slow operations kept for `get_slow_operations`; older ones are dropped
+const MAX_SLOW_OPERATIONS: usize = 100;
+/// Statements kept per operation
+const MAX_STATEMENTS: usize = 50;
+
+static TIMEOUT_MS: AtomicU64 = AtomicU64::new(0);
+static SLOW_THRESHOLD_MS: AtomicU64 = AtomicU64::new(0);
+
+pub static SLOW_OPERATIONS: Lazy<SlowOperationLog> = Lazy::new(SlowOperationLog::default);
+
+thread_local! {
+    /// Statements run by the database operation in progress on this thread
+    static STATEMENTS: RefCell<Option<Vec<StatementTiming>>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatementTiming {
+    /// SQL with its parameters, text and blob values replaced by their length
+    pub sql: String,
+    pub duration_ms: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SlowOperation {
+    /// Where the operation was started, e.g. `src/commands/note_crud.rs:95`
+    pub operation: String,
+    pub statements: Vec<StatementTiming>,
+    pub duration_ms: f64,
+    pub timed_out: bool,
+    /// Unix seconds
+    pub finished_at: i64,
+}
+
+#[derive(Debug, Default)]
+pub struct SlowOperationLog {
+    operations: Mutex<VecDeque<SlowOperation>>,
+}
+
+impl SlowOperationLog {
+    pub fn record(&self, operation: SlowOperation) {
+        let mut operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        if operations.len() == MAX_SLOW_OPERATIONS {
+            operations.pop_front();
+        }
+        operations.push_back(operation);
+    }
+
+    /// Newest first
+    pub fn recent(&self) -> Vec<SlowOperation> {
+        let operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        operations.iter().rev().cloned().collect()
+    }
+}
+
+/// Applies the `[preferences]` database timeout and slow-operation threshold.
+pub fn configure_query_limits(preferences: &PreferencesConfig) {
+    TIMEOUT_MS.store(preferences.db_timeout_ms, Ordering::Relaxed);
+    SLOW_THRESHOLD_MS.store(preferences.slow_query_ms, Ordering::Relaxed);
+}
+
+/// How long a database operation may run before it's interrupted, `None`
+/// when unlimited.
+pub fn operation_timeout() -> Option<Duration> {
+    match TIMEOUT_MS.load(Ordering::Relaxed) {
+        0 => None,
+        ms => Some(Duration::from_millis(ms)),
+    }
+}
+
+/// Starts collecting the statements run on this thread.
+pub fn start_statement_capture() {
+    STATEMENTS.with(|statements| *statements.borrow_mut() = Some(Vec::new()));
+}
+
+/// Stops collecting and returns what was run since `start_statement_capture`.
+pub fn finish_statement_capture() -> Vec<StatementTiming> {
+    STATEMENTS.with(|statements| statements.borrow_mut().take().unwrap_or_default())
+}
+
+/// Trace callback for database connections, see `start_statement_capture`.
+pub fn trace_statement(event: TraceEvent<'_>) {
+    let TraceEvent::Profile(statement, duration) = event else {
+        return;
+    };
+    STATEMENTS.with(|statements| {
+        if let Some(statements) = statements.borrow_mut().as_mut() {
+            if statements.len() < MAX_STATEMENTS {
+                let sql = statement
+                    .expanded_sql()
+                    .unwrap_or_else(|| statement.sql().to_string());
+                statements.push(StatementTiming {
+                    sql: redact_sql(&sql),
+                    duration_ms: millis(duration),
+                });
+            }
+        }
+    });
+}
+
+/// Records and logs an operation if it was slower than the configured
+/// threshold or timed out.
+pub fn report_operation(
+    operation: String,
+    elapsed: Duration,
+    timed_out: bool,
+    statements: Vec<StatementTiming>,
+) {
+    let threshold = SLOW_THRESHOLD_MS.load(Ordering::Relaxed);
+    let duration_ms = millis(elapsed);
+    if !timed_out && (threshold == 0 || duration_ms < threshold as f64) {
+        return;
+    }
+
+    log(
+        "SLOW_QUERY",
+        &format!(
+            "{} took {:.0} ms{}",
+            operation,
+            duration_ms,
+            if timed_out { " and timed out" } else { "" }
+        ),
+        statements
+            .iter()
+            .max_by(|a, b| a.duration_ms.total_cmp(&b.duration_ms))
+            .map(|slowest| slowest.sql.as_str()),
+    );
+    SLOW_OPERATIONS.record(SlowOperation {
+        operation,
+        statements,
+        duration_ms,
+        timed_out,
+        finished_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0),
+    });
+}
+
+/// Replaces every string and blob literal, bound parameters included, with a
+/// placeholder giving only its length, so neither note content nor note names
+/// end up in the log.
+pub fn redact_sql(sql: &str) -> String {
+    let mut redacted = String::with_capacity(sql.len().min(1024));
+    let mut chars = sql.chars().peekable();
+    let mut previous = ' ';
+    while let Some(c) = chars.next() {
+        let is_blob = (c == 'x' || c == 'X')
+            && chars.peek() == Some(&'\'')
+            && !(previous.is_alphanumeric() || previous == '_');
+        previous = c;
+        if c != '\'' && !is_blob {
+            redacted.push(c);
+            continue;
+        }
+        if is_blob {
+            chars.next();
+        }
+
+        let mut length = 0;
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                // '' is an escaped quote inside the literal
+                if chars.peek() != Some(&'\'') {
+                    break;
+                }
+                chars.next();
+            }
+            length += 1;
+        }
+
+        if is_blob {
+            redacted.push_str(&format!("<blob: {} bytes>", length / 2));
+        } else {
+            redacted.push_str(&format!("<text: {} chars>", length));
+        }
+    }
+    redacted
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
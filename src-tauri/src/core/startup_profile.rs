@@ -0,0 +1,134 @@
+use crate::logging::log;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupPhase {
+    /// Everything before the window is built; contains the three phases below
+    InitializeState,
+    LoadConfig,
+    DatabaseInit,
+    /// Indexing notes changed since the last run
+    InitialSync,
+    WatcherSetup,
+    ShortcutRegistration,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PhaseTiming {
+    pub phase: StartupPhase,
+    /// Milliseconds from the start of the profile to the start of the phase
+    pub started_ms: f64,
+    pub duration_ms: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StartupReport {
+    /// In the order the phases started
+    pub phases: Vec<PhaseTiming>,
+    /// Time until the app finished setting up; `None` while it is still starting
+    pub total_ms: Option<f64>,
+}
+
+/// Timings of the startup phases. Phases run after startup finished, such as
+/// a later re-sync, are not recorded.
+#[derive(Debug)]
+pub struct StartupProfiler {
+    started: Instant,
+    phases: Mutex<Vec<PhaseTiming>>,
+    finished: Mutex<Option<Duration>>,
+}
+
+impl Default for StartupProfiler {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            phases: Mutex::new(Vec::new()),
+            finished: Mutex::new(None),
+        }
+    }
+}
+
+impl StartupProfiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `operation`, recording how long it took as `phase`.
+    pub fn time<T>(&self, phase: StartupPhase, operation: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = operation();
+        if self.is_finished() {
+            return result;
+        }
+
+        let timing = PhaseTiming {
+            phase,
+            started_ms: millis(start.duration_since(self.started)),
+            duration_ms: millis(start.elapsed()),
+        };
+        self.phases
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(timing);
+        result
+    }
+
+    /// Marks startup as complete and logs the report.
+    pub fn finish(&self) {
+        {
+            let mut finished = self.finished.lock().unwrap_or_else(|e| e.into_inner());
+            if finished.is_some() {
+                return;
+            }
+            *finished = Some(self.started.elapsed());
+        }
+
+        let report = self.report();
+        let summary = report
+            .phases
+            .iter()
+            .map(|timing| format!("{:?}: {:.1} ms", timing.phase, timing.duration_ms))
+            .collect::<Vec<_>>()
+            .join(", ");
+        log(
+            "STARTUP",
+            &format!("Started in {:.1} ms", report.total_ms.unwrap_or_default()),
+            Some(&summary),
+        );
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some()
+    }
+
+    pub fn report(&self) -> StartupReport {
+        let mut phases = self
+            .phases
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        phases.sort_by(|a, b| a.started_ms.total_cmp(&b.started_ms));
+        StartupReport {
+            phases,
+            total_ms: self
+                .finished
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .map(millis),
+        }
+    }
+}
+
+/// Profile of this process, started by the first phase `run` times.
+pub static STARTUP: Lazy<StartupProfiler> = Lazy::new(StartupProfiler::new);
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -1,4 +1,9 @@
-use crate::{config::AppConfig, core::AppResult, database::DatabaseManager, logging::log};
+use crate::{
+    config::AppConfig,
+    core::{edit_locks::EditLocks, external_edits::ExternalEdits, AppResult},
+    database::DatabaseManager,
+    logging::log,
+};
 use std::sync::{atomic::AtomicBool, Arc, Mutex, RwLock};
 
 #[derive(Clone)]
@@ -8,6 +13,8 @@ pub struct AppState {
     pub programmatic_operation_in_progress: Arc<AtomicBool>,
     pub database_manager: Arc<Mutex<DatabaseManager>>,
     pub database_rebuild_lock: Arc<RwLock<()>>,
+    pub external_edits: Arc<ExternalEdits>,
+    pub edit_locks: Arc<EditLocks>,
 }
 
 impl AppState {
@@ -20,6 +27,8 @@ impl AppState {
             programmatic_operation_in_progress: Arc::new(AtomicBool::new(false)),
             database_manager: Arc::new(Mutex::new(database_manager)),
             database_rebuild_lock: Arc::new(RwLock::new(())),
+            external_edits: Arc::new(ExternalEdits::new()),
+            edit_locks: Arc::new(EditLocks::new()),
         })
     }
 
@@ -82,6 +91,8 @@ impl AppState {
             programmatic_operation_in_progress: Arc::new(AtomicBool::new(false)),
             database_manager: Arc::new(Mutex::new(database_manager)),
             database_rebuild_lock: Arc::new(RwLock::new(())),
+            external_edits: Arc::new(ExternalEdits::new()),
+            edit_locks: Arc::new(EditLocks::new()),
         };
 
         // Recreate database from filesystem
@@ -1,7 +1,17 @@
+use crate::core::slow_operations::{
+    finish_statement_capture, operation_timeout, report_operation, start_statement_capture,
+    trace_statement,
+};
 use crate::core::{AppError, AppResult};
 use crate::utilities::paths::get_database_path;
+use rusqlite::trace::TraceEventCodes;
 use rusqlite::Connection;
+use std::panic::Location;
 use std::path::PathBuf;
+use std::time::Instant;
+
+/// Virtual machine steps between checks of the operation timeout
+const TIMEOUT_CHECK_OPS: i32 = 1000;
 
 pub struct DatabaseManager {
     connection: Connection,
@@ -26,8 +36,10 @@ impl DatabaseManager {
             })?;
         }
 
-        Connection::open(db_path)
-            .map_err(|e| AppError::DatabaseConnection(format!("Failed to open database: {}", e)))
+        let conn = Connection::open(db_path)
+            .map_err(|e| AppError::DatabaseConnection(format!("Failed to open database: {}", e)))?;
+        conn.trace_v2(TraceEventCodes::SQLITE_TRACE_PROFILE, Some(trace_statement));
+        Ok(conn)
     }
 
     pub fn ensure_current_connection(&mut self) -> AppResult<bool> {
@@ -59,10 +71,16 @@ impl DatabaseManager {
     }
 }
 
+/// Runs `f` on the database. It's interrupted after `[preferences]
+/// db_timeout_ms`, and logged with its caller and statements when slower than
+/// `slow_query_ms` (see `get_slow_operations`).
+#[track_caller]
 pub fn with_db<T, F>(app_state: &crate::core::state::AppState, f: F) -> AppResult<T>
 where
     F: FnOnce(&Connection) -> AppResult<T>,
 {
+    let caller = Location::caller();
+
     // First acquire read lock on rebuild_lock to ensure no rebuilds are happening
     let _rebuild_guard = app_state.database_rebuild_lock.read().map_err(|e| {
         AppError::DatabaseConnection(format!("Database rebuild lock poisoned: {}", e))
@@ -73,7 +91,42 @@ where
         AppError::DatabaseConnection(format!("Database manager lock poisoned: {}", e))
     })?;
 
-    manager.with_connection(f)
+    manager.with_connection(|conn| run_timed(conn, caller, f))
+}
+
+fn run_timed<T, F>(conn: &Connection, caller: &Location<'_>, f: F) -> AppResult<T>
+where
+    F: FnOnce(&Connection) -> AppResult<T>,
+{
+    let started = Instant::now();
+    let timeout = operation_timeout();
+    if let Some(timeout) = timeout {
+        conn.progress_handler(TIMEOUT_CHECK_OPS, Some(move || started.elapsed() > timeout));
+    }
+    start_statement_capture();
+
+    let result = f(conn);
+
+    let elapsed = started.elapsed();
+    let statements = finish_statement_capture();
+    if timeout.is_some() {
+        conn.progress_handler(0, None::<fn() -> bool>);
+    }
+    let timed_out = timeout.is_some_and(|timeout| elapsed > timeout) && result.is_err();
+    report_operation(
+        format!("{}:{}", caller.file(), caller.line()),
+        elapsed,
+        timed_out,
+        statements,
+    );
+
+    match result {
+        Err(_) if timed_out => Err(AppError::DatabaseQuery(format!(
+            "Operation timed out after {} ms",
+            elapsed.as_millis()
+        ))),
+        result => result,
+    }
 }
 
 pub fn with_db_mut<T, F>(app_state: &crate::core::state::AppState, f: F) -> AppResult<T>
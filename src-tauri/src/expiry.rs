@@ -0,0 +1,104 @@
+//! Handling of notes past their front-matter `expires` date, run by the
+//! scheduler: each expired note is flagged with a desktop notification once
+//! and, with `[preferences] expired_notes = "archive"`, marked `archived`.
+
+use crate::{
+    config::get_config_notes_dir,
+    core::{
+        events::{emit_event, NotesExpired},
+        state::AppState,
+        AppResult,
+    },
+    database::with_db,
+    logging::log,
+    services::{
+        expiry_service::{mark_expired_handled, newly_expired_notes},
+        note_service::write_note_and_index,
+    },
+    utilities::{
+        frontmatter::{set_frontmatter_field, FrontmatterValue},
+        text_encoding::read_note_text,
+    },
+};
+use chrono::{Local, Utc};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Flags, or archives, notes that expired since the last check.
+pub fn handle_expired_notes(app_handle: &AppHandle) -> AppResult<()> {
+    let Some(app_state) = app_handle.try_state::<AppState>() else {
+        return Ok(());
+    };
+    let archive = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        config.preferences.expired_notes == "archive"
+    };
+
+    let today = Local::now().date_naive();
+    let expired = with_db(&app_state, |conn| newly_expired_notes(conn, today))?;
+    if expired.is_empty() {
+        return Ok(());
+    }
+
+    if archive {
+        for note in &expired {
+            if let Err(e) = archive_note(&app_state, &note.note_name) {
+                log(
+                    "EXPIRY",
+                    &format!("Failed to archive expired note '{}'", note.note_name),
+                    Some(&e.to_string()),
+                );
+            }
+        }
+    }
+    // Failed archives are only reported once too, like the notification
+    with_db(&app_state, |conn| {
+        mark_expired_handled(conn, &expired, Utc::now().timestamp())
+    })?;
+
+    log(
+        "EXPIRY",
+        &format!(
+            "{} {} expired note(s)",
+            if archive { "Archived" } else { "Flagged" },
+            expired.len()
+        ),
+        None,
+    );
+    let titles: Vec<&str> = expired.iter().map(|note| note.title.as_str()).collect();
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title(if archive {
+            "Expired notes archived"
+        } else {
+            "Notes expired"
+        })
+        .body(titles.join("\n"))
+        .show()
+    {
+        log(
+            "EXPIRY",
+            "Failed to show expiry notification",
+            Some(&e.to_string()),
+        );
+    }
+    emit_event(
+        app_handle,
+        NotesExpired {
+            note_names: expired.into_iter().map(|note| note.note_name).collect(),
+            archived: archive,
+        },
+    );
+    Ok(())
+}
+
+fn archive_note(app_state: &AppState, note_name: &str) -> AppResult<()> {
+    let content = read_note_text(&get_config_notes_dir().join(note_name))?;
+    let archived = set_frontmatter_field(
+        &content,
+        "archived",
+        Some(&FrontmatterValue::Text("true".to_string())),
+    );
+    write_note_and_index(app_state, note_name, &archived)
+}
@@ -0,0 +1,81 @@
+//! Periodic check of note files against their last known good checksums, run
+//! by the scheduler every `[preferences] integrity_check_hours`, so silent
+//! corruption or a truncating sync tool is flagged before the damaged version
+//! ends up in backups.
+
+use crate::{
+    config::get_config_notes_dir,
+    core::{
+        events::{emit_event, IntegrityAlertsRaised},
+        state::AppState,
+        AppResult,
+    },
+    database::with_db,
+    logging::log,
+    services::integrity_service::{last_verified_at, verify_notes},
+    utilities::strings::extract_title_from_filename,
+};
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Verifies the notes when the configured interval has passed since the last
+/// verification.
+pub fn verify_if_due(app_handle: &AppHandle) -> AppResult<()> {
+    let Some(app_state) = app_handle.try_state::<AppState>() else {
+        return Ok(());
+    };
+    let interval_hours = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        config.preferences.integrity_check_hours
+    };
+    if interval_hours == 0 {
+        return Ok(());
+    }
+
+    let now = Utc::now().timestamp();
+    let notes_dir = get_config_notes_dir();
+    let alerts = with_db(&app_state, |conn| {
+        let due = last_verified_at(conn)?
+            .is_none_or(|last| now - last >= i64::from(interval_hours) * 3600);
+        if !due {
+            return Ok(Vec::new());
+        }
+        verify_notes(conn, &notes_dir, now)
+    })?;
+    if alerts.is_empty() {
+        return Ok(());
+    }
+
+    for alert in &alerts {
+        log(
+            "INTEGRITY",
+            &format!("'{}': {}", alert.note_name, alert.message),
+            None,
+        );
+    }
+    let titles: Vec<String> = alerts
+        .iter()
+        .map(|alert| extract_title_from_filename(&alert.note_name))
+        .collect();
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title("Damaged notes found")
+        .body(titles.join("\n"))
+        .show()
+    {
+        log(
+            "INTEGRITY",
+            "Failed to show integrity notification",
+            Some(&e.to_string()),
+        );
+    }
+    emit_event(
+        app_handle,
+        IntegrityAlertsRaised {
+            note_names: alerts.into_iter().map(|alert| alert.note_name).collect(),
+        },
+    );
+    Ok(())
+}
@@ -0,0 +1,807 @@
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::{
+    config::get_config_notes_dir,
+    core::{
+        events::{emit_event, CacheRefreshed, LanPairingRequested},
+        service_health::{ServiceName, SERVICES},
+        state::AppState,
+        AppError, AppResult,
+    },
+    database::with_db,
+    logging::log,
+    services::{lan_sync_service::*, note_service::write_note_and_index},
+    utilities::{
+        paths::{get_lan_sync_dir, get_lan_vault_dir},
+        validation::validate_note_name,
+    },
+};
+
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Incoming connections handled at once; further ones are closed unanswered
+const MAX_CONNECTIONS: usize = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoveredPeer {
+    pub device_id: String,
+    pub device_name: String,
+    pub address: String,
+    pub paired: bool,
+}
+
+struct LanRuntime {
+    daemon: ServiceDaemon,
+    fullname: String,
+    port: u16,
+    shutdown: Arc<AtomicBool>,
+    peers: Arc<Mutex<HashMap<String, DiscoveredPeer>>>,
+}
+
+static RUNTIME: Mutex<Option<LanRuntime>> = Mutex::new(None);
+/// When this device started accepting a pairing request
+static PAIRING_WINDOW: Mutex<Option<Instant>> = Mutex::new(None);
+/// Pairings waiting for the user to compare fingerprints, by peer id
+static PENDING_PAIRINGS: Mutex<Vec<(PendingPairing, Instant)>> = Mutex::new(Vec::new());
+/// One sync session at a time, incoming or outgoing
+static SYNC_LOCK: Mutex<()> = Mutex::new(());
+static ACTIVE_CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+fn device_path() -> AppResult<PathBuf> {
+    get_lan_sync_dir().map(|dir| dir.join("device.json"))
+}
+
+pub fn paired_peers_path() -> AppResult<PathBuf> {
+    get_lan_sync_dir().map(|dir| dir.join("paired_devices.json"))
+}
+
+pub fn conflicts_path() -> AppResult<PathBuf> {
+    get_lan_vault_dir(&get_config_notes_dir()).map(|dir| dir.join("conflicts.json"))
+}
+
+fn bases_path(peer_id: &str) -> AppResult<PathBuf> {
+    get_lan_vault_dir(&get_config_notes_dir()).map(|dir| dir.join(format!("{}.json", peer_id)))
+}
+
+pub fn local_device(app_state: &AppState) -> AppResult<DeviceIdentity> {
+    let configured = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .sync
+        .device_name
+        .clone();
+    let default_name = std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .unwrap_or_else(|_| "Symiosis".to_string());
+
+    let mut device = load_or_create_device(&device_path()?, &default_name)?;
+    if !configured.is_empty() {
+        device.device_name = configured;
+    }
+    Ok(device)
+}
+
+/// Advertises this device over mDNS, browses for peers and accepts sync
+/// connections. Returns the listening port; calling it again is a no-op.
+pub fn start(app_handle: AppHandle, app_state: Arc<AppState>) -> AppResult<u16> {
+    crate::core::safe_mode::ensure_not_safe_mode("LAN sync")?;
+    let result = start_runtime(app_handle, app_state);
+    match &result {
+        Ok(_) => SERVICES.mark_running(ServiceName::Sync),
+        Err(e) => SERVICES.mark_error(ServiceName::Sync, &e.to_string()),
+    }
+    result
+}
+
+fn start_runtime(app_handle: AppHandle, app_state: Arc<AppState>) -> AppResult<u16> {
+    let mut runtime = RUNTIME.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(existing) = runtime.as_ref() {
+        return Ok(existing.port);
+    }
+
+    let device = local_device(&app_state)?;
+    let port = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .sync
+        .lan_port;
+
+    let listener = TcpListener::bind(("0.0.0.0", port))
+        .map_err(|e| AppError::FileWrite(format!("Failed to listen on port {}: {}", port, e)))?;
+    listener.set_nonblocking(true)?;
+
+    let mdns_error = |e: mdns_sd::Error| AppError::FileWrite(format!("mDNS error: {}", e));
+    let daemon = ServiceDaemon::new().map_err(mdns_error)?;
+    let properties: HashMap<String, String> = [
+        ("id".to_string(), device.device_id.clone()),
+        ("name".to_string(), device.device_name.clone()),
+        ("v".to_string(), PROTOCOL_VERSION.to_string()),
+    ]
+    .into_iter()
+    .collect();
+    let service = ServiceInfo::new(
+        SERVICE_TYPE,
+        &device.device_id,
+        &format!("symiosis-{}.local.", &device.device_id[..8]),
+        (),
+        port,
+        properties,
+    )
+    .map_err(mdns_error)?
+    .enable_addr_auto();
+    let fullname = service.get_fullname().to_string();
+    daemon.register(service).map_err(mdns_error)?;
+    let events = daemon.browse(SERVICE_TYPE).map_err(mdns_error)?;
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let peers = Arc::new(Mutex::new(HashMap::new()));
+
+    {
+        let shutdown = Arc::clone(&shutdown);
+        let peers = Arc::clone(&peers);
+        let own_id = device.device_id.clone();
+        thread::spawn(move || {
+            while !shutdown.load(Ordering::Relaxed) {
+                match events.recv_timeout(POLL_INTERVAL) {
+                    Ok(event) => handle_discovery_event(event, &own_id, &peers),
+                    Err(_) if events.is_disconnected() => break,
+                    Err(_) => continue,
+                }
+            }
+        });
+    }
+
+    {
+        let shutdown = Arc::clone(&shutdown);
+        thread::spawn(move || {
+            while !shutdown.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, address)) => {
+                        SERVICES.record_activity(ServiceName::Sync);
+                        if ACTIVE_CONNECTIONS.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                            ACTIVE_CONNECTIONS.fetch_sub(1, Ordering::SeqCst);
+                            log(
+                                "LAN_SYNC",
+                                &format!("Refused connection from {}: too many open", address),
+                                None,
+                            );
+                            continue;
+                        }
+                        let app_handle = app_handle.clone();
+                        let app_state = Arc::clone(&app_state);
+                        thread::spawn(move || {
+                            let result = handle_connection(stream, &app_handle, &app_state);
+                            ACTIVE_CONNECTIONS.fetch_sub(1, Ordering::SeqCst);
+                            if let Err(e) = result {
+                                log(
+                                    "LAN_SYNC",
+                                    &format!("Connection from {} failed", address),
+                                    Some(&e.to_string()),
+                                );
+                            }
+                        });
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        thread::sleep(POLL_INTERVAL)
+                    }
+                    Err(e) => {
+                        log("LAN_SYNC", "Accept failed", Some(&e.to_string()));
+                        thread::sleep(POLL_INTERVAL);
+                    }
+                }
+            }
+        });
+    }
+
+    log(
+        "LAN_SYNC",
+        &format!("Advertising '{}' on port {}", device.device_name, port),
+        None,
+    );
+    *runtime = Some(LanRuntime {
+        daemon,
+        fullname,
+        port,
+        shutdown,
+        peers,
+    });
+    Ok(port)
+}
+
+pub fn stop() {
+    let Some(runtime) = RUNTIME.lock().unwrap_or_else(|e| e.into_inner()).take() else {
+        return;
+    };
+    runtime.shutdown.store(true, Ordering::Relaxed);
+    SERVICES.mark_stopped(ServiceName::Sync);
+    let _ = runtime.daemon.unregister(&runtime.fullname);
+    if let Err(e) = runtime.daemon.shutdown() {
+        log(
+            "LAN_SYNC",
+            "Failed to stop mDNS daemon",
+            Some(&e.to_string()),
+        );
+    }
+    log("LAN_SYNC", "Stopped", None);
+}
+
+fn handle_discovery_event(
+    event: ServiceEvent,
+    own_id: &str,
+    peers: &Mutex<HashMap<String, DiscoveredPeer>>,
+) {
+    match event {
+        // Advertisements are unauthenticated: the id only finds an address. Peers
+        // prove their key when syncing, and their fingerprint when pairing
+        ServiceEvent::ServiceResolved(info) => {
+            let Some(device_id) = info.get_property_val_str("id") else {
+                return;
+            };
+            if device_id == own_id {
+                return;
+            }
+            // Prefer IPv4; link-local IPv6 needs a scope id we don't track
+            let Some(ip) = info
+                .get_addresses()
+                .iter()
+                .min_by_key(|ip| !ip.is_ipv4())
+                .copied()
+            else {
+                return;
+            };
+
+            let peer = DiscoveredPeer {
+                device_id: device_id.to_string(),
+                device_name: info
+                    .get_property_val_str("name")
+                    .unwrap_or(device_id)
+                    .to_string(),
+                address: SocketAddr::new(ip, info.get_port()).to_string(),
+                paired: false,
+            };
+            peers
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(peer.device_id.clone(), peer);
+        }
+        ServiceEvent::ServiceRemoved(_, fullname) => {
+            peers
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .retain(|id, _| !fullname.starts_with(id.as_str()));
+        }
+        _ => {}
+    }
+}
+
+pub fn discovered_peers() -> AppResult<Vec<DiscoveredPeer>> {
+    let paired = load_paired_peers(&paired_peers_path()?)?;
+    let runtime = RUNTIME.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(runtime) = runtime.as_ref() else {
+        return Ok(Vec::new());
+    };
+
+    let mut peers: Vec<DiscoveredPeer> = runtime
+        .peers
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .map(|mut peer| {
+            peer.paired = paired.iter().any(|p| p.device_id == peer.device_id);
+            peer
+        })
+        .collect();
+    peers.sort_by_key(|peer| peer.device_name.to_lowercase());
+    Ok(peers)
+}
+
+fn peer_address(peer_id: &str) -> AppResult<String> {
+    discovered_peers()?
+        .into_iter()
+        .find(|peer| peer.device_id == peer_id)
+        .map(|peer| peer.address)
+        .ok_or_else(|| {
+            AppError::FileNotFound(format!(
+                "Device {} was not found on the local network",
+                peer_id
+            ))
+        })
+}
+
+fn connect(address: &str) -> AppResult<TcpStream> {
+    let address: SocketAddr = address
+        .parse()
+        .map_err(|_| AppError::InvalidPath(format!("Invalid peer address: {}", address)))?;
+    let stream = TcpStream::connect_timeout(&address, IO_TIMEOUT)?;
+    prepare_stream(&stream)?;
+    Ok(stream)
+}
+
+fn prepare_stream(stream: &TcpStream) -> AppResult<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    Ok(())
+}
+
+/// Starts accepting one pairing request.
+pub fn begin_pairing() {
+    *PAIRING_WINDOW.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+}
+
+/// Closes the pairing window. Any request consumes it, accepted or not, so
+/// another device can't slip in behind the expected one.
+fn take_pairing_window() -> bool {
+    PAIRING_WINDOW
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+        .is_some_and(|started| started.elapsed().as_secs() < PAIRING_WINDOW_SECS)
+}
+
+fn add_pending_pairing(
+    device: &DeviceIdentity,
+    own_recipient: &str,
+    peer: PairedPeer,
+) -> PendingPairing {
+    let pending = PendingPairing {
+        fingerprint: pairing_fingerprint(
+            &device.device_id,
+            own_recipient,
+            &peer.device_id,
+            &peer.recipient,
+        ),
+        peer,
+    };
+    let mut pairings = PENDING_PAIRINGS.lock().unwrap_or_else(|e| e.into_inner());
+    pairings.retain(|(existing, started)| {
+        existing.peer.device_id != pending.peer.device_id
+            && started.elapsed().as_secs() < PAIRING_WINDOW_SECS
+    });
+    pairings.push((pending.clone(), Instant::now()));
+    pending
+}
+
+fn take_pending_pairing(peer_id: &str) -> Option<PendingPairing> {
+    let mut pairings = PENDING_PAIRINGS.lock().unwrap_or_else(|e| e.into_inner());
+    let index = pairings
+        .iter()
+        .position(|(pending, _)| pending.peer.device_id == peer_id)?;
+    let (pending, started) = pairings.remove(index);
+    (started.elapsed().as_secs() < PAIRING_WINDOW_SECS).then_some(pending)
+}
+
+/// Exchanges identities with a device that is accepting pairing requests. The
+/// peer isn't trusted until [`confirm_pairing`] is called, once the user has
+/// checked both devices show the returned fingerprint.
+pub fn pair_with_peer(app_state: &AppState, peer_id: &str) -> AppResult<PendingPairing> {
+    let device = local_device(app_state)?;
+    let recipient = device.recipient()?;
+    let mut stream = connect(&peer_address(peer_id)?)?;
+
+    write_json(
+        &mut stream,
+        &ClientHello::Pair {
+            protocol: PROTOCOL_VERSION,
+            device_id: device.device_id.clone(),
+            device_name: device.device_name.clone(),
+            recipient: recipient.clone(),
+        },
+    )?;
+    let reply: PairReply = read_json(&mut stream)?;
+
+    if !reply.accepted {
+        return Err(AppError::FilePermission(
+            reply
+                .error
+                .unwrap_or_else(|| "Pairing was rejected".to_string()),
+        ));
+    }
+    if reply.device_id != peer_id {
+        return Err(AppError::FilePermission(format!(
+            "Device {} answered as {}",
+            peer_id, reply.device_id
+        )));
+    }
+
+    let pending = add_pending_pairing(
+        &device,
+        &recipient,
+        PairedPeer {
+            device_id: reply.device_id,
+            device_name: reply.device_name,
+            recipient: reply.recipient,
+            paired_at: chrono::Utc::now().to_rfc3339(),
+        },
+    );
+    log(
+        "LAN_SYNC",
+        &format!(
+            "Pairing with '{}' waits for its fingerprint to be confirmed",
+            pending.peer.device_name
+        ),
+        None,
+    );
+    Ok(pending)
+}
+
+/// Trusts a pending pairing the user has confirmed by comparing fingerprints.
+/// Each device confirms its own side.
+pub fn confirm_pairing(peer_id: &str) -> AppResult<PairedPeer> {
+    let pending = take_pending_pairing(peer_id)
+        .ok_or_else(|| AppError::FileNotFound(format!("No pairing with {} is waiting", peer_id)))?;
+    let peer = PairedPeer {
+        paired_at: chrono::Utc::now().to_rfc3339(),
+        ..pending.peer
+    };
+    save_paired_peer(&paired_peers_path()?, peer.clone())?;
+    log(
+        "LAN_SYNC",
+        &format!("Paired with '{}'", peer.device_name),
+        None,
+    );
+    Ok(peer)
+}
+
+/// Drops a pending pairing, as when the fingerprints don't match.
+pub fn cancel_pairing(peer_id: &str) -> bool {
+    take_pending_pairing(peer_id).is_some()
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    app_handle: &AppHandle,
+    app_state: &AppState,
+) -> AppResult<()> {
+    prepare_stream(&stream)?;
+    let device = local_device(app_state)?;
+
+    match read_json::<_, ClientHello>(&mut stream)? {
+        ClientHello::Pair {
+            protocol,
+            device_id,
+            device_name,
+            recipient,
+        } => {
+            let error = if !take_pairing_window() {
+                Some("This device is not accepting pairing requests".to_string())
+            } else if protocol != PROTOCOL_VERSION {
+                Some(format!("Unsupported protocol version {}", protocol))
+            } else {
+                None
+            };
+
+            let own_recipient = device.recipient()?;
+            if error.is_none() {
+                let pending = add_pending_pairing(
+                    &device,
+                    &own_recipient,
+                    PairedPeer {
+                        device_id,
+                        device_name,
+                        recipient,
+                        paired_at: chrono::Utc::now().to_rfc3339(),
+                    },
+                );
+                log(
+                    "LAN_SYNC",
+                    &format!(
+                        "Pairing request from '{}' waits for its fingerprint to be confirmed",
+                        pending.peer.device_name
+                    ),
+                    None,
+                );
+                emit_event(app_handle, LanPairingRequested(pending));
+            }
+            write_json(
+                &mut stream,
+                &PairReply {
+                    accepted: error.is_none(),
+                    error,
+                    device_id: device.device_id.clone(),
+                    device_name: device.device_name.clone(),
+                    recipient: own_recipient,
+                },
+            )
+        }
+        ClientHello::Sync {
+            protocol,
+            device_id,
+        } => {
+            if protocol != PROTOCOL_VERSION {
+                return write_frame(&mut stream, &[]);
+            }
+            let challenge: SealedChallenge =
+                read_sealed_within(&mut stream, &device, MAX_HANDSHAKE_BYTES)?;
+            let Some(peer) = load_paired_peers(&paired_peers_path()?)?
+                .into_iter()
+                .find(|peer| peer.device_id == device_id)
+            else {
+                return write_frame(&mut stream, &[]);
+            };
+
+            let report = serve_sync(&mut stream, &device, &peer, challenge, app_state)?;
+            if !report.pulled.is_empty() {
+                emit_event(
+                    app_handle,
+                    CacheRefreshed {
+                        changed: report.pulled.clone(),
+                        removed: Vec::new(),
+                    },
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+fn local_notes(app_state: &AppState) -> AppResult<HashMap<String, String>> {
+    with_db(app_state, |conn| {
+        let mut stmt = conn.prepare("SELECT filename, content FROM notes")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<Result<HashMap<_, _>, _>>()?)
+    })
+}
+
+fn hashes(notes: &HashMap<String, String>) -> HashMap<String, String> {
+    notes
+        .iter()
+        .map(|(name, content)| (name.clone(), content_hash(content)))
+        .collect()
+}
+
+/// Responder side of a sync session, run after the initiator's challenge.
+fn serve_sync(
+    stream: &mut TcpStream,
+    device: &DeviceIdentity,
+    peer: &PairedPeer,
+    challenge: SealedChallenge,
+    app_state: &AppState,
+) -> AppResult<SyncReport> {
+    let _guard = SYNC_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let notes = local_notes(app_state)?;
+    let nonce = generate_nonce();
+
+    write_sealed(
+        stream,
+        &peer.recipient,
+        &SealedOffer {
+            nonce_echo: challenge.nonce,
+            nonce: nonce.clone(),
+            manifest: hashes(&notes),
+        },
+    )?;
+    let exchange: SealedExchange = read_sealed(stream, device)?;
+    if exchange.nonce_echo != nonce {
+        return Err(AppError::FilePermission(format!(
+            "Sync from '{}' failed authentication",
+            peer.device_name
+        )));
+    }
+
+    let mut report = SyncReport {
+        peer_id: peer.device_id.clone(),
+        ..Default::default()
+    };
+    for note in exchange.push {
+        let current = notes
+            .get(&note.filename)
+            .map(|content| content_hash(content));
+        if current != note.expected_hash || validate_note_name(&note.filename).is_err() {
+            report.rejected.push(note.filename);
+            continue;
+        }
+        if let Err(e) = write_note_and_index(app_state, &note.filename, &note.content) {
+            log(
+                "LAN_SYNC",
+                &format!(
+                    "Failed to save '{}' from '{}'",
+                    note.filename, peer.device_name
+                ),
+                Some(&e.to_string()),
+            );
+            report.rejected.push(note.filename);
+            continue;
+        }
+        report.pulled.push(note.filename);
+    }
+
+    let delivery = SealedDelivery {
+        notes: exchange
+            .want
+            .iter()
+            .filter_map(|name| {
+                notes.get(name).map(|content| SyncedNote {
+                    filename: name.clone(),
+                    content: content.clone(),
+                    expected_hash: None,
+                })
+            })
+            .collect(),
+        rejected: report.rejected.clone(),
+    };
+    report.pushed = delivery.notes.iter().map(|n| n.filename.clone()).collect();
+    write_sealed(stream, &peer.recipient, &delivery)?;
+
+    let bases_file = bases_path(&peer.device_id)?;
+    let mut bases = load_sync_bases(&bases_file)?;
+    for (name, hash) in exchange.bases {
+        if !report.rejected.contains(&name) {
+            bases.insert(name, hash);
+        }
+    }
+    save_sync_bases(&bases_file, &bases)?;
+
+    log(
+        "LAN_SYNC",
+        &format!(
+            "Served sync for '{}': received {}, sent {}, rejected {}",
+            peer.device_name,
+            report.pulled.len(),
+            report.pushed.len(),
+            report.rejected.len()
+        ),
+        None,
+    );
+    Ok(report)
+}
+
+/// Initiator side: compares vaults with a paired peer, exchanges changed notes
+/// and records divergent edits as conflicts for the user to resolve.
+pub fn sync_with_peer(app_state: &AppState, peer_id: &str) -> AppResult<SyncReport> {
+    SERVICES.record_activity(ServiceName::Sync);
+    let device = local_device(app_state)?;
+    let peer = load_paired_peers(&paired_peers_path()?)?
+        .into_iter()
+        .find(|peer| peer.device_id == peer_id)
+        .ok_or_else(|| AppError::FilePermission(format!("Device {} is not paired", peer_id)))?;
+    let mut stream = connect(&peer_address(peer_id)?)?;
+    let _guard = SYNC_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let nonce = generate_nonce();
+    write_json(
+        &mut stream,
+        &ClientHello::Sync {
+            protocol: PROTOCOL_VERSION,
+            device_id: device.device_id.clone(),
+        },
+    )?;
+    write_sealed(
+        &mut stream,
+        &peer.recipient,
+        &SealedChallenge {
+            nonce: nonce.clone(),
+        },
+    )?;
+    let offer: SealedOffer = read_sealed(&mut stream, &device)?;
+    if offer.nonce_echo != nonce {
+        return Err(AppError::FilePermission(format!(
+            "'{}' failed authentication",
+            peer.device_name
+        )));
+    }
+
+    let notes = local_notes(app_state)?;
+    let local_hashes = hashes(&notes);
+    let bases_file = bases_path(&peer.device_id)?;
+    let mut bases = load_sync_bases(&bases_file)?;
+    let plan = plan_sync(&local_hashes, &offer.manifest, &bases);
+
+    let mut agreed: HashMap<String, String> = HashMap::new();
+    for name in &plan.in_sync {
+        agreed.insert(name.clone(), local_hashes[name].clone());
+    }
+    for name in &plan.push {
+        agreed.insert(name.clone(), local_hashes[name].clone());
+    }
+    for name in &plan.pull {
+        agreed.insert(name.clone(), offer.manifest[name].clone());
+    }
+
+    write_sealed(
+        &mut stream,
+        &peer.recipient,
+        &SealedExchange {
+            nonce_echo: offer.nonce,
+            push: plan
+                .push
+                .iter()
+                .map(|name| SyncedNote {
+                    filename: name.clone(),
+                    content: notes[name].clone(),
+                    expected_hash: offer.manifest.get(name).cloned(),
+                })
+                .collect(),
+            want: plan.pull.iter().chain(&plan.conflicts).cloned().collect(),
+            bases: agreed.clone(),
+        },
+    )?;
+    let delivery: SealedDelivery = read_sealed(&mut stream, &device)?;
+
+    let mut report = SyncReport {
+        peer_id: peer.device_id.clone(),
+        rejected: delivery.rejected.clone(),
+        ..Default::default()
+    };
+    report.pushed = plan
+        .push
+        .iter()
+        .filter(|name| !delivery.rejected.contains(name))
+        .cloned()
+        .collect();
+
+    let conflicts_file = conflicts_path()?;
+    let mut conflicts = load_conflicts(&conflicts_file)?;
+    for note in delivery.notes {
+        let remote_hash = content_hash(&note.content);
+        if offer.manifest.get(&note.filename) != Some(&remote_hash) {
+            continue;
+        }
+        if plan.pull.contains(&note.filename) {
+            // A note that can't be saved keeps its old base, so the next sync retries it
+            if let Err(e) = validate_note_name(&note.filename)
+                .and_then(|_| write_note_and_index(app_state, &note.filename, &note.content))
+            {
+                log(
+                    "LAN_SYNC",
+                    &format!(
+                        "Failed to save '{}' from '{}'",
+                        note.filename, peer.device_name
+                    ),
+                    Some(&e.to_string()),
+                );
+                report.rejected.push(note.filename);
+                continue;
+            }
+            report.pulled.push(note.filename);
+        } else if plan.conflicts.contains(&note.filename) {
+            // The remote version becomes the base, so keeping the local side pushes it next time
+            agreed.insert(note.filename.clone(), remote_hash);
+            record_conflict(
+                &mut conflicts,
+                SyncConflict {
+                    note_name: note.filename.clone(),
+                    peer_id: peer.device_id.clone(),
+                    peer_name: peer.device_name.clone(),
+                    remote_content: note.content,
+                    detected_at: chrono::Utc::now().to_rfc3339(),
+                },
+            );
+            report.conflicts.push(note.filename);
+        }
+    }
+
+    for (name, hash) in agreed {
+        if !report.rejected.contains(&name) {
+            bases.insert(name, hash);
+        }
+    }
+    save_sync_bases(&bases_file, &bases)?;
+    save_conflicts(&conflicts_file, &conflicts)?;
+
+    log(
+        "LAN_SYNC",
+        &format!(
+            "Synced with '{}': pushed {}, pulled {}, conflicts {}, rejected {}",
+            peer.device_name,
+            report.pushed.len(),
+            report.pulled.len(),
+            report.conflicts.len(),
+            report.rejected.len()
+        ),
+        None,
+    );
+    Ok(report)
+}
@@ -2,7 +2,11 @@ mod commands;
 mod config;
 mod core;
 mod database;
+mod expiry;
+mod integrity;
+mod lan_sync;
 mod logging;
+mod reminders;
 mod search;
 mod services;
 #[cfg(test)]
@@ -13,16 +17,21 @@ mod watcher;
 use commands::*;
 use config::{load_config_with_first_run_info, parse_shortcut};
 use core::errors::AppError;
+use core::events::{emit_event, FirstRunDetected, OpenPreferences};
+use core::ipc_guard::IPC_GUARD;
+use core::safe_mode::is_safe_mode;
+use core::startup_profile::{StartupPhase, STARTUP};
 use core::state::AppState;
 use logging::log;
 use services::database_service;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::sync::OnceLock;
+use std::time::Instant;
 use tauri::{
     menu::{Menu, MenuItem, PredefinedMenuItem},
     tray::{TrayIconBuilder, TrayIconEvent},
-    AppHandle, Emitter, Manager,
+    AppHandle, Manager,
 };
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
 use watcher::setup_notes_watcher;
@@ -32,7 +41,10 @@ static DOCK_MENU_ITEM: OnceLock<MenuItem<tauri::Wry>> = OnceLock::new();
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let app_state = load_config_and_initialize_state();
+    let app_state = STARTUP.time(
+        StartupPhase::InitializeState,
+        load_config_and_initialize_state,
+    );
 
     let app = build_tauri_app_with_plugins(app_state)
         .setup(setup_app_components)
@@ -54,11 +66,41 @@ pub fn initialize_notes(app_state: &AppState) {
             Some(&e.to_string()),
         );
     }
+    // Scratchpads last one session
+    if let Err(e) = database::with_db(
+        app_state,
+        services::scratchpad_service::discard_all_scratchpads,
+    ) {
+        log(
+            "DATABASE_INIT",
+            "Failed to discard the previous session's scratchpads",
+            Some(&e.to_string()),
+        );
+    }
 }
 
 fn load_config_and_initialize_state() -> AppState {
-    let (config, was_first_run) = load_config_with_first_run_info();
-    let app_state = match AppState::new_with_fallback(config) {
+    let (config, was_first_run) =
+        STARTUP.time(StartupPhase::LoadConfig, load_config_with_first_run_info);
+    core::slow_operations::configure_query_limits(&config.preferences);
+    utilities::note_renderer::configure_markdown_extensions(&config.interface);
+    utilities::mermaid::configure_mermaid_cli(config.interface.mermaid_cli.as_deref());
+    utilities::text_encoding::configure_line_endings(&config.preferences.line_endings);
+    if is_safe_mode() {
+        log(
+            "SAFE_MODE",
+            "Starting in safe mode: file watcher, LAN sync, CRDT log and background jobs are disabled",
+            None,
+        );
+    } else {
+        utilities::citations::configure_citations(
+            &config.citations,
+            &config::get_config_notes_dir_from_config(&config),
+        );
+    }
+    let app_state = match STARTUP.time(StartupPhase::DatabaseInit, || {
+        AppState::new_with_fallback(config)
+    }) {
         Ok(state) => state,
         Err(e) => {
             log(
@@ -79,13 +121,16 @@ fn load_config_and_initialize_state() -> AppState {
         app_state.set_first_run(true);
     }
 
-    initialize_notes(&app_state);
+    STARTUP.time(StartupPhase::InitialSync, || initialize_notes(&app_state));
     app_state
 }
 
 fn build_tauri_app_with_plugins(app_state: AppState) -> tauri::Builder<tauri::Wry> {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_window_state::Builder::default().build())
         .manage(app_state)
 }
@@ -101,12 +146,35 @@ fn setup_window_configuration(app: &tauri::App) -> Result<(), Box<dyn std::error
 }
 
 fn setup_notes_watcher_for_app(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    if is_safe_mode() {
+        return Ok(());
+    }
     if let Some(app_state) = app.try_state::<AppState>() {
         setup_notes_watcher(app.handle().clone(), Arc::new(app_state.inner().clone()))?;
     }
     Ok(())
 }
 
+fn setup_lan_sync_for_app(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(app_state) = app.try_state::<AppState>() {
+        let lan_enabled = app_state
+            .config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .sync
+            .lan_enabled;
+        if lan_enabled && !is_safe_mode() {
+            // Sync is optional; a busy port or missing network must not block startup
+            if let Err(e) =
+                lan_sync::start(app.handle().clone(), Arc::new(app_state.inner().clone()))
+            {
+                log("LAN_SYNC", "Failed to start LAN sync", Some(&e.to_string()));
+            }
+        }
+    }
+    Ok(())
+}
+
 fn handle_first_run_detection(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     if let Some(app_state) = app.try_state::<AppState>() {
         if app_state
@@ -116,7 +184,7 @@ fn handle_first_run_detection(app: &tauri::App) -> Result<(), Box<dyn std::error
             let app_handle = app.handle().clone();
             std::thread::spawn(move || {
                 std::thread::sleep(std::time::Duration::from_millis(1000));
-                let _ = app_handle.emit("first-run-detected", ());
+                emit_event(&app_handle, FirstRunDetected);
             });
         }
     }
@@ -146,6 +214,29 @@ fn handle_main_window_toggle(app_handle: tauri::AppHandle) {
     }
 }
 
+/// Brings the main window forward and searches it for the clipboard text,
+/// off the event loop since the search can take a moment.
+fn handle_clipboard_search(app_handle: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        match app_handle.get_webview_window("main") {
+            Some(window) => {
+                utilities::mac_focus::save_current_frontmost_app();
+                utilities::mac_focus::show_app(window);
+            }
+            None => {
+                if let Some(app_state) = app_handle.try_state::<AppState>() {
+                    let _ = show_main_window(app_handle.clone(), app_state);
+                }
+            }
+        }
+        if let Some(app_state) = app_handle.try_state::<AppState>() {
+            if let Err(e) = search_clipboard(app_handle.clone(), app_state) {
+                log("CLIPBOARD_SEARCH", "Clipboard search failed", Some(&e));
+            }
+        }
+    });
+}
+
 fn setup_global_shortcuts(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     #[cfg(desktop)]
     {
@@ -161,6 +252,13 @@ fn setup_global_shortcuts(app: &tauri::App) -> Result<(), Box<dyn std::error::Er
         let main_shortcut = parse_shortcut(&config.global_shortcut).unwrap_or_else(|| {
             Shortcut::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyN)
         });
+        // Empty disables quick capture; it never takes over the main shortcut
+        let capture_shortcut =
+            parse_shortcut(&config.capture.shortcut).filter(|shortcut| shortcut != &main_shortcut);
+        let clipboard_search_shortcut = parse_shortcut(&config.capture.clipboard_search_shortcut)
+            .filter(|shortcut| {
+                shortcut != &main_shortcut && Some(shortcut) != capture_shortcut.as_ref()
+            });
 
         app.handle()
             .plugin(
@@ -170,6 +268,10 @@ fn setup_global_shortcuts(app: &tauri::App) -> Result<(), Box<dyn std::error::Er
                             if shortcut == &main_shortcut {
                                 let app_handle = app.clone();
                                 handle_main_window_toggle(app_handle);
+                            } else if Some(shortcut) == capture_shortcut.as_ref() {
+                                let _ = show_capture_window(app.clone());
+                            } else if Some(shortcut) == clipboard_search_shortcut.as_ref() {
+                                handle_clipboard_search(app.clone());
                             }
                         }
                     })
@@ -180,6 +282,27 @@ fn setup_global_shortcuts(app: &tauri::App) -> Result<(), Box<dyn std::error::Er
         app.global_shortcut()
             .register(main_shortcut)
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+
+        if let Some(capture_shortcut) = capture_shortcut {
+            // Another app may own the combination; capture is optional
+            if let Err(e) = app.global_shortcut().register(capture_shortcut) {
+                log(
+                    "GLOBAL_SHORTCUT",
+                    "Failed to register quick capture shortcut",
+                    Some(&e.to_string()),
+                );
+            }
+        }
+
+        if let Some(clipboard_search_shortcut) = clipboard_search_shortcut {
+            if let Err(e) = app.global_shortcut().register(clipboard_search_shortcut) {
+                log(
+                    "GLOBAL_SHORTCUT",
+                    "Failed to register clipboard search shortcut",
+                    Some(&e.to_string()),
+                );
+            }
+        }
     }
     Ok(())
 }
@@ -187,9 +310,19 @@ fn setup_global_shortcuts(app: &tauri::App) -> Result<(), Box<dyn std::error::Er
 fn setup_app_components(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     setup_tray(app.handle())?;
     setup_window_configuration(app)?;
-    setup_notes_watcher_for_app(app)?;
+    STARTUP.time(StartupPhase::WatcherSetup, || {
+        setup_notes_watcher_for_app(app)
+    })?;
+    setup_lan_sync_for_app(app)?;
+    if !is_safe_mode() {
+        reminders::start(app.handle().clone());
+    }
     handle_first_run_detection(app)?;
-    setup_global_shortcuts(app)?;
+    utilities::mac_services::register_services(app.handle());
+    STARTUP.time(StartupPhase::ShortcutRegistration, || {
+        setup_global_shortcuts(app)
+    })?;
+    STARTUP.finish();
     Ok(())
 }
 
@@ -205,35 +338,199 @@ fn handle_window_events(window: &tauri::Window, event: &tauri::WindowEvent) {
             }
             api.prevent_close();
         }
+        tauri::WindowEvent::Destroyed => {
+            if let Some(app_state) = window.try_state::<AppState>() {
+                release_window_edit_locks(window.app_handle(), &app_state, window.label());
+            }
+        }
         _ => {}
     }
 }
 
 fn register_command_handlers(
 ) -> impl Fn(tauri::ipc::Invoke<tauri::Wry>) -> bool + Send + Sync + 'static {
+    let handler = Arc::new(command_handlers());
+    move |invoke: tauri::ipc::Invoke<tauri::Wry>| {
+        // Calls over their limit are rejected with a `Busy:` error instead of
+        // queueing behind the database lock
+        let command = invoke.message.command().to_string();
+        let permit = match IPC_GUARD.acquire(&command, Instant::now()) {
+            Ok(permit) => permit,
+            Err(e) => {
+                invoke.resolver.reject(e.to_string());
+                return true;
+            }
+        };
+        if !IPC_GUARD.is_listed(&command) {
+            return handler(invoke);
+        }
+
+        // Throttled commands run off the main thread, holding the permit until
+        // they have answered
+        let handler = Arc::clone(&handler);
+        tauri::async_runtime::spawn_blocking(move || {
+            handler(invoke);
+            drop(permit);
+        });
+        true
+    }
+}
+
+fn command_handlers() -> impl Fn(tauri::ipc::Invoke<tauri::Wry>) -> bool + Send + Sync + 'static {
     tauri::generate_handler![
         search_notes,
+        search_clipboard,
+        grep_notes,
+        replace_in_notes,
+        quick_switch,
+        fuzzy_match_filenames,
+        search_notes_by_tag,
+        rename_tag,
+        merge_tags,
+        preview_tag_rules,
+        search_notes_by_metadata,
+        query_notes_by_properties,
+        get_keyword_cloud,
+        list_all_tags,
         get_note_content,
+        get_note_content_resolved,
         get_note_html_content,
+        get_note_excerpt,
+        get_note_outline,
         create_new_note,
+        create_untitled_note,
+        duplicate_note,
+        split_note_by_heading,
+        list_templates,
+        create_note_from_template,
         delete_note,
         rename_note,
+        create_folder,
+        rename_folder,
+        delete_folder,
+        move_note_to_folder,
         save_note_with_content_check,
         initialize_notes_with_progress,
         refresh_cache,
+        rerender_all_notes,
+        cancel_rerender,
+        audit_html_renders,
+        run_readonly_query,
+        get_safe_mode,
+        get_service_status,
+        get_startup_report,
+        get_last_sync_report,
+        get_sync_warnings,
+        get_integrity_alerts,
+        verify_note_integrity,
+        dismiss_integrity_alert,
+        get_slow_operations,
+        restart_service,
         open_note_in_editor,
+        end_external_edit,
+        acquire_edit_lock,
+        release_edit_lock,
+        get_edit_lock_holder,
+        get_external_changes,
+        acknowledge_change,
         open_note_folder,
-        list_all_notes,
+        list_notes,
+        list_notes_detailed,
+        get_note_metadata,
+        get_stale_notes,
+        list_expiring_notes,
+        batch_invoke,
+        set_note_appearance,
+        get_frontmatter,
+        set_frontmatter_field,
         get_note_versions,
+        label_version,
+        get_recoverable_writes,
+        discard_recoverable_write,
         get_version_content,
         recover_note_version,
         get_deleted_files,
         recover_deleted_file,
+        list_trash,
+        restore_from_trash,
+        empty_trash,
+        lint_note,
+        lint_vault,
+        get_vault_health_report,
+        export_note_docx,
+        export_note_slides,
+        export_note_plaintext,
+        export_outline,
+        list_export_templates,
+        export_note_with_template,
+        export_search_results,
+        get_dictionary,
+        add_word_to_dictionary,
+        remove_word,
+        list_snippets,
+        save_snippet,
+        expand_snippet,
+        resolve_citation,
+        resolve_reference,
+        insert_reference,
+        publish_vault,
+        list_publishable_notes,
+        add_to_reading_list,
+        list_reading_queue,
+        extract_highlights,
+        set_reading_progress,
+        generate_rollup,
+        parse_meeting_note,
+        get_mentions,
+        get_project_overview,
+        get_time_report,
+        get_habit_history,
+        list_upcoming_reminders,
+        snooze_reminder,
+        create_scratchpad,
+        get_scratchpads,
+        save_scratchpad,
+        promote_scratchpad,
+        discard_scratchpad,
+        resolve_date,
+        get_notes_by_date_range,
+        create_share_bundle,
+        import_share_bundle,
+        get_crdt_state,
+        apply_remote_changes,
+        start_lan_sync,
+        stop_lan_sync,
+        list_lan_peers,
+        list_paired_devices,
+        start_lan_pairing,
+        pair_lan_device,
+        confirm_lan_pairing,
+        cancel_lan_pairing,
+        unpair_lan_device,
+        sync_with_lan_peer,
+        list_sync_conflicts,
+        resolve_sync_conflict,
         show_main_window,
         hide_main_window,
+        show_capture_window,
+        hide_capture_window,
+        append_to_note,
+        email_note,
+        set_smtp_password,
+        clear_smtp_password,
+        send_note_to_channel,
+        get_notes_near,
+        geocode_note_locations,
+        export_locations_map,
         get_config_content,
         save_config_content,
         config_exists,
+        check_vault,
+        list_vaults,
+        add_vault,
+        switch_vault,
+        export_vault_archive,
+        restore_vault_archive,
         get_general_config,
         get_interface_config,
         get_editor_config,
@@ -242,6 +539,9 @@ fn register_command_handlers(
         scan_available_themes,
         load_custom_theme_file,
         validate_theme_path,
+        list_path_grants,
+        choose_path_grant,
+        revoke_path_access,
         utilities::mac_focus::save_current_frontmost_app,
         utilities::mac_focus::show_app,
         utilities::mac_focus::hide_app_and_restore_previous
@@ -289,7 +589,7 @@ fn handle_tray_menu_event(app: &tauri::AppHandle, event: &tauri::menu::MenuEvent
                 let _ = show_main_window(app_handle.clone(), app_state);
             }
             if let Some(window) = app_handle.get_webview_window("main") {
-                let _ = window.emit("open-preferences", ());
+                emit_event(&window, OpenPreferences);
             }
         }
         "toggle_dock" => {
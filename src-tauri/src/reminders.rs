@@ -0,0 +1,149 @@
+//! Background scheduler turning due tasks (`📅 2025-03-01` or `due:2025-03-01`)
+//! into desktop notifications, checked once a minute. It also runs the
+//! periodic note integrity check, see [`crate::integrity`], and handles notes
+//! past their `expires` date, see [`crate::expiry`].
+
+use crate::{
+    core::{
+        service_health::{ServiceName, SERVICES},
+        state::AppState,
+        AppResult,
+    },
+    database::with_db,
+    logging::log,
+    services::reminder_service::{due_reminders, mark_notified, parse_reminder_time, Reminder},
+    utilities::strings::extract_title_from_filename,
+};
+use chrono::{Local, NaiveTime};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Reminders due at once beyond this are summed up in one notification
+const MAX_SEPARATE_NOTIFICATIONS: usize = 3;
+
+/// Bumped on every start and stop; a check thread exits once it's outdated
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Starts the reminder check, replacing one already running. Whether
+/// notifications are shown is read from `[reminders]` on every check, so
+/// turning them off needs no restart.
+pub fn start(app_handle: AppHandle) {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    SERVICES.mark_running(ServiceName::Scheduler);
+    thread::spawn(move || {
+        while GENERATION.load(Ordering::SeqCst) == generation {
+            match check_due_reminders(&app_handle) {
+                Ok(()) => SERVICES.record_activity(ServiceName::Scheduler),
+                Err(e) => log(
+                    "REMINDERS",
+                    "Failed to check due reminders",
+                    Some(&e.to_string()),
+                ),
+            }
+            if let Err(e) = crate::integrity::verify_if_due(&app_handle) {
+                log(
+                    "INTEGRITY",
+                    "Failed to verify note integrity",
+                    Some(&e.to_string()),
+                );
+            }
+            if let Err(e) = crate::expiry::handle_expired_notes(&app_handle) {
+                log(
+                    "EXPIRY",
+                    "Failed to handle expired notes",
+                    Some(&e.to_string()),
+                );
+            }
+            thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+pub fn stop() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    SERVICES.mark_stopped(ServiceName::Scheduler);
+}
+
+fn check_due_reminders(app_handle: &AppHandle) -> AppResult<()> {
+    let Some(app_state) = app_handle.try_state::<AppState>() else {
+        return Ok(());
+    };
+    let notify_time = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        if !config.reminders.enabled {
+            return Ok(());
+        }
+        parse_reminder_time(&config.reminders.notify_time).unwrap_or(NaiveTime::MIN)
+    };
+
+    let now = Local::now();
+    let reminders = with_db(&app_state, |conn| {
+        let reminders = due_reminders(conn, now.naive_local(), notify_time, now.timestamp())?;
+        if !reminders.is_empty() {
+            mark_notified(conn, &reminders, now.date_naive(), now.timestamp())?;
+        }
+        Ok(reminders)
+    })?;
+    notify(app_handle, &reminders);
+    Ok(())
+}
+
+fn reminder_title(reminder: &Reminder) -> &'static str {
+    if reminder.overdue {
+        "Task overdue"
+    } else {
+        "Task due today"
+    }
+}
+
+fn notify(app_handle: &AppHandle, reminders: &[Reminder]) {
+    let notifications: Vec<(String, String)> = if reminders.len() <= MAX_SEPARATE_NOTIFICATIONS {
+        reminders
+            .iter()
+            .map(|reminder| {
+                (
+                    reminder_title(reminder).to_string(),
+                    format!(
+                        "{}\n{}",
+                        reminder.task,
+                        extract_title_from_filename(&reminder.note_name)
+                    ),
+                )
+            })
+            .collect()
+    } else {
+        let tasks: Vec<&str> = reminders
+            .iter()
+            .take(MAX_SEPARATE_NOTIFICATIONS)
+            .map(|reminder| reminder.task.as_str())
+            .collect();
+        vec![(
+            format!("{} tasks due", reminders.len()),
+            format!(
+                "{}\nand {} more",
+                tasks.join("\n"),
+                reminders.len() - tasks.len()
+            ),
+        )]
+    };
+
+    for (title, body) in notifications {
+        if let Err(e) = app_handle
+            .notification()
+            .builder()
+            .title(title)
+            .body(body)
+            .show()
+        {
+            log(
+                "REMINDERS",
+                "Failed to show reminder notification",
+                Some(&e.to_string()),
+            );
+        }
+    }
+}
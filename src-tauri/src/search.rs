@@ -1,18 +1,91 @@
 use crate::core::{AppError, AppResult};
+use crate::services::{derived_index_service::refresh_index, tag_service::NOTE_TAGS_INDEX};
 use crate::utilities::strings::{
-    extract_title_from_content, extract_title_from_filename, sanitize_fts_query,
+    extract_title_from_content, extract_title_from_filename, fold_search_text, sanitize_fts_query,
 };
+use crate::utilities::tags::normalize_tag;
 use nucleo_matcher::{Config, Matcher, Utf32Str};
-use rusqlite::params;
+use rusqlite::{params_from_iter, types::Value, Connection};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
-#[derive(Debug, Clone)]
+/// Private-use characters FTS5 wraps matched terms in. They become `<mark>`
+/// tags only after the surrounding text is HTML-escaped, so note content
+/// can't inject markup into results.
+const MATCH_START: &str = "\u{E000}";
+const MATCH_END: &str = "\u{E001}";
+/// Approximate number of tokens in a content snippet
+const SNIPPET_TOKENS: i32 = 16;
+/// FTS matches re-ranked by the hybrid scorer for relevance sorting, unless a
+/// later page needs more
+const RELEVANCE_CANDIDATES: usize = 500;
+/// bm25() column weights: filename, content, html_render
+const BM25_RANKING: &str = "bm25(notes, 10.0, 1.0, 0.0)";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSort {
+    /// Hybrid title/content scoring on top of bm25(); recency for an empty query
+    #[default]
+    Relevance,
+    ModifiedDesc,
+    ModifiedAsc,
+    Filename,
+}
+
+impl SearchSort {
+    fn order_by(self) -> &'static str {
+        match self {
+            SearchSort::Relevance => BM25_RANKING,
+            SearchSort::ModifiedDesc => "modified DESC, filename",
+            SearchSort::ModifiedAsc => "modified ASC, filename",
+            SearchSort::Filename => "filename COLLATE NOCASE, filename",
+        }
+    }
+}
+
+/// Restricts a search to part of the vault. Unset fields don't restrict.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SearchScope {
+    /// Folder prefix, e.g. `Projects` or `Projects/2024` (case-sensitive)
+    pub folder: Option<String>,
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Results to skip, for paging
+    pub offset: usize,
+    pub limit: usize,
+    pub sort: SearchSort,
+    pub scope: SearchScope,
+}
+
+impl SearchOptions {
+    pub fn first(limit: usize) -> Self {
+        Self {
+            offset: 0,
+            limit,
+            sort: SearchSort::Relevance,
+            scope: SearchScope::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct SearchResult {
     pub filename: String,
     pub title: String,
     pub score: u32,
+    #[serde(skip)]
     match_type: MatchType,
     pub modified: i64,
+    /// Excerpt of the content around the matched terms, HTML-escaped with
+    /// matches wrapped in `<mark>`; `None` when listing recent notes
+    pub snippet: Option<String>,
+    /// The filename marked up the same way as `snippet`
+    pub highlighted_filename: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -29,6 +102,8 @@ struct SearchCandidate {
     title: String,
     content: String,
     modified: i64,
+    snippet: String,
+    highlighted_filename: String,
 }
 
 pub struct HybridSearcher {
@@ -45,108 +120,142 @@ impl HybridSearcher {
         &mut self,
         app_state: &crate::core::state::AppState,
         query: &str,
-        max_results: usize,
-    ) -> AppResult<Vec<String>> {
+        options: &SearchOptions,
+    ) -> AppResult<Vec<SearchResult>> {
+        crate::database::with_db(app_state, |conn| self.search_in(conn, query, options))
+    }
+
+    pub fn search_in(
+        &mut self,
+        conn: &Connection,
+        query: &str,
+        options: &SearchOptions,
+    ) -> AppResult<Vec<SearchResult>> {
         if query.trim().is_empty() {
-            return self.get_recent_notes(app_state, max_results);
+            return self.get_recent_notes(conn, options);
         }
 
-        let candidates = self.get_candidates_from_sqlite(app_state, query)?;
+        let Some(fts_pattern) = build_fts_pattern(query) else {
+            return Ok(Vec::new());
+        };
+
+        if options.sort != SearchSort::Relevance {
+            // The order comes from SQL, so every FTS match is kept; the hybrid
+            // score is informational
+            let candidates = self.get_candidates_from_sqlite(
+                conn,
+                &fts_pattern,
+                &options.scope,
+                options.sort,
+                options.limit,
+                options.offset,
+            )?;
+            return Ok(candidates
+                .iter()
+                .map(|candidate| {
+                    let (score, match_type) = self
+                        .score_candidate(candidate, query)
+                        .unwrap_or((0, MatchType::Content));
+                    candidate_result(candidate, score, match_type)
+                })
+                .collect());
+        }
+
+        let window = options
+            .offset
+            .saturating_add(options.limit)
+            .max(RELEVANCE_CANDIDATES);
+        let candidates = self.get_candidates_from_sqlite(
+            conn,
+            &fts_pattern,
+            &options.scope,
+            SearchSort::Relevance,
+            window,
+            0,
+        )?;
         let mut results = Vec::new();
 
-        for candidate in candidates {
-            if let Some(result) = self.score_candidate(&candidate, query) {
-                results.push(result);
+        for candidate in &candidates {
+            if let Some((score, match_type)) = self.score_candidate(candidate, query) {
+                results.push(candidate_result(candidate, score, match_type));
             }
         }
 
         results.sort_by(|a, b| self.compare_results(a, b));
-        results.truncate(max_results);
 
-        Ok(results.into_iter().map(|r| r.filename).collect())
+        Ok(results
+            .into_iter()
+            .skip(options.offset)
+            .take(options.limit)
+            .collect())
     }
 
     fn get_candidates_from_sqlite(
         &self,
-        app_state: &crate::core::state::AppState,
-        query: &str,
+        conn: &Connection,
+        fts_pattern: &str,
+        scope: &SearchScope,
+        sort: SearchSort,
+        limit: usize,
+        offset: usize,
     ) -> AppResult<Vec<SearchCandidate>> {
-        let sanitized_query = sanitize_fts_query(query);
-
-        if sanitized_query.trim().is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let fts_pattern = if sanitized_query.contains(' ') {
-            sanitized_query
-                .split_whitespace()
-                .filter(|word| !word.trim().is_empty())
-                .map(|word| format!("{}*", word))
-                .collect::<Vec<_>>()
-                .join(" OR ")
-        } else {
-            format!("{}*", sanitized_query)
-        };
-
-        crate::database::with_db(app_state, |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT filename, content, modified FROM notes
-                     WHERE notes MATCH ?
-                     ORDER BY rank
-                     LIMIT 500",
-            )?;
-
-            let rows = stmt.query_map(params![fts_pattern], |row| {
-                let filename: String = row.get(0)?;
-                let content: String = row.get(1)?;
-                let modified: i64 = row.get(2)?;
-
-                let title = extract_title_from_content(&content)
-                    .unwrap_or_else(|| extract_title_from_filename(&filename));
-
-                Ok(SearchCandidate {
-                    filename,
-                    title,
-                    content,
-                    modified,
-                })
-            })?;
+        let mut values: Vec<Value> = vec![
+            fts_pattern.to_string().into(),
+            MATCH_START.to_string().into(),
+            MATCH_END.to_string().into(),
+            i64::from(SNIPPET_TOKENS).into(),
+            sql_count(limit).into(),
+            sql_count(offset).into(),
+        ];
+        let scope_sql = scope_conditions(conn, scope, &mut values)?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT filename, content, modified,
+                    snippet(notes, 1, ?2, ?3, '…', ?4),
+                    highlight(notes, 0, ?2, ?3)
+                 FROM notes
+                 WHERE notes MATCH ?1{}
+                 ORDER BY {}
+                 LIMIT ?5 OFFSET ?6",
+            scope_sql,
+            sort.order_by()
+        ))?;
+
+        let rows = stmt.query_map(params_from_iter(values), |row| {
+            let filename: String = row.get(0)?;
+            let content: String = row.get(1)?;
+            let modified: i64 = row.get(2)?;
+
+            let title = extract_title_from_content(&content)
+                .unwrap_or_else(|| extract_title_from_filename(&filename));
+
+            Ok(SearchCandidate {
+                filename,
+                title,
+                content,
+                modified,
+                snippet: row.get(3)?,
+                highlighted_filename: row.get(4)?,
+            })
+        })?;
 
-            let candidates = rows.collect::<Result<Vec<_>, _>>()?;
-            Ok(candidates)
-        })
+        let candidates = rows.collect::<Result<Vec<_>, _>>()?;
+        Ok(candidates)
     }
 
     fn score_candidate(
         &mut self,
         candidate: &SearchCandidate,
         query: &str,
-    ) -> Option<SearchResult> {
-        let query_lower = query.to_lowercase();
-        let title_lower = candidate.title.to_lowercase();
-        let filename_lower = candidate.filename.to_lowercase();
-
-        if let Some((score, match_type)) =
-            self.score_title_match(&title_lower, &filename_lower, &query_lower)
-        {
-            Some(SearchResult {
-                filename: candidate.filename.clone(),
-                title: candidate.title.clone(),
-                score,
-                match_type,
-                modified: candidate.modified,
-            })
-        } else if let Some(score) = self.score_content_match(&candidate.content, &query_lower) {
-            Some(SearchResult {
-                filename: candidate.filename.clone(),
-                title: candidate.title.clone(),
-                score,
-                match_type: MatchType::Content,
-                modified: candidate.modified,
+    ) -> Option<(u32, MatchType)> {
+        let query_lower = fold_search_text(query);
+        let title_lower = fold_search_text(&candidate.title);
+        let filename_lower = fold_search_text(&candidate.filename);
+
+        self.score_title_match(&title_lower, &filename_lower, &query_lower)
+            .or_else(|| {
+                self.score_content_match(&candidate.content, &query_lower)
+                    .map(|score| (score, MatchType::Content))
             })
-        } else {
-            None
-        }
     }
 
     fn score_title_match(
@@ -183,7 +292,7 @@ impl HybridSearcher {
     }
 
     fn score_content_match(&mut self, content: &str, query_lower: &str) -> Option<u32> {
-        let content_lower = content.to_lowercase();
+        let content_lower = fold_search_text(content);
 
         if content_lower.contains(query_lower) {
             let count = content_lower.matches(query_lower).count() as u32;
@@ -213,27 +322,154 @@ impl HybridSearcher {
 
     fn get_recent_notes(
         &self,
-        app_state: &crate::core::state::AppState,
-        max_results: usize,
-    ) -> AppResult<Vec<String>> {
-        crate::database::with_db(app_state, |conn| {
-            let mut stmt =
-                conn.prepare("SELECT filename FROM notes ORDER BY modified DESC LIMIT ?")?;
+        conn: &Connection,
+        options: &SearchOptions,
+    ) -> AppResult<Vec<SearchResult>> {
+        let order_by = match options.sort {
+            SearchSort::Relevance => SearchSort::ModifiedDesc.order_by(),
+            sort => sort.order_by(),
+        };
+        let mut values: Vec<Value> = vec![
+            sql_count(options.limit).into(),
+            sql_count(options.offset).into(),
+        ];
+        let scope_sql = scope_conditions(conn, &options.scope, &mut values)?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT filename, content, modified FROM notes
+                 WHERE 1 = 1{}
+                 ORDER BY {}
+                 LIMIT ?1 OFFSET ?2",
+            scope_sql, order_by
+        ))?;
+
+        let rows = stmt.query_map(params_from_iter(values), |row| {
+            let filename: String = row.get(0)?;
+            let content: String = row.get(1)?;
+            let title = extract_title_from_content(&content)
+                .unwrap_or_else(|| extract_title_from_filename(&filename));
+            Ok(SearchResult {
+                filename,
+                title,
+                score: 0,
+                match_type: MatchType::Content,
+                modified: row.get(2)?,
+                snippet: None,
+                highlighted_filename: None,
+            })
+        })?;
 
-            let rows = stmt.query_map([max_results], |row| row.get(0))?;
+        let results = rows.collect::<Result<Vec<_>, _>>()?;
+        Ok(results)
+    }
+}
 
-            let filenames = rows.collect::<Result<Vec<_>, _>>()?;
-            Ok(filenames)
-        })
+/// Prefix-matches every word of the query; `None` when nothing searchable is
+/// left after sanitizing. A word with diacritics also matches its folded form,
+/// for tokenizers that don't fold them (`ascii`) and decomposed input.
+fn build_fts_pattern(query: &str) -> Option<String> {
+    let sanitized_query = sanitize_fts_query(query);
+
+    if sanitized_query.trim().is_empty() {
+        return None;
     }
+
+    Some(
+        sanitized_query
+            .split_whitespace()
+            .flat_map(|word| {
+                let folded = fold_search_text(word);
+                let mut terms = vec![format!("{}*", word)];
+                if folded != word.to_lowercase() && !folded.is_empty() {
+                    terms.push(format!("{}*", folded));
+                }
+                terms
+            })
+            .collect::<Vec<_>>()
+            .join(" OR "),
+    )
+}
+
+fn candidate_result(
+    candidate: &SearchCandidate,
+    score: u32,
+    match_type: MatchType,
+) -> SearchResult {
+    SearchResult {
+        filename: candidate.filename.clone(),
+        title: candidate.title.clone(),
+        score,
+        match_type,
+        modified: candidate.modified,
+        snippet: Some(mark_matches(&candidate.snippet)),
+        highlighted_filename: Some(mark_matches(&candidate.highlighted_filename)),
+    }
+}
+
+/// `AND` conditions restricting the notes table to `scope`, numbered after
+/// the parameters already in `values`, which the scope's own values are
+/// appended to.
+pub(crate) fn scope_conditions(
+    conn: &Connection,
+    scope: &SearchScope,
+    values: &mut Vec<Value>,
+) -> AppResult<String> {
+    let mut sql = String::new();
+
+    let folder = scope
+        .folder
+        .as_deref()
+        .map(|folder| folder.trim().trim_matches('/'))
+        .filter(|folder| !folder.is_empty());
+    if let Some(folder) = folder {
+        values.push(format!("{}/", folder).into());
+        let n = values.len();
+        sql.push_str(&format!(" AND substr(filename, 1, length(?{n})) = ?{n}"));
+    }
+
+    let tag = scope
+        .tag
+        .as_deref()
+        .map(normalize_tag)
+        .filter(|tag| !tag.is_empty());
+    if let Some(tag) = tag {
+        refresh_index(conn, &NOTE_TAGS_INDEX)?;
+        values.push(tag.into());
+        sql.push_str(&format!(
+            " AND filename IN (SELECT filename FROM note_tag_paths WHERE tag = ?{})",
+            values.len()
+        ));
+    }
+
+    Ok(sql)
+}
+
+/// LIMIT/OFFSET value; SQLite integers are signed, so huge counts saturate.
+fn sql_count(count: usize) -> i64 {
+    i64::try_from(count).unwrap_or(i64::MAX)
+}
+
+/// HTML-escapes FTS5 output and turns the match markers into `<mark>` tags.
+pub fn mark_matches(text: &str) -> String {
+    html_escape::encode_text(text)
+        .replace(MATCH_START, "<mark>")
+        .replace(MATCH_END, "</mark>")
 }
 
 pub fn search_notes_hybrid(
     app_state: &crate::core::state::AppState,
     query: &str,
     max_results: usize,
-) -> AppResult<Vec<String>> {
+) -> AppResult<Vec<SearchResult>> {
+    search_notes_paged(app_state, query, &SearchOptions::first(max_results))
+}
+
+/// One page of results for `query` in the order `options.sort` asks for.
+pub fn search_notes_paged(
+    app_state: &crate::core::state::AppState,
+    query: &str,
+    options: &SearchOptions,
+) -> AppResult<Vec<SearchResult>> {
     let mut searcher =
         HybridSearcher::new().map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
-    searcher.search(app_state, query, max_results)
+    searcher.search(app_state, query, options)
 }
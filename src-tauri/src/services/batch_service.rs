@@ -0,0 +1,108 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::{
+        derived_index_service::refresh_index,
+        link_service::{backlinks, Backlink, NOTE_LINKS_INDEX},
+        note_details_service::{note_stats, NoteStats, NOTE_STATS_INDEX},
+        tag_service::NOTE_TAGS_INDEX,
+    },
+    utilities::{
+        strings::{note_excerpt, DEFAULT_EXCERPT_CHARS},
+        validation::validate_note_name,
+    },
+};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+/// A read-only query `batch_invoke` can run
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum BatchRequest {
+    Metadata {
+        note_name: String,
+    },
+    Preview {
+        note_name: String,
+        max_chars: Option<usize>,
+    },
+    Backlinks {
+        note_name: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "command", content = "value", rename_all = "snake_case")]
+pub enum BatchValue {
+    Metadata(NoteStats),
+    Preview(String),
+    Backlinks(Vec<Backlink>),
+}
+
+/// Outcome of one request; a failing request doesn't fail the others
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchResponse {
+    pub result: Option<BatchValue>,
+    pub error: Option<String>,
+}
+
+/// Answers every request from one consistent snapshot of the database, in
+/// request order.
+pub fn run_batch(conn: &Connection, requests: &[BatchRequest]) -> AppResult<Vec<BatchResponse>> {
+    // Indexes refresh in their own transaction, so bring them up to date
+    // before taking the snapshot
+    refresh_index(conn, &NOTE_STATS_INDEX)?;
+    refresh_index(conn, &NOTE_TAGS_INDEX)?;
+    if requests
+        .iter()
+        .any(|request| matches!(request, BatchRequest::Backlinks { .. }))
+    {
+        refresh_index(conn, &NOTE_LINKS_INDEX)?;
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let responses = requests
+        .iter()
+        .map(|request| match run_request(&tx, request) {
+            Ok(value) => BatchResponse {
+                result: Some(value),
+                error: None,
+            },
+            Err(e) => BatchResponse {
+                result: None,
+                error: Some(e.to_string()),
+            },
+        })
+        .collect();
+    tx.commit()?;
+    Ok(responses)
+}
+
+fn run_request(conn: &Connection, request: &BatchRequest) -> AppResult<BatchValue> {
+    match request {
+        BatchRequest::Metadata { note_name } => {
+            validate_note_name(note_name)?;
+            Ok(BatchValue::Metadata(note_stats(conn, note_name)?))
+        }
+        BatchRequest::Preview {
+            note_name,
+            max_chars,
+        } => {
+            validate_note_name(note_name)?;
+            let content: String = conn
+                .query_row(
+                    "SELECT content FROM notes WHERE filename = ?1",
+                    params![note_name],
+                    |row| row.get(0),
+                )
+                .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", note_name)))?;
+            Ok(BatchValue::Preview(note_excerpt(
+                &content,
+                max_chars.unwrap_or(DEFAULT_EXCERPT_CHARS).max(1),
+            )))
+        }
+        BatchRequest::Backlinks { note_name } => {
+            validate_note_name(note_name)?;
+            Ok(BatchValue::Backlinks(backlinks(conn, note_name)?))
+        }
+    }
+}
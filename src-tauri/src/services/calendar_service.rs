@@ -0,0 +1,101 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::{habit_service::in_daily_notes_folder, note_created_service::NOTE_CREATED_SCHEMA},
+    utilities::dates::date_from_filename,
+};
+use chrono::{Days, NaiveDate, NaiveTime};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Longest range returned in one call, roughly ten years
+pub const MAX_CALENDAR_DAYS: i64 = 3660;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Notes belonging to one day of a calendar view
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CalendarDay {
+    pub date: String,
+    /// Notes created that day
+    pub created: Vec<String>,
+    /// Notes last modified that day
+    pub modified: Vec<String>,
+    /// Daily notes named after the day, such as `Journal/2024-06-01.md`
+    pub daily_notes: Vec<String>,
+}
+
+/// Notes grouped by the day they were created, last modified or are a daily
+/// note for, for days from `start` to `end` inclusive. Only days with notes are
+/// listed, oldest first. `day_of` turns Unix seconds into the local date.
+pub fn notes_by_date_range(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    daily_notes_folder: &str,
+    day_of: impl Fn(i64) -> Option<NaiveDate>,
+) -> AppResult<Vec<CalendarDay>> {
+    if start > end {
+        return Err(AppError::validation_error(
+            "end",
+            "End date is before the start date",
+        ));
+    }
+    if (end - start).num_days() >= MAX_CALENDAR_DAYS {
+        return Err(AppError::validation_error(
+            "end",
+            "Calendar ranges are limited to ten years at a time",
+        ));
+    }
+    conn.execute_batch(NOTE_CREATED_SCHEMA)?;
+
+    // Timestamps a day either side of the range cover every time zone; the
+    // exact local date is checked below
+    let timestamp = |date: NaiveDate| date.and_time(NaiveTime::MIN).and_utc().timestamp();
+    let earliest = timestamp(start) - SECONDS_PER_DAY;
+    let latest = end
+        .checked_add_days(Days::new(1))
+        .map_or(i64::MAX, |day| timestamp(day) + SECONDS_PER_DAY);
+
+    let mut stmt = conn.prepare(
+        "SELECT notes.filename, notes.modified, note_created.created
+         FROM notes LEFT JOIN note_created ON note_created.filename = notes.filename
+         WHERE notes.modified BETWEEN ?1 AND ?2
+            OR note_created.created BETWEEN ?1 AND ?2
+            OR notes.filename GLOB '*[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]*'
+         ORDER BY notes.filename",
+    )?;
+    let rows = stmt.query_map(params![earliest, latest], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, Option<i64>>(2)?,
+        ))
+    })?;
+
+    let in_range = |date: &NaiveDate| (start..=end).contains(date);
+    let mut days: BTreeMap<NaiveDate, CalendarDay> = BTreeMap::new();
+    for row in rows {
+        let (filename, modified, created) = row?;
+        if let Some(date) = created.and_then(&day_of).filter(in_range) {
+            day_entry(&mut days, date).created.push(filename.clone());
+        }
+        if let Some(date) = day_of(modified).filter(in_range) {
+            day_entry(&mut days, date).modified.push(filename.clone());
+        }
+        if let Some(date) = date_from_filename(&filename).filter(in_range) {
+            if in_daily_notes_folder(&filename, daily_notes_folder) {
+                day_entry(&mut days, date).daily_notes.push(filename);
+            }
+        }
+    }
+
+    Ok(days.into_values().collect())
+}
+
+fn day_entry(days: &mut BTreeMap<NaiveDate, CalendarDay>, date: NaiveDate) -> &mut CalendarDay {
+    days.entry(date).or_insert_with(|| CalendarDay {
+        date: date.to_string(),
+        ..Default::default()
+    })
+}
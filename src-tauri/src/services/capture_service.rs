@@ -0,0 +1,19 @@
+use crate::core::{AppError, AppResult};
+
+/// Content of a note after appending captured `text`: the text starts on its
+/// own line and the note ends with a newline.
+pub fn append_text(existing: &str, text: &str) -> AppResult<String> {
+    let text = text.trim_end();
+    if text.trim().is_empty() {
+        return Err(AppError::validation_error("text", "Nothing to append"));
+    }
+
+    let mut content = String::with_capacity(existing.len() + text.len() + 2);
+    content.push_str(existing);
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(text);
+    content.push('\n');
+    Ok(content)
+}
@@ -0,0 +1,237 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::publish_service::SourceNote,
+    utilities::{
+        frontmatter::{note_title, parse_frontmatter, serialize_frontmatter, FrontmatterValue},
+        strings::parse_note_date,
+        tags::extract_tags,
+        text_encoding::decode_text,
+    },
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::io::Read;
+use std::time::Duration;
+
+pub const READING_FOLDER: &str = "Reading";
+pub const READING_TAG: &str = "toread";
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(20);
+const MAX_PAGE_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_TITLE_CHARS: usize = 80;
+const STRIPPED_ELEMENTS: [&str; 9] = [
+    "script", "style", "noscript", "nav", "header", "footer", "aside", "form", "iframe",
+];
+
+static TITLE_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>"));
+static BLANK_LINES_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"\n[ \t]*(\n[ \t]*)+\n"));
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClippedPage {
+    pub title: String,
+    pub markdown: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReadingItem {
+    pub note_name: String,
+    pub title: String,
+    pub url: Option<String>,
+    /// RFC 3339 time the page was queued, or the note's modification time
+    pub added: String,
+}
+
+/// Accepts only absolute http(s) URLs; anything else is rejected before a request is made.
+pub fn validate_clip_url(url: &str) -> AppResult<String> {
+    let url = url.trim();
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| {
+            AppError::validation_error("url", "Only http and https URLs can be clipped")
+        })?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    if host.is_empty() || url.chars().any(char::is_whitespace) {
+        return Err(AppError::validation_error("url", "URL has no host"));
+    }
+    Ok(url.to_string())
+}
+
+pub fn fetch_page(url: &str) -> AppResult<String> {
+    let response = ureq::AgentBuilder::new()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .get(url)
+        .call()
+        .map_err(|e| AppError::FileRead(format!("Failed to fetch '{}': {}", url, e)))?;
+
+    let content_type = response.content_type().to_ascii_lowercase();
+    if !content_type.contains("html") {
+        return Err(AppError::FileRead(format!(
+            "'{}' is not a web page ({})",
+            url, content_type
+        )));
+    }
+
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_PAGE_BYTES)
+        .read_to_end(&mut bytes)
+        .map_err(|e| AppError::FileRead(format!("Failed to read '{}': {}", url, e)))?;
+    Ok(decode_page(bytes))
+}
+
+/// Decodes a page body that may have been cut off at `MAX_PAGE_BYTES`. Pages
+/// that aren't UTF-8 fall back like notes do, and a character split by the
+/// limit is dropped rather than failing the clip.
+pub fn decode_page(mut bytes: Vec<u8>) -> String {
+    if let Err(e) = std::str::from_utf8(&bytes) {
+        // No error length means the text only ends mid-character
+        if e.error_len().is_none() {
+            bytes.truncate(e.valid_up_to());
+        }
+    }
+    decode_text(&bytes)
+        .map(|decoded| decoded.text)
+        .unwrap_or_else(|| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Converts a page to Markdown, keeping the article/main content when the page marks it.
+pub fn clip_html(url: &str, html: &str) -> ClippedPage {
+    let title = TITLE_REGEX
+        .as_ref()
+        .ok()
+        .and_then(|regex| regex.captures(html))
+        .and_then(|caps| caps.get(1))
+        .map(|title| html_escape::decode_html_entities(title.as_str().trim()).to_string())
+        .map(|title| title.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| url.to_string());
+
+    let mut content = ["article", "main", "body"]
+        .iter()
+        .find_map(|tag| element_inner(html, tag))
+        .unwrap_or(html)
+        .to_string();
+    for tag in STRIPPED_ELEMENTS {
+        content = strip_element(&content, tag);
+    }
+
+    let markdown = html2md::parse_html(&content);
+    let markdown = match BLANK_LINES_REGEX.as_ref() {
+        Ok(regex) => regex.replace_all(&markdown, "\n\n").to_string(),
+        Err(_) => markdown,
+    };
+
+    ClippedPage {
+        title,
+        markdown: markdown.trim().to_string(),
+    }
+}
+
+/// Builds the queued note: front-matter with the source and add time, then the clipped body.
+pub fn reading_note_content(url: &str, page: &ClippedPage, added: &str) -> String {
+    let fields = vec![
+        (
+            "title".to_string(),
+            FrontmatterValue::Text(page.title.clone()),
+        ),
+        (
+            "source".to_string(),
+            FrontmatterValue::Text(url.to_string()),
+        ),
+        (
+            "added".to_string(),
+            FrontmatterValue::Text(added.to_string()),
+        ),
+        (
+            "tags".to_string(),
+            FrontmatterValue::List(vec![READING_TAG.to_string()]),
+        ),
+    ];
+    format!(
+        "{}\n# {}\n\n{}\n",
+        serialize_frontmatter(&fields),
+        page.title,
+        page.markdown
+    )
+}
+
+/// Picks a free `Reading/<title>.md` name, adding a counter when the title is taken.
+pub fn reading_note_name(title: &str, exists: impl Fn(&str) -> bool) -> String {
+    let mut stem: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .take(MAX_TITLE_CHARS)
+        .collect();
+    stem = stem.trim().trim_matches('.').trim().to_string();
+    if stem.is_empty() {
+        stem = "Clipped page".to_string();
+    }
+
+    (1..)
+        .map(|n| {
+            if n == 1 {
+                format!("{}/{}.md", READING_FOLDER, stem)
+            } else {
+                format!("{}/{} {}.md", READING_FOLDER, stem, n)
+            }
+        })
+        .find(|candidate| !exists(candidate))
+        .unwrap_or_else(|| format!("{}/{}.md", READING_FOLDER, stem))
+}
+
+/// Notes in the reading folder still tagged `#toread`, oldest first.
+pub fn reading_queue(notes: &[SourceNote]) -> Vec<ReadingItem> {
+    let prefix = format!("{}/", READING_FOLDER);
+    let mut queue: Vec<(i64, ReadingItem)> = notes
+        .iter()
+        .filter(|note| note.filename.starts_with(&prefix))
+        .filter(|note| {
+            extract_tags(&note.content)
+                .iter()
+                .any(|tag| tag == READING_TAG)
+        })
+        .map(|note| {
+            let frontmatter = parse_frontmatter(&note.content).unwrap_or_default();
+            let added = frontmatter
+                .get_text("added")
+                .and_then(parse_note_date)
+                .or_else(|| chrono::DateTime::from_timestamp(note.modified, 0))
+                .unwrap_or_default();
+            let item = ReadingItem {
+                note_name: note.filename.clone(),
+                title: note_title(&note.filename, &note.content),
+                url: frontmatter.get_text("source").map(str::to_string),
+                added: added.to_rfc3339(),
+            };
+            (added.timestamp(), item)
+        })
+        .collect();
+
+    queue.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.note_name.cmp(&b.1.note_name))
+    });
+    queue.into_iter().map(|(_, item)| item).collect()
+}
+
+fn element_inner<'a>(html: &'a str, tag: &str) -> Option<&'a str> {
+    let regex = Regex::new(&format!(r"(?is)<{0}\b[^>]*>(.*)</{0}>", tag)).ok()?;
+    regex.captures(html)?.get(1).map(|inner| inner.as_str())
+}
+
+fn strip_element(html: &str, tag: &str) -> String {
+    match Regex::new(&format!(r"(?is)<{0}\b[^>]*>.*?</{0}>", tag)) {
+        Ok(regex) => regex.replace_all(html, "").to_string(),
+        Err(_) => html.to_string(),
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::file_safety::write_app_data_file,
+};
+use automerge::{
+    transaction::{CommitOptions, Transactable},
+    ActorId, AutoCommit, Automerge, LoadOptions, ObjId, ObjType, OnPartialLoad, ReadDoc, ROOT,
+};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const CONTENT_KEY: &str = "content";
+const LOG_EXTENSION: &str = "automerge";
+const ACTOR_FILE: &str = "device.actor";
+/// Actor of the shared first change. Every device builds the same genesis
+/// change, so logs created independently still agree on the text object.
+const GENESIS_ACTOR: &[u8] = b"symiosis-genesis";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrdtState {
+    pub note_name: String,
+    pub content: String,
+    /// Hex change hashes of the current heads
+    pub heads: Vec<String>,
+    pub change_count: usize,
+    /// Full saved document, suitable as `apply_remote_changes` payload on another device
+    pub document: Vec<u8>,
+}
+
+/// Location of a note's change log inside the vault's CRDT directory.
+pub fn crdt_log_path(crdt_dir: &Path, note_name: &str) -> PathBuf {
+    crdt_dir.join(format!("{}.{}", note_name, LOG_EXTENSION))
+}
+
+/// Stable per-device actor id, created on first use.
+pub fn device_actor(crdt_dir: &Path) -> AppResult<ActorId> {
+    let actor_path = crdt_dir.join(ACTOR_FILE);
+    if let Ok(hex) = fs::read_to_string(&actor_path) {
+        if let Ok(actor) = ActorId::from_str(hex.trim()) {
+            return Ok(actor);
+        }
+    }
+
+    let actor = ActorId::random();
+    write_app_data_file(&actor_path, actor.to_hex_string())?;
+    Ok(actor)
+}
+
+pub fn genesis_document() -> AutoCommit {
+    let mut doc = AutoCommit::new().with_actor(ActorId::from(GENESIS_ACTOR));
+    // Creating a text object on a fresh document cannot fail
+    let _ = doc.put_object(ROOT, CONTENT_KEY, ObjType::Text);
+    doc.commit_with(CommitOptions::default().with_time(0));
+    doc
+}
+
+pub fn load_document(path: &Path) -> AppResult<Option<AutoCommit>> {
+    match fs::read(path) {
+        Ok(bytes) => AutoCommit::load(&bytes).map(Some).map_err(|e| {
+            AppError::FileRead(format!("Corrupt CRDT log '{}': {}", path.display(), e))
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn save_document(path: &Path, doc: &mut AutoCommit) -> AppResult<()> {
+    write_app_data_file(path, doc.save())
+}
+
+pub fn document_text(doc: &AutoCommit) -> AppResult<String> {
+    let text = content_object(doc)?;
+    doc.text(&text).map_err(crdt_error)
+}
+
+/// Records `content` as a local change when it differs from the document text.
+/// Returns whether a change was committed.
+pub fn record_local_content(
+    doc: &mut AutoCommit,
+    actor: &ActorId,
+    content: &str,
+    time: i64,
+) -> AppResult<bool> {
+    if document_text(doc)? == content {
+        return Ok(false);
+    }
+
+    let text = content_object(doc)?;
+    doc.set_actor(actor.clone());
+    doc.update_text(&text, content).map_err(crdt_error)?;
+    doc.commit_with(CommitOptions::default().with_time(time));
+    Ok(true)
+}
+
+/// Merges a remote payload (a saved document or incremental changes) into the
+/// local log. Without a local log the remote history is adopted as-is.
+pub fn merge_remote(local: Option<AutoCommit>, payload: &[u8]) -> AppResult<AutoCommit> {
+    if payload.is_empty() {
+        return Err(AppError::validation_error(
+            "payload",
+            "CRDT payload is empty",
+        ));
+    }
+
+    let invalid =
+        |e: automerge::AutomergeError| AppError::FileRead(format!("Invalid CRDT payload: {}", e));
+    // load_incremental silently drops unparseable chunks, so parse strictly first
+    Automerge::load_with_options(
+        payload,
+        LoadOptions::new().on_partial_load(OnPartialLoad::Error),
+    )
+    .map_err(invalid)?;
+
+    let mut doc = local.unwrap_or_else(genesis_document);
+    doc.load_incremental(payload).map_err(invalid)?;
+    // Surface a payload whose text object doesn't descend from the shared genesis change
+    content_object(&doc)?;
+    Ok(doc)
+}
+
+pub fn crdt_state(note_name: &str, doc: &mut AutoCommit) -> AppResult<CrdtState> {
+    Ok(CrdtState {
+        note_name: note_name.to_string(),
+        content: document_text(doc)?,
+        heads: doc
+            .get_heads()
+            .iter()
+            .map(|hash| hash.to_string())
+            .collect(),
+        change_count: doc.get_changes(&[]).len(),
+        document: doc.save(),
+    })
+}
+
+fn content_object(doc: &AutoCommit) -> AppResult<ObjId> {
+    match doc.get(ROOT, CONTENT_KEY).map_err(crdt_error)? {
+        Some((automerge::Value::Object(ObjType::Text), id)) => Ok(id),
+        _ => Err(AppError::FileRead(
+            "CRDT document has no note content".to_string(),
+        )),
+    }
+}
+
+fn crdt_error(e: automerge::AutomergeError) -> AppError {
+    AppError::FileRead(format!("CRDT operation failed: {}", e))
+}
@@ -1,25 +1,140 @@
 use crate::{
+    commands::notes::with_programmatic_flag,
     config::get_config_notes_dir,
-    core::{state::AppState, AppError, AppResult},
+    core::{
+        events::{emit_event, DbLoadingProgress},
+        state::AppState,
+        AppError, AppResult,
+    },
     database::with_db,
     logging::log,
+    services::{
+        derived_index_service::{invalidate_all, refresh_index, DERIVED_INDEX_STATE_SCHEMA},
+        expiry_service::EXPIRED_NOTES_SCHEMA,
+        integrity_service::INTEGRITY_SCHEMA,
+        link_service::NOTE_LINKS_INDEX,
+        note_created_service::{file_created_time, NOTE_CREATED_SCHEMA},
+        reading_progress_service::READING_PROGRESS_SCHEMA,
+        reminder_service::REMINDER_STATE_SCHEMA,
+        rename_history_service::{record_rename, RENAME_HISTORY_SCHEMA},
+        render_service::invalidate_stale_renders,
+        scratchpad_service::SCRATCHPADS_SCHEMA,
+        staleness_service::NOTE_OPENS_SCHEMA,
+        sync_warning_service::{
+            find_lookalike_names, plan_duplicate_renames, read_note_file, record_sync_warning,
+            set_last_sync_report, SyncIssue, SyncIssueKind, SyncReport, SyncWarning,
+            SYNC_WARNINGS_SCHEMA,
+        },
+        tag_service::NOTE_TAGS_INDEX,
+    },
+    utilities::text_encoding::{read_note_text, DecodedText},
 };
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use std::{
     collections::{HashMap, HashSet},
     fs,
     path::PathBuf,
     time::UNIX_EPOCH,
 };
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 use walkdir::WalkDir;
 
 // Number of most recent notes to get immediate HTML rendering during startup
 // Remaining notes get metadata-only and are processed on demand
 const IMMEDIATE_RENDER_COUNT: usize = 2000;
 
-pub fn init_db(conn: &Connection) -> rusqlite::Result<()> {
-    conn.execute_batch("CREATE VIRTUAL TABLE IF NOT EXISTS notes USING fts5(filename, content, html_render, modified UNINDEXED, is_indexed UNINDEXED);")?;
+/// Version of the notes table layout, kept in `PRAGMA user_version`. Bump it
+/// when the table or its tokenizer options change so existing databases are
+/// rebuilt once. Version 1 added diacritic folding; 2 indexes the ancestors
+/// of nested tags.
+pub const NOTES_SCHEMA_VERSION: i64 = 2;
+
+/// FTS5 `tokenize` argument for a `[preferences] fts_tokenizer` value. Case
+/// and diacritics are folded, so `cafe` matches `Café`, except by `ascii`,
+/// which has no such option. `trigram` matches substrings, which suits CJK
+/// text without word breaks.
+fn fts_tokenize_arg(tokenizer: &str) -> &'static str {
+    match tokenizer {
+        "porter" => "porter unicode61 remove_diacritics 2",
+        "trigram" => "trigram remove_diacritics 1",
+        "ascii" => "ascii",
+        _ => "unicode61 remove_diacritics 2",
+    }
+}
+
+fn notes_table_sql(conn: &Connection) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'notes'",
+        [],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Whether an existing notes table was built with a different tokenizer.
+pub fn fts_tokenizer_changed(conn: &Connection, tokenizer: &str) -> rusqlite::Result<bool> {
+    Ok(notes_table_sql(conn)?.is_some_and(|sql| {
+        // Tables created before the tokenizer was configurable have no argument
+        let current = sql
+            .split_once("tokenize")
+            .and_then(|(_, rest)| rest.split('\'').nth(1))
+            .unwrap_or("unicode61");
+        current != fts_tokenize_arg(tokenizer)
+    }))
+}
+
+/// Why an existing notes table has to be rebuilt, if it does.
+fn notes_rebuild_reason(conn: &Connection, tokenizer: &str) -> rusqlite::Result<Option<String>> {
+    if notes_table_sql(conn)?.is_none() {
+        return Ok(None);
+    }
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version < NOTES_SCHEMA_VERSION {
+        return Ok(Some(format!(
+            "Notes index schema updated from v{} to v{}",
+            version, NOTES_SCHEMA_VERSION
+        )));
+    }
+    if fts_tokenizer_changed(conn, tokenizer)? {
+        return Ok(Some(format!("Search tokenizer changed to '{}'", tokenizer)));
+    }
+    Ok(None)
+}
+
+pub fn configured_fts_tokenizer(app_state: &AppState) -> String {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    config.preferences.fts_tokenizer.clone()
+}
+
+/// Creates the schema. When the notes table predates `NOTES_SCHEMA_VERSION`
+/// or was built with another tokenizer, it is dropped so the following load
+/// re-indexes every note.
+pub fn init_db(conn: &Connection, tokenizer: &str) -> rusqlite::Result<()> {
+    if let Some(reason) = notes_rebuild_reason(conn, tokenizer)? {
+        log(
+            "DATABASE_INIT",
+            &format!("{}. Rebuilding the notes index...", reason),
+            None,
+        );
+        conn.execute("DROP TABLE IF EXISTS notes", [])?;
+        invalidate_all(conn)?;
+    }
+    conn.execute_batch(&format!(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS notes USING fts5(filename, content, html_render, modified UNINDEXED, is_indexed UNINDEXED, tokenize = '{}');
+         PRAGMA user_version = {};",
+        fts_tokenize_arg(tokenizer),
+        NOTES_SCHEMA_VERSION
+    ))?;
+    conn.execute_batch(DERIVED_INDEX_STATE_SCHEMA)?;
+    conn.execute_batch(RENAME_HISTORY_SCHEMA)?;
+    conn.execute_batch(NOTE_CREATED_SCHEMA)?;
+    conn.execute_batch(NOTE_OPENS_SCHEMA)?;
+    conn.execute_batch(SYNC_WARNINGS_SCHEMA)?;
+    conn.execute_batch(REMINDER_STATE_SCHEMA)?;
+    conn.execute_batch(INTEGRITY_SCHEMA)?;
+    conn.execute_batch(SCRATCHPADS_SCHEMA)?;
+    conn.execute_batch(READING_PROGRESS_SCHEMA)?;
+    conn.execute_batch(EXPIRED_NOTES_SCHEMA)?;
 
     let mut stmt = conn.prepare(
         "SELECT filename, COUNT(*) as count FROM notes GROUP BY filename HAVING count > 1",
@@ -105,6 +220,123 @@ fn scan_filesystem_for_notes() -> rusqlite::Result<Vec<(String, PathBuf, i64)>>
     Ok(filesystem_files)
 }
 
+/// Renames note files whose names are already another file's in the index
+/// (see `plan_duplicate_renames`) so each gets its own row, instead of leaving
+/// duplicate entries that force a full rebuild. Renames are kept as sync
+/// warnings; a file that can't be renamed is skipped this sync. Notes whose
+/// names only look alike are reported but left alone.
+fn resolve_duplicate_filenames(
+    app_state: &AppState,
+    conn: &Connection,
+    filesystem_files: &mut Vec<(String, PathBuf, i64)>,
+) -> Vec<SyncIssue> {
+    let filenames: Vec<String> = filesystem_files
+        .iter()
+        .map(|(name, _, _)| name.clone())
+        .collect();
+    let mut issues: Vec<SyncIssue> = find_lookalike_names(&filenames)
+        .into_iter()
+        .map(|lookalike| {
+            let message = format!(
+                "Differs from '{}' only by case, Unicode form or spacing",
+                lookalike.similar_to
+            );
+            log(
+                "DUPLICATE_FILENAME",
+                &format!("'{}': {}", lookalike.filename, message),
+                None,
+            );
+            SyncIssue {
+                filename: lookalike.filename,
+                kind: SyncIssueKind::NameConflict,
+                message,
+            }
+        })
+        .collect();
+
+    let renames = plan_duplicate_renames(&filenames);
+    if renames.is_empty() {
+        return issues;
+    }
+
+    let notes_dir = get_config_notes_dir();
+    let now = unix_now();
+    let mut skipped = Vec::new();
+
+    for rename in renames {
+        let target = notes_dir.join(&rename.new_name);
+        let result = if target.exists() {
+            Err(AppError::FileWrite(format!(
+                "'{}' already exists",
+                rename.new_name
+            )))
+        } else {
+            with_programmatic_flag(app_state, || {
+                fs::rename(&filesystem_files[rename.index].1, &target).map_err(AppError::from)
+            })
+        };
+
+        let warning = match result {
+            Ok(()) => {
+                filesystem_files[rename.index].0 = rename.new_name.clone();
+                filesystem_files[rename.index].1 = target;
+                if let Err(e) = record_rename(conn, &rename.filename, &rename.new_name, now) {
+                    log(
+                        "DUPLICATE_FILENAME",
+                        &format!("Failed to record rename of '{}'", rename.filename),
+                        Some(&e.to_string()),
+                    );
+                }
+                SyncWarning {
+                    filename: rename.filename,
+                    resolved_name: Some(rename.new_name.clone()),
+                    message: format!(
+                        "Another file has the same name once converted to Unicode, renamed to '{}'",
+                        rename.new_name
+                    ),
+                    detected_at: now,
+                }
+            }
+            Err(e) => {
+                skipped.push(rename.index);
+                SyncWarning {
+                    filename: rename.filename,
+                    resolved_name: None,
+                    message: format!(
+                        "Another file has the same name once converted to Unicode and renaming it failed ({}), so it was skipped",
+                        e
+                    ),
+                    detected_at: now,
+                }
+            }
+        };
+
+        log(
+            "DUPLICATE_FILENAME",
+            &format!("'{}': {}", warning.filename, warning.message),
+            None,
+        );
+        if let Err(e) = record_sync_warning(conn, &warning) {
+            log(
+                "DUPLICATE_FILENAME",
+                "Failed to record sync warning",
+                Some(&e.to_string()),
+            );
+        }
+        issues.push(SyncIssue {
+            filename: warning.filename,
+            kind: SyncIssueKind::NameConflict,
+            message: warning.message,
+        });
+    }
+
+    // Highest index first so earlier positions stay valid
+    for index in skipped.into_iter().rev() {
+        filesystem_files.remove(index);
+    }
+    issues
+}
+
 fn load_existing_database_files(
     conn: &Connection,
 ) -> rusqlite::Result<HashMap<String, (i64, bool)>> {
@@ -131,11 +363,14 @@ fn sync_database_with_filesystem(
     filesystem_files: &[(String, PathBuf, i64)],
     database_files: &HashMap<String, (i64, bool)>,
     app_handle: Option<&AppHandle>,
+    report: &mut SyncReport,
 ) -> rusqlite::Result<()> {
     let tx = conn.transaction()?;
 
-    remove_deleted_files_from_database(&tx, filesystem_files, database_files)?;
-    process_filesystem_files(&tx, filesystem_files, database_files, app_handle)?;
+    report.notes_removed =
+        remove_deleted_files_from_database(&tx, filesystem_files, database_files)?;
+    process_filesystem_files(&tx, filesystem_files, database_files, app_handle, report)?;
+    record_missing_created_times(&tx, filesystem_files)?;
 
     tx.commit()
 }
@@ -144,16 +379,50 @@ fn remove_deleted_files_from_database(
     tx: &rusqlite::Transaction,
     filesystem_files: &[(String, PathBuf, i64)],
     database_files: &HashMap<String, (i64, bool)>,
-) -> rusqlite::Result<()> {
+) -> rusqlite::Result<usize> {
     let filesystem_filenames: HashSet<_> =
         filesystem_files.iter().map(|(name, _, _)| name).collect();
 
+    let mut removed = 0;
     for filename in database_files.keys() {
         if !filesystem_filenames.contains(filename) {
             tx.execute("DELETE FROM notes WHERE filename = ?1", params![filename])?;
+            tx.execute(
+                "DELETE FROM note_created WHERE filename = ?1",
+                params![filename],
+            )?;
+            removed += 1;
         }
     }
 
+    Ok(removed)
+}
+
+/// Stores creation times for notes seen for the first time, including every
+/// note of a vault indexed before creation times were tracked.
+fn record_missing_created_times(
+    tx: &rusqlite::Transaction,
+    filesystem_files: &[(String, PathBuf, i64)],
+) -> rusqlite::Result<()> {
+    let known: HashSet<String> = tx
+        .prepare("SELECT filename FROM note_created")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    let now = unix_now();
+
+    for (filename, path, fs_modified) in filesystem_files {
+        if known.contains(filename) {
+            continue;
+        }
+        let created = path
+            .metadata()
+            .map(|metadata| file_created_time(&metadata, now))
+            .unwrap_or((*fs_modified).min(now));
+        tx.execute(
+            "INSERT OR IGNORE INTO note_created (filename, created) VALUES (?1, ?2)",
+            params![filename, created],
+        )?;
+    }
     Ok(())
 }
 
@@ -162,6 +431,7 @@ fn process_filesystem_files(
     filesystem_files: &[(String, PathBuf, i64)],
     database_files: &HashMap<String, (i64, bool)>,
     app_handle: Option<&AppHandle>,
+    report: &mut SyncReport,
 ) -> rusqlite::Result<()> {
     let total_files = filesystem_files.len();
 
@@ -170,10 +440,49 @@ fn process_filesystem_files(
 
         let (db_modified, is_indexed) = database_files.get(filename).copied().unwrap_or((0, false));
 
-        if *fs_modified != db_modified {
-            process_modified_file(tx, filename, path, *fs_modified, index)?;
+        let result = if *fs_modified != db_modified {
+            process_modified_file(tx, filename, path, *fs_modified, index)
         } else if !is_indexed && index < IMMEDIATE_RENDER_COUNT {
-            update_unindexed_file(tx, filename, path)?;
+            update_unindexed_file(tx, filename, path)
+        } else {
+            continue;
+        };
+
+        match result? {
+            Ok(warning) => {
+                if *fs_modified != db_modified {
+                    report.notes_updated += 1;
+                }
+                if let Some((kind, message)) = warning {
+                    log(
+                        "SYNC_ISSUE",
+                        &format!("Indexed '{}': {}", filename, message),
+                        None,
+                    );
+                    report.issues.push(SyncIssue {
+                        filename: filename.clone(),
+                        kind,
+                        message,
+                    });
+                }
+            }
+            Err((kind, message)) => {
+                // Binaries and oversize files may have been indexed by
+                // older versions, before they were skipped
+                if kind != SyncIssueKind::Unreadable {
+                    tx.execute("DELETE FROM notes WHERE filename = ?1", params![filename])?;
+                }
+                log(
+                    "SYNC_ISSUE",
+                    &format!("Skipped '{}': {}", filename, message),
+                    None,
+                );
+                report.issues.push(SyncIssue {
+                    filename: filename.clone(),
+                    kind,
+                    message,
+                });
+            }
         }
     }
 
@@ -187,30 +496,40 @@ fn emit_progress_if_needed(
 ) -> rusqlite::Result<()> {
     if let Some(app) = app_handle {
         if index == 0 || (index + 1) % 10 == 0 || index == total_files - 1 {
-            let progress_msg = format!("Loading {} of {} notes...", index + 1, total_files);
-            if let Err(e) = app.emit("db-loading-progress", progress_msg) {
-                log(
-                    "UI_UPDATE",
-                    "Failed to emit db-loading-progress event",
-                    Some(&e.to_string()),
-                );
-            }
+            emit_event(app, DbLoadingProgress::counted(index + 1, total_files));
         }
     }
     Ok(())
 }
 
+/// Result of indexing one file: an issue to report about a file that was
+/// indexed anyway, or the error saying why it couldn't be
+type IndexOutcome = Result<Option<(SyncIssueKind, String)>, (SyncIssueKind, String)>;
+
+/// Reports a note read with an encoding other than UTF-8
+fn encoding_issue(decoded: &DecodedText) -> Option<(SyncIssueKind, String)> {
+    decoded
+        .warning()
+        .map(|message| (SyncIssueKind::Encoding, message))
+}
+
+/// Indexes a file that changed on disk. The inner error says why the file
+/// can't be indexed.
 fn process_modified_file(
     tx: &rusqlite::Transaction,
     filename: &str,
     path: &PathBuf,
     fs_modified: i64,
     index: usize,
-) -> rusqlite::Result<()> {
-    let content = fs::read_to_string(path).unwrap_or_default();
+) -> rusqlite::Result<IndexOutcome> {
+    let decoded = match read_note_file(path) {
+        Ok(decoded) => decoded,
+        Err(issue) => return Ok(Err(issue)),
+    };
+    let content = &decoded.text;
 
     if index < IMMEDIATE_RENDER_COUNT {
-        let html_render = crate::utilities::note_renderer::render_note(filename, &content);
+        let html_render = crate::utilities::note_renderer::render_note(filename, content);
         tx.execute(
             "INSERT OR REPLACE INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, ?3, ?4, ?5)",
             params![filename, content, html_render, fs_modified, true],
@@ -222,32 +541,105 @@ fn process_modified_file(
         )?;
     }
 
-    Ok(())
+    Ok(Ok(encoding_issue(&decoded)))
 }
 
 fn update_unindexed_file(
     tx: &rusqlite::Transaction,
     filename: &str,
     path: &PathBuf,
-) -> rusqlite::Result<()> {
-    let content = fs::read_to_string(path).unwrap_or_default();
-    let html_render = crate::utilities::note_renderer::render_note(filename, &content);
+) -> rusqlite::Result<IndexOutcome> {
+    let decoded = match read_note_file(path) {
+        Ok(decoded) => decoded,
+        Err(issue) => return Ok(Err(issue)),
+    };
+    let content = &decoded.text;
+    let html_render = crate::utilities::note_renderer::render_note(filename, content);
     tx.execute(
         "UPDATE notes SET html_render = ?2, is_indexed = ?3 WHERE filename = ?1",
         params![filename, html_render, true],
     )?;
-    Ok(())
+    Ok(Ok(encoding_issue(&decoded)))
 }
 
 pub fn load_all_notes_into_sqlite_with_progress(
-    _app_state: &AppState,
+    app_state: &AppState,
     conn: &mut Connection,
     app_handle: Option<&AppHandle>,
 ) -> rusqlite::Result<()> {
+    let mut report = SyncReport {
+        started_at: unix_now(),
+        ..Default::default()
+    };
     ensure_notes_directory_exists()?;
-    let filesystem_files = scan_filesystem_for_notes()?;
+    invalidate_renders_after_renderer_change(conn);
+    let mut filesystem_files = scan_filesystem_for_notes()?;
     let database_files = load_existing_database_files(conn)?;
-    sync_database_with_filesystem(conn, &filesystem_files, &database_files, app_handle)
+    report.issues = resolve_duplicate_filenames(app_state, conn, &mut filesystem_files);
+    report.files_scanned = filesystem_files.len();
+    sync_database_with_filesystem(
+        conn,
+        &filesystem_files,
+        &database_files,
+        app_handle,
+        &mut report,
+    )?;
+    index_after_sync(conn);
+
+    report.finished_at = unix_now();
+    if !report.issues.is_empty() {
+        log(
+            "SYNC_REPORT",
+            &format!(
+                "Sync finished with {} issue(s), see get_last_sync_report",
+                report.issues.len()
+            ),
+            None,
+        );
+    }
+    set_last_sync_report(report);
+    Ok(())
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Queues every note for re-rendering when the renderer or the markdown
+/// extensions changed since the stored renders were made. A failure leaves
+/// the old renders in place until the next sync.
+fn invalidate_renders_after_renderer_change(conn: &Connection) {
+    match invalidate_stale_renders(conn) {
+        Ok(true) => log(
+            "RENDER_CACHE",
+            "Renderer settings changed, notes will be re-rendered",
+            None,
+        ),
+        Ok(false) => {}
+        Err(e) => log(
+            "RENDER_CACHE",
+            "Failed to check stored renders against the renderer",
+            Some(&e.to_string()),
+        ),
+    }
+}
+
+/// Extracts tags and links right after loading so the tag sidebar and the
+/// vault health report don't pay for the first parse. A failure only delays
+/// indexing until the next query.
+fn index_after_sync(conn: &Connection) {
+    for index in [&NOTE_TAGS_INDEX, &NOTE_LINKS_INDEX] {
+        if let Err(e) = refresh_index(conn, index) {
+            log(
+                "DERIVED_INDEX",
+                &format!("Failed to refresh the {} index", index.name),
+                Some(&e.to_string()),
+            );
+        }
+    }
 }
 
 pub fn recreate_database(app_state: &AppState) -> AppResult<()> {
@@ -257,6 +649,8 @@ pub fn recreate_database(app_state: &AppState) -> AppResult<()> {
         None,
     );
 
+    let tokenizer = configured_fts_tokenizer(app_state);
+
     // Acquire exclusive write lock for entire rebuild operation
     let _rebuild_lock = app_state.database_rebuild_lock.write().map_err(|e| {
         AppError::DatabaseConnection(format!("Database rebuild lock poisoned: {}", e))
@@ -269,8 +663,9 @@ pub fn recreate_database(app_state: &AppState) -> AppResult<()> {
 
     manager.with_connection_mut(|conn| {
         conn.execute("DROP TABLE IF EXISTS notes", [])?;
+        invalidate_all(conn)?;
 
-        init_db(conn)?;
+        init_db(conn, &tokenizer)?;
 
         load_all_notes_into_sqlite(app_state, conn)?;
 
@@ -288,6 +683,8 @@ pub async fn recreate_database_with_progress(
     app_handle: &AppHandle,
     reason: &str,
 ) -> AppResult<()> {
+    let tokenizer = configured_fts_tokenizer(app_state);
+
     // Acquire exclusive write lock for entire rebuild operation
     let _rebuild_lock = app_state.database_rebuild_lock.write().map_err(|e| {
         AppError::DatabaseConnection(format!("Database rebuild lock poisoned: {}", e))
@@ -298,13 +695,10 @@ pub async fn recreate_database_with_progress(
         None,
     );
 
-    if let Err(e) = app_handle.emit("db-loading-progress", "Rebuilding notes database...") {
-        log(
-            "UI_UPDATE",
-            "Failed to emit rebuild progress",
-            Some(&e.to_string()),
-        );
-    }
+    emit_event(
+        app_handle,
+        DbLoadingProgress::new("Rebuilding notes database..."),
+    );
     log("DATABASE_REBUILD_REASON", reason, None);
 
     // We need to access the database manager directly since we're already holding the rebuild lock
@@ -315,16 +709,11 @@ pub async fn recreate_database_with_progress(
 
         manager.with_connection_mut(|conn| {
             conn.execute("DROP TABLE IF EXISTS notes", [])?;
+            invalidate_all(conn)?;
 
-            init_db(conn)?;
+            init_db(conn, &tokenizer)?;
 
-            if let Err(e) = app_handle.emit("db-loading-progress", "Rendering notes...") {
-                log(
-                    "UI_UPDATE",
-                    "Failed to emit rendering progress",
-                    Some(&e.to_string()),
-                );
-            }
+            emit_event(app_handle, DbLoadingProgress::new("Rendering notes..."));
 
             load_all_notes_into_sqlite(app_state, conn).map_err(|e| e.into())
         })
@@ -349,13 +738,7 @@ pub async fn recreate_database_with_progress(
         }
     }
 
-    if let Err(e) = app_handle.emit("db-loading-progress", "Notes database ready.") {
-        log(
-            "UI_UPDATE",
-            "Failed to emit completion progress",
-            Some(&e.to_string()),
-        );
-    }
+    emit_event(app_handle, DbLoadingProgress::new("Notes database ready."));
 
     rebuild_result
 }
@@ -402,7 +785,7 @@ pub fn quick_filesystem_sync_check(app_state: &AppState) -> AppResult<bool> {
             })?;
             let filename = relative_path.to_string_lossy().to_string();
 
-            let file_content = match std::fs::read_to_string(file_path) {
+            let file_content = match read_note_text(file_path) {
                 Ok(content) => content,
                 Err(_) => {
                     log(
@@ -561,7 +944,10 @@ fn handle_database_initialization_failure(
 }
 
 fn initialize_database_schema(app_state: &AppState) -> AppResult<()> {
-    with_db(app_state, |conn| init_db(conn).map_err(|e| e.into()))
+    let tokenizer = configured_fts_tokenizer(app_state);
+    with_db(app_state, |conn| {
+        init_db(conn, &tokenizer).map_err(|e| e.into())
+    })
 }
 
 fn prepare_database_environment() -> () {
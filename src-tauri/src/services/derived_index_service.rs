@@ -0,0 +1,91 @@
+use crate::core::AppResult;
+use rusqlite::{params, Connection};
+
+/// Bookkeeping for tables derived from note content. Each index records the
+/// `modified` value it last saw per note, so refreshing only re-parses notes that
+/// changed since. `update_note_in_database` clears a note's rows to force a re-parse
+/// even when two saves land in the same second.
+pub const DERIVED_INDEX_STATE_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS derived_index_state (
+    index_name TEXT NOT NULL,
+    filename TEXT NOT NULL,
+    modified INTEGER NOT NULL,
+    PRIMARY KEY (index_name, filename)
+);";
+
+/// A table (or set of tables) rebuilt per note from its content.
+pub struct DerivedIndex {
+    pub name: &'static str,
+    pub schema: &'static str,
+    /// Removes every row belonging to a note
+    pub clear_note: fn(&Connection, &str) -> rusqlite::Result<()>,
+    /// Inserts the rows for a note; called after `clear_note`
+    pub index_note: fn(&Connection, &str, &str) -> rusqlite::Result<()>,
+}
+
+/// Brings an index up to date with the notes table. Returns the number of notes
+/// that were re-parsed or dropped.
+pub fn refresh_index(conn: &Connection, index: &DerivedIndex) -> AppResult<usize> {
+    conn.execute_batch(DERIVED_INDEX_STATE_SCHEMA)?;
+    conn.execute_batch(index.schema)?;
+
+    let stale: Vec<(String, String, i64)> = {
+        let mut stmt = conn.prepare(
+            "SELECT n.filename, n.content, n.modified FROM notes n
+             LEFT JOIN derived_index_state s ON s.index_name = ?1 AND s.filename = n.filename
+             WHERE s.modified IS NULL OR s.modified != n.modified",
+        )?;
+        let rows = stmt.query_map(params![index.name], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?;
+        rows.collect::<Result<_, _>>()?
+    };
+    let removed: Vec<String> = {
+        let mut stmt = conn.prepare(
+            "SELECT filename FROM derived_index_state
+             WHERE index_name = ?1 AND filename NOT IN (SELECT filename FROM notes)",
+        )?;
+        let rows = stmt.query_map(params![index.name], |row| row.get(0))?;
+        rows.collect::<Result<_, _>>()?
+    };
+
+    if stale.is_empty() && removed.is_empty() {
+        return Ok(0);
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    for filename in &removed {
+        (index.clear_note)(&tx, filename)?;
+        tx.execute(
+            "DELETE FROM derived_index_state WHERE index_name = ?1 AND filename = ?2",
+            params![index.name, filename],
+        )?;
+    }
+    for (filename, content, modified) in &stale {
+        (index.clear_note)(&tx, filename)?;
+        (index.index_note)(&tx, filename, content)?;
+        tx.execute(
+            "INSERT OR REPLACE INTO derived_index_state (index_name, filename, modified) VALUES (?1, ?2, ?3)",
+            params![index.name, filename, modified],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(stale.len() + removed.len())
+}
+
+/// Marks a note as changed for every derived index.
+pub fn invalidate_note(conn: &Connection, filename: &str) -> AppResult<()> {
+    conn.execute_batch(DERIVED_INDEX_STATE_SCHEMA)?;
+    conn.execute(
+        "DELETE FROM derived_index_state WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+/// Forces every derived index to rebuild, used when the notes table is recreated.
+pub fn invalidate_all(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(DERIVED_INDEX_STATE_SCHEMA)?;
+    conn.execute("DELETE FROM derived_index_state", [])?;
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::file_safety::write_app_data_file,
+};
+use std::{fs, path::Path, sync::Mutex};
+
+// Serializes read-modify-write cycles so concurrent windows don't lose words
+static DICTIONARY_LOCK: Mutex<()> = Mutex::new(());
+
+const MAX_WORD_LENGTH: usize = 100;
+
+pub fn normalize_word(word: &str) -> AppResult<String> {
+    let word = word.trim();
+    if word.is_empty() {
+        return Err(AppError::validation_error("word", "cannot be empty"));
+    }
+    if word.chars().any(char::is_whitespace) {
+        return Err(AppError::validation_error(
+            "word",
+            "must be a single word without whitespace",
+        ));
+    }
+    if word.chars().count() > MAX_WORD_LENGTH {
+        return Err(AppError::validation_error("word", "is too long"));
+    }
+    Ok(word.to_string())
+}
+
+pub fn load_words(path: &Path) -> AppResult<Vec<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(AppError::FileRead(format!(
+            "Failed to read dictionary '{}': {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
+pub fn add_word(path: &Path, word: &str) -> AppResult<Vec<String>> {
+    let word = normalize_word(word)?;
+    let _guard = DICTIONARY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut words = load_words(path)?;
+    if !words.contains(&word) {
+        words.push(word);
+        save_words(path, &mut words)?;
+    }
+    Ok(words)
+}
+
+pub fn remove_word(path: &Path, word: &str) -> AppResult<Vec<String>> {
+    let word = word.trim();
+    let _guard = DICTIONARY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut words = load_words(path)?;
+    let original_len = words.len();
+    words.retain(|existing| existing != word);
+    if words.len() != original_len {
+        save_words(path, &mut words)?;
+    }
+    Ok(words)
+}
+
+pub fn merge_words(sources: &[Vec<String>]) -> Vec<String> {
+    let mut merged: Vec<String> = sources.iter().flatten().cloned().collect();
+    sort_words(&mut merged);
+    merged.dedup();
+    merged
+}
+
+fn sort_words(words: &mut [String]) {
+    words.sort_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()).then(a.cmp(b)));
+}
+
+fn save_words(path: &Path, words: &mut Vec<String>) -> AppResult<()> {
+    sort_words(words);
+    words.dedup();
+
+    let mut content = words.join("\n");
+    content.push('\n');
+    write_app_data_file(path, &content)
+}
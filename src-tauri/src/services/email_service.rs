@@ -0,0 +1,196 @@
+use crate::{
+    config::EmailConfig,
+    core::{AppError, AppResult},
+    utilities::{
+        frontmatter::{note_title, strip_frontmatter},
+        note_renderer::render_note,
+    },
+};
+use lettre::{
+    message::{Mailbox, MultiPart, SinglePart},
+    transport::smtp::authentication::Credentials,
+    Message, SmtpTransport, Transport,
+};
+use std::time::Duration;
+
+/// Keychain service the SMTP password is stored under, keyed by username
+pub const KEYCHAIN_SERVICE: &str = "symiosis-smtp";
+/// Recipients accepted by one `email_note` call
+pub const MAX_RECIPIENTS: usize = 50;
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailFormat {
+    /// The note's markdown as plain text
+    Markdown,
+    /// Rendered HTML, with the markdown as the plain-text alternative
+    Html,
+}
+
+impl EmailFormat {
+    pub fn parse(format: &str) -> AppResult<Self> {
+        match format.trim().to_lowercase().as_str() {
+            "markdown" | "md" => Ok(EmailFormat::Markdown),
+            "html" => Ok(EmailFormat::Html),
+            other => Err(AppError::validation_error(
+                "format",
+                &format!(
+                    "Unsupported email format '{}' (expected markdown or html)",
+                    other
+                ),
+            )),
+        }
+    }
+}
+
+/// Sender address, falling back to the SMTP username.
+pub fn sender_address(config: &EmailConfig) -> &str {
+    if config.from_address.is_empty() {
+        &config.username
+    } else {
+        &config.from_address
+    }
+}
+
+/// Fails unless a server and a sender are configured.
+pub fn ensure_email_configured(config: &EmailConfig) -> AppResult<()> {
+    if config.smtp_host.is_empty() {
+        return Err(AppError::ConfigLoad(
+            "Email is not set up: [email] smtp_host is empty".to_string(),
+        ));
+    }
+    if sender_address(config).is_empty() {
+        return Err(AppError::ConfigLoad(
+            "Email is not set up: set [email] from_address or username".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn parse_recipients(to: &[String]) -> AppResult<Vec<Mailbox>> {
+    let to: Vec<&str> = to
+        .iter()
+        .map(|address| address.trim())
+        .filter(|address| !address.is_empty())
+        .collect();
+    if to.is_empty() {
+        return Err(AppError::validation_error(
+            "to",
+            "At least one recipient is required",
+        ));
+    }
+    if to.len() > MAX_RECIPIENTS {
+        return Err(AppError::validation_error(
+            "to",
+            &format!("At most {} recipients are allowed", MAX_RECIPIENTS),
+        ));
+    }
+
+    to.into_iter()
+        .map(|address| {
+            address.parse::<Mailbox>().map_err(|e| {
+                AppError::validation_error("to", &format!("Invalid address '{}': {}", address, e))
+            })
+        })
+        .collect()
+}
+
+/// The note as an email with its title as the subject. Front matter is left
+/// out of the body.
+pub fn build_note_email(
+    from: &str,
+    to: &[String],
+    note_name: &str,
+    content: &str,
+    format: EmailFormat,
+) -> AppResult<Message> {
+    let from = from
+        .parse::<Mailbox>()
+        .map_err(|e| AppError::ConfigLoad(format!("Invalid sender address '{}': {}", from, e)))?;
+    let mut builder = Message::builder()
+        .from(from)
+        .subject(note_title(note_name, content));
+    for recipient in parse_recipients(to)? {
+        builder = builder.to(recipient);
+    }
+
+    let body = strip_frontmatter(content).to_string();
+    let message = match format {
+        EmailFormat::Markdown => builder.singlepart(SinglePart::plain(body)),
+        EmailFormat::Html => {
+            let html = format!(
+                "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"></head><body>\n{}</body></html>\n",
+                render_note(note_name, &body)
+            );
+            builder.multipart(MultiPart::alternative_plain_html(body, html))
+        }
+    };
+    message.map_err(|e| AppError::FileWrite(format!("Failed to build email: {}", e)))
+}
+
+pub fn send_email(config: &EmailConfig, password: &str, message: &Message) -> AppResult<()> {
+    let host = config.smtp_host.as_str();
+    let builder = match config.security.as_str() {
+        "tls" => SmtpTransport::relay(host),
+        "none" => Ok(SmtpTransport::builder_dangerous(host)),
+        _ => SmtpTransport::starttls_relay(host),
+    }
+    .map_err(|e| AppError::ConfigLoad(format!("Invalid SMTP server '{}': {}", host, e)))?;
+
+    let mut builder = builder.port(config.smtp_port).timeout(Some(SMTP_TIMEOUT));
+    if !config.username.is_empty() {
+        builder = builder.credentials(Credentials::new(
+            config.username.clone(),
+            password.to_string(),
+        ));
+    }
+
+    builder
+        .build()
+        .send(message)
+        .map(|_| ())
+        .map_err(|e| AppError::FileWrite(format!("Failed to send email: {}", e)))
+}
+
+fn keychain_entry(username: &str) -> AppResult<keyring::Entry> {
+    if username.is_empty() {
+        return Err(AppError::ConfigLoad(
+            "Set [email] username before storing an SMTP password".to_string(),
+        ));
+    }
+    keyring::Entry::new(KEYCHAIN_SERVICE, username)
+        .map_err(|e| AppError::ConfigLoad(format!("Failed to open keychain: {}", e)))
+}
+
+/// The stored SMTP password for `username`; empty when none is stored.
+pub fn load_smtp_password(username: &str) -> AppResult<String> {
+    if username.is_empty() {
+        return Ok(String::new());
+    }
+    match keychain_entry(username)?.get_password() {
+        Ok(password) => Ok(password),
+        Err(keyring::Error::NoEntry) => Ok(String::new()),
+        Err(e) => Err(AppError::ConfigLoad(format!(
+            "Failed to read SMTP password from keychain: {}",
+            e
+        ))),
+    }
+}
+
+pub fn store_smtp_password(username: &str, password: &str) -> AppResult<()> {
+    keychain_entry(username)?
+        .set_password(password)
+        .map_err(|e| AppError::ConfigSave(format!("Failed to store SMTP password: {}", e)))
+}
+
+/// Removes the stored password; returns whether there was one.
+pub fn delete_smtp_password(username: &str) -> AppResult<bool> {
+    match keychain_entry(username)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(AppError::ConfigSave(format!(
+            "Failed to remove SMTP password: {}",
+            e
+        ))),
+    }
+}
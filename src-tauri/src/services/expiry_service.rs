@@ -0,0 +1,161 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::{
+        derived_index_service::{refresh_index, DerivedIndex},
+        note_details_service::NOTE_DETAILS_INDEX,
+    },
+    utilities::{
+        frontmatter::parse_frontmatter,
+        strings::{extract_title_from_filename, parse_note_date},
+    },
+};
+use chrono::{Days, Local, NaiveDate};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+/// Front-matter `expires` date of each note that has one, as `YYYY-MM-DD`.
+pub const NOTE_EXPIRY_INDEX: DerivedIndex = DerivedIndex {
+    name: "note_expiry",
+    schema: "CREATE TABLE IF NOT EXISTS note_expiry (
+        filename TEXT PRIMARY KEY,
+        expires TEXT NOT NULL
+    );",
+    clear_note: clear_note_expiry,
+    index_note: index_note_expiry,
+};
+
+/// Expired notes the scheduler has already flagged or archived, per expiry
+/// date, so moving the date on flags the note again when that one passes.
+pub const EXPIRED_NOTES_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS expired_notes (
+    filename TEXT NOT NULL,
+    expires TEXT NOT NULL,
+    handled_at INTEGER NOT NULL,
+    PRIMARY KEY (filename, expires)
+);";
+
+/// Longest advance warning of `list_expiring_notes`, roughly ten years
+pub const MAX_EXPIRY_WARNING_DAYS: u32 = 3660;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExpiringNote {
+    pub note_name: String,
+    pub title: String,
+    pub expires: String,
+    /// Days until the note expires; 0 or less once it has
+    pub days_left: i64,
+    pub expired: bool,
+}
+
+/// The local date an `expires` value names: a date, or the day of a date
+/// and time.
+pub fn parse_expiry_date(value: &str) -> Option<NaiveDate> {
+    parse_note_date(value).map(|date| date.with_timezone(&Local).date_naive())
+}
+
+/// Notes that aren't archived and expire within `days` of `today`, or already
+/// have, soonest first. A note expires at the start of its `expires` date.
+pub fn expiring_notes(
+    conn: &Connection,
+    today: NaiveDate,
+    days: u32,
+) -> AppResult<Vec<ExpiringNote>> {
+    if days > MAX_EXPIRY_WARNING_DAYS {
+        return Err(AppError::validation_error(
+            "days",
+            &format!(
+                "Expiring notes can be listed at most {} days ahead",
+                MAX_EXPIRY_WARNING_DAYS
+            ),
+        ));
+    }
+    let horizon = today
+        .checked_add_days(Days::new(days.into()))
+        .unwrap_or(NaiveDate::MAX);
+    query_expiring(conn, today, horizon, false)
+}
+
+/// Expired notes the scheduler hasn't flagged or archived yet.
+pub fn newly_expired_notes(conn: &Connection, today: NaiveDate) -> AppResult<Vec<ExpiringNote>> {
+    query_expiring(conn, today, today, true)
+}
+
+/// Records that `notes` were flagged or archived, so they aren't again.
+pub fn mark_expired_handled(conn: &Connection, notes: &[ExpiringNote], now: i64) -> AppResult<()> {
+    conn.execute_batch(EXPIRED_NOTES_SCHEMA)?;
+    conn.execute(
+        "DELETE FROM expired_notes WHERE filename NOT IN (SELECT filename FROM notes)",
+        [],
+    )?;
+    for note in notes {
+        conn.execute(
+            "INSERT OR REPLACE INTO expired_notes (filename, expires, handled_at) VALUES (?1, ?2, ?3)",
+            params![note.note_name, note.expires, now],
+        )?;
+    }
+    Ok(())
+}
+
+fn query_expiring(
+    conn: &Connection,
+    today: NaiveDate,
+    horizon: NaiveDate,
+    unhandled_only: bool,
+) -> AppResult<Vec<ExpiringNote>> {
+    refresh_index(conn, &NOTE_EXPIRY_INDEX)?;
+    refresh_index(conn, &NOTE_DETAILS_INDEX)?;
+    conn.execute_batch(EXPIRED_NOTES_SCHEMA)?;
+
+    let unhandled = if unhandled_only {
+        " AND NOT EXISTS (SELECT 1 FROM expired_notes h
+                          WHERE h.filename = e.filename AND h.expires = e.expires)"
+    } else {
+        ""
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT e.filename, e.expires FROM note_expiry e
+         JOIN note_details d ON d.filename = e.filename
+         WHERE e.expires <= ?1 AND d.archived = 0{}
+         ORDER BY e.expires, e.filename",
+        unhandled
+    ))?;
+    let rows = stmt.query_map(params![horizon.to_string()], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut notes = Vec::new();
+    for row in rows {
+        let (note_name, expires) = row?;
+        let Ok(date) = NaiveDate::parse_from_str(&expires, "%Y-%m-%d") else {
+            continue;
+        };
+        let days_left = (date - today).num_days();
+        notes.push(ExpiringNote {
+            title: extract_title_from_filename(&note_name),
+            note_name,
+            expires,
+            days_left,
+            expired: days_left <= 0,
+        });
+    }
+    Ok(notes)
+}
+
+fn clear_note_expiry(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_expiry WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+fn index_note_expiry(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let expires = parse_frontmatter(content)
+        .and_then(|frontmatter| frontmatter.get_text("expires").and_then(parse_expiry_date));
+    if let Some(expires) = expires {
+        conn.execute(
+            "INSERT INTO note_expiry (filename, expires) VALUES (?1, ?2)",
+            params![filename, expires.to_string()],
+        )?;
+    }
+    Ok(())
+}
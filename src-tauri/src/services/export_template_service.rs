@@ -0,0 +1,168 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::search_export_service::csv_field,
+    utilities::{
+        frontmatter::{note_title, parse_frontmatter, strip_frontmatter, FrontmatterValue},
+        note_renderer::render_note,
+        strings::word_count,
+        tags::extract_tags,
+    },
+};
+use chrono::{DateTime, Local, TimeZone};
+use handlebars::{handlebars_helper, no_escape, Handlebars};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const TEMPLATE_EXTENSION: &str = "hbs";
+/// Output extension of templates not named like `letterhead.html.hbs`
+const DEFAULT_OUTPUT_EXTENSION: &str = "txt";
+/// Outputs whose templates HTML-escape `{{value}}`; others insert values as is
+const ESCAPED_EXTENSIONS: [&str; 4] = ["html", "htm", "xml", "svg"];
+
+handlebars_helper!(csv: |value: Json| csv_field(&match value {
+    Value::String(text) => text.clone(),
+    Value::Array(items) => items
+        .iter()
+        .map(|item| item.as_str().map_or_else(|| item.to_string(), str::to_string))
+        .collect::<Vec<_>>()
+        .join(", "),
+    Value::Null => String::new(),
+    other => other.to_string(),
+}));
+
+/// What an export template can use, e.g. `{{title}}`, `{{{html}}}` or
+/// `{{frontmatter.status}}`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportTemplateContext {
+    pub note_name: String,
+    pub title: String,
+    /// Folder of the note inside the notes directory, empty at the top level
+    pub folder: String,
+    /// The whole note, front matter included
+    pub content: String,
+    /// The note without its front matter
+    pub body: String,
+    pub html: String,
+    /// Front-matter fields as written; list values are arrays
+    pub frontmatter: BTreeMap<String, FrontmatterValue>,
+    pub tags: Vec<String>,
+    pub word_count: usize,
+    /// RFC 3339 local times
+    pub modified: String,
+    pub exported_at: String,
+}
+
+pub fn export_template_context(
+    note_name: &str,
+    content: &str,
+    modified: i64,
+    now: DateTime<Local>,
+) -> ExportTemplateContext {
+    let mut frontmatter = BTreeMap::new();
+    for (key, value) in parse_frontmatter(content).unwrap_or_default().fields {
+        frontmatter.entry(key).or_insert(value);
+    }
+    ExportTemplateContext {
+        note_name: note_name.to_string(),
+        title: note_title(note_name, content),
+        folder: note_name
+            .rsplit_once('/')
+            .map(|(folder, _)| folder.to_string())
+            .unwrap_or_default(),
+        content: content.to_string(),
+        body: strip_frontmatter(content).to_string(),
+        html: render_note(note_name, content),
+        frontmatter,
+        tags: extract_tags(content),
+        word_count: word_count(content),
+        modified: Local
+            .timestamp_opt(modified, 0)
+            .single()
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_default(),
+        exported_at: now.to_rfc3339(),
+    }
+}
+
+/// Export template names (file names ending in `.hbs`), sorted. A missing
+/// folder means there are none.
+pub fn list_export_templates(dir: &Path) -> AppResult<Vec<String>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(AppError::FileRead(format!(
+                "Failed to read export templates folder '{}': {}",
+                dir.display(),
+                e
+            )))
+        }
+    };
+
+    let mut templates: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with('.') && is_export_template_file(name))
+        .collect();
+    templates.sort_by_key(|name| name.to_lowercase());
+    Ok(templates)
+}
+
+fn is_export_template_file(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(TEMPLATE_EXTENSION))
+}
+
+/// Path of export template `name`, with or without its `.hbs` extension.
+/// Only files directly inside `dir` can be used.
+pub fn export_template_path(dir: &Path, name: &str) -> AppResult<PathBuf> {
+    let name = name.trim();
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(AppError::validation_error(
+            "template",
+            &format!("Invalid export template name '{}'", name),
+        ));
+    }
+    let file_name = if is_export_template_file(name) {
+        name.to_string()
+    } else {
+        format!("{}.{}", name, TEMPLATE_EXTENSION)
+    };
+    Ok(dir.join(file_name))
+}
+
+/// Extension of the files a template produces: `csv` for `row.csv.hbs`, and
+/// `txt` when the name doesn't say.
+pub fn template_output_extension(template_path: &Path) -> String {
+    template_path
+        .file_stem()
+        .map(Path::new)
+        .and_then(Path::extension)
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .unwrap_or_else(|| DEFAULT_OUTPUT_EXTENSION.to_string())
+}
+
+/// Renders `template` for a note. Values are HTML-escaped only for HTML and
+/// XML outputs; `{{csv value}}` quotes a value as a CSV field.
+pub fn render_export_template(
+    template: &str,
+    output_extension: &str,
+    context: &ExportTemplateContext,
+) -> AppResult<String> {
+    let mut handlebars = Handlebars::new();
+    if !ESCAPED_EXTENSIONS.contains(&output_extension) {
+        handlebars.register_escape_fn(no_escape);
+    }
+    handlebars.register_helper("csv", Box::new(csv));
+    handlebars
+        .render_template(template, context)
+        .map_err(|e| AppError::validation_error("template", &format!("Failed to render: {}", e)))
+}
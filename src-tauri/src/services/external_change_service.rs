@@ -0,0 +1,155 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::{
+        file_safety::write_app_data_file, line_diff::unified_diff, text_encoding::read_note_text,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// Unchanged lines shown around each change in a review diff
+const DIFF_CONTEXT_LINES: usize = 3;
+
+static EXTERNAL_CHANGES_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalChange {
+    pub note_name: String,
+    pub kind: ExternalChangeKind,
+    /// Unix seconds of the latest change to the note
+    pub detected_at: i64,
+    /// Backup of the note as it was before the first unreviewed change
+    pub backup_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExternalChangeReview {
+    pub note_name: String,
+    pub kind: ExternalChangeKind,
+    pub detected_at: i64,
+    /// Unified diff from the pre-change backup to the note as it is now;
+    /// `None` when there is nothing to compare against
+    pub diff: Option<String>,
+}
+
+pub fn load_external_changes(store_path: &Path) -> AppResult<Vec<ExternalChange>> {
+    let content = match fs::read_to_string(store_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(AppError::FileRead(format!(
+                "Failed to read external changes: {}",
+                e
+            )))
+        }
+    };
+
+    serde_json::from_str(&content)
+        .map_err(|e| AppError::FileRead(format!("Invalid external changes file: {}", e)))
+}
+
+fn save_external_changes(store_path: &Path, changes: &[ExternalChange]) -> AppResult<()> {
+    let content = serde_json::to_string_pretty(changes)
+        .map_err(|e| AppError::FileWrite(format!("Failed to serialize external changes: {}", e)))?;
+    write_app_data_file(store_path, &content)
+}
+
+/// Queues a change for review. Repeated changes to a note before it is
+/// acknowledged collapse into one entry that keeps the oldest backup, so the
+/// diff covers everything that happened since the last review.
+pub fn record_external_change(
+    store_path: &Path,
+    note_name: &str,
+    kind: ExternalChangeKind,
+    backup_path: Option<&Path>,
+    now: i64,
+) -> AppResult<()> {
+    let _guard = EXTERNAL_CHANGES_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut changes = load_external_changes(store_path)?;
+
+    match changes.iter().position(|c| c.note_name == note_name) {
+        Some(position) => {
+            let existing = &mut changes[position];
+            let merged = match (existing.kind, kind) {
+                // Appeared and vanished between reviews: nothing left to see
+                (ExternalChangeKind::Created, ExternalChangeKind::Deleted) => None,
+                (ExternalChangeKind::Created, _) => Some(ExternalChangeKind::Created),
+                (_, ExternalChangeKind::Deleted) => Some(ExternalChangeKind::Deleted),
+                _ => Some(ExternalChangeKind::Modified),
+            };
+            match merged {
+                Some(merged) => {
+                    existing.kind = merged;
+                    existing.detected_at = now;
+                    if existing.backup_path.is_none() && merged != ExternalChangeKind::Created {
+                        existing.backup_path = backup_path.map(Path::to_path_buf);
+                    }
+                }
+                None => {
+                    changes.remove(position);
+                }
+            }
+        }
+        None => changes.push(ExternalChange {
+            note_name: note_name.to_string(),
+            kind,
+            detected_at: now,
+            backup_path: backup_path.map(Path::to_path_buf),
+        }),
+    }
+
+    save_external_changes(store_path, &changes)
+}
+
+/// Removes `note_name` from the review queue; returns whether it was queued.
+pub fn acknowledge_external_change(store_path: &Path, note_name: &str) -> AppResult<bool> {
+    let _guard = EXTERNAL_CHANGES_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut changes = load_external_changes(store_path)?;
+    let before = changes.len();
+    changes.retain(|c| c.note_name != note_name);
+    if changes.len() == before {
+        return Ok(false);
+    }
+    save_external_changes(store_path, &changes)?;
+    Ok(true)
+}
+
+/// Pairs a queued change with its diff. Created notes are diffed against an
+/// empty note and deleted ones to an empty note; without a readable backup
+/// there is no diff.
+pub fn review_external_change(change: &ExternalChange, notes_dir: &Path) -> ExternalChangeReview {
+    let before = match (&change.kind, &change.backup_path) {
+        (ExternalChangeKind::Created, _) => Some(String::new()),
+        (_, Some(backup_path)) => read_note_text(backup_path).ok(),
+        (_, None) => None,
+    };
+    let after = match change.kind {
+        ExternalChangeKind::Deleted => Some(String::new()),
+        _ => read_note_text(&notes_dir.join(&change.note_name)).ok(),
+    };
+
+    ExternalChangeReview {
+        note_name: change.note_name.clone(),
+        kind: change.kind,
+        detected_at: change.detected_at,
+        diff: before
+            .zip(after)
+            .map(|(before, after)| unified_diff(&before, &after, DIFF_CONTEXT_LINES)),
+    }
+}
@@ -0,0 +1,82 @@
+use crate::core::{AppError, AppResult};
+
+/// A folder path relative to the notes directory, without surrounding
+/// slashes. Rejects empty, escaping or hidden folders, which the scanner and
+/// the watcher would skip.
+pub fn normalize_folder_name(folder: &str) -> AppResult<String> {
+    let folder = folder.trim().trim_matches('/');
+    if folder.is_empty() {
+        return Err(AppError::validation_error(
+            "folder",
+            "Folder cannot be empty",
+        ));
+    }
+    if folder.contains('\\') || folder.len() > 255 {
+        return Err(AppError::validation_error("folder", "Invalid folder name"));
+    }
+    let parts: Vec<&str> = folder.split('/').map(str::trim).collect();
+    if parts
+        .iter()
+        .any(|part| part.is_empty() || *part == "." || *part == "..")
+    {
+        return Err(AppError::PathTraversal);
+    }
+    if parts.iter().any(|part| part.starts_with('.')) {
+        return Err(AppError::validation_error(
+            "folder",
+            "Folder names cannot start with a dot",
+        ));
+    }
+    Ok(parts.join("/"))
+}
+
+/// Whether `note_name` lies somewhere inside `folder`.
+pub fn is_in_folder(note_name: &str, folder: &str) -> bool {
+    note_name
+        .strip_prefix(folder)
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// `note_name` after its containing `old_folder` was renamed to `new_folder`.
+pub fn renamed_note_name(note_name: &str, old_folder: &str, new_folder: &str) -> Option<String> {
+    if !is_in_folder(note_name, old_folder) {
+        return None;
+    }
+    Some(format!("{}{}", new_folder, &note_name[old_folder.len()..]))
+}
+
+/// `note_name` moved directly into `folder`, or to the top of the notes
+/// directory when `folder` is `None`.
+pub fn note_in_folder(note_name: &str, folder: Option<&str>) -> String {
+    let file_name = note_name.rsplit('/').next().unwrap_or(note_name);
+    match folder {
+        Some(folder) => format!("{}/{}", folder, file_name),
+        None => file_name.to_string(),
+    }
+}
+
+/// Folder a new note goes in under `[preferences] new_note_location`: the
+/// inbox folder, the folder of `current_note`, or `None` for the top of the
+/// notes directory. `same_folder` with no note open (or one at the top) and
+/// an invalid inbox folder also mean the top.
+pub fn new_note_folder(
+    location: &str,
+    inbox_folder: &str,
+    current_note: Option<&str>,
+) -> Option<String> {
+    match location {
+        "inbox" => normalize_folder_name(inbox_folder).ok(),
+        "same_folder" => current_note?
+            .rsplit_once('/')
+            .map(|(folder, _)| folder.to_string()),
+        _ => None,
+    }
+}
+
+/// `note_name`, which may have folders of its own, inside `folder`.
+pub fn note_under_folder(note_name: &str, folder: Option<&str>) -> String {
+    match folder {
+        Some(folder) => format!("{}/{}", folder, note_name),
+        None => note_name.to_string(),
+    }
+}
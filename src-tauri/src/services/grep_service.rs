@@ -0,0 +1,155 @@
+use crate::core::{AppError, AppResult};
+use regex::{Regex, RegexBuilder};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::{num::NonZeroUsize, thread};
+
+/// Matching lines returned per note; further matches only set `truncated`
+pub const MAX_MATCHES_PER_NOTE: usize = 100;
+/// Matched lines longer than this are cut, dropping match ranges past the cut
+const MAX_LINE_CHARS: usize = 500;
+/// Compiled-program budget so a pathological pattern fails instead of
+/// exhausting memory
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Options mirroring the usual grep switches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct GrepFlags {
+    /// `-i`
+    pub case_insensitive: bool,
+    /// `-w`: only matches with word boundaries on both sides
+    pub whole_word: bool,
+    /// `-F`: the pattern is a literal string, not a regex
+    pub fixed_strings: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GrepLineMatch {
+    /// 1-based
+    pub line_number: usize,
+    pub line: String,
+    /// `[start, end)` character offsets of each match within `line`
+    pub ranges: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GrepNoteMatches {
+    pub filename: String,
+    pub matches: Vec<GrepLineMatch>,
+    /// More lines matched than `MAX_MATCHES_PER_NOTE`
+    pub truncated: bool,
+}
+
+pub fn build_grep_regex(pattern: &str, flags: GrepFlags) -> AppResult<Regex> {
+    if pattern.is_empty() {
+        return Err(AppError::validation_error("pattern", "Pattern is empty"));
+    }
+    let mut pattern = if flags.fixed_strings {
+        regex::escape(pattern)
+    } else {
+        pattern.to_string()
+    };
+    if flags.whole_word {
+        pattern = format!(r"\b(?:{})\b", pattern);
+    }
+
+    RegexBuilder::new(&pattern)
+        .case_insensitive(flags.case_insensitive)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| AppError::validation_error("pattern", &format!("Invalid regex: {}", e)))
+}
+
+/// Matching lines of one note, or `None` when nothing matches.
+pub fn grep_content(regex: &Regex, filename: &str, content: &str) -> Option<GrepNoteMatches> {
+    let mut matches = Vec::new();
+    let mut truncated = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let mut ranges = Vec::new();
+        let mut matched = false;
+        for found in regex.find_iter(line) {
+            matched = true;
+            // Empty matches (e.g. `^`) mark the line without highlighting
+            if found.start() == found.end() {
+                continue;
+            }
+            let start = line[..found.start()].chars().count();
+            let end = start + found.as_str().chars().count();
+            if start >= MAX_LINE_CHARS {
+                break;
+            }
+            ranges.push((start, end.min(MAX_LINE_CHARS)));
+        }
+        if !matched {
+            continue;
+        }
+        if matches.len() == MAX_MATCHES_PER_NOTE {
+            truncated = true;
+            break;
+        }
+        matches.push(GrepLineMatch {
+            line_number: index + 1,
+            line: line.chars().take(MAX_LINE_CHARS).collect(),
+            ranges,
+        });
+    }
+
+    if matches.is_empty() {
+        None
+    } else {
+        Some(GrepNoteMatches {
+            filename: filename.to_string(),
+            matches,
+            truncated,
+        })
+    }
+}
+
+/// Scans every indexed note's content with `regex`, splitting the notes
+/// across threads. Results are ordered by filename; at most `max_notes`.
+pub fn grep_notes(
+    conn: &Connection,
+    regex: &Regex,
+    max_notes: usize,
+) -> AppResult<Vec<GrepNoteMatches>> {
+    let mut stmt = conn.prepare("SELECT filename, content FROM notes ORDER BY filename")?;
+    let notes = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    if notes.is_empty() || max_notes == 0 {
+        return Ok(Vec::new());
+    }
+
+    let workers = thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1);
+    let chunk_size = notes.len().div_ceil(workers);
+
+    // Chunks are contiguous runs of the sorted notes, so joining them in
+    // spawn order keeps the filename order
+    let results = thread::scope(|scope| {
+        let handles: Vec<_> = notes
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .filter_map(|(filename, content)| grep_content(regex, filename, content))
+                        .take(max_notes)
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join())
+            .collect::<Result<Vec<_>, _>>()
+    })
+    .map_err(|_| AppError::SearchQuery("Grep worker panicked".to_string()))?;
+
+    Ok(results.into_iter().flatten().take(max_notes).collect())
+}
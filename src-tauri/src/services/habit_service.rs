@@ -0,0 +1,239 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::derived_index_service::{refresh_index, DerivedIndex},
+    utilities::{
+        dates::{date_from_filename, DateRange},
+        tasks::extract_tasks,
+    },
+};
+use chrono::{Duration, NaiveDate};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Longest history returned in one call, roughly ten years
+const MAX_HISTORY_DAYS: i64 = 3660;
+
+/// Every checkbox of every daily note, so changing the habit list needs no reindex.
+pub const HABITS_INDEX: DerivedIndex = DerivedIndex {
+    name: "habits",
+    schema: "CREATE TABLE IF NOT EXISTS habits (
+        filename TEXT NOT NULL,
+        date TEXT NOT NULL,
+        task TEXT NOT NULL,
+        done INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS habits_date ON habits(date);
+    CREATE INDEX IF NOT EXISTS habits_filename ON habits(filename);",
+    clear_note: clear_note_habits,
+    index_note: index_note_habits,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HabitStatus {
+    Done,
+    /// The daily note lists the habit but it isn't checked
+    Missed,
+    /// No daily note mentions the habit that day
+    Untracked,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HabitDay {
+    pub date: String,
+    pub status: HabitStatus,
+    pub note_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HabitHistory {
+    pub habit: String,
+    pub from: String,
+    pub to: String,
+    /// One entry per day, oldest first
+    pub days: Vec<HabitDay>,
+    pub tracked_days: usize,
+    pub completed_days: usize,
+    /// Completed share of tracked days, 0.0 when nothing was tracked
+    pub completion_rate: f64,
+    /// Done days ending at `to`; an unfinished `to` doesn't break the streak yet
+    pub current_streak: usize,
+    pub longest_streak: usize,
+}
+
+/// Whether a checkbox text (already lowercased) is an entry for `habit`, allowing
+/// trailing detail such as `exercise: 30 min run`.
+pub fn task_matches_habit(task: &str, habit: &str) -> bool {
+    task.strip_prefix(habit)
+        .is_some_and(|rest| !rest.starts_with(char::is_alphanumeric))
+}
+
+/// Whether `filename` is in `[habits] daily_notes_folder`; any folder when it's
+/// empty.
+pub fn in_daily_notes_folder(filename: &str, daily_notes_folder: &str) -> bool {
+    let folder = daily_notes_folder.trim_matches('/');
+    folder.is_empty()
+        || filename
+            .strip_prefix(folder)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Day-by-day history of a habit with streaks and completion rate. `to` defaults
+/// to `today` and `from` to the first day the habit was tracked.
+pub fn habit_history(
+    conn: &Connection,
+    habit: &str,
+    daily_notes_folder: &str,
+    range: &DateRange,
+    today: NaiveDate,
+) -> AppResult<HabitHistory> {
+    let key = normalize_habit(habit);
+    if key.is_empty() {
+        return Err(AppError::validation_error("habit", "Habit cannot be empty"));
+    }
+    let (from, to) = range.resolve(today)?;
+    refresh_index(conn, &HABITS_INDEX)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT filename, date, task, done FROM habits
+         WHERE substr(task, 1, length(?1)) = ?1
+         ORDER BY date, filename",
+    )?;
+    let rows = stmt.query_map(params![key], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, bool>(3)?,
+        ))
+    })?;
+
+    // Several notes can share a date; any checked entry makes the day done
+    let mut entries: BTreeMap<NaiveDate, (bool, String)> = BTreeMap::new();
+    for row in rows {
+        let (filename, date, task, done) = row?;
+        let in_folder = in_daily_notes_folder(&filename, daily_notes_folder);
+        let Ok(date) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") else {
+            continue;
+        };
+        if !in_folder || !task_matches_habit(&task, &key) {
+            continue;
+        }
+        let entry = entries.entry(date).or_insert((done, filename.clone()));
+        if done && !entry.0 {
+            *entry = (true, filename);
+        }
+    }
+
+    let to = to.unwrap_or(today);
+    let from = from
+        .or_else(|| entries.keys().next().copied())
+        .unwrap_or(to)
+        .min(to);
+    if (to - from).num_days() >= MAX_HISTORY_DAYS {
+        return Err(AppError::validation_error(
+            "from",
+            "Habit history is limited to ten years at a time",
+        ));
+    }
+
+    let mut days = Vec::new();
+    let mut date = from;
+    while date <= to {
+        days.push(match entries.get(&date) {
+            Some((done, note_name)) => HabitDay {
+                date: date.to_string(),
+                status: if *done {
+                    HabitStatus::Done
+                } else {
+                    HabitStatus::Missed
+                },
+                note_name: Some(note_name.clone()),
+            },
+            None => HabitDay {
+                date: date.to_string(),
+                status: HabitStatus::Untracked,
+                note_name: None,
+            },
+        });
+        date += Duration::days(1);
+    }
+
+    let tracked_days = days
+        .iter()
+        .filter(|day| day.status != HabitStatus::Untracked)
+        .count();
+    let completed_days = days
+        .iter()
+        .filter(|day| day.status == HabitStatus::Done)
+        .count();
+
+    let mut longest_streak = 0;
+    let mut run = 0;
+    for day in &days {
+        run = if day.status == HabitStatus::Done {
+            run + 1
+        } else {
+            0
+        };
+        longest_streak = longest_streak.max(run);
+    }
+
+    let mut recent = days.iter().rev().peekable();
+    if recent
+        .peek()
+        .is_some_and(|day| day.status != HabitStatus::Done)
+    {
+        recent.next();
+    }
+    let current_streak = recent
+        .take_while(|day| day.status == HabitStatus::Done)
+        .count();
+
+    Ok(HabitHistory {
+        habit: habit.trim().to_string(),
+        from: from.to_string(),
+        to: to.to_string(),
+        days,
+        tracked_days,
+        completed_days,
+        completion_rate: if tracked_days == 0 {
+            0.0
+        } else {
+            completed_days as f64 / tracked_days as f64
+        },
+        current_streak,
+        longest_streak,
+    })
+}
+
+fn normalize_habit(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn clear_note_habits(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM habits WHERE filename = ?1", params![filename])?;
+    Ok(())
+}
+
+fn index_note_habits(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let Some(date) = date_from_filename(filename) else {
+        return Ok(());
+    };
+
+    let mut stmt =
+        conn.prepare("INSERT INTO habits (filename, date, task, done) VALUES (?1, ?2, ?3, ?4)")?;
+    for task in extract_tasks(content) {
+        stmt.execute(params![
+            filename,
+            date.to_string(),
+            normalize_habit(&task.text),
+            task.done
+        ])?;
+    }
+    Ok(())
+}
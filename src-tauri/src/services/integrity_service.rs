@@ -0,0 +1,250 @@
+use crate::core::AppResult;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Last known good checksum of every note file, and the notes whose file no
+/// longer matches it in a way an edit wouldn't explain. Kept outside the notes
+/// table so they survive index rebuilds.
+pub const INTEGRITY_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS note_checksums (
+    filename TEXT PRIMARY KEY,
+    hash TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    modified_ms INTEGER NOT NULL,
+    verified_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS integrity_alerts (
+    filename TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    message TEXT NOT NULL,
+    detected_at INTEGER NOT NULL
+);";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityAlertKind {
+    /// The content changed but the modification time and size didn't
+    Corrupted,
+    /// A note that had content is now empty
+    Truncated,
+    /// A run of zero bytes appeared in the file, as left by an interrupted
+    /// sync
+    ZeroFilled,
+}
+
+impl IntegrityAlertKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            IntegrityAlertKind::Corrupted => "corrupted",
+            IntegrityAlertKind::Truncated => "truncated",
+            IntegrityAlertKind::ZeroFilled => "zero_filled",
+        }
+    }
+
+    fn parse(kind: &str) -> Self {
+        match kind {
+            "truncated" => IntegrityAlertKind::Truncated,
+            "zero_filled" => IntegrityAlertKind::ZeroFilled,
+            _ => IntegrityAlertKind::Corrupted,
+        }
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            IntegrityAlertKind::Corrupted => {
+                "Content changed on disk without its modification time changing; restore it from Versions"
+            }
+            IntegrityAlertKind::Truncated => "File is now empty; restore it from Versions",
+            IntegrityAlertKind::ZeroFilled => {
+                "File contains zero bytes, as left by an interrupted sync; restore it from Versions"
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IntegrityAlert {
+    pub note_name: String,
+    pub kind: IntegrityAlertKind,
+    pub message: String,
+    pub detected_at: i64,
+}
+
+/// Zero bytes in a row that no text file has, UTF-16 ones included
+const ZERO_RUN: usize = 8;
+
+/// Checksum of a note file as last verified
+struct Checksum {
+    hash: String,
+    size: i64,
+    modified_ms: i64,
+}
+
+fn file_checksum(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Why a file no longer matching `previous` looks damaged rather than edited
+fn classify_change(
+    previous: &Checksum,
+    bytes: &[u8],
+    modified_ms: i64,
+) -> Option<IntegrityAlertKind> {
+    if modified_ms == previous.modified_ms && bytes.len() as i64 == previous.size {
+        Some(IntegrityAlertKind::Corrupted)
+    } else if bytes.is_empty() && previous.size > 0 {
+        Some(IntegrityAlertKind::Truncated)
+    } else if bytes
+        .windows(ZERO_RUN)
+        .any(|window| window.iter().all(|&byte| byte == 0))
+    {
+        Some(IntegrityAlertKind::ZeroFilled)
+    } else {
+        None
+    }
+}
+
+/// Compares every indexed note in `notes_dir` against its last known good
+/// checksum. Edits become the new checksum; changes that look like corruption
+/// or truncation raise an alert and keep the old checksum until the file is
+/// restored or the alert dismissed. Returns the alerts raised by this run.
+pub fn verify_notes(
+    conn: &Connection,
+    notes_dir: &Path,
+    now: i64,
+) -> AppResult<Vec<IntegrityAlert>> {
+    conn.execute_batch(INTEGRITY_SCHEMA)?;
+    conn.execute(
+        "DELETE FROM note_checksums WHERE filename NOT IN (SELECT filename FROM notes)",
+        [],
+    )?;
+    conn.execute(
+        "DELETE FROM integrity_alerts WHERE filename NOT IN (SELECT filename FROM notes)",
+        [],
+    )?;
+
+    let filenames: Vec<String> = conn
+        .prepare("SELECT filename FROM notes ORDER BY filename")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    let mut raised = Vec::new();
+    for filename in filenames {
+        // Missing or unreadable files are the sync's to report
+        let path = notes_dir.join(&filename);
+        let (Ok(metadata), Ok(bytes)) = (fs::metadata(&path), fs::read(&path)) else {
+            continue;
+        };
+        let modified_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_millis() as i64);
+        let hash = file_checksum(&bytes);
+
+        let previous = conn
+            .query_row(
+                "SELECT hash, size, modified_ms FROM note_checksums WHERE filename = ?1",
+                params![filename],
+                |row| {
+                    Ok(Checksum {
+                        hash: row.get(0)?,
+                        size: row.get(1)?,
+                        modified_ms: row.get(2)?,
+                    })
+                },
+            )
+            .optional()?;
+
+        let damage = previous
+            .filter(|previous| previous.hash != hash)
+            .and_then(|previous| classify_change(&previous, &bytes, modified_ms));
+
+        match damage {
+            Some(kind) => {
+                let already_raised = conn
+                    .query_row(
+                        "SELECT 1 FROM integrity_alerts WHERE filename = ?1",
+                        params![filename],
+                        |_| Ok(()),
+                    )
+                    .optional()?
+                    .is_some();
+                if !already_raised {
+                    let alert = IntegrityAlert {
+                        note_name: filename,
+                        kind,
+                        message: kind.message().to_string(),
+                        detected_at: now,
+                    };
+                    conn.execute(
+                        "INSERT INTO integrity_alerts (filename, kind, message, detected_at) VALUES (?1, ?2, ?3, ?4)",
+                        params![alert.note_name, kind.as_str(), alert.message, now],
+                    )?;
+                    raised.push(alert);
+                }
+            }
+            None => {
+                // Unchanged, edited, or restored after an alert
+                conn.execute(
+                    "DELETE FROM integrity_alerts WHERE filename = ?1",
+                    params![filename],
+                )?;
+                conn.execute(
+                    "INSERT OR REPLACE INTO note_checksums (filename, hash, size, modified_ms, verified_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![filename, hash, bytes.len() as i64, modified_ms, now],
+                )?;
+            }
+        }
+    }
+    Ok(raised)
+}
+
+/// Open alerts, newest first.
+pub fn integrity_alerts(conn: &Connection) -> AppResult<Vec<IntegrityAlert>> {
+    conn.execute_batch(INTEGRITY_SCHEMA)?;
+    let mut stmt = conn.prepare(
+        "SELECT filename, kind, message, detected_at FROM integrity_alerts
+         ORDER BY detected_at DESC, filename",
+    )?;
+    let alerts = stmt
+        .query_map([], |row| {
+            Ok(IntegrityAlert {
+                note_name: row.get(0)?,
+                kind: IntegrityAlertKind::parse(&row.get::<_, String>(1)?),
+                message: row.get(2)?,
+                detected_at: row.get(3)?,
+            })
+        })?
+        .collect::<Result<_, _>>()?;
+    Ok(alerts)
+}
+
+/// Accepts the note as it is on disk now: drops its alert and forgets the old
+/// checksum, so the next verification takes the file as good. False when the
+/// note had no alert.
+pub fn dismiss_integrity_alert(conn: &Connection, note_name: &str) -> AppResult<bool> {
+    conn.execute_batch(INTEGRITY_SCHEMA)?;
+    let removed = conn.execute(
+        "DELETE FROM integrity_alerts WHERE filename = ?1",
+        params![note_name],
+    )?;
+    conn.execute(
+        "DELETE FROM note_checksums WHERE filename = ?1",
+        params![note_name],
+    )?;
+    Ok(removed > 0)
+}
+
+/// Unix seconds of the latest verification, `None` before the first.
+pub fn last_verified_at(conn: &Connection) -> AppResult<Option<i64>> {
+    conn.execute_batch(INTEGRITY_SCHEMA)?;
+    Ok(
+        conn.query_row("SELECT MAX(verified_at) FROM note_checksums", [], |row| {
+            row.get(0)
+        })?,
+    )
+}
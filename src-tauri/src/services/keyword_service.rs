@@ -0,0 +1,208 @@
+use crate::{
+    core::AppResult,
+    search::{scope_conditions, SearchScope},
+};
+use rusqlite::{params_from_iter, types::Value, Connection};
+use serde::Serialize;
+
+/// Shortest term worth showing; shorter tokens are mostly markup or initials
+const MIN_TERM_CHARS: usize = 3;
+
+/// Common English words and front-matter keys that say nothing about what a
+/// vault is about.
+const STOP_WORDS: &[&str] = &[
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "also",
+    "and",
+    "any",
+    "are",
+    "aren",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "can",
+    "could",
+    "did",
+    "does",
+    "doing",
+    "done",
+    "down",
+    "during",
+    "each",
+    "even",
+    "few",
+    "for",
+    "from",
+    "further",
+    "get",
+    "got",
+    "had",
+    "has",
+    "have",
+    "having",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "http",
+    "https",
+    "into",
+    "its",
+    "itself",
+    "just",
+    "let",
+    "like",
+    "made",
+    "make",
+    "many",
+    "may",
+    "more",
+    "most",
+    "much",
+    "must",
+    "myself",
+    "need",
+    "not",
+    "now",
+    "off",
+    "once",
+    "one",
+    "only",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "same",
+    "see",
+    "she",
+    "should",
+    "some",
+    "such",
+    "tags",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "title",
+    "too",
+    "under",
+    "until",
+    "upon",
+    "use",
+    "used",
+    "very",
+    "was",
+    "way",
+    "well",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "would",
+    "www",
+    "yet",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Keyword {
+    pub term: String,
+    /// Occurrences across the notes in scope
+    pub count: usize,
+    /// Notes containing the term
+    pub notes: usize,
+}
+
+pub fn is_keyword(term: &str) -> bool {
+    term.chars().count() >= MIN_TERM_CHARS
+        && term.chars().any(char::is_alphabetic)
+        && !STOP_WORDS.contains(&term)
+}
+
+/// The `limit` most frequent terms in note content, read from the FTS
+/// vocabulary so notes aren't tokenized again. Terms are as indexed: folded,
+/// and stemmed with the `porter` tokenizer.
+pub fn keyword_cloud(
+    conn: &Connection,
+    scope: &SearchScope,
+    limit: usize,
+) -> AppResult<Vec<Keyword>> {
+    let mut values: Vec<Value> = Vec::new();
+    let conditions = scope_conditions(conn, scope, &mut values)?;
+
+    let sql = if conditions.is_empty() {
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS temp.notes_vocab_col USING fts5vocab(main, 'notes', 'col');",
+        )?;
+        "SELECT term, cnt, doc FROM temp.notes_vocab_col WHERE col = 'content' ORDER BY cnt DESC, term"
+            .to_string()
+    } else {
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS temp.notes_vocab_instance USING fts5vocab(main, 'notes', 'instance');",
+        )?;
+        format!(
+            "SELECT v.term, COUNT(*) AS cnt, COUNT(DISTINCT v.doc)
+             FROM temp.notes_vocab_instance v JOIN notes n ON n.rowid = v.doc
+             WHERE v.col = 'content'{}
+             GROUP BY v.term ORDER BY cnt DESC, v.term",
+            conditions
+        )
+    };
+
+    let mut stmt = conn.prepare(&sql)?;
+    let mut rows = stmt.query(params_from_iter(values))?;
+    let mut keywords = Vec::new();
+    while keywords.len() < limit {
+        let Some(row) = rows.next()? else {
+            break;
+        };
+        let term: String = row.get(0)?;
+        if !is_keyword(&term) {
+            continue;
+        }
+        keywords.push(Keyword {
+            term,
+            count: row.get::<_, i64>(1)? as usize,
+            notes: row.get::<_, i64>(2)? as usize,
+        });
+    }
+    Ok(keywords)
+}
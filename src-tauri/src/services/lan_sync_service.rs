@@ -0,0 +1,442 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::file_safety::write_app_data_file,
+};
+use age::secrecy::ExposeSecret;
+use rand::Rng;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+pub const SERVICE_TYPE: &str = "_symiosis._tcp.local.";
+pub const PROTOCOL_VERSION: u32 = 2;
+/// How long a device accepts a pairing request, and a pairing waits for the
+/// user to confirm its fingerprint
+pub const PAIRING_WINDOW_SECS: u64 = 300;
+pub const MAX_FRAME_BYTES: u32 = 64 * 1024 * 1024;
+/// Largest frame read before the peer has proved it holds a paired key: pairing
+/// messages, sync hellos and challenges
+pub const MAX_HANDSHAKE_BYTES: u32 = 8 * 1024;
+const NONCE_BYTES: usize = 32;
+/// 128 bits, so a device in the middle can't generate keys to match it
+const FINGERPRINT_BYTES: usize = 16;
+
+/// This device's LAN identity. The age key encrypts sync traffic addressed to
+/// this device and proves its identity to paired peers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceIdentity {
+    pub device_id: String,
+    pub device_name: String,
+    secret_key: String,
+}
+
+impl DeviceIdentity {
+    pub fn generate(device_name: &str) -> Self {
+        let identity = age::x25519::Identity::generate();
+        Self {
+            device_id: random_hex(16),
+            device_name: device_name.to_string(),
+            secret_key: identity.to_string().expose_secret().to_string(),
+        }
+    }
+
+    pub fn identity(&self) -> AppResult<age::x25519::Identity> {
+        age::x25519::Identity::from_str(&self.secret_key)
+            .map_err(|e| AppError::ConfigLoad(format!("Invalid device key: {}", e)))
+    }
+
+    pub fn recipient(&self) -> AppResult<String> {
+        Ok(self.identity()?.to_public().to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairedPeer {
+    pub device_id: String,
+    pub device_name: String,
+    /// age public key sync traffic for this peer is encrypted to
+    pub recipient: String,
+    pub paired_at: String,
+}
+
+/// A device met while pairing, not trusted until the user confirms that both
+/// devices show the same fingerprint.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PendingPairing {
+    pub peer: PairedPeer,
+    pub fingerprint: String,
+}
+
+/// Both sides of a divergent edit, kept until the user picks a resolution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncConflict {
+    pub note_name: String,
+    pub peer_id: String,
+    pub peer_name: String,
+    pub remote_content: String,
+    pub detected_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    KeepLocal,
+    KeepRemote,
+    /// Keep the local note and save the remote version next to it
+    KeepBoth,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SyncPlan {
+    pub push: Vec<String>,
+    pub pull: Vec<String>,
+    pub conflicts: Vec<String>,
+    pub in_sync: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    pub peer_id: String,
+    pub pushed: Vec<String>,
+    pub pulled: Vec<String>,
+    pub conflicts: Vec<String>,
+    /// Notes left as they were: pushes refused because the copy changed while
+    /// syncing, and notes that couldn't be saved; they are retried next sync
+    pub rejected: Vec<String>,
+}
+
+// Wire messages. Plain frames are JSON; `Sealed*` messages travel age-encrypted
+// to the receiving device's key.
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientHello {
+    Pair {
+        protocol: u32,
+        device_id: String,
+        device_name: String,
+        recipient: String,
+    },
+    Sync {
+        protocol: u32,
+        device_id: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairReply {
+    pub accepted: bool,
+    pub error: Option<String>,
+    pub device_id: String,
+    pub device_name: String,
+    pub recipient: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedChallenge {
+    pub nonce: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedOffer {
+    pub nonce_echo: String,
+    pub nonce: String,
+    /// filename -> content hash
+    pub manifest: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncedNote {
+    pub filename: String,
+    pub content: String,
+    /// Receiver's hash this change was based on; `None` for notes it doesn't have
+    pub expected_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedExchange {
+    pub nonce_echo: String,
+    pub push: Vec<SyncedNote>,
+    pub want: Vec<String>,
+    /// Hashes both sides agree on after this exchange, excluding conflicts
+    pub bases: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedDelivery {
+    pub notes: Vec<SyncedNote>,
+    pub rejected: Vec<String>,
+}
+
+pub fn load_or_create_device(path: &Path, default_name: &str) -> AppResult<DeviceIdentity> {
+    if let Ok(json) = fs::read_to_string(path) {
+        if let Ok(device) = serde_json::from_str::<DeviceIdentity>(&json) {
+            return Ok(device);
+        }
+    }
+
+    let device = DeviceIdentity::generate(default_name);
+    save_json(path, &device)?;
+    Ok(device)
+}
+
+pub fn load_paired_peers(path: &Path) -> AppResult<Vec<PairedPeer>> {
+    load_json(path)
+}
+
+pub fn save_paired_peer(path: &Path, peer: PairedPeer) -> AppResult<()> {
+    let mut peers = load_paired_peers(path)?;
+    peers.retain(|existing| existing.device_id != peer.device_id);
+    peers.push(peer);
+    save_json(path, &peers)
+}
+
+pub fn remove_paired_peer(path: &Path, device_id: &str) -> AppResult<bool> {
+    let mut peers = load_paired_peers(path)?;
+    let before = peers.len();
+    peers.retain(|peer| peer.device_id != device_id);
+    if peers.len() == before {
+        return Ok(false);
+    }
+    save_json(path, &peers)?;
+    Ok(true)
+}
+
+pub fn load_sync_bases(path: &Path) -> AppResult<HashMap<String, String>> {
+    load_json(path)
+}
+
+pub fn save_sync_bases(path: &Path, bases: &HashMap<String, String>) -> AppResult<()> {
+    let sorted: BTreeMap<_, _> = bases.iter().collect();
+    save_json(path, &sorted)
+}
+
+pub fn load_conflicts(path: &Path) -> AppResult<Vec<SyncConflict>> {
+    load_json(path)
+}
+
+pub fn save_conflicts(path: &Path, conflicts: &[SyncConflict]) -> AppResult<()> {
+    save_json(path, &conflicts)
+}
+
+/// Adds or replaces the open conflict for a note.
+pub fn record_conflict(conflicts: &mut Vec<SyncConflict>, conflict: SyncConflict) {
+    conflicts.retain(|existing| existing.note_name != conflict.note_name);
+    conflicts.push(conflict);
+}
+
+pub fn generate_nonce() -> String {
+    random_hex(NONCE_BYTES)
+}
+
+/// Fingerprint of the two device identities exchanged while pairing, shown on
+/// both devices for the user to compare. It is the same whichever side computes
+/// it; a device in the middle has to swap in a key of its own, so each side
+/// would show a different one.
+pub fn pairing_fingerprint(
+    device_id: &str,
+    recipient: &str,
+    peer_id: &str,
+    peer_recipient: &str,
+) -> String {
+    let mut sides = [(device_id, recipient), (peer_id, peer_recipient)];
+    sides.sort();
+
+    let mut hasher = Sha256::new();
+    hasher.update(b"symiosis-pairing");
+    for (id, key) in sides {
+        hasher.update(format!("\n{}\n{}", id, key).as_bytes());
+    }
+    let digest = hex(&hasher.finalize()[..FINGERPRINT_BYTES]);
+    digest
+        .as_bytes()
+        .chunks(4)
+        .map(|group| String::from_utf8_lossy(group).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn content_hash(content: &str) -> String {
+    hex(&Sha256::digest(content.as_bytes()))
+}
+
+/// Three-way comparison of local and remote note hashes against the hashes
+/// recorded at the last successful sync with that peer. Deletions are not
+/// propagated: a note missing on one side is copied over.
+pub fn plan_sync(
+    local: &HashMap<String, String>,
+    remote: &HashMap<String, String>,
+    base: &HashMap<String, String>,
+) -> SyncPlan {
+    let mut names: Vec<&String> = local.keys().chain(remote.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    let mut plan = SyncPlan::default();
+    for name in names {
+        let target = match (local.get(name), remote.get(name)) {
+            (Some(l), Some(r)) if l == r => &mut plan.in_sync,
+            (Some(l), Some(r)) => match base.get(name) {
+                Some(b) if b == r => &mut plan.push,
+                Some(b) if b == l => &mut plan.pull,
+                _ => &mut plan.conflicts,
+            },
+            (Some(_), None) => &mut plan.push,
+            (None, Some(_)) => &mut plan.pull,
+            (None, None) => continue,
+        };
+        target.push(name.clone());
+    }
+    plan
+}
+
+/// Name for the remote side of a conflict kept alongside the local note.
+pub fn conflict_copy_name(note_name: &str, peer_name: &str) -> String {
+    let peer: String = peer_name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == ' ' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let label = format!(" (conflict from {})", peer.trim());
+
+    match note_name.rfind('.') {
+        Some(index) if index > note_name.rfind('/').map_or(0, |slash| slash + 1) => {
+            format!("{}{}{}", &note_name[..index], label, &note_name[index..])
+        }
+        _ => format!("{}{}", note_name, label),
+    }
+}
+
+pub fn write_frame<W: Write>(writer: &mut W, payload: &[u8]) -> AppResult<()> {
+    let length = u32::try_from(payload.len())
+        .ok()
+        .filter(|len| *len <= MAX_FRAME_BYTES)
+        .ok_or_else(|| AppError::FileWrite("Sync message too large".to_string()))?;
+    writer.write_all(&length.to_be_bytes())?;
+    writer.write_all(payload)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads one frame, refusing it when its header announces more than `limit`
+/// bytes. Memory grows with the bytes that actually arrive, not the header.
+pub fn read_frame<R: Read>(reader: &mut R, limit: u32) -> AppResult<Vec<u8>> {
+    let mut length = [0u8; 4];
+    reader.read_exact(&mut length)?;
+    let length = u32::from_be_bytes(length);
+    if length > limit {
+        return Err(AppError::FileRead(format!(
+            "Sync message of {} bytes exceeds the limit",
+            length
+        )));
+    }
+
+    let mut payload = Vec::new();
+    reader.take(u64::from(length)).read_to_end(&mut payload)?;
+    if payload.len() != length as usize {
+        return Err(AppError::FileRead("Sync message was cut short".to_string()));
+    }
+    Ok(payload)
+}
+
+pub fn write_json<W: Write, T: Serialize>(writer: &mut W, message: &T) -> AppResult<()> {
+    let json = serde_json::to_vec(message)
+        .map_err(|e| AppError::FileWrite(format!("Failed to encode sync message: {}", e)))?;
+    write_frame(writer, &json)
+}
+
+/// Reads a plain JSON message. These are only exchanged before peers have
+/// proved their keys, so frames over [`MAX_HANDSHAKE_BYTES`] are refused.
+pub fn read_json<R: Read, T: DeserializeOwned>(reader: &mut R) -> AppResult<T> {
+    serde_json::from_slice(&read_frame(reader, MAX_HANDSHAKE_BYTES)?)
+        .map_err(|e| AppError::FileRead(format!("Invalid sync message: {}", e)))
+}
+
+/// Encrypts a message to the peer's age recipient and writes it as one frame.
+pub fn write_sealed<W: Write, T: Serialize>(
+    writer: &mut W,
+    recipient: &str,
+    message: &T,
+) -> AppResult<()> {
+    let recipient = age::x25519::Recipient::from_str(recipient)
+        .map_err(|e| AppError::ConfigLoad(format!("Invalid peer key: {}", e)))?;
+    let json = serde_json::to_vec(message)
+        .map_err(|e| AppError::FileWrite(format!("Failed to encode sync message: {}", e)))?;
+
+    let encryptor = age::Encryptor::with_recipients(std::iter::once(&recipient as _))
+        .map_err(|e| AppError::FileWrite(format!("Failed to encrypt sync message: {}", e)))?;
+    let mut sealed = Vec::new();
+    let mut stream = encryptor.wrap_output(&mut sealed)?;
+    stream.write_all(&json)?;
+    stream.finish()?;
+    write_frame(writer, &sealed)
+}
+
+pub fn read_sealed<R: Read, T: DeserializeOwned>(
+    reader: &mut R,
+    device: &DeviceIdentity,
+) -> AppResult<T> {
+    read_sealed_within(reader, device, MAX_FRAME_BYTES)
+}
+
+/// Like [`read_sealed`], refusing frames over `limit` bytes.
+pub fn read_sealed_within<R: Read, T: DeserializeOwned>(
+    reader: &mut R,
+    device: &DeviceIdentity,
+    limit: u32,
+) -> AppResult<T> {
+    let sealed = read_frame(reader, limit)?;
+    if sealed.is_empty() {
+        return Err(AppError::FilePermission(
+            "Peer does not recognise this device; pair the devices again".to_string(),
+        ));
+    }
+
+    let identity = device.identity()?;
+    let decryptor = age::Decryptor::new(&sealed[..])
+        .map_err(|e| AppError::FileRead(format!("Invalid sync message: {}", e)))?;
+    let mut plaintext = Vec::new();
+    decryptor
+        .decrypt(std::iter::once(&identity as &dyn age::Identity))
+        .map_err(|e| AppError::FilePermission(format!("Failed to decrypt sync message: {}", e)))?
+        .read_to_end(&mut plaintext)?;
+
+    serde_json::from_slice(&plaintext)
+        .map_err(|e| AppError::FileRead(format!("Invalid sync message: {}", e)))
+}
+
+fn random_hex(bytes: usize) -> String {
+    let mut buffer = vec![0u8; bytes];
+    rand::thread_rng().fill(&mut buffer[..]);
+    hex(&buffer)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn load_json<T: DeserializeOwned + Default>(path: &Path) -> AppResult<T> {
+    match fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| {
+            AppError::FileRead(format!("Failed to parse '{}': {}", path.display(), e))
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn save_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> AppResult<()> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| {
+        AppError::FileWrite(format!("Failed to serialize '{}': {}", path.display(), e))
+    })?;
+    write_app_data_file(path, json)
+}
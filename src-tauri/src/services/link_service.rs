@@ -0,0 +1,327 @@
+use crate::{
+    core::AppResult,
+    services::derived_index_service::{refresh_index, DerivedIndex},
+    utilities::links::{
+        extract_links, replace_wikilinks_with_text, resolve_link_target, strip_note_extension,
+        LinkKind, NoteLink,
+    },
+};
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Mutex,
+};
+
+/// Longest line of context kept for a link
+const MAX_CONTEXT_CHARS: usize = 200;
+
+/// Bumped whenever a note's links are re-indexed or dropped, which is also
+/// when notes appear or disappear, so cached sections know to rebuild
+static LINKS_GENERATION: AtomicU64 = AtomicU64::new(0);
+/// Database file and note name. The database is part of the key so switching
+/// vaults never serves another vault's sections.
+type MentionsKey = (String, String);
+/// Linked-mentions sections, all built at the stored generation
+static LINKED_MENTIONS_CACHE: Lazy<Mutex<(u64, HashMap<MentionsKey, String>)>> =
+    Lazy::new(|| Mutex::new((0, HashMap::new())));
+
+/// Internal links by source note. Links resolve against whichever notes exist
+/// when queried, so rows keep the raw target plus `target_key`, the lowercased
+/// file stem it points at, to narrow down candidates.
+pub const NOTE_LINKS_INDEX: DerivedIndex = DerivedIndex {
+    name: "note_links",
+    schema: "CREATE TABLE IF NOT EXISTS note_links (
+        source TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        target TEXT NOT NULL,
+        target_key TEXT NOT NULL,
+        line INTEGER NOT NULL,
+        context TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS note_links_source ON note_links(source);
+    CREATE INDEX IF NOT EXISTS note_links_target_key ON note_links(target_key);",
+    clear_note: clear_note_links,
+    index_note: index_note_links,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Backlink {
+    /// Note containing the link
+    pub source: String,
+    /// 1-based line of the link
+    pub line: usize,
+    /// The line the link is on, trimmed
+    pub context: String,
+}
+
+/// Notes linking to `note_name`, by source and line. Links a note makes to
+/// itself aren't included.
+pub fn backlinks(conn: &Connection, note_name: &str) -> AppResult<Vec<Backlink>> {
+    refresh_index(conn, &NOTE_LINKS_INDEX)?;
+
+    let key = target_key(note_name);
+    let mut stmt = conn.prepare(
+        "SELECT source, kind, target, line, context FROM note_links
+         WHERE target_key = ?1 AND source != ?2
+         ORDER BY source, line",
+    )?;
+    let candidates: Vec<(String, String, String, i64, String)> = stmt
+        .query_map(params![key, note_name], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        })?
+        .collect::<Result<_, _>>()?;
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Two notes may share a stem, so confirm each link resolves to this note
+    let notes: Vec<String> = conn
+        .prepare("SELECT filename FROM notes ORDER BY filename")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    let backlinks = candidates
+        .into_iter()
+        .filter(|(source, kind, target, line, _)| {
+            let link = NoteLink {
+                kind: parse_kind(kind),
+                target: target.clone(),
+                heading: None,
+                alias: None,
+                line: *line as usize,
+                column: 1,
+                length: 0,
+            };
+            resolve_link_target(&link, source, &notes).as_deref() == Some(note_name)
+        })
+        .map(|(source, _, _, line, context)| Backlink {
+            source,
+            line: line as usize,
+            context,
+        })
+        .collect();
+    Ok(backlinks)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BrokenLink {
+    pub source: String,
+    /// The link target as written
+    pub target: String,
+    pub line: usize,
+    pub context: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct VaultHealthReport {
+    pub notes_checked: usize,
+    /// Links whose target matches no note, by source and line
+    pub broken_links: Vec<BrokenLink>,
+    /// Notes that link to no other note and that no other note links to
+    pub orphans: Vec<String>,
+}
+
+/// Broken links and orphan notes across the vault, from the link index.
+pub fn vault_health_report(conn: &Connection) -> AppResult<VaultHealthReport> {
+    refresh_index(conn, &NOTE_LINKS_INDEX)?;
+
+    let notes: Vec<String> = conn
+        .prepare("SELECT filename FROM notes ORDER BY filename")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    // Links can only resolve to notes sharing their target's stem
+    let mut notes_by_key: HashMap<String, Vec<String>> = HashMap::new();
+    for note in &notes {
+        notes_by_key
+            .entry(target_key(note))
+            .or_default()
+            .push(note.clone());
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT source, kind, target, target_key, line, context FROM note_links
+         ORDER BY source, line",
+    )?;
+    let links = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, i64>(4)?,
+            row.get::<_, String>(5)?,
+        ))
+    })?;
+
+    let mut report = VaultHealthReport {
+        notes_checked: notes.len(),
+        ..VaultHealthReport::default()
+    };
+    let mut linked: HashSet<String> = HashSet::new();
+    for row in links {
+        let (source, kind, target, key, line, context) = row?;
+        let link = NoteLink {
+            kind: parse_kind(&kind),
+            target: target.clone(),
+            heading: None,
+            alias: None,
+            line: line as usize,
+            column: 1,
+            length: 0,
+        };
+        let candidates = notes_by_key
+            .get(&key)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        match resolve_link_target(&link, &source, candidates) {
+            Some(resolved) if resolved != source => {
+                linked.insert(resolved);
+                linked.insert(source);
+            }
+            Some(_) => {}
+            None => report.broken_links.push(BrokenLink {
+                source,
+                target,
+                line: line as usize,
+                context,
+            }),
+        }
+    }
+    report.orphans = notes
+        .into_iter()
+        .filter(|note| !linked.contains(note))
+        .collect();
+    Ok(report)
+}
+
+/// HTML appended to a rendered note listing the notes that link to it, each
+/// with the lines mentioning it; empty when nothing does. Sections are cached
+/// until the link index next changes.
+pub fn linked_mentions_html(conn: &Connection, note_name: &str) -> AppResult<String> {
+    refresh_index(conn, &NOTE_LINKS_INDEX)?;
+    let generation = LINKS_GENERATION.load(Ordering::SeqCst);
+    let key = (
+        conn.path().unwrap_or_default().to_string(),
+        note_name.to_string(),
+    );
+    {
+        let cache = LINKED_MENTIONS_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if cache.0 == generation {
+            if let Some(html) = cache.1.get(&key) {
+                return Ok(html.clone());
+            }
+        }
+    }
+
+    let html = render_linked_mentions(&backlinks(conn, note_name)?);
+    let mut cache = LINKED_MENTIONS_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if cache.0 != generation {
+        *cache = (generation, HashMap::new());
+    }
+    cache.1.insert(key, html.clone());
+    Ok(html)
+}
+
+/// The "Linked mentions" section for `backlinks`, grouped by source note.
+pub fn render_linked_mentions(backlinks: &[Backlink]) -> String {
+    if backlinks.is_empty() {
+        return String::new();
+    }
+    // (line, context) by source; a line linking twice is listed once
+    let mut by_source: BTreeMap<&str, Vec<(usize, &str)>> = BTreeMap::new();
+    for link in backlinks {
+        let lines = by_source.entry(&link.source).or_default();
+        if lines.last().map(|(line, _)| *line) != Some(link.line) {
+            lines.push((link.line, &link.context));
+        }
+    }
+
+    let mut html =
+        String::from("<section class=\"linked-mentions\">\n<hr>\n<h2>Linked mentions</h2>\n<ul>\n");
+    for (source, lines) in by_source {
+        html.push_str(&format!(
+            "<li><strong>{}</strong>\n<ul>\n",
+            html_escape::encode_text(strip_note_extension(source))
+        ));
+        for (_, context) in lines {
+            html.push_str(&format!(
+                "<li>{}</li>\n",
+                html_escape::encode_text(&replace_wikilinks_with_text(context))
+            ));
+        }
+        html.push_str("</ul>\n</li>\n");
+    }
+    html.push_str("</ul>\n</section>\n");
+    html
+}
+
+/// Lowercased file stem a link target or note name refers to.
+fn target_key(target: &str) -> String {
+    let file = target.rsplit('/').next().unwrap_or(target);
+    strip_note_extension(&file.to_lowercase())
+        .trim()
+        .to_string()
+}
+
+fn kind_name(kind: &LinkKind) -> &'static str {
+    match kind {
+        LinkKind::Wikilink => "wikilink",
+        LinkKind::Embed => "embed",
+        LinkKind::Markdown => "markdown",
+    }
+}
+
+fn parse_kind(kind: &str) -> LinkKind {
+    match kind {
+        "embed" => LinkKind::Embed,
+        "markdown" => LinkKind::Markdown,
+        _ => LinkKind::Wikilink,
+    }
+}
+
+fn clear_note_links(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    LINKS_GENERATION.fetch_add(1, Ordering::SeqCst);
+    conn.execute(
+        "DELETE FROM note_links WHERE source = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+fn index_note_links(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let mut insert = conn.prepare(
+        "INSERT INTO note_links (source, kind, target, target_key, line, context) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    let lines: Vec<&str> = content.lines().collect();
+    for link in extract_links(content) {
+        // Links to a heading of the same note
+        if link.target.is_empty() {
+            continue;
+        }
+        let context: String = lines
+            .get(link.line - 1)
+            .map(|line| line.trim().chars().take(MAX_CONTEXT_CHARS).collect())
+            .unwrap_or_default();
+        insert.execute(params![
+            filename,
+            kind_name(&link.kind),
+            link.target,
+            target_key(&link.target),
+            link.line as i64,
+            context
+        ])?;
+    }
+    Ok(())
+}
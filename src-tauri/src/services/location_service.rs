@@ -0,0 +1,306 @@
+use crate::{
+    config::GeoConfig,
+    core::{AppError, AppResult},
+    services::{
+        derived_index_service::{refresh_index, DerivedIndex},
+        publish_service::is_exportable,
+    },
+    utilities::{
+        frontmatter::note_title,
+        geo::{distance_km, extract_locations, normalize_place, Coordinates, NoteLocation},
+    },
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::time::Duration;
+
+/// Used when `[geo] geocoder_url` is empty
+pub const NOMINATIM_URL: &str = "https://nominatim.openstreetmap.org";
+/// Place names looked up per `geocode_note_locations` call
+pub const MAX_GEOCODE_BATCH: usize = 25;
+/// Nominatim's usage policy allows one request per second
+pub const GEOCODE_INTERVAL: Duration = Duration::from_secs(1);
+const GEOCODE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// `location:` front matter, one row per location. Place names carry the
+/// coordinates from `geocode_cache` when they have been looked up.
+pub const NOTE_LOCATIONS_INDEX: DerivedIndex = DerivedIndex {
+    name: "note_locations",
+    schema: "CREATE TABLE IF NOT EXISTS note_locations (
+        filename TEXT NOT NULL,
+        place TEXT,
+        place_key TEXT,
+        lat REAL,
+        lon REAL
+    );
+    CREATE INDEX IF NOT EXISTS note_locations_filename ON note_locations(filename);
+    CREATE INDEX IF NOT EXISTS note_locations_place_key ON note_locations(place_key);
+    CREATE TABLE IF NOT EXISTS geocode_cache (
+        place_key TEXT PRIMARY KEY,
+        lat REAL,
+        lon REAL
+    );",
+    clear_note: clear_note_locations,
+    index_note: index_note_locations,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NearbyNote {
+    pub filename: String,
+    /// Place name the coordinates came from, if any
+    pub place: Option<String>,
+    pub lat: f64,
+    pub lon: f64,
+    pub distance_km: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocatedNote {
+    pub filename: String,
+    pub title: String,
+    pub place: Option<String>,
+    pub coordinates: Coordinates,
+}
+
+fn clear_note_locations(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_locations WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+fn index_note_locations(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let mut insert = conn.prepare(
+        "INSERT INTO note_locations (filename, place, place_key, lat, lon) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    for location in extract_locations(content) {
+        match location {
+            NoteLocation::Coordinates(coordinates) => {
+                insert.execute(params![
+                    filename,
+                    None::<String>,
+                    None::<String>,
+                    coordinates.lat,
+                    coordinates.lon
+                ])?;
+            }
+            NoteLocation::Place(place) => {
+                let place_key = normalize_place(&place);
+                let cached: Option<(Option<f64>, Option<f64>)> = conn
+                    .query_row(
+                        "SELECT lat, lon FROM geocode_cache WHERE place_key = ?1",
+                        params![place_key],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .optional()?;
+                let (lat, lon) = cached.unwrap_or((None, None));
+                insert.execute(params![filename, place, place_key, lat, lon])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Notes with a location within `radius_km` of `center`, nearest first. A
+/// note with several locations is listed once, at its closest one.
+pub fn notes_near(
+    conn: &Connection,
+    center: Coordinates,
+    radius_km: f64,
+    limit: usize,
+) -> AppResult<Vec<NearbyNote>> {
+    refresh_index(conn, &NOTE_LOCATIONS_INDEX)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT filename, place, lat, lon FROM note_locations
+         WHERE lat IS NOT NULL AND lon IS NOT NULL",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, f64>(2)?,
+            row.get::<_, f64>(3)?,
+        ))
+    })?;
+
+    let mut nearby: Vec<NearbyNote> = Vec::new();
+    for row in rows {
+        let (filename, place, lat, lon) = row?;
+        let Some(coordinates) = Coordinates::new(lat, lon) else {
+            continue;
+        };
+        let distance = distance_km(center, coordinates);
+        if distance > radius_km {
+            continue;
+        }
+        match nearby.iter_mut().find(|note| note.filename == filename) {
+            Some(existing) if existing.distance_km <= distance => {}
+            Some(existing) => {
+                existing.place = place;
+                existing.lat = lat;
+                existing.lon = lon;
+                existing.distance_km = distance;
+            }
+            None => nearby.push(NearbyNote {
+                filename,
+                place,
+                lat,
+                lon,
+                distance_km: distance,
+            }),
+        }
+    }
+
+    nearby.sort_by(|a, b| {
+        a.distance_km
+            .total_cmp(&b.distance_km)
+            .then_with(|| a.filename.cmp(&b.filename))
+    });
+    nearby.truncate(limit);
+    Ok(nearby)
+}
+
+/// Every resolved location of an exportable note, in filename order.
+pub fn exportable_locations(conn: &Connection) -> AppResult<Vec<LocatedNote>> {
+    refresh_index(conn, &NOTE_LOCATIONS_INDEX)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT l.filename, l.place, l.lat, l.lon, n.content
+         FROM note_locations l JOIN notes n ON n.filename = l.filename
+         WHERE l.lat IS NOT NULL AND l.lon IS NOT NULL
+         ORDER BY l.filename, l.rowid",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, f64>(2)?,
+            row.get::<_, f64>(3)?,
+            row.get::<_, String>(4)?,
+        ))
+    })?;
+
+    let mut located = Vec::new();
+    for row in rows {
+        let (filename, place, lat, lon, content) = row?;
+        let Some(coordinates) = Coordinates::new(lat, lon) else {
+            continue;
+        };
+        if !is_exportable(&content) {
+            continue;
+        }
+        located.push(LocatedNote {
+            title: note_title(&filename, &content),
+            filename,
+            place,
+            coordinates,
+        });
+    }
+    Ok(located)
+}
+
+/// The locations as a GeoJSON FeatureCollection of points.
+pub fn locations_geojson(located: &[LocatedNote]) -> serde_json::Value {
+    let features: Vec<serde_json::Value> = located
+        .iter()
+        .map(|note| {
+            serde_json::json!({
+                "type": "Feature",
+                // GeoJSON orders positions longitude first
+                "geometry": {
+                    "type": "Point",
+                    "coordinates": [note.coordinates.lon, note.coordinates.lat],
+                },
+                "properties": {
+                    "filename": note.filename,
+                    "title": note.title,
+                    "place": note.place,
+                },
+            })
+        })
+        .collect();
+    serde_json::json!({ "type": "FeatureCollection", "features": features })
+}
+
+/// Place names in notes that have never been looked up.
+pub fn pending_places(conn: &Connection, limit: usize) -> AppResult<Vec<String>> {
+    refresh_index(conn, &NOTE_LOCATIONS_INDEX)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT MIN(place) FROM note_locations
+         WHERE place_key IS NOT NULL
+           AND place_key NOT IN (SELECT place_key FROM geocode_cache)
+         GROUP BY place_key ORDER BY place_key LIMIT ?1",
+    )?;
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    let rows = stmt.query_map(params![limit], |row| row.get(0))?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+/// Caches a lookup result, `None` meaning the geocoder found nothing, and
+/// applies it to every note naming the place.
+pub fn store_geocode(
+    conn: &Connection,
+    place: &str,
+    coordinates: Option<Coordinates>,
+) -> AppResult<()> {
+    conn.execute_batch(NOTE_LOCATIONS_INDEX.schema)?;
+    let place_key = normalize_place(place);
+    let (lat, lon) = match coordinates {
+        Some(c) => (Some(c.lat), Some(c.lon)),
+        None => (None, None),
+    };
+    conn.execute(
+        "INSERT OR REPLACE INTO geocode_cache (place_key, lat, lon) VALUES (?1, ?2, ?3)",
+        params![place_key, lat, lon],
+    )?;
+    conn.execute(
+        "UPDATE note_locations SET lat = ?2, lon = ?3 WHERE place_key = ?1",
+        params![place_key, lat, lon],
+    )?;
+    Ok(())
+}
+
+/// Looks `place` up with the configured geocoder; `None` when it is unknown.
+pub fn geocode_place(config: &GeoConfig, place: &str) -> AppResult<Option<Coordinates>> {
+    if config.geocoder != "nominatim" {
+        return Err(AppError::ConfigLoad(
+            "Geocoding is not set up: set [geo] geocoder = \"nominatim\"".to_string(),
+        ));
+    }
+    let base = if config.geocoder_url.is_empty() {
+        NOMINATIM_URL
+    } else {
+        &config.geocoder_url
+    };
+
+    let response = ureq::AgentBuilder::new()
+        .timeout(GEOCODE_TIMEOUT)
+        .user_agent(concat!("Symiosis/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .get(&format!("{}/search", base))
+        .query("q", place)
+        .query("format", "jsonv2")
+        .query("limit", "1")
+        .call()
+        .map_err(|e| AppError::FileRead(format!("Failed to geocode '{}': {}", place, e)))?;
+    let body = response
+        .into_string()
+        .map_err(|e| AppError::FileRead(format!("Failed to read geocoder response: {}", e)))?;
+    let results: serde_json::Value = serde_json::from_str(&body)
+        .map_err(|e| AppError::FileRead(format!("Invalid geocoder response: {}", e)))?;
+
+    Ok(parse_nominatim_result(&results))
+}
+
+/// The first hit of a Nominatim search response, whose coordinates are strings.
+pub fn parse_nominatim_result(results: &serde_json::Value) -> Option<Coordinates> {
+    let first = results.as_array()?.first()?;
+    let number = |key: &str| match first.get(key)? {
+        serde_json::Value::String(text) => text.parse::<f64>().ok(),
+        value => value.as_f64(),
+    };
+    Coordinates::new(number("lat")?, number("lon")?)
+}
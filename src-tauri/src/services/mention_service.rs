@@ -0,0 +1,115 @@
+use crate::{
+    core::AppResult,
+    services::derived_index_service::{refresh_index, DerivedIndex},
+    utilities::mentions::{extract_mentions, normalize_person, person_page_name, MentionKind},
+};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+const MAX_CONTEXT_CHARS: usize = 200;
+
+pub const MENTIONS_INDEX: DerivedIndex = DerivedIndex {
+    name: "mentions",
+    schema: "CREATE TABLE IF NOT EXISTS mentions (
+        filename TEXT NOT NULL,
+        person TEXT NOT NULL,
+        display TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        line INTEGER NOT NULL,
+        context TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS mentions_person ON mentions(person);
+    CREATE INDEX IF NOT EXISTS mentions_filename ON mentions(filename);",
+    clear_note: clear_note_mentions,
+    index_note: index_note_mentions,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MentionReference {
+    pub note_name: String,
+    /// The name as written in the note
+    pub display: String,
+    pub kind: MentionKind,
+    pub line: usize,
+    /// The mentioning line, trimmed
+    pub context: String,
+    pub modified: i64,
+}
+
+/// Every mention of `person` (a handle, a name or a `People/` note name), most
+/// recently modified notes first. The person's own page is left out.
+pub fn find_mentions(conn: &Connection, person: &str) -> AppResult<Vec<MentionReference>> {
+    refresh_index(conn, &MENTIONS_INDEX)?;
+
+    let key = normalize_person(person_page_name(person).unwrap_or(person));
+    if key.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT m.filename, m.display, m.kind, m.line, m.context, n.modified
+         FROM mentions m JOIN notes n ON n.filename = m.filename
+         WHERE m.person = ?1
+         ORDER BY n.modified DESC, m.filename, m.line",
+    )?;
+    let rows = stmt.query_map(params![key], |row| {
+        let kind: String = row.get(2)?;
+        Ok(MentionReference {
+            note_name: row.get(0)?,
+            display: row.get(1)?,
+            kind: if kind == "link" {
+                MentionKind::Link
+            } else {
+                MentionKind::Handle
+            },
+            line: row.get::<_, i64>(3)? as usize,
+            context: row.get(4)?,
+            modified: row.get(5)?,
+        })
+    })?;
+
+    let mut references = Vec::new();
+    for reference in rows {
+        let reference = reference?;
+        let own_page = person_page_name(&reference.note_name)
+            .is_some_and(|name| normalize_person(name) == key);
+        if !own_page {
+            references.push(reference);
+        }
+    }
+    Ok(references)
+}
+
+fn clear_note_mentions(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM mentions WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+fn index_note_mentions(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut stmt = conn.prepare(
+        "INSERT INTO mentions (filename, person, display, kind, line, context) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    for mention in extract_mentions(content) {
+        let context: String = lines
+            .get(mention.line.saturating_sub(1))
+            .map(|line| line.trim().chars().take(MAX_CONTEXT_CHARS).collect())
+            .unwrap_or_default();
+        let kind = match mention.kind {
+            MentionKind::Handle => "handle",
+            MentionKind::Link => "link",
+        };
+        stmt.execute(params![
+            filename,
+            mention.person,
+            mention.display,
+            kind,
+            mention.line as i64,
+            context
+        ])?;
+    }
+    Ok(())
+}
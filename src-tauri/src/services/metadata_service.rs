@@ -0,0 +1,251 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::derived_index_service::{refresh_index, DerivedIndex},
+    utilities::{
+        frontmatter::parse_frontmatter,
+        property_query::{compare_property_values, PropertyFilter, SortKey},
+    },
+};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+/// Every front-matter value, one row per note, field and list item. Fields are
+/// stored lowercased so `[metadata] fields` can change without a re-index.
+pub const NOTE_METADATA_INDEX: DerivedIndex = DerivedIndex {
+    name: "note_metadata",
+    schema: "CREATE TABLE IF NOT EXISTS note_metadata (
+        filename TEXT NOT NULL,
+        field TEXT NOT NULL,
+        value TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS note_metadata_field ON note_metadata(field, value);
+    CREATE INDEX IF NOT EXISTS note_metadata_filename ON note_metadata(filename);",
+    clear_note: clear_note_metadata,
+    index_note: index_note_metadata,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NoteMetadata {
+    pub note_name: String,
+    pub modified: i64,
+    /// Schema fields the note sets; list values are joined with ", "
+    pub fields: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetadataQuery<'a> {
+    /// Restricts and orders the result, e.g. to full-text search matches;
+    /// `None` means every note, most recently modified first
+    pub candidates: Option<&'a [String]>,
+    /// Field to value; a note matches when every field has that value
+    /// (case-insensitive)
+    pub filters: &'a HashMap<String, String>,
+    pub sort_by: Option<&'a str>,
+    pub descending: bool,
+}
+
+/// Notes with their schema fields, filtered and sorted by `query`. Filter and
+/// sort fields must be part of `schema`.
+pub fn query_notes_by_metadata(
+    conn: &Connection,
+    schema: &[String],
+    query: &MetadataQuery,
+) -> AppResult<Vec<NoteMetadata>> {
+    let filters: Vec<(String, String)> = query
+        .filters
+        .iter()
+        .map(|(field, value)| Ok((schema_field(schema, field)?, value.trim().to_lowercase())))
+        .collect::<AppResult<_>>()?;
+    let sort_by = query
+        .sort_by
+        .map(|field| schema_field(schema, field))
+        .transpose()?;
+
+    let mut values = note_values(conn)?;
+    for note_values in values.values_mut() {
+        note_values.retain(|field, _| schema.contains(field));
+    }
+
+    let modified = notes_by_recency(conn)?;
+    let notes: Vec<(String, i64)> = match query.candidates {
+        Some(candidates) => {
+            let by_name: HashMap<&str, i64> = modified
+                .iter()
+                .map(|(name, time)| (name.as_str(), *time))
+                .collect();
+            candidates
+                .iter()
+                .filter_map(|name| by_name.get(name.as_str()).map(|time| (name.clone(), *time)))
+                .collect()
+        }
+        None => modified,
+    };
+
+    let empty = BTreeMap::new();
+    let mut results: Vec<NoteMetadata> = notes
+        .into_iter()
+        .filter_map(|(note_name, modified)| {
+            let note_values = values.get(&note_name).unwrap_or(&empty);
+            let matches = filters.iter().all(|(field, wanted)| {
+                note_values
+                    .get(field)
+                    .is_some_and(|items| items.iter().any(|item| item.to_lowercase() == *wanted))
+            });
+            matches.then(|| {
+                let fields = note_values
+                    .iter()
+                    .map(|(field, items)| (field.clone(), items.join(", ")))
+                    .collect();
+                NoteMetadata {
+                    note_name,
+                    modified,
+                    fields,
+                }
+            })
+        })
+        .collect();
+
+    if let Some(field) = sort_by {
+        // Stable, so ties keep the candidate or recency order
+        results.sort_by(|a, b| match (a.fields.get(&field), b.fields.get(&field)) {
+            (Some(a), Some(b)) => {
+                let ordering = compare_property_values(a, b);
+                if query.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+    }
+
+    Ok(results)
+}
+
+fn schema_field(schema: &[String], field: &str) -> AppResult<String> {
+    let field = field.trim().to_lowercase();
+    if schema.contains(&field) {
+        Ok(field)
+    } else {
+        Err(AppError::validation_error(
+            "field",
+            &format!("'{}' is not listed in [metadata] fields", field),
+        ))
+    }
+}
+
+/// Notes matching `filter` (every note when `None`) with all their
+/// front-matter values, ordered by `sort` and then most recently modified
+/// first. Notes without a sort field come after those with it.
+pub fn query_notes_by_properties(
+    conn: &Connection,
+    filter: Option<&PropertyFilter>,
+    sort: &[SortKey],
+) -> AppResult<Vec<NoteMetadata>> {
+    let values = note_values(conn)?;
+    let empty = BTreeMap::new();
+    let mut results: Vec<(NoteMetadata, &BTreeMap<String, Vec<String>>)> = notes_by_recency(conn)?
+        .into_iter()
+        .filter_map(|(note_name, modified)| {
+            let note_values = values.get(&note_name).unwrap_or(&empty);
+            if filter.is_some_and(|filter| !filter.matches(note_values)) {
+                return None;
+            }
+            let fields = note_values
+                .iter()
+                .map(|(field, items)| (field.clone(), items.join(", ")))
+                .collect();
+            Some((
+                NoteMetadata {
+                    note_name,
+                    modified,
+                    fields,
+                },
+                note_values,
+            ))
+        })
+        .collect();
+
+    results.sort_by(|(_, a), (_, b)| {
+        sort.iter()
+            .map(|key| {
+                let first = |values: &BTreeMap<String, Vec<String>>| {
+                    values
+                        .get(&key.field)
+                        .and_then(|items| items.first().cloned())
+                };
+                match (first(a), first(b)) {
+                    (Some(a), Some(b)) if key.descending => compare_property_values(&b, &a),
+                    (Some(a), Some(b)) => compare_property_values(&a, &b),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+    Ok(results.into_iter().map(|(note, _)| note).collect())
+}
+
+/// Front-matter values of every note, by note, lowercased field and item.
+fn note_values(conn: &Connection) -> AppResult<HashMap<String, BTreeMap<String, Vec<String>>>> {
+    refresh_index(conn, &NOTE_METADATA_INDEX)?;
+
+    let mut values: HashMap<String, BTreeMap<String, Vec<String>>> = HashMap::new();
+    let mut stmt =
+        conn.prepare("SELECT filename, field, value FROM note_metadata ORDER BY rowid")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+    for row in rows {
+        let (filename, field, value) = row?;
+        values
+            .entry(filename)
+            .or_default()
+            .entry(field)
+            .or_default()
+            .push(value);
+    }
+    Ok(values)
+}
+
+fn notes_by_recency(conn: &Connection) -> AppResult<Vec<(String, i64)>> {
+    let mut stmt = conn.prepare("SELECT filename, modified FROM notes ORDER BY modified DESC")?;
+    let notes = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    Ok(notes)
+}
+
+fn clear_note_metadata(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_metadata WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+fn index_note_metadata(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let Some(frontmatter) = parse_frontmatter(content) else {
+        return Ok(());
+    };
+    let mut stmt =
+        conn.prepare("INSERT INTO note_metadata (filename, field, value) VALUES (?1, ?2, ?3)")?;
+    for (field, value) in &frontmatter.fields {
+        let field = field.to_lowercase();
+        for item in value.as_list() {
+            stmt.execute(params![filename, field, item])?;
+        }
+    }
+    Ok(())
+}
@@ -1,2 +1,54 @@
+pub mod batch_service;
+pub mod calendar_service;
+pub mod capture_service;
+pub mod clipper_service;
+pub mod crdt_service;
 pub mod database_service;
+pub mod derived_index_service;
+pub mod dictionary_service;
+pub mod email_service;
+pub mod expiry_service;
+pub mod export_template_service;
+pub mod external_change_service;
+pub mod folder_service;
+pub mod grep_service;
+pub mod habit_service;
+pub mod integrity_service;
+pub mod keyword_service;
+pub mod lan_sync_service;
+pub mod link_service;
+pub mod location_service;
+pub mod mention_service;
+pub mod metadata_service;
+pub mod note_created_service;
+pub mod note_details_service;
 pub mod note_service;
+pub mod outline_service;
+pub mod path_access_service;
+pub mod project_service;
+pub mod publish_service;
+pub mod query_console_service;
+pub mod quick_switch_service;
+pub mod reading_progress_service;
+pub mod reference_service;
+pub mod reminder_service;
+pub mod rename_history_service;
+pub mod render_service;
+pub mod replace_service;
+pub mod rollup_service;
+pub mod scratchpad_service;
+pub mod search_export_service;
+pub mod share_service;
+pub mod snippet_service;
+pub mod staleness_service;
+pub mod sync_warning_service;
+pub mod tag_rule_service;
+pub mod tag_service;
+pub mod template_service;
+pub mod time_tracking_service;
+pub mod trash_service;
+pub mod vault_archive_service;
+pub mod vault_check_service;
+pub mod vault_lint_service;
+pub mod vault_service;
+pub mod webhook_service;
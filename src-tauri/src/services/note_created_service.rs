@@ -0,0 +1,66 @@
+use crate::core::AppResult;
+use rusqlite::{params, Connection};
+use std::fs::Metadata;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// When each note was created, in Unix seconds. Kept outside the notes table so
+/// first-seen times survive index rebuilds.
+pub const NOTE_CREATED_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS note_created (
+    filename TEXT PRIMARY KEY,
+    created INTEGER NOT NULL
+);";
+
+/// Creation time of a note file: its birth time where the filesystem records
+/// one, otherwise `now` as the time it was first seen. A file modified before
+/// it was first seen existed at least since then, so the modification time
+/// wins when it's earlier.
+pub fn created_time(birth: Option<i64>, modified: Option<i64>, now: i64) -> i64 {
+    birth.unwrap_or_else(|| modified.map_or(now, |modified| modified.min(now)))
+}
+
+pub fn file_created_time(metadata: &Metadata, now: i64) -> i64 {
+    let seconds = |time: std::io::Result<SystemTime>| {
+        time.ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+    };
+    created_time(
+        seconds(metadata.created()),
+        seconds(metadata.modified()),
+        now,
+    )
+}
+
+/// Records when `filename` was created unless it's already known.
+pub fn record_created(conn: &Connection, filename: &str, created: i64) -> AppResult<()> {
+    conn.execute_batch(NOTE_CREATED_SCHEMA)?;
+    conn.execute(
+        "INSERT OR IGNORE INTO note_created (filename, created) VALUES (?1, ?2)",
+        params![filename, created],
+    )?;
+    Ok(())
+}
+
+/// Keeps a renamed note's creation time.
+pub fn move_created(conn: &Connection, old_name: &str, new_name: &str) -> AppResult<()> {
+    conn.execute_batch(NOTE_CREATED_SCHEMA)?;
+    conn.execute(
+        "DELETE FROM note_created WHERE filename = ?1",
+        params![new_name],
+    )?;
+    conn.execute(
+        "UPDATE note_created SET filename = ?1 WHERE filename = ?2",
+        params![new_name, old_name],
+    )?;
+    Ok(())
+}
+
+/// Forgets a deleted note, so a new note with its name starts afresh.
+pub fn forget_created(conn: &Connection, filename: &str) -> AppResult<()> {
+    conn.execute_batch(NOTE_CREATED_SCHEMA)?;
+    conn.execute(
+        "DELETE FROM note_created WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
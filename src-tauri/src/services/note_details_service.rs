@@ -0,0 +1,480 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::{
+        derived_index_service::{refresh_index, DerivedIndex},
+        note_created_service::NOTE_CREATED_SCHEMA,
+        reading_progress_service::{minutes_left, READING_PROGRESS_SCHEMA},
+        tag_service::NOTE_TAGS_INDEX,
+    },
+    utilities::{
+        frontmatter::{note_title, parse_frontmatter},
+        links::{count_external_links, extract_links},
+        note_lint::parse_atx_headings,
+        strings::{natural_cmp, parse_note_date, word_count},
+    },
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Per-note badges for list views, kept so listing never re-reads note content.
+pub const NOTE_DETAILS_INDEX: DerivedIndex = DerivedIndex {
+    name: "note_details",
+    schema: "CREATE TABLE IF NOT EXISTS note_details (
+        filename TEXT PRIMARY KEY,
+        title TEXT NOT NULL,
+        word_count INTEGER NOT NULL,
+        encrypted INTEGER NOT NULL,
+        locked INTEGER NOT NULL,
+        pinned INTEGER NOT NULL,
+        archived INTEGER NOT NULL
+    );",
+    clear_note: clear_note_details,
+    index_note: index_note_details,
+};
+
+/// Front-matter `icon` and `color`, which the sidebar shows next to a note.
+/// Kept apart from `note_details` so that table's schema stays unchanged.
+pub const NOTE_APPEARANCE_INDEX: DerivedIndex = DerivedIndex {
+    name: "note_appearance",
+    schema: "CREATE TABLE IF NOT EXISTS note_appearance (
+        filename TEXT PRIMARY KEY,
+        icon TEXT,
+        color TEXT
+    );",
+    clear_note: clear_note_appearance,
+    index_note: index_note_appearance,
+};
+
+/// Sizes and counts shown in a note's info panel.
+pub const NOTE_STATS_INDEX: DerivedIndex = DerivedIndex {
+    name: "note_stats",
+    schema: "CREATE TABLE IF NOT EXISTS note_stats (
+        filename TEXT PRIMARY KEY,
+        created INTEGER,
+        size INTEGER NOT NULL,
+        word_count INTEGER NOT NULL,
+        heading_count INTEGER NOT NULL,
+        internal_links INTEGER NOT NULL,
+        external_links INTEGER NOT NULL
+    );",
+    clear_note: clear_note_stats,
+    index_note: index_note_stats,
+};
+
+/// Longest accepted `icon`, in characters: an emoji or an icon name
+pub const MAX_ICON_CHARS: usize = 32;
+
+/// Folder whose notes count as archived even without an `archived` flag
+pub const ARCHIVE_FOLDER: &str = "Archive";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NoteDetails {
+    pub note_name: String,
+    pub title: String,
+    pub modified: i64,
+    /// `None` for notes the last sync hasn't seen yet
+    pub created: Option<i64>,
+    pub word_count: usize,
+    /// Front-matter `encrypted: true`
+    pub encrypted: bool,
+    /// Front-matter `locked: true`
+    pub locked: bool,
+    /// Front-matter `pinned: true`
+    pub pinned: bool,
+    /// Front-matter `archived: true` or stored under `Archive/`
+    pub archived: bool,
+    /// An unresolved LAN sync conflict exists for the note
+    pub has_conflicts: bool,
+    /// Front-matter `icon`
+    pub icon: Option<String>,
+    /// Front-matter `color`
+    pub color: Option<String>,
+    /// Fraction read so far, `None` for notes not started
+    pub reading_progress: Option<f64>,
+    /// Estimated minutes to read the rest of the note
+    pub reading_minutes_left: usize,
+}
+
+/// Orderings of the detailed note list: newest first by date, or A to Z by
+/// name with numbers in natural order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteOrder {
+    #[default]
+    Modified,
+    Created,
+    Natural,
+}
+
+/// Sort keys of the paged note list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteSortKey {
+    #[default]
+    Modified,
+    Created,
+    Name,
+    /// Name with numbers compared by value, so `note2` comes before `note10`
+    Natural,
+    Size,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NoteListing {
+    pub filename: String,
+    pub modified: i64,
+    /// `None` for notes the last sync hasn't seen yet
+    pub created: Option<i64>,
+    /// Size in bytes
+    pub size: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct NotePage {
+    pub notes: Vec<NoteListing>,
+    /// Number of notes across all pages
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LinkCounts {
+    /// Wikilinks, embeds and relative markdown links to other notes
+    pub internal: usize,
+    /// `http(s)` and `mailto` links
+    pub external: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NoteStats {
+    pub note_name: String,
+    /// Unix seconds from the `created` front-matter field, falling back to
+    /// when the file was created
+    pub created: Option<i64>,
+    pub modified: i64,
+    /// Size in bytes
+    pub size: usize,
+    pub word_count: usize,
+    pub heading_count: usize,
+    pub tags: Vec<String>,
+    pub links: LinkCounts,
+    /// Fraction read so far, `None` for notes not started
+    pub reading_progress: Option<f64>,
+    /// Estimated minutes to read the rest of the note
+    pub reading_minutes_left: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct NoteAppearance {
+    pub icon: Option<String>,
+    pub color: Option<String>,
+}
+
+/// A trimmed icon, `None` when blank. Rejects multi-line or overlong values.
+pub fn normalize_icon(icon: &str) -> AppResult<Option<String>> {
+    let icon = icon.trim();
+    if icon.is_empty() {
+        return Ok(None);
+    }
+    if icon.chars().any(char::is_control) || icon.chars().count() > MAX_ICON_CHARS {
+        return Err(AppError::validation_error(
+            "icon",
+            &format!(
+                "Icon must be a single line of at most {} characters",
+                MAX_ICON_CHARS
+            ),
+        ));
+    }
+    Ok(Some(icon.to_string()))
+}
+
+/// A lowercased `#rgb` / `#rrggbb` hex color or CSS color name such as
+/// `teal`, `None` when blank.
+pub fn normalize_color(color: &str) -> AppResult<Option<String>> {
+    let color = color.trim().to_lowercase();
+    if color.is_empty() {
+        return Ok(None);
+    }
+    let valid = match color.strip_prefix('#') {
+        Some(hex) => [3, 6].contains(&hex.len()) && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        None => color.len() <= 20 && color.chars().all(|c| c.is_ascii_lowercase()),
+    };
+    if !valid {
+        return Err(AppError::validation_error(
+            "color",
+            &format!(
+                "'{}' is not a color (expected #rgb, #rrggbb or a color name)",
+                color
+            ),
+        ));
+    }
+    Ok(Some(color))
+}
+
+/// The note's `icon` and `color` front matter; invalid values are ignored.
+pub fn note_appearance(content: &str) -> NoteAppearance {
+    let frontmatter = parse_frontmatter(content).unwrap_or_default();
+    let field = |key: &str, normalize: fn(&str) -> AppResult<Option<String>>| {
+        frontmatter
+            .get_text(key)
+            .and_then(|value| normalize(value).ok().flatten())
+    };
+    NoteAppearance {
+        icon: field("icon", normalize_icon),
+        color: field("color", normalize_color),
+    }
+}
+
+/// Every note with its badges, most recently modified first.
+pub fn list_note_details(
+    conn: &Connection,
+    conflicted: &HashSet<String>,
+    order: NoteOrder,
+) -> AppResult<Vec<NoteDetails>> {
+    refresh_index(conn, &NOTE_DETAILS_INDEX)?;
+    refresh_index(conn, &NOTE_APPEARANCE_INDEX)?;
+    conn.execute_batch(NOTE_CREATED_SCHEMA)?;
+    conn.execute_batch(READING_PROGRESS_SCHEMA)?;
+
+    let order_by = match order {
+        NoteOrder::Modified => "n.modified DESC",
+        // Notes without a creation time yet go last
+        NoteOrder::Created => "c.created IS NULL, c.created DESC, n.modified DESC",
+        // Sorted below, SQLite has no natural collation
+        NoteOrder::Natural => "n.filename",
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT n.filename, n.modified, d.title, d.word_count, d.encrypted, d.locked, d.pinned, d.archived,
+                a.icon, a.color, c.created, r.fraction
+         FROM notes n JOIN note_details d ON d.filename = n.filename
+         LEFT JOIN note_appearance a ON a.filename = n.filename
+         LEFT JOIN note_created c ON c.filename = n.filename
+         LEFT JOIN reading_progress r ON r.filename = n.filename
+         ORDER BY {}",
+        order_by
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        let note_name: String = row.get(0)?;
+        let word_count = row.get::<_, i64>(3)? as usize;
+        let reading_progress = row.get(11)?;
+        Ok(NoteDetails {
+            has_conflicts: conflicted.contains(&note_name),
+            note_name,
+            modified: row.get(1)?,
+            title: row.get(2)?,
+            word_count,
+            encrypted: row.get(4)?,
+            locked: row.get(5)?,
+            pinned: row.get(6)?,
+            archived: row.get(7)?,
+            icon: row.get(8)?,
+            color: row.get(9)?,
+            created: row.get(10)?,
+            reading_progress,
+            reading_minutes_left: minutes_left(word_count, reading_progress),
+        })
+    })?;
+    let mut details: Vec<NoteDetails> = rows.collect::<Result<_, _>>()?;
+    if order == NoteOrder::Natural {
+        details.sort_by(|a, b| natural_cmp(&a.note_name, &b.note_name));
+    }
+    Ok(details)
+}
+
+/// One page of the note list, sorted by `sort_by`. Ties, and notes without a
+/// creation time when sorting by it, go last in name order.
+pub fn list_notes_page(
+    conn: &Connection,
+    sort_by: NoteSortKey,
+    direction: SortDirection,
+    offset: usize,
+    limit: usize,
+) -> AppResult<NotePage> {
+    conn.execute_batch(NOTE_CREATED_SCHEMA)?;
+
+    let sql_direction = match direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+    let select = "SELECT n.filename, n.modified, c.created, length(CAST(n.content AS BLOB)) AS size
+         FROM notes n LEFT JOIN note_created c ON c.filename = n.filename";
+    let to_listing = |row: &rusqlite::Row| {
+        Ok(NoteListing {
+            filename: row.get(0)?,
+            modified: row.get(1)?,
+            created: row.get(2)?,
+            size: row.get::<_, i64>(3)? as usize,
+        })
+    };
+
+    let total: i64 = conn.query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0))?;
+    let order_by = match sort_by {
+        NoteSortKey::Modified => format!("n.modified {}", sql_direction),
+        NoteSortKey::Created => format!("c.created IS NULL, c.created {}", sql_direction),
+        NoteSortKey::Name => format!("n.filename COLLATE NOCASE {}", sql_direction),
+        NoteSortKey::Size => format!("size {}", sql_direction),
+        NoteSortKey::Natural => {
+            // SQLite has no natural collation, so the whole list is sorted here
+            let mut stmt = conn.prepare(select)?;
+            let mut notes: Vec<NoteListing> =
+                stmt.query_map([], to_listing)?.collect::<Result<_, _>>()?;
+            notes.sort_by(|a, b| natural_cmp(&a.filename, &b.filename));
+            if direction == SortDirection::Desc {
+                notes.reverse();
+            }
+            return Ok(NotePage {
+                notes: notes.into_iter().skip(offset).take(limit).collect(),
+                total: total as usize,
+            });
+        }
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        "{} ORDER BY {}, n.filename LIMIT ?1 OFFSET ?2",
+        select, order_by
+    ))?;
+    let notes = stmt
+        .query_map(params![limit as i64, offset as i64], to_listing)?
+        .collect::<Result<_, _>>()?;
+
+    Ok(NotePage {
+        notes,
+        total: total as usize,
+    })
+}
+
+/// Sizes, counts and tags of one note.
+pub fn note_stats(conn: &Connection, note_name: &str) -> AppResult<NoteStats> {
+    refresh_index(conn, &NOTE_STATS_INDEX)?;
+    refresh_index(conn, &NOTE_TAGS_INDEX)?;
+    conn.execute_batch(NOTE_CREATED_SCHEMA)?;
+    conn.execute_batch(READING_PROGRESS_SCHEMA)?;
+
+    let stats = conn
+        .query_row(
+            "SELECT n.modified, COALESCE(s.created, c.created), s.size, s.word_count, s.heading_count,
+                    s.internal_links, s.external_links, r.fraction
+             FROM notes n JOIN note_stats s ON s.filename = n.filename
+             LEFT JOIN note_created c ON c.filename = n.filename
+             LEFT JOIN reading_progress r ON r.filename = n.filename
+             WHERE n.filename = ?1",
+            params![note_name],
+            |row| {
+                let word_count = row.get::<_, i64>(3)? as usize;
+                let reading_progress = row.get(7)?;
+                Ok(NoteStats {
+                    note_name: note_name.to_string(),
+                    modified: row.get(0)?,
+                    created: row.get(1)?,
+                    size: row.get::<_, i64>(2)? as usize,
+                    word_count,
+                    heading_count: row.get::<_, i64>(4)? as usize,
+                    tags: Vec::new(),
+                    links: LinkCounts {
+                        internal: row.get::<_, i64>(5)? as usize,
+                        external: row.get::<_, i64>(6)? as usize,
+                    },
+                    reading_progress,
+                    reading_minutes_left: minutes_left(word_count, reading_progress),
+                })
+            },
+        )
+        .optional()?;
+    let Some(mut stats) = stats else {
+        return Err(AppError::FileNotFound(format!(
+            "Note not found: {}",
+            note_name
+        )));
+    };
+
+    let mut stmt = conn.prepare("SELECT tag FROM note_tags WHERE filename = ?1 ORDER BY rowid")?;
+    stats.tags = stmt
+        .query_map(params![note_name], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    Ok(stats)
+}
+
+fn clear_note_details(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_details WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+fn index_note_details(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let frontmatter = parse_frontmatter(content).unwrap_or_default();
+    let in_archive = filename
+        .split_once('/')
+        .is_some_and(|(folder, _)| folder.eq_ignore_ascii_case(ARCHIVE_FOLDER));
+    conn.execute(
+        "INSERT INTO note_details (filename, title, word_count, encrypted, locked, pinned, archived)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            filename,
+            note_title(filename, content),
+            word_count(content) as i64,
+            frontmatter.get_flag("encrypted"),
+            frontmatter.get_flag("locked"),
+            frontmatter.get_flag("pinned"),
+            frontmatter.get_flag("archived") || in_archive
+        ],
+    )?;
+    Ok(())
+}
+
+fn clear_note_appearance(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_appearance WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+fn index_note_appearance(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let appearance = note_appearance(content);
+    if appearance == NoteAppearance::default() {
+        return Ok(());
+    }
+    conn.execute(
+        "INSERT INTO note_appearance (filename, icon, color) VALUES (?1, ?2, ?3)",
+        params![filename, appearance.icon, appearance.color],
+    )?;
+    Ok(())
+}
+
+fn clear_note_stats(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_stats WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+fn index_note_stats(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let created = parse_frontmatter(content)
+        .and_then(|fm| fm.get_text("created").and_then(parse_note_date))
+        .map(|date| date.timestamp());
+    conn.execute(
+        "INSERT INTO note_stats (filename, created, size, word_count, heading_count, internal_links, external_links)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            filename,
+            created,
+            content.len() as i64,
+            word_count(content) as i64,
+            parse_atx_headings(content).len() as i64,
+            extract_links(content).len() as i64,
+            count_external_links(content) as i64
+        ],
+    )?;
+    Ok(())
+}
@@ -2,9 +2,11 @@ use crate::{
     core::{AppError, AppResult},
     database::with_db,
     logging::log,
+    services::{derived_index_service::invalidate_note, note_created_service::record_created},
     utilities::note_renderer::render_note,
 };
-use rusqlite::params;
+use rusqlite::{params, Connection};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn update_note_in_database(
     app_state: &crate::core::state::AppState,
@@ -13,60 +15,94 @@ pub fn update_note_in_database(
     modified: i64,
 ) -> AppResult<()> {
     with_db(app_state, |conn| {
-        let html_render = render_note(note_name, content);
+        update_note_row(conn, note_name, content, modified)
+    })
+}
 
-        // First try to update existing note
-        let updated_rows = conn
-            .execute(
-                "UPDATE notes SET content = ?2, html_render = ?3, modified = ?4, is_indexed = ?5 WHERE filename = ?1",
-                params![note_name, content, html_render, modified, true],
-            )?;
+/// Updates or inserts a note's row on `conn`, which may be a transaction
+/// covering several notes.
+pub fn update_note_row(
+    conn: &Connection,
+    note_name: &str,
+    content: &str,
+    modified: i64,
+) -> AppResult<()> {
+    let html_render = render_note(note_name, content);
 
-        // If no rows were updated, insert new note
-        if updated_rows == 0 {
-            conn.execute(
-                "INSERT OR REPLACE INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![note_name, content, html_render, modified, true],
-            )?;
-        }
+    // First try to update existing note
+    let updated_rows = conn
+        .execute(
+            "UPDATE notes SET content = ?2, html_render = ?3, modified = ?4, is_indexed = ?5 WHERE filename = ?1",
+            params![note_name, content, html_render, modified, true],
+        )?;
 
-        // Verify database was updated correctly
-        let db_content = conn
-            .query_row(
-                "SELECT content FROM notes WHERE filename = ?1",
-                params![note_name],
-                |row| row.get::<_, String>(0),
-            )
-            .map_err(|e| {
-                AppError::DatabaseQuery(format!("Failed to verify database update: {}", e))
-            })?;
+    // If no rows were updated, insert new note
+    if updated_rows == 0 {
+        conn.execute(
+            "INSERT OR REPLACE INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![note_name, content, html_render, modified, true],
+        )?;
+    }
+    record_created(conn, note_name, modified)?;
+    invalidate_note(conn, note_name)?;
 
-        if db_content != content {
-            let error_msg = format!(
-                "Database update verification failed for '{}': expected {} bytes, found {} bytes",
-                note_name,
-                content.len(),
-                db_content.len()
-            );
-            log(
-                "DATABASE_VERIFICATION",
-                "Database update verification failed",
-                Some(&error_msg),
-            );
-            return Err(AppError::DatabaseQuery(error_msg));
-        }
+    // Verify database was updated correctly
+    let db_content = conn
+        .query_row(
+            "SELECT content FROM notes WHERE filename = ?1",
+            params![note_name],
+            |row| row.get::<_, String>(0),
+        )
+        .map_err(|e| AppError::DatabaseQuery(format!("Failed to verify database update: {}", e)))?;
 
-        // Log successful database operation
+    if db_content != content {
+        let error_msg = format!(
+            "Database update verification failed for '{}': expected {} bytes, found {} bytes",
+            note_name,
+            content.len(),
+            db_content.len()
+        );
         log(
-            "DATABASE_OPERATION",
-            &format!(
-                "UPDATE/INSERT: {} | Size: {} bytes | SUCCESS",
-                note_name,
-                content.len()
-            ),
-            None,
+            "DATABASE_VERIFICATION",
+            "Database update verification failed",
+            Some(&error_msg),
         );
+        return Err(AppError::DatabaseQuery(error_msg));
+    }
 
-        Ok(())
-    })
+    // Log successful database operation
+    log(
+        "DATABASE_OPERATION",
+        &format!(
+            "UPDATE/INSERT: {} | Size: {} bytes | SUCCESS",
+            note_name,
+            content.len()
+        ),
+        None,
+    );
+
+    Ok(())
+}
+
+/// Writes a note produced by the app itself (sync, clipping) to disk and indexes it,
+/// without the watcher treating the write as an external change.
+pub fn write_note_and_index(
+    app_state: &crate::core::state::AppState,
+    note_name: &str,
+    content: &str,
+) -> AppResult<()> {
+    crate::utilities::validation::validate_note_name(note_name)?;
+    let note_path = crate::config::get_config_notes_dir().join(note_name);
+    if let Some(parent) = note_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    crate::commands::notes::with_programmatic_flag(app_state, || {
+        crate::utilities::file_safety::safe_write_note(&note_path, content)
+    })?;
+
+    let modified = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    update_note_in_database(app_state, note_name, content, modified)
 }
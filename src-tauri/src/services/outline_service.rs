@@ -0,0 +1,105 @@
+use crate::{
+    core::AppResult,
+    services::{folder_service::is_in_folder, publish_service::is_exportable},
+    utilities::{
+        frontmatter::{note_title, parse_frontmatter},
+        note_renderer::note_outline,
+    },
+};
+use rusqlite::Connection;
+use std::cmp::Ordering;
+
+/// Heading levels below each note included when no depth is given
+pub const DEFAULT_OUTLINE_DEPTH: usize = 2;
+/// Deepest outline, keeping every entry a markdown heading (`#` to `######`)
+pub const MAX_OUTLINE_DEPTH: usize = 4;
+/// Front-matter field placing a note in the outline; lower numbers come first
+const ORDER_FIELD: &str = "order";
+
+/// A markdown report skeleton for the notes in `folder` and its subfolders:
+/// each note is a numbered section (`## 2. Methods`) and its headings, up to
+/// `depth` levels, numbered beneath it (`### 2.1. Sampling`). Private notes
+/// are left out.
+pub fn folder_outline(conn: &Connection, folder: &str, depth: usize) -> AppResult<String> {
+    let notes: Vec<(String, String)> = conn
+        .prepare("SELECT filename, content FROM notes")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    let notes = notes
+        .into_iter()
+        .filter(|(filename, content)| is_in_folder(filename, folder) && is_exportable(content))
+        .collect();
+    Ok(outline_document(folder, notes, depth))
+}
+
+/// Orders `notes` by their `order` front-matter field, notes without one
+/// last, then by name, and writes the numbered outline. A note's section is
+/// titled by its `title` field, else a leading `# Heading`, else its name.
+pub fn outline_document(folder: &str, mut notes: Vec<(String, String)>, depth: usize) -> String {
+    let depth = depth.min(MAX_OUTLINE_DEPTH);
+    notes.sort_by(|(a_name, a_content), (b_name, b_content)| {
+        match (note_order(a_content), note_order(b_content)) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+        .then_with(|| a_name.to_lowercase().cmp(&b_name.to_lowercase()))
+    });
+
+    let mut document = format!("# {}\n", folder);
+    for (index, (note_name, content)) in notes.iter().enumerate() {
+        let number = index + 1;
+        let mut headings = note_outline(note_name, content);
+        // A leading level-1 heading is the note's title, not one of its sections
+        let leading_title = match headings.first() {
+            Some(first) if first.level == 1 => Some(headings.remove(0).text),
+            _ => None,
+        };
+        let has_title_field = parse_frontmatter(content)
+            .and_then(|fm| fm.get_text("title").map(|title| !title.trim().is_empty()))
+            .unwrap_or(false);
+        let title = match leading_title {
+            Some(heading) if !has_title_field => heading,
+            _ => note_title(note_name, content),
+        };
+        document.push_str(&format!("\n## {}. {}\n", number, title));
+
+        // (heading level, number) of the current heading and its parents
+        let mut open: Vec<(usize, usize)> = Vec::new();
+        for heading in headings {
+            let mut sibling = 0;
+            while open
+                .last()
+                .is_some_and(|(level, _)| *level >= heading.level)
+            {
+                sibling = open.pop().map_or(0, |(_, n)| n);
+            }
+            open.push((heading.level, sibling + 1));
+            if open.len() > depth {
+                continue;
+            }
+
+            let numbers: Vec<String> = std::iter::once(number)
+                .chain(open.iter().map(|(_, n)| *n))
+                .map(|n| n.to_string())
+                .collect();
+            document.push_str(&format!(
+                "{} {}. {}\n",
+                "#".repeat(open.len() + 2),
+                numbers.join("."),
+                heading.text
+            ));
+        }
+    }
+    document
+}
+
+fn note_order(content: &str) -> Option<f64> {
+    parse_frontmatter(content)?
+        .get_text(ORDER_FIELD)?
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|order| order.is_finite())
+}
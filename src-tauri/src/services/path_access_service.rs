@@ -0,0 +1,138 @@
+use crate::{
+    config::get_config_notes_dir,
+    core::{AppError, AppResult},
+    utilities::{
+        file_safety::write_app_data_file,
+        paths::{get_config_path, get_data_dir},
+    },
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+static GRANTS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Directories commands may always read: the notes folder, the app's data folder
+/// and the folder holding `config.toml`.
+pub fn default_read_roots() -> Vec<PathBuf> {
+    let mut roots = vec![get_config_notes_dir()];
+    if let Some(data_dir) = get_data_dir() {
+        roots.push(data_dir.join("symiosis"));
+    }
+    if let Some(config_dir) = get_config_path().parent() {
+        roots.push(config_dir.to_path_buf());
+    }
+    roots
+        .into_iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .collect()
+}
+
+/// Whether an already canonical path lies under one of `roots` or is, or lies
+/// under, one of `grants`.
+pub fn is_path_authorized(canonical: &Path, roots: &[PathBuf], grants: &[PathBuf]) -> bool {
+    roots
+        .iter()
+        .chain(grants)
+        .any(|allowed| canonical.starts_with(allowed))
+}
+
+/// Grants persisted by the user, as canonical paths. A missing file means none.
+pub fn load_path_grants(grants_path: &Path) -> AppResult<Vec<String>> {
+    let content = match fs::read_to_string(grants_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(AppError::FileRead(format!(
+                "Failed to read path grants '{}': {}",
+                grants_path.display(),
+                e
+            )))
+        }
+    };
+
+    serde_json::from_str(&content)
+        .map_err(|e| AppError::FileRead(format!("Invalid path grants file: {}", e)))
+}
+
+fn save_path_grants(grants_path: &Path, grants: &[String]) -> AppResult<()> {
+    let content = serde_json::to_string_pretty(grants)
+        .map_err(|e| AppError::FileWrite(format!("Failed to serialize path grants: {}", e)))?;
+    write_app_data_file(grants_path, &content)
+}
+
+/// Records a user grant for `target` (a file or a folder) and returns the
+/// updated list.
+pub fn add_path_grant(grants_path: &Path, target: &str) -> AppResult<Vec<String>> {
+    let canonical = Path::new(target.trim())
+        .canonicalize()
+        .map_err(|_| AppError::FileNotFound(target.to_string()))?
+        .to_string_lossy()
+        .to_string();
+
+    let _guard = GRANTS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut grants = load_path_grants(grants_path)?;
+    if !grants.contains(&canonical) {
+        grants.push(canonical);
+        grants.sort();
+        save_path_grants(grants_path, &grants)?;
+    }
+    Ok(grants)
+}
+
+/// Removes a grant, matching either the stored canonical path or the path as
+/// given. Returns whether anything was removed.
+pub fn remove_path_grant(grants_path: &Path, target: &str) -> AppResult<bool> {
+    let target = target.trim();
+    let canonical = Path::new(target)
+        .canonicalize()
+        .map(|path| path.to_string_lossy().to_string())
+        .ok();
+
+    let _guard = GRANTS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut grants = load_path_grants(grants_path)?;
+    let before = grants.len();
+    grants.retain(|grant| grant != target && Some(grant) != canonical.as_ref());
+    if grants.len() == before {
+        return Ok(false);
+    }
+    save_path_grants(grants_path, &grants)?;
+    Ok(true)
+}
+
+/// Resolves `path` for reading, refusing anything outside `roots` and the
+/// persisted grants. Symlinks and `..` are resolved before checking, and a
+/// missing path outside them is refused rather than reported missing, so
+/// callers can't probe for files they may not read.
+pub fn authorize_read(path: &str, roots: &[PathBuf], grants_path: &Path) -> AppResult<PathBuf> {
+    let grants: Vec<PathBuf> = load_path_grants(grants_path)?
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    let denied = || {
+        AppError::FilePermission(format!(
+            "Reading '{}' requires access to be granted first",
+            path
+        ))
+    };
+
+    let requested = Path::new(path.trim());
+    match requested.canonicalize() {
+        Ok(canonical) if is_path_authorized(&canonical, roots, &grants) => Ok(canonical),
+        Ok(_) => Err(denied()),
+        Err(_) => {
+            let parent_authorized = requested
+                .ancestors()
+                .skip(1)
+                .find_map(|ancestor| ancestor.canonicalize().ok())
+                .is_some_and(|ancestor| is_path_authorized(&ancestor, roots, &grants));
+            if parent_authorized {
+                Err(AppError::FileNotFound(path.to_string()))
+            } else {
+                Err(denied())
+            }
+        }
+    }
+}
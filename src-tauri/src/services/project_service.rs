@@ -0,0 +1,201 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::publish_service::SourceNote,
+    utilities::{
+        frontmatter::{note_title, parse_frontmatter},
+        links::{extract_links, lines_outside_code_blocks, strip_note_extension, LinkKind},
+        strings::parse_note_date,
+        tasks::extract_tasks,
+    },
+};
+use chrono::{Local, NaiveDate};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+
+const RECENT_NOTE_LIMIT: usize = 10;
+const UPCOMING_LIMIT: usize = 20;
+const KEY_LINK_LIMIT: usize = 10;
+/// Front-matter fields treated as dates worth showing on a dashboard
+const DATE_FIELDS: [&str; 4] = ["due", "deadline", "date", "event"];
+
+static URL_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r#"https?://[^\s<>()\[\]"'`]+"#));
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectTask {
+    pub note_name: String,
+    pub text: String,
+    pub due: Option<String>,
+    pub owner: Option<String>,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectNote {
+    pub note_name: String,
+    pub title: String,
+    pub modified: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpcomingDate {
+    pub date: String,
+    pub label: String,
+    pub note_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeyLink {
+    /// Note name (without extension) or URL
+    pub target: String,
+    pub external: bool,
+    /// Number of times the folder's notes link to it
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectOverview {
+    pub folder: String,
+    pub note_count: usize,
+    /// Open tasks, those with due dates first
+    pub open_tasks: Vec<ProjectTask>,
+    pub recent_notes: Vec<ProjectNote>,
+    /// Task due dates and front-matter dates from `today` on, soonest first
+    pub upcoming: Vec<UpcomingDate>,
+    pub key_links: Vec<KeyLink>,
+}
+
+/// Normalizes a folder argument to `name/` form, rejecting empty or escaping paths.
+pub fn normalize_project_folder(folder: &str) -> AppResult<String> {
+    let folder = folder.trim().trim_matches('/');
+    if folder.is_empty() {
+        return Err(AppError::validation_error(
+            "folder",
+            "Folder cannot be empty",
+        ));
+    }
+    if folder
+        .split('/')
+        .any(|part| part.is_empty() || part == "." || part == "..")
+    {
+        return Err(AppError::PathTraversal);
+    }
+    Ok(format!("{}/", folder))
+}
+
+/// Aggregates a project folder (including subfolders) into one dashboard payload.
+pub fn project_overview(folder: &str, notes: &[SourceNote], today: NaiveDate) -> ProjectOverview {
+    let mut folder_notes: Vec<&SourceNote> = notes
+        .iter()
+        .filter(|note| note.filename.starts_with(folder))
+        .collect();
+    folder_notes.sort_by(|a, b| {
+        b.modified
+            .cmp(&a.modified)
+            .then_with(|| a.filename.cmp(&b.filename))
+    });
+
+    let mut open_tasks = Vec::new();
+    let mut upcoming = Vec::new();
+    let mut link_counts: HashMap<(String, bool), usize> = HashMap::new();
+
+    for note in &folder_notes {
+        for task in extract_tasks(&note.content) {
+            if task.done {
+                continue;
+            }
+            if let Some(due) = task
+                .due
+                .as_deref()
+                .and_then(|due| NaiveDate::parse_from_str(due, "%Y-%m-%d").ok())
+                .filter(|due| *due >= today)
+            {
+                upcoming.push(UpcomingDate {
+                    date: due.to_string(),
+                    label: task.text.clone(),
+                    note_name: note.filename.clone(),
+                });
+            }
+            open_tasks.push(ProjectTask {
+                note_name: note.filename.clone(),
+                text: task.text,
+                due: task.due,
+                owner: task.owner,
+                line: task.line,
+            });
+        }
+
+        if let Some(frontmatter) = parse_frontmatter(&note.content) {
+            for field in DATE_FIELDS {
+                let Some(date) = frontmatter
+                    .get_text(field)
+                    .and_then(parse_note_date)
+                    .map(|date| date.with_timezone(&Local).date_naive())
+                    .filter(|date| *date >= today)
+                else {
+                    continue;
+                };
+                upcoming.push(UpcomingDate {
+                    date: date.to_string(),
+                    label: format!("{} ({})", note_title(&note.filename, &note.content), field),
+                    note_name: note.filename.clone(),
+                });
+            }
+        }
+
+        for link in extract_links(&note.content) {
+            if link.kind != LinkKind::Embed {
+                let target = strip_note_extension(&link.target).to_string();
+                *link_counts.entry((target, false)).or_default() += 1;
+            }
+        }
+        if let Ok(regex) = URL_REGEX.as_ref() {
+            for (_, line) in lines_outside_code_blocks(&note.content) {
+                for url in regex.find_iter(line) {
+                    let url = url.as_str().trim_end_matches(['.', ',', ';', ':']);
+                    *link_counts.entry((url.to_string(), true)).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    // Dated tasks first by due date; undated keep note recency order
+    open_tasks.sort_by(|a, b| match (&a.due, &b.due) {
+        (Some(a_due), Some(b_due)) => a_due.cmp(b_due),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    upcoming.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.label.cmp(&b.label)));
+    upcoming.truncate(UPCOMING_LIMIT);
+
+    let mut key_links: Vec<KeyLink> = link_counts
+        .into_iter()
+        .map(|((target, external), count)| KeyLink {
+            target,
+            external,
+            count,
+        })
+        .collect();
+    key_links.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.target.cmp(&b.target)));
+    key_links.truncate(KEY_LINK_LIMIT);
+
+    ProjectOverview {
+        folder: folder.trim_end_matches('/').to_string(),
+        note_count: folder_notes.len(),
+        open_tasks,
+        recent_notes: folder_notes
+            .iter()
+            .take(RECENT_NOTE_LIMIT)
+            .map(|note| ProjectNote {
+                note_name: note.filename.clone(),
+                title: note_title(&note.filename, &note.content),
+                modified: note.modified,
+            })
+            .collect(),
+        upcoming,
+        key_links,
+    }
+}
@@ -0,0 +1,598 @@
+use crate::{
+    core::{AppError, AppResult},
+    logging::log,
+    utilities::{
+        frontmatter::{note_title, parse_frontmatter, strip_frontmatter},
+        links::{extract_links, resolve_link_target, strip_note_extension, LinkKind},
+        note_renderer::render_note,
+        strings::{note_excerpt, parse_note_date},
+        tags::extract_tags,
+    },
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Marker written into every generated site so republishing never wipes a
+/// directory Symiosis did not create.
+pub const SITE_MARKER_FILE: &str = ".symiosis-site";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PublishOptions {
+    /// Folders (relative to the notes directory) to publish; empty publishes every note
+    pub folders: Vec<String>,
+    pub site_title: String,
+    pub include_tag_pages: bool,
+    /// Only publish notes that opt in with `publish: true`
+    pub require_publish_flag: bool,
+    /// Write an Atom `feed.xml` with the most recent notes
+    pub generate_feed: bool,
+    /// Public URL of the site, used for absolute links in the feed
+    pub base_url: String,
+    pub feed_limit: usize,
+}
+
+impl Default for PublishOptions {
+    fn default() -> Self {
+        Self {
+            folders: Vec::new(),
+            site_title: "Notes".to_string(),
+            include_tag_pages: true,
+            require_publish_flag: false,
+            generate_feed: true,
+            base_url: String::new(),
+            feed_limit: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PublishFlag {
+    /// `publish: true`
+    Public,
+    /// No publish-related front-matter
+    Unset,
+    /// `publish: false` or `visibility: private`
+    Private,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishableNote {
+    pub filename: String,
+    pub title: String,
+    pub flag: PublishFlag,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishReport {
+    pub output_dir: String,
+    pub notes_published: usize,
+    pub tag_pages: usize,
+    pub feed_entries: usize,
+}
+
+/// A note as read from the database, the input to every publishing step.
+#[derive(Debug, Clone)]
+pub struct SourceNote {
+    pub filename: String,
+    pub content: String,
+    /// Unix seconds of the last file modification
+    pub modified: i64,
+}
+
+struct PublishedNote<'a> {
+    filename: &'a str,
+    content: &'a str,
+    slug: String,
+    title: String,
+    tags: Vec<String>,
+    date: DateTime<Utc>,
+}
+
+pub fn is_in_selected_folders(filename: &str, folders: &[String]) -> bool {
+    if folders.is_empty() {
+        return true;
+    }
+    folders.iter().any(|folder| {
+        let folder = folder.trim().trim_matches('/');
+        folder.is_empty()
+            || filename == folder
+            || filename
+                .strip_prefix(folder)
+                .map(|rest| rest.starts_with('/'))
+                .unwrap_or(false)
+    })
+}
+
+pub fn publish_flag(content: &str) -> PublishFlag {
+    let Some(frontmatter) = parse_frontmatter(content) else {
+        return PublishFlag::Unset;
+    };
+
+    let visibility = frontmatter.get_text("visibility").map(str::to_lowercase);
+    if matches!(visibility.as_deref(), Some("private")) {
+        return PublishFlag::Private;
+    }
+
+    match frontmatter
+        .get_text("publish")
+        .map(|value| value.trim().to_lowercase())
+        .as_deref()
+    {
+        Some("true" | "yes") => PublishFlag::Public,
+        Some("false" | "no") => PublishFlag::Private,
+        _ if matches!(visibility.as_deref(), Some("public")) => PublishFlag::Public,
+        _ => PublishFlag::Unset,
+    }
+}
+
+/// Whether a note may leave the machine through exports. Only an explicit
+/// private flag blocks it.
+pub fn is_exportable(content: &str) -> bool {
+    publish_flag(content) != PublishFlag::Private
+}
+
+pub fn is_publishable(filename: &str, content: &str, options: &PublishOptions) -> bool {
+    if !is_in_selected_folders(filename, &options.folders) {
+        return false;
+    }
+    match publish_flag(content) {
+        PublishFlag::Public => true,
+        PublishFlag::Unset => !options.require_publish_flag,
+        PublishFlag::Private => false,
+    }
+}
+
+/// Lists exactly the notes `publish_notes` would include for these options.
+pub fn list_publishable(notes: &[SourceNote], options: &PublishOptions) -> Vec<PublishableNote> {
+    let mut publishable: Vec<PublishableNote> = notes
+        .iter()
+        .filter(|note| is_publishable(&note.filename, &note.content, options))
+        .map(|note| PublishableNote {
+            filename: note.filename.clone(),
+            title: note_title(&note.filename, &note.content),
+            flag: publish_flag(&note.content),
+        })
+        .collect();
+    publishable.sort_by(|a, b| a.filename.cmp(&b.filename));
+    publishable
+}
+
+pub fn slugify(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-').to_string();
+    if slug.is_empty() {
+        "note".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Publication date from the `date`/`published`/`created` front-matter fields,
+/// falling back to the file's modification time.
+pub fn note_publish_date(note: &SourceNote) -> DateTime<Utc> {
+    parse_frontmatter(&note.content)
+        .and_then(|fm| {
+            ["date", "published", "created"]
+                .iter()
+                .find_map(|key| fm.get_text(key).and_then(parse_note_date))
+        })
+        .or_else(|| DateTime::from_timestamp(note.modified, 0))
+        .unwrap_or_default()
+}
+
+/// Renders the given notes into a static site under `output_dir`.
+pub fn publish_notes(
+    notes: &[SourceNote],
+    output_dir: &Path,
+    options: &PublishOptions,
+) -> AppResult<PublishReport> {
+    prepare_site_directory(output_dir)?;
+
+    let mut used_slugs: HashMap<String, usize> = HashMap::new();
+    let mut published: Vec<PublishedNote> = notes
+        .iter()
+        .filter(|note| is_publishable(&note.filename, &note.content, options))
+        .map(|note| {
+            let base = slugify(strip_note_extension(&note.filename));
+            let count = used_slugs.entry(base.clone()).or_insert(0);
+            *count += 1;
+            let slug = if *count == 1 {
+                base
+            } else {
+                format!("{}-{}", base, count)
+            };
+            PublishedNote {
+                filename: &note.filename,
+                content: &note.content,
+                slug,
+                title: note_title(&note.filename, &note.content),
+                tags: extract_tags(&note.content),
+                date: note_publish_date(note),
+            }
+        })
+        .collect();
+    published.sort_by_key(|note| note.title.to_lowercase());
+
+    let published_names: Vec<String> = published.iter().map(|n| n.filename.to_string()).collect();
+    let slugs: HashMap<&str, &str> = published
+        .iter()
+        .map(|note| (note.filename, note.slug.as_str()))
+        .collect();
+
+    let notes_dir = output_dir.join("notes");
+    fs::create_dir_all(&notes_dir)?;
+    for note in &published {
+        let body = rewrite_note_links(
+            strip_frontmatter(note.content),
+            note.filename,
+            &published_names,
+            |target| slugs.get(target).map(|slug| format!("{}.html", slug)),
+        );
+        let article = render_note(note.filename, &body);
+        let title_heading = if body.trim_start().starts_with("# ") {
+            String::new()
+        } else {
+            format!("<h1 class=\"note-title\">{}</h1>\n", escape(&note.title))
+        };
+
+        let tag_links: Vec<String> = note
+            .tags
+            .iter()
+            .map(|tag| {
+                if options.include_tag_pages {
+                    format!(
+                        r#"<a class="tag" href="../tags/{}.html">#{}</a>"#,
+                        slugify(tag),
+                        escape(tag)
+                    )
+                } else {
+                    format!(r#"<span class="tag">#{}</span>"#, escape(tag))
+                }
+            })
+            .collect();
+        let tags_html = if tag_links.is_empty() {
+            String::new()
+        } else {
+            format!("<p class=\"tags\">{}</p>\n", tag_links.join(" "))
+        };
+
+        let page = page_html(
+            &note.title,
+            &options.site_title,
+            "../",
+            &format!(
+                "<article>\n{}{}{}\n</article>",
+                title_heading, tags_html, article
+            ),
+        );
+        fs::write(notes_dir.join(format!("{}.html", note.slug)), page)?;
+    }
+
+    let mut tag_pages = 0;
+    if options.include_tag_pages {
+        let mut by_tag: BTreeMap<&str, Vec<&PublishedNote>> = BTreeMap::new();
+        for note in &published {
+            for tag in &note.tags {
+                by_tag.entry(tag.as_str()).or_default().push(note);
+            }
+        }
+
+        let tags_dir = output_dir.join("tags");
+        fs::create_dir_all(&tags_dir)?;
+        for (tag, tagged) in &by_tag {
+            let items: Vec<String> = tagged
+                .iter()
+                .map(|note| note_list_item(note, "../notes/"))
+                .collect();
+            let page = page_html(
+                &format!("#{}", tag),
+                &options.site_title,
+                "../",
+                &format!(
+                    "<h1>#{}</h1>\n<ul class=\"note-list\">\n{}\n</ul>",
+                    escape(tag),
+                    items.join("\n")
+                ),
+            );
+            fs::write(tags_dir.join(format!("{}.html", slugify(tag))), page)?;
+        }
+        tag_pages = by_tag.len();
+    }
+
+    fs::write(
+        output_dir.join("index.html"),
+        index_html(&published, options),
+    )?;
+    fs::write(output_dir.join("style.css"), SITE_CSS)?;
+
+    let feed_path = output_dir.join("feed.xml");
+    let feed_entries = if options.generate_feed {
+        let (feed, entries) = feed_xml(&published, options);
+        fs::write(&feed_path, feed)?;
+        entries
+    } else {
+        if feed_path.exists() {
+            fs::remove_file(&feed_path)?;
+        }
+        0
+    };
+
+    log(
+        "PUBLISH",
+        &format!(
+            "Published {} notes, {} tag pages and {} feed entries to {}",
+            published.len(),
+            tag_pages,
+            feed_entries,
+            output_dir.display()
+        ),
+        None,
+    );
+
+    Ok(PublishReport {
+        output_dir: output_dir.to_string_lossy().to_string(),
+        notes_published: published.len(),
+        tag_pages,
+        feed_entries,
+    })
+}
+
+/// Creates the output directory, or clears pages from a previous publish.
+/// Refuses to touch non-empty directories that were not generated by Symiosis.
+fn prepare_site_directory(output_dir: &Path) -> AppResult<()> {
+    if output_dir.exists() {
+        let is_empty = fs::read_dir(output_dir)?.next().is_none();
+        if !is_empty && !output_dir.join(SITE_MARKER_FILE).exists() {
+            return Err(AppError::InvalidPath(format!(
+                "Output directory '{}' is not empty and was not created by Symiosis",
+                output_dir.display()
+            )));
+        }
+        for generated in ["notes", "tags"] {
+            let path = output_dir.join(generated);
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            }
+        }
+    } else {
+        fs::create_dir_all(output_dir)?;
+    }
+
+    fs::write(
+        output_dir.join(SITE_MARKER_FILE),
+        "Generated by Symiosis. Files in notes/ and tags/ are replaced on every publish.\n",
+    )?;
+    Ok(())
+}
+
+/// Rewrites internal links so that published targets point at their pages and
+/// links to unpublished notes degrade to plain text instead of dangling.
+pub fn rewrite_note_links<F>(
+    content: &str,
+    source: &str,
+    published: &[String],
+    page_for: F,
+) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut links = extract_links(content);
+    if links.is_empty() {
+        return content.to_string();
+    }
+    // A line's wikilinks come before its markdown links, whatever their columns
+    links.sort_by_key(|link| (link.line, link.column));
+
+    let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+    // Replace right-to-left so earlier columns stay valid
+    for link in links.iter().rev() {
+        let Some(line) = lines.get_mut(link.line - 1) else {
+            continue;
+        };
+
+        if link.kind == LinkKind::Markdown {
+            let target_is_note = strip_note_extension(&link.target) != link.target
+                || !link.target.rsplit('/').next().unwrap_or("").contains('.');
+            if !target_is_note {
+                continue;
+            }
+        }
+
+        let target_page =
+            resolve_link_target(link, source, published).and_then(|target| page_for(&target));
+        let label = link
+            .alias
+            .clone()
+            .or_else(|| {
+                if link.target.is_empty() {
+                    link.heading.clone()
+                } else {
+                    Some(link.target.clone())
+                }
+            })
+            .unwrap_or_default();
+        let replacement = match target_page {
+            Some(page) => format!("[{}]({})", label, page),
+            None => label,
+        };
+
+        let chars: Vec<char> = line.chars().collect();
+        let start = link.column - 1;
+        let end = (start + link.length).min(chars.len());
+        let mut rewritten: String = chars[..start].iter().collect();
+        rewritten.push_str(&replacement);
+        rewritten.extend(chars[end..].iter());
+        *line = rewritten;
+    }
+
+    lines.join("\n")
+}
+
+const FEED_SUMMARY_LENGTH: usize = 280;
+
+/// Builds an Atom feed of the most recently dated notes. Returns the XML and entry count.
+fn feed_xml(published: &[PublishedNote], options: &PublishOptions) -> (String, usize) {
+    let base_url = options.base_url.trim().trim_end_matches('/');
+    let absolute = |path: &str| {
+        if base_url.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}/{}", base_url, path)
+        }
+    };
+    let feed_id = if base_url.is_empty() {
+        format!("urn:symiosis:{}", slugify(&options.site_title))
+    } else {
+        format!("{}/", base_url)
+    };
+
+    let mut recent: Vec<&PublishedNote> = published.iter().collect();
+    recent.sort_by_key(|note| std::cmp::Reverse(note.date));
+    recent.truncate(options.feed_limit);
+
+    let updated = recent
+        .first()
+        .map(|note| note.date)
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339();
+
+    let mut xml = format!(
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\">\n",
+            "<title>{}</title>\n<id>{}</id>\n<updated>{}</updated>\n",
+            "<link rel=\"alternate\" href=\"{}\"/>\n<link rel=\"self\" href=\"{}\"/>\n",
+            "<generator>Symiosis</generator>\n"
+        ),
+        xml_escape(&options.site_title),
+        xml_escape(&feed_id),
+        updated,
+        xml_escape(&absolute("index.html")),
+        xml_escape(&absolute("feed.xml")),
+    );
+
+    for note in &recent {
+        let link = absolute(&format!("notes/{}.html", note.slug));
+        let id = if base_url.is_empty() {
+            format!("urn:symiosis:note:{}", note.slug)
+        } else {
+            link.clone()
+        };
+        let date = note.date.to_rfc3339();
+        xml.push_str(&format!(
+            "<entry>\n<title>{}</title>\n<id>{}</id>\n<link rel=\"alternate\" href=\"{}\"/>\n<published>{}</published>\n<updated>{}</updated>\n<summary>{}</summary>\n",
+            xml_escape(&note.title),
+            xml_escape(&id),
+            xml_escape(&link),
+            date,
+            date,
+            xml_escape(&note_excerpt(note.content, FEED_SUMMARY_LENGTH))
+        ));
+        for tag in &note.tags {
+            xml.push_str(&format!("<category term=\"{}\"/>\n", xml_escape(tag)));
+        }
+        xml.push_str("</entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    (xml, recent.len())
+}
+
+fn xml_escape(text: &str) -> String {
+    html_escape::encode_double_quoted_attribute(text).to_string()
+}
+
+fn note_list_item(note: &PublishedNote, prefix: &str) -> String {
+    format!(
+        r#"<li><a href="{}{}.html">{}</a></li>"#,
+        prefix,
+        note.slug,
+        escape(&note.title)
+    )
+}
+
+fn index_html(published: &[PublishedNote], options: &PublishOptions) -> String {
+    let mut by_folder: BTreeMap<&str, Vec<&PublishedNote>> = BTreeMap::new();
+    for note in published {
+        let folder = note
+            .filename
+            .rsplit_once('/')
+            .map(|(dir, _)| dir)
+            .unwrap_or("");
+        by_folder.entry(folder).or_default().push(note);
+    }
+
+    let mut body = format!("<h1>{}</h1>\n", escape(&options.site_title));
+    for (folder, notes) in by_folder {
+        if !folder.is_empty() {
+            body.push_str(&format!("<h2>{}</h2>\n", escape(folder)));
+        }
+        let items: Vec<String> = notes
+            .iter()
+            .map(|note| note_list_item(note, "notes/"))
+            .collect();
+        body.push_str(&format!(
+            "<ul class=\"note-list\">\n{}\n</ul>\n",
+            items.join("\n")
+        ));
+    }
+
+    page_html(&options.site_title, &options.site_title, "", &body)
+}
+
+fn page_html(title: &str, site_title: &str, root: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<link rel="stylesheet" href="{root}style.css">
+</head>
+<body>
+<header><a href="{root}index.html">{site_title}</a></header>
+<main>
+{body}
+</main>
+</body>
+</html>
+"#,
+        title = escape(title),
+        root = root,
+        site_title = escape(site_title),
+        body = body
+    )
+}
+
+fn escape(text: &str) -> String {
+    html_escape::encode_text(text).to_string()
+}
+
+const SITE_CSS: &str = r#"body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; line-height: 1.6; color: #222; background: #fdfdfd; margin: 0; }
+header { padding: 1rem 2rem; border-bottom: 1px solid #e5e5e5; }
+header a { color: inherit; font-weight: 600; text-decoration: none; }
+main { max-width: 46rem; margin: 0 auto; padding: 1.5rem 2rem 4rem; }
+a { color: #0b63c5; }
+pre { background: #f4f4f4; padding: 1rem; overflow-x: auto; }
+code { font-family: "JetBrains Mono", Consolas, monospace; font-size: 0.9em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ddd; padding: 0.3rem 0.6rem; }
+blockquote { margin-left: 0; padding-left: 1rem; border-left: 3px solid #ddd; color: #555; }
+img { max-width: 100%; }
+.tags { margin-top: -0.5rem; }
+.tag { margin-right: 0.5rem; font-size: 0.9em; color: #666; }
+.note-list { padding-left: 1.2rem; }
+"#;
@@ -0,0 +1,118 @@
+use crate::core::{AppError, AppResult};
+use rusqlite::{types::ValueRef, Connection, OpenFlags};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+
+pub const DEFAULT_QUERY_LIMIT: usize = 100;
+pub const MAX_QUERY_LIMIT: usize = 1000;
+/// Queries still running after this are interrupted
+pub const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// More rows matched than the limit allowed
+    pub truncated: bool,
+}
+
+/// Opens the notes index without write access, so even a statement that slips
+/// past [`validate_readonly_sql`] cannot modify it.
+pub fn open_readonly(db_path: &Path) -> AppResult<Connection> {
+    let conn = Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| {
+        AppError::DatabaseConnection(format!("Failed to open read-only database: {}", e))
+    })?;
+    conn.pragma_update(None, "query_only", true)?;
+    Ok(conn)
+}
+
+/// Accepts a single `SELECT` or `WITH ... SELECT` statement, with an optional
+/// trailing semicolon, and returns it without the semicolon.
+pub fn validate_readonly_sql(sql: &str) -> AppResult<&str> {
+    let sql = sql.trim();
+    let sql = sql.strip_suffix(';').unwrap_or(sql).trim_end();
+    if sql.is_empty() {
+        return Err(AppError::validation_error("sql", "Query cannot be empty"));
+    }
+    if sql.contains(';') {
+        return Err(AppError::validation_error(
+            "sql",
+            "Only a single statement is allowed",
+        ));
+    }
+
+    let keyword = sql
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default()
+        .to_uppercase();
+    if keyword != "SELECT" && keyword != "WITH" {
+        return Err(AppError::validation_error(
+            "sql",
+            "Only SELECT queries are allowed",
+        ));
+    }
+    Ok(sql)
+}
+
+/// Runs `sql` after validation, returning at most `limit` rows (capped at
+/// [`MAX_QUERY_LIMIT`]). Runaway queries are interrupted after [`QUERY_TIMEOUT`].
+pub fn run_readonly_query(conn: &Connection, sql: &str, limit: usize) -> AppResult<QueryResult> {
+    let sql = validate_readonly_sql(sql)?;
+    let limit = limit.clamp(1, MAX_QUERY_LIMIT);
+
+    let mut stmt = conn.prepare(sql)?;
+    if !stmt.readonly() {
+        return Err(AppError::validation_error(
+            "sql",
+            "Only read-only statements are allowed",
+        ));
+    }
+    let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+
+    // The watchdog interrupts the query unless it finishes first and drops `done`
+    let (done, finished) = mpsc::channel::<()>();
+    let interrupt = conn.get_interrupt_handle();
+    std::thread::spawn(move || {
+        if let Err(mpsc::RecvTimeoutError::Timeout) = finished.recv_timeout(QUERY_TIMEOUT) {
+            interrupt.interrupt();
+        }
+    });
+
+    let mut rows = Vec::new();
+    let mut truncated = false;
+    let mut results = stmt.query([])?;
+    while let Some(row) = results.next()? {
+        if rows.len() == limit {
+            truncated = true;
+            break;
+        }
+        let values = (0..columns.len())
+            .map(|index| row.get_ref(index).map(json_value))
+            .collect::<Result<_, _>>()?;
+        rows.push(values);
+    }
+    drop(done);
+
+    Ok(QueryResult {
+        columns,
+        rows,
+        truncated,
+    })
+}
+
+fn json_value(value: ValueRef<'_>) -> serde_json::Value {
+    match value {
+        ValueRef::Null => serde_json::Value::Null,
+        ValueRef::Integer(i) => i.into(),
+        ValueRef::Real(f) => f.into(),
+        ValueRef::Text(text) => String::from_utf8_lossy(text).into(),
+        ValueRef::Blob(blob) => format!("<blob: {} bytes>", blob.len()).into(),
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{
+    core::AppResult,
+    services::derived_index_service::{refresh_index, DerivedIndex},
+    utilities::{
+        frontmatter::{note_title, parse_frontmatter},
+        links::strip_note_extension,
+        note_lint::parse_atx_headings,
+    },
+};
+use nucleo_matcher::{
+    pattern::{CaseMatching, Normalization, Pattern},
+    Config, Matcher, Utf32Str,
+};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::HashMap;
+
+pub const DEFAULT_QUICK_SWITCH_LIMIT: usize = 50;
+
+/// Names, aliases and headings only, so matching never touches note bodies.
+pub const SWITCH_TARGETS_INDEX: DerivedIndex = DerivedIndex {
+    name: "switch_targets",
+    schema: "CREATE TABLE IF NOT EXISTS switch_targets (
+        filename TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        text TEXT NOT NULL,
+        text_lower TEXT NOT NULL,
+        line INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS switch_targets_filename ON switch_targets(filename);",
+    clear_note: clear_note_targets,
+    index_note: index_note_targets,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwitchMatchKind {
+    Filename,
+    /// Front-matter `title` or `aliases` entry
+    Alias,
+    Heading,
+}
+
+impl SwitchMatchKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            SwitchMatchKind::Filename => "filename",
+            SwitchMatchKind::Alias => "alias",
+            SwitchMatchKind::Heading => "heading",
+        }
+    }
+
+    fn from_str(kind: &str) -> Self {
+        match kind {
+            "alias" => SwitchMatchKind::Alias,
+            "heading" => SwitchMatchKind::Heading,
+            _ => SwitchMatchKind::Filename,
+        }
+    }
+
+    fn boost(self) -> u32 {
+        match self {
+            SwitchMatchKind::Filename => 200,
+            SwitchMatchKind::Alias => 150,
+            SwitchMatchKind::Heading => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuickSwitchResult {
+    pub note_name: String,
+    pub kind: SwitchMatchKind,
+    /// The filename, alias or heading that matched
+    pub matched: String,
+    /// 1-based heading line; `None` for filename and alias matches
+    pub line: Option<usize>,
+    pub score: u32,
+}
+
+/// Ranks notes for the open-note palette. Each note appears once for its best
+/// filename or alias match, and matching headings are listed as jump targets.
+/// An empty query returns the most recently modified notes.
+pub fn quick_switch(
+    conn: &Connection,
+    query: &str,
+    limit: usize,
+) -> AppResult<Vec<QuickSwitchResult>> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        let mut stmt =
+            conn.prepare("SELECT filename FROM notes ORDER BY modified DESC LIMIT ?1")?;
+        let rows = stmt.query_map(params![limit as i64], |row| row.get::<_, String>(0))?;
+        let mut results = Vec::new();
+        for filename in rows {
+            let filename = filename?;
+            results.push(QuickSwitchResult {
+                matched: strip_note_extension(&filename).to_string(),
+                note_name: filename,
+                kind: SwitchMatchKind::Filename,
+                line: None,
+                score: 0,
+            });
+        }
+        return Ok(results);
+    }
+
+    refresh_index(conn, &SWITCH_TARGETS_INDEX)?;
+
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let mut needle_buf = Vec::new();
+    let needle = Utf32Str::new(&query, &mut needle_buf);
+    let mut haystack_buf = Vec::new();
+
+    let mut stmt = conn.prepare(
+        "SELECT t.filename, t.kind, t.text, t.text_lower, t.line, n.modified
+         FROM switch_targets t JOIN notes n ON n.filename = t.filename",
+    )?;
+    let mut rows = stmt.query([])?;
+
+    // Best name/alias match per note; headings are kept individually
+    let mut best_names: HashMap<String, (QuickSwitchResult, i64)> = HashMap::new();
+    let mut headings: Vec<(QuickSwitchResult, i64)> = Vec::new();
+    while let Some(row) = rows.next()? {
+        let text_lower: String = row.get(3)?;
+        // Filenames also count as exact/prefix matches on their last path segment
+        let name_lower = text_lower.rsplit('/').next().unwrap_or(&text_lower);
+        let base = if text_lower == query || name_lower == query {
+            1000
+        } else if text_lower.starts_with(&query) || name_lower.starts_with(&query) {
+            500
+        } else {
+            0
+        };
+        let Some(fuzzy) =
+            matcher.fuzzy_match(Utf32Str::new(&text_lower, &mut haystack_buf), needle)
+        else {
+            continue;
+        };
+
+        let kind = SwitchMatchKind::from_str(&row.get::<_, String>(1)?);
+        let line: i64 = row.get(4)?;
+        let result = QuickSwitchResult {
+            note_name: row.get(0)?,
+            kind,
+            matched: row.get(2)?,
+            line: (kind == SwitchMatchKind::Heading).then_some(line as usize),
+            score: base + u32::from(fuzzy) + kind.boost(),
+        };
+        let modified: i64 = row.get(5)?;
+
+        if kind == SwitchMatchKind::Heading {
+            headings.push((result, modified));
+        } else {
+            match best_names.get(&result.note_name) {
+                Some((existing, _)) if existing.score >= result.score => {}
+                _ => {
+                    best_names.insert(result.note_name.clone(), (result, modified));
+                }
+            }
+        }
+    }
+
+    let mut results: Vec<(QuickSwitchResult, i64)> =
+        best_names.into_values().chain(headings).collect();
+    results.sort_by(|(a, a_modified), (b, b_modified)| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| b_modified.cmp(a_modified))
+            .then_with(|| a.note_name.cmp(&b.note_name))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    results.truncate(limit);
+    Ok(results.into_iter().map(|(result, _)| result).collect())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FilenameMatch {
+    pub filename: String,
+    pub score: u32,
+    /// Sorted character positions in `filename` that matched, for highlighting
+    pub indices: Vec<u32>,
+}
+
+/// fzf-style fuzzy match over note paths: space-separated terms must all
+/// match, with `^prefix`, `suffix$`, `'exact` and `!negation` supported, and
+/// smart case (case-sensitive once the query has an uppercase letter). Path
+/// segment starts score higher. Ties go to shorter, then more recent, paths.
+/// An empty query returns the most recently modified notes.
+pub fn fuzzy_match_filenames(
+    conn: &Connection,
+    query: &str,
+    limit: usize,
+) -> AppResult<Vec<FilenameMatch>> {
+    let limit_param = i64::try_from(limit).unwrap_or(i64::MAX);
+    if query.trim().is_empty() {
+        let mut stmt =
+            conn.prepare("SELECT filename FROM notes ORDER BY modified DESC LIMIT ?1")?;
+        let rows = stmt.query_map(params![limit_param], |row| row.get::<_, String>(0))?;
+        return Ok(rows
+            .map(|filename| {
+                filename.map(|filename| FilenameMatch {
+                    filename,
+                    score: 0,
+                    indices: Vec::new(),
+                })
+            })
+            .collect::<Result<_, _>>()?);
+    }
+
+    let pattern = Pattern::parse(query, CaseMatching::Smart, Normalization::Smart);
+    let mut matcher = Matcher::new(Config::DEFAULT.match_paths());
+    let mut haystack_buf = Vec::new();
+
+    let mut stmt = conn.prepare("SELECT filename, modified FROM notes")?;
+    let mut rows = stmt.query([])?;
+    let mut matches: Vec<(FilenameMatch, i64)> = Vec::new();
+    while let Some(row) = rows.next()? {
+        let filename: String = row.get(0)?;
+        let mut indices = Vec::new();
+        let Some(score) = pattern.indices(
+            Utf32Str::new(&filename, &mut haystack_buf),
+            &mut matcher,
+            &mut indices,
+        ) else {
+            continue;
+        };
+        indices.sort_unstable();
+        indices.dedup();
+        matches.push((
+            FilenameMatch {
+                filename,
+                score,
+                indices,
+            },
+            row.get(1)?,
+        ));
+    }
+
+    matches.sort_by(|(a, a_modified), (b, b_modified)| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.filename.chars().count().cmp(&b.filename.chars().count()))
+            .then_with(|| b_modified.cmp(a_modified))
+            .then_with(|| a.filename.cmp(&b.filename))
+    });
+    matches.truncate(limit);
+    Ok(matches.into_iter().map(|(result, _)| result).collect())
+}
+
+fn clear_note_targets(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM switch_targets WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+fn index_note_targets(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare(
+        "INSERT INTO switch_targets (filename, kind, text, text_lower, line)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    let mut insert = |kind: SwitchMatchKind, text: &str, line: usize| {
+        stmt.execute(params![
+            filename,
+            kind.as_str(),
+            text,
+            text.to_lowercase(),
+            line as i64
+        ])
+        .map(|_| ())
+    };
+
+    let stem = strip_note_extension(filename);
+    insert(SwitchMatchKind::Filename, stem, 0)?;
+
+    let mut aliases = Vec::new();
+    let title = note_title(filename, content);
+    if stem.rsplit('/').next() != Some(title.as_str()) {
+        aliases.push(title);
+    }
+    if let Some(frontmatter) = parse_frontmatter(content) {
+        if let Some(value) = frontmatter.get("aliases") {
+            aliases.extend(value.as_list());
+        }
+    }
+    for alias in aliases {
+        let alias = alias.trim();
+        if !alias.is_empty() {
+            insert(SwitchMatchKind::Alias, alias, 0)?;
+        }
+    }
+
+    for (index, _, text) in parse_atx_headings(content) {
+        if !text.is_empty() {
+            insert(SwitchMatchKind::Heading, &text, index + 1)?;
+        }
+    }
+    Ok(())
+}
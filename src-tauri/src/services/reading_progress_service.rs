@@ -0,0 +1,69 @@
+use crate::core::{AppError, AppResult};
+use rusqlite::{params, Connection};
+
+/// How far through each note the user has read, as a fraction of its length.
+/// Kept outside the notes table so progress survives index rebuilds.
+pub const READING_PROGRESS_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS reading_progress (
+    filename TEXT PRIMARY KEY,
+    fraction REAL NOT NULL,
+    updated_at INTEGER NOT NULL
+);";
+
+/// Average silent reading speed used for time estimates
+pub const WORDS_PER_MINUTE: usize = 200;
+
+/// Records that `filename` has been read up to `fraction` (0.0 to 1.0).
+/// Setting it back to 0 forgets the note's progress.
+pub fn set_reading_progress(
+    conn: &Connection,
+    filename: &str,
+    fraction: f64,
+    now: i64,
+) -> AppResult<()> {
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(AppError::validation_error(
+            "fraction",
+            "Reading progress must be between 0 and 1",
+        ));
+    }
+    conn.execute_batch(READING_PROGRESS_SCHEMA)?;
+    if fraction == 0.0 {
+        return forget_reading_progress(conn, filename);
+    }
+    conn.execute(
+        "INSERT OR REPLACE INTO reading_progress (filename, fraction, updated_at) VALUES (?1, ?2, ?3)",
+        params![filename, fraction, now],
+    )?;
+    Ok(())
+}
+
+/// Minutes left to read `word_count` words with `fraction` of them read,
+/// rounded up so an unfinished note never shows 0.
+pub fn minutes_left(word_count: usize, fraction: Option<f64>) -> usize {
+    let unread = word_count as f64 * (1.0 - fraction.unwrap_or(0.0).clamp(0.0, 1.0));
+    (unread / WORDS_PER_MINUTE as f64).ceil() as usize
+}
+
+/// Keeps a renamed note's reading progress.
+pub fn move_reading_progress(conn: &Connection, old_name: &str, new_name: &str) -> AppResult<()> {
+    conn.execute_batch(READING_PROGRESS_SCHEMA)?;
+    conn.execute(
+        "DELETE FROM reading_progress WHERE filename = ?1",
+        params![new_name],
+    )?;
+    conn.execute(
+        "UPDATE reading_progress SET filename = ?1 WHERE filename = ?2",
+        params![new_name, old_name],
+    )?;
+    Ok(())
+}
+
+/// Forgets a deleted note, so a new note with its name starts unread.
+pub fn forget_reading_progress(conn: &Connection, filename: &str) -> AppResult<()> {
+    conn.execute_batch(READING_PROGRESS_SCHEMA)?;
+    conn.execute(
+        "DELETE FROM reading_progress WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
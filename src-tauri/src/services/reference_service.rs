@@ -0,0 +1,64 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::{
+        citations::BibEntry,
+        paths::get_reference_cache_path,
+        references::{cache_reference, lookup_local_reference, normalize_doi, ReferenceLink},
+    },
+};
+use std::io::Read;
+use std::time::Duration;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(20);
+const MAX_METADATA_BYTES: u64 = 1024 * 1024;
+/// doi.org content negotiation returns the registrar's metadata in this form
+const CSL_JSON_MEDIA_TYPE: &str = "application/vnd.citationstyles.csl+json";
+
+/// Fetches CSL JSON metadata for a normalized DOI from doi.org.
+pub fn fetch_doi_metadata(doi: &str) -> AppResult<serde_json::Value> {
+    let url = format!("https://doi.org/{}", doi);
+    let response = ureq::AgentBuilder::new()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .get(&url)
+        .set("Accept", CSL_JSON_MEDIA_TYPE)
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(404, _) => {
+                AppError::FileNotFound(format!("DOI '{}' is not registered", doi))
+            }
+            e => AppError::FileRead(format!("Failed to fetch metadata for '{}': {}", doi, e)),
+        })?;
+
+    let mut body = String::new();
+    response
+        .into_reader()
+        .take(MAX_METADATA_BYTES)
+        .read_to_string(&mut body)
+        .map_err(|e| AppError::FileRead(format!("Failed to read metadata for '{}': {}", doi, e)))?;
+    serde_json::from_str(&body)
+        .map_err(|e| AppError::FileRead(format!("Invalid metadata for '{}': {}", doi, e)))
+}
+
+/// Metadata for `link`: Zotero keys come from the configured bibliography,
+/// DOIs from the local cache or else doi.org, caching the result.
+pub fn resolve_reference(link: &ReferenceLink) -> AppResult<Option<BibEntry>> {
+    if let Some(entry) = lookup_local_reference(link) {
+        return Ok(Some(entry));
+    }
+    match link {
+        ReferenceLink::Zotero(_) => Ok(None),
+        ReferenceLink::Doi(doi) => {
+            let item = fetch_doi_metadata(doi)?;
+            cache_reference(&get_reference_cache_path()?, doi, item).map(Some)
+        }
+    }
+}
+
+pub fn resolve_doi(doi: &str) -> AppResult<BibEntry> {
+    let doi = normalize_doi(doi).ok_or_else(|| {
+        AppError::validation_error("doi", &format!("'{}' is not a DOI", doi.trim()))
+    })?;
+    resolve_reference(&ReferenceLink::Doi(doi.clone()))?
+        .ok_or_else(|| AppError::FileNotFound(format!("No metadata found for {}", doi)))
+}
@@ -0,0 +1,218 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::tasks::{extract_tasks, Task},
+};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Days `list_upcoming_reminders` looks ahead when not told
+pub const DEFAULT_UPCOMING_DAYS: u32 = 7;
+pub const MAX_UPCOMING_DAYS: u32 = 366;
+pub const DEFAULT_SNOOZE_MINUTES: u32 = 60;
+pub const MAX_SNOOZE_MINUTES: u32 = 30 * 24 * 60;
+/// Tasks overdue by more than this are still listed but no longer notified,
+/// so a first run doesn't bring up every forgotten task in the vault
+pub const MAX_NOTIFIED_OVERDUE_DAYS: i64 = 7;
+
+/// Snoozes and sent notifications per task. Keyed by the task's text and due
+/// date rather than its line, so edits elsewhere in the note don't lose them,
+/// and kept outside the notes table so they survive index rebuilds.
+pub const REMINDER_STATE_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS reminder_state (
+    filename TEXT NOT NULL,
+    task TEXT NOT NULL,
+    due TEXT NOT NULL,
+    snoozed_until INTEGER,
+    notified_at INTEGER,
+    PRIMARY KEY (filename, task, due)
+);";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Reminder {
+    pub note_name: String,
+    /// Task text with its due marker removed
+    pub task: String,
+    pub due: String,
+    /// 1-based line number
+    pub line: usize,
+    pub overdue: bool,
+    /// Unix seconds the reminder is snoozed until, while it is
+    pub snoozed_until: Option<i64>,
+}
+
+struct ReminderState {
+    snoozed_until: Option<i64>,
+    notified_at: Option<i64>,
+}
+
+/// `[reminders] notify_time` as a time of day
+pub fn parse_reminder_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+/// Open tasks with a valid due date on or before `until`, soonest first.
+fn open_tasks_due_by(
+    conn: &Connection,
+    until: NaiveDate,
+) -> AppResult<Vec<(String, NaiveDate, Task)>> {
+    // Most notes have no due dates; skip parsing them
+    let mut stmt = conn.prepare(
+        "SELECT filename, content FROM notes
+         WHERE content LIKE '%📅%' OR content LIKE '%due:%'",
+    )?;
+    let notes = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<(String, String)>, _>>()?;
+
+    let mut tasks: Vec<_> = notes
+        .into_iter()
+        .flat_map(|(note_name, content)| {
+            extract_tasks(&content)
+                .into_iter()
+                .filter(|task| !task.done)
+                .filter_map(|task| {
+                    let due = NaiveDate::parse_from_str(task.due.as_deref()?, "%Y-%m-%d").ok()?;
+                    (due <= until).then(|| (note_name.clone(), due, task))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    tasks.sort_by(|a, b| (a.1, &a.0, a.2.line).cmp(&(b.1, &b.0, b.2.line)));
+    Ok(tasks)
+}
+
+fn load_reminder_state(
+    conn: &Connection,
+) -> AppResult<HashMap<(String, String, String), ReminderState>> {
+    conn.execute_batch(REMINDER_STATE_SCHEMA)?;
+    let mut stmt =
+        conn.prepare("SELECT filename, task, due, snoozed_until, notified_at FROM reminder_state")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                (row.get(0)?, row.get(1)?, row.get(2)?),
+                ReminderState {
+                    snoozed_until: row.get(3)?,
+                    notified_at: row.get(4)?,
+                },
+            ))
+        })?
+        .collect::<Result<_, _>>()?;
+    Ok(rows)
+}
+
+/// Open tasks due from now until `days` after `today`, overdue ones
+/// included, soonest first.
+pub fn upcoming_reminders(
+    conn: &Connection,
+    today: NaiveDate,
+    days: u32,
+    now: i64,
+) -> AppResult<Vec<Reminder>> {
+    let until = today + Duration::days(i64::from(days.min(MAX_UPCOMING_DAYS)));
+    let state = load_reminder_state(conn)?;
+    Ok(open_tasks_due_by(conn, until)?
+        .into_iter()
+        .map(|(note_name, due, task)| {
+            let due_text = due.format("%Y-%m-%d").to_string();
+            let snoozed_until = state
+                .get(&(note_name.clone(), task.text.clone(), due_text.clone()))
+                .and_then(|state| state.snoozed_until)
+                .filter(|until| *until > now);
+            Reminder {
+                note_name,
+                task: task.text,
+                due: due_text,
+                line: task.line,
+                overdue: due < today,
+                snoozed_until,
+            }
+        })
+        .collect())
+}
+
+/// Reminders to notify about at `now` (local time): tasks whose due date
+/// reached `notify_time`, not notified yet and not snoozed past `now_ts`.
+pub fn due_reminders(
+    conn: &Connection,
+    now: NaiveDateTime,
+    notify_time: NaiveTime,
+    now_ts: i64,
+) -> AppResult<Vec<Reminder>> {
+    let today = now.date();
+    let oldest = today - Duration::days(MAX_NOTIFIED_OVERDUE_DAYS);
+    let state = load_reminder_state(conn)?;
+    Ok(open_tasks_due_by(conn, today)?
+        .into_iter()
+        .filter(|(_, due, _)| *due >= oldest && due.and_time(notify_time) <= now)
+        .filter_map(|(note_name, due, task)| {
+            let due_text = due.format("%Y-%m-%d").to_string();
+            let key = (note_name, task.text, due_text);
+            let state = state.get(&key);
+            let snoozed = state
+                .and_then(|state| state.snoozed_until)
+                .is_some_and(|until| until > now_ts);
+            if snoozed || state.is_some_and(|state| state.notified_at.is_some()) {
+                return None;
+            }
+            let (note_name, task_text, due_text) = key;
+            Some(Reminder {
+                note_name,
+                task: task_text,
+                due: due_text,
+                line: task.line,
+                overdue: due < today,
+                snoozed_until: None,
+            })
+        })
+        .collect())
+}
+
+/// Records that `reminders` were notified, so they aren't again until
+/// snoozed. State for dates no longer notified about is dropped.
+pub fn mark_notified(
+    conn: &Connection,
+    reminders: &[Reminder],
+    today: NaiveDate,
+    now_ts: i64,
+) -> AppResult<()> {
+    conn.execute_batch(REMINDER_STATE_SCHEMA)?;
+    for reminder in reminders {
+        conn.execute(
+            "INSERT INTO reminder_state (filename, task, due, notified_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(filename, task, due) DO UPDATE SET notified_at = excluded.notified_at",
+            params![reminder.note_name, reminder.task, reminder.due, now_ts],
+        )?;
+    }
+    let cutoff = today - Duration::days(MAX_NOTIFIED_OVERDUE_DAYS);
+    conn.execute(
+        "DELETE FROM reminder_state WHERE due < ?1",
+        params![cutoff.format("%Y-%m-%d").to_string()],
+    )?;
+    Ok(())
+}
+
+/// Holds off the reminder for `task` (its text as listed) in `note_name`
+/// until `until`, when it's notified again.
+pub fn snooze_reminder(
+    conn: &Connection,
+    note_name: &str,
+    task: &str,
+    due: &str,
+    until: i64,
+) -> AppResult<()> {
+    if NaiveDate::parse_from_str(due, "%Y-%m-%d").is_err() {
+        return Err(AppError::validation_error(
+            "due",
+            "Due date must be in YYYY-MM-DD format",
+        ));
+    }
+    conn.execute_batch(REMINDER_STATE_SCHEMA)?;
+    conn.execute(
+        "INSERT INTO reminder_state (filename, task, due, snoozed_until) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(filename, task, due) DO UPDATE SET snoozed_until = excluded.snoozed_until, notified_at = NULL",
+        params![note_name, task, due, until],
+    )?;
+    Ok(())
+}
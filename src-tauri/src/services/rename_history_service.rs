@@ -0,0 +1,71 @@
+use crate::core::AppResult;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Redirect chains longer than this are treated as broken
+const MAX_REDIRECTS: usize = 32;
+
+/// Maps every former filename straight to the note's current name. Unlike the notes
+/// table it survives index rebuilds, since it can't be recovered from the files.
+pub const RENAME_HISTORY_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS rename_history (
+    old_name TEXT PRIMARY KEY,
+    new_name TEXT NOT NULL,
+    renamed_at INTEGER NOT NULL
+);";
+
+/// Records a rename and points earlier names of the note at the new one.
+pub fn record_rename(
+    conn: &Connection,
+    old_name: &str,
+    new_name: &str,
+    renamed_at: i64,
+) -> AppResult<()> {
+    conn.execute_batch(RENAME_HISTORY_SCHEMA)?;
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "UPDATE rename_history SET new_name = ?1 WHERE new_name = ?2",
+        params![new_name, old_name],
+    )?;
+    // The new name is a live note again, so it no longer redirects anywhere
+    tx.execute(
+        "DELETE FROM rename_history WHERE old_name = ?1",
+        params![new_name],
+    )?;
+    tx.execute(
+        "INSERT OR REPLACE INTO rename_history (old_name, new_name, renamed_at) VALUES (?1, ?2, ?3)",
+        params![old_name, new_name, renamed_at],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Current name of a note last known as `note_name`, if it was renamed and the
+/// target still exists.
+pub fn resolve_renamed_note(conn: &Connection, note_name: &str) -> AppResult<Option<String>> {
+    conn.execute_batch(RENAME_HISTORY_SCHEMA)?;
+    let mut current = note_name.to_string();
+    for _ in 0..MAX_REDIRECTS {
+        let next: Option<String> = conn
+            .query_row(
+                "SELECT new_name FROM rename_history WHERE old_name = ?1",
+                params![current],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(next) = next else {
+            break;
+        };
+        if next == note_name {
+            return Ok(None);
+        }
+        current = next;
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM notes WHERE filename = ?1)",
+            params![current],
+            |row| row.get(0),
+        )?;
+        if exists {
+            return Ok(Some(current));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,153 @@
+use crate::{
+    core::AppResult,
+    utilities::{
+        html_audit::{audit_html, HtmlIssue},
+        links::{find_embeds, resolve_wikilink_name},
+        note_renderer::{render_note, render_note_with_embeds, render_version},
+    },
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+/// Notes re-rendered per transaction, so saves and searches can interleave
+pub const RERENDER_BATCH_SIZE: usize = 200;
+
+/// Render version (see `render_version`) the stored `html_render` values were
+/// produced with.
+pub const RENDER_STATE_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS render_state (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    renderer_version INTEGER NOT NULL
+);";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RenderAuditFinding {
+    pub note_name: String,
+    pub issues: Vec<HtmlIssue>,
+}
+
+fn stored_render_version(conn: &Connection) -> AppResult<Option<i64>> {
+    conn.execute_batch(RENDER_STATE_SCHEMA)?;
+    Ok(conn
+        .query_row(
+            "SELECT renderer_version FROM render_state WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+/// Notes whose stored render is missing or from an older renderer; every note
+/// when `force` is set.
+pub fn notes_needing_render(conn: &Connection, force: bool) -> AppResult<Vec<String>> {
+    let stored_version = stored_render_version(conn)?;
+    let query = if force || stored_version != Some(render_version()) {
+        "SELECT filename FROM notes ORDER BY modified DESC"
+    } else {
+        "SELECT filename FROM notes WHERE is_indexed = 0 ORDER BY modified DESC"
+    };
+    let mut stmt = conn.prepare(query)?;
+    let rows = stmt.query_map([], |row| row.get(0))?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+/// Regenerates `html_render` for a batch of notes in one transaction. Notes
+/// deleted since the batch was planned are skipped. Returns the number rendered.
+pub fn rerender_notes(conn: &Connection, filenames: &[String]) -> AppResult<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let mut rendered = 0;
+    for filename in filenames {
+        let content: Option<String> = tx
+            .query_row(
+                "SELECT content FROM notes WHERE filename = ?1",
+                params![filename],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(content) = content else {
+            continue;
+        };
+        tx.execute(
+            "UPDATE notes SET html_render = ?2, is_indexed = ?3 WHERE filename = ?1",
+            params![filename, render_note(filename, &content), true],
+        )?;
+        rendered += 1;
+    }
+    tx.commit()?;
+    Ok(rendered)
+}
+
+/// Whether `content` embeds other notes or sections with `![[...]]`, so its
+/// stored render can't be shown as is.
+pub fn has_embeds(content: &str) -> bool {
+    content.contains("![[") && !find_embeds(content).is_empty()
+}
+
+/// Renders a note with its embeds expanded from the notes as they are now.
+/// The result isn't stored, since embedded notes change on their own.
+pub fn render_with_embedded_notes(
+    conn: &Connection,
+    note_name: &str,
+    content: &str,
+) -> AppResult<String> {
+    let notes: Vec<String> = conn
+        .prepare("SELECT filename FROM notes ORDER BY filename")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    let resolve = |target: &str| {
+        let name = resolve_wikilink_name(target, &notes)?;
+        let content: String = conn
+            .query_row(
+                "SELECT content FROM notes WHERE filename = ?1",
+                params![name],
+                |row| row.get(0),
+            )
+            .ok()?;
+        Some((name, content))
+    };
+    Ok(render_note_with_embeds(note_name, content, &resolve))
+}
+
+/// Records that every stored render now matches the current renderer.
+pub fn mark_renders_current(conn: &Connection) -> AppResult<()> {
+    conn.execute_batch(RENDER_STATE_SCHEMA)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO render_state (id, renderer_version) VALUES (1, ?1)",
+        params![render_version()],
+    )?;
+    Ok(())
+}
+
+/// Marks every stored render as not indexed when they come from another
+/// renderer or markdown extension set, so notes are re-rendered as they're
+/// synced or opened rather than shown as before. Returns whether they were.
+pub fn invalidate_stale_renders(conn: &Connection) -> AppResult<bool> {
+    if stored_render_version(conn)? == Some(render_version()) {
+        return Ok(false);
+    }
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("UPDATE notes SET is_indexed = 0", [])?;
+    mark_renders_current(&tx)?;
+    tx.commit()?;
+    Ok(true)
+}
+
+/// Checks every cached render against the HTML allowlist and lists the notes
+/// with offending tags, handlers or URLs. Notes not yet rendered are skipped.
+pub fn audit_renders(conn: &Connection) -> AppResult<Vec<RenderAuditFinding>> {
+    let mut stmt = conn.prepare(
+        "SELECT filename, html_render FROM notes WHERE html_render != '' ORDER BY filename",
+    )?;
+    let mut rows = stmt.query([])?;
+    let mut findings = Vec::new();
+    while let Some(row) = rows.next()? {
+        let html: String = row.get(1)?;
+        let issues = audit_html(&html);
+        if !issues.is_empty() {
+            findings.push(RenderAuditFinding {
+                note_name: row.get(0)?,
+                issues,
+            });
+        }
+    }
+    Ok(findings)
+}
@@ -0,0 +1,141 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::grep_service::{build_grep_regex, GrepFlags},
+    utilities::line_diff::unified_diff,
+};
+use regex::{NoExpand, Regex};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// Unchanged lines shown around each change in a preview diff
+const PREVIEW_CONTEXT_LINES: usize = 1;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ReplaceOptions {
+    #[serde(flatten)]
+    pub flags: GrepFlags,
+    /// Only return the proposed changes
+    pub dry_run: bool,
+    /// Limit to notes in this folder, e.g. `Projects` or `Projects/2024`
+    pub folder: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileReplacement {
+    pub filename: String,
+    pub replacements: usize,
+    /// Unified diff of the change; set for dry runs
+    pub diff: Option<String>,
+    /// Backup of the note taken before it was rewritten
+    pub backup_path: Option<String>,
+    /// Why this note was left unchanged when applying
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReplaceReport {
+    pub dry_run: bool,
+    pub files: Vec<FileReplacement>,
+    /// Replacements made, or proposed for a dry run
+    pub total_replacements: usize,
+}
+
+impl ReplaceReport {
+    pub fn push(&mut self, file: FileReplacement) {
+        if file.error.is_none() {
+            self.total_replacements += file.replacements;
+        }
+        self.files.push(file);
+    }
+}
+
+/// A compiled find-and-replace. With `fixed_strings` the replacement is used
+/// verbatim; otherwise `$1` / `${name}` refer to capture groups.
+pub struct Replacement {
+    regex: Regex,
+    replacement: String,
+    literal: bool,
+}
+
+impl Replacement {
+    pub fn new(pattern: &str, replacement: &str, flags: GrepFlags) -> AppResult<Self> {
+        let regex = build_grep_regex(pattern, flags)?;
+        // `x*` or `^` would insert the replacement all over every note
+        if regex.is_match("") {
+            return Err(AppError::validation_error(
+                "pattern",
+                "Pattern must not match empty text",
+            ));
+        }
+        Ok(Self {
+            regex,
+            replacement: replacement.to_string(),
+            literal: flags.fixed_strings,
+        })
+    }
+
+    pub fn is_match(&self, content: &str) -> bool {
+        self.regex.is_match(content)
+    }
+
+    /// The rewritten content and number of replacements, or `None` when the
+    /// content would not change.
+    pub fn apply(&self, content: &str) -> Option<(String, usize)> {
+        let count = self.regex.find_iter(content).count();
+        if count == 0 {
+            return None;
+        }
+        let replaced = if self.literal {
+            self.regex.replace_all(content, NoExpand(&self.replacement))
+        } else {
+            self.regex.replace_all(content, self.replacement.as_str())
+        };
+        (replaced != content).then(|| (replaced.into_owned(), count))
+    }
+
+    /// The proposed change to one note, with its diff.
+    pub fn preview(&self, filename: &str, content: &str) -> Option<FileReplacement> {
+        let (replaced, count) = self.apply(content)?;
+        Some(FileReplacement {
+            filename: filename.to_string(),
+            replacements: count,
+            diff: Some(unified_diff(content, &replaced, PREVIEW_CONTEXT_LINES)),
+            backup_path: None,
+            error: None,
+        })
+    }
+}
+
+/// Notes whose indexed content contains a match, with that
+/// content, in filename order.
+pub fn candidate_notes(
+    conn: &Connection,
+    replacement: &Replacement,
+    folder: Option<&str>,
+) -> AppResult<Vec<(String, String)>> {
+    let prefix = folder
+        .map(|folder| folder.trim().trim_matches('/'))
+        .filter(|folder| !folder.is_empty())
+        .map(|folder| format!("{}/", folder));
+
+    let mut stmt = conn.prepare("SELECT filename, content FROM notes ORDER BY filename")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut candidates = Vec::new();
+    for row in rows {
+        let (filename, content) = row?;
+        if prefix
+            .as_ref()
+            .is_some_and(|prefix| !filename.starts_with(prefix.as_str()))
+        {
+            continue;
+        }
+        if replacement.is_match(&content) {
+            candidates.push((filename, content));
+        }
+    }
+    Ok(candidates)
+}
@@ -0,0 +1,296 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::snippet_service::expand_template,
+    utilities::{
+        frontmatter::parse_frontmatter,
+        highlights::{find_highlights, highlights_origin},
+        links::strip_note_extension,
+        strings::parse_note_date,
+        tasks::extract_tasks,
+    },
+};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const REVIEWS_FOLDER: &str = "Reviews";
+
+/// Used when the vault has no `Templates/Weekly Review.md` / `Templates/Monthly Review.md`.
+pub const DEFAULT_ROLLUP_TEMPLATE: &str = "---
+title: {{title}}
+tags: [review]
+---
+
+# {{title}}
+
+{{start}} – {{end}}
+
+## Completed tasks ({{task_count}})
+
+{{completed_tasks}}
+
+## Notes created ({{note_count}})
+
+{{notes_created}}
+
+## Highlights ({{highlight_count}})
+
+{{highlights}}
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupPeriod {
+    Week,
+    Month,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RollupRange {
+    pub period: RollupPeriod,
+    pub start: NaiveDate,
+    /// First day after the period
+    pub end: NaiveDate,
+}
+
+impl RollupRange {
+    /// The ISO week (Monday to Sunday) or calendar month containing `date`.
+    pub fn containing(period: RollupPeriod, date: NaiveDate) -> AppResult<Self> {
+        let invalid = || AppError::validation_error("date", "Date is out of range");
+        let (start, end) = match period {
+            RollupPeriod::Week => {
+                let start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                (start, start + Duration::days(7))
+            }
+            RollupPeriod::Month => {
+                let start = date.with_day(1).ok_or_else(invalid)?;
+                let end = if start.month() == 12 {
+                    NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+                } else {
+                    NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+                }
+                .ok_or_else(invalid)?;
+                (start, end)
+            }
+        };
+        Ok(Self { period, start, end })
+    }
+
+    /// `2024-W23` for weeks, `2024-06` for months.
+    pub fn label(&self) -> String {
+        match self.period {
+            RollupPeriod::Week => {
+                let week = self.start.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            RollupPeriod::Month => self.start.format("%Y-%m").to_string(),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self.period {
+            RollupPeriod::Week => format!("Weekly Review {}", self.label()),
+            RollupPeriod::Month => format!("Monthly Review {}", self.start.format("%B %Y")),
+        }
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        date >= self.start && date < self.end
+    }
+
+    pub fn contains_timestamp(&self, timestamp: i64) -> bool {
+        local_date(timestamp).is_some_and(|date| self.contains(date))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RollupNote {
+    pub filename: String,
+    pub content: String,
+    pub created: i64,
+    pub modified: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CompletedTask {
+    pub note_name: String,
+    pub text: String,
+    /// Completion date from a `✅ 2024-06-01`, `done:` or `@done()` marker
+    pub completed: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RollupHighlight {
+    pub note_name: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RollupSummary {
+    pub label: String,
+    pub start: String,
+    /// Last day of the period, inclusive
+    pub end: String,
+    pub completed_tasks: Vec<CompletedTask>,
+    pub notes_created: Vec<String>,
+    pub highlights: Vec<RollupHighlight>,
+}
+
+pub fn rollup_note_name(range: &RollupRange) -> String {
+    format!("{}/{}.md", REVIEWS_FOLDER, range.label())
+}
+
+pub fn template_note_name(period: RollupPeriod) -> &'static str {
+    match period {
+        RollupPeriod::Week => "Templates/Weekly Review.md",
+        RollupPeriod::Month => "Templates/Monthly Review.md",
+    }
+}
+
+/// Gathers the period's activity. Checked tasks count when their completion marker
+/// falls in the period, or, without a marker, when their note was modified in it.
+/// Earlier reviews and generated highlights notes are skipped so nothing is counted twice.
+pub fn summarize(notes: &[RollupNote], range: &RollupRange) -> RollupSummary {
+    let review_prefix = format!("{}/", REVIEWS_FOLDER);
+    let mut notes: Vec<&RollupNote> = notes
+        .iter()
+        .filter(|note| !note.filename.starts_with(&review_prefix))
+        .collect();
+    notes.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+    let mut summary = RollupSummary {
+        label: range.label(),
+        start: range.start.to_string(),
+        end: (range.end - Duration::days(1)).to_string(),
+        completed_tasks: Vec::new(),
+        notes_created: Vec::new(),
+        highlights: Vec::new(),
+    };
+
+    for note in notes {
+        let modified_in_range = range.contains_timestamp(note.modified);
+
+        for task in extract_tasks(&note.content)
+            .into_iter()
+            .filter(|task| task.done)
+        {
+            let counts = match &task.completed {
+                Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .is_ok_and(|date| range.contains(date)),
+                None => modified_in_range,
+            };
+            if counts {
+                summary.completed_tasks.push(CompletedTask {
+                    note_name: note.filename.clone(),
+                    text: task.text,
+                    completed: task.completed,
+                });
+            }
+        }
+
+        if note_created(note).is_some_and(|date| range.contains(date)) {
+            summary.notes_created.push(note.filename.clone());
+        }
+
+        if modified_in_range && highlights_origin(&note.content).is_none() {
+            summary
+                .highlights
+                .extend(find_highlights(&note.content).into_iter().map(|highlight| {
+                    RollupHighlight {
+                        note_name: note.filename.clone(),
+                        text: highlight.text,
+                    }
+                }));
+        }
+    }
+
+    summary
+}
+
+/// Fills the template. Besides the summary lists it offers `{{title}}`, `{{label}}`,
+/// `{{start}}`, `{{end}}`, counts, and the usual `{{date}}`/`{{time}}` variables.
+pub fn render_rollup(
+    template: &str,
+    range: &RollupRange,
+    summary: &RollupSummary,
+    now: DateTime<Local>,
+) -> String {
+    let link = |note_name: &str| format!("[[{}]]", strip_note_extension(note_name));
+    let list = |items: Vec<String>| {
+        if items.is_empty() {
+            "_None_".to_string()
+        } else {
+            items.join("\n")
+        }
+    };
+
+    let variables: HashMap<String, String> = [
+        ("title", range.title()),
+        ("label", summary.label.clone()),
+        ("start", summary.start.clone()),
+        ("end", summary.end.clone()),
+        ("task_count", summary.completed_tasks.len().to_string()),
+        ("note_count", summary.notes_created.len().to_string()),
+        ("highlight_count", summary.highlights.len().to_string()),
+        (
+            "completed_tasks",
+            list(
+                summary
+                    .completed_tasks
+                    .iter()
+                    .map(|task| format!("- [x] {} ({})", task.text, link(&task.note_name)))
+                    .collect(),
+            ),
+        ),
+        (
+            "notes_created",
+            list(
+                summary
+                    .notes_created
+                    .iter()
+                    .map(|note_name| format!("- {}", link(note_name)))
+                    .collect(),
+            ),
+        ),
+        (
+            "highlights",
+            list(
+                summary
+                    .highlights
+                    .iter()
+                    .map(|highlight| {
+                        format!(
+                            "> {}\n> — {}\n",
+                            highlight.text.replace('\n', "\n> "),
+                            link(&highlight.note_name)
+                        )
+                    })
+                    .collect(),
+            ),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect();
+
+    expand_template(template, &variables, now)
+}
+
+/// Front-matter `created`/`date` wins over the filesystem creation time.
+fn note_created(note: &RollupNote) -> Option<NaiveDate> {
+    parse_frontmatter(&note.content)
+        .and_then(|fm| {
+            fm.get_text("created")
+                .or_else(|| fm.get_text("date"))
+                .and_then(parse_note_date)
+        })
+        .map(|date| date.with_timezone(&Local).date_naive())
+        .or_else(|| local_date(note.created))
+}
+
+fn local_date(timestamp: i64) -> Option<NaiveDate> {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|time| time.date_naive())
+}
@@ -0,0 +1,126 @@
+use crate::core::{AppError, AppResult};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+/// Scratchpads are notes kept only in the database, never written to the
+/// notes directory. They last one session: whatever the previous session left
+/// is discarded at startup.
+pub const SCRATCHPADS_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS scratchpads (
+    name TEXT PRIMARY KEY,
+    content TEXT NOT NULL,
+    created INTEGER NOT NULL,
+    modified INTEGER NOT NULL
+);";
+
+/// Most scratchpads open at once
+pub const MAX_SCRATCHPADS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Scratchpad {
+    pub name: String,
+    pub content: String,
+    /// Unix seconds
+    pub created: i64,
+    pub modified: i64,
+}
+
+fn scratchpad_name(n: usize) -> String {
+    format!("Scratchpad {}", n)
+}
+
+fn not_found(name: &str) -> AppError {
+    AppError::FileNotFound(format!("Scratchpad not found: {}", name))
+}
+
+/// Creates an empty scratchpad named `Scratchpad 1` (or `Scratchpad 2`, ...).
+pub fn create_scratchpad(conn: &Connection, now: i64) -> AppResult<Scratchpad> {
+    conn.execute_batch(SCRATCHPADS_SCHEMA)?;
+    let taken: Vec<String> = conn
+        .prepare("SELECT name FROM scratchpads")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    let name = (1..=MAX_SCRATCHPADS)
+        .map(scratchpad_name)
+        .find(|name| !taken.contains(name))
+        .ok_or_else(|| {
+            AppError::validation_error(
+                "scratchpad",
+                &format!("At most {} scratchpads can be open", MAX_SCRATCHPADS),
+            )
+        })?;
+
+    conn.execute(
+        "INSERT INTO scratchpads (name, content, created, modified) VALUES (?1, '', ?2, ?2)",
+        params![name, now],
+    )?;
+    Ok(Scratchpad {
+        name,
+        content: String::new(),
+        created: now,
+        modified: now,
+    })
+}
+
+/// Open scratchpads, oldest first.
+pub fn list_scratchpads(conn: &Connection) -> AppResult<Vec<Scratchpad>> {
+    conn.execute_batch(SCRATCHPADS_SCHEMA)?;
+    let mut stmt = conn.prepare(
+        "SELECT name, content, created, modified FROM scratchpads ORDER BY created, name",
+    )?;
+    let scratchpads = stmt
+        .query_map([], |row| {
+            Ok(Scratchpad {
+                name: row.get(0)?,
+                content: row.get(1)?,
+                created: row.get(2)?,
+                modified: row.get(3)?,
+            })
+        })?
+        .collect::<Result<_, _>>()?;
+    Ok(scratchpads)
+}
+
+pub fn get_scratchpad(conn: &Connection, name: &str) -> AppResult<Scratchpad> {
+    conn.execute_batch(SCRATCHPADS_SCHEMA)?;
+    conn.query_row(
+        "SELECT name, content, created, modified FROM scratchpads WHERE name = ?1",
+        params![name],
+        |row| {
+            Ok(Scratchpad {
+                name: row.get(0)?,
+                content: row.get(1)?,
+                created: row.get(2)?,
+                modified: row.get(3)?,
+            })
+        },
+    )
+    .optional()?
+    .ok_or_else(|| not_found(name))
+}
+
+pub fn save_scratchpad(conn: &Connection, name: &str, content: &str, now: i64) -> AppResult<()> {
+    conn.execute_batch(SCRATCHPADS_SCHEMA)?;
+    let updated = conn.execute(
+        "UPDATE scratchpads SET content = ?2, modified = ?3 WHERE name = ?1",
+        params![name, content, now],
+    )?;
+    if updated == 0 {
+        return Err(not_found(name));
+    }
+    Ok(())
+}
+
+pub fn discard_scratchpad(conn: &Connection, name: &str) -> AppResult<()> {
+    conn.execute_batch(SCRATCHPADS_SCHEMA)?;
+    let removed = conn.execute("DELETE FROM scratchpads WHERE name = ?1", params![name])?;
+    if removed == 0 {
+        return Err(not_found(name));
+    }
+    Ok(())
+}
+
+/// Discards every scratchpad, returning how many there were.
+pub fn discard_all_scratchpads(conn: &Connection) -> AppResult<usize> {
+    conn.execute_batch(SCRATCHPADS_SCHEMA)?;
+    Ok(conn.execute("DELETE FROM scratchpads", [])?)
+}
@@ -0,0 +1,203 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::{
+        derived_index_service::refresh_index,
+        note_details_service::{NOTE_APPEARANCE_INDEX, NOTE_DETAILS_INDEX},
+        publish_service::is_exportable,
+        tag_service::NOTE_TAGS_INDEX,
+    },
+    utilities::tags::{normalize_tag, tag_within},
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// Search results an export may contain; well above any interactive limit.
+pub const EXPORT_MAX_RESULTS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn parse(format: &str) -> AppResult<Self> {
+        match format.trim().to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            other => Err(AppError::validation_error(
+                "format",
+                &format!(
+                    "Unsupported export format '{}' (expected csv or json)",
+                    other
+                ),
+            )),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Narrows search results before export. Unset fields don't filter.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct SearchExportFilters {
+    /// Also matches tags nested under it
+    pub tag: Option<String>,
+    /// Top-level or nested folder, e.g. `Projects` or `Projects/2024`
+    pub folder: Option<String>,
+    /// Unix seconds, inclusive
+    pub modified_after: Option<i64>,
+    /// Unix seconds, inclusive
+    pub modified_before: Option<i64>,
+    pub pinned: Option<bool>,
+    pub archived: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchExportRow {
+    pub note_name: String,
+    pub title: String,
+    pub modified: i64,
+    pub word_count: usize,
+    pub tags: Vec<String>,
+    pub pinned: bool,
+    pub archived: bool,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+}
+
+const CSV_HEADER: &str = "note_name,title,modified,word_count,tags,pinned,archived,icon,color";
+
+/// Metadata for `note_names`, kept in search order. Private notes and notes
+/// rejected by `filters` are left out.
+pub fn collect_export_rows(
+    conn: &Connection,
+    note_names: &[String],
+    filters: &SearchExportFilters,
+) -> AppResult<Vec<SearchExportRow>> {
+    refresh_index(conn, &NOTE_DETAILS_INDEX)?;
+    refresh_index(conn, &NOTE_TAGS_INDEX)?;
+    refresh_index(conn, &NOTE_APPEARANCE_INDEX)?;
+
+    let wanted_tag = filters
+        .tag
+        .as_deref()
+        .map(normalize_tag)
+        .filter(|tag| !tag.is_empty());
+    let folder = filters
+        .folder
+        .as_deref()
+        .map(|folder| folder.trim_matches('/'))
+        .filter(|folder| !folder.is_empty());
+
+    let mut details = conn.prepare(
+        "SELECT n.content, n.modified, d.title, d.word_count, d.pinned, d.archived, a.icon, a.color
+         FROM notes n JOIN note_details d ON d.filename = n.filename
+         LEFT JOIN note_appearance a ON a.filename = n.filename
+         WHERE n.filename = ?1",
+    )?;
+    let mut tags = conn.prepare("SELECT tag FROM note_tags WHERE filename = ?1 ORDER BY tag")?;
+
+    let mut rows = Vec::new();
+    for note_name in note_names {
+        let found = details
+            .query_row(params![note_name], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    SearchExportRow {
+                        note_name: note_name.clone(),
+                        modified: row.get(1)?,
+                        title: row.get(2)?,
+                        word_count: row.get::<_, i64>(3)? as usize,
+                        tags: Vec::new(),
+                        pinned: row.get(4)?,
+                        archived: row.get(5)?,
+                        icon: row.get(6)?,
+                        color: row.get(7)?,
+                    },
+                ))
+            })
+            .optional()?;
+        let Some((content, mut row)) = found else {
+            continue;
+        };
+        if !is_exportable(&content) {
+            continue;
+        }
+
+        row.tags = tags
+            .query_map(params![note_name], |r| r.get(0))?
+            .collect::<Result<_, _>>()?;
+
+        if let Some(tag) = &wanted_tag {
+            if !row.tags.iter().any(|t| tag_within(t, tag)) {
+                continue;
+            }
+        }
+        if let Some(folder) = folder {
+            let in_folder = note_name
+                .strip_prefix(folder)
+                .is_some_and(|rest| rest.starts_with('/'));
+            if !in_folder {
+                continue;
+            }
+        }
+        if filters
+            .modified_after
+            .is_some_and(|after| row.modified < after)
+            || filters
+                .modified_before
+                .is_some_and(|before| row.modified > before)
+            || filters.pinned.is_some_and(|pinned| row.pinned != pinned)
+            || filters
+                .archived
+                .is_some_and(|archived| row.archived != archived)
+        {
+            continue;
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+pub fn render_export(rows: &[SearchExportRow], format: ExportFormat) -> AppResult<String> {
+    match format {
+        ExportFormat::Json => serde_json::to_string_pretty(rows)
+            .map_err(|e| AppError::FileWrite(format!("Failed to serialize export: {}", e))),
+        ExportFormat::Csv => {
+            let mut csv = String::from(CSV_HEADER);
+            csv.push_str("\r\n");
+            for row in rows {
+                let fields = [
+                    csv_field(&row.note_name),
+                    csv_field(&row.title),
+                    row.modified.to_string(),
+                    row.word_count.to_string(),
+                    csv_field(&row.tags.join(";")),
+                    row.pinned.to_string(),
+                    row.archived.to_string(),
+                    csv_field(row.icon.as_deref().unwrap_or_default()),
+                    csv_field(row.color.as_deref().unwrap_or_default()),
+                ];
+                csv.push_str(&fields.join(","));
+                csv.push_str("\r\n");
+            }
+            Ok(csv)
+        }
+    }
+}
+
+/// Quotes a field when it holds a separator, quote or line break (RFC 4180).
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::validation::validate_note_name,
+};
+use age::secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Read, Write};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+pub const SHARE_BUNDLE_EXTENSION: &str = "symshare";
+pub const SHARE_FORMAT: &str = "symiosis-share";
+pub const SHARE_FORMAT_VERSION: u32 = 1;
+pub const MIN_PASSPHRASE_LENGTH: usize = 8;
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const PAYLOAD_ENTRY: &str = "notes.age";
+const MAX_PAYLOAD_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Plain-text description stored next to the encrypted payload so a bundle can be
+/// identified without the passphrase. Note names stay inside the encrypted part.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShareManifest {
+    pub format: String,
+    pub version: u32,
+    pub created: String,
+    pub note_count: usize,
+    pub encryption: String,
+    pub payload: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedNote {
+    pub filename: String,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SharePayload {
+    notes: Vec<SharedNote>,
+}
+
+pub fn validate_passphrase(passphrase: &str) -> AppResult<()> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LENGTH {
+        return Err(AppError::validation_error(
+            "passphrase",
+            &format!("must be at least {} characters long", MIN_PASSPHRASE_LENGTH),
+        ));
+    }
+    Ok(())
+}
+
+/// Builds a share bundle: a zip holding `manifest.json` and the notes encrypted
+/// with an age passphrase (scrypt) recipient.
+pub fn build_share_bundle(notes: &[SharedNote], passphrase: &str) -> AppResult<Vec<u8>> {
+    validate_passphrase(passphrase)?;
+    if notes.is_empty() {
+        return Err(AppError::validation_error(
+            "note_names",
+            "select at least one note to share",
+        ));
+    }
+
+    let payload = serde_json::to_vec(&SharePayload {
+        notes: notes.to_vec(),
+    })
+    .map_err(|e| AppError::FileWrite(format!("Failed to serialize shared notes: {}", e)))?;
+    let encrypted = encrypt(&payload, passphrase)?;
+
+    let manifest = ShareManifest {
+        format: SHARE_FORMAT.to_string(),
+        version: SHARE_FORMAT_VERSION,
+        created: chrono::Utc::now().to_rfc3339(),
+        note_count: notes.len(),
+        encryption: "age-scrypt".to_string(),
+        payload: PAYLOAD_ENTRY.to_string(),
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| AppError::FileWrite(format!("Failed to serialize manifest: {}", e)))?;
+
+    let write_error = |e: zip::result::ZipError| {
+        AppError::FileWrite(format!("Failed to write share bundle: {}", e))
+    };
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file(
+        MANIFEST_ENTRY,
+        SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+    )
+    .map_err(write_error)?;
+    zip.write_all(&manifest_json)?;
+    // age output is already incompressible
+    zip.start_file(
+        PAYLOAD_ENTRY,
+        SimpleFileOptions::default().compression_method(CompressionMethod::Stored),
+    )
+    .map_err(write_error)?;
+    zip.write_all(&encrypted)?;
+
+    Ok(zip.finish().map_err(write_error)?.into_inner())
+}
+
+/// Reads and checks the manifest without decrypting anything.
+pub fn read_share_manifest(bundle: &[u8]) -> AppResult<ShareManifest> {
+    let mut archive = open_archive(bundle)?;
+    let manifest: ShareManifest =
+        serde_json::from_slice(&read_entry(&mut archive, MANIFEST_ENTRY)?)
+            .map_err(|e| AppError::FileRead(format!("Invalid share manifest: {}", e)))?;
+
+    if manifest.format != SHARE_FORMAT {
+        return Err(AppError::FileRead(format!(
+            "Not a Symiosis share bundle (format '{}')",
+            manifest.format
+        )));
+    }
+    if manifest.version > SHARE_FORMAT_VERSION {
+        return Err(AppError::FileRead(format!(
+            "Share bundle version {} is newer than this app supports ({})",
+            manifest.version, SHARE_FORMAT_VERSION
+        )));
+    }
+    Ok(manifest)
+}
+
+/// Decrypts a bundle and returns its manifest and notes. Every note name is
+/// validated before it is handed back, so callers can join it onto the notes directory.
+pub fn open_share_bundle(
+    bundle: &[u8],
+    passphrase: &str,
+) -> AppResult<(ShareManifest, Vec<SharedNote>)> {
+    let manifest = read_share_manifest(bundle)?;
+    let mut archive = open_archive(bundle)?;
+    let encrypted = read_entry(&mut archive, &manifest.payload)?;
+
+    let payload: SharePayload = serde_json::from_slice(&decrypt(&encrypted, passphrase)?)
+        .map_err(|e| AppError::FileRead(format!("Invalid share payload: {}", e)))?;
+
+    for note in &payload.notes {
+        validate_note_name(&note.filename)?;
+    }
+    Ok((manifest, payload.notes))
+}
+
+/// Picks a name for an imported note that does not collide with an existing one:
+/// `Note.md`, then `Note (shared).md`, `Note (shared 2).md`, ...
+pub fn unique_import_name(filename: &str, exists: impl Fn(&str) -> bool) -> String {
+    if !exists(filename) {
+        return filename.to_string();
+    }
+
+    let (stem, extension) = match filename.rfind('.') {
+        Some(index) if index > filename.rfind('/').map_or(0, |slash| slash + 1) => {
+            filename.split_at(index)
+        }
+        _ => (filename, ""),
+    };
+
+    (1..)
+        .map(|n| {
+            if n == 1 {
+                format!("{} (shared){}", stem, extension)
+            } else {
+                format!("{} (shared {}){}", stem, n, extension)
+            }
+        })
+        .find(|candidate| !exists(candidate))
+        .unwrap_or_else(|| filename.to_string())
+}
+
+/// Encrypts to an age passphrase (scrypt) recipient.
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> AppResult<Vec<u8>> {
+    let encryptor =
+        age::Encryptor::with_user_passphrase(SecretString::from(passphrase.to_string()));
+    let mut encrypted = Vec::new();
+    let mut writer = encryptor
+        .wrap_output(&mut encrypted)
+        .map_err(|e| AppError::FileWrite(format!("Failed to encrypt notes: {}", e)))?;
+    writer.write_all(plaintext)?;
+    writer
+        .finish()
+        .map_err(|e| AppError::FileWrite(format!("Failed to encrypt notes: {}", e)))?;
+    Ok(encrypted)
+}
+
+pub fn decrypt(encrypted: &[u8], passphrase: &str) -> AppResult<Vec<u8>> {
+    let decryptor = age::Decryptor::new(encrypted)
+        .map_err(|e| AppError::FileRead(format!("Invalid encrypted payload: {}", e)))?;
+    if !decryptor.is_scrypt() {
+        return Err(AppError::FileRead(
+            "Archive is not passphrase-encrypted".to_string(),
+        ));
+    }
+
+    let identity = age::scrypt::Identity::new(SecretString::from(passphrase.to_string()));
+    let mut reader = decryptor
+        .decrypt(std::iter::once(&identity as &dyn age::Identity))
+        .map_err(|e| match e {
+            age::DecryptError::DecryptionFailed | age::DecryptError::NoMatchingKeys => {
+                AppError::FilePermission("Incorrect passphrase for archive".to_string())
+            }
+            other => AppError::FileRead(format!("Failed to decrypt archive: {}", other)),
+        })?;
+
+    let mut plaintext = Vec::new();
+    reader
+        .read_to_end(&mut plaintext)
+        .map_err(|e| AppError::FileRead(format!("Failed to decrypt archive: {}", e)))?;
+    Ok(plaintext)
+}
+
+pub fn open_archive(bundle: &[u8]) -> AppResult<ZipArchive<Cursor<&[u8]>>> {
+    ZipArchive::new(Cursor::new(bundle))
+        .map_err(|e| AppError::FileRead(format!("Not a valid archive: {}", e)))
+}
+
+/// Reads one zip entry, refusing entries over the payload size limit.
+pub fn read_entry(archive: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> AppResult<Vec<u8>> {
+    let entry = archive
+        .by_name(name)
+        .map_err(|_| AppError::FileRead(format!("Archive is missing '{}'", name)))?;
+    if entry.size() > MAX_PAYLOAD_BYTES {
+        return Err(AppError::FileRead(format!(
+            "Archive entry '{}' is too large",
+            name
+        )));
+    }
+
+    let mut data = Vec::new();
+    entry.take(MAX_PAYLOAD_BYTES).read_to_end(&mut data)?;
+    Ok(data)
+}
@@ -0,0 +1,134 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::file_safety::write_app_data_file,
+};
+use chrono::{format::StrftimeItems, DateTime, Local};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path, sync::Mutex};
+
+static SNIPPETS_LOCK: Mutex<()> = Mutex::new(());
+
+static VARIABLE_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)(?::([^}]*))?\s*\}\}"));
+
+const MAX_TRIGGER_LENGTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snippet {
+    pub trigger: String,
+    pub body: String,
+}
+
+pub fn validate_trigger(trigger: &str) -> AppResult<String> {
+    let trigger = trigger.trim();
+    if trigger.is_empty() {
+        return Err(AppError::validation_error("trigger", "cannot be empty"));
+    }
+    if trigger.chars().any(char::is_whitespace) {
+        return Err(AppError::validation_error(
+            "trigger",
+            "cannot contain whitespace",
+        ));
+    }
+    if trigger.chars().count() > MAX_TRIGGER_LENGTH {
+        return Err(AppError::validation_error("trigger", "is too long"));
+    }
+    Ok(trigger.to_string())
+}
+
+pub fn load_snippets(path: &Path) -> AppResult<Vec<Snippet>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(AppError::FileRead(format!(
+                "Failed to read snippets '{}': {}",
+                path.display(),
+                e
+            )))
+        }
+    };
+
+    serde_json::from_str(&content)
+        .map_err(|e| AppError::FileRead(format!("Invalid snippets file: {}", e)))
+}
+
+/// Inserts or replaces the snippet for `trigger`. An empty body removes it.
+pub fn save_snippet(path: &Path, trigger: &str, body: &str) -> AppResult<Vec<Snippet>> {
+    let trigger = validate_trigger(trigger)?;
+    let _guard = SNIPPETS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut snippets = load_snippets(path)?;
+    snippets.retain(|snippet| snippet.trigger != trigger);
+    if !body.is_empty() {
+        snippets.push(Snippet {
+            trigger,
+            body: body.to_string(),
+        });
+    }
+    snippets.sort_by(|a, b| a.trigger.cmp(&b.trigger));
+
+    let content = serde_json::to_string_pretty(&snippets)
+        .map_err(|e| AppError::FileWrite(format!("Failed to serialize snippets: {}", e)))?;
+    write_app_data_file(path, &content)?;
+    Ok(snippets)
+}
+
+/// Returns true when the body references `{{name}}`, so callers can skip
+/// expensive lookups (like reading the clipboard) for unused variables.
+pub fn uses_variable(body: &str, name: &str) -> bool {
+    let Ok(regex) = VARIABLE_REGEX.as_ref() else {
+        return false;
+    };
+    regex
+        .captures_iter(body)
+        .any(|caps| caps.get(1).map(|m| m.as_str()) == Some(name))
+}
+
+/// Expands `{{date}}`, `{{time}}`, `{{datetime}}` (each accepting an optional
+/// strftime format, e.g. `{{date:%d/%m/%Y}}`) and caller-supplied variables.
+/// Unknown variables are left untouched.
+pub fn expand_template(
+    body: &str,
+    variables: &HashMap<String, String>,
+    now: DateTime<Local>,
+) -> String {
+    let Ok(regex) = VARIABLE_REGEX.as_ref() else {
+        return body.to_string();
+    };
+
+    regex
+        .replace_all(body, |caps: &regex::Captures| {
+            let whole = caps.get(0).map(|m| m.as_str()).unwrap_or_default();
+            let name = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
+            let format = caps.get(2).map(|m| m.as_str().trim());
+
+            if let Some(value) = variables.get(name) {
+                return value.clone();
+            }
+
+            let default_format = match name {
+                "date" => "%Y-%m-%d",
+                "time" => "%H:%M",
+                "datetime" => "%Y-%m-%d %H:%M",
+                _ => return whole.to_string(),
+            };
+            format_timestamp(now, format.unwrap_or(default_format))
+                .unwrap_or_else(|| whole.to_string())
+        })
+        .to_string()
+}
+
+fn format_timestamp(now: DateTime<Local>, format: &str) -> Option<String> {
+    // Invalid specifiers make chrono's Display panic, so validate first
+    let items: Vec<_> = StrftimeItems::new(format).collect();
+    if items
+        .iter()
+        .any(|item| matches!(item, chrono::format::Item::Error))
+    {
+        return None;
+    }
+    Some(now.format_with_items(items.into_iter()).to_string())
+}
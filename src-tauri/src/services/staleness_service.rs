@@ -0,0 +1,94 @@
+use crate::{
+    core::AppResult,
+    services::{derived_index_service::refresh_index, tag_service::NOTE_TAGS_INDEX},
+    utilities::{strings::extract_title_from_filename, tags::normalize_tag},
+};
+use rusqlite::{params, params_from_iter, types::Value, Connection};
+use serde::Serialize;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// When each note was last shown. Kept outside the notes table so it
+/// survives index rebuilds.
+pub const NOTE_OPENS_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS note_opens (
+    filename TEXT PRIMARY KEY,
+    opened_at INTEGER NOT NULL
+);";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StaleNote {
+    pub note_name: String,
+    pub title: String,
+    pub modified: i64,
+    /// `None` when the note wasn't opened since opens were first recorded
+    pub last_opened: Option<i64>,
+    /// Whole days since the note was last modified or opened
+    pub idle_days: i64,
+}
+
+pub fn record_note_opened(conn: &Connection, filename: &str, opened_at: i64) -> AppResult<()> {
+    conn.execute_batch(NOTE_OPENS_SCHEMA)?;
+    conn.execute(
+        "INSERT INTO note_opens (filename, opened_at) VALUES (?1, ?2)
+         ON CONFLICT(filename) DO UPDATE SET opened_at = MAX(opened_at, excluded.opened_at)",
+        params![filename, opened_at],
+    )?;
+    Ok(())
+}
+
+/// Notes neither modified nor opened in the `older_than_days` before `now`,
+/// least recently touched first. Notes carrying one of `exclude_tags`, or a
+/// tag nested under one, are left out.
+pub fn stale_notes(
+    conn: &Connection,
+    older_than_days: u32,
+    exclude_tags: &[String],
+    now: i64,
+) -> AppResult<Vec<StaleNote>> {
+    conn.execute_batch(NOTE_OPENS_SCHEMA)?;
+
+    let cutoff = now - i64::from(older_than_days) * SECONDS_PER_DAY;
+    let mut values: Vec<Value> = vec![cutoff.into()];
+    let exclude_tags: Vec<String> = exclude_tags
+        .iter()
+        .map(|tag| normalize_tag(tag))
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    let mut exclusion = String::new();
+    if !exclude_tags.is_empty() {
+        refresh_index(conn, &NOTE_TAGS_INDEX)?;
+        let placeholders: Vec<String> = exclude_tags
+            .into_iter()
+            .map(|tag| {
+                values.push(tag.into());
+                format!("?{}", values.len())
+            })
+            .collect();
+        exclusion = format!(
+            " AND n.filename NOT IN (SELECT filename FROM note_tag_paths WHERE tag IN ({}))",
+            placeholders.join(", ")
+        );
+    }
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT n.filename, n.modified, o.opened_at, MAX(n.modified, COALESCE(o.opened_at, 0)) AS touched
+         FROM notes n LEFT JOIN note_opens o ON o.filename = n.filename
+         WHERE touched < ?1{}
+         ORDER BY touched, n.filename",
+        exclusion
+    ))?;
+    let notes = stmt
+        .query_map(params_from_iter(values), |row| {
+            let note_name: String = row.get(0)?;
+            let touched: i64 = row.get(3)?;
+            Ok(StaleNote {
+                title: extract_title_from_filename(&note_name),
+                note_name,
+                modified: row.get(1)?,
+                last_opened: row.get(2)?,
+                idle_days: (now - touched).max(0) / SECONDS_PER_DAY,
+            })
+        })?
+        .collect::<Result<_, _>>()?;
+    Ok(notes)
+}
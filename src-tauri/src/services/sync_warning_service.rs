@@ -0,0 +1,263 @@
+use crate::core::AppResult;
+use crate::utilities::text_encoding::{decode_text, DecodedText};
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use unicode_normalization::UnicodeNormalization;
+
+/// Warnings kept from past syncs; older ones are dropped
+const MAX_SYNC_WARNINGS: i64 = 200;
+/// Files larger than this aren't indexed as notes
+pub const MAX_NOTE_BYTES: u64 = 10 * 1024 * 1024;
+
+static LAST_SYNC_REPORT: Lazy<Mutex<Option<SyncReport>>> = Lazy::new(|| Mutex::new(None));
+
+/// Problems a sync resolved on its own but the user should know about. Kept
+/// outside the notes table so they survive index rebuilds.
+pub const SYNC_WARNINGS_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS sync_warnings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    filename TEXT NOT NULL,
+    resolved_name TEXT,
+    message TEXT NOT NULL,
+    detected_at INTEGER NOT NULL
+);";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncWarning {
+    pub filename: String,
+    /// Name the file was given to resolve the problem, `None` when it was
+    /// left alone and skipped
+    pub resolved_name: Option<String>,
+    pub message: String,
+    pub detected_at: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncIssueKind {
+    /// The file couldn't be read; a previously indexed version is kept
+    Unreadable,
+    /// Not text, so not indexed
+    Binary,
+    /// Indexed, but not UTF-8; saving the note in the app converts it
+    Encoding,
+    /// Larger than `MAX_NOTE_BYTES`, so not indexed
+    Oversize,
+    /// The name is, or looks like, another note's; see `plan_duplicate_renames`
+    /// and `find_lookalike_names`
+    NameConflict,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncIssue {
+    pub filename: String,
+    pub kind: SyncIssueKind,
+    pub message: String,
+}
+
+/// What a sync of the notes directory into the database did
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SyncReport {
+    /// Unix seconds
+    pub started_at: i64,
+    pub finished_at: i64,
+    pub files_scanned: usize,
+    /// Notes added or updated because they changed on disk
+    pub notes_updated: usize,
+    pub notes_removed: usize,
+    pub issues: Vec<SyncIssue>,
+}
+
+pub fn set_last_sync_report(report: SyncReport) {
+    *LAST_SYNC_REPORT.lock().unwrap_or_else(|e| e.into_inner()) = Some(report);
+}
+
+/// Report of the latest sync, `None` until the first one finished.
+pub fn last_sync_report() -> Option<SyncReport> {
+    LAST_SYNC_REPORT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Reads a note file for indexing, or says why it can't be indexed.
+pub fn read_note_file(path: &Path) -> Result<DecodedText, (SyncIssueKind, String)> {
+    let unreadable = |e: std::io::Error| {
+        (
+            SyncIssueKind::Unreadable,
+            format!("Couldn't read file: {}", e),
+        )
+    };
+    let size = fs::metadata(path).map_err(unreadable)?.len();
+    if size > MAX_NOTE_BYTES {
+        return Err((
+            SyncIssueKind::Oversize,
+            format!(
+                "File is {} bytes, more than the {} bytes notes may have",
+                size, MAX_NOTE_BYTES
+            ),
+        ));
+    }
+
+    let bytes = fs::read(path).map_err(unreadable)?;
+    decode_text(&bytes).ok_or_else(|| (SyncIssueKind::Binary, "File is binary".to_string()))
+}
+
+/// File extensions the sync treats as notes when checking names. Other files,
+/// such as attachments, are never renamed or reported.
+const NOTE_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+
+/// A note file whose name is already another file's in the index and the
+/// free name it gets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateRename {
+    /// Position of the file in the list passed to `plan_duplicate_renames`
+    pub index: usize,
+    pub filename: String,
+    pub new_name: String,
+}
+
+/// A note whose name differs from another note's only by case, Unicode form
+/// or trailing spaces. Both keep their names and rows; the user is warned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookalikeName {
+    pub filename: String,
+    pub similar_to: String,
+}
+
+pub fn is_note_filename(filename: &str) -> bool {
+    Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            NOTE_EXTENSIONS
+                .iter()
+                .any(|note_ext| ext.eq_ignore_ascii_case(note_ext))
+        })
+}
+
+/// Key two filenames share when they'd be the same note to the user: Unicode
+/// composed, without whitespace before separators or the extension, and
+/// case-insensitive.
+pub fn note_key(filename: &str) -> String {
+    clean_filename(filename).to_lowercase()
+}
+
+/// `filename` composed to NFC with trailing whitespace trimmed from each path
+/// component and from the name before its extension.
+fn clean_filename(filename: &str) -> String {
+    let filename: String = filename.nfc().collect();
+    filename
+        .split('/')
+        .map(|component| match component.rsplit_once('.') {
+            Some((stem, ext)) if !stem.trim_end().is_empty() => {
+                format!("{}.{}", stem.trim_end(), ext.trim_end())
+            }
+            _ => component.trim_end().to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Picks a new name for every note file whose name, as indexed, is the same
+/// as an earlier one's. That only happens when names that aren't valid
+/// Unicode are converted lossily, and without a rename only one of them could
+/// have a row. The first file keeps the name; the others get the first free
+/// `name (n).ext`.
+pub fn plan_duplicate_renames(filenames: &[String]) -> Vec<DuplicateRename> {
+    let mut taken: HashSet<String> = filenames.iter().map(|name| note_key(name)).collect();
+    let mut seen = HashSet::new();
+    let mut renames = Vec::new();
+    for (index, filename) in filenames.iter().enumerate() {
+        if !is_note_filename(filename) || seen.insert(filename.as_str()) {
+            continue;
+        }
+        renames.push(DuplicateRename {
+            index,
+            filename: filename.clone(),
+            new_name: free_name(filename, &mut taken),
+        });
+    }
+    renames
+}
+
+/// Note files whose names differ only in ways the user can't easily see, each
+/// paired with the first such name in byte order.
+pub fn find_lookalike_names(filenames: &[String]) -> Vec<LookalikeName> {
+    let mut groups: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    for filename in filenames.iter().filter(|name| is_note_filename(name)) {
+        groups.entry(note_key(filename)).or_default().push(filename);
+    }
+
+    let mut lookalikes = Vec::new();
+    for group in groups.values_mut() {
+        group.sort();
+        group.dedup();
+        let Some((first, others)) = group.split_first() else {
+            continue;
+        };
+        for filename in others {
+            lookalikes.push(LookalikeName {
+                filename: filename.to_string(),
+                similar_to: first.to_string(),
+            });
+        }
+    }
+    lookalikes
+}
+
+fn free_name(filename: &str, taken: &mut HashSet<String>) -> String {
+    let (stem, ext) = match filename.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() && !ext.contains('/') => (stem, format!(".{}", ext)),
+        _ => (filename, String::new()),
+    };
+    let mut n = 2;
+    loop {
+        let candidate = format!("{} ({}){}", stem, n, ext);
+        if taken.insert(note_key(&candidate)) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+pub fn record_sync_warning(conn: &Connection, warning: &SyncWarning) -> AppResult<()> {
+    conn.execute_batch(SYNC_WARNINGS_SCHEMA)?;
+    conn.execute(
+        "INSERT INTO sync_warnings (filename, resolved_name, message, detected_at) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            warning.filename,
+            warning.resolved_name,
+            warning.message,
+            warning.detected_at
+        ],
+    )?;
+    conn.execute(
+        "DELETE FROM sync_warnings WHERE id <= (SELECT MAX(id) FROM sync_warnings) - ?1",
+        params![MAX_SYNC_WARNINGS],
+    )?;
+    Ok(())
+}
+
+/// Warnings from past syncs, newest first.
+pub fn sync_warnings(conn: &Connection) -> AppResult<Vec<SyncWarning>> {
+    conn.execute_batch(SYNC_WARNINGS_SCHEMA)?;
+    let mut stmt = conn.prepare(
+        "SELECT filename, resolved_name, message, detected_at FROM sync_warnings ORDER BY id DESC",
+    )?;
+    let warnings = stmt
+        .query_map([], |row| {
+            Ok(SyncWarning {
+                filename: row.get(0)?,
+                resolved_name: row.get(1)?,
+                message: row.get(2)?,
+                detected_at: row.get(3)?,
+            })
+        })?
+        .collect::<Result<_, _>>()?;
+    Ok(warnings)
+}
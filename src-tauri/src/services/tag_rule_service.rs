@@ -0,0 +1,172 @@
+use crate::{
+    config::TagRule,
+    core::AppResult,
+    services::template_service::render_template,
+    utilities::{
+        frontmatter::strip_frontmatter,
+        line_diff::unified_diff,
+        tags::{extract_tags, tag_within},
+    },
+};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+/// Unchanged lines shown around appended templates in a preview diff
+const PREVIEW_CONTEXT_LINES: usize = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagRuleAction {
+    AppendTemplate,
+    Move,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TriggeredRule {
+    pub tag: String,
+    pub action: TagRuleAction,
+    /// Template name or destination folder
+    pub target: String,
+    /// Why the action was skipped
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TagRulePlan {
+    pub note_name: String,
+    /// Where the note is moved; `None` when it stays where it is
+    pub new_name: Option<String>,
+    pub rules: Vec<TriggeredRule>,
+    /// Unified diff of the appended templates
+    pub diff: Option<String>,
+    /// Content with the templates appended; `None` when unchanged
+    #[serde(skip)]
+    pub content: Option<String>,
+}
+
+impl TagRulePlan {
+    /// The note's name once the plan has been carried out.
+    pub fn final_name(&self) -> &str {
+        self.new_name.as_deref().unwrap_or(&self.note_name)
+    }
+
+    /// Skips the move, recording why on the rule that asked for it.
+    pub fn cancel_move(&mut self, error: String) {
+        self.new_name = None;
+        if let Some(rule) = self
+            .rules
+            .iter_mut()
+            .find(|rule| rule.action == TagRuleAction::Move && rule.error.is_none())
+        {
+            rule.error = Some(error);
+        }
+    }
+}
+
+/// Rules for a tag `content` carries, directly or through a nested tag, that
+/// `previous` did not. A new note has no previous content.
+pub fn triggered_rules<'a>(
+    rules: &'a [TagRule],
+    previous: Option<&str>,
+    content: &str,
+) -> Vec<&'a TagRule> {
+    let before = previous.map(extract_tags).unwrap_or_default();
+    let after = extract_tags(content);
+    let carries =
+        |tags: &[String], rule: &TagRule| tags.iter().any(|tag| tag_within(tag, &rule.tag));
+    rules
+        .iter()
+        .filter(|rule| carries(&after, rule) && !carries(&before, rule))
+        .collect()
+}
+
+/// `note_name` moved into `folder`, keeping its file name; `None` when it is
+/// already somewhere inside that folder.
+pub fn moved_note_name(note_name: &str, folder: &str) -> Option<String> {
+    if note_name
+        .strip_prefix(folder)
+        .is_some_and(|rest| rest.starts_with('/'))
+    {
+        return None;
+    }
+    let file_name = note_name.rsplit('/').next().unwrap_or(note_name);
+    Some(format!("{}/{}", folder, file_name))
+}
+
+/// `section` added at the end of the note, or `None` when the note already
+/// contains it.
+pub fn append_section(content: &str, section: &str) -> Option<String> {
+    let section = section.trim();
+    if section.is_empty() || content.contains(section) {
+        return None;
+    }
+    let mut output = content.trim_end().to_string();
+    if !output.is_empty() {
+        output.push_str("\n\n");
+    }
+    output.push_str(section);
+    output.push('\n');
+    Some(output)
+}
+
+/// What the rules do when `note_name` is saved as `content`. Templates, read
+/// with `load_template` and without their front matter, are appended in rule
+/// order; only the first move applies.
+pub fn plan_tag_rules(
+    rules: &[TagRule],
+    note_name: &str,
+    previous: Option<&str>,
+    content: &str,
+    load_template: impl Fn(&str) -> AppResult<String>,
+    now: DateTime<Local>,
+) -> TagRulePlan {
+    let mut plan = TagRulePlan {
+        note_name: note_name.to_string(),
+        ..Default::default()
+    };
+    let mut updated = content.to_string();
+    let mut moved = false;
+
+    for rule in triggered_rules(rules, previous, content) {
+        if !rule.append_template.is_empty() {
+            let error = match load_template(&rule.append_template) {
+                Ok(body) => {
+                    let section = render_template(strip_frontmatter(&body), note_name, now);
+                    if let Some(appended) = append_section(&updated, &section) {
+                        updated = appended;
+                    }
+                    None
+                }
+                Err(e) => Some(e.to_string()),
+            };
+            plan.rules.push(TriggeredRule {
+                tag: rule.tag.clone(),
+                action: TagRuleAction::AppendTemplate,
+                target: rule.append_template.clone(),
+                error,
+            });
+        }
+
+        if !rule.move_to.is_empty() {
+            let error = if moved {
+                Some("The note is already moved by an earlier rule".to_string())
+            } else {
+                moved = true;
+                plan.new_name = moved_note_name(note_name, &rule.move_to);
+                None
+            };
+            plan.rules.push(TriggeredRule {
+                tag: rule.tag.clone(),
+                action: TagRuleAction::Move,
+                target: rule.move_to.clone(),
+                error,
+            });
+        }
+    }
+
+    if updated != content {
+        plan.diff = Some(unified_diff(content, &updated, PREVIEW_CONTEXT_LINES));
+        plan.content = Some(updated);
+    }
+    plan
+}
@@ -0,0 +1,201 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::derived_index_service::{refresh_index, DerivedIndex},
+    utilities::tags::{
+        extract_tags, is_valid_tag_name, normalize_tag, tag_with_ancestors, tag_within,
+    },
+};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Front-matter `tags:` and inline `#tags`, normalized, one row per note and tag.
+/// `note_tag_paths` also has a row for each ancestor of a nested tag (`project`
+/// for `#project/alpha`), so a parent tag finds the notes tagged with children.
+pub const NOTE_TAGS_INDEX: DerivedIndex = DerivedIndex {
+    name: "note_tags",
+    schema: "CREATE TABLE IF NOT EXISTS note_tags (
+        filename TEXT NOT NULL,
+        tag TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS note_tags_tag ON note_tags(tag);
+    CREATE INDEX IF NOT EXISTS note_tags_filename ON note_tags(filename);
+    CREATE TABLE IF NOT EXISTS note_tag_paths (
+        filename TEXT NOT NULL,
+        tag TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS note_tag_paths_tag ON note_tag_paths(tag);
+    CREATE INDEX IF NOT EXISTS note_tag_paths_filename ON note_tag_paths(filename);",
+    clear_note: clear_note_tags,
+    index_note: index_note_tags,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TagNode {
+    /// Last segment of the tag, e.g. `alpha` for `project/alpha`
+    pub name: String,
+    pub tag: String,
+    /// Notes carrying exactly this tag
+    pub count: usize,
+    /// Notes carrying this tag or one nested under it
+    pub total: usize,
+    pub children: Vec<TagNode>,
+}
+
+/// Every tag in the vault as a tree of nested tags, most used first at each
+/// level. A parent only used through its children has a `count` of 0.
+pub fn list_all_tags(conn: &Connection) -> AppResult<Vec<TagNode>> {
+    refresh_index(conn, &NOTE_TAGS_INDEX)?;
+
+    let mut stmt = conn.prepare("SELECT tag, COUNT(*) FROM note_tags GROUP BY tag")?;
+    let counts: HashMap<String, usize> = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+        })?
+        .collect::<Result<_, _>>()?;
+
+    let mut stmt =
+        conn.prepare("SELECT tag, COUNT(DISTINCT filename) FROM note_tag_paths GROUP BY tag")?;
+    let mut totals: Vec<(String, usize)> = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+        })?
+        .collect::<Result<_, _>>()?;
+
+    // Longest first, so every node's children are complete before it is built
+    totals.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    let known: HashSet<String> = totals.iter().map(|(tag, _)| tag.clone()).collect();
+    let mut children: HashMap<String, Vec<TagNode>> = HashMap::new();
+    for (tag, total) in totals {
+        let mut ancestors = tag_with_ancestors(&tag);
+        ancestors.pop();
+        let parent = ancestors
+            .into_iter()
+            .rev()
+            .find(|ancestor| known.contains(ancestor))
+            .unwrap_or_default();
+        let name = match parent.is_empty() {
+            true => tag.clone(),
+            false => tag[parent.len()..].trim_start_matches('/').to_string(),
+        };
+        let mut node_children = children.remove(&tag).unwrap_or_default();
+        sort_tag_nodes(&mut node_children);
+        children.entry(parent).or_default().push(TagNode {
+            name,
+            count: counts.get(&tag).copied().unwrap_or(0),
+            tag,
+            total,
+            children: node_children,
+        });
+    }
+
+    let mut roots = children.remove("").unwrap_or_default();
+    sort_tag_nodes(&mut roots);
+    Ok(roots)
+}
+
+fn sort_tag_nodes(nodes: &mut [TagNode]) {
+    nodes.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.tag.cmp(&b.tag)));
+}
+
+/// Notes tagged `tag` (with or without the leading `#`) or a tag nested under
+/// it, most recently modified first.
+pub fn search_notes_by_tag(conn: &Connection, tag: &str) -> AppResult<Vec<String>> {
+    let tag = normalize_tag(tag);
+    if tag.is_empty() {
+        return Ok(Vec::new());
+    }
+    refresh_index(conn, &NOTE_TAGS_INDEX)?;
+    let mut stmt = conn.prepare(
+        "SELECT t.filename FROM note_tag_paths t JOIN notes n ON n.filename = t.filename
+         WHERE t.tag = ?1
+         ORDER BY n.modified DESC, t.filename",
+    )?;
+    let rows = stmt.query_map(params![tag], |row| row.get(0))?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+/// Checks a rename or merge of `sources` into `target`. Returns the normalized
+/// sources and the target as it will be written: no `#`, case kept.
+pub fn prepare_retag(sources: &[String], target: &str) -> AppResult<(Vec<String>, String)> {
+    let target = target.trim().trim_start_matches('#').trim_end_matches('/');
+    if !is_valid_tag_name(target) {
+        return Err(AppError::validation_error(
+            "target",
+            &format!("'{}' is not a valid tag name", target),
+        ));
+    }
+
+    let mut normalized: Vec<String> = Vec::new();
+    for source in sources {
+        let source = normalize_tag(source);
+        if !source.is_empty() && !normalized.contains(&source) {
+            normalized.push(source);
+        }
+    }
+    if normalized.is_empty() {
+        return Err(AppError::validation_error("tags", "No tags to rename"));
+    }
+
+    let normalized_target = normalize_tag(target);
+    if let Some(parent) = normalized
+        .iter()
+        .find(|source| **source != normalized_target && tag_within(&normalized_target, source))
+    {
+        return Err(AppError::validation_error(
+            "target",
+            &format!("Cannot move #{} under itself", parent),
+        ));
+    }
+    Ok((normalized, target.to_string()))
+}
+
+/// Notes carrying one of the normalized `sources` or a tag nested under one,
+/// in filename order.
+pub fn notes_with_tags(conn: &Connection, sources: &[String]) -> AppResult<Vec<String>> {
+    refresh_index(conn, &NOTE_TAGS_INDEX)?;
+
+    let mut stmt =
+        conn.prepare("SELECT DISTINCT filename, tag FROM note_tags ORDER BY filename")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+    let mut filenames: Vec<String> = Vec::new();
+    for row in rows {
+        let (filename, tag) = row?;
+        if filenames.last() != Some(&filename)
+            && sources.iter().any(|source| tag_within(&tag, source))
+        {
+            filenames.push(filename);
+        }
+    }
+    Ok(filenames)
+}
+
+fn clear_note_tags(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_tags WHERE filename = ?1",
+        params![filename],
+    )?;
+    conn.execute(
+        "DELETE FROM note_tag_paths WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+fn index_note_tags(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let mut insert_tag = conn.prepare("INSERT INTO note_tags (filename, tag) VALUES (?1, ?2)")?;
+    let mut insert_path =
+        conn.prepare("INSERT INTO note_tag_paths (filename, tag) VALUES (?1, ?2)")?;
+    let mut paths: HashSet<String> = HashSet::new();
+    for tag in extract_tags(content) {
+        insert_tag.execute(params![filename, &tag])?;
+        for path in tag_with_ancestors(&tag) {
+            if paths.insert(path.clone()) {
+                insert_path.execute(params![filename, path])?;
+            }
+        }
+    }
+    Ok(())
+}
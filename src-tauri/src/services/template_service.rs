@@ -0,0 +1,88 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::snippet_service::expand_template,
+    utilities::strings::extract_title_from_filename,
+};
+use chrono::{DateTime, Local};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Templates folder used when `[templates] directory` is empty. As a dot
+/// folder it is never indexed as notes.
+pub const DEFAULT_TEMPLATES_DIR: &str = ".templates";
+const TEMPLATE_EXTENSION: &str = "md";
+
+pub fn templates_dir(notes_dir: &Path, configured: &str) -> PathBuf {
+    let configured = configured.trim();
+    if configured.is_empty() {
+        notes_dir.join(DEFAULT_TEMPLATES_DIR)
+    } else {
+        notes_dir.join(configured)
+    }
+}
+
+/// Template names (file names with the `.md` extension), sorted. A missing
+/// folder means there are no templates.
+pub fn list_templates(dir: &Path) -> AppResult<Vec<String>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(AppError::FileRead(format!(
+                "Failed to read templates folder '{}': {}",
+                dir.display(),
+                e
+            )))
+        }
+    };
+
+    let mut templates: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with('.') && is_template_file(name))
+        .collect();
+    templates.sort_by_key(|name| name.to_lowercase());
+    Ok(templates)
+}
+
+fn is_template_file(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(TEMPLATE_EXTENSION))
+}
+
+/// Reads template `name`, with or without its `.md` extension. Only files
+/// directly inside `dir` can be loaded.
+pub fn load_template(dir: &Path, name: &str) -> AppResult<String> {
+    let name = name.trim();
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(AppError::validation_error(
+            "template",
+            &format!("Invalid template name '{}'", name),
+        ));
+    }
+
+    let file_name = if is_template_file(name) {
+        name.to_string()
+    } else {
+        format!("{}.{}", name, TEMPLATE_EXTENSION)
+    };
+    fs::read_to_string(dir.join(&file_name)).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            AppError::FileNotFound(format!("Template not found: {}", name))
+        }
+        _ => AppError::FileRead(format!("Failed to read template '{}': {}", name, e)),
+    })
+}
+
+/// Fills `{{title}}` from the new note's file name and the date variables
+/// from `now`; other placeholders are left as written.
+pub fn render_template(body: &str, note_name: &str, now: DateTime<Local>) -> String {
+    let file_name = note_name.rsplit('/').next().unwrap_or(note_name);
+    let variables = HashMap::from([("title".to_string(), extract_title_from_filename(file_name))]);
+    expand_template(body, &variables, now)
+}
@@ -0,0 +1,170 @@
+use crate::{
+    core::AppResult,
+    services::derived_index_service::{refresh_index, DerivedIndex},
+    utilities::{
+        dates::DateRange, frontmatter::parse_frontmatter, time_entries::extract_time_entries,
+    },
+};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const UNTAGGED_GROUP: &str = "(untagged)";
+pub const NO_PROJECT_GROUP: &str = "(no project)";
+
+pub const TIME_ENTRIES_INDEX: DerivedIndex = DerivedIndex {
+    name: "time_entries",
+    schema: "CREATE TABLE IF NOT EXISTS time_entries (
+        filename TEXT NOT NULL,
+        line INTEGER NOT NULL,
+        date TEXT NOT NULL,
+        start TEXT NOT NULL,
+        end TEXT NOT NULL,
+        minutes INTEGER NOT NULL,
+        tags TEXT NOT NULL,
+        project TEXT NOT NULL,
+        description TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS time_entries_date ON time_entries(date);
+    CREATE INDEX IF NOT EXISTS time_entries_filename ON time_entries(filename);",
+    clear_note: clear_note_entries,
+    index_note: index_note_entries,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeGroupBy {
+    Tag,
+    Project,
+    Note,
+    Day,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimeGroup {
+    pub key: String,
+    pub minutes: i64,
+    pub entries: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimeReport {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub group_by: TimeGroupBy,
+    /// Each entry counted once, even when it carries several tags
+    pub total_minutes: i64,
+    /// Largest totals first
+    pub groups: Vec<TimeGroup>,
+}
+
+/// The note's `project` front-matter field, falling back to its folder.
+pub fn note_project(filename: &str, content: &str) -> String {
+    parse_frontmatter(content)
+        .and_then(|fm| fm.get_text("project").map(|p| p.trim().to_string()))
+        .filter(|project| !project.is_empty())
+        .or_else(|| {
+            filename
+                .rsplit_once('/')
+                .map(|(folder, _)| folder.to_string())
+        })
+        .unwrap_or_else(|| NO_PROJECT_GROUP.to_string())
+}
+
+pub fn time_report(
+    conn: &Connection,
+    range: &DateRange,
+    group_by: TimeGroupBy,
+) -> AppResult<TimeReport> {
+    let (from, to) = range.bounds()?;
+    let from = from.map(|date| date.to_string());
+    let to = to.map(|date| date.to_string());
+    refresh_index(conn, &TIME_ENTRIES_INDEX)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT filename, date, minutes, tags, project FROM time_entries
+         WHERE (?1 IS NULL OR date >= ?1) AND (?2 IS NULL OR date <= ?2)",
+    )?;
+    let rows = stmt.query_map(params![from, to], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+        ))
+    })?;
+
+    let mut total_minutes = 0;
+    let mut groups: HashMap<String, TimeGroup> = HashMap::new();
+    for row in rows {
+        let (filename, date, minutes, tags, project) = row?;
+        total_minutes += minutes;
+
+        let keys: Vec<String> = match group_by {
+            TimeGroupBy::Tag if tags.is_empty() => vec![UNTAGGED_GROUP.to_string()],
+            TimeGroupBy::Tag => tags.split(' ').map(str::to_string).collect(),
+            TimeGroupBy::Project => vec![project],
+            TimeGroupBy::Note => vec![filename],
+            TimeGroupBy::Day => vec![date],
+        };
+        for key in keys {
+            let group = groups.entry(key.clone()).or_insert(TimeGroup {
+                key,
+                minutes: 0,
+                entries: 0,
+            });
+            group.minutes += minutes;
+            group.entries += 1;
+        }
+    }
+
+    let mut groups: Vec<TimeGroup> = groups.into_values().collect();
+    match group_by {
+        TimeGroupBy::Day => groups.sort_by(|a, b| a.key.cmp(&b.key)),
+        _ => groups.sort_by(|a, b| b.minutes.cmp(&a.minutes).then_with(|| a.key.cmp(&b.key))),
+    }
+
+    Ok(TimeReport {
+        from,
+        to,
+        group_by,
+        total_minutes,
+        groups,
+    })
+}
+
+fn clear_note_entries(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM time_entries WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+fn index_note_entries(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let entries = extract_time_entries(content);
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let project = note_project(filename, content);
+    let mut stmt = conn.prepare(
+        "INSERT INTO time_entries (filename, line, date, start, end, minutes, tags, project, description)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+    )?;
+    for entry in entries {
+        stmt.execute(params![
+            filename,
+            entry.line as i64,
+            entry.date,
+            entry.start,
+            entry.end,
+            entry.minutes,
+            entry.tags.join(" "),
+            project,
+            entry.description
+        ])?;
+    }
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::file_safety::write_app_data_file,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// Folder inside the notes directory holding deleted notes. Dot folders are
+/// skipped by the scanner and the watcher, so trashed notes never get indexed.
+pub const TRASH_DIR_NAME: &str = ".trash";
+const TRASH_INDEX_FILE: &str = "index.json";
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+static TRASH_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    /// Name the note had before it was deleted
+    pub note_name: String,
+    /// Unix seconds
+    pub deleted_at: i64,
+}
+
+pub fn trash_dir(notes_dir: &Path) -> PathBuf {
+    notes_dir.join(TRASH_DIR_NAME)
+}
+
+/// Where `note_name`, trashed with id `id`, is stored: the id with the
+/// file's own extension, so trashed attachments stay openable.
+pub fn trashed_note_path(notes_dir: &Path, id: &str, note_name: &str) -> PathBuf {
+    match Path::new(note_name).extension() {
+        Some(extension) => {
+            trash_dir(notes_dir).join(format!("{}.{}", id, extension.to_string_lossy()))
+        }
+        None => trash_dir(notes_dir).join(id),
+    }
+}
+
+/// Where a trashed file is. Files trashed before extensions were kept were
+/// all stored as `<id>.md`.
+fn stored_path(notes_dir: &Path, entry: &TrashEntry) -> PathBuf {
+    let path = trashed_note_path(notes_dir, &entry.id, &entry.note_name);
+    let legacy = trash_dir(notes_dir).join(format!("{}.md", entry.id));
+    if !path.exists() && legacy.exists() {
+        legacy
+    } else {
+        path
+    }
+}
+
+fn load_index(notes_dir: &Path) -> AppResult<Vec<TrashEntry>> {
+    let index_path = trash_dir(notes_dir).join(TRASH_INDEX_FILE);
+    let content = match fs::read_to_string(&index_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(AppError::FileRead(format!(
+                "Failed to read trash index: {}",
+                e
+            )))
+        }
+    };
+
+    serde_json::from_str(&content)
+        .map_err(|e| AppError::FileRead(format!("Invalid trash index: {}", e)))
+}
+
+fn save_index(notes_dir: &Path, entries: &[TrashEntry]) -> AppResult<()> {
+    let content = serde_json::to_string_pretty(entries)
+        .map_err(|e| AppError::FileWrite(format!("Failed to serialize trash index: {}", e)))?;
+    write_app_data_file(&trash_dir(notes_dir).join(TRASH_INDEX_FILE), &content)
+}
+
+/// The id the next note trashed at `now` would get.
+pub fn next_trash_id(notes_dir: &Path, now: i64) -> AppResult<String> {
+    let entries = load_index(notes_dir)?;
+    Ok(unique_id(&entries, now))
+}
+
+/// The ids the next `count` notes trashed at `now` would get, in order.
+pub fn next_trash_ids(notes_dir: &Path, count: usize, now: i64) -> AppResult<Vec<String>> {
+    let mut entries = load_index(notes_dir)?;
+    let mut ids = Vec::with_capacity(count);
+    for _ in 0..count {
+        let id = unique_id(&entries, now);
+        entries.push(TrashEntry {
+            id: id.clone(),
+            note_name: String::new(),
+            deleted_at: now,
+        });
+        ids.push(id);
+    }
+    Ok(ids)
+}
+
+fn unique_id(entries: &[TrashEntry], now: i64) -> String {
+    (0..)
+        .map(|n| {
+            if n == 0 {
+                now.to_string()
+            } else {
+                format!("{}-{}", now, n)
+            }
+        })
+        .find(|candidate| !entries.iter().any(|entry| &entry.id == candidate))
+        .unwrap_or_else(|| now.to_string())
+}
+
+/// Moves `note_name` out of the notes directory into the trash.
+pub fn move_to_trash(notes_dir: &Path, note_name: &str, now: i64) -> AppResult<TrashEntry> {
+    let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load_index(notes_dir)?;
+    let entry = TrashEntry {
+        id: unique_id(&entries, now),
+        note_name: note_name.to_string(),
+        deleted_at: now,
+    };
+
+    let target = trashed_note_path(notes_dir, &entry.id, note_name);
+    fs::create_dir_all(trash_dir(notes_dir))?;
+    fs::rename(notes_dir.join(note_name), &target).map_err(|e| {
+        AppError::FileWrite(format!("Failed to move '{}' to trash: {}", note_name, e))
+    })?;
+
+    entries.push(entry.clone());
+    if let Err(e) = save_index(notes_dir, &entries) {
+        // Put the note back rather than leave a file the index doesn't know about
+        let _ = fs::rename(&target, notes_dir.join(note_name));
+        return Err(e);
+    }
+    Ok(entry)
+}
+
+/// Trashed notes, newest first.
+pub fn list_trash(notes_dir: &Path) -> AppResult<Vec<TrashEntry>> {
+    let mut entries = load_index(notes_dir)?;
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.deleted_at));
+    Ok(entries)
+}
+
+/// Moves a trashed note back into the notes directory and returns the name it
+/// was restored under, which gets a numbered suffix if the original name is
+/// taken again.
+pub fn restore_from_trash(notes_dir: &Path, id: &str) -> AppResult<String> {
+    let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load_index(notes_dir)?;
+    let position = entries
+        .iter()
+        .position(|entry| entry.id == id)
+        .ok_or_else(|| AppError::FileNotFound(format!("Trash entry not found: {}", id)))?;
+
+    let restored_name = unique_restore_name(&entries[position].note_name, |candidate| {
+        notes_dir.join(candidate).exists()
+    });
+    let target = notes_dir.join(&restored_name);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(stored_path(notes_dir, &entries[position]), &target).map_err(|e| {
+        AppError::FileWrite(format!("Failed to restore '{}': {}", restored_name, e))
+    })?;
+
+    entries.remove(position);
+    save_index(notes_dir, &entries)?;
+    Ok(restored_name)
+}
+
+fn unique_restore_name(note_name: &str, exists: impl Fn(&str) -> bool) -> String {
+    if !exists(note_name) {
+        return note_name.to_string();
+    }
+
+    let (stem, extension) = match note_name.rfind('.') {
+        Some(index) if index > note_name.rfind('/').map_or(0, |slash| slash + 1) => {
+            note_name.split_at(index)
+        }
+        _ => (note_name, ""),
+    };
+
+    (1..)
+        .map(|n| {
+            if n == 1 {
+                format!("{} (restored){}", stem, extension)
+            } else {
+                format!("{} (restored {}){}", stem, n, extension)
+            }
+        })
+        .find(|candidate| !exists(candidate))
+        .unwrap_or_else(|| note_name.to_string())
+}
+
+/// Permanently deletes everything in the trash and returns how many notes
+/// were removed.
+pub fn empty_trash(notes_dir: &Path) -> AppResult<usize> {
+    purge_matching(notes_dir, |_| true)
+}
+
+/// Permanently deletes notes trashed more than `retention_days` ago. A
+/// retention of 0 keeps everything.
+pub fn purge_expired_trash(notes_dir: &Path, retention_days: u32, now: i64) -> AppResult<usize> {
+    if retention_days == 0 {
+        return Ok(0);
+    }
+    let cutoff = now - i64::from(retention_days) * SECONDS_PER_DAY;
+    purge_matching(notes_dir, |entry| entry.deleted_at <= cutoff)
+}
+
+fn purge_matching(notes_dir: &Path, expired: impl Fn(&TrashEntry) -> bool) -> AppResult<usize> {
+    let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let entries = load_index(notes_dir)?;
+    let (purged, kept): (Vec<_>, Vec<_>) = entries.into_iter().partition(|entry| expired(entry));
+    if purged.is_empty() {
+        return Ok(0);
+    }
+
+    for entry in &purged {
+        match fs::remove_file(stored_path(notes_dir, entry)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(AppError::from(e)),
+        }
+    }
+    save_index(notes_dir, &kept)?;
+    Ok(purged.len())
+}
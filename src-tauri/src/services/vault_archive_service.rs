@@ -0,0 +1,158 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::share_service::{
+        decrypt, encrypt, open_archive, read_entry, validate_passphrase, SharedNote,
+    },
+    utilities::validation::validate_note_name,
+};
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Write};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+pub const VAULT_ARCHIVE_EXTENSION: &str = "symvault";
+pub const VAULT_ARCHIVE_FORMAT: &str = "symiosis-vault";
+pub const VAULT_ARCHIVE_VERSION: u32 = 1;
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const ENCRYPTED_ENTRY: &str = "notes.age";
+/// Folder plain archives keep the notes in, as ordinary files
+const NOTES_FOLDER: &str = "notes/";
+
+/// Plain-text description of a vault archive, readable without the passphrase.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VaultArchiveManifest {
+    pub format: String,
+    pub version: u32,
+    pub created: String,
+    pub note_count: usize,
+    /// `none`, or `age-scrypt` when the notes are encrypted with a passphrase
+    pub encryption: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchivePayload {
+    notes: Vec<SharedNote>,
+}
+
+/// Builds a vault archive: a zip holding `manifest.json` and the notes, either
+/// as plain files under `notes/` or, with a passphrase, encrypted together in
+/// `notes.age` so the copy is unreadable at rest.
+pub fn build_vault_archive(notes: &[SharedNote], passphrase: Option<&str>) -> AppResult<Vec<u8>> {
+    if let Some(passphrase) = passphrase {
+        validate_passphrase(passphrase)?;
+    }
+
+    let manifest = VaultArchiveManifest {
+        format: VAULT_ARCHIVE_FORMAT.to_string(),
+        version: VAULT_ARCHIVE_VERSION,
+        created: chrono::Utc::now().to_rfc3339(),
+        note_count: notes.len(),
+        encryption: if passphrase.is_some() {
+            "age-scrypt"
+        } else {
+            "none"
+        }
+        .to_string(),
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| AppError::FileWrite(format!("Failed to serialize manifest: {}", e)))?;
+
+    let write_error = |e: zip::result::ZipError| {
+        AppError::FileWrite(format!("Failed to write vault archive: {}", e))
+    };
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file(MANIFEST_ENTRY, deflated)
+        .map_err(write_error)?;
+    zip.write_all(&manifest_json)?;
+
+    match passphrase {
+        Some(passphrase) => {
+            let payload = serde_json::to_vec(&ArchivePayload {
+                notes: notes.to_vec(),
+            })
+            .map_err(|e| AppError::FileWrite(format!("Failed to serialize notes: {}", e)))?;
+            // age output is already incompressible
+            zip.start_file(
+                ENCRYPTED_ENTRY,
+                SimpleFileOptions::default().compression_method(CompressionMethod::Stored),
+            )
+            .map_err(write_error)?;
+            zip.write_all(&encrypt(&payload, passphrase)?)?;
+        }
+        None => {
+            for note in notes {
+                zip.start_file(format!("{}{}", NOTES_FOLDER, note.filename), deflated)
+                    .map_err(write_error)?;
+                zip.write_all(note.content.as_bytes())?;
+            }
+        }
+    }
+
+    Ok(zip.finish().map_err(write_error)?.into_inner())
+}
+
+/// Reads and checks the manifest without decrypting anything.
+pub fn read_vault_archive_manifest(archive: &[u8]) -> AppResult<VaultArchiveManifest> {
+    let mut zip = open_archive(archive)?;
+    let manifest: VaultArchiveManifest =
+        serde_json::from_slice(&read_entry(&mut zip, MANIFEST_ENTRY)?)
+            .map_err(|e| AppError::FileRead(format!("Invalid vault archive manifest: {}", e)))?;
+
+    if manifest.format != VAULT_ARCHIVE_FORMAT {
+        return Err(AppError::FileRead(format!(
+            "Not a Symiosis vault archive (format '{}')",
+            manifest.format
+        )));
+    }
+    if manifest.version > VAULT_ARCHIVE_VERSION {
+        return Err(AppError::FileRead(format!(
+            "Vault archive version {} is newer than this app supports ({})",
+            manifest.version, VAULT_ARCHIVE_VERSION
+        )));
+    }
+    Ok(manifest)
+}
+
+/// Returns an archive's manifest and notes, decrypting them when the archive
+/// was made with a passphrase. Every note name is validated, so callers can
+/// join it onto the notes directory.
+pub fn open_vault_archive(
+    archive: &[u8],
+    passphrase: Option<&str>,
+) -> AppResult<(VaultArchiveManifest, Vec<SharedNote>)> {
+    let manifest = read_vault_archive_manifest(archive)?;
+    let mut zip = open_archive(archive)?;
+
+    let notes = if manifest.encryption == "none" {
+        let names: Vec<String> = zip
+            .file_names()
+            .filter(|name| name.starts_with(NOTES_FOLDER) && !name.ends_with('/'))
+            .map(str::to_string)
+            .collect();
+        let mut notes = Vec::with_capacity(names.len());
+        for name in names {
+            let content = String::from_utf8(read_entry(&mut zip, &name)?).map_err(|_| {
+                AppError::FileRead(format!("'{}' in the vault archive is not UTF-8", name))
+            })?;
+            notes.push(SharedNote {
+                filename: name[NOTES_FOLDER.len()..].to_string(),
+                content,
+            });
+        }
+        notes
+    } else {
+        let passphrase = passphrase.ok_or_else(|| {
+            AppError::FilePermission("This vault archive needs its passphrase".to_string())
+        })?;
+        let encrypted = read_entry(&mut zip, ENCRYPTED_ENTRY)?;
+        let payload: ArchivePayload = serde_json::from_slice(&decrypt(&encrypted, passphrase)?)
+            .map_err(|e| AppError::FileRead(format!("Invalid vault archive payload: {}", e)))?;
+        payload.notes
+    };
+
+    for note in &notes {
+        validate_note_name(&note.filename)?;
+    }
+    Ok((manifest, notes))
+}
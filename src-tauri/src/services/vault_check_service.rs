@@ -0,0 +1,184 @@
+use crate::utilities::{
+    paths::{encode_path_for_backup, get_data_dir},
+    validation::validate_notes_directory,
+};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Files counted before the estimate stops, keeping the check quick on huge
+/// folders
+pub const MAX_CHECKED_FILES: usize = 100_000;
+/// Folders other note apps keep in the root of their vaults
+const VAULT_MARKERS: [&str; 2] = [".obsidian", ".logseq"];
+
+/// What `check_vault` found in a folder about to become the notes directory.
+/// `warnings` describes anything worth confirming before it's indexed;
+/// `usable` is false when it can't be used at all.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct VaultCheck {
+    pub path: String,
+    pub exists: bool,
+    pub readable: bool,
+    pub usable: bool,
+    /// Files that would be indexed as notes
+    pub note_count: usize,
+    pub total_bytes: u64,
+    /// Whether the counts stopped at `MAX_CHECKED_FILES`
+    pub truncated: bool,
+    /// Files and folders inside that couldn't be read
+    pub unreadable_entries: usize,
+    /// Folders inside that are vaults of their own
+    pub nested_vaults: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Where the app keeps its databases and backups, which must never be indexed
+fn app_data_dir() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("symiosis"))
+}
+
+/// Whether the app has indexed `dir` as a notes directory before
+fn has_app_database(app_dir: &Path, dir: &Path) -> bool {
+    app_dir
+        .join("databases")
+        .join(encode_path_for_backup(dir))
+        .join("notes.sqlite")
+        .is_file()
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+pub fn check_vault(path: &str, current_vault: &Path) -> VaultCheck {
+    check_vault_with_app_dir(path, current_vault, app_data_dir().as_deref())
+}
+
+/// Checks `path` the way the notes sync would read it, with the app's own
+/// folder at `app_dir`. Hidden files and folders are skipped, as the sync
+/// skips them.
+pub fn check_vault_with_app_dir(
+    path: &str,
+    current_vault: &Path,
+    app_dir: Option<&Path>,
+) -> VaultCheck {
+    let mut check = VaultCheck {
+        path: path.to_string(),
+        ..Default::default()
+    };
+    if let Err(e) = validate_notes_directory(path) {
+        check.warnings.push(e.to_string());
+        return check;
+    }
+
+    let root = Path::new(path);
+    check.exists = root.is_dir();
+    if !check.exists {
+        check.usable = !root.exists();
+        check.warnings.push(if check.usable {
+            "Folder doesn't exist yet and will be created empty".to_string()
+        } else {
+            "Path exists but is not a folder".to_string()
+        });
+        return check;
+    }
+    check.readable = std::fs::read_dir(root).is_ok();
+    if !check.readable {
+        check.warnings.push("Folder can't be read".to_string());
+        return check;
+    }
+    check.usable = true;
+
+    let canonical = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    if let Some(app_dir) = app_dir {
+        let app_dir = app_dir
+            .canonicalize()
+            .unwrap_or_else(|_| app_dir.to_path_buf());
+        if app_dir.starts_with(&canonical) {
+            check.warnings.push(format!(
+                "Contains the app's data folder ({}), whose backups would be indexed as notes",
+                app_dir.display()
+            ));
+        } else if canonical.starts_with(&app_dir) {
+            check.usable = false;
+            check
+                .warnings
+                .push("Folder is inside the app's data folder".to_string());
+        }
+    }
+    let current = current_vault
+        .canonicalize()
+        .unwrap_or_else(|_| current_vault.to_path_buf());
+    if current != canonical {
+        if current.starts_with(&canonical) {
+            check
+                .warnings
+                .push("Contains the current notes directory".to_string());
+        } else if canonical.starts_with(&current) {
+            check
+                .warnings
+                .push("Is inside the current notes directory".to_string());
+        }
+    }
+
+    let walker = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_hidden(entry.path()));
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => {
+                check.unreadable_entries += 1;
+                continue;
+            }
+        };
+        if entry.file_type().is_dir() {
+            let dir = entry.path();
+            let is_nested = entry.depth() > 0
+                && (VAULT_MARKERS.iter().any(|marker| dir.join(marker).is_dir())
+                    || app_dir.is_some_and(|app_dir| has_app_database(app_dir, dir)));
+            if is_nested {
+                let relative = dir.strip_prefix(root).unwrap_or(dir);
+                check
+                    .nested_vaults
+                    .push(relative.to_string_lossy().to_string());
+            }
+            continue;
+        }
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if check.note_count == MAX_CHECKED_FILES {
+            check.truncated = true;
+            break;
+        }
+        check.note_count += 1;
+        match entry.metadata() {
+            Ok(metadata) => check.total_bytes += metadata.len(),
+            Err(_) => check.unreadable_entries += 1,
+        }
+    }
+
+    check.nested_vaults.sort();
+    if !check.nested_vaults.is_empty() {
+        check.warnings.push(format!(
+            "Contains {} other vault(s), whose notes would be indexed here too",
+            check.nested_vaults.len()
+        ));
+    }
+    if check.unreadable_entries > 0 {
+        check.warnings.push(format!(
+            "{} file(s) or folder(s) can't be read and will be skipped",
+            check.unreadable_entries
+        ));
+    }
+    if check.truncated {
+        check.warnings.push(format!(
+            "More than {} files; indexing may take a long time",
+            MAX_CHECKED_FILES
+        ));
+    }
+    check
+}
@@ -0,0 +1,216 @@
+use crate::{
+    config::LintConfig,
+    core::{AppError, AppResult},
+    utilities::{
+        frontmatter::{parse_frontmatter, split_frontmatter, FrontmatterValue},
+        links::strip_note_extension,
+    },
+};
+use chrono::NaiveDate;
+use regex::Regex;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// `[lint]` settings `lint_vault` checks every note against, with the
+/// naming patterns compiled once.
+pub struct VaultLintRules<'a> {
+    config: &'a LintConfig,
+    filename_pattern: Option<Regex>,
+    field_name_pattern: Option<Regex>,
+}
+
+impl<'a> VaultLintRules<'a> {
+    pub fn new(config: &'a LintConfig) -> AppResult<Self> {
+        let compile = |name: &str, pattern: &str| -> AppResult<Option<Regex>> {
+            if pattern.is_empty() {
+                return Ok(None);
+            }
+            Regex::new(pattern)
+                .map(Some)
+                .map_err(|e| AppError::ConfigLoad(format!("Invalid {} '{}': {}", name, pattern, e)))
+        };
+        Ok(Self {
+            config,
+            filename_pattern: compile("filename_pattern", &config.filename_pattern)?,
+            field_name_pattern: compile("field_name_pattern", &config.field_name_pattern)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VaultLintIssue {
+    pub note_name: String,
+    /// 1-based line the issue is on; 1 for issues about the whole note
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct VaultLintReport {
+    pub notes_checked: usize,
+    pub notes_with_issues: usize,
+    /// Issues by rule, e.g. `"invalid-field-type"`, each sorted by note
+    pub issues: BTreeMap<&'static str, Vec<VaultLintIssue>>,
+}
+
+/// Checks every note's front matter against the `[lint]` schema (required
+/// fields and `field_types`) and the naming patterns.
+pub fn lint_vault(conn: &Connection, rules: &VaultLintRules) -> AppResult<VaultLintReport> {
+    let notes: Vec<(String, String)> = conn
+        .prepare("SELECT filename, content FROM notes ORDER BY filename")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+
+    let mut report = VaultLintReport {
+        notes_checked: notes.len(),
+        ..VaultLintReport::default()
+    };
+    for (note_name, content) in &notes {
+        let issues = lint_note_frontmatter(note_name, content, rules);
+        if !issues.is_empty() {
+            report.notes_with_issues += 1;
+        }
+        for (rule, line, message) in issues {
+            report.issues.entry(rule).or_default().push(VaultLintIssue {
+                note_name: note_name.clone(),
+                line,
+                message,
+            });
+        }
+    }
+    Ok(report)
+}
+
+/// Front-matter and naming issues of one note as `(rule, line, message)`.
+pub fn lint_note_frontmatter(
+    note_name: &str,
+    content: &str,
+    rules: &VaultLintRules,
+) -> Vec<(&'static str, usize, String)> {
+    let mut issues = Vec::new();
+
+    if let Some(pattern) = &rules.filename_pattern {
+        let file = note_name.rsplit('/').next().unwrap_or(note_name);
+        let stem = strip_note_extension(file);
+        if !pattern.is_match(stem) {
+            issues.push((
+                "filename-convention",
+                1,
+                format!("'{}' doesn't match {}", stem, pattern.as_str()),
+            ));
+        }
+    }
+
+    let opens_block = content.starts_with("---\n") || content.starts_with("---\r\n");
+    if opens_block && split_frontmatter(content).is_none() {
+        issues.push((
+            "unterminated-frontmatter",
+            1,
+            "Front matter has no closing '---'".to_string(),
+        ));
+    }
+    let frontmatter = parse_frontmatter(content).unwrap_or_default();
+    let field_line = |key: &str| {
+        frontmatter_lines(content, key)
+            .first()
+            .copied()
+            .unwrap_or(1)
+    };
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (key, _) in &frontmatter.fields {
+        let occurrence = seen.entry(key.to_lowercase()).or_default();
+        *occurrence += 1;
+        if *occurrence > 1 {
+            let lines = frontmatter_lines(content, key);
+            let line = lines.get(*occurrence - 1).copied().unwrap_or(1);
+            issues.push((
+                "duplicate-field",
+                line,
+                format!("Field '{}' is set more than once", key),
+            ));
+        }
+        if let Some(pattern) = &rules.field_name_pattern {
+            if !pattern.is_match(key) {
+                issues.push((
+                    "field-name-convention",
+                    field_line(key),
+                    format!("Field '{}' doesn't match {}", key, pattern.as_str()),
+                ));
+            }
+        }
+    }
+
+    for field in &rules.config.required_frontmatter_fields {
+        match frontmatter.get(field) {
+            None => issues.push((
+                "missing-frontmatter-field",
+                1,
+                format!("Missing front-matter field '{}'", field),
+            )),
+            Some(value) if value.as_list().is_empty() => issues.push((
+                "missing-frontmatter-field",
+                field_line(field),
+                format!("Front-matter field '{}' is empty", field),
+            )),
+            Some(_) => {}
+        }
+    }
+
+    for (field, field_type) in &rules.config.field_types {
+        let Some(value) = frontmatter.get(field) else {
+            continue;
+        };
+        if value.as_list().is_empty() || value_has_type(value, field_type) {
+            continue;
+        }
+        issues.push((
+            "invalid-field-type",
+            field_line(field),
+            format!("Field '{}' should be a {}", field, field_type),
+        ));
+    }
+
+    issues.sort_by_key(|(_, line, _)| *line);
+    issues
+}
+
+/// Whether a non-empty value has one of the `[lint.field_types]` types.
+fn value_has_type(value: &FrontmatterValue, field_type: &str) -> bool {
+    let FrontmatterValue::Text(text) = value else {
+        return field_type == "list";
+    };
+    match field_type {
+        "text" => true,
+        "number" => text.parse::<f64>().is_ok_and(f64::is_finite),
+        // A time may follow the date, as in 2024-06-01T09:30
+        "date" => {
+            text.get(..10)
+                .is_some_and(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok())
+                && (text.len() == 10 || text[10..].starts_with(['T', ' ']))
+        }
+        "boolean" => ["true", "false", "yes", "no"]
+            .iter()
+            .any(|flag| text.eq_ignore_ascii_case(flag)),
+        _ => false,
+    }
+}
+
+/// 1-based lines setting `key` in the front matter, in order.
+fn frontmatter_lines(content: &str, key: &str) -> Vec<usize> {
+    let Some((block, _)) = split_frontmatter(content) else {
+        return Vec::new();
+    };
+    block
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            !line.starts_with([' ', '\t'])
+                && line
+                    .split_once(':')
+                    .is_some_and(|(field, _)| field.trim().eq_ignore_ascii_case(key))
+        })
+        .map(|(index, _)| index + 2)
+        .collect()
+}
@@ -0,0 +1,146 @@
+use crate::{
+    config::VaultConfig,
+    core::{AppError, AppResult},
+    utilities::validation::validate_vault,
+};
+use serde::Serialize;
+use std::path::Path;
+
+/// Name of a vault whose folder has no name of its own, such as `/`
+const FALLBACK_VAULT_NAME: &str = "Notes";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Vault {
+    pub name: String,
+    pub path: String,
+    /// The vault whose notes are open
+    pub active: bool,
+}
+
+fn same_path(a: &str, b: &str) -> bool {
+    Path::new(a) == Path::new(b)
+}
+
+/// `base`, or `base 2`, `base 3`, ... when another vault already has it
+fn unique_vault_name(base: &str, vaults: &[VaultConfig]) -> String {
+    let taken = |name: &str| vaults.iter().any(|v| v.name.eq_ignore_ascii_case(name));
+    if !taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{} {}", base, n))
+        .find(|name| !taken(name))
+        .unwrap_or_else(|| base.to_string())
+}
+
+fn folder_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| FALLBACK_VAULT_NAME.to_string())
+}
+
+/// `[[vaults]]` with the open notes directory among them: when no vault
+/// points at it, it comes first, named after its folder.
+pub fn known_vaults(vaults: &[VaultConfig], notes_directory: &str) -> Vec<VaultConfig> {
+    if vaults.iter().any(|v| same_path(&v.path, notes_directory)) {
+        return vaults.to_vec();
+    }
+    let current = VaultConfig {
+        name: unique_vault_name(&folder_name(notes_directory), vaults),
+        path: notes_directory.to_string(),
+    };
+    std::iter::once(current)
+        .chain(vaults.iter().cloned())
+        .collect()
+}
+
+pub fn list_vaults(vaults: &[VaultConfig], notes_directory: &str) -> Vec<Vault> {
+    known_vaults(vaults, notes_directory)
+        .into_iter()
+        .map(|vault| Vault {
+            active: same_path(&vault.path, notes_directory),
+            name: vault.name,
+            path: vault.path,
+        })
+        .collect()
+}
+
+/// `[[vaults]]` with the folder at `path` added last, named `name` or after
+/// the folder. The open notes directory is kept among them so it can be
+/// switched back to. The folder doesn't have to exist yet; it's created when
+/// first opened.
+pub fn add_vault(
+    vaults: &[VaultConfig],
+    notes_directory: &str,
+    path: &str,
+    name: Option<&str>,
+) -> AppResult<Vec<VaultConfig>> {
+    let path = path.trim();
+    let mut vaults = known_vaults(vaults, notes_directory);
+    if let Some(existing) = vaults.iter().find(|v| same_path(&v.path, path)) {
+        return Err(AppError::validation_error(
+            "path",
+            &format!("'{}' is already the vault '{}'", path, existing.name),
+        ));
+    }
+    let root = Path::new(path);
+    if root.exists() && !root.is_dir() {
+        return Err(AppError::validation_error(
+            "path",
+            &format!("'{}' is not a folder", path),
+        ));
+    }
+
+    let name = match name.map(str::trim).filter(|name| !name.is_empty()) {
+        Some(name) if vaults.iter().any(|v| v.name.eq_ignore_ascii_case(name)) => {
+            return Err(AppError::validation_error(
+                "name",
+                &format!("A vault named '{}' already exists", name),
+            ));
+        }
+        Some(name) => name.to_string(),
+        None => unique_vault_name(&folder_name(path), &vaults),
+    };
+    let vault = VaultConfig {
+        name,
+        path: path.to_string(),
+    };
+    validate_vault(&vault)?;
+    vaults.push(vault);
+    Ok(vaults)
+}
+
+/// Folders that `notes_directory` or `[[vaults]]` point at in a proposed
+/// config but no vault of the current one does. New vault folders only come
+/// from the native folder picker, so an edited config can't open the app onto
+/// a folder the user never chose.
+pub fn unpicked_vault_paths(
+    current_vaults: &[VaultConfig],
+    current_notes_directory: &str,
+    vaults: &[VaultConfig],
+    notes_directory: &str,
+) -> Vec<String> {
+    let known = known_vaults(current_vaults, current_notes_directory);
+    let mut unpicked: Vec<String> = Vec::new();
+    for path in std::iter::once(notes_directory).chain(vaults.iter().map(|v| v.path.as_str())) {
+        let is_known = known.iter().any(|vault| same_path(&vault.path, path));
+        if !is_known && !unpicked.iter().any(|p| same_path(p, path)) {
+            unpicked.push(path.to_string());
+        }
+    }
+    unpicked
+}
+
+/// The vault called `name`, ignoring case.
+pub fn find_vault(
+    vaults: &[VaultConfig],
+    notes_directory: &str,
+    name: &str,
+) -> AppResult<VaultConfig> {
+    known_vaults(vaults, notes_directory)
+        .into_iter()
+        .find(|vault| vault.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| AppError::validation_error("name", &format!("No vault named '{}'", name)))
+}
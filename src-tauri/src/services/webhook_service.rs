@@ -0,0 +1,116 @@
+use crate::{
+    config::WebhooksConfig,
+    core::{AppError, AppResult},
+    utilities::{
+        chat_format::{
+            discord_title, markdown_to_discord, markdown_to_slack, slack_title, split_message,
+        },
+        frontmatter::{note_title, strip_frontmatter},
+    },
+};
+use std::time::Duration;
+
+/// Slack truncates message text beyond roughly 4000 characters
+pub const SLACK_MESSAGE_LIMIT: usize = 4000;
+/// Discord rejects message content longer than 2000 characters
+pub const DISCORD_MESSAGE_LIMIT: usize = 2000;
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelTarget {
+    Slack,
+    Discord,
+}
+
+impl ChannelTarget {
+    pub fn parse(target: &str) -> AppResult<Self> {
+        match target.trim().to_lowercase().as_str() {
+            "slack" => Ok(ChannelTarget::Slack),
+            "discord" => Ok(ChannelTarget::Discord),
+            other => Err(AppError::validation_error(
+                "target",
+                &format!(
+                    "Unsupported channel target '{}' (expected slack or discord)",
+                    other
+                ),
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ChannelTarget::Slack => "slack",
+            ChannelTarget::Discord => "discord",
+        }
+    }
+
+    /// The configured webhook URL; fails when the target is not set up.
+    pub fn webhook_url(self, config: &WebhooksConfig) -> AppResult<&str> {
+        let url = match self {
+            ChannelTarget::Slack => &config.slack_url,
+            ChannelTarget::Discord => &config.discord_url,
+        };
+        if url.is_empty() {
+            return Err(AppError::ConfigLoad(format!(
+                "No webhook configured for {}: set [webhooks] {}_url",
+                self.name(),
+                self.name()
+            )));
+        }
+        Ok(url)
+    }
+
+    fn message_limit(self) -> usize {
+        match self {
+            ChannelTarget::Slack => SLACK_MESSAGE_LIMIT,
+            ChannelTarget::Discord => DISCORD_MESSAGE_LIMIT,
+        }
+    }
+}
+
+/// The note in the target's formatting, headed by its title and split into
+/// messages that fit the platform's length limit. Front matter is left out.
+pub fn format_note_messages(target: ChannelTarget, note_name: &str, content: &str) -> Vec<String> {
+    let title = note_title(note_name, content);
+    let body = strip_frontmatter(content);
+    let text = match target {
+        ChannelTarget::Slack => format!("{}\n\n{}", slack_title(&title), markdown_to_slack(body)),
+        ChannelTarget::Discord => {
+            format!("{}\n\n{}", discord_title(&title), markdown_to_discord(body))
+        }
+    };
+    split_message(text.trim_end(), target.message_limit())
+}
+
+/// The JSON body for one message.
+pub fn webhook_payload(target: ChannelTarget, message: &str) -> serde_json::Value {
+    match target {
+        ChannelTarget::Slack => serde_json::json!({ "text": message }),
+        // Notes should not ping anyone who happens to be @mentioned
+        ChannelTarget::Discord => serde_json::json!({
+            "content": message,
+            "allowed_mentions": { "parse": [] },
+        }),
+    }
+}
+
+/// Posts `messages` in order, stopping at the first failure.
+pub fn post_messages(target: ChannelTarget, url: &str, messages: &[String]) -> AppResult<()> {
+    let agent = ureq::AgentBuilder::new().timeout(WEBHOOK_TIMEOUT).build();
+    for (index, message) in messages.iter().enumerate() {
+        agent
+            .post(url)
+            .set("Content-Type", "application/json")
+            .send_string(&webhook_payload(target, message).to_string())
+            .map_err(|e| {
+                AppError::FileWrite(format!(
+                    "Failed to post message {} of {} to {}: {}",
+                    index + 1,
+                    messages.len(),
+                    target.name(),
+                    e
+                ))
+            })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,264 @@
+//! Backlink Unit Tests
+//!
+//! Tests for the link index, finding the notes that link to a note, the linked
+//! mentions section, the vault health report, and rewriting links when a note
+//! is renamed.
+
+use crate::services::link_service::{
+    backlinks, linked_mentions_html, render_linked_mentions, vault_health_report,
+};
+use crate::tests::test_utils::notes_db;
+use crate::utilities::links::rewrite_wikilinks;
+use rusqlite::{params, Connection};
+use tempfile::TempDir;
+
+fn sources(conn: &Connection, note_name: &str) -> Vec<(String, usize)> {
+    backlinks(conn, note_name)
+        .unwrap()
+        .into_iter()
+        .map(|link| (link.source, link.line))
+        .collect()
+}
+
+#[test]
+fn test_backlinks_find_wikilinks_embeds_and_markdown_links() {
+    let conn = notes_db(&[
+        ("Plan.md", "# Plan\nSee [[Plan#Goals]] and [[Other]]", 1),
+        ("Wiki.md", "Intro\n  Read the [[plan|plan]] first  ", 1),
+        ("Embed.md", "![[Plan]]", 1),
+        ("work/Markdown.md", "[plan](../Plan.md)", 1),
+        ("Code.md", "```\n[[Plan]]\n```", 1),
+        ("Other.md", "No links", 1),
+    ]);
+
+    assert_eq!(
+        sources(&conn, "Plan.md"),
+        vec![
+            ("Embed.md".to_string(), 1),
+            ("Wiki.md".to_string(), 2),
+            ("work/Markdown.md".to_string(), 1),
+        ]
+    );
+    let wiki = backlinks(&conn, "Plan.md").unwrap().remove(1);
+    assert_eq!(wiki.context, "Read the [[plan|plan]] first");
+    assert_eq!(sources(&conn, "Other.md"), vec![("Plan.md".to_string(), 2)]);
+}
+
+#[test]
+fn test_backlinks_resolve_notes_sharing_a_name() {
+    let conn = notes_db(&[
+        ("a/Topic.md", "", 1),
+        ("b/Topic.md", "", 1),
+        ("Links.md", "[[a/Topic]]\n[[b/Topic]]", 1),
+    ]);
+
+    assert_eq!(
+        sources(&conn, "a/Topic.md"),
+        vec![("Links.md".to_string(), 1)]
+    );
+    assert_eq!(
+        sources(&conn, "b/Topic.md"),
+        vec![("Links.md".to_string(), 2)]
+    );
+}
+
+#[test]
+fn test_backlinks_follow_edits() {
+    let conn = notes_db(&[("Target.md", "", 1), ("Source.md", "[[Target]]", 1)]);
+    assert_eq!(sources(&conn, "Target.md").len(), 1);
+
+    conn.execute(
+        "UPDATE notes SET content = 'Unlinked', modified = 2 WHERE filename = 'Source.md'",
+        [],
+    )
+    .unwrap();
+    assert!(sources(&conn, "Target.md").is_empty());
+}
+
+#[test]
+fn test_vault_health_report_lists_broken_links_and_orphans() {
+    let conn = notes_db(&[
+        (
+            "Hub.md",
+            "[[Spoke]] and [[Missing note]]\n[gone](gone.md)",
+            1,
+        ),
+        ("projects/Spoke.md", "Back to [[hub]]", 1),
+        ("Self.md", "Only [[Self]] and [[#Heading]]", 1),
+        ("Lonely.md", "Nothing here", 1),
+        ("Broken.md", "[[Nowhere]]", 1),
+    ]);
+
+    let report = vault_health_report(&conn).unwrap();
+    assert_eq!(report.notes_checked, 5);
+    let broken: Vec<(&str, &str, usize)> = report
+        .broken_links
+        .iter()
+        .map(|link| (link.source.as_str(), link.target.as_str(), link.line))
+        .collect();
+    assert_eq!(
+        broken,
+        vec![
+            ("Broken.md", "Nowhere", 1),
+            ("Hub.md", "Missing note", 1),
+            ("Hub.md", "gone.md", 2),
+        ]
+    );
+    assert_eq!(
+        report.broken_links[1].context,
+        "[[Spoke]] and [[Missing note]]"
+    );
+    // Links to itself or to nothing don't connect a note
+    assert_eq!(report.orphans, vec!["Broken.md", "Lonely.md", "Self.md"]);
+
+    conn.execute(
+        "UPDATE notes SET content = '[[Lonely]]', modified = 2 WHERE filename = 'Broken.md'",
+        [],
+    )
+    .unwrap();
+    let report = vault_health_report(&conn).unwrap();
+    assert_eq!(report.broken_links.len(), 2);
+    assert_eq!(report.orphans, vec!["Self.md"]);
+}
+
+#[test]
+fn test_linked_mentions_group_lines_by_source() {
+    let conn = notes_db(&[
+        ("Plan.md", "# Plan", 1),
+        (
+            "work/Log.md",
+            "Read [[Plan]] and [[Plan#Goals]]\nThen <b>[[plan|the plan]]</b>",
+            1,
+        ),
+        ("Alpha.md", "[[Plan]]", 1),
+    ]);
+
+    assert_eq!(
+        render_linked_mentions(&backlinks(&conn, "Plan.md").unwrap()),
+        "<section class=\"linked-mentions\">\n<hr>\n<h2>Linked mentions</h2>\n<ul>\n\
+         <li><strong>Alpha</strong>\n<ul>\n<li>Plan</li>\n</ul>\n</li>\n\
+         <li><strong>work/Log</strong>\n<ul>\n<li>Read Plan and Plan</li>\n\
+         <li>Then &lt;b&gt;the plan&lt;/b&gt;</li>\n</ul>\n</li>\n</ul>\n</section>\n"
+    );
+    assert_eq!(render_linked_mentions(&[]), "");
+}
+
+#[test]
+fn test_linked_mentions_follow_link_changes() {
+    let conn = notes_db(&[
+        ("Mentioned.md", "# Mentioned", 1),
+        ("Source.md", "See [[Mentioned]]", 1),
+    ]);
+    assert!(linked_mentions_html(&conn, "Mentioned.md")
+        .unwrap()
+        .contains("See Mentioned"));
+
+    conn.execute(
+        "UPDATE notes SET content = 'No link', modified = 2 WHERE filename = 'Source.md'",
+        [],
+    )
+    .unwrap();
+    assert_eq!(linked_mentions_html(&conn, "Mentioned.md").unwrap(), "");
+
+    conn.execute(
+        "INSERT INTO notes (filename, content, html_render, modified, is_indexed) VALUES ('New.md', 'Also [[Mentioned]]', '', 3, 1)",
+        [],
+    )
+    .unwrap();
+    assert!(linked_mentions_html(&conn, "Mentioned.md")
+        .unwrap()
+        .contains("Also Mentioned"));
+}
+
+#[test]
+fn test_linked_mentions_are_cached_per_database() {
+    let dir = TempDir::new().unwrap();
+    let open = |file: &str, source: &str| {
+        let conn = Connection::open(dir.path().join(file)).unwrap();
+        conn.execute_batch("CREATE VIRTUAL TABLE IF NOT EXISTS notes USING fts5(filename, content, html_render, modified UNINDEXED, is_indexed UNINDEXED);")
+            .unwrap();
+        conn.execute(
+            "INSERT INTO notes (filename, content, html_render, modified, is_indexed) VALUES ('Mentioned.md', '', '', 1, 1), ('Source.md', ?1, '', 1, 1)",
+            params![source],
+        )
+        .unwrap();
+        conn
+    };
+    let first = open("first.db", "First vault on [[Mentioned]]");
+    let second = open("second.db", "Second vault on [[Mentioned]]");
+
+    assert!(linked_mentions_html(&first, "Mentioned.md")
+        .unwrap()
+        .contains("First vault"));
+    assert!(linked_mentions_html(&second, "Mentioned.md")
+        .unwrap()
+        .contains("Second vault"));
+    // Both indexes are current now, so nothing invalidates the cache
+    assert!(linked_mentions_html(&first, "Mentioned.md")
+        .unwrap()
+        .contains("First vault"));
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn test_rewrite_wikilinks_keeps_headings_aliases_and_code() {
+    let before = names(&["Other.md", "Plan.md"]);
+    let after = names(&["Other.md", "Roadmap.md"]);
+    let content = "See [[Plan]], [[plan#Goals|the goals]] and ![[Plan.md]].\r\n\
+                   Not [[Other]] or [[Planning]].\n```\n[[Plan]]\n```\n[[ Plan |Plan]]";
+
+    let (rewritten, count) = rewrite_wikilinks(content, "Plan.md", "Roadmap.md", &before, &after);
+
+    assert_eq!(count, 4);
+    assert_eq!(
+        rewritten,
+        "See [[Roadmap]], [[Roadmap#Goals|the goals]] and ![[Roadmap.md]].\r\n\
+         Not [[Other]] or [[Planning]].\n```\n[[Plan]]\n```\n[[Roadmap|Plan]]"
+    );
+}
+
+#[test]
+fn test_rewrite_wikilinks_uses_folders_where_needed() {
+    let before = names(&["archive/Plan.md", "work/Draft.md", "work/Plan.md"]);
+    let after = names(&["archive/Plan.md", "work/Draft.md", "work/Roadmap.md"]);
+
+    // Links written with a folder keep one
+    let (rewritten, count) = rewrite_wikilinks(
+        "[[work/Plan]] [[Draft]]",
+        "work/Plan.md",
+        "archive/Plan 2.md",
+        &before,
+        &names(&["archive/Plan 2.md", "archive/Plan.md", "work/Draft.md"]),
+    );
+    assert_eq!(
+        (rewritten.as_str(), count),
+        ("[[archive/Plan 2]] [[Draft]]", 1)
+    );
+
+    let (rewritten, count) = rewrite_wikilinks(
+        "[[Plan]] [[work/plan]]",
+        "work/Plan.md",
+        "work/Roadmap.md",
+        &before,
+        &after,
+    );
+    // `[[Plan]]` resolves to archive/Plan.md, which keeps its name
+    assert_eq!(
+        (rewritten.as_str(), count),
+        ("[[Plan]] [[work/Roadmap]]", 1)
+    );
+}
+
+#[test]
+fn test_rewrite_wikilinks_adds_folder_for_ambiguous_names() {
+    let before = names(&["a/Notes.md", "b/Old.md"]);
+    let after = names(&["a/Notes.md", "b/Notes.md"]);
+
+    let (rewritten, count) =
+        rewrite_wikilinks("[[Old]]", "b/Old.md", "b/Notes.md", &before, &after);
+
+    assert_eq!((rewritten.as_str(), count), ("[[b/Notes]]", 1));
+}
@@ -0,0 +1,63 @@
+//! Batch Query Unit Tests
+//!
+//! Tests for answering several read-only queries in one call.
+
+use crate::services::batch_service::{run_batch, BatchRequest, BatchValue};
+use crate::tests::test_utils::notes_db;
+
+#[test]
+fn test_batch_answers_requests_in_order() {
+    let conn = notes_db(&[
+        ("Plan.md", "The plan has three steps. #work", 10),
+        ("Log.md", "Following [[Plan]] today", 20),
+    ]);
+    let requests: Vec<BatchRequest> = serde_json::from_str(
+        r#"[
+            {"command": "preview", "note_name": "Plan.md", "max_chars": 8},
+            {"command": "metadata", "note_name": "Plan.md"},
+            {"command": "backlinks", "note_name": "Plan.md"}
+        ]"#,
+    )
+    .unwrap();
+
+    let responses = run_batch(&conn, &requests).unwrap();
+    assert_eq!(responses.len(), 3);
+    assert!(responses.iter().all(|response| response.error.is_none()));
+
+    let Some(BatchValue::Preview(preview)) = &responses[0].result else {
+        panic!("Expected a preview");
+    };
+    assert!(preview.chars().count() <= 9, "{}", preview);
+    let Some(BatchValue::Metadata(stats)) = &responses[1].result else {
+        panic!("Expected metadata");
+    };
+    assert_eq!(stats.modified, 10);
+    assert_eq!(stats.tags, vec!["work".to_string()]);
+    let Some(BatchValue::Backlinks(links)) = &responses[2].result else {
+        panic!("Expected backlinks");
+    };
+    assert_eq!(links[0].source, "Log.md");
+}
+
+#[test]
+fn test_batch_reports_failures_per_request() {
+    let conn = notes_db(&[("Plan.md", "Plan", 10)]);
+    let requests = vec![
+        BatchRequest::Metadata {
+            note_name: "Missing.md".to_string(),
+        },
+        BatchRequest::Preview {
+            note_name: "Plan.md".to_string(),
+            max_chars: None,
+        },
+    ];
+
+    let responses = run_batch(&conn, &requests).unwrap();
+    assert!(responses[0].result.is_none());
+    assert!(responses[0].error.is_some());
+    assert_eq!(
+        responses[1].result,
+        Some(BatchValue::Preview("Plan".to_string()))
+    );
+    assert!(run_batch(&conn, &[]).unwrap().is_empty());
+}
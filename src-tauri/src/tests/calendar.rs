@@ -0,0 +1,98 @@
+//! Calendar Unit Tests
+//!
+//! Tests for grouping notes by the day they were created, modified or are a
+//! daily note for.
+
+use crate::services::calendar_service::notes_by_date_range;
+use crate::tests::test_utils::notes_db;
+use chrono::{DateTime, NaiveDate};
+use rusqlite::{params, Connection};
+
+fn calendar_db(notes: &[(&str, i64, Option<i64>)]) -> Connection {
+    let rows: Vec<(&str, &str, i64)> = notes
+        .iter()
+        .map(|(filename, modified, _)| (*filename, "", *modified))
+        .collect();
+    let conn = notes_db(&rows);
+    for (filename, _, created) in notes {
+        if let Some(created) = created {
+            conn.execute(
+                "INSERT INTO note_created (filename, created) VALUES (?1, ?2)",
+                params![filename, created],
+            )
+            .unwrap();
+        }
+    }
+    conn
+}
+
+fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+}
+
+fn timestamp(value: &str) -> i64 {
+    date(value)
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+        .and_utc()
+        .timestamp()
+}
+
+fn utc_day(seconds: i64) -> Option<NaiveDate> {
+    DateTime::from_timestamp(seconds, 0).map(|time| time.date_naive())
+}
+
+#[test]
+fn test_notes_grouped_by_created_modified_and_daily_date() {
+    let conn = calendar_db(&[
+        (
+            "plan.md",
+            timestamp("2024-06-03"),
+            Some(timestamp("2024-06-01")),
+        ),
+        ("Daily/2024-06-02.md", timestamp("2024-06-10"), None),
+        ("Other/2024-06-02.md", timestamp("2024-05-01"), None),
+        (
+            "old.md",
+            timestamp("2024-01-01"),
+            Some(timestamp("2024-01-01")),
+        ),
+    ]);
+
+    let days = notes_by_date_range(
+        &conn,
+        date("2024-06-01"),
+        date("2024-06-05"),
+        "Daily",
+        utc_day,
+    )
+    .unwrap();
+    let dates: Vec<&str> = days.iter().map(|day| day.date.as_str()).collect();
+    assert_eq!(dates, vec!["2024-06-01", "2024-06-02", "2024-06-03"]);
+    assert_eq!(days[0].created, vec!["plan.md"]);
+    assert_eq!(days[1].daily_notes, vec!["Daily/2024-06-02.md"]);
+    assert!(days[1].modified.is_empty());
+    assert_eq!(days[2].modified, vec!["plan.md"]);
+}
+
+#[test]
+fn test_notes_by_date_range_uses_local_day() {
+    let conn = calendar_db(&[("late.md", timestamp("2024-06-01") + 11 * 3600, None)]);
+    let ahead = |seconds: i64| utc_day(seconds + 2 * 3600);
+
+    let days =
+        notes_by_date_range(&conn, date("2024-06-02"), date("2024-06-02"), "", ahead).unwrap();
+    assert_eq!(days.len(), 1);
+    assert_eq!(days[0].modified, vec!["late.md"]);
+}
+
+#[test]
+fn test_notes_by_date_range_rejects_invalid_ranges() {
+    let conn = calendar_db(&[]);
+    assert!(
+        notes_by_date_range(&conn, date("2024-06-02"), date("2024-06-01"), "", utc_day).is_err()
+    );
+    assert!(
+        notes_by_date_range(&conn, date("2000-01-01"), date("2024-01-01"), "", utc_day).is_err()
+    );
+}
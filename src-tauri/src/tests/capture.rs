@@ -0,0 +1,36 @@
+//! Quick Capture Unit Tests
+//!
+//! Tests for appending captured text to a note's content.
+
+use crate::services::capture_service::append_text;
+
+#[test]
+fn test_append_to_empty_note() {
+    assert_eq!(append_text("", "first idea").unwrap(), "first idea\n");
+}
+
+#[test]
+fn test_append_starts_on_new_line() {
+    assert_eq!(
+        append_text("# Inbox\n- old", "- new").unwrap(),
+        "# Inbox\n- old\n- new\n"
+    );
+    assert_eq!(
+        append_text("# Inbox\n", "- new\n\n").unwrap(),
+        "# Inbox\n- new\n"
+    );
+}
+
+#[test]
+fn test_append_keeps_multiline_text() {
+    assert_eq!(
+        append_text("a\n", "line one\nline two").unwrap(),
+        "a\nline one\nline two\n"
+    );
+}
+
+#[test]
+fn test_append_rejects_blank_text() {
+    assert!(append_text("a\n", "").is_err());
+    assert!(append_text("a\n", "  \n\t").is_err());
+}
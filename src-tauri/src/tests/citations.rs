@@ -0,0 +1,246 @@
+//! Citation Unit Tests
+//!
+//! Tests for BibTeX / CSL JSON parsing, citation rendering and DOI / Zotero
+//! reference links.
+
+use crate::utilities::citations::{
+    apply_citations, fingerprint_bibliography, format_inline_citation, format_reference,
+    parse_bibtex, parse_citation_group, parse_csl_json,
+};
+use crate::utilities::references::{
+    annotate_reference_links, append_reference_entry, cache_reference, load_reference_cache,
+    normalize_doi, parse_reference_link, ReferenceLink,
+};
+
+const BIBTEX: &str = r#"
+@string{jml = "Journal of Machine Learning"}
+
+@article{smith2020,
+  author = {Smith, John and Jones, Alice B.},
+  title = {{Deep} Notes -- A Study},
+  journal = jml,
+  year = 2020,
+  volume = {12},
+  number = {3},
+  pages = {45--67},
+  doi = {10.1000/xyz}
+}
+
+@book{who2019,
+  author = {{World Health Organization}},
+  title = "Global Report",
+  publisher = {WHO Press},
+  year = {2019}
+}
+
+@comment{ignored}
+"#;
+
+#[test]
+fn test_parse_bibtex_entries_macros_and_authors() {
+    let bibliography = parse_bibtex(BIBTEX);
+    assert!(bibliography.get("jml").is_none());
+    assert!(bibliography.get("ignored").is_none());
+
+    let smith = bibliography
+        .get("smith2020")
+        .expect("smith2020 should parse");
+    assert_eq!(smith.title.as_deref(), Some("Deep Notes \u{2013} A Study"));
+    assert_eq!(
+        smith.container_title.as_deref(),
+        Some("Journal of Machine Learning")
+    );
+    assert_eq!(smith.year.as_deref(), Some("2020"));
+    assert_eq!(smith.authors.len(), 2);
+    assert_eq!(smith.authors[1].family, "Jones");
+    assert_eq!(smith.authors[1].given.as_deref(), Some("Alice B."));
+
+    let who = bibliography.get("who2019").unwrap();
+    assert_eq!(who.authors.len(), 1);
+    assert_eq!(who.authors[0].family, "World Health Organization");
+}
+
+#[test]
+fn test_parse_csl_json() {
+    let json = r#"[{"id": "doe2021", "type": "article-journal", "title": "On Links",
+        "author": [{"family": "Doe", "given": "Jane"}, {"family": "Roe"}, {"family": "Poe"}],
+        "issued": {"date-parts": [[2021, 5]]}, "container-title": "Notes Quarterly"}]"#;
+    let bibliography = parse_csl_json(json).unwrap();
+    let entry = bibliography.get("doe2021").unwrap();
+
+    assert_eq!(entry.year.as_deref(), Some("2021"));
+    assert_eq!(
+        format_inline_citation(entry, Some("p. 4"), false),
+        "Doe et al., 2021, p. 4"
+    );
+    assert!(parse_csl_json("{}").is_err());
+}
+
+#[test]
+fn test_format_reference() {
+    let bibliography = parse_bibtex(BIBTEX);
+    assert_eq!(
+        format_reference(bibliography.get("smith2020").unwrap()),
+        "Smith, J., & Jones, A. B. (2020). Deep Notes \u{2013} A Study. Journal of Machine Learning, 12(3), 45\u{2013}67. https://doi.org/10.1000/xyz"
+    );
+}
+
+#[test]
+fn test_parse_citation_group() {
+    let items = parse_citation_group("@smith2020, p. 4; -@who2019").unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].locator.as_deref(), Some("p. 4"));
+    assert!(items[1].suppress_author);
+
+    assert!(parse_citation_group("email me at a@b.com").is_none());
+}
+
+#[test]
+fn test_apply_citations_renders_inline_and_references() {
+    let bibliography = parse_bibtex(BIBTEX);
+    let content = "As shown [@smith2020; @missing].\n\n```\n[@who2019]\n```\n[link](@smith2020)\n";
+
+    let rendered = apply_citations(content, &bibliography, "References").unwrap();
+
+    assert!(rendered.contains(r##"<a href="#ref-smith2020">Smith &amp; Jones, 2020</a>"##));
+    assert!(rendered.contains(r#"<span class="citation-missing">@missing?</span>"#));
+    assert!(rendered.contains("```\n[@who2019]\n```"));
+    assert!(rendered.contains("[link](@smith2020)"));
+    assert!(rendered.contains(r#"<li id="ref-smith2020">"#));
+    assert!(!rendered.contains(r#"id="ref-who2019""#));
+
+    assert!(apply_citations("No citations here.", &bibliography, "References").is_none());
+}
+
+#[test]
+fn test_bibliography_fingerprint_follows_file_and_heading() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("first.bib");
+    let second = dir.path().join("second.bib");
+    std::fs::write(&first, BIBTEX).unwrap();
+    std::fs::write(&second, BIBTEX).unwrap();
+
+    let fingerprint = fingerprint_bibliography(&first, "References");
+    assert_ne!(fingerprint, 0);
+    assert_eq!(fingerprint_bibliography(&first, "References"), fingerprint);
+    assert_ne!(fingerprint_bibliography(&second, "References"), fingerprint);
+    assert_ne!(fingerprint_bibliography(&first, "Sources"), fingerprint);
+}
+
+#[test]
+fn test_normalize_doi_and_parse_links() {
+    assert_eq!(
+        normalize_doi(" https://doi.org/10.1000/XYZ.1 ").as_deref(),
+        Some("10.1000/xyz.1")
+    );
+    assert_eq!(
+        normalize_doi("doi:10.1000/xyz").as_deref(),
+        Some("10.1000/xyz")
+    );
+    assert!(normalize_doi("10.1/too-short-prefix").is_none());
+    assert!(normalize_doi("not a doi").is_none());
+
+    assert_eq!(
+        parse_reference_link("https://dx.doi.org/10.1000/xyz"),
+        Some(ReferenceLink::Doi("10.1000/xyz".to_string()))
+    );
+    assert_eq!(
+        parse_reference_link("zotero://select/items/@smith2020"),
+        Some(ReferenceLink::Zotero("smith2020".to_string()))
+    );
+    assert_eq!(
+        parse_reference_link("zotero://select/items/0_ABCD1234"),
+        None
+    );
+    assert_eq!(
+        parse_reference_link("https://example.com/10.1000/xyz"),
+        None
+    );
+}
+
+#[test]
+fn test_cache_reference_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache_path = dir.path().join("references.json");
+    assert!(load_reference_cache(&cache_path).unwrap().is_empty());
+
+    let item = serde_json::json!({
+        "type": "article-journal",
+        "title": "Old Title",
+        "author": [{"family": "Doe", "given": "Jane"}],
+        "issued": {"date-parts": [[2021]]},
+        "DOI": "10.1000/ABC"
+    });
+    cache_reference(&cache_path, "10.1000/abc", item.clone()).unwrap();
+    let mut updated = item;
+    updated["title"] = serde_json::json!("New Title");
+    let entry = cache_reference(&cache_path, "10.1000/abc", updated).unwrap();
+    assert_eq!(entry.title.as_deref(), Some("New Title"));
+
+    let cached = load_reference_cache(&cache_path).unwrap();
+    assert_eq!(cached.len(), 1);
+    assert_eq!(cached[0]["id"], "10.1000/abc");
+    // The cache doubles as a CSL JSON bibliography
+    let content = std::fs::read_to_string(&cache_path).unwrap();
+    assert!(parse_csl_json(&content)
+        .unwrap()
+        .get("10.1000/abc")
+        .is_some());
+
+    assert!(cache_reference(&cache_path, "10.1000/abc", serde_json::json!([])).is_err());
+}
+
+#[test]
+fn test_annotate_reference_links() {
+    let bibliography = parse_bibtex(BIBTEX);
+    let html = concat!(
+        r#"<p><a href="doi:10.1000/XYZ">paper</a> "#,
+        r#"<a href="zotero://select/items/@missing">gone</a> "#,
+        r#"<a href="https://example.com">site</a></p>"#
+    );
+
+    let annotated = annotate_reference_links(html, |link| match link {
+        ReferenceLink::Doi(doi) if doi == "10.1000/xyz" => bibliography.get("smith2020").cloned(),
+        _ => None,
+    })
+    .unwrap();
+
+    assert!(annotated.contains(
+        r#"<a class="reference-link" href="https://doi.org/10.1000/xyz" title="Smith, J., &amp; Jones, A. B. (2020)."#
+    ));
+    assert!(annotated
+        .contains(r#"<a class="reference-link" href="zotero://select/items/@missing">gone</a>"#));
+    assert!(annotated.contains(r#"<a href="https://example.com">site</a>"#));
+
+    assert!(annotate_reference_links("<p>No links</p>", |_| None).is_none());
+}
+
+#[test]
+fn test_append_reference_entry() {
+    let entry = "Doe, J. (2021). On Links.";
+
+    let created = append_reference_entry("# Note\n\nBody\n", "References", entry);
+    assert_eq!(
+        created,
+        "# Note\n\nBody\n\n## References\n\n- Doe, J. (2021). On Links.\n"
+    );
+
+    let existing = "# Note\n\n## References\n\n- Earlier\n\n## Appendix\n\nMore\n";
+    let appended = append_reference_entry(existing, "references", entry);
+    assert_eq!(
+        appended,
+        "# Note\n\n## References\n\n- Earlier\n- Doe, J. (2021). On Links.\n\n## Appendix\n\nMore\n"
+    );
+
+    // Already listed: unchanged
+    assert_eq!(
+        append_reference_entry(&appended, "References", entry),
+        appended
+    );
+
+    let empty_section = append_reference_entry("## References\n", "References", entry);
+    assert_eq!(
+        empty_section,
+        "## References\n\n- Doe, J. (2021). On Links.\n"
+    );
+}
@@ -320,6 +320,231 @@ max_search_results = 0
     assert_eq!(config.preferences.max_search_results, 100); // default
 }
 
+#[test]
+fn test_load_config_fts_tokenizer() {
+    let config = load_config_from_content("[preferences]\nfts_tokenizer = \" Trigram \"\n");
+    assert_eq!(config.preferences.fts_tokenizer, "trigram");
+
+    let invalid = load_config_from_content("[preferences]\nfts_tokenizer = \"icu\"\n");
+    assert_eq!(invalid.preferences.fts_tokenizer, "unicode61");
+}
+
+#[test]
+fn test_load_config_line_endings() {
+    let config = load_config_from_content("[preferences]\nline_endings = \" CRLF \"\n");
+    assert_eq!(config.preferences.line_endings, "crlf");
+
+    let invalid = load_config_from_content("[preferences]\nline_endings = \"cr\"\n");
+    assert_eq!(invalid.preferences.line_endings, "preserve");
+}
+
+#[test]
+fn test_load_config_integrity_check_hours() {
+    let config = load_config_from_content("[preferences]\nintegrity_check_hours = 0\n");
+    assert_eq!(config.preferences.integrity_check_hours, 0);
+
+    let invalid = load_config_from_content("[preferences]\nintegrity_check_hours = 10000\n");
+    assert_eq!(invalid.preferences.integrity_check_hours, 24);
+}
+
+#[test]
+fn test_load_config_expired_notes() {
+    let config = load_config_from_content("[preferences]\nexpired_notes = \"Archive\"\n");
+    assert_eq!(config.preferences.expired_notes, "archive");
+
+    let invalid = load_config_from_content("[preferences]\nexpired_notes = \"delete\"\n");
+    assert_eq!(invalid.preferences.expired_notes, "flag");
+}
+
+#[test]
+fn test_load_config_metadata_fields() {
+    let metadata_toml = r#"
+[metadata]
+fields = ["Status", "priority", "status", "", "bad field", "due-date"]
+"#;
+
+    let config = load_config_from_content(metadata_toml);
+
+    // Lowercased and deduplicated; invalid names are dropped
+    assert_eq!(
+        config.metadata.fields,
+        vec!["status", "priority", "due-date"]
+    );
+}
+
+#[test]
+fn test_load_config_email_section() {
+    let email_toml = r#"
+[email]
+smtp_host = " smtp.example.com "
+smtp_port = 70000
+security = "TLS"
+username = "me@example.com"
+"#;
+
+    let config = load_config_from_content(email_toml);
+
+    assert_eq!(config.email.smtp_host, "smtp.example.com");
+    // Out-of-range port falls back to the default
+    assert_eq!(config.email.smtp_port, 587);
+    assert_eq!(config.email.security, "tls");
+    assert_eq!(config.email.username, "me@example.com");
+    assert!(config.email.from_address.is_empty());
+}
+
+#[test]
+fn test_load_config_webhooks_section() {
+    let webhooks_toml = r#"
+[webhooks]
+slack_url = " https://hooks.slack.com/services/T/B/X "
+discord_url = "http://discord.com/api/webhooks/1/abc"
+"#;
+
+    let config = load_config_from_content(webhooks_toml);
+
+    assert_eq!(
+        config.webhooks.slack_url,
+        "https://hooks.slack.com/services/T/B/X"
+    );
+    // Plain http would send the webhook secret unencrypted
+    assert!(config.webhooks.discord_url.is_empty());
+}
+
+#[test]
+fn test_load_config_markdown_extensions() {
+    let config = load_config_from_content(
+        r#"
+[interface]
+markdown_tables = false
+markdown_footnotes = "no"
+"#,
+    );
+
+    assert!(!config.interface.markdown_tables);
+    assert!(config.interface.markdown_task_lists);
+    assert!(config.interface.markdown_footnotes); // not a boolean, default kept
+    assert!(config.interface.markdown_strikethrough);
+}
+
+#[test]
+fn test_load_config_clipboard_search_shortcut() {
+    let config =
+        load_config_from_content("[capture]\nclipboard_search_shortcut = \"Ctrl+Alt+F\"\n");
+    assert_eq!(config.capture.clipboard_search_shortcut, "Ctrl+Alt+F");
+
+    let config =
+        load_config_from_content("[capture]\nclipboard_search_shortcut = \"++Invalid++\"\n");
+    assert_eq!(config.capture.clipboard_search_shortcut, "");
+    assert_eq!(AppConfig::default().capture.clipboard_search_shortcut, "");
+}
+
+#[test]
+fn test_load_config_reminders_section() {
+    let config =
+        load_config_from_content("[reminders]\nenabled = false\nnotify_time = \"18:30\"\n");
+    assert!(!config.reminders.enabled);
+    assert_eq!(config.reminders.notify_time, "18:30");
+
+    let config = load_config_from_content("[reminders]\nnotify_time = \"6pm\"\n");
+    assert!(config.reminders.enabled);
+    assert_eq!(config.reminders.notify_time, "09:00");
+}
+
+#[test]
+fn test_load_config_geo_section() {
+    let geo_toml = r#"
+[geo]
+geocoder = " Nominatim "
+geocoder_url = "https://geo.example.com/"
+"#;
+
+    let config = load_config_from_content(geo_toml);
+    assert_eq!(config.geo.geocoder, "nominatim");
+    assert_eq!(config.geo.geocoder_url, "https://geo.example.com");
+
+    let invalid =
+        load_config_from_content("[geo]\ngeocoder = \"google\"\ngeocoder_url = \"http://geo\"\n");
+    assert!(invalid.geo.geocoder.is_empty());
+    assert!(invalid.geo.geocoder_url.is_empty());
+}
+
+#[test]
+fn test_load_config_automation_rules() {
+    let automation_toml = r##"
+[[automation.rules]]
+tag = "#Archive"
+move_to = "/archive/"
+
+[[automation.rules]]
+tag = "meeting"
+append_template = "meeting"
+
+[[automation.rules]]
+tag = "no-action"
+
+[[automation.rules]]
+tag = "hidden"
+move_to = ".trash"
+"##;
+
+    let config = load_config_from_content(automation_toml);
+    let rules = &config.automation.rules;
+    assert_eq!(rules.len(), 2);
+    assert_eq!(
+        (rules[0].tag.as_str(), rules[0].move_to.as_str()),
+        ("archive", "archive")
+    );
+    assert_eq!(
+        (rules[1].tag.as_str(), rules[1].append_template.as_str()),
+        ("meeting", "meeting")
+    );
+    assert!(rules[1].move_to.is_empty());
+}
+
+#[test]
+fn test_load_config_vaults() {
+    let vaults_toml = r#"
+notes_directory = "/test/notes"
+
+[[vaults]]
+name = " Personal "
+path = "/test/notes"
+
+[[vaults]]
+name = "Work"
+path = "/test/work"
+
+[[vaults]]
+name = "work"
+path = "/test/other"
+
+[[vaults]]
+name = "Relative"
+path = "notes"
+
+[[vaults]]
+name = ""
+path = "/test/unnamed"
+"#;
+
+    let config = load_config_from_content(vaults_toml);
+    let vaults: Vec<(&str, &str)> = config
+        .vaults
+        .iter()
+        .map(|v| (v.name.as_str(), v.path.as_str()))
+        .collect();
+    assert_eq!(
+        vaults,
+        vec![("Personal", "/test/notes"), ("Work", "/test/work")]
+    );
+
+    // Vaults survive the file being rewritten
+    let toml_str = toml::to_string_pretty(&config).unwrap();
+    let reloaded = load_config_from_content(&toml_str);
+    assert_eq!(reloaded.vaults, config.vaults);
+    assert_eq!(reloaded.interface.ui_theme, config.interface.ui_theme);
+}
+
 #[test]
 fn test_load_config_mixed_sections_some_empty() {
     let mixed_sections_toml = r#"
@@ -0,0 +1,67 @@
+//! CRDT Change Log Unit Tests
+//!
+//! Tests for per-note Automerge documents used by multi-device sync.
+
+use crate::services::crdt_service::{
+    crdt_log_path, crdt_state, device_actor, document_text, genesis_document, load_document,
+    merge_remote, record_local_content, save_document,
+};
+use automerge::ActorId;
+use tempfile::TempDir;
+
+#[test]
+fn test_concurrent_edits_merge() {
+    let laptop = ActorId::random();
+    let desktop = ActorId::random();
+
+    let mut original = genesis_document();
+    record_local_content(&mut original, &laptop, "one\ntwo\nthree\n", 1).unwrap();
+    let shared = original.save();
+
+    let mut on_desktop = merge_remote(None, &shared).unwrap();
+    assert_eq!(document_text(&on_desktop).unwrap(), "one\ntwo\nthree\n");
+
+    record_local_content(&mut original, &laptop, "zero\none\ntwo\nthree\n", 2).unwrap();
+    record_local_content(&mut on_desktop, &desktop, "one\ntwo\nthree\nfour\n", 2).unwrap();
+
+    let merged = merge_remote(Some(original), &on_desktop.save()).unwrap();
+    assert_eq!(
+        document_text(&merged).unwrap(),
+        "zero\none\ntwo\nthree\nfour\n"
+    );
+}
+
+#[test]
+fn test_independent_logs_share_genesis() {
+    let mut a = genesis_document();
+    let mut b = genesis_document();
+    assert_eq!(a.get_heads(), b.get_heads());
+
+    record_local_content(&mut a, &ActorId::random(), "from a", 1).unwrap();
+    let merged = merge_remote(Some(b.clone()), &a.save()).unwrap();
+    assert_eq!(document_text(&merged).unwrap(), "from a");
+    assert!(!record_local_content(&mut b, &ActorId::random(), "", 1).unwrap());
+}
+
+#[test]
+fn test_log_persistence_and_state() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let actor = device_actor(temp_dir.path()).unwrap();
+    assert_eq!(device_actor(temp_dir.path()).unwrap(), actor);
+
+    let path = crdt_log_path(temp_dir.path(), "folder/note.md");
+    assert!(load_document(&path).unwrap().is_none());
+
+    let mut doc = genesis_document();
+    record_local_content(&mut doc, &actor, "hello", 1).unwrap();
+    save_document(&path, &mut doc).unwrap();
+
+    let mut loaded = load_document(&path).unwrap().expect("log missing");
+    let state = crdt_state("folder/note.md", &mut loaded).unwrap();
+    assert_eq!(state.content, "hello");
+    assert_eq!(state.change_count, 2);
+    assert_eq!(state.heads.len(), 1);
+
+    assert!(merge_remote(None, &[]).is_err());
+    assert!(merge_remote(None, b"garbage").is_err());
+}
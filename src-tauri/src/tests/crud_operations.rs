@@ -5,8 +5,9 @@
 
 // Test wrappers imported from test_utils
 use crate::tests::test_utils::{
-    test_create_new_note, test_delete_note, test_get_note_content, test_get_note_html_content,
-    test_list_all_notes, test_rename_note, test_save_note_with_content_check, TestConfigOverride,
+    test_create_new_note, test_delete_note, test_delete_note_dry_run, test_duplicate_note,
+    test_get_note_content, test_get_note_html_content, test_list_all_notes, test_rename_note,
+    test_rename_note_updating_links, test_save_note_with_content_check, TestConfigOverride,
 };
 use serial_test::serial;
 use std::fs;
@@ -121,6 +122,42 @@ mod serial_tests {
         }
     }
 
+    #[test]
+    fn test_duplicate_note_suffixes_copies() {
+        let _test_config = TestConfigOverride::new().expect("Should create test config");
+        let notes_dir = _test_config.notes_dir();
+        fs::create_dir_all(notes_dir.join("work")).unwrap();
+        fs::write(notes_dir.join("work/plan.md"), "# Plan").unwrap();
+
+        let first = test_duplicate_note("work/plan.md", None).expect("Should duplicate note");
+        assert_eq!(first, "work/plan copy.md");
+        let second = test_duplicate_note("work/plan.md", None).expect("Should duplicate again");
+        assert_eq!(second, "work/plan copy 2.md");
+
+        assert_eq!(
+            fs::read_to_string(notes_dir.join(&second)).unwrap(),
+            "# Plan"
+        );
+        let notes_list = test_list_all_notes().expect("Should list notes");
+        assert!(notes_list.contains(&first));
+        assert!(notes_list.contains(&second));
+    }
+
+    #[test]
+    fn test_duplicate_note_to_given_name() {
+        let _test_config = TestConfigOverride::new().expect("Should create test config");
+        fs::write(_test_config.notes_dir().join("source.md"), "Body").unwrap();
+
+        let copy = test_duplicate_note("source.md", Some("target.md")).unwrap();
+        assert_eq!(copy, "target.md");
+        assert_eq!(test_get_note_content("target.md").unwrap(), "Body");
+
+        let taken = test_duplicate_note("source.md", Some("target.md"));
+        assert!(taken.unwrap_err().contains("already exists"));
+        assert!(test_duplicate_note("missing.md", None).is_err());
+        assert!(test_duplicate_note("source.md", Some("../escape.md")).is_err());
+    }
+
     #[test]
     fn test_get_note_content_success() {
         let _test_config = TestConfigOverride::new().expect("Should create test config");
@@ -305,6 +342,29 @@ mod serial_tests {
         assert!(old_result.is_err(), "Old name should not exist in database");
     }
 
+    #[test]
+    fn test_rename_note_rewrites_links() {
+        let _test_config = TestConfigOverride::new().expect("Should create test config");
+
+        test_create_new_note("plan.md").expect("Should create note");
+        test_create_new_note("index.md").expect("Should create note");
+        test_save_note_with_content_check("index.md", "See [[plan]] first", "")
+            .expect("Should save content");
+
+        test_rename_note_updating_links("plan.md".to_string(), "roadmap.md".to_string())
+            .expect("Should rename note and its links");
+
+        assert!(!_test_config.notes_dir().join("plan.md").exists());
+        assert!(_test_config.notes_dir().join("roadmap.md").exists());
+        let index = fs::read_to_string(_test_config.notes_dir().join("index.md")).unwrap();
+        assert_eq!(index, "See [[roadmap]] first");
+        assert_eq!(
+            test_get_note_content("index.md").unwrap(),
+            "See [[roadmap]] first",
+            "Database should hold the rewritten link"
+        );
+    }
+
     #[test]
     fn test_rename_note_to_existing_fails() {
         let _test_config = TestConfigOverride::new().expect("Should create test config");
@@ -408,6 +468,28 @@ mod serial_tests {
         );
     }
 
+    #[test]
+    fn test_delete_note_dry_run_changes_nothing() {
+        let _test_config = TestConfigOverride::new().expect("Should create test config");
+        use crate::utilities::dry_run::PlannedAction;
+
+        test_create_new_note("dry_run.md").expect("Should create note");
+        let note_path = _test_config.notes_dir().join("dry_run.md");
+
+        let report = test_delete_note_dry_run("dry_run.md").expect("Dry run should succeed");
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(report.changes[0].action, PlannedAction::Delete);
+        assert_eq!(report.changes[0].note_name, "dry_run.md");
+        assert_eq!(report.backups.len(), 1);
+        assert!(!std::path::Path::new(&report.backups[0]).exists());
+
+        assert!(note_path.exists(), "Dry run must not delete the file");
+        assert!(test_get_note_content("dry_run.md").is_ok());
+
+        let report = test_delete_note_dry_run("nonexistent.md").expect("Dry run should succeed");
+        assert!(report.changes.is_empty());
+    }
+
     #[test]
     fn test_delete_note_nonexistent() {
         let _test_config = TestConfigOverride::new().expect("Should create test config");
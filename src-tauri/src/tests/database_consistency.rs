@@ -7,7 +7,8 @@ use super::test_utils::database_testing::{
     check_database_integrity, quick_health_check, verify_sync_consistency,
 };
 use super::test_utils::DbTestHarness;
-use crate::services::database_service::init_db;
+use crate::services::database_service::{fts_tokenizer_changed, init_db};
+use crate::utilities::config_helpers::DEFAULT_FTS_TOKENIZER;
 use rusqlite::params;
 use std::collections::HashMap;
 
@@ -26,7 +27,7 @@ mod real_database_function_tests {
             .expect("Failed to get connection");
 
         // Test the ACTUAL production init_db function
-        let result = init_db(&conn);
+        let result = init_db(&conn, DEFAULT_FTS_TOKENIZER);
         assert!(result.is_ok(), "Production init_db should succeed");
 
         // Verify it created the correct schema by using the database
@@ -63,6 +64,53 @@ mod real_database_function_tests {
         );
     }
 
+    #[test]
+    fn test_init_db_rebuilds_notes_table_when_tokenizer_changes() {
+        let harness = DbTestHarness::new().expect("Failed to create test harness");
+        let conn = harness
+            .get_test_connection()
+            .expect("Failed to get connection");
+
+        init_db(&conn, DEFAULT_FTS_TOKENIZER).expect("Should initialize database");
+        conn.execute(
+            "INSERT INTO notes (filename, content, html_render, modified) VALUES (?1, ?2, '', 1)",
+            params!["tokyo.md", "東京タワーに行った"],
+        )
+        .expect("Should insert note");
+        assert!(!fts_tokenizer_changed(&conn, DEFAULT_FTS_TOKENIZER).unwrap());
+        assert!(fts_tokenizer_changed(&conn, "trigram").unwrap());
+
+        // Same tokenizer: existing rows are kept
+        init_db(&conn, DEFAULT_FTS_TOKENIZER).expect("Should reinitialize database");
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+
+        // New tokenizer: the table is recreated empty, ready to be reloaded
+        init_db(&conn, "trigram").expect("Should switch tokenizer");
+        assert!(!fts_tokenizer_changed(&conn, "trigram").unwrap());
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+
+        // Trigram matches inside CJK runs, which unicode61 treats as one token
+        conn.execute(
+            "INSERT INTO notes (filename, content, html_render, modified) VALUES (?1, ?2, '', 1)",
+            params!["tokyo.md", "東京タワーに行った"],
+        )
+        .unwrap();
+        let matches: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM notes WHERE notes MATCH ?1",
+                params!["タワー"],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(matches, 1);
+    }
+
     #[test]
     fn test_database_integrity_functions() {
         let harness = DbTestHarness::new().expect("Failed to create test harness");
@@ -71,7 +119,7 @@ mod real_database_function_tests {
             .expect("Failed to get connection");
 
         // Initialize with production function
-        init_db(&conn).expect("Should initialize database");
+        init_db(&conn, DEFAULT_FTS_TOKENIZER).expect("Should initialize database");
 
         // Test ACTUAL quick_health_check function on empty database
         assert!(
@@ -124,7 +172,7 @@ mod real_database_function_tests {
             .expect("Failed to get connection");
 
         // Initialize with production function
-        init_db(&conn).expect("Should initialize database");
+        init_db(&conn, DEFAULT_FTS_TOKENIZER).expect("Should initialize database");
 
         // Add data to database
         conn.execute(
@@ -196,7 +244,7 @@ mod real_database_function_tests {
             .expect("Failed to get connection");
 
         // Initialize with production function
-        init_db(&conn).expect("Should initialize database");
+        init_db(&conn, DEFAULT_FTS_TOKENIZER).expect("Should initialize database");
 
         // Test successful transaction using real database operations
         let tx = conn.transaction().expect("Should start transaction");
@@ -274,7 +322,7 @@ mod real_database_function_tests {
             .expect("Failed to get connection");
 
         // Initialize with ACTUAL production function
-        init_db(&conn).expect("Should initialize database");
+        init_db(&conn, DEFAULT_FTS_TOKENIZER).expect("Should initialize database");
 
         // Insert test data using real database operations
         let test_data = vec![
@@ -340,7 +388,7 @@ mod real_database_function_tests {
             .expect("Failed to get connection");
 
         // Initialize with production function
-        init_db(&conn).expect("Should initialize database");
+        init_db(&conn, DEFAULT_FTS_TOKENIZER).expect("Should initialize database");
 
         // Create large content (1MB)
         let large_content = "x".repeat(1024 * 1024);
@@ -404,7 +452,7 @@ mod real_database_function_tests {
             .expect("Failed to get connection");
 
         // Initialize with production function
-        init_db(&conn).expect("Should initialize database");
+        init_db(&conn, DEFAULT_FTS_TOKENIZER).expect("Should initialize database");
 
         // Insert normal data first
         conn.execute(
@@ -467,7 +515,7 @@ mod real_database_function_tests {
             .expect("Failed to get connection");
 
         // Initialize with production function
-        init_db(&conn).expect("Should initialize database");
+        init_db(&conn, DEFAULT_FTS_TOKENIZER).expect("Should initialize database");
 
         // Insert test data
         conn.execute(
@@ -496,7 +544,7 @@ mod real_database_function_tests {
 
         // Test recreate_database function behavior
         // Note: This tests the database recreation logic, not the full file sync
-        let recreate_result = init_db(&conn);
+        let recreate_result = init_db(&conn, DEFAULT_FTS_TOKENIZER);
         assert!(
             recreate_result.is_ok(),
             "Should be able to recreate database schema"
@@ -517,7 +565,7 @@ mod real_database_function_tests {
             .expect("Failed to get connection");
 
         // Initialize with production function
-        init_db(&conn).expect("Should initialize database");
+        init_db(&conn, DEFAULT_FTS_TOKENIZER).expect("Should initialize database");
 
         // Test that database operations handle expected error conditions
 
@@ -571,7 +619,7 @@ mod real_database_function_tests {
             .get_test_connection()
             .expect("Failed to get connection");
 
-        init_db(&conn).expect("Should initialize database");
+        init_db(&conn, DEFAULT_FTS_TOKENIZER).expect("Should initialize database");
 
         // Add file to database
         conn.execute(
@@ -0,0 +1,64 @@
+//! Date Parsing Unit Tests
+//!
+//! Tests for natural-language dates and date range bounds.
+
+use crate::utilities::dates::{parse_natural_date, DateRange};
+use chrono::NaiveDate;
+
+fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn test_relative_days_and_weekdays() {
+    // A Wednesday
+    let today = date("2024-06-05");
+    let parse = |text: &str| parse_natural_date(text, today).map(|d| d.to_string());
+
+    assert_eq!(parse("2024-01-31").as_deref(), Some("2024-01-31"));
+    assert_eq!(parse(" Today ").as_deref(), Some("2024-06-05"));
+    assert_eq!(parse("tomorrow").as_deref(), Some("2024-06-06"));
+    assert_eq!(parse("yesterday").as_deref(), Some("2024-06-04"));
+    assert_eq!(parse("friday").as_deref(), Some("2024-06-07"));
+    assert_eq!(parse("wednesday").as_deref(), Some("2024-06-05"));
+    assert_eq!(parse("next wednesday").as_deref(), Some("2024-06-12"));
+    assert_eq!(parse("next fri").as_deref(), Some("2024-06-07"));
+    assert_eq!(parse("last monday").as_deref(), Some("2024-06-03"));
+    assert_eq!(parse("last wednesday").as_deref(), Some("2024-05-29"));
+}
+
+#[test]
+fn test_offsets_and_month_names() {
+    let today = date("2024-01-31");
+    let parse = |text: &str| parse_natural_date(text, today).map(|d| d.to_string());
+
+    assert_eq!(parse("in 2 weeks").as_deref(), Some("2024-02-14"));
+    assert_eq!(parse("in a month").as_deref(), Some("2024-02-29"));
+    assert_eq!(parse("3 days ago").as_deref(), Some("2024-01-28"));
+    assert_eq!(parse("two years from now").as_deref(), Some("2026-01-31"));
+    assert_eq!(parse("next week").as_deref(), Some("2024-02-07"));
+    assert_eq!(parse("last year").as_deref(), Some("2023-01-31"));
+    assert_eq!(parse("June 5th").as_deref(), Some("2024-06-05"));
+    assert_eq!(parse("5 jun, 2025").as_deref(), Some("2025-06-05"));
+
+    assert_eq!(parse("someday"), None);
+    assert_eq!(parse("february 30"), None);
+    assert_eq!(parse("in 2 fortnights"), None);
+}
+
+#[test]
+fn test_date_range_accepts_natural_bounds() {
+    let range = DateRange {
+        from: Some("last monday".to_string()),
+        to: Some("today".to_string()),
+    };
+    let (from, to) = range.resolve(date("2024-06-05")).unwrap();
+    assert_eq!(from, Some(date("2024-06-03")));
+    assert_eq!(to, Some(date("2024-06-05")));
+
+    let invalid = DateRange {
+        from: Some("whenever".to_string()),
+        to: None,
+    };
+    assert!(invalid.resolve(date("2024-06-05")).is_err());
+}
@@ -0,0 +1,44 @@
+//! Dictionary Unit Tests
+//!
+//! Tests for personal spellcheck dictionary storage.
+
+use crate::services::dictionary_service::{add_word, load_words, merge_words, remove_word};
+use tempfile::TempDir;
+
+#[test]
+fn test_add_and_remove_words_persist_sorted_unique() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("nested").join("dictionary.txt");
+
+    assert!(load_words(&path).unwrap().is_empty());
+
+    add_word(&path, "symiosis").unwrap();
+    add_word(&path, "  Tauri ").unwrap();
+    let words = add_word(&path, "symiosis").unwrap();
+    assert_eq!(words, vec!["symiosis".to_string(), "Tauri".to_string()]);
+    assert_eq!(load_words(&path).unwrap(), words);
+
+    let words = remove_word(&path, "symiosis").unwrap();
+    assert_eq!(words, vec!["Tauri".to_string()]);
+    assert_eq!(load_words(&path).unwrap(), words);
+}
+
+#[test]
+fn test_add_word_rejects_invalid_input() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("dictionary.txt");
+
+    assert!(add_word(&path, "   ").is_err());
+    assert!(add_word(&path, "two words").is_err());
+    assert!(add_word(&path, &"a".repeat(101)).is_err());
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_merge_words_deduplicates_across_dictionaries() {
+    let merged = merge_words(&[
+        vec!["beta".to_string(), "Alpha".to_string()],
+        vec!["alpha".to_string(), "beta".to_string()],
+    ]);
+    assert_eq!(merged, vec!["Alpha", "alpha", "beta"]);
+}
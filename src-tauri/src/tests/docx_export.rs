@@ -0,0 +1,71 @@
+//! DOCX Export Unit Tests
+//!
+//! Tests for converting markdown notes into Word documents.
+
+use crate::utilities::docx_export::build_docx;
+use std::fs;
+use std::io::{Cursor, Read};
+use tempfile::TempDir;
+
+fn read_bytes(docx: &[u8], name: &str) -> Vec<u8> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(docx)).expect("Should be a valid zip");
+    let mut part = archive.by_name(name).expect("Part should exist");
+    let mut content = Vec::new();
+    part.read_to_end(&mut content).unwrap();
+    content
+}
+
+fn read_part(docx: &[u8], name: &str) -> String {
+    String::from_utf8(read_bytes(docx, name)).expect("Part should be UTF-8")
+}
+
+#[test]
+fn test_build_docx_structure() {
+    let markdown = "---\ntitle: Hidden\n---\n# Title\n\nSome **bold** & *italic* text[^1].\n\n1. first\n2. second\n\n| A | B |\n|:--|--:|\n| 1 | 2 |\n\n[^1]: The footnote.\n";
+    let docx = build_docx(markdown, "My <Note>", None, None).unwrap();
+
+    let document = read_part(&docx, "word/document.xml");
+    assert!(document.contains(r#"<w:pStyle w:val="Heading1"/>"#));
+    assert!(document.contains("<w:b/>"));
+    assert!(document.contains("bold"));
+    assert!(document.contains(" &amp; "));
+    assert!(document.contains(r#"<w:footnoteReference w:id="1"/>"#));
+    assert!(document.contains(r#"<w:numId w:val="2"/>"#));
+    assert!(document.contains("<w:tbl>"));
+    assert!(document.contains(r#"<w:jc w:val="right"/>"#));
+    assert!(!document.contains("Hidden"));
+    assert!(!document.contains("The footnote."));
+
+    let footnotes = read_part(&docx, "word/footnotes.xml");
+    assert!(footnotes.contains(r#"<w:footnote w:id="1">"#));
+    assert!(footnotes.contains("The footnote."));
+
+    let core = read_part(&docx, "docProps/core.xml");
+    assert!(core.contains("<dc:title>My &lt;Note&gt;</dc:title>"));
+}
+
+#[test]
+fn test_build_docx_embeds_local_images_only_inside_notes_root() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let notes_root = temp_dir.path().join("notes");
+    fs::create_dir_all(notes_root.join("img")).unwrap();
+
+    // Minimal PNG header: signature + IHDR declaring a 2x3 image
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    png.extend_from_slice(&2u32.to_be_bytes());
+    png.extend_from_slice(&3u32.to_be_bytes());
+    fs::write(notes_root.join("img").join("pic.png"), &png).unwrap();
+    fs::write(temp_dir.path().join("outside.png"), &png).unwrap();
+
+    let markdown = "![diagram](img/pic.png)\n\n![secret](../outside.png)\n";
+    let docx = build_docx(markdown, "t", Some(&notes_root), Some(&notes_root)).unwrap();
+
+    let document = read_part(&docx, "word/document.xml");
+    assert_eq!(document.matches("<w:drawing>").count(), 1);
+    assert!(document.contains(r#"cx="19050" cy="28575""#));
+    assert!(document.contains("[secret]"));
+    assert_eq!(read_bytes(&docx, "word/media/image1.png"), png);
+
+    let rels = read_part(&docx, "word/_rels/document.xml.rels");
+    assert!(rels.contains(r#"Target="media/image1.png""#));
+}
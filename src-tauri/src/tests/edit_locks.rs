@@ -0,0 +1,55 @@
+//! Edit Lock Unit Tests
+//!
+//! Tests for the advisory locks keeping windows from overwriting each other's
+//! edits.
+
+use crate::core::{edit_locks::EditLocks, AppError};
+
+#[test]
+fn test_second_window_is_refused_the_lock() {
+    let locks = EditLocks::new();
+    assert!(locks.acquire("work.md", "main").unwrap());
+    assert!(!locks.acquire("work.md", "main").unwrap());
+    assert!(matches!(
+        locks.acquire("work.md", "note-2"),
+        Err(AppError::EditLocked(_))
+    ));
+    assert_eq!(locks.holder("work.md").as_deref(), Some("main"));
+    assert!(locks.acquire("other.md", "note-2").unwrap());
+}
+
+#[test]
+fn test_saves_refused_for_other_windows() {
+    let locks = EditLocks::new();
+    assert!(locks.ensure_can_save("work.md", "note-2").is_ok());
+
+    locks.acquire("work.md", "main").unwrap();
+    assert!(locks.ensure_can_save("work.md", "main").is_ok());
+    assert!(matches!(
+        locks.ensure_can_save("work.md", "note-2"),
+        Err(AppError::EditLocked(_))
+    ));
+}
+
+#[test]
+fn test_only_the_holder_releases_a_lock() {
+    let locks = EditLocks::new();
+    locks.acquire("work.md", "main").unwrap();
+
+    assert!(!locks.release("work.md", "note-2"));
+    assert!(locks.release("work.md", "main"));
+    assert!(!locks.release("work.md", "main"));
+    assert!(locks.acquire("work.md", "note-2").unwrap());
+}
+
+#[test]
+fn test_closed_window_releases_its_locks() {
+    let locks = EditLocks::new();
+    locks.acquire("b.md", "note-2").unwrap();
+    locks.acquire("a.md", "note-2").unwrap();
+    locks.acquire("c.md", "main").unwrap();
+
+    assert_eq!(locks.release_window("note-2"), vec!["a.md", "b.md"]);
+    assert_eq!(locks.holder("a.md"), None);
+    assert_eq!(locks.holder("c.md").as_deref(), Some("main"));
+}
@@ -0,0 +1,86 @@
+//! Email Unit Tests
+//!
+//! Tests for building note emails and checking the SMTP settings. Nothing is
+//! sent and the keychain is not touched.
+
+use crate::config::EmailConfig;
+use crate::services::email_service::{
+    build_note_email, ensure_email_configured, parse_recipients, sender_address, EmailFormat,
+    MAX_RECIPIENTS,
+};
+
+const NOTE: &str = "---\ntitle: Weekly sync\n---\n# Agenda\n\n- **Budget**\n";
+
+fn formatted(format: EmailFormat) -> String {
+    let message = build_note_email(
+        "me@example.com",
+        &["a@example.com".to_string(), " b@example.com ".to_string()],
+        "meetings/sync.md",
+        NOTE,
+        format,
+    )
+    .unwrap();
+    String::from_utf8(message.formatted()).unwrap()
+}
+
+#[test]
+fn test_email_format_parse() {
+    assert_eq!(
+        EmailFormat::parse("Markdown").unwrap(),
+        EmailFormat::Markdown
+    );
+    assert_eq!(EmailFormat::parse("md").unwrap(), EmailFormat::Markdown);
+    assert_eq!(EmailFormat::parse(" html ").unwrap(), EmailFormat::Html);
+    assert!(EmailFormat::parse("pdf").is_err());
+}
+
+#[test]
+fn test_markdown_email() {
+    let email = formatted(EmailFormat::Markdown);
+    assert!(email.contains("Subject: Weekly sync"));
+    assert!(email.contains("To: a@example.com, b@example.com"));
+    assert!(email.contains("Content-Type: text/plain"));
+    assert!(email.contains("- **Budget**"));
+    assert!(!email.contains("title: Weekly sync"));
+}
+
+#[test]
+fn test_html_email_has_plain_alternative() {
+    let email = formatted(EmailFormat::Html);
+    assert!(email.contains("multipart/alternative"));
+    assert!(email.contains("Content-Type: text/plain"));
+    assert!(email.contains("Content-Type: text/html"));
+    assert!(email.contains("<strong>Budget</strong>"));
+}
+
+#[test]
+fn test_parse_recipients() {
+    assert!(parse_recipients(&[]).is_err());
+    assert!(parse_recipients(&[" ".to_string()]).is_err());
+    assert!(parse_recipients(&["not an address".to_string()]).is_err());
+    assert_eq!(
+        parse_recipients(&["Ana <ana@example.com>".to_string()])
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let too_many = vec!["x@example.com".to_string(); MAX_RECIPIENTS + 1];
+    assert!(parse_recipients(&too_many).is_err());
+}
+
+#[test]
+fn test_email_configuration() {
+    let mut config = EmailConfig::default();
+    assert!(ensure_email_configured(&config).is_err());
+
+    config.smtp_host = "smtp.example.com".to_string();
+    assert!(ensure_email_configured(&config).is_err());
+
+    config.username = "me@example.com".to_string();
+    assert!(ensure_email_configured(&config).is_ok());
+    assert_eq!(sender_address(&config), "me@example.com");
+
+    config.from_address = "notes@example.com".to_string();
+    assert_eq!(sender_address(&config), "notes@example.com");
+}
@@ -0,0 +1,38 @@
+//! Event Payload Unit Tests
+//!
+//! Tests for the JSON shape of events emitted to the frontend.
+
+use crate::core::events::{AppEvent, CacheRefreshed, DbLoadingComplete, DbLoadingProgress};
+use serde_json::json;
+
+#[test]
+fn test_loading_progress_fraction() {
+    let progress = DbLoadingProgress::counted(5, 20);
+    assert_eq!(progress.message, "Loading 5 of 20 notes...");
+    assert_eq!(progress.fraction, Some(0.25));
+
+    assert_eq!(DbLoadingProgress::counted(0, 0).fraction, Some(1.0));
+    assert_eq!(
+        serde_json::to_value(DbLoadingProgress::new("Rendering notes...")).unwrap(),
+        json!({ "message": "Rendering notes...", "fraction": null })
+    );
+}
+
+#[test]
+fn test_event_names_and_payloads() {
+    assert_eq!(CacheRefreshed::NAME, "cache-refreshed");
+    assert_eq!(DbLoadingProgress::NAME, "db-loading-progress");
+
+    let refreshed = CacheRefreshed {
+        changed: vec!["a.md".to_string()],
+        removed: vec!["b.md".to_string()],
+    };
+    assert_eq!(
+        serde_json::to_value(refreshed).unwrap(),
+        json!({ "changed": ["a.md"], "removed": ["b.md"] })
+    );
+    assert_eq!(
+        serde_json::to_value(DbLoadingComplete).unwrap(),
+        serde_json::Value::Null
+    );
+}
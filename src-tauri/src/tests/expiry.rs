@@ -0,0 +1,89 @@
+//! Note Expiry Unit Tests
+//!
+//! Tests for front-matter `expires` dates and listing expiring notes.
+
+use crate::core::AppError;
+use crate::services::expiry_service::{
+    expiring_notes, mark_expired_handled, newly_expired_notes, parse_expiry_date,
+    MAX_EXPIRY_WARNING_DAYS,
+};
+use crate::tests::test_utils::notes_db;
+use chrono::NaiveDate;
+use rusqlite::params;
+
+fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+}
+
+fn names(notes: &[crate::services::expiry_service::ExpiringNote]) -> Vec<&str> {
+    notes.iter().map(|note| note.note_name.as_str()).collect()
+}
+
+#[test]
+fn test_parse_expiry_date() {
+    assert_eq!(parse_expiry_date("2024-12-31"), Some(date("2024-12-31")));
+    assert_eq!(
+        parse_expiry_date(" 2024-12-31 12:00 "),
+        Some(date("2024-12-31"))
+    );
+    assert_eq!(parse_expiry_date("next week"), None);
+}
+
+#[test]
+fn test_expiring_notes_within_days() {
+    let conn = notes_db(&[
+        ("ticket.md", "---\nexpires: 2024-06-10\n---\nTicket"),
+        ("token.md", "---\nexpires: 2024-06-01\n---\nToken"),
+        ("later.md", "---\nexpires: 2024-09-01\n---\nLater"),
+        (
+            "old.md",
+            "---\nexpires: 2024-01-01\narchived: true\n---\nOld",
+        ),
+        ("Archive/moved.md", "---\nexpires: 2024-01-01\n---\nMoved"),
+        ("plain.md", "No expiry"),
+        ("bad.md", "---\nexpires: someday\n---\n"),
+    ]);
+
+    let notes = expiring_notes(&conn, date("2024-06-01"), 14).unwrap();
+    assert_eq!(names(&notes), vec!["token.md", "ticket.md"]);
+    assert_eq!((notes[0].days_left, notes[0].expired), (0, true));
+    assert_eq!((notes[1].days_left, notes[1].expired), (9, false));
+    assert_eq!(notes[1].expires, "2024-06-10");
+    assert_eq!(notes[1].title, "ticket");
+
+    let notes = expiring_notes(&conn, date("2024-06-01"), 0).unwrap();
+    assert_eq!(names(&notes), vec!["token.md"]);
+}
+
+#[test]
+fn test_expiring_notes_limits_days() {
+    let conn = notes_db::<&str>(&[]);
+    assert!(matches!(
+        expiring_notes(&conn, date("2024-06-01"), MAX_EXPIRY_WARNING_DAYS + 1),
+        Err(AppError::InvalidNoteName(_))
+    ));
+}
+
+#[test]
+fn test_newly_expired_notes_handled_once_per_date() {
+    let conn = notes_db(&[
+        ("token.md", "---\nexpires: 2024-06-01\n---\nToken"),
+        ("ticket.md", "---\nexpires: 2024-06-10\n---\nTicket"),
+    ]);
+    let today = date("2024-06-02");
+
+    let expired = newly_expired_notes(&conn, today).unwrap();
+    assert_eq!(names(&expired), vec!["token.md"]);
+    mark_expired_handled(&conn, &expired, 100).unwrap();
+    assert!(newly_expired_notes(&conn, today).unwrap().is_empty());
+
+    // Extending the note flags it again once the new date passes
+    conn.execute(
+        "UPDATE notes SET content = ?1, modified = 2 WHERE filename = 'token.md'",
+        params!["---\nexpires: 2024-06-05\n---\nToken"],
+    )
+    .unwrap();
+    assert!(newly_expired_notes(&conn, today).unwrap().is_empty());
+    let expired = newly_expired_notes(&conn, date("2024-06-05")).unwrap();
+    assert_eq!(names(&expired), vec!["token.md"]);
+}
@@ -0,0 +1,110 @@
+//! Export Template Unit Tests
+//!
+//! Tests for Handlebars export templates: naming, context and rendering.
+
+use crate::services::export_template_service::{
+    export_template_context, export_template_path, list_export_templates, render_export_template,
+    template_output_extension,
+};
+use chrono::{Local, TimeZone};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const NOTE: &str = "---\ntitle: Q3 \"Plan\"\nstatus: active\nowners: [ana, bo]\n---\n# Goals\n\nShip it & celebrate #work\n";
+
+fn context() -> crate::services::export_template_service::ExportTemplateContext {
+    let now = Local.with_ymd_and_hms(2024, 6, 1, 9, 30, 0).unwrap();
+    export_template_context("projects/plan.md", NOTE, now.timestamp(), now)
+}
+
+#[test]
+fn test_context_has_content_and_metadata() {
+    let context = context();
+
+    assert_eq!(context.title, "Q3 \"Plan\"");
+    assert_eq!(context.folder, "projects");
+    assert_eq!(context.body, "# Goals\n\nShip it & celebrate #work\n");
+    assert!(context.html.starts_with("<h1>Goals</h1>"));
+    assert_eq!(context.tags, vec!["work"]);
+    assert_eq!(context.word_count, 5);
+    assert_eq!(context.modified, context.exported_at);
+    assert!(context.exported_at.starts_with("2024-06-01T09:30:00"));
+}
+
+#[test]
+fn test_escaping_depends_on_output() {
+    let context = context();
+    let template =
+        "{{title}} | {{frontmatter.status}} | {{#each frontmatter.owners}}{{this}};{{/each}}";
+
+    assert_eq!(
+        render_export_template(template, "md", &context).unwrap(),
+        "Q3 \"Plan\" | active | ana;bo;"
+    );
+    assert_eq!(
+        render_export_template(template, "html", &context).unwrap(),
+        "Q3 &quot;Plan&quot; | active | ana;bo;"
+    );
+    assert!(
+        render_export_template("<main>{{{html}}}</main>", "html", &context)
+            .unwrap()
+            .starts_with("<main><h1>Goals</h1>")
+    );
+}
+
+#[test]
+fn test_csv_helper_quotes_fields() {
+    let context = context();
+    let row = render_export_template(
+        "{{csv note_name}},{{csv title}},{{csv frontmatter.owners}},{{csv word_count}},{{csv frontmatter.missing}}",
+        "csv",
+        &context,
+    )
+    .unwrap();
+    assert_eq!(row, "projects/plan.md,\"Q3 \"\"Plan\"\"\",\"ana, bo\",5,");
+}
+
+#[test]
+fn test_invalid_template_is_an_error() {
+    assert!(render_export_template("{{#each tags}}", "txt", &context()).is_err());
+}
+
+#[test]
+fn test_template_names_and_extensions() {
+    let dir = TempDir::new().unwrap();
+    assert!(list_export_templates(&dir.path().join("missing"))
+        .unwrap()
+        .is_empty());
+
+    for name in [
+        "row.csv.hbs",
+        "Letterhead.html.hbs",
+        "plain.hbs",
+        "notes.md",
+        ".hidden.hbs",
+    ] {
+        fs::write(dir.path().join(name), "").unwrap();
+    }
+    assert_eq!(
+        list_export_templates(dir.path()).unwrap(),
+        vec!["Letterhead.html.hbs", "plain.hbs", "row.csv.hbs"]
+    );
+
+    let path = export_template_path(dir.path(), "row.csv").unwrap();
+    assert_eq!(path, dir.path().join("row.csv.hbs"));
+    assert_eq!(template_output_extension(&path), "csv");
+    assert_eq!(
+        template_output_extension(Path::new("Letterhead.HTML.hbs")),
+        "html"
+    );
+    assert_eq!(template_output_extension(Path::new("plain.hbs")), "txt");
+
+    for name in ["", "../row.csv", "sub/row", ".hidden"] {
+        assert!(
+            export_template_path(dir.path(), name).is_err(),
+            "{:?}",
+            name
+        );
+    }
+}
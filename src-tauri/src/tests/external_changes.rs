@@ -0,0 +1,109 @@
+//! External Change Review Unit Tests
+//!
+//! Tests for the review queue of notes changed outside the app and the line
+//! diff shown for each entry.
+
+use crate::services::external_change_service::{
+    acknowledge_external_change, load_external_changes, record_external_change,
+    review_external_change, ExternalChangeKind,
+};
+use crate::utilities::line_diff::unified_diff;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_unified_diff_hunks() {
+    assert_eq!(unified_diff("a\nb\n", "a\nb\n", 3), "");
+    assert_eq!(
+        unified_diff("one\ntwo\nthree\n", "one\n2\nthree\n", 1),
+        "@@ -1,3 +1,3 @@\n one\n-two\n+2\n three\n"
+    );
+    assert_eq!(unified_diff("", "new\n", 3), "@@ -0,0 +1,1 @@\n+new\n");
+
+    let old: String = (1..=20).map(|n| format!("{}\n", n)).collect();
+    let new = old
+        .replacen("2\n", "two\n", 1)
+        .replace("19\n", "nineteen\n");
+    let diff = unified_diff(&old, &new, 1);
+    assert_eq!(diff.matches("@@ -").count(), 2);
+    assert!(diff.starts_with("@@ -1,3 +1,3 @@\n 1\n-2\n+two\n 3\n"));
+    assert!(diff.ends_with("@@ -18,3 +18,3 @@\n 18\n-19\n+nineteen\n 20\n"));
+}
+
+#[test]
+fn test_record_and_acknowledge() {
+    let dir = TempDir::new().unwrap();
+    let store = dir.path().join("changes.json");
+    assert!(load_external_changes(&store).unwrap().is_empty());
+
+    let first_backup = dir.path().join("first.bak");
+    record_external_change(
+        &store,
+        "a.md",
+        ExternalChangeKind::Modified,
+        Some(&first_backup),
+        10,
+    )
+    .unwrap();
+    record_external_change(
+        &store,
+        "a.md",
+        ExternalChangeKind::Modified,
+        Some(&dir.path().join("second.bak")),
+        20,
+    )
+    .unwrap();
+    record_external_change(&store, "b.md", ExternalChangeKind::Created, None, 30).unwrap();
+
+    let changes = load_external_changes(&store).unwrap();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].detected_at, 20);
+    assert_eq!(
+        changes[0].backup_path.as_deref(),
+        Some(first_backup.as_path())
+    );
+
+    assert!(acknowledge_external_change(&store, "a.md").unwrap());
+    assert!(!acknowledge_external_change(&store, "a.md").unwrap());
+    assert_eq!(load_external_changes(&store).unwrap()[0].note_name, "b.md");
+}
+
+#[test]
+fn test_created_then_deleted_leaves_nothing_to_review() {
+    let dir = TempDir::new().unwrap();
+    let store = dir.path().join("changes.json");
+
+    record_external_change(&store, "tmp.md", ExternalChangeKind::Created, None, 1).unwrap();
+    record_external_change(&store, "tmp.md", ExternalChangeKind::Deleted, None, 2).unwrap();
+    assert!(load_external_changes(&store).unwrap().is_empty());
+}
+
+#[test]
+fn test_review_diffs_against_backup() {
+    let dir = TempDir::new().unwrap();
+    let store = dir.path().join("changes.json");
+    let notes_dir = dir.path().join("notes");
+    fs::create_dir_all(&notes_dir).unwrap();
+    fs::write(notes_dir.join("a.md"), "synced\n").unwrap();
+    let backup = dir.path().join("a.md.bak");
+    fs::write(&backup, "local\n").unwrap();
+
+    record_external_change(
+        &store,
+        "a.md",
+        ExternalChangeKind::Modified,
+        Some(&backup),
+        5,
+    )
+    .unwrap();
+    record_external_change(&store, "gone.md", ExternalChangeKind::Deleted, None, 6).unwrap();
+
+    let changes = load_external_changes(&store).unwrap();
+    let modified = review_external_change(&changes[0], &notes_dir);
+    assert_eq!(
+        modified.diff.as_deref(),
+        Some("@@ -1,1 +1,1 @@\n-local\n+synced\n")
+    );
+    // Without a backup there is nothing to diff a deletion against
+    assert_eq!(review_external_change(&changes[1], &notes_dir).diff, None);
+}
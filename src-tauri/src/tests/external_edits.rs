@@ -0,0 +1,29 @@
+//! External Edit Unit Tests
+//!
+//! Tests for refusing in-app saves of notes open in an external editor.
+
+use crate::core::{external_edits::ExternalEdits, AppError};
+
+#[test]
+fn test_saves_refused_during_external_edit() {
+    let edits = ExternalEdits::new();
+    assert!(edits.ensure_not_active("work.md").is_ok());
+
+    edits.begin("work.md");
+    assert!(edits.is_active("work.md"));
+    assert!(matches!(
+        edits.ensure_not_active("work.md"),
+        Err(AppError::ExternallyEdited(_))
+    ));
+    assert!(edits.ensure_not_active("other.md").is_ok());
+}
+
+#[test]
+fn test_ending_external_edit_allows_saves_again() {
+    let edits = ExternalEdits::new();
+    edits.begin("work.md");
+
+    assert!(edits.end("work.md"));
+    assert!(!edits.end("work.md"));
+    assert!(edits.ensure_not_active("work.md").is_ok());
+}
@@ -0,0 +1,81 @@
+//! Folder Unit Tests
+//!
+//! Tests for validating folder names and working out where notes end up when
+//! folders are renamed, notes are moved or new notes are created.
+
+use crate::core::AppError;
+use crate::services::folder_service::{
+    is_in_folder, new_note_folder, normalize_folder_name, note_in_folder, note_under_folder,
+    renamed_note_name,
+};
+use crate::utilities::strings::untitled_note_name;
+
+#[test]
+fn test_normalize_folder_name() {
+    assert_eq!(
+        normalize_folder_name(" /Projects/2024/ ").unwrap(),
+        "Projects/2024"
+    );
+    assert_eq!(normalize_folder_name("a / b").unwrap(), "a/b");
+    assert!(normalize_folder_name("  / ").is_err());
+    assert!(matches!(
+        normalize_folder_name("a/../b"),
+        Err(AppError::PathTraversal)
+    ));
+    assert!(normalize_folder_name("a//b").is_err());
+    assert!(normalize_folder_name(".trash").is_err());
+    assert!(normalize_folder_name("a/.hidden").is_err());
+    assert!(normalize_folder_name("a\\b").is_err());
+}
+
+#[test]
+fn test_renamed_note_name_keeps_subfolders() {
+    assert_eq!(
+        renamed_note_name("Work/plans/q1.md", "Work", "Archive/Work"),
+        Some("Archive/Work/plans/q1.md".to_string())
+    );
+    assert_eq!(renamed_note_name("Workshop/q1.md", "Work", "Jobs"), None);
+    assert_eq!(renamed_note_name("Work", "Work", "Jobs"), None);
+    assert!(is_in_folder("a/b/c.md", "a/b"));
+    assert!(!is_in_folder("a/bc.md", "a/b"));
+}
+
+#[test]
+fn test_note_in_folder() {
+    assert_eq!(
+        note_in_folder("inbox/idea.md", Some("Projects")),
+        "Projects/idea.md"
+    );
+    assert_eq!(note_in_folder("inbox/idea.md", None), "idea.md");
+    assert_eq!(note_in_folder("idea.md", Some("a/b")), "a/b/idea.md");
+}
+
+#[test]
+fn test_new_note_folder_follows_location() {
+    assert_eq!(
+        new_note_folder("inbox", "/Inbox/Later/", Some("work/plan.md")),
+        Some("Inbox/Later".to_string())
+    );
+    assert_eq!(new_note_folder("inbox", "../out", None), None);
+    assert_eq!(
+        new_note_folder("same_folder", "Inbox", Some("work/2024/plan.md")),
+        Some("work/2024".to_string())
+    );
+    assert_eq!(
+        new_note_folder("same_folder", "Inbox", Some("plan.md")),
+        None
+    );
+    assert_eq!(new_note_folder("same_folder", "Inbox", None), None);
+    assert_eq!(new_note_folder("root", "Inbox", Some("work/plan.md")), None);
+}
+
+#[test]
+fn test_new_note_names() {
+    assert_eq!(
+        note_under_folder("Reading/Page.md", Some("Inbox")),
+        "Inbox/Reading/Page.md"
+    );
+    assert_eq!(note_under_folder("Untitled.md", None), "Untitled.md");
+    assert_eq!(untitled_note_name(1), "Untitled.md");
+    assert_eq!(untitled_note_name(3), "Untitled 3.md");
+}
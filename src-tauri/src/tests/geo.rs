@@ -0,0 +1,146 @@
+//! Geo Unit Tests
+//!
+//! Tests for `location:` parsing, the locations index, nearby-note queries and
+//! map export. No geocoder is contacted.
+
+use crate::services::location_service::{
+    exportable_locations, locations_geojson, notes_near, parse_nominatim_result, pending_places,
+    store_geocode,
+};
+use crate::tests::test_utils::notes_db;
+use crate::utilities::geo::{
+    distance_km, extract_locations, normalize_place, parse_coordinates, Coordinates, NoteLocation,
+};
+
+fn coordinates(lat: f64, lon: f64) -> Coordinates {
+    Coordinates::new(lat, lon).unwrap()
+}
+
+#[test]
+fn test_extract_locations() {
+    assert_eq!(
+        extract_locations("---\nlocation: 48.8566, 2.3522\n---\nParis"),
+        vec![NoteLocation::Coordinates(coordinates(48.8566, 2.3522))]
+    );
+    assert_eq!(
+        extract_locations("---\nlocation: [38.72, -9.14]\n---\n"),
+        vec![NoteLocation::Coordinates(coordinates(38.72, -9.14))]
+    );
+    assert_eq!(
+        extract_locations("---\nlocation:\n  - Lisbon\n  - 41.15, -8.61\n  - Lisbon\n---\n"),
+        vec![
+            NoteLocation::Place("Lisbon".to_string()),
+            NoteLocation::Coordinates(coordinates(41.15, -8.61)),
+        ]
+    );
+    assert!(extract_locations("No front matter").is_empty());
+
+    assert!(parse_coordinates("91, 0").is_none());
+    assert!(parse_coordinates("NaN, 0").is_none());
+    assert_eq!(normalize_place("  New   York "), "new york");
+}
+
+#[test]
+fn test_distance_km() {
+    let paris = coordinates(48.8566, 2.3522);
+    let london = coordinates(51.5074, -0.1278);
+    let distance = distance_km(paris, london);
+    assert!((distance - 343.5).abs() < 2.0, "got {}", distance);
+    assert_eq!(distance_km(paris, paris), 0.0);
+}
+
+#[test]
+fn test_notes_near() {
+    let conn = notes_db(&[
+        ("paris.md", "---\nlocation: 48.8566, 2.3522\n---\n"),
+        ("versailles.md", "---\nlocation: 48.8049, 2.1204\n---\n"),
+        (
+            "trip.md",
+            "---\nlocation:\n  - 51.5074, -0.1278\n  - 48.86, 2.35\n---\n",
+        ),
+        ("london.md", "---\nlocation: 51.5074, -0.1278\n---\n"),
+        ("plain.md", "No location"),
+    ]);
+
+    let near = notes_near(&conn, coordinates(48.8566, 2.3522), 50.0, 10).unwrap();
+    let filenames: Vec<&str> = near.iter().map(|n| n.filename.as_str()).collect();
+    assert_eq!(filenames, vec!["paris.md", "trip.md", "versailles.md"]);
+    // trip.md is listed once, at its Paris location
+    assert!(near[1].distance_km < 1.0);
+
+    let limited = notes_near(&conn, coordinates(48.8566, 2.3522), 50.0, 1).unwrap();
+    assert_eq!(limited.len(), 1);
+}
+
+#[test]
+fn test_geocoded_places() {
+    let conn = notes_db(&[
+        ("a.md", "---\nlocation: Lisbon\n---\n"),
+        ("b.md", "---\nlocation: lisbon\n---\n"),
+        ("c.md", "---\nlocation: Atlantis\n---\n"),
+    ]);
+
+    assert_eq!(pending_places(&conn, 10).unwrap().len(), 2);
+    assert!(notes_near(&conn, coordinates(38.72, -9.14), 10.0, 10)
+        .unwrap()
+        .is_empty());
+
+    store_geocode(&conn, "Lisbon", Some(coordinates(38.7223, -9.1393))).unwrap();
+    store_geocode(&conn, "Atlantis", None).unwrap();
+    assert!(pending_places(&conn, 10).unwrap().is_empty());
+
+    let near = notes_near(&conn, coordinates(38.72, -9.14), 10.0, 10).unwrap();
+    let filenames: Vec<&str> = near.iter().map(|n| n.filename.as_str()).collect();
+    assert_eq!(filenames, vec!["a.md", "b.md"]);
+    assert_eq!(near[0].place.as_deref(), Some("Lisbon"));
+
+    // Notes indexed later pick up the cached result
+    conn.execute(
+        "INSERT INTO notes (filename, content, html_render, modified, is_indexed) VALUES ('d.md', '---\nlocation: LISBON\n---\n', '', 0, 1)",
+        [],
+    )
+    .unwrap();
+    assert_eq!(
+        notes_near(&conn, coordinates(38.72, -9.14), 10.0, 10)
+            .unwrap()
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn test_locations_geojson_skips_private_notes() {
+    let conn = notes_db(&[
+        (
+            "trip.md",
+            "---\ntitle: Road trip\nlocation: 40.4168, -3.7038\n---\n",
+        ),
+        (
+            "secret.md",
+            "---\nvisibility: private\nlocation: 40.0, -3.0\n---\n",
+        ),
+    ]);
+
+    let located = exportable_locations(&conn).unwrap();
+    assert_eq!(located.len(), 1);
+
+    let geojson = locations_geojson(&located);
+    assert_eq!(geojson["type"], "FeatureCollection");
+    let feature = &geojson["features"][0];
+    assert_eq!(
+        feature["geometry"]["coordinates"],
+        serde_json::json!([-3.7038, 40.4168])
+    );
+    assert_eq!(feature["properties"]["title"], "Road trip");
+    assert_eq!(feature["properties"]["place"], serde_json::Value::Null);
+}
+
+#[test]
+fn test_parse_nominatim_result() {
+    let results = serde_json::json!([{"lat": "38.7077507", "lon": "-9.1365919", "name": "Lisboa"}]);
+    assert_eq!(
+        parse_nominatim_result(&results),
+        Some(coordinates(38.7077507, -9.1365919))
+    );
+    assert_eq!(parse_nominatim_result(&serde_json::json!([])), None);
+}
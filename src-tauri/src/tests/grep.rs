@@ -0,0 +1,98 @@
+//! Grep Unit Tests
+//!
+//! Tests for regex scans over note content.
+
+use crate::services::grep_service::{
+    build_grep_regex, grep_content, grep_notes, GrepFlags, MAX_MATCHES_PER_NOTE,
+};
+use crate::tests::test_utils::notes_db;
+
+#[test]
+fn test_grep_finds_symbols_fts_cannot() {
+    let conn = notes_db(&[
+        ("b.md", "# B\nTODO(ana): review\nnothing\nTODO(bo) later"),
+        ("a.md", "fn main() {}\n// TODO(cy)"),
+        ("c.md", "TODO without owner"),
+    ]);
+    let regex = build_grep_regex(r"TODO\(\w+\)", GrepFlags::default()).unwrap();
+    let results = grep_notes(&conn, &regex, 10).unwrap();
+
+    let filenames: Vec<&str> = results.iter().map(|r| r.filename.as_str()).collect();
+    assert_eq!(filenames, vec!["a.md", "b.md"]);
+
+    let b = &results[1];
+    let lines: Vec<usize> = b.matches.iter().map(|m| m.line_number).collect();
+    assert_eq!(lines, vec![2, 4]);
+    assert_eq!(b.matches[0].line, "TODO(ana): review");
+    assert_eq!(b.matches[0].ranges, vec![(0, 9)]);
+    assert!(!b.truncated);
+}
+
+#[test]
+fn test_grep_flags() {
+    let content = "Rust and rusty\nRUST";
+
+    let plain = build_grep_regex("rust", GrepFlags::default()).unwrap();
+    let matches = grep_content(&plain, "n.md", content).unwrap().matches;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].ranges, vec![(9, 13)]);
+
+    let insensitive = build_grep_regex(
+        "rust",
+        GrepFlags {
+            case_insensitive: true,
+            whole_word: true,
+            ..GrepFlags::default()
+        },
+    )
+    .unwrap();
+    let matches = grep_content(&insensitive, "n.md", content).unwrap().matches;
+    assert_eq!(matches.len(), 2);
+    // `rusty` is not a whole-word match
+    assert_eq!(matches[0].ranges, vec![(0, 4)]);
+
+    let literal = build_grep_regex(
+        "a.b(",
+        GrepFlags {
+            fixed_strings: true,
+            ..GrepFlags::default()
+        },
+    )
+    .unwrap();
+    let matches = grep_content(&literal, "n.md", "axb(\na.b(")
+        .unwrap()
+        .matches;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].line_number, 2);
+}
+
+#[test]
+fn test_grep_ranges_count_characters() {
+    let regex = build_grep_regex("café", GrepFlags::default()).unwrap();
+    let matches = grep_content(&regex, "n.md", "ünïcode café")
+        .unwrap()
+        .matches;
+    assert_eq!(matches[0].ranges, vec![(8, 12)]);
+}
+
+#[test]
+fn test_grep_rejects_bad_patterns() {
+    assert!(build_grep_regex("", GrepFlags::default()).is_err());
+    assert!(build_grep_regex("(unclosed", GrepFlags::default()).is_err());
+}
+
+#[test]
+fn test_grep_limits() {
+    let content = "hit\n".repeat(MAX_MATCHES_PER_NOTE + 5);
+    let regex = build_grep_regex("hit", GrepFlags::default()).unwrap();
+    let note = grep_content(&regex, "n.md", &content).unwrap();
+    assert_eq!(note.matches.len(), MAX_MATCHES_PER_NOTE);
+    assert!(note.truncated);
+
+    let names: Vec<String> = (0..20).map(|i| format!("{:02}.md", i)).collect();
+    let notes: Vec<(&str, &str)> = names.iter().map(|n| (n.as_str(), "hit")).collect();
+    let conn = notes_db(&notes);
+    let results = grep_notes(&conn, &regex, 5).unwrap();
+    let filenames: Vec<&str> = results.iter().map(|r| r.filename.as_str()).collect();
+    assert_eq!(filenames, vec!["00.md", "01.md", "02.md", "03.md", "04.md"]);
+}
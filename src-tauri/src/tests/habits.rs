@@ -0,0 +1,98 @@
+//! Habit Tracking Unit Tests
+//!
+//! Tests for habit checkboxes in daily notes and habit history streaks.
+
+use crate::services::habit_service::{habit_history, task_matches_habit, HabitStatus};
+use crate::tests::test_utils::notes_db;
+use crate::utilities::dates::{date_from_filename, DateRange};
+use chrono::NaiveDate;
+
+fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn test_daily_note_dates_and_habit_matching() {
+    assert_eq!(
+        date_from_filename("Daily/2024-06-01.md"),
+        Some(date("2024-06-01"))
+    );
+    assert_eq!(date_from_filename("Meeting 2024-06-01.md"), None);
+
+    assert!(task_matches_habit("exercise", "exercise"));
+    assert!(task_matches_habit("exercise: 30 min run", "exercise"));
+    assert!(!task_matches_habit("exercises", "exercise"));
+}
+
+#[test]
+fn test_habit_history_streaks() {
+    let conn = notes_db(&[
+        ("Daily/2024-06-01.md", "- [x] Exercise\n- [ ] Read"),
+        ("Daily/2024-06-02.md", "- [x] exercise: 5k run"),
+        ("Daily/2024-06-03.md", "- [ ] Exercise"),
+        ("Daily/2024-06-05.md", "- [x] Exercise"),
+        ("Daily/2024-06-06.md", "- [x] Exercise"),
+        ("Daily/2024-06-07.md", "- [ ] Exercise"),
+        ("Ideas/2024-06-04.md", "- [x] Exercise"),
+        ("Projects/Plan.md", "- [x] Exercise"),
+    ]);
+
+    let history = habit_history(
+        &conn,
+        "Exercise",
+        "Daily",
+        &DateRange::default(),
+        date("2024-06-07"),
+    )
+    .unwrap();
+    assert_eq!(history.from, "2024-06-01");
+    assert_eq!(history.days.len(), 7);
+    assert_eq!(history.days[3].status, HabitStatus::Untracked);
+    assert_eq!(history.days[2].status, HabitStatus::Missed);
+    assert_eq!(
+        history.days[0].note_name.as_deref(),
+        Some("Daily/2024-06-01.md")
+    );
+    assert_eq!(history.tracked_days, 6);
+    assert_eq!(history.completed_days, 4);
+    assert_eq!(history.longest_streak, 2);
+    // An unchecked today doesn't end the streak yet
+    assert_eq!(history.current_streak, 2);
+
+    let range = DateRange {
+        from: Some("2024-06-01".to_string()),
+        to: Some("2024-06-03".to_string()),
+    };
+    let early = habit_history(&conn, "exercise", "", &range, date("2024-06-07")).unwrap();
+    assert_eq!(early.days.len(), 3);
+    assert_eq!(early.current_streak, 2);
+
+    let anywhere = habit_history(
+        &conn,
+        "Exercise",
+        "",
+        &DateRange::default(),
+        date("2024-06-07"),
+    )
+    .unwrap();
+    assert_eq!(anywhere.days[3].status, HabitStatus::Done);
+    assert_eq!(anywhere.longest_streak, 3);
+    assert_eq!(anywhere.current_streak, 3);
+}
+
+#[test]
+fn test_habit_history_rejects_bad_input() {
+    let conn = notes_db::<&str>(&[]);
+    assert!(habit_history(&conn, " ", "", &DateRange::default(), date("2024-06-07")).is_err());
+
+    let reversed = DateRange {
+        from: Some("2024-06-07".to_string()),
+        to: Some("2024-06-01".to_string()),
+    };
+    assert!(habit_history(&conn, "Read", "", &reversed, date("2024-06-07")).is_err());
+
+    let empty =
+        habit_history(&conn, "Read", "", &DateRange::default(), date("2024-06-07")).unwrap();
+    assert_eq!(empty.days.len(), 1);
+    assert_eq!(empty.completion_rate, 0.0);
+}
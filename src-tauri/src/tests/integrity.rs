@@ -0,0 +1,106 @@
+//! Note Integrity Unit Tests
+//!
+//! Tests for checking note files against their last known good checksums.
+
+use crate::services::integrity_service::{
+    dismiss_integrity_alert, integrity_alerts, last_verified_at, verify_notes, IntegrityAlertKind,
+};
+use crate::tests::test_utils::notes_db;
+use std::fs::{self, File, FileTimes};
+use std::path::Path;
+use std::time::{Duration, UNIX_EPOCH};
+use tempfile::TempDir;
+
+/// Writes `content` with a fixed modification time, as a bit flip would leave it
+fn write_at(path: &Path, content: &[u8], modified_secs: u64) {
+    fs::write(path, content).unwrap();
+    let time = UNIX_EPOCH + Duration::from_secs(modified_secs);
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_times(FileTimes::new().set_modified(time))
+        .unwrap();
+}
+
+#[test]
+fn test_edits_update_the_checksum() {
+    let dir = TempDir::new().unwrap();
+    let note = dir.path().join("note.md");
+    let conn = notes_db(&["note.md"]);
+
+    write_at(&note, b"first", 1_000);
+    assert!(verify_notes(&conn, dir.path(), 10).unwrap().is_empty());
+    assert_eq!(last_verified_at(&conn).unwrap(), Some(10));
+
+    write_at(&note, b"second version", 2_000);
+    assert!(verify_notes(&conn, dir.path(), 20).unwrap().is_empty());
+    assert!(integrity_alerts(&conn).unwrap().is_empty());
+}
+
+#[test]
+fn test_silent_change_is_flagged_until_restored() {
+    let dir = TempDir::new().unwrap();
+    let note = dir.path().join("note.md");
+    let conn = notes_db(&["note.md"]);
+
+    write_at(&note, b"abcdef", 1_000);
+    verify_notes(&conn, dir.path(), 10).unwrap();
+
+    write_at(&note, b"abcxef", 1_000);
+    let raised = verify_notes(&conn, dir.path(), 20).unwrap();
+    assert_eq!(raised.len(), 1);
+    assert_eq!(raised[0].kind, IntegrityAlertKind::Corrupted);
+
+    // Raised once, not on every check
+    assert!(verify_notes(&conn, dir.path(), 30).unwrap().is_empty());
+    assert_eq!(integrity_alerts(&conn).unwrap().len(), 1);
+
+    write_at(&note, b"abcdef", 3_000);
+    verify_notes(&conn, dir.path(), 40).unwrap();
+    assert!(integrity_alerts(&conn).unwrap().is_empty());
+}
+
+#[test]
+fn test_truncated_and_zero_filled_files_are_flagged() {
+    let dir = TempDir::new().unwrap();
+    let emptied = dir.path().join("emptied.md");
+    let zeroed = dir.path().join("zeroed.md");
+    let conn = notes_db(&["emptied.md", "zeroed.md"]);
+
+    write_at(&emptied, b"# Notes", 1_000);
+    write_at(&zeroed, b"# Notes", 1_000);
+    verify_notes(&conn, dir.path(), 10).unwrap();
+
+    write_at(&emptied, b"", 2_000);
+    write_at(&zeroed, b"# No\0\0\0\0\0\0\0\0\0\0", 2_000);
+    let kinds: Vec<IntegrityAlertKind> = verify_notes(&conn, dir.path(), 20)
+        .unwrap()
+        .into_iter()
+        .map(|alert| alert.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            IntegrityAlertKind::Truncated,
+            IntegrityAlertKind::ZeroFilled
+        ]
+    );
+}
+
+#[test]
+fn test_dismissed_alert_accepts_the_file() {
+    let dir = TempDir::new().unwrap();
+    let note = dir.path().join("note.md");
+    let conn = notes_db(&["note.md"]);
+
+    write_at(&note, b"# Notes", 1_000);
+    verify_notes(&conn, dir.path(), 10).unwrap();
+    write_at(&note, b"", 2_000);
+    verify_notes(&conn, dir.path(), 20).unwrap();
+
+    assert!(dismiss_integrity_alert(&conn, "note.md").unwrap());
+    assert!(!dismiss_integrity_alert(&conn, "note.md").unwrap());
+    assert!(verify_notes(&conn, dir.path(), 30).unwrap().is_empty());
+    assert!(integrity_alerts(&conn).unwrap().is_empty());
+}
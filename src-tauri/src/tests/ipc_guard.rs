@@ -0,0 +1,67 @@
+//! IPC Guard Unit Tests
+//!
+//! Tests for per-command concurrency and rate limits.
+
+use crate::core::ipc_guard::{CommandGuard, CommandLimit};
+use crate::core::AppError;
+use std::time::{Duration, Instant};
+
+static LIMITS: &[(&str, CommandLimit)] = &[(
+    "search_notes",
+    CommandLimit {
+        max_concurrent: 2,
+        max_per_second: 3,
+    },
+)];
+
+const DEFAULT: CommandLimit = CommandLimit {
+    max_concurrent: 4,
+    max_per_second: 10,
+};
+
+#[test]
+fn test_unlisted_commands_get_the_default_limit() {
+    let guard = CommandGuard::new(LIMITS, DEFAULT);
+    let now = Instant::now();
+    assert!(!guard.is_listed("list_all_notes"));
+    for _ in 0..10 {
+        guard.acquire("list_all_notes", now).unwrap();
+    }
+    assert!(matches!(
+        guard.acquire("list_all_notes", now),
+        Err(AppError::Busy(_))
+    ));
+    // Each command is counted on its own
+    assert!(guard.acquire("get_config_content", now).is_ok());
+}
+
+#[test]
+fn test_concurrency_limit_releases_on_drop() {
+    let guard = CommandGuard::new(LIMITS, DEFAULT);
+    let now = Instant::now();
+
+    let first = guard.acquire("search_notes", now).unwrap();
+    let _second = guard.acquire("search_notes", now).unwrap();
+    assert!(matches!(
+        guard.acquire("search_notes", now),
+        Err(AppError::Busy(_))
+    ));
+
+    drop(first);
+    assert!(guard.acquire("search_notes", now).is_ok());
+}
+
+#[test]
+fn test_rate_limit_resets_each_window() {
+    let guard = CommandGuard::new(LIMITS, DEFAULT);
+    let start = Instant::now();
+
+    for _ in 0..3 {
+        guard.acquire("search_notes", start).unwrap();
+    }
+    let error = guard.acquire("search_notes", start).unwrap_err();
+    assert!(error.to_string().starts_with("Busy:"));
+
+    let later = start + Duration::from_millis(1100);
+    assert!(guard.acquire("search_notes", later).is_ok());
+}
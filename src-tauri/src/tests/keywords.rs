@@ -0,0 +1,64 @@
+//! Keyword Cloud Unit Tests
+//!
+//! Tests for reading the most frequent terms from the FTS vocabulary.
+
+use crate::search::SearchScope;
+use crate::services::keyword_service::{is_keyword, keyword_cloud};
+use crate::tests::test_utils::notes_db;
+use rusqlite::Connection;
+
+fn terms(conn: &Connection, scope: &SearchScope, limit: usize) -> Vec<(String, usize, usize)> {
+    keyword_cloud(conn, scope, limit)
+        .unwrap()
+        .into_iter()
+        .map(|k| (k.term, k.count, k.notes))
+        .collect()
+}
+
+#[test]
+fn test_is_keyword_skips_stop_words_and_noise() {
+    assert!(is_keyword("garden"));
+    assert!(!is_keyword("the"));
+    assert!(!is_keyword("ab"));
+    assert!(!is_keyword("2024"));
+    assert!(is_keyword("q3plan"));
+}
+
+#[test]
+fn test_keyword_cloud_counts_content_only() {
+    let conn = notes_db(&[
+        (
+            "Garden/tomatoes.md",
+            "Tomatoes and the garden. Garden soil.",
+            1,
+        ),
+        ("Garden/herbs.md", "Herbs in the garden #plants", 2),
+        ("Work/plan.md", "Plan the roadmap, the roadmap", 3),
+    ]);
+    conn.execute("UPDATE notes SET html_render = '<p>markup markup</p>'", [])
+        .unwrap();
+
+    let all = terms(&conn, &SearchScope::default(), 3);
+    assert_eq!(
+        all,
+        vec![
+            ("garden".to_string(), 3, 2),
+            ("roadmap".to_string(), 2, 1),
+            ("herbs".to_string(), 1, 1),
+        ]
+    );
+
+    let work = SearchScope {
+        folder: Some("Work".to_string()),
+        tag: None,
+    };
+    assert_eq!(terms(&conn, &work, 1), vec![("roadmap".to_string(), 2, 1)]);
+
+    let plants = SearchScope {
+        folder: None,
+        tag: Some("#plants".to_string()),
+    };
+    let tagged = terms(&conn, &plants, 10);
+    assert_eq!(tagged[0], ("garden".to_string(), 1, 1));
+    assert!(tagged.iter().all(|(term, _, _)| term != "markup"));
+}
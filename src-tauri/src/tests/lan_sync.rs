@@ -0,0 +1,184 @@
+//! LAN Sync Unit Tests
+//!
+//! Tests for the peer-to-peer sync protocol helpers: planning, pairing fingerprints and framing.
+
+use crate::services::lan_sync_service::*;
+use std::collections::HashMap;
+use std::io::Cursor;
+use tempfile::TempDir;
+
+fn hashes(entries: &[(&str, &str)]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|(name, hash)| (name.to_string(), hash.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_plan_sync_three_way() {
+    let local = hashes(&[
+        ("same.md", "1"),
+        ("local-edit.md", "2"),
+        ("remote-edit.md", "1"),
+        ("both.md", "2"),
+        ("only-local.md", "1"),
+        ("never-synced.md", "1"),
+    ]);
+    let remote = hashes(&[
+        ("same.md", "1"),
+        ("local-edit.md", "1"),
+        ("remote-edit.md", "2"),
+        ("both.md", "3"),
+        ("only-remote.md", "1"),
+        ("never-synced.md", "2"),
+    ]);
+    let base = hashes(&[
+        ("local-edit.md", "1"),
+        ("remote-edit.md", "1"),
+        ("both.md", "1"),
+    ]);
+
+    let plan = plan_sync(&local, &remote, &base);
+    assert_eq!(plan.in_sync, vec!["same.md"]);
+    assert_eq!(plan.push, vec!["local-edit.md", "only-local.md"]);
+    assert_eq!(plan.pull, vec!["only-remote.md", "remote-edit.md"]);
+    assert_eq!(plan.conflicts, vec!["both.md", "never-synced.md"]);
+}
+
+#[test]
+fn test_pairing_fingerprint_matches_on_both_sides() {
+    let fingerprint = pairing_fingerprint("device-a", "age1a", "device-b", "age1b");
+    assert_eq!(
+        fingerprint,
+        pairing_fingerprint("device-b", "age1b", "device-a", "age1a")
+    );
+
+    let groups: Vec<&str> = fingerprint.split(' ').collect();
+    assert_eq!(groups.len(), 8);
+    assert!(groups
+        .iter()
+        .all(|group| group.len() == 4 && group.chars().all(|c| c.is_ascii_hexdigit())));
+}
+
+#[test]
+fn test_pairing_fingerprint_changes_with_a_swapped_key() {
+    let fingerprint = pairing_fingerprint("device-a", "age1a", "device-b", "age1b");
+    // What each side sees when a device in the middle relays with its own key
+    assert_ne!(
+        fingerprint,
+        pairing_fingerprint("device-a", "age1a", "device-b", "age1mallory")
+    );
+    assert_ne!(
+        fingerprint,
+        pairing_fingerprint("device-a", "age1mallory", "device-b", "age1b")
+    );
+    assert_ne!(
+        fingerprint,
+        pairing_fingerprint("device-a", "age1a", "device-c", "age1b")
+    );
+}
+
+#[test]
+fn test_sealed_frames_round_trip() {
+    let receiver = DeviceIdentity::generate("receiver");
+    let outsider = DeviceIdentity::generate("outsider");
+
+    let mut wire = Vec::new();
+    write_json(
+        &mut wire,
+        &ClientHello::Sync {
+            protocol: PROTOCOL_VERSION,
+            device_id: "sender".to_string(),
+        },
+    )
+    .unwrap();
+    write_sealed(
+        &mut wire,
+        &receiver.recipient().unwrap(),
+        &SealedChallenge {
+            nonce: "abc".to_string(),
+        },
+    )
+    .unwrap();
+    write_frame(&mut wire, &[]).unwrap();
+
+    let mut reader = Cursor::new(wire.clone());
+    let hello: ClientHello = read_json(&mut reader).unwrap();
+    assert!(matches!(hello, ClientHello::Sync { device_id, .. } if device_id == "sender"));
+    let challenge: SealedChallenge = read_sealed(&mut reader, &receiver).unwrap();
+    assert_eq!(challenge.nonce, "abc");
+    let refused = read_sealed::<_, SealedChallenge>(&mut reader, &receiver).unwrap_err();
+    assert!(refused.to_string().contains("pair the devices again"));
+
+    let mut reader = Cursor::new(wire);
+    let _: ClientHello = read_json(&mut reader).unwrap();
+    assert!(read_sealed::<_, SealedChallenge>(&mut reader, &outsider).is_err());
+
+    let oversized = (u32::MAX).to_be_bytes();
+    assert!(read_frame(&mut Cursor::new(oversized.to_vec()), MAX_FRAME_BYTES).is_err());
+}
+
+#[test]
+fn test_handshake_frames_are_capped() {
+    let mut wire = Vec::new();
+    write_frame(&mut wire, &vec![b' '; MAX_HANDSHAKE_BYTES as usize + 1]).unwrap();
+    let error = read_json::<_, ClientHello>(&mut Cursor::new(wire.clone())).unwrap_err();
+    assert!(error.to_string().contains("exceeds the limit"));
+    assert!(read_frame(&mut Cursor::new(wire), MAX_FRAME_BYTES).is_ok());
+
+    // A header promising more than arrives fails without reserving that much
+    let mut truncated = (1024u32 * 1024).to_be_bytes().to_vec();
+    truncated.extend_from_slice(b"short");
+    assert!(read_frame(&mut Cursor::new(truncated), MAX_FRAME_BYTES).is_err());
+}
+
+#[test]
+fn test_peer_and_conflict_stores() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let device_path = temp_dir.path().join("device.json");
+    let device = load_or_create_device(&device_path, "laptop").unwrap();
+    assert_eq!(
+        load_or_create_device(&device_path, "other")
+            .unwrap()
+            .device_id,
+        device.device_id
+    );
+
+    let peers_path = temp_dir.path().join("peers.json");
+    let peer = PairedPeer {
+        device_id: "desk".to_string(),
+        device_name: "Desktop".to_string(),
+        recipient: device.recipient().unwrap(),
+        paired_at: "2024-01-01T00:00:00Z".to_string(),
+    };
+    save_paired_peer(&peers_path, peer.clone()).unwrap();
+    save_paired_peer(&peers_path, peer.clone()).unwrap();
+    assert_eq!(load_paired_peers(&peers_path).unwrap(), vec![peer]);
+    assert!(remove_paired_peer(&peers_path, "desk").unwrap());
+    assert!(!remove_paired_peer(&peers_path, "desk").unwrap());
+
+    let mut conflicts = Vec::new();
+    for content in ["first", "second"] {
+        record_conflict(
+            &mut conflicts,
+            SyncConflict {
+                note_name: "a.md".to_string(),
+                peer_id: "desk".to_string(),
+                peer_name: "Desktop".to_string(),
+                remote_content: content.to_string(),
+                detected_at: String::new(),
+            },
+        );
+    }
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].remote_content, "second");
+
+    assert_eq!(
+        conflict_copy_name("dir/a.md", "Jo's Mac"),
+        "dir/a (conflict from Jo_s Mac).md"
+    );
+    assert_eq!(
+        conflict_copy_name("v1.2/notes", "Desk"),
+        "v1.2/notes (conflict from Desk)"
+    );
+}
@@ -0,0 +1,68 @@
+//! Meeting Note Unit Tests
+//!
+//! Tests for attendee, decision and action-item extraction.
+
+use crate::utilities::meetings::parse_meeting;
+
+#[test]
+fn test_parse_meeting_sections() {
+    let content = "---
+date: 2024-06-03
+attendees: [Ana, \"@bo\"]
+---
+# Planning sync
+
+## Attendees
+- [[People/Cy]]
+- ana
+
+## Decisions
+- Ship on Friday
+1. Drop the beta flag
+
+Decision: keep weekly syncs
+
+## Action items
+- [ ] @ana write release notes 📅 2024-06-07
+- [x] Book the room
+- Follow up with legal @cy
+
+## Notes
+- [ ] @bo check metrics due: 2024-06-10
+- [ ] Unowned checkbox
+```
+- [ ] @ghost inside code
+```
+";
+
+    let meeting = parse_meeting("Meetings/Planning.md", content);
+    assert_eq!(meeting.title, "Planning");
+    assert_eq!(meeting.date.as_deref(), Some("2024-06-03"));
+    assert_eq!(meeting.attendees, vec!["Ana", "bo", "Cy"]);
+    assert_eq!(
+        meeting.decisions,
+        vec!["Ship on Friday", "Drop the beta flag", "keep weekly syncs"]
+    );
+
+    let owners: Vec<Option<&str>> = meeting
+        .action_items
+        .iter()
+        .map(|item| item.owner.as_deref())
+        .collect();
+    assert_eq!(owners, vec![Some("ana"), None, Some("cy"), Some("bo")]);
+    assert_eq!(meeting.action_items[0].due.as_deref(), Some("2024-06-07"));
+    assert_eq!(meeting.action_items[0].line, 18);
+    assert!(meeting.action_items[1].done);
+    assert!(!meeting.action_items[2].done);
+    assert_eq!(meeting.action_items[3].due.as_deref(), Some("2024-06-10"));
+}
+
+#[test]
+fn test_parse_meeting_inline_labels() {
+    let content = "**Attendees:** Ana, @Bo; [[People/Cy|Cyrus]]\nAction item: @ana send notes\nNothing else, email@example.com\n";
+    let meeting = parse_meeting("standup.md", content);
+    assert_eq!(meeting.attendees, vec!["Ana", "Bo", "Cyrus"]);
+    assert_eq!(meeting.action_items.len(), 1);
+    assert_eq!(meeting.action_items[0].owner.as_deref(), Some("ana"));
+    assert!(meeting.decisions.is_empty());
+}
@@ -0,0 +1,65 @@
+//! Mention Index Unit Tests
+//!
+//! Tests for `@mention` / `[[People/Name]]` extraction and the derived mentions table.
+
+use crate::services::derived_index_service::{invalidate_note, refresh_index};
+use crate::services::mention_service::{find_mentions, MENTIONS_INDEX};
+use crate::tests::test_utils::notes_db;
+use crate::utilities::mentions::{extract_mentions, normalize_person, MentionKind};
+
+#[test]
+fn test_extract_mentions() {
+    let content = "Met @ana_lopez and (@Bo) today.\nMail ana@example.com\nSee [[People/Ana Lopez|Ana]] and [notes](People/Cy.md)\n```\n@ghost [[People/Ghost]]\n```\n[[Projects/Alpha]]";
+    let mentions = extract_mentions(content);
+    let people: Vec<(&str, MentionKind, usize)> = mentions
+        .iter()
+        .map(|m| (m.person.as_str(), m.kind, m.line))
+        .collect();
+    assert_eq!(
+        people,
+        vec![
+            ("ana lopez", MentionKind::Handle, 1),
+            ("bo", MentionKind::Handle, 1),
+            ("ana lopez", MentionKind::Link, 3),
+            ("cy", MentionKind::Link, 3),
+        ]
+    );
+    assert_eq!(normalize_person("@Jean-Luc.Picard"), "jean luc picard");
+}
+
+#[test]
+fn test_mentions_index_refreshes_incrementally() {
+    let conn = notes_db(&[
+        ("a.md", "Call @ana_lopez", 100),
+        ("b.md", "Lunch with [[People/Ana Lopez]]", 200),
+        (
+            "People/Ana Lopez.md",
+            "Reports to @ana_lopez's manager",
+            300,
+        ),
+        ("c.md", "Nothing here", 50),
+    ]);
+
+    let mentions = find_mentions(&conn, "Ana Lopez").unwrap();
+    let names: Vec<&str> = mentions.iter().map(|m| m.note_name.as_str()).collect();
+    assert_eq!(names, vec!["b.md", "a.md"]);
+    assert_eq!(mentions[1].context, "Call @ana_lopez");
+    assert_eq!(
+        find_mentions(&conn, "People/Ana Lopez.md").unwrap().len(),
+        2
+    );
+    assert_eq!(refresh_index(&conn, &MENTIONS_INDEX).unwrap(), 0);
+
+    // Same modified stamp, but the note service invalidates on every write
+    conn.execute(
+        "UPDATE notes SET content = 'No one' WHERE filename = 'a.md'",
+        [],
+    )
+    .unwrap();
+    invalidate_note(&conn, "a.md").unwrap();
+    conn.execute("DELETE FROM notes WHERE filename = 'b.md'", [])
+        .unwrap();
+    assert_eq!(refresh_index(&conn, &MENTIONS_INDEX).unwrap(), 2);
+    assert!(find_mentions(&conn, "@ana_lopez").unwrap().is_empty());
+    assert!(find_mentions(&conn, "  ").unwrap().is_empty());
+}
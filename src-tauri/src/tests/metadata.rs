@@ -0,0 +1,238 @@
+//! Metadata Index Unit Tests
+//!
+//! Tests for the derived `note_metadata` table, schema-driven filtering and sorting,
+//! and front-matter field updates.
+
+use crate::services::metadata_service::{query_notes_by_metadata, MetadataQuery};
+use crate::tests::test_utils::notes_db;
+use crate::utilities::frontmatter::{parse_frontmatter, set_frontmatter_field, FrontmatterValue};
+use crate::utilities::validation::validate_frontmatter_field;
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+
+fn schema() -> Vec<String> {
+    vec![
+        "status".to_string(),
+        "priority".to_string(),
+        "project".to_string(),
+    ]
+}
+
+fn sample_db() -> Connection {
+    notes_db(&[
+        (
+            "a.md",
+            "---\nStatus: Active\npriority: 10\nproject: [apollo, gemini]\nsecret: x\n---\nA",
+            1,
+        ),
+        ("b.md", "---\nstatus: done\npriority: 2\n---\nB", 2),
+        ("c.md", "---\nstatus: active\n---\nC", 3),
+        ("d.md", "No front matter", 4),
+    ])
+}
+
+fn names(conn: &Connection, query: &MetadataQuery) -> Vec<String> {
+    query_notes_by_metadata(conn, &schema(), query)
+        .unwrap()
+        .into_iter()
+        .map(|note| note.note_name)
+        .collect()
+}
+
+#[test]
+fn test_fields_limited_to_schema() {
+    let conn = sample_db();
+    let filters = HashMap::new();
+    let notes = query_notes_by_metadata(
+        &conn,
+        &schema(),
+        &MetadataQuery {
+            candidates: Some(&["a.md".to_string()]),
+            filters: &filters,
+            sort_by: None,
+            descending: false,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].modified, 1);
+    assert_eq!(
+        notes[0].fields.iter().collect::<Vec<_>>(),
+        vec![
+            (&"priority".to_string(), &"10".to_string()),
+            (&"project".to_string(), &"apollo, gemini".to_string()),
+            (&"status".to_string(), &"Active".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_filters_are_case_insensitive_and_match_list_items() {
+    let conn = sample_db();
+    let filters = HashMap::from([("STATUS".to_string(), "active".to_string())]);
+    let query = MetadataQuery {
+        candidates: None,
+        filters: &filters,
+        sort_by: None,
+        descending: false,
+    };
+    assert_eq!(names(&conn, &query), vec!["c.md", "a.md"]);
+
+    let filters = HashMap::from([("project".to_string(), "Gemini".to_string())]);
+    assert_eq!(
+        names(
+            &conn,
+            &MetadataQuery {
+                filters: &filters,
+                ..query
+            }
+        ),
+        vec!["a.md"]
+    );
+}
+
+#[test]
+fn test_sort_numeric_with_missing_values_last() {
+    let conn = sample_db();
+    let filters = HashMap::new();
+    let query = MetadataQuery {
+        candidates: None,
+        filters: &filters,
+        sort_by: Some("priority"),
+        descending: false,
+    };
+    assert_eq!(names(&conn, &query), vec!["b.md", "a.md", "d.md", "c.md"]);
+    assert_eq!(
+        names(
+            &conn,
+            &MetadataQuery {
+                descending: true,
+                ..query
+            }
+        ),
+        vec!["a.md", "b.md", "d.md", "c.md"]
+    );
+}
+
+#[test]
+fn test_candidates_keep_their_order() {
+    let conn = sample_db();
+    let filters = HashMap::new();
+    let candidates = vec![
+        "b.md".to_string(),
+        "missing.md".to_string(),
+        "a.md".to_string(),
+    ];
+    assert_eq!(
+        names(
+            &conn,
+            &MetadataQuery {
+                candidates: Some(&candidates),
+                filters: &filters,
+                sort_by: None,
+                descending: false,
+            }
+        ),
+        vec!["b.md", "a.md"]
+    );
+}
+
+#[test]
+fn test_fields_outside_schema_are_rejected() {
+    let conn = sample_db();
+    let filters = HashMap::from([("secret".to_string(), "x".to_string())]);
+    let query = MetadataQuery {
+        candidates: None,
+        filters: &filters,
+        sort_by: None,
+        descending: false,
+    };
+    assert!(query_notes_by_metadata(&conn, &schema(), &query).is_err());
+
+    let filters = HashMap::new();
+    let query = MetadataQuery {
+        filters: &filters,
+        sort_by: Some("secret"),
+        ..query
+    };
+    assert!(query_notes_by_metadata(&conn, &schema(), &query).is_err());
+}
+
+#[test]
+fn test_frontmatter_fields_round_trip() {
+    let content = "---\ntitle: Plan\n---\nBody\n";
+    for (key, value) in [
+        (
+            "status",
+            FrontmatterValue::Text("in progress: 50%".to_string()),
+        ),
+        ("owner", FrontmatterValue::Text(" #lead ".to_string())),
+        (
+            "project",
+            FrontmatterValue::List(vec!["apollo".to_string(), "gemini 2".to_string()]),
+        ),
+    ] {
+        validate_frontmatter_field(key, Some(&value)).unwrap();
+        let updated = set_frontmatter_field(content, key, Some(&value));
+        let frontmatter = parse_frontmatter(&updated).unwrap();
+        assert_eq!(frontmatter.get(key), Some(&value));
+        assert_eq!(frontmatter.get_text("title"), Some("Plan"));
+    }
+}
+
+#[test]
+fn test_invalid_frontmatter_fields_are_rejected() {
+    let text = FrontmatterValue::Text("x".to_string());
+    for key in ["", " status", "a:b", "# status", "- item", "two\nlines"] {
+        assert!(
+            validate_frontmatter_field(key, Some(&text)).is_err(),
+            "{:?}",
+            key
+        );
+    }
+    assert!(validate_frontmatter_field("status", None).is_ok());
+    assert!(validate_frontmatter_field(
+        "status",
+        Some(&FrontmatterValue::Text("a\nb".to_string()))
+    )
+    .is_err());
+    assert!(validate_frontmatter_field(
+        "project",
+        Some(&FrontmatterValue::List(vec!["a, b".to_string()]))
+    )
+    .is_err());
+}
+
+#[test]
+fn test_updated_frontmatter_is_queryable() {
+    let conn = sample_db();
+    let filters = HashMap::from([("status".to_string(), "archived".to_string())]);
+    let query = MetadataQuery {
+        candidates: None,
+        filters: &filters,
+        sort_by: None,
+        descending: false,
+    };
+    assert!(query_notes_by_metadata(&conn, &schema(), &query)
+        .unwrap()
+        .is_empty());
+
+    let content: String = conn
+        .query_row(
+            "SELECT content FROM notes WHERE filename = 'c.md'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    let archived = FrontmatterValue::Text("archived".to_string());
+    conn.execute(
+        "UPDATE notes SET content = ?1, modified = 5 WHERE filename = 'c.md'",
+        params![set_frontmatter_field(&content, "status", Some(&archived))],
+    )
+    .unwrap();
+
+    let notes = query_notes_by_metadata(&conn, &schema(), &query).unwrap();
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].note_name, "c.md");
+}
@@ -4,18 +4,86 @@
 //! These tests can access private functions and are part of the same crate.
 
 pub mod atomic_operations;
+pub mod backlinks;
+pub mod batch;
+pub mod calendar;
+pub mod capture;
+pub mod citations;
 pub mod cleanup_test;
 pub mod concurrency;
 pub mod config;
 pub mod content_synchronization;
+pub mod crdt;
 pub mod crud_operations;
 pub mod database;
 pub mod database_consistency;
+pub mod dates;
+pub mod dictionary;
 pub mod directory_paths;
+pub mod docx_export;
+pub mod edit_locks;
+pub mod email;
 pub mod error_handling;
+pub mod events;
+pub mod expiry;
+pub mod export_templates;
+pub mod external_changes;
+pub mod external_edits;
+pub mod folders;
+pub mod geo;
+pub mod grep;
+pub mod habits;
+pub mod integrity;
+pub mod ipc_guard;
+pub mod keywords;
+pub mod lan_sync;
+pub mod meetings;
+pub mod mentions;
+pub mod metadata;
+pub mod note_created;
+pub mod note_details;
+pub mod note_lint;
 pub mod note_rendering;
+pub mod note_sections;
+pub mod outline_export;
+pub mod path_access;
+pub mod projects;
+pub mod property_query;
+pub mod publish;
+pub mod query_console;
+pub mod quick_switch;
+pub mod reading;
+pub mod reading_progress;
+pub mod reminders;
+pub mod rename_history;
+pub mod replace;
+pub mod rerender;
+pub mod rollups;
+pub mod safe_mode;
+pub mod scratchpads;
 pub mod search;
+pub mod search_export;
 pub mod security;
+pub mod service_health;
+pub mod share;
+pub mod slides;
+pub mod slow_operations;
+pub mod snippets;
+pub mod staleness;
+pub mod startup_profile;
+pub mod sync_warnings;
+pub mod tag_rules;
+pub mod tags;
+pub mod templates;
 pub mod test_utils;
+pub mod text_encoding;
+pub mod time_tracking;
+pub mod trash;
 pub mod validation;
+pub mod vault_archive;
+pub mod vault_check;
+pub mod vaults;
+pub mod version_labels;
 pub mod watcher;
+pub mod webhooks;
+pub mod write_recovery;
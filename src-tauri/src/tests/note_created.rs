@@ -0,0 +1,72 @@
+//! Note Creation Time Unit Tests
+//!
+//! Tests for tracking when notes were created and listing them by it.
+
+use crate::services::note_created_service::{
+    created_time, forget_created, move_created, record_created,
+};
+use crate::services::note_details_service::{list_note_details, note_stats, NoteOrder};
+use crate::tests::test_utils::notes_db;
+use std::collections::HashSet;
+
+#[test]
+fn test_created_time_prefers_birth_time() {
+    assert_eq!(created_time(Some(10), Some(50), 100), 10);
+    // First seen now, unless the file was modified earlier
+    assert_eq!(created_time(None, Some(50), 100), 50);
+    assert_eq!(created_time(None, Some(200), 100), 100);
+    assert_eq!(created_time(None, None, 100), 100);
+}
+
+#[test]
+fn test_created_time_follows_renames_and_deletes() {
+    let conn = notes_db(&[("Final.md", "", 5)]);
+    record_created(&conn, "Draft.md", 1).unwrap();
+    // Later saves keep the first creation time
+    record_created(&conn, "Draft.md", 3).unwrap();
+    move_created(&conn, "Draft.md", "Final.md").unwrap();
+
+    let details = list_note_details(&conn, &HashSet::new(), NoteOrder::Modified).unwrap();
+    assert_eq!(details[0].created, Some(1));
+
+    forget_created(&conn, "Final.md").unwrap();
+    record_created(&conn, "Final.md", 9).unwrap();
+    assert_eq!(note_stats(&conn, "Final.md").unwrap().created, Some(9));
+}
+
+#[test]
+fn test_list_note_details_by_created() {
+    let conn = notes_db(&[
+        ("Old.md", "", 30),
+        ("New.md", "", 10),
+        ("Unseen.md", "", 20),
+    ]);
+    record_created(&conn, "Old.md", 1).unwrap();
+    record_created(&conn, "New.md", 5).unwrap();
+
+    let names = |order| {
+        list_note_details(&conn, &HashSet::new(), order)
+            .unwrap()
+            .into_iter()
+            .map(|d| d.note_name)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        names(NoteOrder::Created),
+        vec!["New.md", "Old.md", "Unseen.md"]
+    );
+    assert_eq!(
+        names(NoteOrder::Modified),
+        vec!["Old.md", "Unseen.md", "New.md"]
+    );
+}
+
+#[test]
+fn test_frontmatter_created_overrides_file_time() {
+    let conn = notes_db(&[("Plan.md", "---\ncreated: 2024-03-01T00:00:00Z\n---\n", 1)]);
+    record_created(&conn, "Plan.md", 5).unwrap();
+    assert_eq!(
+        note_stats(&conn, "Plan.md").unwrap().created,
+        Some(1_709_251_200)
+    );
+}
@@ -0,0 +1,254 @@
+//! Note Details Unit Tests
+//!
+//! Tests for per-note badges and word counts in detailed listings.
+
+use crate::core::AppError;
+use crate::services::note_details_service::{
+    list_note_details, list_notes_page, normalize_color, normalize_icon, note_stats, LinkCounts,
+    NoteOrder, NoteSortKey, SortDirection,
+};
+use crate::tests::test_utils::notes_db;
+use crate::utilities::frontmatter::{set_frontmatter_field, FrontmatterValue};
+use crate::utilities::links::count_external_links;
+use crate::utilities::strings::{natural_cmp, word_count};
+use std::collections::HashSet;
+
+#[test]
+fn test_word_count_ignores_frontmatter_and_markup() {
+    assert_eq!(
+        word_count("---\ntitle: Long title here\n---\n# Heading\n- one two\n"),
+        3
+    );
+    assert_eq!(word_count(""), 0);
+}
+
+#[test]
+fn test_list_note_details_flags() {
+    let conn = notes_db(&[
+        (
+            "Pinned.md",
+            "---\npinned: true\nlocked: yes\n---\nHello world\n",
+            1,
+        ),
+        ("Archive/Old.md", "Old words here\n", 2),
+        (
+            "Secret.md",
+            "---\nencrypted: true\narchived: false\n---\n",
+            3,
+        ),
+    ]);
+    let conflicted: HashSet<String> = ["Pinned.md".to_string()].into_iter().collect();
+
+    let details = list_note_details(&conn, &conflicted, NoteOrder::Modified).unwrap();
+    let names: Vec<&str> = details.iter().map(|d| d.note_name.as_str()).collect();
+    assert_eq!(names, vec!["Secret.md", "Archive/Old.md", "Pinned.md"]);
+
+    let pinned = &details[2];
+    assert!(pinned.pinned && pinned.locked && pinned.has_conflicts);
+    assert!(!pinned.archived && !pinned.encrypted);
+    assert_eq!(pinned.word_count, 2);
+
+    assert!(details[1].archived);
+    assert_eq!(details[1].word_count, 3);
+    assert!(details[0].encrypted && !details[0].archived);
+}
+
+#[test]
+fn test_list_note_details_appearance() {
+    let conn = notes_db(&[
+        (
+            "Styled.md",
+            "---\nicon: 📌\ncolor: \"#FF8800\"\n---\nBody\n",
+            1,
+        ),
+        ("Invalid.md", "---\ncolor: not a color\n---\n", 2),
+        ("Plain.md", "Body\n", 3),
+    ]);
+
+    let details = list_note_details(&conn, &HashSet::new(), NoteOrder::Modified).unwrap();
+    let styled = details.iter().find(|d| d.note_name == "Styled.md").unwrap();
+    assert_eq!(styled.icon.as_deref(), Some("📌"));
+    assert_eq!(styled.color.as_deref(), Some("#ff8800"));
+    assert!(details
+        .iter()
+        .filter(|d| d.note_name != "Styled.md")
+        .all(|d| d.icon.is_none() && d.color.is_none()));
+}
+
+#[test]
+fn test_normalize_appearance_values() {
+    assert_eq!(normalize_color(" Teal ").unwrap().as_deref(), Some("teal"));
+    assert_eq!(normalize_color("#0aF").unwrap().as_deref(), Some("#0af"));
+    assert_eq!(normalize_color("").unwrap(), None);
+    assert!(normalize_color("#12345").is_err());
+    assert!(normalize_color("red; background").is_err());
+
+    assert_eq!(normalize_icon(" 📁 ").unwrap().as_deref(), Some("📁"));
+    assert_eq!(normalize_icon("  ").unwrap(), None);
+    assert!(normalize_icon("two\nlines").is_err());
+    assert!(normalize_icon(&"x".repeat(33)).is_err());
+}
+
+#[test]
+fn test_set_frontmatter_field_keeps_other_lines() {
+    let icon = FrontmatterValue::Text("📌".to_string());
+    let content = "---\n# comment\ntitle: Plan\nicon: old\ntags:\n  - a\n---\nBody\n";
+
+    assert_eq!(
+        set_frontmatter_field(content, "icon", Some(&icon)),
+        "---\n# comment\ntitle: Plan\nicon: 📌\ntags:\n  - a\n---\nBody\n"
+    );
+    assert_eq!(
+        set_frontmatter_field(content, "tags", None),
+        "---\n# comment\ntitle: Plan\nicon: old\n---\nBody\n"
+    );
+    assert_eq!(
+        set_frontmatter_field(
+            content,
+            "color",
+            Some(&FrontmatterValue::Text("#abc".into()))
+        ),
+        "---\n# comment\ntitle: Plan\nicon: old\ntags:\n  - a\ncolor: \"#abc\"\n---\nBody\n"
+    );
+
+    assert_eq!(
+        set_frontmatter_field("Body\n", "icon", Some(&icon)),
+        "---\nicon: 📌\n---\nBody\n"
+    );
+    assert_eq!(set_frontmatter_field("Body\n", "icon", None), "Body\n");
+    assert_eq!(
+        set_frontmatter_field("---\nicon: x\n---\nBody\n", "icon", None),
+        "Body\n"
+    );
+}
+
+#[test]
+fn test_count_external_links_skips_code_blocks() {
+    let content = "See [docs](https://example.com/a) and <http://b.org>\n\
+                   ```\nhttps://ignored.dev\n```\nmail mailto:me@x.io, [[Local]]\n";
+    assert_eq!(count_external_links(content), 3);
+}
+
+#[test]
+fn test_note_stats_counts_content() {
+    let content = "---\ncreated: 2024-03-01T00:00:00Z\ntags: [project/alpha]\n---\n\
+                   # Plan\n\nSee [[Roadmap]] and [spec](spec.md).\n\n\
+                   ## Links\n\nhttps://example.com #draft\n";
+    let conn = notes_db(&[("plan.md", content, 1_700_000_000)]);
+
+    let stats = note_stats(&conn, "plan.md").unwrap();
+    assert_eq!(stats.modified, 1_700_000_000);
+    assert_eq!(stats.created, Some(1_709_251_200));
+    assert_eq!(stats.size, content.len());
+    assert_eq!(stats.word_count, word_count(content));
+    assert_eq!(stats.heading_count, 2);
+    assert_eq!(stats.tags, vec!["project/alpha", "draft"]);
+    assert_eq!(
+        stats.links,
+        LinkCounts {
+            internal: 2,
+            external: 1
+        }
+    );
+}
+
+#[test]
+fn test_note_stats_missing_note() {
+    let conn = notes_db(&[("plan.md", "# Plan", 1)]);
+    let stats = note_stats(&conn, "plan.md").unwrap();
+    assert_eq!(stats.created, None);
+    assert!(stats.tags.is_empty());
+
+    assert!(matches!(
+        note_stats(&conn, "missing.md"),
+        Err(AppError::FileNotFound(_))
+    ));
+}
+
+#[test]
+fn test_list_notes_page_sorts_and_pages() {
+    let conn = notes_db(&[
+        ("b.md", "medium body", 3),
+        ("A.md", "short", 1),
+        ("c.md", "the longest body of all", 2),
+    ]);
+    let names = |sort_by, direction, offset, limit| {
+        list_notes_page(&conn, sort_by, direction, offset, limit)
+            .unwrap()
+            .notes
+            .into_iter()
+            .map(|note| note.filename)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        names(NoteSortKey::Modified, SortDirection::Desc, 0, 10),
+        vec!["b.md", "c.md", "A.md"]
+    );
+    assert_eq!(
+        names(NoteSortKey::Name, SortDirection::Asc, 0, 10),
+        vec!["A.md", "b.md", "c.md"]
+    );
+    assert_eq!(
+        names(NoteSortKey::Size, SortDirection::Desc, 1, 1),
+        vec!["b.md"]
+    );
+
+    let page = list_notes_page(
+        &conn,
+        NoteSortKey::default(),
+        SortDirection::default(),
+        2,
+        5,
+    )
+    .unwrap();
+    assert_eq!(page.total, 3);
+    assert_eq!(page.notes.len(), 1);
+    assert_eq!(page.notes[0].size, 5);
+    assert_eq!(page.notes[0].created, None);
+}
+
+#[test]
+fn test_natural_cmp_orders_numbers_by_value() {
+    let mut names = vec![
+        "note10.md",
+        "Note2.md",
+        "note1.md",
+        "note02.md",
+        "chapter 9/a.md",
+        "chapter 10/a.md",
+        "note.md",
+    ];
+    names.sort_by(|a, b| natural_cmp(a, b));
+    assert_eq!(
+        names,
+        vec![
+            "chapter 9/a.md",
+            "chapter 10/a.md",
+            "note.md",
+            "note1.md",
+            "Note2.md",
+            "note02.md",
+            "note10.md",
+        ]
+    );
+    assert_eq!(natural_cmp("a", "a"), std::cmp::Ordering::Equal);
+}
+
+#[test]
+fn test_natural_sort_in_note_lists() {
+    let conn = notes_db(&[
+        ("note10.md", "", 1),
+        ("note2.md", "", 2),
+        ("note1.md", "", 3),
+    ]);
+
+    let page = list_notes_page(&conn, NoteSortKey::Natural, SortDirection::Desc, 1, 5).unwrap();
+    let names: Vec<&str> = page.notes.iter().map(|n| n.filename.as_str()).collect();
+    assert_eq!(names, vec!["note2.md", "note1.md"]);
+    assert_eq!(page.total, 3);
+
+    let details = list_note_details(&conn, &HashSet::new(), NoteOrder::Natural).unwrap();
+    let names: Vec<&str> = details.iter().map(|d| d.note_name.as_str()).collect();
+    assert_eq!(names, vec!["note1.md", "note2.md", "note10.md"]);
+}
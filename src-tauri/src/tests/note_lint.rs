@@ -0,0 +1,234 @@
+//! Note Lint Unit Tests
+//!
+//! Tests for front-matter parsing, link extraction, lint diagnostics and vault linting.
+
+use crate::config::LintConfig;
+use crate::services::vault_lint_service::{lint_vault, VaultLintRules};
+use crate::tests::test_utils::notes_db;
+use crate::utilities::frontmatter::{parse_frontmatter, serialize_frontmatter, FrontmatterValue};
+use crate::utilities::links::{extract_links, resolve_link_target, LinkKind};
+use crate::utilities::note_lint::lint_content;
+use std::collections::BTreeMap;
+
+fn rules(diagnostics: &[crate::utilities::note_lint::LintDiagnostic]) -> Vec<&'static str> {
+    diagnostics.iter().map(|d| d.rule).collect()
+}
+
+#[test]
+fn test_parse_frontmatter_scalars_and_lists() {
+    let content = "---\ntitle: \"My Note\"\ntags: [work, ideas]\naliases:\n  - first\n  - second\n---\n# Body\n";
+    let frontmatter = parse_frontmatter(content).expect("Should parse front-matter");
+
+    assert_eq!(frontmatter.get_text("title"), Some("My Note"));
+    assert_eq!(
+        frontmatter.get("tags"),
+        Some(&FrontmatterValue::List(vec![
+            "work".to_string(),
+            "ideas".to_string()
+        ]))
+    );
+    assert_eq!(
+        frontmatter.get("aliases").map(|v| v.as_list()),
+        Some(vec!["first".to_string(), "second".to_string()])
+    );
+    assert_eq!(frontmatter.line_count, 7);
+}
+
+#[test]
+fn test_parse_frontmatter_requires_leading_fence() {
+    assert!(parse_frontmatter("# Title\n---\ntitle: x\n---\n").is_none());
+    assert!(parse_frontmatter("---\ntitle: unterminated\n").is_none());
+}
+
+#[test]
+fn test_serialize_frontmatter_roundtrip() {
+    let fields = vec![
+        (
+            "title".to_string(),
+            FrontmatterValue::Text("New: value".to_string()),
+        ),
+        (
+            "tags".to_string(),
+            FrontmatterValue::List(vec!["a".to_string(), "b".to_string()]),
+        ),
+    ];
+
+    let updated = format!("{}Body text\n", serialize_frontmatter(&fields));
+    assert!(updated.ends_with("---\nBody text\n"));
+
+    let reparsed = parse_frontmatter(&updated).expect("Should reparse");
+    assert_eq!(reparsed.get_text("title"), Some("New: value"));
+    assert_eq!(reparsed.get("tags").map(|v| v.as_list().len()), Some(2));
+}
+
+#[test]
+fn test_extract_links_skips_code_and_external() {
+    let content = "See [[Other Note#Intro|intro]] and [doc](docs/guide.md).\n```\n[[not a link]]\n```\n[site](https://example.com) ![[embedded]]";
+    let links = extract_links(content);
+
+    assert_eq!(links.len(), 3);
+    assert_eq!(links[0].kind, LinkKind::Wikilink);
+    assert_eq!(links[0].target, "Other Note");
+    assert_eq!(links[0].heading.as_deref(), Some("Intro"));
+    assert_eq!(links[0].alias.as_deref(), Some("intro"));
+    assert_eq!(links[1].kind, LinkKind::Markdown);
+    assert_eq!(links[1].target, "docs/guide.md");
+    assert_eq!(links[2].kind, LinkKind::Embed);
+    assert_eq!(links[2].line, 5);
+}
+
+#[test]
+fn test_resolve_link_targets() {
+    let notes = vec![
+        "projects/Other Note.md".to_string(),
+        "projects/docs/guide.md".to_string(),
+    ];
+    let links = extract_links("[[other note]] [g](docs/guide.md) [[missing]]");
+
+    assert_eq!(
+        resolve_link_target(&links[0], "projects/index.md", &notes).as_deref(),
+        Some("projects/Other Note.md")
+    );
+    assert_eq!(
+        resolve_link_target(&links[2], "projects/index.md", &notes).as_deref(),
+        Some("projects/docs/guide.md")
+    );
+    assert!(resolve_link_target(&links[1], "projects/index.md", &notes).is_none());
+}
+
+#[test]
+fn test_lint_reports_each_rule() {
+    let config = LintConfig {
+        max_line_length: 20,
+        required_frontmatter_fields: vec!["status".to_string()],
+        ..LintConfig::default()
+    };
+    let content = "# Title\ntrailing \n## Section\nthis line is definitely longer than twenty\n## Section\n[[Nowhere]]\n";
+
+    let diagnostics = lint_content("note.md", content, &config, &["note.md".to_string()]);
+    let found = rules(&diagnostics);
+
+    assert!(found.contains(&"missing-frontmatter-field"));
+    assert!(found.contains(&"trailing-whitespace"));
+    assert!(found.contains(&"line-too-long"));
+    assert!(found.contains(&"duplicate-heading"));
+    assert!(found.contains(&"broken-link"));
+
+    let duplicate = diagnostics
+        .iter()
+        .find(|d| d.rule == "duplicate-heading")
+        .unwrap();
+    assert_eq!(duplicate.line, 5);
+
+    let trailing = diagnostics
+        .iter()
+        .find(|d| d.rule == "trailing-whitespace")
+        .unwrap();
+    assert_eq!(
+        (trailing.line, trailing.column, trailing.end_column),
+        (2, 9, 10)
+    );
+}
+
+#[test]
+fn test_lint_respects_disabled_checks_and_hard_breaks() {
+    let config = LintConfig {
+        check_broken_links: false,
+        check_duplicate_headings: false,
+        max_line_length: 0,
+        ..LintConfig::default()
+    };
+    let content = "line with hard break  \n# A\n# A\n[[missing]]\n";
+
+    assert!(lint_content("note.md", content, &config, &[]).is_empty());
+}
+
+fn schema_config() -> LintConfig {
+    LintConfig {
+        required_frontmatter_fields: vec!["status".to_string()],
+        field_types: BTreeMap::from([
+            ("due".to_string(), "date".to_string()),
+            ("priority".to_string(), "number".to_string()),
+            ("tags".to_string(), "list".to_string()),
+            ("draft".to_string(), "boolean".to_string()),
+        ]),
+        filename_pattern: "^[a-z0-9-]+$".to_string(),
+        field_name_pattern: "^[a-z_]+$".to_string(),
+        ..LintConfig::default()
+    }
+}
+
+#[test]
+fn test_lint_vault_groups_issues_by_rule() {
+    let conn = notes_db(&[
+        (
+            "good-note.md",
+            "---\nstatus: done\ndue: 2024-06-01T09:30\npriority: 2.5\ntags: [a, b]\ndraft: yes\n---\nBody",
+        ),
+        (
+            "projects/Bad Name.md",
+            "---\nstatus:\ndue: June\npriority: high\ntags: a\ndraft: maybe\nDueDate: x\nstatus: open\n---\nBody",
+        ),
+        ("no-frontmatter.md", "Just text"),
+        ("broken.md", "---\nstatus: open\nBody without a closing fence"),
+    ]);
+    let config = schema_config();
+    let report = lint_vault(&conn, &VaultLintRules::new(&config).unwrap()).unwrap();
+
+    assert_eq!(report.notes_checked, 4);
+    assert_eq!(report.notes_with_issues, 3);
+
+    let issues = |rule: &str| -> Vec<(String, usize)> {
+        report.issues[rule]
+            .iter()
+            .map(|issue| (issue.note_name.clone(), issue.line))
+            .collect()
+    };
+    assert_eq!(
+        issues("invalid-field-type"),
+        vec![
+            ("projects/Bad Name.md".to_string(), 3),
+            ("projects/Bad Name.md".to_string(), 4),
+            ("projects/Bad Name.md".to_string(), 5),
+            ("projects/Bad Name.md".to_string(), 6),
+        ]
+    );
+    assert_eq!(
+        issues("missing-frontmatter-field"),
+        vec![
+            ("broken.md".to_string(), 1),
+            ("no-frontmatter.md".to_string(), 1),
+            ("projects/Bad Name.md".to_string(), 2),
+        ]
+    );
+    assert_eq!(
+        issues("duplicate-field"),
+        vec![("projects/Bad Name.md".to_string(), 8)]
+    );
+    assert_eq!(
+        issues("field-name-convention"),
+        vec![("projects/Bad Name.md".to_string(), 7)]
+    );
+    assert_eq!(
+        issues("filename-convention"),
+        vec![("projects/Bad Name.md".to_string(), 1)]
+    );
+    assert_eq!(
+        issues("unterminated-frontmatter"),
+        vec![("broken.md".to_string(), 1)]
+    );
+}
+
+#[test]
+fn test_lint_vault_invalid_pattern_is_an_error() {
+    let config = LintConfig {
+        filename_pattern: "([a-z".to_string(),
+        ..LintConfig::default()
+    };
+    assert!(VaultLintRules::new(&config).is_err());
+
+    let conn = notes_db(&[("Any Name.md", "---\nx: 1\n---\n")]);
+    let config = LintConfig::default();
+    let report = lint_vault(&conn, &VaultLintRules::new(&config).unwrap()).unwrap();
+    assert!(report.issues.is_empty());
+}
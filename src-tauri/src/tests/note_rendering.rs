@@ -2,7 +2,16 @@
 //!
 //! Tests for note content rendering functionality.
 
-use crate::utilities::note_renderer::render_note;
+use crate::services::render_service::{
+    has_embeds, invalidate_stale_renders, notes_needing_render, render_with_embedded_notes,
+};
+use crate::utilities::mermaid::configure_mermaid_cli;
+use crate::utilities::note_renderer::{
+    note_outline, note_plain_text, render_note, render_note_with_embeds, render_version,
+    OutlineHeading,
+};
+use rusqlite::{params, Connection};
+use serial_test::serial;
 
 // Import the private function for testing
 use crate::utilities::note_renderer::linkify_urls_in_html;
@@ -18,6 +27,20 @@ fn test_render_markdown_note() {
     assert!(result.contains("bold"));
 }
 
+#[test]
+fn test_render_markdown_note_hides_frontmatter() {
+    let content = "---\ntitle: Plan\ntags: [work]\n---\n# Plan\n\nBody";
+    let result = render_note("test.md", content);
+
+    assert!(!result.contains("title"));
+    assert!(!result.contains("<hr"));
+    assert!(result.starts_with("<h1>Plan</h1>"));
+    assert!(result.contains("<p>Body</p>"));
+
+    // A thematic break later in the note still renders
+    assert!(render_note("test.md", "Intro\n\n---\n\nMore").contains("<hr />"));
+}
+
 #[test]
 fn test_render_plain_text_note() {
     let text_content = "This is plain text with <script>alert('xss')</script>";
@@ -86,3 +109,207 @@ fn test_render_plain_text_with_urls() {
     assert!(result.contains(r#"<a href="https://example.com" target="_blank" rel="noopener noreferrer">https://example.com</a>"#));
     assert!(result.ends_with("</pre>"));
 }
+
+#[test]
+fn test_note_outline_lists_headings_with_lines_and_anchors() {
+    let content = "# Plan\n\nIntro\n\n## Goals & `Risks`\n```\n# not a heading\n```\nSetext\n------\n## Goals & Risks\n### !!!";
+    let outline = note_outline("plan.md", content);
+
+    let heading = |text: &str, level, line, anchor: &str| OutlineHeading {
+        text: text.to_string(),
+        level,
+        line,
+        anchor: anchor.to_string(),
+    };
+    assert_eq!(
+        outline,
+        vec![
+            heading("Plan", 1, 1, "plan"),
+            heading("Goals & Risks", 2, 5, "goals--risks"),
+            heading("Setext", 2, 9, "setext"),
+            heading("Goals & Risks", 2, 11, "goals--risks-1"),
+            heading("!!!", 3, 12, "section"),
+        ]
+    );
+}
+
+#[test]
+fn test_note_outline_is_empty_for_plain_text() {
+    assert!(note_outline("notes.txt", "# Not markdown").is_empty());
+}
+
+#[test]
+fn test_note_plain_text_strips_markdown() {
+    let content = "---\ntitle: Draft\n---\n# Cover *letter*\n\nSee [[people/Ana|Ana]] and [the site](https://example.com).\nSecond line with `[[kept]]`.\n\n- first\n  1. nested\n- [x] done\n\n```\nlet x = [[raw]];\n```\n\n<div>html</div>\n\n| a | b |\n|---|---|\n| 1 | 2 |";
+
+    assert_eq!(
+        note_plain_text("letter.md", content),
+        "Cover letter\n\nSee Ana and the site.\nSecond line with [[kept]].\n\n• first\n  1. nested\n• [x] done\n\nlet x = [[raw]];\n\na\tb\n1\t2"
+    );
+}
+
+#[test]
+fn test_note_plain_text_keeps_wikilink_targets_and_plain_notes() {
+    assert_eq!(
+        note_plain_text(
+            "a.md",
+            "Go to [[Plan#Goals]], [[#Intro]] or ![[diagram.png]]"
+        ),
+        "Go to Plan, Intro or diagram.png"
+    );
+    assert_eq!(note_plain_text("a.txt", "# [[kept]]\n"), "# [[kept]]\n");
+}
+
+fn embed_notes(target: &str) -> Option<(String, String)> {
+    let notes = [
+        ("Intro.md", "---\ntitle: Intro\n---\nHello **there**"),
+        (
+            "Plan.md",
+            "# Plan\n## Goals\nShip it\n### Detail\nSmall\n## Risks\nNone",
+        ),
+        ("Loop.md", "Loop start ![[Back]]"),
+        ("Back.md", "Back again ![[Loop]]"),
+        ("log.txt", "plain <text>"),
+    ];
+    notes
+        .iter()
+        .find(|(name, _)| name.to_lowercase().starts_with(&target.to_lowercase()))
+        .map(|(name, content)| (name.to_string(), content.to_string()))
+}
+
+#[test]
+fn test_render_note_with_embeds_inlines_notes_and_sections() {
+    let html = render_note_with_embeds(
+        "Main.md",
+        "Before\n\n![[Intro]]\n\n![[Plan#goals]]\n\nSee ![[log]] and ![[Missing]]\n\n```\n![[Intro]]\n```",
+        &embed_notes,
+    );
+
+    assert!(
+        html.contains("<div class=\"note-embed\">\n<p>Hello <strong>there</strong></p>\n</div>")
+    );
+    assert!(!html.contains("title: Intro"));
+    assert!(html.contains("<h2>Goals</h2>\n<p>Ship it</p>\n<h3>Detail</h3>\n<p>Small</p>\n</div>"));
+    assert!(!html.contains("Risks"));
+    assert!(html.contains("<pre>plain &lt;text&gt;</pre>"));
+    assert!(html.contains("![[Missing]]"));
+    assert!(html.contains("<code>![[Intro]]\n</code>"));
+    // Without a resolver embeds stay as written
+    assert_eq!(render_note("Main.md", "![[Intro]]"), "<p>![[Intro]]</p>\n");
+}
+
+#[test]
+fn test_render_note_with_embeds_stops_cycles() {
+    let html = render_note_with_embeds("Loop.md", "Loop start ![[Back]]", &embed_notes);
+
+    assert_eq!(html.matches("note-embed").count(), 1);
+    assert!(html.contains("Back again ![[Loop]]"));
+
+    let html = render_note_with_embeds("Main.md", "# Top\nA ![[#Top]]", &embed_notes);
+    assert_eq!(html.matches("note-embed").count(), 1);
+}
+
+#[test]
+fn test_render_with_embedded_notes_reads_current_notes() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch("CREATE TABLE notes (filename TEXT, content TEXT);")
+        .unwrap();
+    for (filename, content) in [("work/Part.md", "Old text"), ("Main.md", "![[part]]")] {
+        conn.execute(
+            "INSERT INTO notes (filename, content) VALUES (?1, ?2)",
+            params![filename, content],
+        )
+        .unwrap();
+    }
+    conn.execute(
+        "UPDATE notes SET content = 'New text' WHERE filename = 'work/Part.md'",
+        [],
+    )
+    .unwrap();
+
+    assert!(has_embeds("![[part]]"));
+    assert!(!has_embeds("![[part] and [[link]]"));
+    let html = render_with_embedded_notes(&conn, "Main.md", "![[part]]").unwrap();
+    assert_eq!(
+        html,
+        "<div class=\"note-embed\">\n<p>New text</p>\n</div>\n"
+    );
+}
+
+#[test]
+fn test_render_note_uses_markdown_extensions() {
+    let content = "| A | B |\n|---|---|\n| 1 | 2 |\n\n- [x] done\n- [ ] todo\n\n~~old~~ text[^1]\n\n[^1]: A note.\n";
+    let html = render_note("Note.md", content);
+
+    assert!(html.contains("<table>"));
+    assert!(html.contains("<input disabled=\"\" type=\"checkbox\" checked=\"\"/>"));
+    assert!(html.contains("<del>old</del>"));
+    assert!(html.contains("footnote-definition"));
+}
+
+#[test]
+#[serial]
+fn test_invalidate_stale_renders_queues_notes_once() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE notes (filename TEXT, content TEXT, modified INTEGER, is_indexed INTEGER);
+         INSERT INTO notes VALUES ('a.md', 'A', 1, 1), ('b.md', 'B', 2, 1);",
+    )
+    .unwrap();
+
+    assert!(invalidate_stale_renders(&conn).unwrap());
+    let indexed: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM notes WHERE is_indexed = 1",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(indexed, 0);
+    assert_eq!(
+        notes_needing_render(&conn, false).unwrap(),
+        vec!["b.md", "a.md"]
+    );
+
+    conn.execute("UPDATE notes SET is_indexed = 1", []).unwrap();
+    assert!(!invalidate_stale_renders(&conn).unwrap());
+    assert!(notes_needing_render(&conn, false).unwrap().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_render_note_wraps_mermaid_blocks() {
+    let html = render_note(
+        "Flow.md",
+        "```mermaid\ngraph TD\n  A-->B\n```\n\n```rust\nfn a() {}\n```\n",
+    );
+    assert!(html.contains(
+        "<div class=\"mermaid-diagram\"><pre class=\"mermaid\">graph TD\n  A--&gt;B\n</pre></div>"
+    ));
+    assert!(html.contains("<code class=\"language-rust\">"));
+    assert!(!html.contains("language-mermaid"));
+}
+
+#[cfg(unix)]
+#[test]
+#[serial]
+fn test_render_note_prerenders_mermaid_with_cli() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp = tempfile::TempDir::new().unwrap();
+    let cli = temp.path().join("mmdc");
+    // Called as `mmdc -i input -o output -b transparent`
+    std::fs::write(&cli, "#!/bin/sh\nprintf '<svg/>' > \"$4\"\n").unwrap();
+    std::fs::set_permissions(&cli, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let version = render_version();
+
+    configure_mermaid_cli(Some(cli.to_str().unwrap()));
+    let html = render_note("Flow.md", "```mermaid\ngraph TD\n```\n");
+    let prerendered_version = render_version();
+    configure_mermaid_cli(None);
+
+    assert!(html.contains("src=\"data:image/svg+xml;base64,PHN2Zy8+\""));
+    assert!(!html.contains("<pre"));
+    assert_ne!(prerendered_version, version);
+    assert_eq!(render_version(), version);
+}
@@ -0,0 +1,47 @@
+//! Note Section Unit Tests
+//!
+//! Tests for splitting notes into sections at their headings.
+
+use crate::utilities::note_sections::{section_file_stem, split_sections, NoteSection};
+
+#[test]
+fn test_split_sections_at_headings_of_a_level() {
+    let content = "---\ntitle: Plan\n---\nIntro text\n\n# Goals\nShip it\n## Detail\nMore\n\n# `Budget` 2025\n\n```\n# not a heading\n```\n";
+    let sections = split_sections(content, 1);
+
+    assert_eq!(
+        sections,
+        vec![
+            NoteSection {
+                title: "Goals".to_string(),
+                content: "# Goals\nShip it\n## Detail\nMore\n".to_string(),
+            },
+            NoteSection {
+                title: "Budget 2025".to_string(),
+                content: "# `Budget` 2025\n\n```\n# not a heading\n```\n".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_split_sections_end_at_higher_level_headings() {
+    let content = "# Part\n## One\nA\n# Next part\nB\n## Two\nC";
+    let sections = split_sections(content, 2);
+
+    let titles: Vec<&str> = sections.iter().map(|s| s.title.as_str()).collect();
+    assert_eq!(titles, vec!["One", "Two"]);
+    assert_eq!(sections[0].content, "## One\nA\n");
+    assert_eq!(sections[1].content, "## Two\nC\n");
+    assert!(split_sections("No headings here", 1).is_empty());
+}
+
+#[test]
+fn test_section_file_stem_drops_unsafe_characters() {
+    assert_eq!(
+        section_file_stem("Q3: What/Why?  Plan").as_deref(),
+        Some("Q3 WhatWhy Plan")
+    );
+    assert_eq!(section_file_stem("..hidden").as_deref(), Some("hidden"));
+    assert_eq!(section_file_stem(" ?* "), None);
+}
@@ -0,0 +1,64 @@
+//! Outline Export Unit Tests
+//!
+//! Tests for the numbered outline of a folder's notes.
+
+use crate::services::outline_service::{folder_outline, outline_document};
+use crate::tests::test_utils::notes_db;
+
+#[test]
+fn test_outline_numbers_notes_and_headings() {
+    let notes = vec![
+        (
+            "research/methods.md".to_string(),
+            "---\norder: 2\n---\n# Methods\n## Sampling\n### Size\n## Analysis".to_string(),
+        ),
+        (
+            "research/intro.md".to_string(),
+            "---\norder: 1\n---\n# Introduction\nText\n### Skipped level\n## Scope".to_string(),
+        ),
+        (
+            "research/appendix.md".to_string(),
+            "No headings".to_string(),
+        ),
+    ];
+
+    assert_eq!(
+        outline_document("research", notes, 2),
+        "# research\n\n## 1. Introduction\n### 1.1. Skipped level\n### 1.2. Scope\n\n\
+         ## 2. Methods\n### 2.1. Sampling\n#### 2.1.1. Size\n### 2.2. Analysis\n\n\
+         ## 3. appendix\n"
+    );
+}
+
+#[test]
+fn test_outline_depth_limits_headings() {
+    let notes = vec![(
+        "r/a.md".to_string(),
+        "# A\n## One\n### Deep\n## Two".to_string(),
+    )];
+
+    assert_eq!(
+        outline_document("r", notes.clone(), 1),
+        "# r\n\n## 1. A\n### 1.1. One\n### 1.2. Two\n"
+    );
+    assert_eq!(outline_document("r", notes, 0), "# r\n\n## 1. A\n");
+}
+
+#[test]
+fn test_folder_outline_skips_other_folders_and_private_notes() {
+    let conn = notes_db(&[
+        ("research/a.md", "# A"),
+        ("research/deep/b.md", "# B"),
+        (
+            "research/secret.md",
+            "---\nvisibility: private\n---\n# Secret",
+        ),
+        ("researcher/c.md", "# C"),
+        ("top.md", "# Top"),
+    ]);
+
+    assert_eq!(
+        folder_outline(&conn, "research", 2).unwrap(),
+        "# research\n\n## 1. A\n\n## 2. B\n"
+    );
+}
@@ -0,0 +1,109 @@
+//! Path Access Unit Tests
+//!
+//! Tests for read authorization outside the vault and persisted path grants.
+
+use crate::core::AppError;
+use crate::services::path_access_service::{
+    add_path_grant, authorize_read, is_path_authorized, load_path_grants, remove_path_grant,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+#[test]
+fn test_is_path_authorized() {
+    let roots = vec![PathBuf::from("/vault")];
+    let grants = vec![PathBuf::from("/themes/dark.css"), PathBuf::from("/shared")];
+
+    assert!(is_path_authorized(
+        Path::new("/vault/a.md"),
+        &roots,
+        &grants
+    ));
+    assert!(is_path_authorized(
+        Path::new("/themes/dark.css"),
+        &roots,
+        &grants
+    ));
+    assert!(is_path_authorized(
+        Path::new("/shared/x/y.zip"),
+        &roots,
+        &grants
+    ));
+    assert!(!is_path_authorized(
+        Path::new("/vault-other/a.md"),
+        &roots,
+        &grants
+    ));
+    assert!(!is_path_authorized(
+        Path::new("/themes/light.css"),
+        &roots,
+        &grants
+    ));
+}
+
+#[test]
+fn test_authorize_read_resolves_traversal() {
+    let dir = TempDir::new().unwrap();
+    let vault = dir.path().join("vault");
+    let outside = dir.path().join("outside");
+    fs::create_dir_all(&vault).unwrap();
+    fs::create_dir_all(&outside).unwrap();
+    fs::write(vault.join("theme.css"), "a").unwrap();
+    fs::write(outside.join("secret.css"), "b").unwrap();
+
+    let roots = vec![vault.canonicalize().unwrap()];
+    let grants_path = dir.path().join("grants.json");
+
+    assert!(authorize_read(
+        &vault.join("theme.css").to_string_lossy(),
+        &roots,
+        &grants_path
+    )
+    .is_ok());
+
+    let sneaky = vault.join("..").join("outside").join("secret.css");
+    let result = authorize_read(&sneaky.to_string_lossy(), &roots, &grants_path);
+    assert!(matches!(result, Err(AppError::FilePermission(_))));
+
+    // Missing files outside the roots are refused, not reported missing
+    let probe = outside.join("missing.css");
+    assert!(matches!(
+        authorize_read(&probe.to_string_lossy(), &roots, &grants_path),
+        Err(AppError::FilePermission(_))
+    ));
+
+    add_path_grant(&grants_path, &outside.to_string_lossy()).unwrap();
+    assert!(authorize_read(&sneaky.to_string_lossy(), &roots, &grants_path).is_ok());
+    assert!(matches!(
+        authorize_read(&probe.to_string_lossy(), &roots, &grants_path),
+        Err(AppError::FileNotFound(_))
+    ));
+
+    let missing = vault.join("missing.css");
+    assert!(matches!(
+        authorize_read(&missing.to_string_lossy(), &roots, &grants_path),
+        Err(AppError::FileNotFound(_))
+    ));
+}
+
+#[test]
+fn test_path_grants_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let grants_path = dir.path().join("grants.json");
+    let target = dir.path().join("themes");
+    fs::create_dir_all(&target).unwrap();
+
+    assert!(load_path_grants(&grants_path).unwrap().is_empty());
+    let grants = add_path_grant(&grants_path, &target.to_string_lossy()).unwrap();
+    assert_eq!(grants.len(), 1);
+    // Granting twice keeps one entry
+    add_path_grant(&grants_path, &target.to_string_lossy()).unwrap();
+    assert_eq!(load_path_grants(&grants_path).unwrap().len(), 1);
+
+    assert!(add_path_grant(&grants_path, "/does/not/exist").is_err());
+
+    assert!(remove_path_grant(&grants_path, &target.to_string_lossy()).unwrap());
+    assert!(!remove_path_grant(&grants_path, &target.to_string_lossy()).unwrap());
+    assert!(load_path_grants(&grants_path).unwrap().is_empty());
+}
@@ -0,0 +1,108 @@
+//! Project Overview Unit Tests
+//!
+//! Tests for task parsing and per-folder dashboard aggregation.
+
+use crate::services::project_service::{normalize_project_folder, project_overview};
+use crate::services::publish_service::SourceNote;
+use crate::utilities::tasks::{extract_tasks, parse_task_line};
+use chrono::NaiveDate;
+
+fn note(filename: &str, content: &str, modified: i64) -> SourceNote {
+    SourceNote {
+        filename: filename.to_string(),
+        content: content.to_string(),
+        modified,
+    }
+}
+
+#[test]
+fn test_parse_task_lines() {
+    let task = parse_task_line("  - [ ] @ana send   draft 📅 2024-06-07", 3).unwrap();
+    assert_eq!(task.text, "@ana send draft");
+    assert_eq!(task.owner.as_deref(), Some("ana"));
+    assert_eq!(task.due.as_deref(), Some("2024-06-07"));
+    assert!(!task.done);
+    assert_eq!(task.line, 3);
+
+    let done = parse_task_line("1. [X] Ship ✅ 2024-06-04", 1).unwrap();
+    assert!(done.done);
+    assert_eq!(done.completed.as_deref(), Some("2024-06-04"));
+    assert_eq!(done.text, "Ship");
+
+    assert!(parse_task_line("- plain item", 1).is_none());
+    assert!(parse_task_line("- [ ] 📅 2024-06-07", 1).is_none());
+
+    let tasks =
+        extract_tasks("---\ntodo: - [ ] not a task\n---\n- [ ] real\n```\n- [ ] code\n```\n");
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].line, 4);
+}
+
+#[test]
+fn test_project_overview() {
+    let notes = vec![
+        note(
+            "Projects/Alpha/plan.md",
+            "---\ndeadline: 2024-07-01\n---\n- [ ] Later task\n- [ ] Urgent due:2024-06-10\n- [x] Done task\n- [ ] Overdue 📅 2024-05-01\nSee [[Projects/Alpha/spec]] and https://example.com/board.\n",
+            200,
+        ),
+        note(
+            "Projects/Alpha/notes/spec.md",
+            "# Spec\nBack to [[plan]] and [[Projects/Alpha/spec]]\n![[diagram.png]]\n",
+            300,
+        ),
+        note("Projects/Alphabet.md", "- [ ] Not in the folder", 400),
+        note("Inbox.md", "- [ ] Elsewhere", 500),
+    ];
+
+    let folder = normalize_project_folder("/Projects/Alpha/").unwrap();
+    assert_eq!(folder, "Projects/Alpha/");
+    let overview = project_overview(
+        &folder,
+        &notes,
+        NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+    );
+
+    assert_eq!(overview.folder, "Projects/Alpha");
+    assert_eq!(overview.note_count, 2);
+    let tasks: Vec<&str> = overview
+        .open_tasks
+        .iter()
+        .map(|t| t.text.as_str())
+        .collect();
+    assert_eq!(tasks, vec!["Overdue", "Urgent", "Later task"]);
+
+    let recent: Vec<&str> = overview
+        .recent_notes
+        .iter()
+        .map(|n| n.note_name.as_str())
+        .collect();
+    assert_eq!(
+        recent,
+        vec!["Projects/Alpha/notes/spec.md", "Projects/Alpha/plan.md"]
+    );
+
+    let upcoming: Vec<(&str, &str)> = overview
+        .upcoming
+        .iter()
+        .map(|u| (u.date.as_str(), u.label.as_str()))
+        .collect();
+    assert_eq!(
+        upcoming,
+        vec![("2024-06-10", "Urgent"), ("2024-07-01", "plan (deadline)")]
+    );
+
+    assert_eq!(overview.key_links[0].target, "Projects/Alpha/spec");
+    assert_eq!(overview.key_links[0].count, 2);
+    assert!(overview
+        .key_links
+        .iter()
+        .any(|link| link.external && link.target == "https://example.com/board"));
+    assert!(!overview
+        .key_links
+        .iter()
+        .any(|link| link.target == "diagram.png"));
+
+    assert!(normalize_project_folder(" / ").is_err());
+    assert!(normalize_project_folder("../secrets").is_err());
+}
@@ -0,0 +1,147 @@
+//! Property Query Unit Tests
+//!
+//! Tests for parsing front-matter property filters and querying notes with them.
+
+use crate::services::metadata_service::query_notes_by_properties;
+use crate::tests::test_utils::notes_db;
+use crate::utilities::property_query::{
+    parse_property_filter, parse_property_sort, CompareOp, PropertyFilter, SortKey,
+};
+use rusqlite::Connection;
+
+fn sample_db() -> Connection {
+    notes_db(&[
+        (
+            "a.md",
+            "---\nstatus: Active\ndue: 2024-11-30\npriority: 10\ntags: [work, urgent]\n---\nA",
+            1,
+        ),
+        (
+            "b.md",
+            "---\nstatus: active\ndue: 2025-03-01\npriority: 2\n---\nB",
+            2,
+        ),
+        ("c.md", "---\nstatus: done\ndue: 2024-01-15\n---\nC", 3),
+        ("d.md", "No front matter", 4),
+    ])
+}
+
+fn query(conn: &Connection, filter: &str, sort: &str) -> Vec<String> {
+    let filter = parse_property_filter(filter).unwrap();
+    let sort = parse_property_sort(sort).unwrap();
+    query_notes_by_properties(conn, filter.as_ref(), &sort)
+        .unwrap()
+        .into_iter()
+        .map(|note| note.note_name)
+        .collect()
+}
+
+#[test]
+fn test_parse_precedence_and_values() {
+    let filter =
+        parse_property_filter("Status = \"in progress\" OR due < 2025-01-01 AND NOT draft")
+            .unwrap()
+            .unwrap();
+    assert_eq!(
+        filter,
+        PropertyFilter::Or(vec![
+            PropertyFilter::Compare {
+                field: "status".to_string(),
+                op: CompareOp::Eq,
+                value: "in progress".to_string(),
+            },
+            PropertyFilter::And(vec![
+                PropertyFilter::Compare {
+                    field: "due".to_string(),
+                    op: CompareOp::Lt,
+                    value: "2025-01-01".to_string(),
+                },
+                PropertyFilter::Not(Box::new(PropertyFilter::Exists("draft".to_string()))),
+            ]),
+        ])
+    );
+    assert_eq!(parse_property_filter("  ").unwrap(), None);
+}
+
+#[test]
+fn test_parse_errors() {
+    for filter in [
+        "status =",
+        "status = 'open",
+        "(status = open",
+        "status = open)",
+        "AND status",
+        "status ! open",
+        "a = 1 b = 2",
+    ] {
+        assert!(parse_property_filter(filter).is_err(), "{:?}", filter);
+    }
+    assert!(parse_property_filter(&format!("{}x{}", "(".repeat(40), ")".repeat(40))).is_err());
+}
+
+#[test]
+fn test_parse_sort() {
+    assert_eq!(
+        parse_property_sort("due, Priority desc").unwrap(),
+        vec![
+            SortKey {
+                field: "due".to_string(),
+                descending: false,
+            },
+            SortKey {
+                field: "priority".to_string(),
+                descending: true,
+            },
+        ]
+    );
+    assert!(parse_property_sort("").unwrap().is_empty());
+    assert!(parse_property_sort("due sideways").is_err());
+}
+
+#[test]
+fn test_query_filters_notes() {
+    let conn = sample_db();
+
+    assert_eq!(
+        query(&conn, "status = \"active\" AND due < 2025-01-01", ""),
+        vec!["a.md"]
+    );
+    // Numbers compare numerically, not as text
+    assert_eq!(query(&conn, "priority > 9", ""), vec!["a.md"]);
+    assert_eq!(
+        query(&conn, "priority >= 2", "priority"),
+        vec!["b.md", "a.md"]
+    );
+    // List values match when any item does
+    assert_eq!(query(&conn, "tags = urgent", ""), vec!["a.md"]);
+    // Missing fields only satisfy !=
+    assert_eq!(
+        query(&conn, "status != done", ""),
+        vec!["d.md", "b.md", "a.md"]
+    );
+    assert_eq!(query(&conn, "NOT priority", ""), vec!["d.md", "c.md"]);
+    assert_eq!(
+        query(&conn, "(status = done OR priority = 2) AND due", ""),
+        vec!["c.md", "b.md"]
+    );
+}
+
+#[test]
+fn test_query_sorts_with_missing_fields_last() {
+    let conn = sample_db();
+
+    assert_eq!(query(&conn, "", ""), vec!["d.md", "c.md", "b.md", "a.md"]);
+    assert_eq!(
+        query(&conn, "", "due"),
+        vec!["c.md", "a.md", "b.md", "d.md"]
+    );
+    assert_eq!(
+        query(&conn, "", "status desc, priority"),
+        vec!["c.md", "b.md", "a.md", "d.md"]
+    );
+
+    let notes = query_notes_by_properties(&conn, None, &[]).unwrap();
+    let a = notes.iter().find(|note| note.note_name == "a.md").unwrap();
+    assert_eq!(a.fields["tags"], "work, urgent");
+    assert_eq!(a.fields["status"], "Active");
+}
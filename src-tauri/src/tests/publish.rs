@@ -0,0 +1,219 @@
+//! Publish Unit Tests
+//!
+//! Tests for static site generation and tag extraction.
+
+use crate::services::publish_service::{
+    is_exportable, is_in_selected_folders, list_publishable, publish_flag, publish_notes,
+    rewrite_note_links, slugify, PublishFlag, PublishOptions, SourceNote, SITE_MARKER_FILE,
+};
+use crate::utilities::strings::{note_excerpt, parse_note_date};
+use crate::utilities::tags::extract_tags;
+use std::fs;
+use tempfile::TempDir;
+
+fn note(name: &str, content: &str) -> SourceNote {
+    SourceNote {
+        filename: name.to_string(),
+        content: content.to_string(),
+        modified: 1_700_000_000,
+    }
+}
+
+#[test]
+fn test_extract_tags_from_frontmatter_and_body() {
+    let content = "---\ntags: [Work, ideas]\n---\n# Heading\nSome #project/alpha text #123 and (#work).\n```\n#not-a-tag\n```\n";
+    assert_eq!(
+        extract_tags(content),
+        vec!["work", "ideas", "project/alpha"]
+    );
+}
+
+#[test]
+fn test_folder_selection_and_slugs() {
+    let folders = vec!["garden/".to_string()];
+    assert!(is_in_selected_folders("garden/a.md", &folders));
+    assert!(is_in_selected_folders("garden/deep/b.md", &folders));
+    assert!(!is_in_selected_folders("gardening/c.md", &folders));
+    assert!(is_in_selected_folders("anything.md", &[]));
+
+    assert_eq!(slugify("garden/My Note (v2)"), "garden-my-note-v2");
+    assert_eq!(slugify("???"), "note");
+}
+
+#[test]
+fn test_rewrite_note_links() {
+    let published = vec!["a.md".to_string()];
+    let content = "See [[A|first]], [[Private]] and [doc](a.md) or [pdf](file.pdf).";
+    let rewritten = rewrite_note_links(content, "b.md", &published, |target| {
+        (target == "a.md").then(|| "a.html".to_string())
+    });
+    assert_eq!(
+        rewritten,
+        "See [first](a.html), Private and [doc](a.html) or [pdf](file.pdf)."
+    );
+}
+
+#[test]
+fn test_rewrite_note_links_markdown_before_wikilink() {
+    let published = vec!["a.md".to_string()];
+    let content = "Read [doc](a.md) before [[A|the note]] and [[Private]].";
+    let rewritten = rewrite_note_links(content, "b.md", &published, |target| {
+        (target == "a.md").then(|| "a.html".to_string())
+    });
+    assert_eq!(
+        rewritten,
+        "Read [doc](a.html) before [the note](a.html) and Private."
+    );
+}
+
+#[test]
+fn test_publish_notes_writes_site() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output = temp_dir.path().join("site");
+    let notes = vec![
+        note(
+            "garden/Intro.md",
+            "# Intro\nLinks to [[Other]] and [[secret]]. #garden",
+        ),
+        note("garden/Other.md", "---\ntitle: The Other One\n---\nBody"),
+        note("private/secret.md", "Do not publish"),
+    ];
+    let options = PublishOptions {
+        folders: vec!["garden".to_string()],
+        site_title: "My Garden".to_string(),
+        ..PublishOptions::default()
+    };
+
+    let report = publish_notes(&notes, &output, &options).unwrap();
+    assert_eq!(report.notes_published, 2);
+    assert_eq!(report.tag_pages, 1);
+
+    let intro = fs::read_to_string(output.join("notes/garden-intro.html")).unwrap();
+    assert!(intro.contains(r#"<a href="garden-other.html">Other</a>"#));
+    assert!(intro.contains("and secret."));
+    assert!(intro.contains(r#"href="../tags/garden.html""#));
+
+    let index = fs::read_to_string(output.join("index.html")).unwrap();
+    assert!(index.contains("The Other One"));
+    assert!(!index.contains("secret"));
+    assert!(output.join(SITE_MARKER_FILE).exists());
+    assert!(!output.join("notes/private-secret.html").exists());
+
+    // Republishing replaces generated pages
+    publish_notes(&notes[..1], &output, &options).unwrap();
+    assert!(!output.join("notes/garden-other.html").exists());
+}
+
+#[test]
+fn test_publish_refuses_foreign_non_empty_directory() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("important.txt"), "keep me").unwrap();
+
+    let result = publish_notes(&[], temp_dir.path(), &PublishOptions::default());
+    assert!(result.is_err());
+    assert!(temp_dir.path().join("important.txt").exists());
+}
+
+#[test]
+fn test_publish_flags_filter_notes() {
+    let notes = vec![
+        note("open.md", "No front-matter"),
+        note("opt-in.md", "---\npublish: true\n---\nYes"),
+        note(
+            "hidden.md",
+            "---\nvisibility: private\npublish: true\n---\nNo",
+        ),
+        note("off.md", "---\npublish: false\n---\nNo"),
+    ];
+
+    assert_eq!(publish_flag(&notes[0].content), PublishFlag::Unset);
+    assert_eq!(publish_flag(&notes[1].content), PublishFlag::Public);
+    assert_eq!(publish_flag(&notes[2].content), PublishFlag::Private);
+    assert!(!is_exportable(&notes[3].content));
+
+    let names = |options: &PublishOptions| -> Vec<String> {
+        list_publishable(&notes, options)
+            .into_iter()
+            .map(|n| n.filename)
+            .collect()
+    };
+    assert_eq!(
+        names(&PublishOptions::default()),
+        vec!["open.md", "opt-in.md"]
+    );
+    assert_eq!(
+        names(&PublishOptions {
+            require_publish_flag: true,
+            ..PublishOptions::default()
+        }),
+        vec!["opt-in.md"]
+    );
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let report = publish_notes(&notes, temp_dir.path(), &PublishOptions::default()).unwrap();
+    assert_eq!(report.notes_published, 2);
+    assert!(!temp_dir.path().join("notes/hidden.html").exists());
+}
+
+#[test]
+fn test_note_excerpt_and_dates() {
+    let content = "---\ntitle: T\n---\n# Heading\n\nFirst paragraph with `code`.\n\n```\nskipped\n```\n\nSecond one here.";
+    assert_eq!(
+        note_excerpt(content, 200),
+        "First paragraph with code. Second one here."
+    );
+    assert_eq!(note_excerpt(content, 18), "First paragraph\u{2026}");
+    assert_eq!(
+        note_excerpt("---\nsummary: Given summary\n---\nBody", 200),
+        "Given summary"
+    );
+
+    let utc = parse_note_date("2024-03-05T10:00:00+02:00").unwrap();
+    assert_eq!(utc.to_rfc3339(), "2024-03-05T08:00:00+00:00");
+    assert!(parse_note_date("2024-03-05").is_some());
+    assert!(parse_note_date("2024-03-05 09:30").is_some());
+    assert!(parse_note_date("March 5th").is_none());
+}
+
+#[test]
+fn test_publish_writes_atom_feed() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output = temp_dir.path().join("site");
+    let notes = vec![
+        note(
+            "old.md",
+            "---\ndate: 2023-12-01T00:00:00Z\n---\nOld & dusty. #archive",
+        ),
+        note(
+            "new.md",
+            "---\ndate: 2024-06-01T00:00:00Z\n---\nFresh news: 1 < 2.",
+        ),
+        note("undated.md", "Falls back to the modified time."),
+    ];
+    let options = PublishOptions {
+        base_url: "https://example.com/notes/".to_string(),
+        feed_limit: 2,
+        ..Default::default()
+    };
+
+    let report = publish_notes(&notes, &output, &options).expect("publish failed");
+    assert_eq!(report.feed_entries, 2);
+
+    let feed = fs::read_to_string(output.join("feed.xml")).unwrap();
+    assert!(feed.contains("<updated>2024-06-01T00:00:00+00:00</updated>"));
+    assert!(feed.contains("<link rel=\"self\" href=\"https://example.com/notes/feed.xml\"/>"));
+    assert!(feed.contains("<id>https://example.com/notes/notes/new.html</id>"));
+    assert!(feed.contains("<summary>Fresh news: 1 &lt; 2.</summary>"));
+    assert!(feed.contains("<summary>Old &amp; dusty. #archive</summary>"));
+    assert!(feed.contains("<category term=\"archive\"/>"));
+    assert!(!feed.contains("undated"));
+    assert!(feed.find("new.html").unwrap() < feed.find("old.html").unwrap());
+
+    let options = PublishOptions {
+        generate_feed: false,
+        ..Default::default()
+    };
+    let report = publish_notes(&notes, &output, &options).expect("republish failed");
+    assert_eq!(report.feed_entries, 0);
+    assert!(!output.join("feed.xml").exists());
+}
@@ -0,0 +1,124 @@
+//! Query Console Unit Tests
+//!
+//! Tests for the statement allowlist, row caps and read-only enforcement.
+
+use crate::services::query_console_service::{
+    open_readonly, run_readonly_query, validate_readonly_sql, MAX_QUERY_LIMIT,
+};
+use crate::tests::test_utils::init_notes_db;
+use rusqlite::Connection;
+use tempfile::TempDir;
+
+fn numbered_notes(conn: &Connection, count: i64) {
+    let names: Vec<String> = (0..count).map(|i| format!("note{}.md", i)).collect();
+    let rows: Vec<(&str, &str, i64)> = names
+        .iter()
+        .zip(0..)
+        .map(|(name, modified)| (name.as_str(), "body", modified))
+        .collect();
+    init_notes_db(conn, &rows);
+}
+
+#[test]
+fn test_validate_readonly_sql() {
+    assert_eq!(
+        validate_readonly_sql("  select * from notes;  ").unwrap(),
+        "select * from notes"
+    );
+    assert!(validate_readonly_sql("WITH x AS (SELECT 1) SELECT * FROM x").is_ok());
+
+    for rejected in [
+        "",
+        ";",
+        "DELETE FROM notes",
+        "PRAGMA query_only = OFF",
+        "SELECT 1; DROP TABLE notes",
+        "ATTACH DATABASE 'x.db' AS x",
+        "-- comment\nSELECT 1",
+    ] {
+        assert!(
+            validate_readonly_sql(rejected).is_err(),
+            "should reject {:?}",
+            rejected
+        );
+    }
+}
+
+#[test]
+fn test_run_readonly_query_returns_columns_and_values() {
+    let conn = Connection::open_in_memory().unwrap();
+    numbered_notes(&conn, 2);
+
+    let result = run_readonly_query(
+        &conn,
+        "SELECT filename, modified, NULL AS empty, 1.5 AS ratio, x'0102' AS data FROM notes ORDER BY modified",
+        10,
+    )
+    .unwrap();
+    assert_eq!(
+        result.columns,
+        vec!["filename", "modified", "empty", "ratio", "data"]
+    );
+    assert_eq!(
+        result.rows[0],
+        vec![
+            serde_json::json!("note0.md"),
+            serde_json::json!(0),
+            serde_json::Value::Null,
+            serde_json::json!(1.5),
+            serde_json::json!("<blob: 2 bytes>"),
+        ]
+    );
+    assert!(!result.truncated);
+}
+
+#[test]
+fn test_run_readonly_query_caps_rows() {
+    let conn = Connection::open_in_memory().unwrap();
+    numbered_notes(&conn, 5);
+
+    let result = run_readonly_query(&conn, "SELECT filename FROM notes", 3).unwrap();
+    assert_eq!(result.rows.len(), 3);
+    assert!(result.truncated);
+
+    let result = run_readonly_query(&conn, "SELECT filename FROM notes", 0).unwrap();
+    assert_eq!(result.rows.len(), 1);
+
+    let result = run_readonly_query(
+        &conn,
+        "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n LIMIT 5000) SELECT i FROM n",
+        usize::MAX,
+    )
+    .unwrap();
+    assert_eq!(result.rows.len(), MAX_QUERY_LIMIT);
+    assert!(result.truncated);
+}
+
+#[test]
+fn test_writes_disguised_as_cte_are_rejected() {
+    let conn = Connection::open_in_memory().unwrap();
+    numbered_notes(&conn, 1);
+
+    assert!(run_readonly_query(
+        &conn,
+        "WITH x AS (SELECT 1) DELETE FROM notes WHERE filename = 'note0.md'",
+        10
+    )
+    .is_err());
+    let count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn test_open_readonly_blocks_writes() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("notes.db");
+    numbered_notes(&Connection::open(&db_path).unwrap(), 3);
+
+    let conn = open_readonly(&db_path).unwrap();
+    let result = run_readonly_query(&conn, "SELECT COUNT(*) FROM notes", 10).unwrap();
+    assert_eq!(result.rows, vec![vec![serde_json::json!(3)]]);
+    assert!(conn.execute("DELETE FROM notes", []).is_err());
+}
@@ -0,0 +1,87 @@
+//! Quick Switcher Unit Tests
+//!
+//! Tests for fuzzy matching over filenames, aliases and headings.
+
+use crate::services::quick_switch_service::{fuzzy_match_filenames, quick_switch, SwitchMatchKind};
+use crate::tests::test_utils::notes_db;
+
+#[test]
+fn test_quick_switch_ranks_names_before_headings() {
+    let conn = notes_db(&[
+        ("Projects/Roadmap.md", "# Roadmap\n## Quarterly goals\n", 1),
+        (
+            "Ideas.md",
+            "---\naliases: [Brainstorm, Roadmap ideas]\n---\n# Ideas\n",
+            2,
+        ),
+        (
+            "Journal.md",
+            "The roadmap is mentioned only in the body.\n",
+            3,
+        ),
+    ]);
+
+    let results = quick_switch(&conn, "roadmap", 10).unwrap();
+    assert_eq!(results[0].note_name, "Projects/Roadmap.md");
+    assert_eq!(results[0].kind, SwitchMatchKind::Filename);
+    assert!(results
+        .iter()
+        .any(|r| r.note_name == "Ideas.md" && r.kind == SwitchMatchKind::Alias));
+    assert!(results.iter().all(|r| r.note_name != "Journal.md"));
+    // One name/alias entry per note
+    assert_eq!(
+        results.iter().filter(|r| r.note_name == "Ideas.md").count(),
+        1
+    );
+
+    let headings = quick_switch(&conn, "qrtly gls", 10).unwrap();
+    assert_eq!(headings[0].kind, SwitchMatchKind::Heading);
+    assert_eq!(headings[0].matched, "Quarterly goals");
+    assert_eq!(headings[0].line, Some(2));
+
+    let brainstorm = quick_switch(&conn, "brain", 10).unwrap();
+    assert_eq!(brainstorm[0].note_name, "Ideas.md");
+}
+
+#[test]
+fn test_quick_switch_empty_query_lists_recent_notes() {
+    let conn = notes_db(&[("Old.md", "", 1), ("New.md", "", 5)]);
+    let results = quick_switch(&conn, "  ", 1).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].note_name, "New.md");
+    assert_eq!(results[0].matched, "New");
+}
+
+#[test]
+fn test_fuzzy_match_filenames_scores_and_highlights() {
+    let conn = notes_db(&[
+        ("Projects/Roadmap.md", "", 1),
+        ("Reading/Random map notes.md", "", 2),
+        ("Journal.md", "", 3),
+    ]);
+
+    let results = fuzzy_match_filenames(&conn, "roadmap", 10).unwrap();
+    assert_eq!(results[0].filename, "Projects/Roadmap.md");
+    assert_eq!(results[0].indices, (9..16).collect::<Vec<u32>>());
+    assert!(results.iter().all(|r| r.filename != "Journal.md"));
+    assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+
+    // Smart case: an uppercase letter makes the query case-sensitive
+    assert!(fuzzy_match_filenames(&conn, "ROADMAP", 10)
+        .unwrap()
+        .is_empty());
+
+    // Space-separated terms must all match
+    let both = fuzzy_match_filenames(&conn, "proj road", 10).unwrap();
+    assert_eq!(both.len(), 1);
+    assert_eq!(both[0].filename, "Projects/Roadmap.md");
+}
+
+#[test]
+fn test_fuzzy_match_filenames_empty_query_lists_recent_notes() {
+    let conn = notes_db(&[("Old.md", "", 1), ("New.md", "", 5)]);
+    let results = fuzzy_match_filenames(&conn, "", 1).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].filename, "New.md");
+    assert!(results[0].indices.is_empty());
+}
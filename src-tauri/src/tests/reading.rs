@@ -0,0 +1,159 @@
+//! Reading List Unit Tests
+//!
+//! Tests for page clipping, the read-later queue and highlight extraction.
+
+use crate::services::clipper_service::{
+    clip_html, decode_page, reading_note_content, reading_note_name, reading_queue,
+    validate_clip_url, ClippedPage,
+};
+use crate::services::publish_service::SourceNote;
+use crate::utilities::highlights::{
+    build_highlights_note, find_highlights, highlights_note_name, highlights_origin, HighlightKind,
+};
+
+#[test]
+fn test_clip_html_keeps_article_content() {
+    let html = r#"<html><head><title> Rust &amp; You
+        </title><style>p { color: red }</style></head>
+        <body><nav><a href="/">Home</a></nav>
+        <article><h1>Heading</h1><script>track()</script><p>First <b>bold</b> paragraph.</p>
+
+
+        <p>Second.</p></article><footer>Copyright</footer></body></html>"#;
+
+    let page = clip_html("https://example.com/post", html);
+    assert_eq!(page.title, "Rust & You");
+    assert!(page.markdown.contains("Heading"));
+    assert!(page.markdown.contains("**bold**"));
+    assert!(page.markdown.contains("Second."));
+    assert!(!page.markdown.contains("track()"));
+    assert!(!page.markdown.contains("Home"));
+    assert!(!page.markdown.contains("Copyright"));
+    assert!(!page.markdown.contains("\n\n\n"));
+
+    let untitled = clip_html("https://example.com/x", "<p>Body only</p>");
+    assert_eq!(untitled.title, "https://example.com/x");
+}
+
+#[test]
+fn test_decode_page_handles_cut_and_legacy_pages() {
+    let mut cut = "<p>Caf\u{e9}</p>".as_bytes().to_vec();
+    cut.extend_from_slice(&"\u{20ac}".as_bytes()[..2]);
+    assert_eq!(decode_page(cut), "<p>Caf\u{e9}</p>");
+
+    let latin1 = b"<p>Caf\xe9 \x93quoted\x94</p>".to_vec();
+    assert_eq!(
+        decode_page(latin1),
+        "<p>Caf\u{e9} \u{201c}quoted\u{201d}</p>"
+    );
+
+    assert_eq!(decode_page(b"<p>a\0b</p>".to_vec()), "<p>a\0b</p>");
+}
+
+#[test]
+fn test_clip_url_validation_and_note_names() {
+    assert!(validate_clip_url(" https://example.com/a?b=1 ").is_ok());
+    assert!(validate_clip_url("http://localhost:8080").is_ok());
+    assert!(validate_clip_url("file:///etc/passwd").is_err());
+    assert!(validate_clip_url("https://").is_err());
+    assert!(validate_clip_url("javascript:alert(1)").is_err());
+
+    assert_eq!(
+        reading_note_name("A/B: C?", |_| false),
+        "Reading/A-B- C-.md"
+    );
+    assert_eq!(
+        reading_note_name("Post", |name| name == "Reading/Post.md"),
+        "Reading/Post 2.md"
+    );
+    assert_eq!(
+        reading_note_name("...", |_| false),
+        "Reading/Clipped page.md"
+    );
+}
+
+#[test]
+fn test_reading_queue_orders_by_add_date() {
+    let page = |title: &str| ClippedPage {
+        title: title.to_string(),
+        markdown: "Body".to_string(),
+    };
+    let note = |filename: &str, content: String| SourceNote {
+        filename: filename.to_string(),
+        content,
+        modified: 1_700_000_000,
+    };
+
+    let notes = vec![
+        note(
+            "Reading/Newer.md",
+            reading_note_content("https://b.example", &page("Newer"), "2024-03-02T10:00:00Z"),
+        ),
+        note(
+            "Reading/Older.md",
+            reading_note_content("https://a.example", &page("Older"), "2024-03-01T10:00:00Z"),
+        ),
+        note(
+            "Reading/Done.md",
+            "---\ntitle: Done\ntags: [read]\n---\nFinished".to_string(),
+        ),
+        note(
+            "Reading/Manual.md",
+            "# Manual\nSaved by hand #toread".to_string(),
+        ),
+        note("Elsewhere.md", "#toread but not in the folder".to_string()),
+    ];
+
+    let queue = reading_queue(&notes);
+    let names: Vec<&str> = queue.iter().map(|item| item.note_name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["Reading/Manual.md", "Reading/Older.md", "Reading/Newer.md"]
+    );
+    assert_eq!(queue[1].title, "Older");
+    assert_eq!(queue[1].url.as_deref(), Some("https://a.example"));
+    assert_eq!(queue[0].url, None);
+}
+
+#[test]
+fn test_find_highlights_marks_and_quotes() {
+    let content = "---\nsource: https://example.com\n---\n# Article\nPlain ==first mark== and ==second==.\n> A quoted\n> passage\n\n> Another\n```\n> not a quote\n==not a mark==\n```\nRepeat ==first mark== and a==b==c.\n";
+
+    let highlights = find_highlights(content);
+    let texts: Vec<&str> = highlights.iter().map(|h| h.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        vec!["first mark", "second", "A quoted\npassage", "Another", "b"]
+    );
+    assert_eq!(highlights[2].kind, HighlightKind::Quote);
+    assert_eq!(highlights[2].line, 6);
+    assert_eq!(highlights[0].kind, HighlightKind::Mark);
+}
+
+#[test]
+fn test_highlights_note_links_back_to_origin() {
+    let origin = "Reading/Article.md";
+    let content = "---\ntitle: Article\nsource: https://example.com/a\n---\n==Key idea==\n";
+    let note = build_highlights_note(origin, content, &find_highlights(content));
+
+    assert!(note.contains("From [[Reading/Article]] · <https://example.com/a>"));
+    assert!(note.contains("> Key idea\n"));
+    assert_eq!(highlights_origin(&note).as_deref(), Some(origin));
+    // The highlights note itself quotes passages but is recognised as derived
+    assert!(highlights_origin(content).is_none());
+
+    assert_eq!(
+        highlights_note_name(origin, |_| None),
+        "Highlights/Article.md"
+    );
+    assert_eq!(
+        highlights_note_name(origin, |name| (name == "Highlights/Article.md")
+            .then(|| note.clone())),
+        "Highlights/Article.md"
+    );
+    assert_eq!(
+        highlights_note_name("Other/Article.md", |name| (name == "Highlights/Article.md")
+            .then(|| note.clone())),
+        "Highlights/Article 2.md"
+    );
+}
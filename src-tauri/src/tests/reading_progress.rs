@@ -0,0 +1,88 @@
+//! Reading Progress Unit Tests
+//!
+//! Tests for per-note reading progress and remaining reading time estimates.
+
+use crate::core::AppError;
+use crate::services::note_details_service::{list_note_details, note_stats, NoteOrder};
+use crate::services::reading_progress_service::{
+    forget_reading_progress, minutes_left, move_reading_progress, set_reading_progress,
+    WORDS_PER_MINUTE,
+};
+use crate::tests::test_utils::notes_db;
+use rusqlite::Connection;
+use std::collections::HashSet;
+
+/// Progress as the note metadata reports it
+fn reading_progress(conn: &Connection, filename: &str) -> Option<f64> {
+    note_stats(conn, filename).unwrap().reading_progress
+}
+
+fn words(count: usize) -> String {
+    vec!["word"; count].join(" ")
+}
+
+#[test]
+fn test_minutes_left_rounds_up() {
+    assert_eq!(minutes_left(0, None), 0);
+    assert_eq!(minutes_left(1, None), 1);
+    assert_eq!(minutes_left(WORDS_PER_MINUTE * 4, None), 4);
+    assert_eq!(minutes_left(WORDS_PER_MINUTE * 4, Some(0.5)), 2);
+    assert_eq!(minutes_left(WORDS_PER_MINUTE * 4, Some(0.9)), 1);
+    assert_eq!(minutes_left(WORDS_PER_MINUTE * 4, Some(1.0)), 0);
+}
+
+#[test]
+fn test_set_reading_progress_validates_fraction() {
+    let conn = notes_db(&["article.md"]);
+    for fraction in [-0.1, 1.5, f64::NAN] {
+        assert!(matches!(
+            set_reading_progress(&conn, "article.md", fraction, 10),
+            Err(AppError::InvalidNoteName(_))
+        ));
+    }
+    assert_eq!(reading_progress(&conn, "article.md"), None);
+}
+
+#[test]
+fn test_set_reading_progress_overwrites_and_resets() {
+    let conn = notes_db(&["article.md"]);
+    set_reading_progress(&conn, "article.md", 0.25, 10).unwrap();
+    set_reading_progress(&conn, "article.md", 0.75, 20).unwrap();
+    assert_eq!(reading_progress(&conn, "article.md"), Some(0.75));
+
+    // Back to the start forgets the note's progress
+    set_reading_progress(&conn, "article.md", 0.0, 30).unwrap();
+    assert_eq!(reading_progress(&conn, "article.md"), None);
+}
+
+#[test]
+fn test_reading_progress_follows_rename_and_delete() {
+    let conn = notes_db(&["old.md", "new.md"]);
+    set_reading_progress(&conn, "old.md", 0.5, 10).unwrap();
+    set_reading_progress(&conn, "new.md", 0.1, 10).unwrap();
+
+    move_reading_progress(&conn, "old.md", "new.md").unwrap();
+    assert_eq!(reading_progress(&conn, "old.md"), None);
+    assert_eq!(reading_progress(&conn, "new.md"), Some(0.5));
+
+    forget_reading_progress(&conn, "new.md").unwrap();
+    assert_eq!(reading_progress(&conn, "new.md"), None);
+}
+
+#[test]
+fn test_note_metadata_includes_reading_time() {
+    let long = words(WORDS_PER_MINUTE * 10);
+    let conn = notes_db(&[("article.md", long.as_str()), ("short.md", "A few words")]);
+    set_reading_progress(&conn, "article.md", 0.4, 10).unwrap();
+
+    let stats = note_stats(&conn, "article.md").unwrap();
+    assert_eq!(stats.reading_progress, Some(0.4));
+    assert_eq!(stats.reading_minutes_left, 6);
+
+    let details = list_note_details(&conn, &HashSet::new(), NoteOrder::Natural).unwrap();
+    assert_eq!(details[0].note_name, "article.md");
+    assert_eq!(details[0].reading_progress, Some(0.4));
+    assert_eq!(details[0].reading_minutes_left, 6);
+    assert_eq!(details[1].reading_progress, None);
+    assert_eq!(details[1].reading_minutes_left, 1);
+}
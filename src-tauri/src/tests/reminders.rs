@@ -0,0 +1,106 @@
+//! Reminder Unit Tests
+//!
+//! Tests for listing, notifying and snoozing tasks with due dates.
+
+use crate::services::reminder_service::{
+    due_reminders, mark_notified, parse_reminder_time, snooze_reminder, upcoming_reminders,
+};
+use crate::tests::test_utils::notes_db;
+use chrono::{NaiveDate, NaiveTime};
+
+fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+}
+
+const NINE: NaiveTime = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+
+#[test]
+fn test_upcoming_reminders_lists_open_tasks_soonest_first() {
+    let conn = notes_db(&[
+        (
+            "work.md",
+            "- [ ] Send report 📅 2025-03-03\n- [x] Old task due:2025-03-01\n- [ ] Later due:2025-04-01",
+        ),
+        ("home.md", "- [ ] Pay rent due:2025-02-28\n- [ ] No date"),
+        ("plain.md", "Nothing due here"),
+    ]);
+
+    let reminders = upcoming_reminders(&conn, date("2025-03-01"), 7, 0).unwrap();
+    let listed: Vec<(&str, &str, bool)> = reminders
+        .iter()
+        .map(|r| (r.note_name.as_str(), r.task.as_str(), r.overdue))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            ("home.md", "Pay rent", true),
+            ("work.md", "Send report", false)
+        ]
+    );
+    assert_eq!(reminders[1].due, "2025-03-03");
+    assert_eq!(reminders[1].line, 1);
+}
+
+#[test]
+fn test_due_reminders_wait_for_notify_time_and_fire_once() {
+    let conn = notes_db(&[("work.md", "- [ ] Send report 📅 2025-03-03")]);
+    let today = date("2025-03-03");
+
+    let early = today.and_hms_opt(8, 59, 0).unwrap();
+    assert!(due_reminders(&conn, early, NINE, 0).unwrap().is_empty());
+
+    let now = today.and_hms_opt(9, 0, 0).unwrap();
+    let due = due_reminders(&conn, now, NINE, 100).unwrap();
+    assert_eq!(due.len(), 1);
+    assert!(!due[0].overdue);
+
+    mark_notified(&conn, &due, today, 100).unwrap();
+    assert!(due_reminders(&conn, now, NINE, 200).unwrap().is_empty());
+}
+
+#[test]
+fn test_long_overdue_tasks_are_not_notified() {
+    let conn = notes_db(&[("old.md", "- [ ] Forgotten due:2024-01-01")]);
+    let now = date("2025-03-03").and_hms_opt(12, 0, 0).unwrap();
+
+    assert!(due_reminders(&conn, now, NINE, 0).unwrap().is_empty());
+    assert_eq!(
+        upcoming_reminders(&conn, now.date(), 7, 0).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn test_snoozed_reminder_fires_again_after_snooze() {
+    let conn = notes_db(&[("work.md", "- [ ] Send report 📅 2025-03-03")]);
+    let today = date("2025-03-03");
+    let now = today.and_hms_opt(10, 0, 0).unwrap();
+
+    let due = due_reminders(&conn, now, NINE, 100).unwrap();
+    mark_notified(&conn, &due, today, 100).unwrap();
+    snooze_reminder(&conn, "work.md", "Send report", "2025-03-03", 3700).unwrap();
+
+    assert!(due_reminders(&conn, now, NINE, 3600).unwrap().is_empty());
+    let listed = upcoming_reminders(&conn, today, 7, 3600).unwrap();
+    assert_eq!(listed[0].snoozed_until, Some(3700));
+
+    assert_eq!(due_reminders(&conn, now, NINE, 3700).unwrap().len(), 1);
+    let listed = upcoming_reminders(&conn, today, 7, 3700).unwrap();
+    assert_eq!(listed[0].snoozed_until, None);
+}
+
+#[test]
+fn test_snooze_rejects_invalid_due_date() {
+    let conn = notes_db::<&str>(&[]);
+    assert!(snooze_reminder(&conn, "work.md", "Task", "March 3rd", 0).is_err());
+}
+
+#[test]
+fn test_parse_reminder_time() {
+    assert_eq!(
+        parse_reminder_time(" 18:30 "),
+        NaiveTime::from_hms_opt(18, 30, 0)
+    );
+    assert_eq!(parse_reminder_time("25:00"), None);
+    assert_eq!(parse_reminder_time("9am"), None);
+}
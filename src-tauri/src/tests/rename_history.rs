@@ -0,0 +1,42 @@
+//! Rename History Unit Tests
+//!
+//! Tests for resolving old note names through the rename history.
+
+use crate::services::rename_history_service::{record_rename, resolve_renamed_note};
+use crate::tests::test_utils::notes_db;
+
+#[test]
+fn test_old_names_resolve_to_current_name() {
+    let conn = notes_db(&["Projects/Final.md"]);
+    record_rename(&conn, "Draft.md", "Second draft.md", 1).unwrap();
+    record_rename(&conn, "Second draft.md", "Projects/Final.md", 2).unwrap();
+
+    assert_eq!(
+        resolve_renamed_note(&conn, "Draft.md").unwrap().as_deref(),
+        Some("Projects/Final.md")
+    );
+    assert_eq!(
+        resolve_renamed_note(&conn, "Second draft.md")
+            .unwrap()
+            .as_deref(),
+        Some("Projects/Final.md")
+    );
+    assert_eq!(resolve_renamed_note(&conn, "Other.md").unwrap(), None);
+}
+
+#[test]
+fn test_renaming_back_and_deleted_targets() {
+    let conn = notes_db(&["A.md"]);
+    record_rename(&conn, "A.md", "B.md", 1).unwrap();
+    record_rename(&conn, "B.md", "A.md", 2).unwrap();
+
+    // A is a live name again; B points back at it
+    assert_eq!(resolve_renamed_note(&conn, "A.md").unwrap(), None);
+    assert_eq!(
+        resolve_renamed_note(&conn, "B.md").unwrap().as_deref(),
+        Some("A.md")
+    );
+
+    record_rename(&conn, "Gone.md", "Deleted.md", 3).unwrap();
+    assert_eq!(resolve_renamed_note(&conn, "Gone.md").unwrap(), None);
+}
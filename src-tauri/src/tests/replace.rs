@@ -0,0 +1,127 @@
+//! Find and Replace Unit Tests
+//!
+//! Tests for matching, rewriting and previewing project-wide replacements.
+
+use crate::services::grep_service::GrepFlags;
+use crate::services::replace_service::{candidate_notes, ReplaceOptions, Replacement};
+use crate::tests::test_utils::notes_db;
+
+fn literal() -> GrepFlags {
+    GrepFlags {
+        fixed_strings: true,
+        ..GrepFlags::default()
+    }
+}
+
+#[test]
+fn test_replace_literal_text() {
+    let replacer = Replacement::new("Acme Corp", "Acme Inc. ($1)", literal()).unwrap();
+    let (replaced, count) = replacer
+        .apply("Acme Corp hired us.\nAcme Corp pays late.")
+        .unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(
+        replaced,
+        "Acme Inc. ($1) hired us.\nAcme Inc. ($1) pays late."
+    );
+    assert!(replacer.apply("Nothing here").is_none());
+}
+
+#[test]
+fn test_replace_regex_with_groups_and_flags() {
+    let replacer = Replacement::new(
+        r"todo\((\w+)\)",
+        "TODO(@$1)",
+        GrepFlags {
+            case_insensitive: true,
+            ..GrepFlags::default()
+        },
+    )
+    .unwrap();
+    let (replaced, count) = replacer.apply("TODO(ana) and todo(bo)").unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(replaced, "TODO(@ana) and TODO(@bo)");
+
+    // Matches that rewrite to the same text change nothing
+    let identity = Replacement::new("same", "same", literal()).unwrap();
+    assert!(identity.apply("same").is_none());
+
+    let whole_word = Replacement::new(
+        "cat",
+        "dog",
+        GrepFlags {
+            whole_word: true,
+            ..GrepFlags::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        whole_word.apply("cat category cat").unwrap().0,
+        "dog category dog"
+    );
+}
+
+#[test]
+fn test_replace_rejects_empty_matches() {
+    assert!(Replacement::new("", "x", GrepFlags::default()).is_err());
+    assert!(Replacement::new("x*", "y", GrepFlags::default()).is_err());
+    assert!(Replacement::new("^", "> ", GrepFlags::default()).is_err());
+    assert!(Replacement::new("(", "x", GrepFlags::default()).is_err());
+}
+
+#[test]
+fn test_replace_preview_diff() {
+    let replacer = Replacement::new("old", "new", literal()).unwrap();
+    let preview = replacer
+        .preview("n.md", "one\ntwo\nold name\nthree\nfour\n")
+        .unwrap();
+    assert_eq!(preview.replacements, 1);
+    assert_eq!(
+        preview.diff.as_deref(),
+        Some("@@ -2,3 +2,3 @@\n two\n-old name\n+new name\n three\n")
+    );
+    assert!(preview.backup_path.is_none());
+}
+
+#[test]
+fn test_candidate_notes_respects_folder() {
+    let conn = notes_db(&[
+        ("b.md", "Acme"),
+        ("Clients/acme.md", "Acme"),
+        ("Clients/other.md", "Other"),
+        ("Clientsx/acme.md", "Acme"),
+        ("a.md", "Acme"),
+    ]);
+    let replacer = Replacement::new("Acme", "Acme Inc.", literal()).unwrap();
+
+    let all: Vec<String> = candidate_notes(&conn, &replacer, None)
+        .unwrap()
+        .into_iter()
+        .map(|(filename, _)| filename)
+        .collect();
+    assert_eq!(
+        all,
+        vec!["Clients/acme.md", "Clientsx/acme.md", "a.md", "b.md"]
+    );
+
+    let clients: Vec<String> = candidate_notes(&conn, &replacer, Some("/Clients/"))
+        .unwrap()
+        .into_iter()
+        .map(|(filename, _)| filename)
+        .collect();
+    assert_eq!(clients, vec!["Clients/acme.md"]);
+}
+
+#[test]
+fn test_replace_options_deserialize_flat_flags() {
+    let options: ReplaceOptions =
+        serde_json::from_str(r#"{"fixed_strings": true, "dry_run": true, "folder": "Work"}"#)
+            .unwrap();
+    assert!(options.flags.fixed_strings);
+    assert!(!options.flags.case_insensitive);
+    assert!(options.dry_run);
+    assert_eq!(options.folder.as_deref(), Some("Work"));
+
+    let empty: ReplaceOptions = serde_json::from_str("{}").unwrap();
+    assert_eq!(empty, ReplaceOptions::default());
+}
@@ -0,0 +1,98 @@
+//! Re-render Unit Tests
+//!
+//! Tests for picking stale HTML renders, regenerating them in batches and
+//! auditing cached HTML against the allowlist.
+
+use crate::services::render_service::{
+    audit_renders, mark_renders_current, notes_needing_render, rerender_notes,
+};
+use crate::tests::test_utils::notes_db;
+use crate::utilities::html_audit::{audit_html, HtmlIssueKind};
+use rusqlite::{params, Connection};
+
+fn rendered_db(notes: &[(&str, &str, bool)]) -> Connection {
+    let rows: Vec<(&str, &str)> = notes
+        .iter()
+        .map(|(filename, content, _)| (*filename, *content))
+        .collect();
+    let conn = notes_db(&rows);
+    for (filename, _, indexed) in notes {
+        conn.execute(
+            "UPDATE notes SET html_render = 'stale', is_indexed = ?2 WHERE filename = ?1",
+            params![filename, indexed],
+        )
+        .unwrap();
+    }
+    conn
+}
+
+fn html_render(conn: &Connection, filename: &str) -> String {
+    conn.query_row(
+        "SELECT html_render FROM notes WHERE filename = ?1",
+        params![filename],
+        |row| row.get(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_renderer_version_decides_stale_notes() {
+    let conn = rendered_db(&[("a.md", "# A", true), ("b.md", "# B", false)]);
+
+    // No recorded renderer version: everything is stale
+    assert_eq!(notes_needing_render(&conn, false).unwrap().len(), 2);
+
+    mark_renders_current(&conn).unwrap();
+    assert_eq!(notes_needing_render(&conn, false).unwrap(), vec!["b.md"]);
+    assert_eq!(notes_needing_render(&conn, true).unwrap().len(), 2);
+}
+
+#[test]
+fn test_rerender_notes_updates_html_and_skips_missing() {
+    let conn = rendered_db(&[("a.md", "# Title", false)]);
+    let rendered = rerender_notes(&conn, &["a.md".to_string(), "gone.md".to_string()]).unwrap();
+    assert_eq!(rendered, 1);
+    assert!(html_render(&conn, "a.md").contains("<h1"));
+
+    mark_renders_current(&conn).unwrap();
+    assert!(notes_needing_render(&conn, false).unwrap().is_empty());
+}
+
+#[test]
+fn test_audit_html_flags_scripts_and_handlers() {
+    let issues = audit_html(
+        "<p>Hi</p><script>alert(1)</script><SCRIPT src=x></SCRIPT>\
+         <img src=\"a.png\" onerror=\"alert(1)\"><a href=\" JavaScript:alert(1)\">x</a>\
+         <input type=\"checkbox\" disabled=\"\" checked=\"\"><input type=\"text\">",
+    );
+    let find = |kind: HtmlIssueKind| issues.iter().find(|issue| issue.kind == kind);
+
+    let script = find(HtmlIssueKind::DisallowedTag).unwrap();
+    assert_eq!(script.detail, "script");
+    assert_eq!(script.count, 2);
+    assert_eq!(find(HtmlIssueKind::EventHandler).unwrap().detail, "onerror");
+    assert_eq!(find(HtmlIssueKind::ScriptUrl).unwrap().detail, "href");
+    assert_eq!(find(HtmlIssueKind::FormControl).unwrap().count, 1);
+}
+
+#[test]
+fn test_audit_renders_reports_offending_notes() {
+    let conn = rendered_db(&[
+        (
+            "safe.md",
+            "- [x] done\n\n| a |\n|:-:|\n| b |\n\n<kbd>Ctrl</kbd>",
+            false,
+        ),
+        (
+            "unsafe.md",
+            "<iframe src=\"https://example.com\"></iframe>",
+            false,
+        ),
+    ]);
+    rerender_notes(&conn, &["safe.md".to_string(), "unsafe.md".to_string()]).unwrap();
+
+    let findings = audit_renders(&conn).unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].note_name, "unsafe.md");
+    assert_eq!(findings[0].issues[0].detail, "iframe");
+}
@@ -0,0 +1,109 @@
+//! Rollup Unit Tests
+//!
+//! Tests for weekly/monthly review aggregation and rendering.
+
+use crate::services::rollup_service::{
+    render_rollup, rollup_note_name, summarize, RollupNote, RollupPeriod, RollupRange,
+    DEFAULT_ROLLUP_TEMPLATE,
+};
+use chrono::{Local, NaiveDate, TimeZone};
+
+fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+}
+
+/// Local noon on the given day, so the tests don't depend on the machine's time zone.
+fn timestamp(value: &str) -> i64 {
+    Local
+        .from_local_datetime(&date(value).and_hms_opt(12, 0, 0).unwrap())
+        .single()
+        .unwrap()
+        .timestamp()
+}
+
+fn note(filename: &str, content: &str, created: &str, modified: &str) -> RollupNote {
+    RollupNote {
+        filename: filename.to_string(),
+        content: content.to_string(),
+        created: timestamp(created),
+        modified: timestamp(modified),
+    }
+}
+
+#[test]
+fn test_rollup_ranges() {
+    let week = RollupRange::containing(RollupPeriod::Week, date("2024-06-05")).unwrap();
+    assert_eq!(week.start, date("2024-06-03"));
+    assert_eq!(week.end, date("2024-06-10"));
+    assert_eq!(week.label(), "2024-W23");
+    assert_eq!(rollup_note_name(&week), "Reviews/2024-W23.md");
+
+    let month = RollupRange::containing(RollupPeriod::Month, date("2024-12-31")).unwrap();
+    assert_eq!(month.start, date("2024-12-01"));
+    assert_eq!(month.end, date("2025-01-01"));
+    assert_eq!(month.label(), "2024-12");
+    assert_eq!(month.title(), "Monthly Review December 2024");
+
+    // ISO weeks can belong to the next year
+    let boundary = RollupRange::containing(RollupPeriod::Week, date("2024-12-31")).unwrap();
+    assert_eq!(boundary.label(), "2025-W01");
+}
+
+#[test]
+fn test_summarize_and_render_week() {
+    let notes = vec![
+        note(
+            "project.md",
+            "- [x] Ship it ✅ 2024-06-04\n- [x] Old task ✅ 2024-05-01\n- [ ] Open task\n- [x] Undated\n==Big idea==\n",
+            "2024-01-01",
+            "2024-06-05",
+        ),
+        note(
+            "stale.md",
+            "- [x] Undated elsewhere\n> Old quote\n",
+            "2024-01-01",
+            "2024-05-01",
+        ),
+        note(
+            "new.md",
+            "---\ncreated: 2024-06-08\n---\nFresh",
+            "2024-07-01",
+            "2024-07-01",
+        ),
+        note(
+            "Reviews/2024-W22.md",
+            "- [x] Counted last week ✅ 2024-06-04",
+            "2024-06-04",
+            "2024-06-04",
+        ),
+    ];
+
+    let range = RollupRange::containing(RollupPeriod::Week, date("2024-06-05")).unwrap();
+    let summary = summarize(&notes, &range);
+    let tasks: Vec<&str> = summary
+        .completed_tasks
+        .iter()
+        .map(|task| task.text.as_str())
+        .collect();
+    assert_eq!(tasks, vec!["Ship it", "Undated"]);
+    assert_eq!(
+        summary.completed_tasks[0].completed.as_deref(),
+        Some("2024-06-04")
+    );
+    assert_eq!(summary.notes_created, vec!["new.md"]);
+    assert_eq!(summary.highlights.len(), 1);
+    assert_eq!(summary.highlights[0].text, "Big idea");
+    assert_eq!(summary.end, "2024-06-09");
+
+    let rendered = render_rollup(DEFAULT_ROLLUP_TEMPLATE, &range, &summary, Local::now());
+    assert!(rendered.contains("# Weekly Review 2024-W23"));
+    assert!(rendered.contains("2024-06-03 – 2024-06-09"));
+    assert!(rendered.contains("## Completed tasks (2)"));
+    assert!(rendered.contains("- [x] Ship it ([[project]])"));
+    assert!(rendered.contains("- [[new]]"));
+    assert!(rendered.contains("> Big idea\n> — [[project]]"));
+
+    let empty = summarize(&[], &range);
+    let rendered = render_rollup("{{highlights}}|{{label}}", &range, &empty, Local::now());
+    assert_eq!(rendered, "_None_|2024-W23");
+}
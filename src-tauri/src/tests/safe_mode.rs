@@ -0,0 +1,33 @@
+//! Safe Mode Unit Tests
+//!
+//! Tests for detecting the safe-mode startup flag.
+
+use crate::core::safe_mode::{safe_mode_requested, SAFE_MODE_FLAG};
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn test_safe_mode_from_flag() {
+    assert!(safe_mode_requested(None, &args(&[SAFE_MODE_FLAG])));
+    assert!(safe_mode_requested(
+        None,
+        &args(&["--verbose", "--safe-mode"])
+    ));
+    assert!(!safe_mode_requested(None, &args(&[])));
+    assert!(!safe_mode_requested(
+        None,
+        &args(&["--safe-mode=false", "safe-mode"])
+    ));
+}
+
+#[test]
+fn test_safe_mode_from_env() {
+    for value in ["1", "true", "TRUE", " yes "] {
+        assert!(safe_mode_requested(Some(value), &[]), "{value}");
+    }
+    for value in ["", "0", "false", "off"] {
+        assert!(!safe_mode_requested(Some(value), &[]), "{value}");
+    }
+}
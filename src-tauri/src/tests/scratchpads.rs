@@ -0,0 +1,50 @@
+//! Scratchpad Unit Tests
+//!
+//! Tests for database-only scratchpad notes.
+
+use crate::services::scratchpad_service::{
+    create_scratchpad, discard_all_scratchpads, discard_scratchpad, get_scratchpad,
+    list_scratchpads, save_scratchpad,
+};
+use rusqlite::Connection;
+
+#[test]
+fn test_scratchpads_take_the_first_free_name() {
+    let conn = Connection::open_in_memory().unwrap();
+    assert_eq!(create_scratchpad(&conn, 1).unwrap().name, "Scratchpad 1");
+    assert_eq!(create_scratchpad(&conn, 2).unwrap().name, "Scratchpad 2");
+
+    discard_scratchpad(&conn, "Scratchpad 1").unwrap();
+    assert_eq!(create_scratchpad(&conn, 3).unwrap().name, "Scratchpad 1");
+
+    let names: Vec<String> = list_scratchpads(&conn)
+        .unwrap()
+        .into_iter()
+        .map(|scratchpad| scratchpad.name)
+        .collect();
+    assert_eq!(names, vec!["Scratchpad 2", "Scratchpad 1"]);
+}
+
+#[test]
+fn test_saved_scratchpad_content() {
+    let conn = Connection::open_in_memory().unwrap();
+    let name = create_scratchpad(&conn, 1).unwrap().name;
+    save_scratchpad(&conn, &name, "phone: 555-0100", 5).unwrap();
+
+    let scratchpad = get_scratchpad(&conn, &name).unwrap();
+    assert_eq!(scratchpad.content, "phone: 555-0100");
+    assert_eq!((scratchpad.created, scratchpad.modified), (1, 5));
+
+    assert!(save_scratchpad(&conn, "Scratchpad 9", "text", 5).is_err());
+    assert!(get_scratchpad(&conn, "Scratchpad 9").is_err());
+    assert!(discard_scratchpad(&conn, "Scratchpad 9").is_err());
+}
+
+#[test]
+fn test_discard_all_scratchpads() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_scratchpad(&conn, 1).unwrap();
+    create_scratchpad(&conn, 1).unwrap();
+    assert_eq!(discard_all_scratchpads(&conn).unwrap(), 2);
+    assert!(list_scratchpads(&conn).unwrap().is_empty());
+}
@@ -2,7 +2,11 @@
 //!
 //! Tests for search functionality, FTS security, and performance.
 
+use crate::search::{HybridSearcher, SearchOptions, SearchScope, SearchSort};
+use crate::tests::test_utils::notes_db;
 use crate::tests::test_utils::{test_search_notes_hybrid, TestConfigOverride};
+use crate::utilities::strings::{clipboard_search_query, MAX_CLIPBOARD_QUERY_CHARS};
+use rusqlite::{params, Connection};
 use serial_test::serial;
 use std::time::Instant;
 
@@ -227,3 +231,225 @@ fn test_search_performance_stress_queries() {
         }
     }
 }
+
+#[test]
+fn test_search_results_include_marked_snippets() {
+    let conn = notes_db(&[
+        (
+            "garden.md",
+            "Plant the <b>tomatoes</b> after the last frost.",
+            10,
+        ),
+        ("tomato-recipes.md", "Sauce and salsa.", 20),
+    ]);
+    let mut searcher = HybridSearcher::new().unwrap();
+
+    let results = searcher
+        .search_in(&conn, "tomato", &SearchOptions::first(10))
+        .unwrap();
+    assert_eq!(results.len(), 2);
+
+    // Title matches rank above content matches
+    assert_eq!(results[0].filename, "tomato-recipes.md");
+    assert_eq!(
+        results[0].highlighted_filename.as_deref(),
+        Some("<mark>tomato</mark>-recipes.md")
+    );
+
+    let garden = &results[1];
+    assert_eq!(garden.modified, 10);
+    let snippet = garden.snippet.as_deref().unwrap();
+    // Note markup is escaped; only the match markers become tags
+    assert!(snippet.contains("&lt;b&gt;<mark>tomatoes</mark>&lt;/b&gt;"));
+    assert!(!snippet.contains("<b>"));
+}
+
+#[test]
+fn test_empty_query_lists_recent_notes_without_snippets() {
+    let conn = notes_db(&[("old.md", "old", 1), ("new.md", "new", 2)]);
+    let mut searcher = HybridSearcher::new().unwrap();
+
+    let results = searcher
+        .search_in(&conn, "", &SearchOptions::first(10))
+        .unwrap();
+    let names: Vec<&str> = results.iter().map(|r| r.filename.as_str()).collect();
+    assert_eq!(names, vec!["new.md", "old.md"]);
+    assert!(results.iter().all(|r| r.snippet.is_none()));
+}
+
+#[test]
+fn test_search_sort_and_paging() {
+    let conn = notes_db(&[
+        ("b-report.md", "quarterly report", 30),
+        ("a-report.md", "annual report", 10),
+        ("c-report.md", "weekly report", 20),
+        ("unrelated.md", "nothing here", 40),
+    ]);
+    let mut searcher = HybridSearcher::new().unwrap();
+    let mut page = |sort, offset, limit| -> Vec<String> {
+        searcher
+            .search_in(
+                &conn,
+                "report",
+                &SearchOptions {
+                    offset,
+                    limit,
+                    sort,
+                    scope: SearchScope::default(),
+                },
+            )
+            .unwrap()
+            .into_iter()
+            .map(|r| r.filename)
+            .collect()
+    };
+
+    assert_eq!(
+        page(SearchSort::ModifiedDesc, 0, 10),
+        vec!["b-report.md", "c-report.md", "a-report.md"]
+    );
+    assert_eq!(
+        page(SearchSort::ModifiedAsc, 0, 10),
+        vec!["a-report.md", "c-report.md", "b-report.md"]
+    );
+    assert_eq!(page(SearchSort::Filename, 1, 1), vec!["b-report.md"]);
+    assert_eq!(page(SearchSort::Filename, 3, 10), Vec::<String>::new());
+
+    // Relevance pages line up with the full ranking
+    let all = page(SearchSort::Relevance, 0, 10);
+    assert_eq!(all.len(), 3);
+    assert_eq!(page(SearchSort::Relevance, 1, 2), all[1..].to_vec());
+}
+
+#[test]
+fn test_empty_query_respects_sort_and_offset() {
+    let conn = notes_db(&[("b.md", "", 1), ("a.md", "", 2), ("c.md", "", 3)]);
+    let mut searcher = HybridSearcher::new().unwrap();
+
+    let results = searcher
+        .search_in(
+            &conn,
+            "",
+            &SearchOptions {
+                offset: 1,
+                limit: usize::MAX,
+                sort: SearchSort::Filename,
+                scope: SearchScope::default(),
+            },
+        )
+        .unwrap();
+    let names: Vec<&str> = results.iter().map(|r| r.filename.as_str()).collect();
+    assert_eq!(names, vec!["b.md", "c.md"]);
+}
+
+#[test]
+fn test_scoped_search_by_folder_and_tag() {
+    let conn = notes_db(&[
+        ("Work/plan.md", "budget plan #finance", 1),
+        ("Work/2024/review.md", "budget review", 2),
+        ("Workshop/notes.md", "budget ideas #finance", 3),
+        ("home.md", "household budget #Finance", 4),
+    ]);
+    let mut searcher = HybridSearcher::new().unwrap();
+    let mut scoped = |query: &str, folder: Option<&str>, tag: Option<&str>| -> Vec<String> {
+        let options = SearchOptions {
+            sort: SearchSort::Filename,
+            scope: SearchScope {
+                folder: folder.map(str::to_string),
+                tag: tag.map(str::to_string),
+            },
+            ..SearchOptions::first(10)
+        };
+        searcher
+            .search_in(&conn, query, &options)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.filename)
+            .collect()
+    };
+
+    // A folder prefix doesn't match sibling folders sharing its name
+    assert_eq!(
+        scoped("budget", Some("Work/"), None),
+        vec!["Work/2024/review.md", "Work/plan.md"]
+    );
+    assert_eq!(
+        scoped("budget", None, Some("#finance")),
+        vec!["home.md", "Work/plan.md", "Workshop/notes.md"]
+    );
+    assert_eq!(
+        scoped("budget", Some("Work"), Some("finance")),
+        vec!["Work/plan.md"]
+    );
+    // Scope applies before the limit, and to the recent-notes listing too
+    assert_eq!(
+        scoped("", Some("Workshop"), None),
+        vec!["Workshop/notes.md"]
+    );
+}
+
+#[test]
+fn test_search_ignores_case_and_diacritics() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE notes USING fts5(filename, content, html_render, modified UNINDEXED, is_indexed UNINDEXED, tokenize = 'unicode61 remove_diacritics 2');",
+    )
+    .unwrap();
+    for (filename, content, modified) in [
+        ("Café notes.md", "# Café\nOpening hours", 1),
+        ("menu.md", "Crème brûlée and espresso", 2),
+        ("decomposed.md", "Cafe\u{301} on the corner", 3),
+    ] {
+        conn.execute(
+            "INSERT INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, '', ?3, 1)",
+            params![filename, content, modified],
+        )
+        .unwrap();
+    }
+    let mut searcher = HybridSearcher::new().unwrap();
+
+    let cafe = searcher
+        .search_in(&conn, "cafe", &SearchOptions::first(10))
+        .unwrap();
+    assert_eq!(cafe.len(), 2);
+    // The folded title still counts as an exact title match
+    assert_eq!(cafe[0].filename, "Café notes.md");
+
+    let creme = searcher
+        .search_in(&conn, "CREME BRULEE", &SearchOptions::first(10))
+        .unwrap();
+    assert_eq!(creme.len(), 1);
+    assert_eq!(creme[0].filename, "menu.md");
+}
+
+#[test]
+fn test_accented_query_matches_unaccented_text_without_folding_tokenizer() {
+    let conn = notes_db(&[("resume.md", "My resume draft", 1)]);
+    let mut searcher = HybridSearcher::new().unwrap();
+
+    let results = searcher
+        .search_in(&conn, "résumé", &SearchOptions::first(10))
+        .unwrap();
+    assert_eq!(results.len(), 1);
+}
+
+#[test]
+fn test_clipboard_search_query_collapses_whitespace() {
+    assert_eq!(
+        clipboard_search_query("  meeting\n\tnotes   from\r\nmonday "),
+        Some("meeting notes from monday".to_string())
+    );
+    assert_eq!(clipboard_search_query(" \n\t "), None);
+}
+
+#[test]
+fn test_clipboard_search_query_cuts_long_text_at_a_word() {
+    let text = "word ".repeat(100);
+    let query = clipboard_search_query(&text).unwrap();
+    assert!(query.chars().count() <= MAX_CLIPBOARD_QUERY_CHARS);
+    assert!(query.ends_with("word"));
+
+    let long_word = "é".repeat(MAX_CLIPBOARD_QUERY_CHARS + 10);
+    let query = clipboard_search_query(&long_word).unwrap();
+    assert_eq!(query.chars().count(), MAX_CLIPBOARD_QUERY_CHARS);
+}
@@ -0,0 +1,123 @@
+//! Search Export Unit Tests
+//!
+//! Tests for filtering search results and rendering them as CSV or JSON.
+
+use crate::services::search_export_service::{
+    collect_export_rows, render_export, ExportFormat, SearchExportFilters,
+};
+use crate::tests::test_utils::notes_db;
+
+fn names(notes: &[&str]) -> Vec<String> {
+    notes.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn test_rows_keep_search_order_and_skip_private_notes() {
+    let conn = notes_db(&[
+        ("a.md", "---\ntitle: Alpha\n---\n#work one two", 10),
+        ("b.md", "---\nvisibility: private\n---\nsecret", 20),
+        ("c.md", "three", 30),
+    ]);
+
+    let rows = collect_export_rows(
+        &conn,
+        &names(&["c.md", "b.md", "a.md", "missing.md"]),
+        &SearchExportFilters::default(),
+    )
+    .unwrap();
+    let exported: Vec<&str> = rows.iter().map(|r| r.note_name.as_str()).collect();
+    assert_eq!(exported, vec!["c.md", "a.md"]);
+    assert_eq!(rows[1].title, "Alpha");
+    assert_eq!(rows[1].tags, vec!["work".to_string()]);
+    assert_eq!(rows[1].modified, 10);
+}
+
+#[test]
+fn test_filters() {
+    let conn = notes_db(&[
+        ("Projects/a.md", "#work", 10),
+        ("Projects/2024/b.md", "---\npinned: true\n---\n#work", 20),
+        ("ProjectsOld/c.md", "#work", 30),
+        ("Archive/d.md", "#work", 40),
+    ]);
+    let all = names(&[
+        "Projects/a.md",
+        "Projects/2024/b.md",
+        "ProjectsOld/c.md",
+        "Archive/d.md",
+    ]);
+    let exported = |filters: SearchExportFilters| -> Vec<String> {
+        collect_export_rows(&conn, &all, &filters)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.note_name)
+            .collect()
+    };
+
+    assert_eq!(
+        exported(SearchExportFilters {
+            folder: Some("Projects/".to_string()),
+            ..Default::default()
+        }),
+        vec!["Projects/a.md", "Projects/2024/b.md"]
+    );
+    assert_eq!(
+        exported(SearchExportFilters {
+            modified_after: Some(20),
+            modified_before: Some(30),
+            ..Default::default()
+        }),
+        vec!["Projects/2024/b.md", "ProjectsOld/c.md"]
+    );
+    assert_eq!(
+        exported(SearchExportFilters {
+            pinned: Some(true),
+            ..Default::default()
+        }),
+        vec!["Projects/2024/b.md"]
+    );
+    assert_eq!(
+        exported(SearchExportFilters {
+            archived: Some(false),
+            tag: Some("#Work".to_string()),
+            ..Default::default()
+        })
+        .len(),
+        3
+    );
+}
+
+#[test]
+fn test_render_csv_quotes_fields() {
+    let conn = notes_db(&[(
+        "a.md",
+        "---\ntitle: Hello, \"world\"\ncolor: \"#FA0\"\n---\n#x #y",
+        5,
+    )]);
+    let rows = collect_export_rows(&conn, &names(&["a.md"]), &Default::default()).unwrap();
+
+    let csv = render_export(&rows, ExportFormat::Csv).unwrap();
+    assert_eq!(
+        csv,
+        "note_name,title,modified,word_count,tags,pinned,archived,icon,color\r\n\
+         a.md,\"Hello, \"\"world\"\"\",5,2,x;y,false,false,,#fa0\r\n"
+    );
+}
+
+#[test]
+fn test_render_json() {
+    let conn = notes_db(&[("a.md", "#x", 5)]);
+    let rows = collect_export_rows(&conn, &names(&["a.md"]), &Default::default()).unwrap();
+
+    let json: serde_json::Value =
+        serde_json::from_str(&render_export(&rows, ExportFormat::Json).unwrap()).unwrap();
+    assert_eq!(json[0]["note_name"], "a.md");
+    assert_eq!(json[0]["tags"], serde_json::json!(["x"]));
+}
+
+#[test]
+fn test_export_format_parse() {
+    assert_eq!(ExportFormat::parse("CSV").unwrap(), ExportFormat::Csv);
+    assert_eq!(ExportFormat::parse(" json ").unwrap().extension(), "json");
+    assert!(ExportFormat::parse("xlsx").is_err());
+}
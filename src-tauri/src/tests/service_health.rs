@@ -0,0 +1,78 @@
+//! Service Health Unit Tests
+//!
+//! Tests for the background service status registry.
+
+use crate::core::service_health::{ServiceName, ServiceRegistry, ServiceState};
+
+fn state_of(registry: &ServiceRegistry, name: ServiceName) -> ServiceState {
+    registry
+        .statuses()
+        .into_iter()
+        .find(|status| status.name == name)
+        .unwrap()
+        .state
+}
+
+#[test]
+fn test_all_services_reported() {
+    let registry = ServiceRegistry::new();
+    let statuses = registry.statuses();
+
+    assert_eq!(statuses.len(), ServiceName::ALL.len());
+    assert_eq!(
+        state_of(&registry, ServiceName::Watcher),
+        ServiceState::Stopped
+    );
+    assert_eq!(
+        state_of(&registry, ServiceName::Scheduler),
+        ServiceState::Stopped
+    );
+    assert_eq!(
+        state_of(&registry, ServiceName::Sync),
+        ServiceState::Stopped
+    );
+    assert_eq!(
+        state_of(&registry, ServiceName::HttpApi),
+        ServiceState::Unavailable
+    );
+
+    // Unshipped services stay unavailable whatever gets recorded for them
+    registry.mark_running(ServiceName::EmbeddingWorker);
+    assert_eq!(
+        state_of(&registry, ServiceName::EmbeddingWorker),
+        ServiceState::Unavailable
+    );
+}
+
+#[test]
+fn test_state_transitions() {
+    let registry = ServiceRegistry::new();
+
+    registry.mark_error(ServiceName::Watcher, "channel closed");
+    let status = registry.statuses().remove(0);
+    assert_eq!(status.state, ServiceState::Error);
+    assert_eq!(status.error.as_deref(), Some("channel closed"));
+    assert_eq!(status.last_activity, None);
+
+    registry.mark_running(ServiceName::Watcher);
+    let status = registry.statuses().remove(0);
+    assert_eq!(status.state, ServiceState::Running);
+    assert_eq!(status.error, None);
+    assert!(status.last_activity.is_some());
+
+    registry.mark_stopped(ServiceName::Watcher);
+    assert_eq!(
+        state_of(&registry, ServiceName::Watcher),
+        ServiceState::Stopped
+    );
+}
+
+#[test]
+fn test_parse_service_name() {
+    assert_eq!(ServiceName::parse("watcher").unwrap(), ServiceName::Watcher);
+    assert_eq!(
+        ServiceName::parse(" Embedding-Worker ").unwrap(),
+        ServiceName::EmbeddingWorker
+    );
+    assert!(ServiceName::parse("plugins").is_err());
+}
@@ -0,0 +1,58 @@
+//! Share Bundle Unit Tests
+//!
+//! Tests for encrypted note bundles exchanged between Symiosis users.
+
+use crate::services::share_service::{
+    build_share_bundle, open_share_bundle, read_share_manifest, unique_import_name,
+    validate_passphrase, SharedNote, SHARE_FORMAT,
+};
+
+fn shared(filename: &str, content: &str) -> SharedNote {
+    SharedNote {
+        filename: filename.to_string(),
+        content: content.to_string(),
+    }
+}
+
+#[test]
+fn test_share_bundle_round_trip() {
+    let notes = vec![
+        shared("Recipes/Bread.md", "# Bread\nFlour, water, salt."),
+        shared("todo.txt", "buy yeast"),
+    ];
+    let bundle = build_share_bundle(&notes, "correct horse battery").expect("bundle failed");
+
+    let manifest = read_share_manifest(&bundle).expect("manifest unreadable");
+    assert_eq!(manifest.format, SHARE_FORMAT);
+    assert_eq!(manifest.note_count, 2);
+    assert!(
+        !String::from_utf8_lossy(&bundle).contains("Flour"),
+        "note content must not be stored in plain text"
+    );
+
+    let (_, opened) = open_share_bundle(&bundle, "correct horse battery").expect("open failed");
+    assert_eq!(opened, notes);
+
+    let wrong = open_share_bundle(&bundle, "wrong passphrase").unwrap_err();
+    assert!(wrong.to_string().contains("Incorrect passphrase"));
+}
+
+#[test]
+fn test_share_bundle_rejects_bad_input() {
+    assert!(validate_passphrase("short").is_err());
+    assert!(build_share_bundle(&[], "long enough passphrase").is_err());
+    assert!(read_share_manifest(b"not a zip").is_err());
+}
+
+#[test]
+fn test_unique_import_name() {
+    let existing = ["a.md", "a (shared).md", "dir.v2/notes"];
+    let exists = |name: &str| existing.contains(&name);
+
+    assert_eq!(unique_import_name("b.md", exists), "b.md");
+    assert_eq!(unique_import_name("a.md", exists), "a (shared 2).md");
+    assert_eq!(
+        unique_import_name("dir.v2/notes", exists),
+        "dir.v2/notes (shared)"
+    );
+}
@@ -0,0 +1,43 @@
+//! Slide Export Unit Tests
+//!
+//! Tests for splitting notes into reveal.js slides.
+
+use crate::utilities::slides::{slides_html, split_slides};
+
+#[test]
+fn test_split_on_rules_and_second_level_headings() {
+    let content = "---\ntitle: Talk\n---\n# Talk\n\nBy me\n\n---\n\nAgenda\n\n## Part one\n\nPoint\n### Detail\n\n## Part two\n\n```\n---\n## not a slide\n```\n\n---\n\n---\n";
+
+    assert_eq!(
+        split_slides(content),
+        vec![
+            "# Talk\n\nBy me\n",
+            "Agenda\n",
+            "## Part one\n\nPoint\n### Detail\n",
+            "## Part two\n\n```\n---\n## not a slide\n```\n",
+        ]
+    );
+}
+
+#[test]
+fn test_note_without_boundaries_is_one_slide() {
+    assert_eq!(split_slides("Just a thought"), vec!["Just a thought\n"]);
+    assert!(split_slides("---\ntitle: Empty\n---\n").is_empty());
+}
+
+#[test]
+fn test_deck_renders_each_slide() {
+    let html = slides_html(
+        "talk.md",
+        "Q&A <live>",
+        "# Intro\n\nHello **all**\n\n## Next\n\n- one\n",
+    );
+
+    assert!(html.contains("<title>Q&amp;A &lt;live&gt;</title>"));
+    assert_eq!(html.matches("<section>").count(), 2);
+    assert!(
+        html.contains("<section>\n<h1>Intro</h1>\n<p>Hello <strong>all</strong></p>\n</section>")
+    );
+    assert!(html.contains("<h2>Next</h2>"));
+    assert!(html.contains("Reveal.initialize"));
+}
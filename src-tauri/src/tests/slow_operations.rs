@@ -0,0 +1,86 @@
+//! Slow Operation Unit Tests
+//!
+//! Tests for statement capture, redaction and the slow operation log.
+
+use crate::core::slow_operations::{
+    finish_statement_capture, redact_sql, start_statement_capture, trace_statement, SlowOperation,
+    SlowOperationLog,
+};
+use rusqlite::trace::TraceEventCodes;
+use rusqlite::{params, Connection};
+
+fn slow_operation(operation: &str) -> SlowOperation {
+    SlowOperation {
+        operation: operation.to_string(),
+        statements: Vec::new(),
+        duration_ms: 750.0,
+        timed_out: false,
+        finished_at: 0,
+    }
+}
+
+#[test]
+fn test_text_literals_are_redacted() {
+    let content = "secret ".repeat(20);
+    let sql = format!(
+        "UPDATE notes SET content = '{}' WHERE filename = 'a.md'",
+        content
+    );
+    assert_eq!(
+        redact_sql(&sql),
+        "UPDATE notes SET content = <text: 140 chars> WHERE filename = <text: 4 chars>"
+    );
+}
+
+#[test]
+fn test_escaped_quotes_and_blobs() {
+    assert_eq!(
+        redact_sql("SELECT 'it''s', X'00ff00', max(1) FROM notes"),
+        "SELECT <text: 4 chars>, <blob: 3 bytes>, max(1) FROM notes"
+    );
+    // An identifier ending in x isn't a blob prefix
+    assert_eq!(
+        redact_sql("SELECT * FROM fts_idx WHERE a = 'x'"),
+        "SELECT * FROM fts_idx WHERE a = <text: 1 chars>"
+    );
+}
+
+#[test]
+fn test_statements_captured_with_parameters() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.trace_v2(TraceEventCodes::SQLITE_TRACE_PROFILE, Some(trace_statement));
+    conn.execute_batch("CREATE TABLE notes (filename TEXT, content TEXT)")
+        .unwrap();
+
+    start_statement_capture();
+    conn.execute(
+        "INSERT INTO notes (filename, content) VALUES (?1, ?2)",
+        params!["a.md", "word ".repeat(50)],
+    )
+    .unwrap();
+    let statements = finish_statement_capture();
+
+    assert_eq!(statements.len(), 1);
+    assert_eq!(
+        statements[0].sql,
+        "INSERT INTO notes (filename, content) VALUES (<text: 4 chars>, <text: 250 chars>)"
+    );
+    assert!(statements[0].duration_ms >= 0.0);
+
+    // Nothing is collected outside a capture
+    conn.execute("DELETE FROM notes", []).unwrap();
+    assert!(finish_statement_capture().is_empty());
+}
+
+#[test]
+fn test_log_keeps_newest_operations() {
+    let log = SlowOperationLog::default();
+    for i in 0..105 {
+        log.record(slow_operation(&format!("op{}", i)));
+    }
+
+    let recent = log.recent();
+    assert_eq!(recent.len(), 100);
+    assert_eq!(recent[0].operation, "op104");
+    assert_eq!(recent[99].operation, "op5");
+}
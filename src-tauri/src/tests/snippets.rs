@@ -0,0 +1,54 @@
+//! Snippet Unit Tests
+//!
+//! Tests for snippet storage and template variable expansion.
+
+use crate::services::snippet_service::{
+    expand_template, load_snippets, save_snippet, uses_variable,
+};
+use chrono::{Local, TimeZone};
+use std::collections::HashMap;
+use tempfile::TempDir;
+
+fn fixed_time() -> chrono::DateTime<Local> {
+    Local.with_ymd_and_hms(2024, 3, 5, 14, 7, 0).unwrap()
+}
+
+#[test]
+fn test_save_snippet_upserts_and_removes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("snippets.json");
+
+    save_snippet(&path, "sig", "Regards").unwrap();
+    save_snippet(&path, ";date", "{{date}}").unwrap();
+    let snippets = save_snippet(&path, "sig", "Best").unwrap();
+
+    assert_eq!(snippets.len(), 2);
+    assert_eq!(snippets[0].trigger, ";date");
+    assert_eq!(snippets[1].body, "Best");
+    assert_eq!(load_snippets(&path).unwrap(), snippets);
+
+    let snippets = save_snippet(&path, "sig", "").unwrap();
+    assert_eq!(snippets.len(), 1);
+    assert!(save_snippet(&path, "two words", "x").is_err());
+}
+
+#[test]
+fn test_expand_template_builtin_and_custom_variables() {
+    let mut variables = HashMap::new();
+    variables.insert("clipboard".to_string(), "copied".to_string());
+    variables.insert("title".to_string(), "Standup".to_string());
+
+    let body = "# {{title}} {{date}} {{ time }}\n{{date:%d/%m}} {{clipboard}} {{unknown}}";
+    assert_eq!(
+        expand_template(body, &variables, fixed_time()),
+        "# Standup 2024-03-05 14:07\n05/03 copied {{unknown}}"
+    );
+}
+
+#[test]
+fn test_expand_template_leaves_invalid_formats_untouched() {
+    let body = "{{date:%Q}}";
+    assert_eq!(expand_template(body, &HashMap::new(), fixed_time()), body);
+    assert!(uses_variable("x {{clipboard}}", "clipboard"));
+    assert!(!uses_variable("x {{date}}", "clipboard"));
+}
@@ -0,0 +1,71 @@
+//! Stale Note Unit Tests
+//!
+//! Tests for finding notes that haven't been modified or opened in a while.
+
+use crate::services::staleness_service::{record_note_opened, stale_notes};
+use crate::tests::test_utils::notes_db;
+use rusqlite::Connection;
+
+const DAY: i64 = 24 * 60 * 60;
+const NOW: i64 = 1_000 * DAY;
+
+fn stale_names(conn: &Connection, days: u32, exclude_tags: &[&str]) -> Vec<String> {
+    let exclude_tags: Vec<String> = exclude_tags.iter().map(|tag| tag.to_string()).collect();
+    stale_notes(conn, days, &exclude_tags, NOW)
+        .unwrap()
+        .into_iter()
+        .map(|note| note.note_name)
+        .collect()
+}
+
+#[test]
+fn test_stale_notes_lists_untouched_notes_oldest_first() {
+    let conn = notes_db(&[
+        ("Recent.md", "", NOW - 2 * DAY),
+        ("Old.md", "", NOW - 100 * DAY),
+        ("Older.md", "", NOW - 200 * DAY),
+    ]);
+
+    let notes = stale_notes(&conn, 30, &[], NOW).unwrap();
+    let names: Vec<&str> = notes.iter().map(|note| note.note_name.as_str()).collect();
+    assert_eq!(names, vec!["Older.md", "Old.md"]);
+    assert_eq!(notes[0].title, "Older");
+    assert_eq!(notes[0].idle_days, 200);
+    assert_eq!(notes[0].last_opened, None);
+}
+
+#[test]
+fn test_opening_a_note_keeps_it_fresh() {
+    let conn = notes_db(&[
+        ("Read.md", "", NOW - 100 * DAY),
+        ("Unread.md", "", NOW - 100 * DAY),
+    ]);
+    record_note_opened(&conn, "Read.md", NOW - 5 * DAY).unwrap();
+    assert_eq!(stale_names(&conn, 30, &[]), vec!["Unread.md"]);
+
+    // An older open doesn't move the last one back
+    record_note_opened(&conn, "Read.md", NOW - 90 * DAY).unwrap();
+    assert_eq!(stale_names(&conn, 30, &[]), vec!["Unread.md"]);
+
+    let notes = stale_notes(&conn, 3, &[], NOW).unwrap();
+    let read = notes
+        .iter()
+        .find(|note| note.note_name == "Read.md")
+        .unwrap();
+    assert_eq!(read.last_opened, Some(NOW - 5 * DAY));
+    assert_eq!(read.idle_days, 5);
+}
+
+#[test]
+fn test_stale_notes_skips_excluded_tags() {
+    let conn = notes_db(&[
+        ("Reference.md", "Kept for good #reference", NOW - 100 * DAY),
+        ("Nested.md", "#evergreen/recipes", NOW - 100 * DAY),
+        ("Plain.md", "Nothing here", NOW - 100 * DAY),
+    ]);
+
+    assert_eq!(
+        stale_names(&conn, 30, &["#Reference", "evergreen", " "]),
+        vec!["Plain.md"]
+    );
+}
@@ -0,0 +1,49 @@
+//! Startup Profile Unit Tests
+//!
+//! Tests for the startup phase timings.
+
+use crate::core::startup_profile::{StartupPhase, StartupProfiler};
+use std::thread::sleep;
+use std::time::Duration;
+
+#[test]
+fn test_phases_reported_in_start_order() {
+    let profiler = StartupProfiler::new();
+
+    let value = profiler.time(StartupPhase::InitializeState, || {
+        profiler.time(StartupPhase::LoadConfig, || sleep(Duration::from_millis(5)));
+        profiler.time(StartupPhase::DatabaseInit, || 42)
+    });
+    assert_eq!(value, 42);
+
+    let report = profiler.report();
+    let phases: Vec<StartupPhase> = report.phases.iter().map(|timing| timing.phase).collect();
+    assert_eq!(
+        phases,
+        vec![
+            StartupPhase::InitializeState,
+            StartupPhase::LoadConfig,
+            StartupPhase::DatabaseInit
+        ]
+    );
+    assert!(report.phases[1].duration_ms >= 5.0);
+    assert!(report.phases[0].duration_ms >= report.phases[1].duration_ms);
+    assert!(report.phases[2].started_ms >= report.phases[1].started_ms + 5.0);
+    assert!(report.total_ms.is_none());
+}
+
+#[test]
+fn test_phases_after_finish_are_not_recorded() {
+    let profiler = StartupProfiler::new();
+    profiler.time(StartupPhase::WatcherSetup, || ());
+    profiler.finish();
+
+    let total = profiler.report().total_ms.unwrap();
+    profiler.time(StartupPhase::InitialSync, || ());
+    profiler.finish();
+
+    let report = profiler.report();
+    assert_eq!(report.phases.len(), 1);
+    assert_eq!(report.total_ms, Some(total));
+    assert!(total >= report.phases[0].duration_ms);
+}
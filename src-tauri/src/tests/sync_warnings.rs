@@ -0,0 +1,138 @@
+//! Sync Warning Unit Tests
+//!
+//! Tests for resolving filenames that collide in the index, reporting ones
+//! that only look alike, and recording it.
+
+use crate::services::sync_warning_service::{
+    find_lookalike_names, note_key, plan_duplicate_renames, read_note_file, record_sync_warning,
+    sync_warnings, DuplicateRename, LookalikeName, SyncIssueKind, SyncWarning, MAX_NOTE_BYTES,
+};
+use rusqlite::Connection;
+use std::fs;
+use tempfile::TempDir;
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn test_note_key_ignores_case_unicode_form_and_trailing_spaces() {
+    let composed = "Caf\u{e9}.md";
+    let decomposed = "Cafe\u{301}.md";
+    assert_eq!(note_key(composed), note_key(decomposed));
+    assert_eq!(note_key("Notes/Plan.md"), note_key("notes /plan .md"));
+    assert_ne!(note_key("Plan.md"), note_key("Plan 2.md"));
+}
+
+#[test]
+fn test_plan_renames_only_names_the_index_shares() {
+    // Two files whose names only differ in bytes lost to lossy conversion
+    let files = names(&[
+        "Caf\u{fffd}.md",
+        "Plan.md",
+        "Caf\u{fffd}.md",
+        "Caf\u{fffd} (2).md",
+        "plan.md",
+    ]);
+    assert_eq!(
+        plan_duplicate_renames(&files),
+        vec![DuplicateRename {
+            index: 2,
+            filename: "Caf\u{fffd}.md".to_string(),
+            new_name: "Caf\u{fffd} (3).md".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_plan_leaves_attachments_alone() {
+    let files = names(&["img/a\u{fffd}.png", "img/a\u{fffd}.png", "notes.txt"]);
+    assert!(plan_duplicate_renames(&files).is_empty());
+}
+
+#[test]
+fn test_lookalike_names_are_reported_not_renamed() {
+    let files = names(&[
+        "b/Plan.md",
+        "plan.md",
+        "Plan.md",
+        "Plan .md",
+        "Image.png",
+        "image.png",
+    ]);
+    assert!(plan_duplicate_renames(&files).is_empty());
+    assert_eq!(
+        find_lookalike_names(&files),
+        vec![
+            LookalikeName {
+                filename: "Plan.md".to_string(),
+                similar_to: "Plan .md".to_string(),
+            },
+            LookalikeName {
+                filename: "plan.md".to_string(),
+                similar_to: "Plan .md".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_no_warnings_without_collisions() {
+    let files = names(&["Plan.md", "Plan (2).md", "Other/Plan.md"]);
+    assert!(plan_duplicate_renames(&files).is_empty());
+    assert!(find_lookalike_names(&files).is_empty());
+}
+
+#[test]
+fn test_sync_warnings_are_listed_newest_first() {
+    let conn = Connection::open_in_memory().unwrap();
+    assert!(sync_warnings(&conn).unwrap().is_empty());
+
+    let warning = |filename: &str, detected_at| SyncWarning {
+        filename: filename.to_string(),
+        resolved_name: None,
+        message: "Skipped".to_string(),
+        detected_at,
+    };
+    record_sync_warning(&conn, &warning("a.md", 1)).unwrap();
+    record_sync_warning(&conn, &warning("b.md", 2)).unwrap();
+
+    assert_eq!(
+        sync_warnings(&conn).unwrap(),
+        vec![warning("b.md", 2), warning("a.md", 1)]
+    );
+}
+
+#[test]
+fn test_read_note_file_reports_files_it_cant_index() {
+    let dir = TempDir::new().unwrap();
+    let note = dir.path().join("note.md");
+    fs::write(&note, "# Text").unwrap();
+    assert_eq!(read_note_file(&note).unwrap().text, "# Text");
+
+    let image = dir.path().join("image.png");
+    fs::write(&image, [0x89, b'P', b'N', b'G', 0, 0]).unwrap();
+    assert_eq!(read_note_file(&image).unwrap_err().0, SyncIssueKind::Binary);
+
+    let latin1 = dir.path().join("latin1.md");
+    fs::write(&latin1, [b'c', b'a', b'f', 0xe9]).unwrap();
+    let decoded = read_note_file(&latin1).unwrap();
+    assert_eq!(decoded.text, "café");
+    assert!(decoded.warning().is_some());
+
+    let huge = dir.path().join("huge.md");
+    fs::File::create(&huge)
+        .unwrap()
+        .set_len(MAX_NOTE_BYTES + 1)
+        .unwrap();
+    assert_eq!(
+        read_note_file(&huge).unwrap_err().0,
+        SyncIssueKind::Oversize
+    );
+
+    let missing = dir.path().join("missing.md");
+    assert_eq!(
+        read_note_file(&missing).unwrap_err().0,
+        SyncIssueKind::Unreadable
+    );
+}
@@ -0,0 +1,148 @@
+//! Tag Rule Unit Tests
+//!
+//! Tests for deciding which `[automation]` rules fire on a save and what they
+//! do to the note.
+
+use crate::config::TagRule;
+use crate::core::AppError;
+use crate::services::tag_rule_service::{
+    append_section, moved_note_name, plan_tag_rules, triggered_rules, TagRuleAction,
+};
+use chrono::{Local, TimeZone};
+
+fn rule(tag: &str, move_to: &str, append_template: &str) -> TagRule {
+    TagRule {
+        tag: tag.to_string(),
+        move_to: move_to.to_string(),
+        append_template: append_template.to_string(),
+    }
+}
+
+fn meeting_template(name: &str) -> Result<String, AppError> {
+    match name {
+        "meeting" => {
+            Ok("---\ntags: template\n---\n## Attendees\n\n## Notes for {{title}}\n".to_string())
+        }
+        other => Err(AppError::FileNotFound(format!(
+            "Template not found: {}",
+            other
+        ))),
+    }
+}
+
+#[test]
+fn test_rules_fire_only_when_tag_is_added() {
+    let rules = vec![
+        rule("archive", "archive", ""),
+        rule("meeting", "", "meeting"),
+    ];
+
+    let fired = triggered_rules(&rules, Some("plain note"), "done #archive");
+    assert_eq!(fired, vec![&rules[0]]);
+
+    // Already tagged before the save
+    assert!(triggered_rules(&rules, Some("#archive"), "#archive edited").is_empty());
+
+    // New notes fire for every tag they carry, nested tags included
+    let fired = triggered_rules(&rules, None, "---\ntags: [Meeting/Weekly]\n---\n#archive");
+    assert_eq!(fired.len(), 2);
+
+    assert!(triggered_rules(&rules, None, "#archived").is_empty());
+}
+
+#[test]
+fn test_moved_note_name_keeps_file_name() {
+    assert_eq!(
+        moved_note_name("projects/plan.md", "archive"),
+        Some("archive/plan.md".to_string())
+    );
+    assert_eq!(
+        moved_note_name("plan.md", "archive/2024"),
+        Some("archive/2024/plan.md".to_string())
+    );
+    assert_eq!(moved_note_name("archive/old/plan.md", "archive"), None);
+    assert_eq!(
+        moved_note_name("archived/plan.md", "archive"),
+        Some("archive/plan.md".to_string())
+    );
+}
+
+#[test]
+fn test_append_section_skips_existing_text() {
+    assert_eq!(
+        append_section("# Sync\n\n", "## Notes\n").unwrap(),
+        "# Sync\n\n## Notes\n"
+    );
+    assert_eq!(append_section("", "## Notes").unwrap(), "## Notes\n");
+    assert!(append_section("# Sync\n\n## Notes\n", "## Notes").is_none());
+    assert!(append_section("# Sync", "  \n").is_none());
+}
+
+#[test]
+fn test_plan_appends_template_and_moves_once() {
+    let rules = vec![
+        rule("meeting", "meetings", "meeting"),
+        rule("archive", "archive", ""),
+        rule("standup", "", "missing"),
+    ];
+    let now = Local.with_ymd_and_hms(2024, 6, 1, 9, 30, 0).unwrap();
+    let plan = plan_tag_rules(
+        &rules,
+        "inbox/team-sync.md",
+        Some("# Sync\n"),
+        "# Sync\n#meeting #archive #standup\n",
+        meeting_template,
+        now,
+    );
+
+    assert_eq!(plan.new_name.as_deref(), Some("meetings/team-sync.md"));
+    assert_eq!(plan.final_name(), "meetings/team-sync.md");
+    assert_eq!(
+        plan.content.as_deref(),
+        Some("# Sync\n#meeting #archive #standup\n\n## Attendees\n\n## Notes for team sync\n")
+    );
+    assert!(plan.diff.as_deref().unwrap().contains("+## Attendees"));
+
+    let actions: Vec<(TagRuleAction, &str, bool)> = plan
+        .rules
+        .iter()
+        .map(|r| (r.action, r.target.as_str(), r.error.is_some()))
+        .collect();
+    assert_eq!(
+        actions,
+        vec![
+            (TagRuleAction::AppendTemplate, "meeting", false),
+            (TagRuleAction::Move, "meetings", false),
+            (TagRuleAction::Move, "archive", true),
+            (TagRuleAction::AppendTemplate, "missing", true),
+        ]
+    );
+}
+
+#[test]
+fn test_plan_without_triggered_rules_is_empty() {
+    let rules = vec![rule("archive", "archive", "")];
+    let plan = plan_tag_rules(
+        &rules,
+        "plan.md",
+        None,
+        "#draft",
+        meeting_template,
+        Local::now(),
+    );
+    assert!(plan.rules.is_empty());
+    assert!(plan.new_name.is_none());
+    assert!(plan.content.is_none());
+
+    let mut plan = plan_tag_rules(
+        &rules,
+        "plan.md",
+        None,
+        "#archive",
+        meeting_template,
+        Local::now(),
+    );
+    plan.cancel_move("Note 'archive/plan.md' already exists".to_string());
+    assert_eq!(plan.final_name(), "plan.md");
+    assert!(plan.rules[0].error.is_some());
+}
@@ -0,0 +1,166 @@
+//! Tag Index Unit Tests
+//!
+//! Tests for the derived `note_tags` table and tag queries.
+
+use crate::services::tag_service::{
+    list_all_tags, notes_with_tags, prepare_retag, search_notes_by_tag,
+};
+use crate::tests::test_utils::notes_db;
+use crate::utilities::tags::retag_content;
+
+#[test]
+fn test_list_all_tags_counts_notes() {
+    let conn = notes_db(&[
+        ("a.md", "---\ntags: [Work, ideas]\n---\nBody #work", 1),
+        ("b.md", "Some #ideas and #Reading\n```\n#code\n```", 2),
+        ("c.md", "Issue #123 only", 3),
+    ]);
+
+    let tags: Vec<(String, usize)> = list_all_tags(&conn)
+        .unwrap()
+        .into_iter()
+        .map(|t| (t.tag, t.count))
+        .collect();
+    assert_eq!(
+        tags,
+        vec![
+            ("ideas".to_string(), 2),
+            ("reading".to_string(), 1),
+            ("work".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+fn test_search_notes_by_tag() {
+    let conn = notes_db(&[
+        ("old.md", "#project/alpha", 1),
+        ("new.md", "---\ntags: project/alpha\n---\n", 5),
+        ("other.md", "#project", 3),
+    ]);
+
+    assert_eq!(
+        search_notes_by_tag(&conn, "#Project/Alpha").unwrap(),
+        vec!["new.md".to_string(), "old.md".to_string()]
+    );
+    // A parent tag includes notes tagged with its children
+    assert_eq!(
+        search_notes_by_tag(&conn, "project").unwrap(),
+        vec![
+            "new.md".to_string(),
+            "other.md".to_string(),
+            "old.md".to_string()
+        ]
+    );
+    assert!(search_notes_by_tag(&conn, "proj").unwrap().is_empty());
+    assert!(search_notes_by_tag(&conn, " # ").unwrap().is_empty());
+
+    // Edits are picked up on the next query
+    conn.execute(
+        "UPDATE notes SET content = 'untagged', modified = 9 WHERE filename = 'other.md'",
+        [],
+    )
+    .unwrap();
+    conn.execute(
+        "UPDATE notes SET content = '#project/beta', modified = 9 WHERE filename = 'old.md'",
+        [],
+    )
+    .unwrap();
+    assert_eq!(
+        search_notes_by_tag(&conn, "project").unwrap(),
+        vec!["old.md".to_string(), "new.md".to_string()]
+    );
+    assert_eq!(
+        search_notes_by_tag(&conn, "project/alpha").unwrap(),
+        vec!["new.md".to_string()]
+    );
+}
+
+#[test]
+fn test_list_all_tags_builds_tree() {
+    let conn = notes_db(&[
+        ("a.md", "#project/alpha #project/alpha/docs", 1),
+        ("b.md", "#project/beta", 2),
+        ("c.md", "#project #area/home", 3),
+    ]);
+
+    let tags = list_all_tags(&conn).unwrap();
+    let summary: Vec<(&str, usize, usize)> = tags
+        .iter()
+        .map(|t| (t.tag.as_str(), t.count, t.total))
+        .collect();
+    assert_eq!(summary, vec![("project", 1, 3), ("area", 0, 1)]);
+
+    let project = &tags[0];
+    let children: Vec<(&str, &str, usize, usize)> = project
+        .children
+        .iter()
+        .map(|t| (t.name.as_str(), t.tag.as_str(), t.count, t.total))
+        .collect();
+    assert_eq!(
+        children,
+        vec![
+            ("alpha", "project/alpha", 1, 1),
+            ("beta", "project/beta", 1, 1)
+        ]
+    );
+    let docs = &project.children[0].children;
+    assert_eq!(docs.len(), 1);
+    assert_eq!((docs[0].name.as_str(), docs[0].count), ("docs", 1));
+    assert_eq!(tags[1].children[0].name, "home");
+}
+
+fn sources(tags: &[&str]) -> Vec<String> {
+    tags.iter().map(|tag| tag.to_string()).collect()
+}
+
+#[test]
+fn test_retag_content_rewrites_inline_and_frontmatter() {
+    let content = "---\ntitle: Plan\ntags: [Work, ideas]\n---\nSee #work and #Work/Q3, not #workshop.\n```\n#work\n```\n";
+    let (retagged, count) = retag_content(content, &sources(&["work"]), "Job").unwrap();
+    assert_eq!(
+        retagged,
+        "---\ntitle: Plan\ntags: [Job, ideas]\n---\nSee #Job and #Job/Q3, not #workshop.\n```\n#work\n```\n"
+    );
+    assert_eq!(count, 3);
+
+    assert!(retag_content(content, &sources(&["reading"]), "books").is_none());
+}
+
+#[test]
+fn test_retag_content_merges_frontmatter_duplicates() {
+    let content = "---\ntags: draft, wip, done\n---\n#wip today\n";
+    let (merged, count) = retag_content(content, &sources(&["draft", "wip"]), "todo").unwrap();
+    assert_eq!(merged, "---\ntags: [todo, done]\n---\n#todo today\n");
+    assert_eq!(count, 3);
+
+    // Merging into one of the sources leaves that tag alone
+    let (merged, _) = retag_content("#a and #b\n", &sources(&["a", "b"]), "a").unwrap();
+    assert_eq!(merged, "#a and #a\n");
+}
+
+#[test]
+fn test_prepare_retag_validates_target() {
+    let (normalized, target) =
+        prepare_retag(&sources(&["#Work", "work", " "]), "#Projects/Job/").unwrap();
+    assert_eq!(normalized, vec!["work"]);
+    assert_eq!(target, "Projects/Job");
+
+    assert!(prepare_retag(&sources(&["work"]), "two words").is_err());
+    assert!(prepare_retag(&sources(&["work"]), "123").is_err());
+    assert!(prepare_retag(&sources(&[""]), "job").is_err());
+    assert!(prepare_retag(&sources(&["work"]), "work/old").is_err());
+}
+
+#[test]
+fn test_notes_with_tags_includes_nested_tags() {
+    let conn = notes_db(&[
+        ("a.md", "#project/alpha", 1),
+        ("b.md", "#project", 2),
+        ("c.md", "#projects", 3),
+    ]);
+    assert_eq!(
+        notes_with_tags(&conn, &sources(&["project"])).unwrap(),
+        vec!["a.md", "b.md"]
+    );
+}
@@ -0,0 +1,66 @@
+//! Template Unit Tests
+//!
+//! Tests for locating, listing and rendering note templates.
+
+use crate::services::template_service::{
+    list_templates, load_template, render_template, templates_dir, DEFAULT_TEMPLATES_DIR,
+};
+use chrono::{Local, TimeZone};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[test]
+fn test_templates_dir_resolution() {
+    let notes = Path::new("/vault");
+    assert_eq!(templates_dir(notes, ""), notes.join(DEFAULT_TEMPLATES_DIR));
+    assert_eq!(templates_dir(notes, "Templates"), notes.join("Templates"));
+    assert_eq!(
+        templates_dir(notes, "/elsewhere/templates"),
+        Path::new("/elsewhere/templates")
+    );
+}
+
+#[test]
+fn test_list_templates_only_markdown_files() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("meeting.md"), "").unwrap();
+    fs::write(dir.path().join("Daily.MD"), "").unwrap();
+    fs::write(dir.path().join("notes.txt"), "").unwrap();
+    fs::write(dir.path().join(".hidden.md"), "").unwrap();
+    fs::create_dir(dir.path().join("nested.md")).unwrap();
+
+    assert_eq!(
+        list_templates(dir.path()).unwrap(),
+        vec!["Daily.MD".to_string(), "meeting.md".to_string()]
+    );
+    assert!(list_templates(&dir.path().join("missing"))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_load_template() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("meeting.md"), "# {{title}}").unwrap();
+
+    assert_eq!(
+        load_template(dir.path(), "meeting.md").unwrap(),
+        "# {{title}}"
+    );
+    assert_eq!(load_template(dir.path(), "meeting").unwrap(), "# {{title}}");
+    assert!(load_template(dir.path(), "missing").is_err());
+    assert!(load_template(dir.path(), "../secret.md").is_err());
+    assert!(load_template(dir.path(), "").is_err());
+}
+
+#[test]
+fn test_render_template() {
+    let now = Local.with_ymd_and_hms(2024, 6, 1, 9, 5, 0).unwrap();
+    let body = "# {{title}}\nDate: {{date}} {{time}}\n{{date:%d/%m/%Y}} {{unknown}}";
+
+    assert_eq!(
+        render_template(body, "Projects/weekly_review.md", now),
+        "# weekly review\nDate: 2024-06-01 09:05\n01/06/2024 {{unknown}}"
+    );
+}
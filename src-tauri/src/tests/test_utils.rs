@@ -5,7 +5,9 @@
 
 use crate::config::AppConfig;
 use crate::core::state::AppState;
-use crate::services::database_service::recreate_database;
+use crate::services::database_service::{init_db, recreate_database};
+use crate::utilities::config_helpers::DEFAULT_FTS_TOKENIZER;
+use rusqlite::{params, Connection};
 use std::path::Path;
 use std::sync::Mutex;
 use tempfile::TempDir;
@@ -160,6 +162,51 @@ impl DbTestHarness {
     }
 }
 
+/// A note row for [`notes_db`]: `(filename, content, modified)`, or a shorter
+/// tuple leaving the rest empty.
+pub trait NoteRow {
+    fn row(&self) -> (&str, &str, i64);
+}
+
+impl NoteRow for (&str, &str, i64) {
+    fn row(&self) -> (&str, &str, i64) {
+        (self.0, self.1, self.2)
+    }
+}
+
+impl NoteRow for (&str, &str) {
+    fn row(&self) -> (&str, &str, i64) {
+        (self.0, self.1, 0)
+    }
+}
+
+impl NoteRow for &str {
+    fn row(&self) -> (&str, &str, i64) {
+        (self, "", 0)
+    }
+}
+
+/// Creates the app's schema on `conn` the way startup does, then adds `notes`
+/// as indexed notes.
+pub fn init_notes_db<N: NoteRow>(conn: &Connection, notes: &[N]) {
+    init_db(conn, DEFAULT_FTS_TOKENIZER).expect("Should initialize database");
+    for note in notes {
+        let (filename, content, modified) = note.row();
+        conn.execute(
+            "INSERT INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, '', ?3, 1)",
+            params![filename, content, modified],
+        )
+        .unwrap();
+    }
+}
+
+/// An in-memory database set up by [`init_notes_db`].
+pub fn notes_db<N: NoteRow>(notes: &[N]) -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    init_notes_db(&conn, notes);
+    conn
+}
+
 /// Test configuration override utility
 ///
 /// This struct temporarily overrides the global APP_CONFIG to use a test directory,
@@ -347,6 +394,21 @@ mod test_command_wrappers {
         crate::commands::notes::create_new_note(note_name, app_state)
     }
 
+    pub fn test_duplicate_note(note_name: &str, new_name: Option<&str>) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_duplicate_note() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::duplicate_note(
+            note_name,
+            new_name.map(|name| name.to_string()),
+            app_state,
+        )
+    }
+
     pub fn test_get_note_content(note_name: &str) -> Result<String, String> {
         // SAFETY CHECK: Ensure we're in test mode before proceeding
         if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
@@ -355,7 +417,7 @@ mod test_command_wrappers {
 
         let app = create_test_mock_app();
         let app_state = app.state::<AppState>();
-        crate::commands::notes::get_note_content(note_name, app_state)
+        crate::commands::notes::read_note_content(&app_state, note_name).map_err(|e| e.to_string())
     }
 
     pub fn test_delete_note(note_name: &str) -> Result<(), String> {
@@ -368,7 +430,21 @@ mod test_command_wrappers {
 
         let app = create_test_mock_app();
         let app_state = app.state::<AppState>();
-        crate::commands::notes::delete_note(note_name, app_state)
+        crate::commands::notes::delete_note(note_name, None, app_state).map(|_| ())
+    }
+
+    pub fn test_delete_note_dry_run(
+        note_name: &str,
+    ) -> Result<crate::utilities::dry_run::DryRunReport, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_delete_note_dry_run() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::delete_note(note_name, Some(true), app_state)
+            .map(|report| report.unwrap_or_default())
     }
 
     pub fn test_save_note_with_content_check(
@@ -387,6 +463,8 @@ mod test_command_wrappers {
             note_name,
             content,
             original_content,
+            None,
+            app.handle().clone(),
             app_state,
         )
     }
@@ -401,7 +479,21 @@ mod test_command_wrappers {
 
         let app = create_test_mock_app();
         let app_state = app.state::<AppState>();
-        crate::commands::notes::rename_note(old_name, new_name, app_state)
+        crate::commands::notes::rename_note(old_name, new_name, None, app_state)
+    }
+
+    pub fn test_rename_note_updating_links(
+        old_name: String,
+        new_name: String,
+    ) -> Result<(), String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_rename_note_updating_links() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::rename_note(old_name, new_name, Some(true), app_state)
     }
 
     pub fn test_list_all_notes() -> Result<Vec<String>, String> {
@@ -412,7 +504,14 @@ mod test_command_wrappers {
 
         let app = create_test_mock_app();
         let app_state = app.state::<AppState>();
-        crate::commands::notes::list_all_notes(app_state)
+        let page = crate::commands::notes::list_notes(
+            None,
+            Some(crate::commands::notes::MAX_PAGE_SIZE),
+            None,
+            None,
+            app_state,
+        )?;
+        Ok(page.notes.into_iter().map(|note| note.filename).collect())
     }
 
     pub fn test_get_note_html_content(note_name: &str) -> Result<String, String> {
@@ -423,13 +522,13 @@ mod test_command_wrappers {
 
         let app = create_test_mock_app();
         let app_state = app.state::<AppState>();
-        crate::commands::notes::get_note_html_content(note_name, app_state)
+        crate::commands::notes::read_note_html(&app_state, note_name).map_err(|e| e.to_string())
     }
 
     pub fn test_search_notes_hybrid(
         query: &str,
         max_results: usize,
-    ) -> crate::core::AppResult<Vec<String>> {
+    ) -> crate::core::AppResult<Vec<crate::search::SearchResult>> {
         // SAFETY CHECK: Ensure we're in test mode before proceeding
         if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
             panic!("CRITICAL SAFETY ERROR: test_search_notes_hybrid() called outside of TestConfigOverride!");
@@ -0,0 +1,78 @@
+//! Text Encoding Unit Tests
+//!
+//! Tests decoding note files and normalizing their line endings.
+
+use crate::utilities::text_encoding::{
+    decode_text, detect_line_ending, encode_note_text, normalize_line_endings, with_line_endings,
+    LineEnding, TextEncoding,
+};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_detect_line_ending() {
+    assert_eq!(detect_line_ending("one line"), LineEnding::None);
+    assert_eq!(detect_line_ending("a\nb\n"), LineEnding::Lf);
+    assert_eq!(detect_line_ending("a\r\nb\r\n"), LineEnding::Crlf);
+    assert_eq!(detect_line_ending("a\r\nb\n"), LineEnding::Mixed);
+    assert_eq!(detect_line_ending("a\rb"), LineEnding::Mixed);
+}
+
+#[test]
+fn test_normalize_line_endings() {
+    assert_eq!(normalize_line_endings("a\r\nb\rc\n"), "a\nb\nc\n");
+    assert_eq!(with_line_endings("a\r\nb\n", true), "a\r\nb\r\n");
+    assert_eq!(with_line_endings("a\r\nb\n", false), "a\nb\n");
+}
+
+#[test]
+fn test_decode_utf8_with_and_without_bom() {
+    let plain = decode_text("# Café\r\nText".as_bytes()).unwrap();
+    assert_eq!(plain.text, "# Café\nText");
+    assert_eq!(plain.encoding, TextEncoding::Utf8);
+    assert_eq!(plain.line_ending, LineEnding::Crlf);
+    assert_eq!(plain.warning(), None);
+
+    let bom = decode_text(b"\xEF\xBB\xBF# Note").unwrap();
+    assert_eq!(bom.text, "# Note");
+    assert_eq!(bom.encoding, TextEncoding::Utf8Bom);
+}
+
+#[test]
+fn test_decode_utf16() {
+    let mut little = vec![0xFF, 0xFE];
+    little.extend("hé\n".encode_utf16().flat_map(u16::to_le_bytes));
+    let decoded = decode_text(&little).unwrap();
+    assert_eq!(decoded.text, "hé\n");
+    assert_eq!(decoded.encoding, TextEncoding::Utf16Le);
+    assert!(decoded.warning().is_some());
+
+    let mut big = vec![0xFE, 0xFF];
+    big.extend("hé".encode_utf16().flat_map(u16::to_be_bytes));
+    assert_eq!(decode_text(&big).unwrap().encoding, TextEncoding::Utf16Be);
+}
+
+#[test]
+fn test_decode_windows_1252_keeps_every_byte() {
+    let decoded = decode_text(b"\x93quoted\x94 caf\xE9 \x81").unwrap();
+    assert_eq!(decoded.text, "\u{201C}quoted\u{201D} café \u{0081}");
+    assert_eq!(decoded.encoding, TextEncoding::Windows1252);
+    assert!(decoded.warning().is_some());
+}
+
+#[test]
+fn test_decode_rejects_binary() {
+    assert_eq!(decode_text(&[0x89, b'P', b'N', b'G', 0, 0]), None);
+    assert_eq!(decode_text(b"\xFF\xFEa"), None);
+}
+
+#[test]
+fn test_encode_note_text_preserves_crlf_notes() {
+    let dir = TempDir::new().unwrap();
+    let crlf_note = dir.path().join("windows.md");
+    fs::write(&crlf_note, "a\r\nb\r\n").unwrap();
+    let new_note = dir.path().join("new.md");
+
+    assert_eq!(encode_note_text("a\nc\n", &crlf_note), "a\r\nc\r\n");
+    assert_eq!(encode_note_text("a\r\nc\n", &new_note), "a\nc\n");
+}
@@ -0,0 +1,85 @@
+//! Time Tracking Unit Tests
+//!
+//! Tests for `⏱` time entry parsing and grouped time reports.
+
+use crate::services::time_tracking_service::{
+    note_project, time_report, TimeGroupBy, UNTAGGED_GROUP,
+};
+use crate::tests::test_utils::notes_db;
+use crate::utilities::dates::DateRange;
+use crate::utilities::time_entries::{extract_time_entries, parse_time_entry};
+
+#[test]
+fn test_parse_time_entries() {
+    let entry = parse_time_entry(
+        "- ⏱ 2024-06-01 09:00-10:30 #ProjectX #meeting Sprint planning",
+        4,
+    )
+    .unwrap();
+    assert_eq!(entry.minutes, 90);
+    assert_eq!(entry.tags, vec!["projectx", "meeting"]);
+    assert_eq!(entry.description, "Sprint planning");
+    assert_eq!(entry.start, "09:00");
+    assert_eq!(entry.line, 4);
+
+    let overnight = parse_time_entry("⏱️ 2024-06-01 23:30 – 0:15", 1).unwrap();
+    assert_eq!(overnight.minutes, 45);
+    assert_eq!(overnight.end, "00:15");
+    assert!(overnight.tags.is_empty());
+
+    assert!(parse_time_entry("⏱ 2024-13-01 09:00-10:00", 1).is_none());
+    assert!(parse_time_entry("⏱ 2024-06-01 25:00-10:00", 1).is_none());
+    assert_eq!(
+        extract_time_entries("```\n⏱ 2024-06-01 09:00-10:00\n```\n").len(),
+        0
+    );
+
+    assert_eq!(
+        note_project("Work/Alpha/log.md", "no front-matter"),
+        "Work/Alpha"
+    );
+    assert_eq!(note_project("log.md", "---\nproject: Beta\n---\n"), "Beta");
+    assert_eq!(note_project("log.md", ""), "(no project)");
+}
+
+#[test]
+fn test_time_report_groups() {
+    let conn = notes_db(&[
+        (
+            "Work/Alpha/log.md",
+            "⏱ 2024-06-01 09:00-10:30 #dev #alpha\n⏱ 2024-06-02 14:00-15:00 #dev\n⏱ 2024-05-20 08:00-09:00 #dev",
+        ),
+        ("journal.md", "⏱ 2024-06-02 20:00-20:30 reading"),
+    ]);
+    let june = DateRange {
+        from: Some("2024-06-01".to_string()),
+        to: Some("2024-06-30".to_string()),
+    };
+
+    let by_tag = time_report(&conn, &june, TimeGroupBy::Tag).unwrap();
+    assert_eq!(by_tag.total_minutes, 180);
+    let groups: Vec<(&str, i64)> = by_tag
+        .groups
+        .iter()
+        .map(|g| (g.key.as_str(), g.minutes))
+        .collect();
+    assert_eq!(
+        groups,
+        vec![("dev", 150), ("alpha", 90), (UNTAGGED_GROUP, 30)]
+    );
+
+    let by_project = time_report(&conn, &june, TimeGroupBy::Project).unwrap();
+    assert_eq!(by_project.groups[0].key, "Work/Alpha");
+    assert_eq!(by_project.groups[0].entries, 2);
+
+    let by_day = time_report(&conn, &DateRange::default(), TimeGroupBy::Day).unwrap();
+    let days: Vec<&str> = by_day.groups.iter().map(|g| g.key.as_str()).collect();
+    assert_eq!(days, vec!["2024-05-20", "2024-06-01", "2024-06-02"]);
+    assert_eq!(by_day.total_minutes, 240);
+
+    let invalid = DateRange {
+        from: Some("June".to_string()),
+        to: None,
+    };
+    assert!(time_report(&conn, &invalid, TimeGroupBy::Note).is_err());
+}
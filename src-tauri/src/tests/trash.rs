@@ -0,0 +1,164 @@
+//! Trash Unit Tests
+//!
+//! Tests for moving deleted notes to the trash, restoring them and purging.
+
+use crate::services::trash_service::{
+    empty_trash, list_trash, move_to_trash, next_trash_id, next_trash_ids, purge_expired_trash,
+    restore_from_trash, trash_dir, trashed_note_path,
+};
+use std::fs;
+use tempfile::TempDir;
+
+const DAY: i64 = 24 * 60 * 60;
+
+fn vault_with(notes: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (name, content) in notes {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_move_to_trash_and_restore() {
+    let vault = vault_with(&[("work/plan.md", "the plan")]);
+    let notes_dir = vault.path();
+
+    let entry = move_to_trash(notes_dir, "work/plan.md", 1_000).unwrap();
+    assert!(!notes_dir.join("work/plan.md").exists());
+    assert_eq!(
+        fs::read_to_string(trashed_note_path(notes_dir, &entry.id, &entry.note_name)).unwrap(),
+        "the plan"
+    );
+    assert_eq!(list_trash(notes_dir).unwrap(), vec![entry.clone()]);
+
+    let restored = restore_from_trash(notes_dir, &entry.id).unwrap();
+    assert_eq!(restored, "work/plan.md");
+    assert_eq!(
+        fs::read_to_string(notes_dir.join("work/plan.md")).unwrap(),
+        "the plan"
+    );
+    assert!(list_trash(notes_dir).unwrap().is_empty());
+}
+
+#[test]
+fn test_restore_does_not_overwrite_existing_note() {
+    let vault = vault_with(&[("a.md", "old")]);
+    let notes_dir = vault.path();
+
+    let entry = move_to_trash(notes_dir, "a.md", 1_000).unwrap();
+    fs::write(notes_dir.join("a.md"), "new").unwrap();
+
+    let restored = restore_from_trash(notes_dir, &entry.id).unwrap();
+    assert_eq!(restored, "a (restored).md");
+    assert_eq!(fs::read_to_string(notes_dir.join("a.md")).unwrap(), "new");
+    assert_eq!(
+        fs::read_to_string(notes_dir.join("a (restored).md")).unwrap(),
+        "old"
+    );
+}
+
+#[test]
+fn test_same_second_deletes_get_distinct_ids() {
+    let vault = vault_with(&[("a.md", "a"), ("b.md", "b")]);
+    let notes_dir = vault.path();
+
+    assert_eq!(next_trash_id(notes_dir, 5).unwrap(), "5");
+    let first = move_to_trash(notes_dir, "a.md", 5).unwrap();
+    assert_eq!(next_trash_id(notes_dir, 5).unwrap(), "5-1");
+    let second = move_to_trash(notes_dir, "b.md", 5).unwrap();
+    assert_ne!(first.id, second.id);
+    assert_eq!(list_trash(notes_dir).unwrap().len(), 2);
+}
+
+#[test]
+fn test_next_trash_ids_plan_several_deletes() {
+    let vault = vault_with(&[("a.md", "a")]);
+    let notes_dir = vault.path();
+
+    move_to_trash(notes_dir, "a.md", 5).unwrap();
+    assert_eq!(
+        next_trash_ids(notes_dir, 2, 5).unwrap(),
+        vec!["5-1".to_string(), "5-2".to_string()]
+    );
+}
+
+#[test]
+fn test_trashed_files_keep_their_extension() {
+    let vault = vault_with(&[("img/photo.png", "png"), ("img/.hidden", "dot")]);
+    let notes_dir = vault.path();
+
+    let photo = move_to_trash(notes_dir, "img/photo.png", 1_000).unwrap();
+    let hidden = move_to_trash(notes_dir, "img/.hidden", 1_000).unwrap();
+    assert!(trash_dir(notes_dir).join("1000.png").is_file());
+    assert!(trash_dir(notes_dir).join("1000-1").is_file());
+
+    assert_eq!(
+        restore_from_trash(notes_dir, &photo.id).unwrap(),
+        "img/photo.png"
+    );
+    assert_eq!(
+        restore_from_trash(notes_dir, &hidden.id).unwrap(),
+        "img/.hidden"
+    );
+    assert_eq!(
+        fs::read_to_string(notes_dir.join("img/photo.png")).unwrap(),
+        "png"
+    );
+}
+
+#[test]
+fn test_restore_file_trashed_with_legacy_name() {
+    let vault = vault_with(&[("photo.png", "png")]);
+    let notes_dir = vault.path();
+
+    let entry = move_to_trash(notes_dir, "photo.png", 1_000).unwrap();
+    fs::rename(
+        trash_dir(notes_dir).join("1000.png"),
+        trash_dir(notes_dir).join("1000.md"),
+    )
+    .unwrap();
+    assert_eq!(
+        restore_from_trash(notes_dir, &entry.id).unwrap(),
+        "photo.png"
+    );
+}
+
+#[test]
+fn test_restore_unknown_id_fails() {
+    let vault = vault_with(&[]);
+    assert!(restore_from_trash(vault.path(), "../escape").is_err());
+}
+
+#[test]
+fn test_purge_expired_trash() {
+    let vault = vault_with(&[("old.md", "old"), ("recent.md", "recent")]);
+    let notes_dir = vault.path();
+    let now = 100 * DAY;
+
+    let old = move_to_trash(notes_dir, "old.md", now - 31 * DAY).unwrap();
+    let recent = move_to_trash(notes_dir, "recent.md", now - DAY).unwrap();
+
+    assert_eq!(purge_expired_trash(notes_dir, 0, now).unwrap(), 0);
+    assert_eq!(purge_expired_trash(notes_dir, 30, now).unwrap(), 1);
+    assert!(!trashed_note_path(notes_dir, &old.id, &old.note_name).exists());
+    assert_eq!(list_trash(notes_dir).unwrap(), vec![recent]);
+}
+
+#[test]
+fn test_empty_trash() {
+    let vault = vault_with(&[("a.md", "a"), ("b.md", "b")]);
+    let notes_dir = vault.path();
+    move_to_trash(notes_dir, "a.md", 1).unwrap();
+    move_to_trash(notes_dir, "b.md", 2).unwrap();
+
+    assert_eq!(empty_trash(notes_dir).unwrap(), 2);
+    assert!(list_trash(notes_dir).unwrap().is_empty());
+    assert_eq!(
+        fs::read_dir(trash_dir(notes_dir)).unwrap().count(),
+        1,
+        "only the index should remain"
+    );
+}
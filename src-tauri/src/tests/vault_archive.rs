@@ -0,0 +1,59 @@
+//! Vault Archive Unit Tests
+//!
+//! Tests for whole-vault archives, plain and passphrase-encrypted.
+
+use crate::services::share_service::SharedNote;
+use crate::services::vault_archive_service::{
+    build_vault_archive, open_vault_archive, read_vault_archive_manifest,
+};
+
+fn notes() -> Vec<SharedNote> {
+    vec![
+        SharedNote {
+            filename: "Recipes/Bread.md".to_string(),
+            content: "# Bread\nFlour, water, salt.".to_string(),
+        },
+        SharedNote {
+            filename: "todo.txt".to_string(),
+            content: "buy yeast".to_string(),
+        },
+    ]
+}
+
+#[test]
+fn test_plain_vault_archive_round_trip() {
+    let archive = build_vault_archive(&notes(), None).expect("archive failed");
+
+    let manifest = read_vault_archive_manifest(&archive).unwrap();
+    assert_eq!(manifest.encryption, "none");
+    assert_eq!(manifest.note_count, 2);
+
+    let (_, opened) = open_vault_archive(&archive, None).unwrap();
+    assert_eq!(opened, notes());
+}
+
+#[test]
+fn test_encrypted_vault_archive_needs_its_passphrase() {
+    let archive = build_vault_archive(&notes(), Some("correct horse battery")).unwrap();
+    assert_eq!(
+        read_vault_archive_manifest(&archive).unwrap().encryption,
+        "age-scrypt"
+    );
+    assert!(
+        !String::from_utf8_lossy(&archive).contains("Flour"),
+        "note content must not be stored in plain text"
+    );
+    assert!(!String::from_utf8_lossy(&archive).contains("Bread.md"));
+
+    let (_, opened) = open_vault_archive(&archive, Some("correct horse battery")).unwrap();
+    assert_eq!(opened, notes());
+
+    assert!(open_vault_archive(&archive, None).is_err());
+    let wrong = open_vault_archive(&archive, Some("wrong passphrase")).unwrap_err();
+    assert!(wrong.to_string().contains("Incorrect passphrase"));
+}
+
+#[test]
+fn test_vault_archive_rejects_short_passphrase() {
+    assert!(build_vault_archive(&notes(), Some("short")).is_err());
+}
@@ -0,0 +1,72 @@
+//! Vault Check Unit Tests
+//!
+//! Tests for the pre-flight check run before switching notes directories.
+
+use crate::services::vault_check_service::check_vault_with_app_dir;
+use crate::utilities::paths::encode_path_for_backup;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_check_vault_counts_notes_and_skips_hidden_files() {
+    let temp = TempDir::new().unwrap();
+    let vault = temp.path().join("vault");
+    fs::create_dir_all(vault.join("work")).unwrap();
+    fs::create_dir_all(vault.join(".git")).unwrap();
+    fs::write(vault.join("a.md"), "12345").unwrap();
+    fs::write(vault.join("work/b.md"), "123").unwrap();
+    fs::write(vault.join(".git/config"), "ignored").unwrap();
+    fs::write(vault.join(".hidden.md"), "ignored").unwrap();
+
+    let current = temp.path().join("current");
+    let check = check_vault_with_app_dir(vault.to_str().unwrap(), &current, None);
+    assert!(check.exists && check.readable && check.usable);
+    assert_eq!(check.note_count, 2);
+    assert_eq!(check.total_bytes, 8);
+    assert!(!check.truncated);
+    assert!(check.nested_vaults.is_empty());
+    assert!(check.warnings.is_empty());
+}
+
+#[test]
+fn test_check_vault_finds_nested_vaults_and_app_folder() {
+    let temp = TempDir::new().unwrap();
+    let vault = temp.path().join("vault");
+    let app_dir = vault.join("data/symiosis");
+    fs::create_dir_all(vault.join("other/.obsidian")).unwrap();
+    fs::create_dir_all(&app_dir).unwrap();
+    let indexed = vault.join("indexed");
+    let database = app_dir
+        .join("databases")
+        .join(encode_path_for_backup(&indexed))
+        .join("notes.sqlite");
+    fs::create_dir_all(database.parent().unwrap()).unwrap();
+    fs::write(&database, "").unwrap();
+    fs::create_dir_all(&indexed).unwrap();
+
+    let check = check_vault_with_app_dir(vault.to_str().unwrap(), &indexed, Some(&app_dir));
+    assert!(check.usable);
+    assert_eq!(check.nested_vaults, vec!["indexed", "other"]);
+    assert_eq!(check.warnings.len(), 3);
+    assert!(check.warnings[0].contains("app's data folder"));
+    assert_eq!(check.warnings[1], "Contains the current notes directory");
+    assert!(check.warnings[2].contains("2 other vault(s)"));
+}
+
+#[test]
+fn test_check_vault_rejects_unusable_paths() {
+    let temp = TempDir::new().unwrap();
+    let file = temp.path().join("note.md");
+    fs::write(&file, "").unwrap();
+
+    let check = check_vault_with_app_dir(file.to_str().unwrap(), temp.path(), None);
+    assert!(!check.exists && !check.usable);
+
+    let check = check_vault_with_app_dir("/", temp.path(), None);
+    assert!(!check.usable);
+    assert_eq!(check.warnings.len(), 1);
+
+    let missing = temp.path().join("new");
+    let check = check_vault_with_app_dir(missing.to_str().unwrap(), temp.path(), None);
+    assert!(check.usable && !check.exists);
+}
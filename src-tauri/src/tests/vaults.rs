@@ -0,0 +1,132 @@
+//! Vault Unit Tests
+//!
+//! Tests for listing, adding and finding vaults to switch between.
+
+use crate::config::VaultConfig;
+use crate::core::AppError;
+use crate::services::vault_service::{
+    add_vault, find_vault, known_vaults, list_vaults, unpicked_vault_paths, Vault,
+};
+use tempfile::TempDir;
+
+fn vault(name: &str, path: &str) -> VaultConfig {
+    VaultConfig {
+        name: name.to_string(),
+        path: path.to_string(),
+    }
+}
+
+#[test]
+fn test_list_vaults_marks_open_vault() {
+    let vaults = vec![
+        vault("Personal", "/test/notes"),
+        vault("Work", "/test/work"),
+    ];
+    let listed = list_vaults(&vaults, "/test/work/");
+    assert_eq!(
+        listed,
+        vec![
+            Vault {
+                name: "Personal".to_string(),
+                path: "/test/notes".to_string(),
+                active: false,
+            },
+            Vault {
+                name: "Work".to_string(),
+                path: "/test/work".to_string(),
+                active: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_known_vaults_include_unlisted_notes_directory() {
+    assert_eq!(
+        known_vaults(&[], "/test/Notes"),
+        vec![vault("Notes", "/test/Notes")]
+    );
+
+    // Named after its folder, without taking another vault's name
+    let vaults = vec![vault("notes", "/test/other")];
+    assert_eq!(
+        known_vaults(&vaults, "/test/Notes"),
+        vec![
+            vault("Notes 2", "/test/Notes"),
+            vault("notes", "/test/other")
+        ]
+    );
+}
+
+#[test]
+fn test_add_vault_keeps_open_vault() {
+    let vaults = add_vault(&[], "/test/notes", "/test/Work Notes", None).unwrap();
+    assert_eq!(
+        vaults,
+        vec![
+            vault("notes", "/test/notes"),
+            vault("Work Notes", "/test/Work Notes")
+        ]
+    );
+
+    let vaults = add_vault(&vaults, "/test/notes", " /test/archive ", Some(" Old ")).unwrap();
+    assert_eq!(vaults.last(), Some(&vault("Old", "/test/archive")));
+    assert_eq!(vaults.len(), 3);
+}
+
+#[test]
+fn test_add_vault_rejects_duplicates_and_bad_paths() {
+    let vaults = vec![vault("Personal", "/test/notes")];
+    for (path, name) in [
+        ("/test/notes/", None),
+        ("/test/work", Some("personal")),
+        ("relative/notes", None),
+        ("/etc/notes", None),
+    ] {
+        assert!(
+            add_vault(&vaults, "/test/notes", path, name).is_err(),
+            "{} should be rejected",
+            path
+        );
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("file.md");
+    std::fs::write(&file, "not a folder").unwrap();
+    assert!(matches!(
+        add_vault(&vaults, "/test/notes", &file.to_string_lossy(), None),
+        Err(AppError::InvalidNoteName(_))
+    ));
+}
+
+#[test]
+fn test_find_vault_ignores_case() {
+    let vaults = vec![vault("Work", "/test/work")];
+    assert_eq!(
+        find_vault(&vaults, "/test/notes", "work").unwrap(),
+        vault("Work", "/test/work")
+    );
+    assert_eq!(
+        find_vault(&vaults, "/test/notes", "Notes").unwrap().path,
+        "/test/notes"
+    );
+    assert!(find_vault(&vaults, "/test/notes", "Missing").is_err());
+}
+
+#[test]
+fn test_unpicked_vault_paths_flags_new_folders() {
+    let vaults = vec![vault("Work", "/test/work")];
+
+    // Switching between and renaming known vaults is fine
+    let renamed = vec![
+        vault("Job", "/test/work/"),
+        vault("Personal", "/test/notes"),
+    ];
+    assert!(unpicked_vault_paths(&vaults, "/test/notes", &renamed, "/test/work").is_empty());
+
+    let edited = vec![vault("Work", "/test/work"), vault("Secrets", "/test/other")];
+    assert_eq!(
+        unpicked_vault_paths(&vaults, "/test/notes", &edited, "/test/other/"),
+        vec!["/test/other/".to_string()]
+    );
+}
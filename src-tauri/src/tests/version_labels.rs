@@ -0,0 +1,54 @@
+//! Version Label Unit Tests
+//!
+//! Tests for naming note backups so they are kept and listed first.
+
+use crate::utilities::version_labels::{
+    load_version_labels, normalize_version_label, set_version_label, VERSION_LABELS_FILE,
+};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_normalize_version_label() {
+    assert_eq!(
+        normalize_version_label("  before big rewrite ").unwrap(),
+        Some("before big rewrite".to_string())
+    );
+    assert_eq!(normalize_version_label("   ").unwrap(), None);
+    assert!(normalize_version_label("two\nlines").is_err());
+    assert!(normalize_version_label(&"x".repeat(101)).is_err());
+}
+
+#[test]
+fn test_set_version_label_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    assert!(load_version_labels(dir.path()).unwrap().is_empty());
+
+    fs::write(dir.path().join("plan.rename_backup.100.md"), "v1").unwrap();
+    fs::write(dir.path().join("plan.rename_backup.200.md"), "v2").unwrap();
+    set_version_label(dir.path(), "plan.rename_backup.100.md", "before rewrite").unwrap();
+    set_version_label(dir.path(), "plan.rename_backup.200.md", "draft").unwrap();
+
+    let labels = load_version_labels(dir.path()).unwrap();
+    assert_eq!(labels["plan.rename_backup.100.md"], "before rewrite");
+    assert_eq!(labels.len(), 2);
+
+    // Blank labels remove, and labels of pruned backups are dropped
+    set_version_label(dir.path(), "plan.rename_backup.200.md", "").unwrap();
+    fs::remove_file(dir.path().join("plan.rename_backup.100.md")).unwrap();
+    fs::write(dir.path().join("plan.rename_backup.300.md"), "v3").unwrap();
+    set_version_label(dir.path(), "plan.rename_backup.300.md", "final").unwrap();
+
+    let labels = load_version_labels(dir.path()).unwrap();
+    assert_eq!(
+        labels.keys().collect::<Vec<_>>(),
+        vec!["plan.rename_backup.300.md"]
+    );
+}
+
+#[test]
+fn test_invalid_labels_file_is_an_error() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join(VERSION_LABELS_FILE), "not json").unwrap();
+    assert!(load_version_labels(dir.path()).is_err());
+}
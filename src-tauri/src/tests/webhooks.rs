@@ -0,0 +1,148 @@
+//! Webhook Unit Tests
+//!
+//! Tests for converting notes to Slack and Discord formatting and splitting
+//! them into messages. Nothing is posted.
+
+use crate::config::WebhooksConfig;
+use crate::services::webhook_service::{
+    format_note_messages, webhook_payload, ChannelTarget, DISCORD_MESSAGE_LIMIT,
+};
+use crate::utilities::chat_format::{markdown_to_discord, markdown_to_slack, split_message};
+
+#[test]
+fn test_channel_target_parse() {
+    assert_eq!(ChannelTarget::parse("Slack").unwrap(), ChannelTarget::Slack);
+    assert_eq!(
+        ChannelTarget::parse(" discord ").unwrap(),
+        ChannelTarget::Discord
+    );
+    assert!(ChannelTarget::parse("teams").is_err());
+}
+
+#[test]
+fn test_webhook_url_requires_configuration() {
+    let config = WebhooksConfig {
+        slack_url: "https://hooks.slack.com/services/T/B/X".to_string(),
+        discord_url: String::new(),
+    };
+
+    assert_eq!(
+        ChannelTarget::Slack.webhook_url(&config).unwrap(),
+        "https://hooks.slack.com/services/T/B/X"
+    );
+    let error = ChannelTarget::Discord
+        .webhook_url(&config)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("discord_url"));
+}
+
+#[test]
+fn test_markdown_to_slack_inline() {
+    let slack = markdown_to_slack(
+        "Some **bold**, *italic*, ~~gone~~ and `code` with [a link](https://example.com).",
+    );
+    assert_eq!(
+        slack,
+        "Some *bold*, _italic_, ~gone~ and `code` with <https://example.com|a link>."
+    );
+}
+
+#[test]
+fn test_markdown_to_slack_blocks() {
+    let slack = markdown_to_slack(
+        "# Standup\n\n- Done\n- [x] Shipped\n- [ ] Review\n\n1. First\n2. Second\n\n> Quoted\n\n```\nlet a = 1 < 2;\n```\n",
+    );
+    assert_eq!(
+        slack,
+        "*Standup*\n\n• Done\n• ☑ Shipped\n• ☐ Review\n\n1. First\n2. Second\n\n> Quoted\n\n```\nlet a = 1 &lt; 2;\n```"
+    );
+}
+
+#[test]
+fn test_markdown_to_slack_escapes_control_characters() {
+    assert_eq!(
+        markdown_to_slack("Tom & Jerry <@channel>"),
+        "Tom &amp; Jerry &lt;@channel&gt;"
+    );
+}
+
+#[test]
+fn test_markdown_to_discord() {
+    let discord = markdown_to_discord(
+        "## Kept\n#### Deep heading\n- [ ] Open\n  * [x] Done\n```\n#### not a heading\n```",
+    );
+    assert_eq!(
+        discord,
+        "## Kept\n**Deep heading**\n- ☐ Open\n  * ☑ Done\n```\n#### not a heading\n```"
+    );
+}
+
+#[test]
+fn test_split_message_breaks_at_lines() {
+    let text = (1..=30)
+        .map(|i| format!("line {:02}", i))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let messages = split_message(&text, 50);
+
+    assert!(messages.len() > 1);
+    assert!(messages.iter().all(|m| m.chars().count() <= 50));
+    assert!(messages.iter().all(|m| m.starts_with("line ")));
+    assert_eq!(messages.join("\n"), text);
+}
+
+#[test]
+fn test_split_message_reopens_code_fences() {
+    let code = (1..=20)
+        .map(|i| format!("x = {}", i))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let messages = split_message(&format!("```\n{}\n```", code), 60);
+
+    assert!(messages.len() > 1);
+    for message in &messages {
+        assert!(message.chars().count() <= 60);
+        assert!(message.starts_with("```"));
+        assert!(message.ends_with("```"));
+    }
+}
+
+#[test]
+fn test_split_message_cuts_long_lines() {
+    let messages = split_message(&"a".repeat(250), 100);
+    assert!(messages.iter().all(|m| m.chars().count() <= 100));
+    assert_eq!(messages.concat(), "a".repeat(250));
+}
+
+#[test]
+fn test_format_note_messages() {
+    let note = "---\ntitle: Standup\n---\nYesterday **done**\n";
+
+    let slack = format_note_messages(ChannelTarget::Slack, "standup.md", note);
+    assert_eq!(slack, vec!["*Standup*\n\nYesterday *done*".to_string()]);
+
+    let discord = format_note_messages(ChannelTarget::Discord, "standup.md", note);
+    assert_eq!(
+        discord,
+        vec!["**Standup**\n\nYesterday **done**".to_string()]
+    );
+
+    let long_note = "- item\n".repeat(600);
+    let messages = format_note_messages(ChannelTarget::Discord, "long.md", &long_note);
+    assert!(messages.len() > 1);
+    assert!(messages
+        .iter()
+        .all(|m| m.chars().count() <= DISCORD_MESSAGE_LIMIT));
+}
+
+#[test]
+fn test_webhook_payload() {
+    assert_eq!(
+        webhook_payload(ChannelTarget::Slack, "hi"),
+        serde_json::json!({ "text": "hi" })
+    );
+    let discord = webhook_payload(ChannelTarget::Discord, "hi");
+    assert_eq!(discord["content"], "hi");
+    assert_eq!(discord["allowed_mentions"]["parse"], serde_json::json!([]));
+}
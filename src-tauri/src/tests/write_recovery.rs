@@ -0,0 +1,88 @@
+//! Write Recovery Unit Tests
+//!
+//! Tests for keeping the text of saves interrupted by a crash.
+
+use crate::utilities::write_recovery::{
+    discard_recoverable_write, list_recoverable_writes, recover_temp_writes, temp_target_path,
+};
+use std::fs;
+use tempfile::TempDir;
+
+struct Dirs {
+    _root: TempDir,
+    temp: std::path::PathBuf,
+    recovery: std::path::PathBuf,
+    notes: std::path::PathBuf,
+}
+
+fn dirs() -> Dirs {
+    let root = TempDir::new().unwrap();
+    let temp = root.path().join("temp");
+    let recovery = root.path().join("recovered");
+    let notes = root.path().join("notes");
+    fs::create_dir_all(&temp).unwrap();
+    fs::create_dir_all(notes.join("work")).unwrap();
+    Dirs {
+        _root: root,
+        temp,
+        recovery,
+        notes,
+    }
+}
+
+fn orphan(dirs: &Dirs, id: &str, content: &str, target: Option<&str>) {
+    let temp_path = dirs.temp.join(format!("write_temp_{}.md", id));
+    fs::write(&temp_path, content).unwrap();
+    if let Some(target) = target {
+        fs::write(
+            temp_target_path(&temp_path),
+            dirs.notes.join(target).to_string_lossy().as_bytes(),
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn test_interrupted_saves_are_registered() {
+    let dirs = dirs();
+    fs::write(dirs.notes.join("work/plan.md"), "old text").unwrap();
+    fs::write(dirs.notes.join("done.md"), "saved text").unwrap();
+
+    orphan(
+        &dirs,
+        "1700000000000000000",
+        "new text",
+        Some("work/plan.md"),
+    );
+    orphan(&dirs, "1700000100000000000", "unknown note", None);
+    // Already on disk, or nothing to lose
+    orphan(&dirs, "1700000200000000000", "saved text", Some("done.md"));
+    orphan(&dirs, "1700000300000000000", "  \n", Some("work/plan.md"));
+
+    let recovered = recover_temp_writes(&dirs.temp, &dirs.recovery, &dirs.notes).unwrap();
+    assert_eq!(recovered, 2);
+
+    let writes = list_recoverable_writes(&dirs.recovery).unwrap();
+    assert_eq!(writes.len(), 2);
+    assert_eq!(writes[0].note_name, None);
+    assert_eq!(writes[1].note_name.as_deref(), Some("work/plan.md"));
+    assert_eq!(writes[1].content, "new text");
+    assert_eq!(writes[1].written_at, 1_700_000_000);
+
+    // Running again doesn't register the same files twice
+    assert_eq!(
+        recover_temp_writes(&dirs.temp, &dirs.recovery, &dirs.notes).unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_discard_recoverable_write() {
+    let dirs = dirs();
+    orphan(&dirs, "1700000000000000000", "new text", None);
+    recover_temp_writes(&dirs.temp, &dirs.recovery, &dirs.notes).unwrap();
+
+    discard_recoverable_write(&dirs.recovery, "1700000000000000000").unwrap();
+    assert!(list_recoverable_writes(&dirs.recovery).unwrap().is_empty());
+    assert!(discard_recoverable_write(&dirs.recovery, "1700000000000000000").is_err());
+}
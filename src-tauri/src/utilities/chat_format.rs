@@ -0,0 +1,246 @@
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+fn parser(markdown: &str) -> Parser<'_> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_TASKLISTS);
+    Parser::new_ext(markdown, options)
+}
+
+/// Slack requires these three characters escaped in message text.
+fn escape_slack(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Converts markdown to Slack mrkdwn: `*bold*`, `_italic_`, `~strike~`,
+/// `<url|text>` links, bold headings and bulleted lists.
+pub fn markdown_to_slack(markdown: &str) -> String {
+    let mut out = String::new();
+    // Open lists: next number for ordered ones, `None` for bullets
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    let mut link_depth = 0;
+    let mut quote_depth = 0;
+    let mut in_code_block = false;
+
+    for event in parser(markdown) {
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Paragraph if quote_depth > 0 => {
+                    out.push_str(&"> ".repeat(quote_depth));
+                }
+                Tag::Heading { .. } => out.push('*'),
+                Tag::BlockQuote(_) => quote_depth += 1,
+                Tag::CodeBlock(_) => {
+                    in_code_block = true;
+                    out.push_str("```\n");
+                }
+                Tag::List(start) => {
+                    if !lists.is_empty() && !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    lists.push(start);
+                }
+                Tag::Item => {
+                    out.push_str(&"    ".repeat(lists.len().saturating_sub(1)));
+                    match lists.last_mut() {
+                        Some(Some(number)) => {
+                            out.push_str(&format!("{}. ", number));
+                            *number += 1;
+                        }
+                        _ => out.push_str("• "),
+                    }
+                }
+                Tag::Emphasis => out.push('_'),
+                Tag::Strong => out.push('*'),
+                Tag::Strikethrough => out.push('~'),
+                Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                    out.push('<');
+                    out.push_str(&dest_url);
+                    out.push('|');
+                    link_depth += 1;
+                }
+                _ => {}
+            },
+            Event::End(tag) => match tag {
+                TagEnd::Paragraph => {
+                    out.push('\n');
+                    if lists.is_empty() {
+                        out.push('\n');
+                    }
+                }
+                TagEnd::Heading(_) => out.push_str("*\n\n"),
+                TagEnd::BlockQuote(_) => quote_depth -= 1,
+                TagEnd::CodeBlock => {
+                    in_code_block = false;
+                    out.push_str("```\n\n");
+                }
+                TagEnd::List(_) => {
+                    lists.pop();
+                    if lists.is_empty() {
+                        out.push('\n');
+                    }
+                }
+                TagEnd::Item if !out.ends_with('\n') => out.push('\n'),
+                TagEnd::Emphasis => out.push('_'),
+                TagEnd::Strong => out.push('*'),
+                TagEnd::Strikethrough => out.push('~'),
+                TagEnd::Link | TagEnd::Image => {
+                    link_depth -= 1;
+                    out.push('>');
+                }
+                TagEnd::TableCell => out.push_str(" | "),
+                TagEnd::TableHead | TagEnd::TableRow => {
+                    let trimmed = out.trim_end_matches(" | ").len();
+                    out.truncate(trimmed);
+                    out.push('\n');
+                }
+                TagEnd::Table => out.push('\n'),
+                _ => {}
+            },
+            Event::Text(text) => {
+                if in_code_block || link_depth == 0 {
+                    out.push_str(&escape_slack(&text));
+                } else {
+                    // A `|` would end the link label early
+                    out.push_str(&escape_slack(&text).replace('|', "¦"));
+                }
+            }
+            Event::Code(code) => {
+                out.push('`');
+                out.push_str(&escape_slack(&code));
+                out.push('`');
+            }
+            Event::Html(html) | Event::InlineHtml(html) => out.push_str(&escape_slack(&html)),
+            Event::SoftBreak | Event::HardBreak => {
+                out.push('\n');
+                if quote_depth > 0 {
+                    out.push_str(&"> ".repeat(quote_depth));
+                }
+            }
+            Event::Rule => out.push_str("———\n\n"),
+            Event::TaskListMarker(checked) => out.push_str(if checked { "☑ " } else { "☐ " }),
+            _ => {}
+        }
+    }
+
+    out.trim_end().to_string()
+}
+
+/// Discord renders most markdown itself; only headings below `###` and task
+/// checkboxes need rewriting.
+pub fn markdown_to_discord(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if in_fence {
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+        if hashes > 3 && hashes <= 6 && trimmed[hashes..].starts_with(' ') {
+            out.push_str(&format!("**{}**\n", trimmed[hashes..].trim()));
+            continue;
+        }
+
+        let indent = &line[..line.len() - trimmed.len()];
+        let converted = ["- ", "* ", "+ "].iter().find_map(|bullet| {
+            let item = trimmed.strip_prefix(bullet)?;
+            let (mark, rest) = if let Some(rest) = item.strip_prefix("[ ] ") {
+                ("☐", rest)
+            } else if let Some(rest) = item
+                .strip_prefix("[x] ")
+                .or_else(|| item.strip_prefix("[X] "))
+            {
+                ("☑", rest)
+            } else {
+                return None;
+            };
+            Some(format!("{}{}{} {}", indent, bullet, mark, rest))
+        });
+        out.push_str(converted.as_deref().unwrap_or(line));
+        out.push('\n');
+    }
+
+    out.trim_end().to_string()
+}
+
+/// A note title as a bold Slack line.
+pub fn slack_title(title: &str) -> String {
+    format!("*{}*", escape_slack(title))
+}
+
+/// A note title as a bold Discord line.
+pub fn discord_title(title: &str) -> String {
+    format!("**{}**", title.replace('*', "\\*"))
+}
+
+/// Splits `text` into messages of at most `max_chars` characters, breaking at
+/// line ends where possible. Code fences cut in two are closed and reopened.
+pub fn split_message(text: &str, max_chars: usize) -> Vec<String> {
+    const FENCE: &str = "```";
+    // Room for a closing and a reopening fence
+    let budget = max_chars.saturating_sub(2 * (FENCE.len() + 1)).max(1);
+
+    let mut messages = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+    let mut in_fence = false;
+
+    let mut flush = |current: &mut String, current_len: &mut usize, in_fence: bool| {
+        if current.is_empty() {
+            return;
+        }
+        let mut message = std::mem::take(current);
+        if in_fence {
+            message.push_str(FENCE);
+        }
+        messages.push(message.trim_end().to_string());
+        if in_fence {
+            current.push_str(FENCE);
+            current.push('\n');
+        }
+        *current_len = current.chars().count();
+    };
+
+    for line in text.lines() {
+        let mut line = line.to_string();
+        loop {
+            let line_len = line.chars().count() + 1;
+            if current_len + line_len <= budget {
+                break;
+            }
+            if current_len > 0 && line_len <= budget {
+                flush(&mut current, &mut current_len, in_fence);
+                break;
+            }
+            // A single line longer than a message: cut it
+            let room = budget.saturating_sub(current_len).max(1);
+            let cut: String = line.chars().take(room).collect();
+            line = line.chars().skip(room).collect();
+            current.push_str(&cut);
+            current.push('\n');
+            flush(&mut current, &mut current_len, in_fence);
+        }
+
+        if line.trim_start().starts_with(FENCE) {
+            in_fence = !in_fence;
+        }
+        current_len += line.chars().count() + 1;
+        current.push_str(&line);
+        current.push('\n');
+    }
+    flush(&mut current, &mut current_len, false);
+
+    messages.retain(|message| !message.trim().is_empty());
+    messages
+}
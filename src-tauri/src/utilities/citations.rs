@@ -0,0 +1,789 @@
+use crate::config::CitationsConfig;
+use crate::core::{AppError, AppResult};
+use crate::logging::log;
+use crate::utilities::links::lines_outside_code_blocks;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static CITATION_GROUP_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"\[([^\[\]\n]*@[^\[\]\n]*)\]([(\[:]?)"));
+
+static CITATION_ITEM_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"^(-?)@([A-Za-z0-9_][A-Za-z0-9_:.#$%&+?<>~/-]*)\s*(?:,\s*(.+))?$"));
+
+static BIBLIOGRAPHY_CACHE: Lazy<RwLock<BibliographyCache>> =
+    Lazy::new(|| RwLock::new(BibliographyCache::default()));
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Author {
+    pub family: String,
+    pub given: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BibEntry {
+    pub key: String,
+    pub entry_type: String,
+    pub title: Option<String>,
+    pub authors: Vec<Author>,
+    pub year: Option<String>,
+    pub container_title: Option<String>,
+    pub publisher: Option<String>,
+    pub volume: Option<String>,
+    pub issue: Option<String>,
+    pub pages: Option<String>,
+    pub doi: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Bibliography {
+    entries: HashMap<String, BibEntry>,
+}
+
+impl Bibliography {
+    pub fn get(&self, key: &str) -> Option<&BibEntry> {
+        self.entries.get(key)
+    }
+
+    fn insert(&mut self, entry: BibEntry) {
+        if !entry.key.is_empty() {
+            self.entries.insert(entry.key.clone(), entry);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CitationItem {
+    pub key: String,
+    pub locator: Option<String>,
+    pub suppress_author: bool,
+}
+
+#[derive(Default)]
+struct BibliographyCache {
+    path: Option<PathBuf>,
+    references_heading: String,
+    loaded: Option<(Option<SystemTime>, Option<Arc<Bibliography>>)>,
+}
+
+/// Points the renderer at the configured bibliography. The file is parsed lazily
+/// on first use and re-parsed whenever its modification time changes.
+pub fn configure_citations(config: &CitationsConfig, notes_dir: &Path) {
+    let path = resolve_bibliography_path(&config.bibliography_file, notes_dir);
+    let mut cache = BIBLIOGRAPHY_CACHE
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    cache.path = path;
+    cache.references_heading = config.references_heading.clone();
+    cache.loaded = None;
+}
+
+/// Identifies the configured bibliography as last modified, 0 when none is.
+/// Part of `render_version`, so renders made with another bibliography, or an
+/// older version of the file, count as stale and are redone.
+pub fn bibliography_fingerprint() -> u32 {
+    let cache = BIBLIOGRAPHY_CACHE.read().unwrap_or_else(|e| e.into_inner());
+    match &cache.path {
+        Some(path) => fingerprint_bibliography(path, &cache.references_heading),
+        None => 0,
+    }
+}
+
+/// Fingerprint of the bibliography at `path` rendered under
+/// `references_heading`, see `bibliography_fingerprint`. Never 0.
+pub fn fingerprint_bibliography(path: &Path, references_heading: &str) -> u32 {
+    let modified = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since_epoch| since_epoch.as_nanos());
+
+    let mut hasher = Sha256::new();
+    hasher.update(path.to_string_lossy().as_bytes());
+    hasher.update([0]);
+    hasher.update(references_heading.as_bytes());
+    hasher.update(modified.to_le_bytes());
+    let digest = hasher.finalize();
+    u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]]).max(1)
+}
+
+/// Returns the active bibliography and references heading, if one is configured and readable.
+pub fn active_bibliography() -> Option<(Arc<Bibliography>, String)> {
+    let (path, modified) = {
+        let cache = BIBLIOGRAPHY_CACHE.read().unwrap_or_else(|e| e.into_inner());
+        let path = cache.path.clone()?;
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+        if let Some((loaded_at, bibliography)) = &cache.loaded {
+            if *loaded_at == modified {
+                return bibliography
+                    .clone()
+                    .map(|b| (b, cache.references_heading.clone()));
+            }
+        }
+        (path, modified)
+    };
+
+    let bibliography = match load_bibliography(&path) {
+        Ok(bibliography) => Some(Arc::new(bibliography)),
+        Err(e) => {
+            log(
+                "CITATIONS",
+                &format!("Failed to load bibliography '{}'", path.display()),
+                Some(&e.to_string()),
+            );
+            None
+        }
+    };
+
+    let mut cache = BIBLIOGRAPHY_CACHE
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    cache.loaded = Some((modified, bibliography.clone()));
+    bibliography.map(|b| (b, cache.references_heading.clone()))
+}
+
+fn resolve_bibliography_path(file: &str, notes_dir: &Path) -> Option<PathBuf> {
+    let file = file.trim();
+    if file.is_empty() {
+        return None;
+    }
+    if let Some(rest) = file.strip_prefix("~/") {
+        return home::home_dir().map(|home| home.join(rest));
+    }
+    let path = PathBuf::from(file);
+    Some(if path.is_absolute() {
+        path
+    } else {
+        notes_dir.join(path)
+    })
+}
+
+pub fn load_bibliography(path: &Path) -> AppResult<Bibliography> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        AppError::FileRead(format!(
+            "Failed to read bibliography '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let is_json = path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("json"))
+        .unwrap_or(false);
+    if is_json {
+        parse_csl_json(&content)
+    } else {
+        Ok(parse_bibtex(&content))
+    }
+}
+
+/// Parses BibTeX entries, including `@string` macros. Malformed entries are skipped.
+pub fn parse_bibtex(content: &str) -> Bibliography {
+    let mut bibliography = Bibliography::default();
+    let mut macros: HashMap<String, String> = HashMap::new();
+    let bytes = content.as_bytes();
+    let mut pos = 0;
+
+    while let Some(offset) = content[pos..].find('@') {
+        let start = pos + offset + 1;
+        let type_end = content[start..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .map(|i| start + i)
+            .unwrap_or(content.len());
+        let entry_type = content[start..type_end].to_lowercase();
+
+        let open = skip_whitespace(content, type_end);
+        let close_char = match bytes.get(open) {
+            Some(b'{') => '}',
+            Some(b'(') => ')',
+            _ => {
+                pos = type_end;
+                continue;
+            }
+        };
+        let Some(close) = find_closing(content, open, close_char) else {
+            break;
+        };
+        let body = &content[open + 1..close];
+        pos = close + 1;
+
+        match entry_type.as_str() {
+            "comment" | "preamble" | "" => {}
+            "string" => {
+                for (name, value) in parse_bibtex_fields(body, &macros) {
+                    macros.insert(name, value);
+                }
+            }
+            _ => {
+                let Some((key, fields)) = body.split_once(',') else {
+                    continue;
+                };
+                let fields: HashMap<String, String> =
+                    parse_bibtex_fields(fields, &macros).into_iter().collect();
+                bibliography.insert(bibtex_entry(key.trim(), &entry_type, &fields));
+            }
+        }
+    }
+
+    bibliography
+}
+
+fn bibtex_entry(key: &str, entry_type: &str, fields: &HashMap<String, String>) -> BibEntry {
+    let field = |name: &str| {
+        fields
+            .get(name)
+            .map(|value| clean_bibtex_text(value))
+            .filter(|value| !value.is_empty())
+    };
+
+    let authors = fields
+        .get("author")
+        .or_else(|| fields.get("editor"))
+        .map(|names| parse_bibtex_authors(names))
+        .unwrap_or_default();
+
+    BibEntry {
+        key: key.to_string(),
+        entry_type: entry_type.to_string(),
+        title: field("title"),
+        authors,
+        year: field("year").or_else(|| field("date").map(|d| d.chars().take(4).collect())),
+        container_title: field("journal")
+            .or_else(|| field("journaltitle"))
+            .or_else(|| field("booktitle")),
+        publisher: field("publisher")
+            .or_else(|| field("institution"))
+            .or_else(|| field("school")),
+        volume: field("volume"),
+        issue: field("number"),
+        pages: field("pages"),
+        doi: field("doi"),
+        url: field("url"),
+    }
+}
+
+fn parse_bibtex_fields(body: &str, macros: &HashMap<String, String>) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+
+    while pos < body.len() {
+        pos = skip_separators(body, pos);
+        let Some(eq) = body[pos..].find('=').map(|i| pos + i) else {
+            break;
+        };
+        let name = body[pos..eq].trim().to_lowercase();
+        let mut value = String::new();
+        pos = eq + 1;
+
+        // Values can be concatenated with '#'
+        loop {
+            pos = skip_whitespace(body, pos);
+            match body.as_bytes().get(pos) {
+                Some(b'{') => {
+                    let Some(close) = find_closing(body, pos, '}') else {
+                        return fields;
+                    };
+                    value.push_str(&body[pos + 1..close]);
+                    pos = close + 1;
+                }
+                Some(b'"') => {
+                    let Some(close) = find_closing_quote(body, pos) else {
+                        return fields;
+                    };
+                    value.push_str(&body[pos + 1..close]);
+                    pos = close + 1;
+                }
+                Some(_) => {
+                    let end = body[pos..]
+                        .find([',', '#', '}', ')'])
+                        .map(|i| pos + i)
+                        .unwrap_or(body.len());
+                    let token = body[pos..end].trim();
+                    match macros.get(&token.to_lowercase()) {
+                        Some(expansion) => value.push_str(expansion),
+                        None => value.push_str(token),
+                    }
+                    pos = end;
+                }
+                None => break,
+            }
+
+            pos = skip_whitespace(body, pos);
+            if body.as_bytes().get(pos) == Some(&b'#') {
+                pos += 1;
+            } else {
+                break;
+            }
+        }
+
+        if !name.is_empty() {
+            fields.push((name, value));
+        }
+    }
+
+    fields
+}
+
+fn parse_bibtex_authors(names: &str) -> Vec<Author> {
+    split_top_level_and(names)
+        .into_iter()
+        .filter_map(|name| {
+            let name = name.trim();
+            if name.is_empty() {
+                return None;
+            }
+            // Fully braced names are corporate authors and must not be split
+            if name.starts_with('{') && find_closing(name, 0, '}') == Some(name.len() - 1) {
+                return Some(Author {
+                    family: clean_bibtex_text(name),
+                    given: None,
+                });
+            }
+
+            let name = clean_bibtex_text(name);
+            if let Some((family, given)) = name.split_once(',') {
+                let given = given.rsplit(',').next().unwrap_or(given).trim();
+                return Some(Author {
+                    family: family.trim().to_string(),
+                    given: Some(given.to_string()).filter(|g| !g.is_empty()),
+                });
+            }
+            match name.rsplit_once(' ') {
+                Some((given, family)) => Some(Author {
+                    family: family.to_string(),
+                    given: Some(given.trim().to_string()),
+                }),
+                None => Some(Author {
+                    family: name,
+                    given: None,
+                }),
+            }
+        })
+        .collect()
+}
+
+fn split_top_level_and(names: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let bytes = names.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && names[i..].starts_with(" and ") => {
+                parts.push(&names[start..i]);
+                i += 5;
+                start = i;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    parts.push(&names[start..]);
+    parts
+}
+
+fn clean_bibtex_text(value: &str) -> String {
+    let cleaned = value
+        .replace("\\&", "&")
+        .replace("\\%", "%")
+        .replace("---", "\u{2014}")
+        .replace("--", "\u{2013}")
+        .replace('~', " ")
+        .replace(['{', '}'], "");
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn skip_whitespace(text: &str, pos: usize) -> usize {
+    text[pos.min(text.len())..]
+        .find(|c: char| !c.is_whitespace())
+        .map(|i| pos + i)
+        .unwrap_or(text.len())
+}
+
+fn skip_separators(text: &str, pos: usize) -> usize {
+    text[pos.min(text.len())..]
+        .find(|c: char| !c.is_whitespace() && c != ',')
+        .map(|i| pos + i)
+        .unwrap_or(text.len())
+}
+
+fn find_closing(text: &str, open: usize, close_char: char) -> Option<usize> {
+    let open_char = text[open..].chars().next()?;
+    let mut depth = 0usize;
+    for (i, c) in text[open..].char_indices() {
+        if c == open_char {
+            depth += 1;
+        } else if c == close_char {
+            depth -= 1;
+            if depth == 0 {
+                return Some(open + i);
+            }
+        }
+    }
+    None
+}
+
+fn find_closing_quote(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in text[open + 1..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            '"' if depth == 0 => return Some(open + 1 + i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses a CSL JSON array as exported by Zotero and most reference managers.
+pub fn parse_csl_json(content: &str) -> AppResult<Bibliography> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| AppError::FileRead(format!("Invalid CSL JSON: {}", e)))?;
+    let items = value
+        .as_array()
+        .ok_or_else(|| AppError::FileRead("CSL JSON must be an array of items".to_string()))?;
+
+    let mut bibliography = Bibliography::default();
+    for item in items {
+        let text = |name: &str| match item.get(name) {
+            Some(serde_json::Value::String(s)) if !s.trim().is_empty() => {
+                Some(s.trim().to_string())
+            }
+            Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        };
+
+        let authors = item
+            .get("author")
+            .or_else(|| item.get("editor"))
+            .and_then(|a| a.as_array())
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| {
+                        let family = name
+                            .get("family")
+                            .or_else(|| name.get("literal"))
+                            .and_then(|f| f.as_str())?;
+                        Some(Author {
+                            family: family.to_string(),
+                            given: name
+                                .get("given")
+                                .and_then(|g| g.as_str())
+                                .map(str::to_string),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let year = item.get("issued").and_then(|issued| {
+            issued
+                .get("date-parts")
+                .and_then(|parts| parts.get(0))
+                .and_then(|first| first.get(0))
+                .map(|year| match year {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .or_else(|| {
+                    issued
+                        .get("raw")
+                        .or_else(|| issued.get("literal"))
+                        .and_then(|raw| raw.as_str())
+                        .map(|raw| raw.chars().take(4).collect())
+                })
+        });
+
+        bibliography.insert(BibEntry {
+            key: text("id").unwrap_or_default(),
+            entry_type: text("type").unwrap_or_else(|| "article".to_string()),
+            title: text("title"),
+            authors,
+            year,
+            container_title: text("container-title"),
+            publisher: text("publisher"),
+            volume: text("volume"),
+            issue: text("issue"),
+            pages: text("page"),
+            doi: text("DOI"),
+            url: text("URL"),
+        });
+    }
+
+    Ok(bibliography)
+}
+
+/// Parses the inside of a `[...]` group into citation items, or `None` if it is not a citation.
+pub fn parse_citation_group(inner: &str) -> Option<Vec<CitationItem>> {
+    let item_regex = CITATION_ITEM_REGEX.as_ref().ok()?;
+    inner
+        .split(';')
+        .map(|part| {
+            let caps = item_regex.captures(part.trim())?;
+            Some(CitationItem {
+                key: caps.get(2)?.as_str().to_string(),
+                locator: caps.get(3).map(|m| m.as_str().trim().to_string()),
+                suppress_author: caps.get(1).map(|m| !m.as_str().is_empty()).unwrap_or(false),
+            })
+        })
+        .collect()
+}
+
+/// Author-date label such as `Smith`, `Smith & Jones` or `Smith et al.`.
+pub fn author_label(entry: &BibEntry) -> String {
+    match entry.authors.as_slice() {
+        [] => entry.title.clone().unwrap_or_else(|| entry.key.clone()),
+        [only] => only.family.clone(),
+        [first, second] => format!("{} & {}", first.family, second.family),
+        [first, ..] => format!("{} et al.", first.family),
+    }
+}
+
+pub fn format_inline_citation(
+    entry: &BibEntry,
+    locator: Option<&str>,
+    suppress_author: bool,
+) -> String {
+    let year = entry.year.as_deref().unwrap_or("n.d.");
+    let mut text = if suppress_author {
+        year.to_string()
+    } else {
+        format!("{}, {}", author_label(entry), year)
+    };
+    if let Some(locator) = locator {
+        text.push_str(", ");
+        text.push_str(locator);
+    }
+    text
+}
+
+/// Formats a reference list entry in an APA-like style.
+pub fn format_reference(entry: &BibEntry) -> String {
+    build_reference(entry, false)
+}
+
+fn build_reference(entry: &BibEntry, html: bool) -> String {
+    let escape = |text: &str| {
+        if html {
+            html_escape::encode_text(text).to_string()
+        } else {
+            text.to_string()
+        }
+    };
+    let emphasize = |text: &str| {
+        if html {
+            format!("<em>{}</em>", escape(text))
+        } else {
+            text.to_string()
+        }
+    };
+
+    let mut parts: Vec<String> = Vec::new();
+
+    let authors = format_author_list(&entry.authors);
+    if !authors.is_empty() {
+        parts.push(escape(&authors));
+    }
+    parts.push(format!("({}).", entry.year.as_deref().unwrap_or("n.d.")));
+
+    let is_book = matches!(entry.entry_type.as_str(), "book" | "thesis" | "phdthesis");
+    if let Some(title) = &entry.title {
+        let title = title.trim_end_matches('.');
+        parts.push(if is_book {
+            format!("{}.", emphasize(title))
+        } else {
+            format!("{}.", escape(title))
+        });
+    }
+
+    if let Some(container) = &entry.container_title {
+        let mut container_part = emphasize(container);
+        if let Some(volume) = &entry.volume {
+            container_part.push_str(&format!(", {}", emphasize(volume)));
+            if let Some(issue) = &entry.issue {
+                container_part.push_str(&format!("({})", escape(issue)));
+            }
+        }
+        if let Some(pages) = &entry.pages {
+            container_part.push_str(&format!(", {}", escape(&pages.replace("--", "\u{2013}"))));
+        }
+        parts.push(format!("{}.", container_part));
+    }
+
+    if let Some(publisher) = &entry.publisher {
+        parts.push(format!("{}.", escape(publisher)));
+    }
+
+    let link = entry
+        .doi
+        .as_ref()
+        .map(|doi| {
+            if doi.starts_with("http") {
+                doi.clone()
+            } else {
+                format!("https://doi.org/{}", doi)
+            }
+        })
+        .or_else(|| entry.url.clone());
+    if let Some(link) = link {
+        parts.push(if html {
+            let href = html_escape::encode_double_quoted_attribute(&link);
+            format!(r#"<a href="{}">{}</a>"#, href, escape(&link))
+        } else {
+            link
+        });
+    }
+
+    parts.join(" ")
+}
+
+fn format_author_list(authors: &[Author]) -> String {
+    let names: Vec<String> = authors
+        .iter()
+        .map(|author| match &author.given {
+            Some(given) => {
+                let initials: Vec<String> = given
+                    .split([' ', '-'])
+                    .filter_map(|part| part.chars().next())
+                    .map(|c| format!("{}.", c))
+                    .collect();
+                format!("{}, {}", author.family, initials.join(" "))
+            }
+            None => author.family.clone(),
+        })
+        .collect();
+
+    match names.as_slice() {
+        [] => String::new(),
+        [only] => only.clone(),
+        [init @ .., last] => format!("{}, & {}", init.join(", "), last),
+    }
+}
+
+/// Renders the active bibliography's citations into the note, or `None` when
+/// no bibliography is configured or the note cites nothing.
+pub fn render_citations(content: &str) -> Option<String> {
+    if !content.contains("[@") && !content.contains("[-@") {
+        return None;
+    }
+    let (bibliography, heading) = active_bibliography()?;
+    apply_citations(content, &bibliography, &heading)
+}
+
+/// Replaces `[@key]` citation groups with inline author-date citations and appends
+/// a references section listing every cited entry.
+pub fn apply_citations(
+    content: &str,
+    bibliography: &Bibliography,
+    references_heading: &str,
+) -> Option<String> {
+    let group_regex = CITATION_GROUP_REGEX.as_ref().ok()?;
+    let prose_lines: HashSet<usize> = lines_outside_code_blocks(content)
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut cited: Vec<&BibEntry> = Vec::new();
+    let mut output = String::with_capacity(content.len());
+    let mut replaced_any = false;
+
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        if !prose_lines.contains(&index) || !line.contains('@') {
+            output.push_str(line);
+            continue;
+        }
+
+        let rendered = group_regex.replace_all(line, |caps: &regex::Captures| {
+            let whole = caps.get(0).map(|m| m.as_str()).unwrap_or_default();
+            let follower = caps.get(2).map(|m| m.as_str()).unwrap_or_default();
+            // Links, reference definitions and footnotes are not citations
+            if !follower.is_empty() {
+                return whole.to_string();
+            }
+            let inner = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
+            let Some(items) = parse_citation_group(inner) else {
+                return whole.to_string();
+            };
+
+            replaced_any = true;
+            let rendered_items: Vec<String> = items
+                .iter()
+                .map(|item| match bibliography.get(&item.key) {
+                    Some(entry) => {
+                        if !cited.iter().any(|c| c.key == entry.key) {
+                            cited.push(entry);
+                        }
+                        format!(
+                            r##"<a href="#ref-{}">{}</a>"##,
+                            html_escape::encode_double_quoted_attribute(&entry.key),
+                            html_escape::encode_text(&format_inline_citation(
+                                entry,
+                                item.locator.as_deref(),
+                                item.suppress_author,
+                            ))
+                        )
+                    }
+                    None => format!(
+                        r#"<span class="citation-missing">@{}?</span>"#,
+                        html_escape::encode_text(&item.key)
+                    ),
+                })
+                .collect();
+
+            let keys: Vec<&str> = items.iter().map(|item| item.key.as_str()).collect();
+            format!(
+                r#"<cite class="citation" data-cites="{}">({})</cite>"#,
+                html_escape::encode_double_quoted_attribute(&keys.join(" ")),
+                rendered_items.join("; ")
+            )
+        });
+        output.push_str(&rendered);
+    }
+
+    if !replaced_any {
+        return None;
+    }
+
+    if !cited.is_empty() {
+        cited.sort_by(|a, b| {
+            author_label(a)
+                .to_lowercase()
+                .cmp(&author_label(b).to_lowercase())
+                .then(a.year.cmp(&b.year))
+        });
+
+        if !output.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str("\n<section class=\"bibliography\" id=\"references\">\n");
+        output.push_str(&format!(
+            "<h2>{}</h2>\n<ol>\n",
+            html_escape::encode_text(references_heading)
+        ));
+        for entry in cited {
+            output.push_str(&format!(
+                "<li id=\"ref-{}\">{}</li>\n",
+                html_escape::encode_double_quoted_attribute(&entry.key),
+                build_reference(entry, true)
+            ));
+        }
+        output.push_str("</ol>\n</section>\n");
+    }
+
+    Some(output)
+}
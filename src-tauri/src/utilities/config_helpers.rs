@@ -1,17 +1,46 @@
 use crate::logging::log;
+use crate::services::folder_service::normalize_folder_name;
+use crate::services::reminder_service::parse_reminder_time;
 use crate::utilities::paths::get_default_notes_dir;
+use crate::utilities::tags::normalize_tag;
+use crate::utilities::text_encoding::LINE_ENDING_MODES;
 use crate::utilities::validation::{
-    validate_basic_shortcut_format, validate_font_size, validate_notes_directory,
-    validate_shortcut_format,
+    validate_basic_shortcut_format, validate_font_size, validate_note_name,
+    validate_notes_directory, validate_shortcut_format, validate_tag_rule, validate_vault,
 };
 use std::path::PathBuf;
 use tauri_plugin_global_shortcut::Shortcut;
 
 use crate::config::{
-    AppConfig, EditorConfig, GeneralConfig, InterfaceConfig, PreferencesConfig, ShortcutsConfig,
+    AppConfig, AutomationConfig, CaptureConfig, CitationsConfig, EditorConfig, EmailConfig,
+    GeneralConfig, GeoConfig, HabitsConfig, InterfaceConfig, LintConfig, MetadataConfig,
+    PreferencesConfig, RemindersConfig, ShortcutsConfig, SyncConfig, TagRule, TemplatesConfig,
+    VaultConfig, WebhooksConfig,
 };
 extern crate toml;
 
+/// Accepted `[email] security` values
+pub const SMTP_SECURITY_MODES: [&str; 3] = ["starttls", "tls", "none"];
+/// Accepted `[geo] geocoder` values; empty disables geocoding
+pub const GEOCODERS: [&str; 2] = ["", "nominatim"];
+/// Accepted `[lint.field_types]` values
+pub const FRONTMATTER_FIELD_TYPES: [&str; 5] = ["text", "list", "number", "date", "boolean"];
+/// Longest `[preferences] db_timeout_ms`, ten minutes
+pub const MAX_DB_TIMEOUT_MS: i64 = 600_000;
+/// Longest `[[vaults]]` name, in characters
+pub const MAX_VAULT_NAME_CHARS: usize = 64;
+
+/// Accepted `[preferences] fts_tokenizer` values
+pub const FTS_TOKENIZERS: [&str; 4] = ["unicode61", "porter", "trigram", "ascii"];
+pub const DEFAULT_FTS_TOKENIZER: &str = "unicode61";
+/// Accepted `[preferences] new_note_location` values
+pub const NEW_NOTE_LOCATIONS: [&str; 3] = ["inbox", "same_folder", "root"];
+pub const DEFAULT_NEW_NOTE_LOCATION: &str = "root";
+pub const DEFAULT_INBOX_FOLDER: &str = "Inbox";
+/// Accepted `[preferences] expired_notes` values
+pub const EXPIRED_NOTE_ACTIONS: [&str; 2] = ["flag", "archive"];
+pub const DEFAULT_EXPIRED_NOTES: &str = "flag";
+
 pub fn default_max_results() -> usize {
     100
 }
@@ -24,6 +53,10 @@ pub fn default_window_decorations() -> bool {
     true
 }
 
+pub fn default_markdown_extension() -> bool {
+    true
+}
+
 pub fn get_available_ui_themes() -> Vec<&'static str> {
     vec!["gruvbox-dark", "article", "modern-dark"]
 }
@@ -115,6 +148,19 @@ pub fn load_config_from_content(content: &str) -> AppConfig {
     let editor = extract_editor_config(&toml_value);
     let shortcuts = extract_shortcuts_config(&toml_value);
     let preferences = extract_preferences_config(&toml_value);
+    let lint = extract_lint_config(&toml_value);
+    let citations = extract_citations_config(&toml_value);
+    let sync = extract_sync_config(&toml_value);
+    let habits = extract_habits_config(&toml_value);
+    let templates = extract_templates_config(&toml_value);
+    let metadata = extract_metadata_config(&toml_value);
+    let capture = extract_capture_config(&toml_value);
+    let email = extract_email_config(&toml_value);
+    let webhooks = extract_webhooks_config(&toml_value);
+    let geo = extract_geo_config(&toml_value);
+    let automation = extract_automation_config(&toml_value);
+    let reminders = extract_reminders_config(&toml_value);
+    let vaults = extract_vaults(&toml_value);
 
     AppConfig {
         notes_directory,
@@ -124,6 +170,19 @@ pub fn load_config_from_content(content: &str) -> AppConfig {
         editor,
         shortcuts,
         preferences,
+        lint,
+        citations,
+        sync,
+        habits,
+        templates,
+        metadata,
+        capture,
+        email,
+        webhooks,
+        geo,
+        automation,
+        reminders,
+        vaults,
     }
 }
 
@@ -192,6 +251,7 @@ fn extract_interface_config(value: &toml::Value) -> InterfaceConfig {
         extract_theme_configuration(section, &mut config);
         extract_font_configuration(section, &mut config);
         extract_window_configuration(section, &mut config);
+        extract_markdown_extensions(section, &mut config);
     }
 
     config
@@ -305,6 +365,23 @@ fn extract_window_configuration(section: &toml::Value, config: &mut InterfaceCon
     }
 }
 
+fn extract_markdown_extensions(section: &toml::Value, config: &mut InterfaceConfig) {
+    for (key, flag) in [
+        ("markdown_tables", &mut config.markdown_tables),
+        ("markdown_task_lists", &mut config.markdown_task_lists),
+        ("markdown_footnotes", &mut config.markdown_footnotes),
+        ("markdown_strikethrough", &mut config.markdown_strikethrough),
+    ] {
+        if let Some(enabled) = section.get(key).and_then(|v| v.as_bool()) {
+            *flag = enabled;
+        }
+    }
+
+    if let Some(cli) = section.get("mermaid_cli").and_then(|v| v.as_str()) {
+        config.mermaid_cli = Some(cli.to_string());
+    }
+}
+
 fn extract_editor_config(value: &toml::Value) -> EditorConfig {
     let editor_section = value.get("editor");
     let mut config = EditorConfig::default();
@@ -427,7 +504,669 @@ fn extract_preferences_config(value: &toml::Value) -> PreferencesConfig {
                 );
             }
         }
+
+        if let Some(track) = section
+            .get("track_rename_history")
+            .and_then(|v| v.as_bool())
+        {
+            config.track_rename_history = track;
+        }
+
+        if let Some(days) = section
+            .get("trash_retention_days")
+            .and_then(|v| v.as_integer())
+        {
+            if (0..=3650).contains(&days) {
+                config.trash_retention_days = days as u32;
+            } else {
+                eprintln!(
+                    "Warning: Invalid trash_retention_days {}. Using default {}.",
+                    days, config.trash_retention_days
+                );
+            }
+        }
+
+        if let Some(review) = section
+            .get("review_external_changes")
+            .and_then(|v| v.as_bool())
+        {
+            config.review_external_changes = review;
+        }
+
+        if let Some(tokenizer) = section.get("fts_tokenizer").and_then(|v| v.as_str()) {
+            let tokenizer = tokenizer.trim().to_lowercase();
+            if FTS_TOKENIZERS.contains(&tokenizer.as_str()) {
+                config.fts_tokenizer = tokenizer;
+            } else {
+                log(
+                    "CONFIG_VALIDATION",
+                    &format!(
+                        "Warning: Unknown fts_tokenizer '{}'. Using default {}.",
+                        tokenizer, config.fts_tokenizer
+                    ),
+                    None,
+                );
+            }
+        }
+
+        if let Some(timeout) = section.get("db_timeout_ms").and_then(|v| v.as_integer()) {
+            if (0..=MAX_DB_TIMEOUT_MS).contains(&timeout) {
+                config.db_timeout_ms = timeout as u64;
+            } else {
+                eprintln!(
+                    "Warning: Invalid db_timeout_ms {}. Using default {}.",
+                    timeout, config.db_timeout_ms
+                );
+            }
+        }
+
+        if let Some(threshold) = section.get("slow_query_ms").and_then(|v| v.as_integer()) {
+            if threshold >= 0 {
+                config.slow_query_ms = threshold as u64;
+            } else {
+                eprintln!(
+                    "Warning: Invalid slow_query_ms {}. Using default {}.",
+                    threshold, config.slow_query_ms
+                );
+            }
+        }
+
+        if let Some(show) = section
+            .get("show_linked_mentions")
+            .and_then(|v| v.as_bool())
+        {
+            config.show_linked_mentions = show;
+        }
+
+        if let Some(location) = section.get("new_note_location").and_then(|v| v.as_str()) {
+            let location = location.trim().to_lowercase();
+            if NEW_NOTE_LOCATIONS.contains(&location.as_str()) {
+                config.new_note_location = location;
+            } else {
+                eprintln!(
+                    "Warning: Unknown new_note_location '{}'. Using default {}.",
+                    location, config.new_note_location
+                );
+            }
+        }
+
+        if let Some(folder) = section.get("inbox_folder").and_then(|v| v.as_str()) {
+            match normalize_folder_name(folder) {
+                Ok(folder) => config.inbox_folder = folder,
+                Err(e) => eprintln!(
+                    "Warning: Invalid inbox_folder '{}': {}. Using default {}.",
+                    folder, e, config.inbox_folder
+                ),
+            }
+        }
+
+        if let Some(mode) = section.get("line_endings").and_then(|v| v.as_str()) {
+            let mode = mode.trim().to_lowercase();
+            if LINE_ENDING_MODES.contains(&mode.as_str()) {
+                config.line_endings = mode;
+            } else {
+                eprintln!(
+                    "Warning: Unknown line_endings '{}'. Using default {}.",
+                    mode, config.line_endings
+                );
+            }
+        }
+
+        if let Some(hours) = section
+            .get("integrity_check_hours")
+            .and_then(|v| v.as_integer())
+        {
+            if (0..=720).contains(&hours) {
+                config.integrity_check_hours = hours as u32;
+            } else {
+                eprintln!(
+                    "Warning: Invalid integrity_check_hours {}. Using default {}.",
+                    hours, config.integrity_check_hours
+                );
+            }
+        }
+
+        if let Some(action) = section.get("expired_notes").and_then(|v| v.as_str()) {
+            let action = action.trim().to_lowercase();
+            if EXPIRED_NOTE_ACTIONS.contains(&action.as_str()) {
+                config.expired_notes = action;
+            } else {
+                eprintln!(
+                    "Warning: Unknown expired_notes '{}'. Using default {}.",
+                    action, config.expired_notes
+                );
+            }
+        }
     }
 
     config
 }
+
+fn extract_lint_config(value: &toml::Value) -> LintConfig {
+    let lint_section = value.get("lint");
+    let mut config = LintConfig::default();
+
+    if let Some(section) = lint_section {
+        if let Some(check) = section.get("check_broken_links").and_then(|v| v.as_bool()) {
+            config.check_broken_links = check;
+        }
+
+        if let Some(check) = section
+            .get("check_duplicate_headings")
+            .and_then(|v| v.as_bool())
+        {
+            config.check_duplicate_headings = check;
+        }
+
+        if let Some(check) = section
+            .get("check_trailing_whitespace")
+            .and_then(|v| v.as_bool())
+        {
+            config.check_trailing_whitespace = check;
+        }
+
+        if let Some(length) = section.get("max_line_length").and_then(|v| v.as_integer()) {
+            if (0..=10000).contains(&length) {
+                config.max_line_length = length as usize;
+            } else {
+                log(
+                    "CONFIG_VALIDATION",
+                    &format!(
+                        "Warning: Invalid max_line_length {}. Using default {}.",
+                        length, config.max_line_length
+                    ),
+                    None,
+                );
+            }
+        }
+
+        if let Some(fields) = section
+            .get("required_frontmatter_fields")
+            .and_then(|v| v.as_array())
+        {
+            config.required_frontmatter_fields = fields
+                .iter()
+                .filter_map(|f| f.as_str())
+                .map(|f| f.trim().to_string())
+                .filter(|f| !f.is_empty())
+                .collect();
+        }
+
+        if let Some(types) = section.get("field_types").and_then(|v| v.as_table()) {
+            for (field, field_type) in types {
+                let field_type = field_type
+                    .as_str()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_lowercase();
+                if FRONTMATTER_FIELD_TYPES.contains(&field_type.as_str()) {
+                    config
+                        .field_types
+                        .insert(field.trim().to_string(), field_type);
+                } else {
+                    log(
+                        "CONFIG_VALIDATION",
+                        &format!(
+                            "Warning: Invalid type '{}' for front-matter field '{}'. Ignoring it.",
+                            field_type, field
+                        ),
+                        None,
+                    );
+                }
+            }
+        }
+
+        for (key, pattern) in [
+            ("filename_pattern", &mut config.filename_pattern),
+            ("field_name_pattern", &mut config.field_name_pattern),
+        ] {
+            if let Some(value) = section.get(key).and_then(|v| v.as_str()) {
+                if regex::Regex::new(value).is_ok() {
+                    *pattern = value.to_string();
+                } else {
+                    log(
+                        "CONFIG_VALIDATION",
+                        &format!("Warning: Invalid {} '{}'. Ignoring it.", key, value),
+                        None,
+                    );
+                }
+            }
+        }
+    }
+
+    config
+}
+
+fn extract_citations_config(value: &toml::Value) -> CitationsConfig {
+    let citations_section = value.get("citations");
+    let mut config = CitationsConfig::default();
+
+    if let Some(section) = citations_section {
+        if let Some(file) = section.get("bibliography_file").and_then(|v| v.as_str()) {
+            let file = file.trim();
+            if file.is_empty() || is_supported_bibliography_file(file) {
+                config.bibliography_file = file.to_string();
+            } else {
+                log(
+                    "CONFIG_VALIDATION",
+                    &format!(
+                        "Warning: Unsupported bibliography_file '{}'. Expected a .bib or .json file.",
+                        file
+                    ),
+                    None,
+                );
+            }
+        }
+
+        if let Some(heading) = section.get("references_heading").and_then(|v| v.as_str()) {
+            if !heading.trim().is_empty() {
+                config.references_heading = heading.trim().to_string();
+            }
+        }
+    }
+
+    config
+}
+
+fn extract_sync_config(value: &toml::Value) -> SyncConfig {
+    let mut config = SyncConfig::default();
+
+    if let Some(section) = value.get("sync") {
+        if let Some(enabled) = section.get("crdt_enabled").and_then(|v| v.as_bool()) {
+            config.crdt_enabled = enabled;
+        }
+
+        if let Some(enabled) = section.get("lan_enabled").and_then(|v| v.as_bool()) {
+            config.lan_enabled = enabled;
+        }
+
+        if let Some(port) = section.get("lan_port").and_then(|v| v.as_integer()) {
+            if (1024..=65535).contains(&port) {
+                config.lan_port = port as u16;
+            } else {
+                log(
+                    "CONFIG_VALIDATION",
+                    &format!(
+                        "Warning: Invalid lan_port {}. Using default {}.",
+                        port, config.lan_port
+                    ),
+                    None,
+                );
+            }
+        }
+
+        if let Some(name) = section.get("device_name").and_then(|v| v.as_str()) {
+            config.device_name = name.trim().to_string();
+        }
+    }
+
+    config
+}
+
+fn extract_habits_config(value: &toml::Value) -> HabitsConfig {
+    let mut config = HabitsConfig::default();
+
+    if let Some(section) = value.get("habits") {
+        if let Some(habits) = section.get("habits").and_then(|v| v.as_array()) {
+            for habit in habits.iter().filter_map(|h| h.as_str()) {
+                let habit = habit.trim();
+                if habit.is_empty() {
+                    log(
+                        "CONFIG_VALIDATION",
+                        "Warning: Ignoring empty habit name.",
+                        None,
+                    );
+                } else if !config
+                    .habits
+                    .iter()
+                    .any(|existing| existing.eq_ignore_ascii_case(habit))
+                {
+                    config.habits.push(habit.to_string());
+                }
+            }
+        }
+
+        if let Some(folder) = section.get("daily_notes_folder").and_then(|v| v.as_str()) {
+            config.daily_notes_folder = folder.trim().trim_matches('/').to_string();
+        }
+    }
+
+    config
+}
+
+fn extract_templates_config(value: &toml::Value) -> TemplatesConfig {
+    let mut config = TemplatesConfig::default();
+
+    if let Some(section) = value.get("templates") {
+        if let Some(directory) = section.get("directory").and_then(|v| v.as_str()) {
+            config.directory = directory.trim().to_string();
+        }
+    }
+
+    config
+}
+
+fn extract_metadata_config(value: &toml::Value) -> MetadataConfig {
+    let mut config = MetadataConfig::default();
+
+    if let Some(section) = value.get("metadata") {
+        if let Some(fields) = section.get("fields").and_then(|v| v.as_array()) {
+            for field in fields.iter().filter_map(|f| f.as_str()) {
+                let field = field.trim().to_lowercase();
+                if !is_valid_metadata_field(&field) {
+                    log(
+                        "CONFIG_VALIDATION",
+                        &format!("Warning: Ignoring invalid metadata field '{}'.", field),
+                        None,
+                    );
+                } else if !config.fields.contains(&field) {
+                    config.fields.push(field);
+                }
+            }
+        }
+    }
+
+    config
+}
+
+fn extract_capture_config(value: &toml::Value) -> CaptureConfig {
+    let mut config = CaptureConfig::default();
+
+    if let Some(section) = value.get("capture") {
+        if let Some(shortcut) = section.get("shortcut").and_then(|v| v.as_str()) {
+            let shortcut = shortcut.trim();
+            if shortcut.is_empty() {
+                config.shortcut = String::new();
+            } else if let Err(e) = validate_shortcut_format(shortcut) {
+                log(
+                    "CONFIG_VALIDATION",
+                    &format!(
+                        "Warning: Invalid capture shortcut '{}': {}. Using default.",
+                        shortcut, e
+                    ),
+                    None,
+                );
+            } else {
+                config.shortcut = shortcut.to_string();
+            }
+        }
+
+        if let Some(note) = section.get("inbox_note").and_then(|v| v.as_str()) {
+            let note = note.trim();
+            match validate_note_name(note) {
+                Ok(()) => config.inbox_note = note.to_string(),
+                Err(e) => log(
+                    "CONFIG_VALIDATION",
+                    &format!(
+                        "Warning: Invalid inbox_note '{}': {}. Using default.",
+                        note, e
+                    ),
+                    None,
+                ),
+            }
+        }
+
+        if let Some(shortcut) = section
+            .get("clipboard_search_shortcut")
+            .and_then(|v| v.as_str())
+        {
+            let shortcut = shortcut.trim();
+            if shortcut.is_empty() {
+                config.clipboard_search_shortcut = String::new();
+            } else if let Err(e) = validate_shortcut_format(shortcut) {
+                log(
+                    "CONFIG_VALIDATION",
+                    &format!(
+                        "Warning: Invalid clipboard_search_shortcut '{}': {}. Leaving it disabled.",
+                        shortcut, e
+                    ),
+                    None,
+                );
+            } else {
+                config.clipboard_search_shortcut = shortcut.to_string();
+            }
+        }
+    }
+
+    config
+}
+
+fn extract_email_config(value: &toml::Value) -> EmailConfig {
+    let mut config = EmailConfig::default();
+
+    if let Some(section) = value.get("email") {
+        if let Some(host) = section.get("smtp_host").and_then(|v| v.as_str()) {
+            config.smtp_host = host.trim().to_string();
+        }
+
+        if let Some(port) = section.get("smtp_port").and_then(|v| v.as_integer()) {
+            if (1..=65535).contains(&port) {
+                config.smtp_port = port as u16;
+            } else {
+                log(
+                    "CONFIG_VALIDATION",
+                    &format!(
+                        "Warning: Invalid smtp_port {}. Using default {}.",
+                        port, config.smtp_port
+                    ),
+                    None,
+                );
+            }
+        }
+
+        if let Some(security) = section.get("security").and_then(|v| v.as_str()) {
+            let security = security.trim().to_lowercase();
+            if SMTP_SECURITY_MODES.contains(&security.as_str()) {
+                config.security = security;
+            } else {
+                log(
+                    "CONFIG_VALIDATION",
+                    &format!(
+                        "Warning: Invalid email security '{}'. Using default '{}'.",
+                        security, config.security
+                    ),
+                    None,
+                );
+            }
+        }
+
+        if let Some(username) = section.get("username").and_then(|v| v.as_str()) {
+            config.username = username.trim().to_string();
+        }
+
+        if let Some(from) = section.get("from_address").and_then(|v| v.as_str()) {
+            config.from_address = from.trim().to_string();
+        }
+    }
+
+    config
+}
+
+fn extract_webhooks_config(value: &toml::Value) -> WebhooksConfig {
+    let mut config = WebhooksConfig::default();
+
+    if let Some(section) = value.get("webhooks") {
+        for (key, target) in [
+            ("slack_url", &mut config.slack_url),
+            ("discord_url", &mut config.discord_url),
+        ] {
+            if let Some(url) = section.get(key).and_then(|v| v.as_str()) {
+                let url = url.trim();
+                if url.is_empty() || is_https_url(url) {
+                    *target = url.to_string();
+                } else {
+                    log(
+                        "CONFIG_VALIDATION",
+                        &format!("Warning: Ignoring {}: webhooks must use https.", key),
+                        None,
+                    );
+                }
+            }
+        }
+    }
+
+    config
+}
+
+/// Front-matter keys: letters, digits, `_` and `-`, not starting with a digit.
+pub fn is_valid_metadata_field(field: &str) -> bool {
+    field
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && field
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+pub fn is_supported_bibliography_file(file: &str) -> bool {
+    let lower = file.to_lowercase();
+    lower.ends_with(".bib") || lower.ends_with(".json")
+}
+
+/// Webhook URLs carry their own credentials, so only https is accepted.
+fn extract_geo_config(value: &toml::Value) -> GeoConfig {
+    let mut config = GeoConfig::default();
+
+    if let Some(section) = value.get("geo") {
+        if let Some(geocoder) = section.get("geocoder").and_then(|v| v.as_str()) {
+            let geocoder = geocoder.trim().to_lowercase();
+            if GEOCODERS.contains(&geocoder.as_str()) {
+                config.geocoder = geocoder;
+            } else {
+                log(
+                    "CONFIG_VALIDATION",
+                    &format!(
+                        "Warning: Unknown geocoder '{}'. Place names will not be geocoded.",
+                        geocoder
+                    ),
+                    None,
+                );
+            }
+        }
+
+        if let Some(url) = section.get("geocoder_url").and_then(|v| v.as_str()) {
+            let url = url.trim().trim_end_matches('/');
+            if url.is_empty() || is_https_url(url) {
+                config.geocoder_url = url.to_string();
+            } else {
+                log(
+                    "CONFIG_VALIDATION",
+                    "Warning: Ignoring geocoder_url: the geocoder must use https.",
+                    None,
+                );
+            }
+        }
+    }
+
+    config
+}
+
+fn extract_reminders_config(value: &toml::Value) -> RemindersConfig {
+    let mut config = RemindersConfig::default();
+
+    if let Some(section) = value.get("reminders") {
+        if let Some(enabled) = section.get("enabled").and_then(|v| v.as_bool()) {
+            config.enabled = enabled;
+        }
+
+        if let Some(time) = section.get("notify_time").and_then(|v| v.as_str()) {
+            if parse_reminder_time(time).is_some() {
+                config.notify_time = time.trim().to_string();
+            } else {
+                log(
+                    "CONFIG_VALIDATION",
+                    &format!(
+                        "Warning: Invalid reminders notify_time '{}'. Using default {}.",
+                        time, config.notify_time
+                    ),
+                    None,
+                );
+            }
+        }
+    }
+
+    config
+}
+
+fn extract_automation_config(value: &toml::Value) -> AutomationConfig {
+    let mut config = AutomationConfig::default();
+
+    let rules = value
+        .get("automation")
+        .and_then(|section| section.get("rules"))
+        .and_then(|v| v.as_array());
+    for rule in rules.into_iter().flatten() {
+        let text = |key: &str| {
+            rule.get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        };
+        let rule = TagRule {
+            tag: normalize_tag(&text("tag")),
+            move_to: text("move_to").trim_matches('/').to_string(),
+            append_template: text("append_template"),
+        };
+        match validate_tag_rule(&rule) {
+            Ok(()) => config.rules.push(rule),
+            Err(e) => log(
+                "CONFIG_VALIDATION",
+                &format!(
+                    "Warning: Ignoring automation rule for '{}': {}",
+                    rule.tag, e
+                ),
+                None,
+            ),
+        }
+    }
+
+    config
+}
+
+fn extract_vaults(value: &toml::Value) -> Vec<VaultConfig> {
+    let mut vaults: Vec<VaultConfig> = Vec::new();
+
+    let entries = value.get("vaults").and_then(|v| v.as_array());
+    for entry in entries.into_iter().flatten() {
+        let text = |key: &str| {
+            entry
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        };
+        let vault = VaultConfig {
+            name: text("name"),
+            path: text("path"),
+        };
+        let duplicate = vaults
+            .iter()
+            .any(|other| other.name.eq_ignore_ascii_case(&vault.name));
+        match validate_vault(&vault) {
+            Ok(()) if duplicate => log(
+                "CONFIG_VALIDATION",
+                &format!(
+                    "Warning: Ignoring vault '{}': another vault has that name",
+                    vault.name
+                ),
+                None,
+            ),
+            Ok(()) => vaults.push(vault),
+            Err(e) => log(
+                "CONFIG_VALIDATION",
+                &format!("Warning: Ignoring vault '{}': {}", vault.name, e),
+                None,
+            ),
+        }
+    }
+
+    vaults
+}
+
+pub fn is_https_url(url: &str) -> bool {
+    url.strip_prefix("https://")
+        .is_some_and(|rest| !rest.is_empty() && !rest.starts_with('/'))
+}
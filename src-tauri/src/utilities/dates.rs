@@ -0,0 +1,218 @@
+use crate::core::{AppError, AppResult};
+use chrono::{Datelike, Days, Local, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+const DATE_HINT: &str = "Expected a date like 2024-06-01 or \"next friday\"";
+
+/// Inclusive bounds given as `YYYY-MM-DD` or natural language; either side may be
+/// left open.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DateRange {
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+impl DateRange {
+    /// Parses both bounds relative to the local date, treating blank strings as open.
+    pub fn bounds(&self) -> AppResult<(Option<NaiveDate>, Option<NaiveDate>)> {
+        self.resolve(Local::now().date_naive())
+    }
+
+    pub fn resolve(&self, today: NaiveDate) -> AppResult<(Option<NaiveDate>, Option<NaiveDate>)> {
+        let from = parse_bound(self.from.as_deref(), "from", today)?;
+        let to = parse_bound(self.to.as_deref(), "to", today)?;
+        if let (Some(from), Some(to)) = (from, to) {
+            if from > to {
+                return Err(AppError::validation_error(
+                    "to",
+                    "End date is before the start date",
+                ));
+            }
+        }
+        Ok((from, to))
+    }
+}
+
+/// Date from a file stem such as `Daily/2024-06-01.md`.
+pub fn date_from_filename(filename: &str) -> Option<NaiveDate> {
+    let stem = crate::utilities::links::strip_note_extension(filename);
+    let stem = stem.rsplit('/').next().unwrap_or(stem);
+    NaiveDate::parse_from_str(stem, DATE_FORMAT).ok()
+}
+
+/// Like [`parse_natural_date`], but reports unrecognised text as a validation error.
+pub fn parse_date_argument(text: &str, field: &str, today: NaiveDate) -> AppResult<NaiveDate> {
+    parse_natural_date(text, today).ok_or_else(|| AppError::validation_error(field, DATE_HINT))
+}
+
+/// Resolves `2024-06-01`, `today`, `tomorrow`, `friday`, `next friday`, `last week`,
+/// `in 2 weeks`, `3 days ago`, `june 5` and similar phrases against `today`.
+///
+/// A bare or `this` weekday is the next one on or after today, `next` the first
+/// strictly after and `last` the most recent strictly before. Month names without a
+/// year fall in the current year.
+pub fn parse_natural_date(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let text = text.trim().to_lowercase();
+    for format in [DATE_FORMAT, "%Y/%m/%d"] {
+        if let Ok(date) = NaiveDate::parse_from_str(&text, format) {
+            return Some(date);
+        }
+    }
+
+    let words: Vec<&str> = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    match words.as_slice() {
+        ["today"] | ["now"] => Some(today),
+        ["tomorrow"] => today.checked_add_days(Days::new(1)),
+        ["yesterday"] => today.checked_sub_days(Days::new(1)),
+        ["day", "after", "tomorrow"] => today.checked_add_days(Days::new(2)),
+        ["day", "before", "yesterday"] => today.checked_sub_days(Days::new(2)),
+        [day] | ["this", day] if parse_weekday(day).is_some() => {
+            let weekday = parse_weekday(day)?;
+            let ahead = days_until(today.weekday(), weekday);
+            today.checked_add_days(Days::new(ahead))
+        }
+        ["next", word] => match parse_weekday(word) {
+            Some(weekday) => {
+                let ahead = match days_until(today.weekday(), weekday) {
+                    0 => 7,
+                    ahead => ahead,
+                };
+                today.checked_add_days(Days::new(ahead))
+            }
+            None => shift(today, 1, word),
+        },
+        ["last", word] => match parse_weekday(word) {
+            Some(weekday) => {
+                let back = match days_until(weekday, today.weekday()) {
+                    0 => 7,
+                    back => back,
+                };
+                today.checked_sub_days(Days::new(back))
+            }
+            None => shift(today, -1, word),
+        },
+        ["in", amount, unit] | [amount, unit, "from", "now"] => {
+            shift(today, parse_amount(amount)?, unit)
+        }
+        [amount, unit, "ago"] => shift(today, -parse_amount(amount)?, unit),
+        [first, second] => month_day(first, second, today.year()),
+        [first, second, year] => month_day(first, second, year.parse().ok()?),
+        _ => None,
+    }
+}
+
+fn parse_bound(value: Option<&str>, field: &str, today: NaiveDate) -> AppResult<Option<NaiveDate>> {
+    match value.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => parse_date_argument(value, field, today).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    let weekday = match word {
+        "monday" | "mon" => Weekday::Mon,
+        "tuesday" | "tue" | "tues" => Weekday::Tue,
+        "wednesday" | "wed" => Weekday::Wed,
+        "thursday" | "thu" | "thur" | "thurs" => Weekday::Thu,
+        "friday" | "fri" => Weekday::Fri,
+        "saturday" | "sat" => Weekday::Sat,
+        "sunday" | "sun" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+/// Days from `from` forward to the next `to`, 0 when they are the same weekday.
+fn days_until(from: Weekday, to: Weekday) -> u64 {
+    ((7 + to.num_days_from_monday() - from.num_days_from_monday()) % 7) as u64
+}
+
+fn parse_amount(word: &str) -> Option<i64> {
+    let amount = match word {
+        "a" | "an" | "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        "ten" => 10,
+        "eleven" => 11,
+        "twelve" => 12,
+        _ => word.parse().ok()?,
+    };
+    Some(amount)
+}
+
+/// Moves `today` by `amount` days, weeks, months or years; months clamp to the last day.
+fn shift(today: NaiveDate, amount: i64, unit: &str) -> Option<NaiveDate> {
+    let magnitude = amount.unsigned_abs();
+    let (days, months) = match unit.trim_end_matches('s') {
+        "day" => (magnitude, 0),
+        "week" | "wk" => (magnitude.checked_mul(7)?, 0),
+        "month" => (0, u32::try_from(magnitude).ok()?),
+        "year" | "yr" => (0, u32::try_from(magnitude.checked_mul(12)?).ok()?),
+        _ => return None,
+    };
+    if amount >= 0 {
+        today
+            .checked_add_days(Days::new(days))?
+            .checked_add_months(Months::new(months))
+    } else {
+        today
+            .checked_sub_days(Days::new(days))?
+            .checked_sub_months(Months::new(months))
+    }
+}
+
+fn parse_month(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+    let word = word.trim_end_matches('.');
+    if word.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|month| month.starts_with(word))
+        .map(|index| index as u32 + 1)
+}
+
+fn parse_day(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = &word[digits.len()..];
+    if !matches!(suffix, "" | "st" | "nd" | "rd" | "th") {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// `june 5` or `5 june`, in either order.
+fn month_day(first: &str, second: &str, year: i32) -> Option<NaiveDate> {
+    let (month, day) = match (parse_month(first), parse_month(second)) {
+        (Some(month), None) => (month, parse_day(second)?),
+        (None, Some(month)) => (month, parse_day(first)?),
+        _ => return None,
+    };
+    NaiveDate::from_ymd_opt(year, month, day)
+}
@@ -0,0 +1,886 @@
+use crate::core::{AppError, AppResult};
+use crate::logging::log;
+use crate::utilities::frontmatter::strip_frontmatter;
+use pulldown_cmark::{Alignment, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+// 6 inches at 914400 EMU per inch
+const MAX_IMAGE_WIDTH_EMU: u64 = 5_486_400;
+// 96 DPI
+const EMU_PER_PIXEL: u64 = 9_525;
+
+const BULLET_NUM_ID: usize = 1;
+
+struct DocxImage {
+    file_name: String,
+    data: Vec<u8>,
+}
+
+struct ListContext {
+    num_id: usize,
+}
+
+struct TableContext {
+    alignments: Vec<Alignment>,
+    column: usize,
+    cell_has_paragraph: bool,
+}
+
+/// Converts markdown into a `.docx` package preserving headings, lists, tables,
+/// local images and footnotes. Images are only embedded when they live inside
+/// `notes_root`; anything else falls back to its alt text.
+pub fn build_docx(
+    content: &str,
+    title: &str,
+    note_dir: Option<&Path>,
+    notes_root: Option<&Path>,
+) -> AppResult<Vec<u8>> {
+    let mut writer = DocxWriter::new(note_dir, notes_root);
+    writer.convert(strip_frontmatter(content));
+    writer.package(title)
+}
+
+struct DocxWriter<'a> {
+    note_dir: Option<&'a Path>,
+    notes_root: Option<PathBuf>,
+
+    body: String,
+    saved_body: Option<String>,
+    paragraph_open: bool,
+
+    heading: Option<usize>,
+    in_code_block: bool,
+    quote_depth: usize,
+    lists: Vec<ListContext>,
+    item_needs_number: bool,
+    table: Option<TableContext>,
+
+    bold: usize,
+    italic: usize,
+    strike: usize,
+    links: Vec<bool>,
+    image: Option<(String, String)>,
+
+    relationships: Vec<(String, &'static str, String, bool)>,
+    ordered_lists: Vec<(usize, usize, u64)>,
+    media: Vec<DocxImage>,
+    footnote_ids: HashMap<String, usize>,
+    footnotes: Vec<(usize, String)>,
+    current_footnote: Option<usize>,
+    footnote_ref_pending: bool,
+    drawing_count: usize,
+}
+
+impl<'a> DocxWriter<'a> {
+    fn new(note_dir: Option<&'a Path>, notes_root: Option<&Path>) -> Self {
+        Self {
+            note_dir,
+            notes_root: notes_root.and_then(|root| root.canonicalize().ok()),
+            body: String::new(),
+            saved_body: None,
+            paragraph_open: false,
+            heading: None,
+            in_code_block: false,
+            quote_depth: 0,
+            lists: Vec::new(),
+            item_needs_number: false,
+            table: None,
+            bold: 0,
+            italic: 0,
+            strike: 0,
+            links: Vec::new(),
+            image: None,
+            relationships: Vec::new(),
+            ordered_lists: Vec::new(),
+            media: Vec::new(),
+            footnote_ids: HashMap::new(),
+            footnotes: Vec::new(),
+            current_footnote: None,
+            footnote_ref_pending: false,
+            drawing_count: 0,
+        }
+    }
+
+    fn convert(&mut self, markdown: &str) {
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_FOOTNOTES);
+        options.insert(Options::ENABLE_TASKLISTS);
+        options.insert(Options::ENABLE_SMART_PUNCTUATION);
+
+        for event in Parser::new_ext(markdown, options) {
+            self.handle_event(event);
+        }
+        self.close_paragraph();
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start_tag(tag),
+            Event::End(tag) => self.end_tag(tag),
+            Event::Text(text) => {
+                if let Some((_, alt)) = self.image.as_mut() {
+                    alt.push_str(&text);
+                } else if self.in_code_block {
+                    self.write_code_block_text(&text);
+                } else {
+                    self.write_run(&text, false);
+                }
+            }
+            Event::Code(code) => {
+                if let Some((_, alt)) = self.image.as_mut() {
+                    alt.push_str(&code);
+                } else {
+                    self.write_run(&code, true);
+                }
+            }
+            Event::SoftBreak => self.write_run(" ", false),
+            Event::HardBreak => {
+                self.ensure_paragraph();
+                self.body.push_str("<w:r><w:br/></w:r>");
+            }
+            Event::Rule => {
+                self.close_paragraph();
+                self.body.push_str(
+                    r#"<w:p><w:pPr><w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="auto"/></w:pBdr></w:pPr></w:p>"#,
+                );
+            }
+            Event::FootnoteReference(label) => {
+                let id = self.footnote_id(&label);
+                self.ensure_paragraph();
+                let _ = write!(
+                    self.body,
+                    r#"<w:r><w:rPr><w:rStyle w:val="FootnoteReference"/></w:rPr><w:footnoteReference w:id="{}"/></w:r>"#,
+                    id
+                );
+            }
+            Event::TaskListMarker(checked) => {
+                self.write_run(if checked { "\u{2612} " } else { "\u{2610} " }, false);
+            }
+            // Raw HTML has no sensible Word equivalent
+            _ => {}
+        }
+    }
+
+    fn start_tag(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.close_paragraph(),
+            Tag::Heading { level, .. } => {
+                self.close_paragraph();
+                self.heading = Some(heading_level(level));
+            }
+            Tag::BlockQuote(_) => {
+                self.close_paragraph();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.close_paragraph();
+                self.in_code_block = true;
+            }
+            Tag::List(start) => {
+                self.close_paragraph();
+                let num_id = match start {
+                    Some(start) => {
+                        let num_id = BULLET_NUM_ID + 1 + self.ordered_lists.len();
+                        self.ordered_lists.push((num_id, self.lists.len(), start));
+                        num_id
+                    }
+                    None => BULLET_NUM_ID,
+                };
+                self.lists.push(ListContext { num_id });
+            }
+            Tag::Item => {
+                self.close_paragraph();
+                self.item_needs_number = true;
+            }
+            Tag::FootnoteDefinition(label) => {
+                self.close_paragraph();
+                let id = self.footnote_id(&label);
+                self.saved_body = Some(std::mem::take(&mut self.body));
+                self.current_footnote = Some(id);
+                self.footnote_ref_pending = true;
+            }
+            Tag::Table(alignments) => {
+                self.close_paragraph();
+                self.body.push_str(
+                    r#"<w:tbl><w:tblPr><w:tblStyle w:val="TableGrid"/><w:tblW w:w="0" w:type="auto"/></w:tblPr><w:tblGrid>"#,
+                );
+                for _ in &alignments {
+                    self.body.push_str("<w:gridCol/>");
+                }
+                self.body.push_str("</w:tblGrid>");
+                self.table = Some(TableContext {
+                    alignments,
+                    column: 0,
+                    cell_has_paragraph: false,
+                });
+            }
+            Tag::TableHead => {
+                self.body.push_str("<w:tr><w:trPr><w:tblHeader/></w:trPr>");
+                self.bold += 1;
+                self.reset_table_column();
+            }
+            Tag::TableRow => {
+                self.body.push_str("<w:tr>");
+                self.reset_table_column();
+            }
+            Tag::TableCell => {
+                self.body
+                    .push_str(r#"<w:tc><w:tcPr><w:tcW w:w="0" w:type="auto"/></w:tcPr>"#);
+                if let Some(table) = self.table.as_mut() {
+                    table.cell_has_paragraph = false;
+                }
+            }
+            Tag::Emphasis => self.italic += 1,
+            Tag::Strong => self.bold += 1,
+            Tag::Strikethrough => self.strike += 1,
+            Tag::Link { dest_url, .. } => {
+                self.ensure_paragraph();
+                if is_external_url(&dest_url) {
+                    let rel_id = self.add_relationship(
+                        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink",
+                        &dest_url,
+                        true,
+                    );
+                    let _ = write!(self.body, r#"<w:hyperlink r:id="{}">"#, rel_id);
+                    self.links.push(true);
+                } else {
+                    self.links.push(false);
+                }
+            }
+            Tag::Image { dest_url, .. } => {
+                self.image = Some((dest_url.to_string(), String::new()));
+            }
+            _ => {}
+        }
+    }
+
+    fn end_tag(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.close_paragraph(),
+            TagEnd::Heading(_) => {
+                self.close_paragraph();
+                self.heading = None;
+            }
+            TagEnd::BlockQuote(_) => {
+                self.close_paragraph();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            TagEnd::CodeBlock => {
+                self.close_paragraph();
+                self.in_code_block = false;
+            }
+            TagEnd::List(_) => {
+                self.close_paragraph();
+                self.lists.pop();
+            }
+            TagEnd::Item => {
+                self.close_paragraph();
+                self.item_needs_number = false;
+            }
+            TagEnd::FootnoteDefinition => {
+                self.close_paragraph();
+                if let (Some(id), Some(saved)) =
+                    (self.current_footnote.take(), self.saved_body.take())
+                {
+                    let footnote_body = std::mem::replace(&mut self.body, saved);
+                    self.footnotes.push((id, footnote_body));
+                }
+                self.footnote_ref_pending = false;
+            }
+            TagEnd::Table => {
+                self.body.push_str("</w:tbl>");
+                // Word merges adjacent tables without a separating paragraph
+                self.body.push_str("<w:p/>");
+                self.table = None;
+            }
+            TagEnd::TableHead => {
+                self.body.push_str("</w:tr>");
+                self.bold = self.bold.saturating_sub(1);
+            }
+            TagEnd::TableRow => self.body.push_str("</w:tr>"),
+            TagEnd::TableCell => {
+                self.close_paragraph();
+                if let Some(table) = self.table.as_mut() {
+                    if !table.cell_has_paragraph {
+                        self.body.push_str("<w:p/>");
+                    }
+                    table.column += 1;
+                }
+                self.body.push_str("</w:tc>");
+            }
+            TagEnd::Emphasis => self.italic = self.italic.saturating_sub(1),
+            TagEnd::Strong => self.bold = self.bold.saturating_sub(1),
+            TagEnd::Strikethrough => self.strike = self.strike.saturating_sub(1),
+            TagEnd::Link => {
+                let opened_hyperlink = self.links.pop().unwrap_or(false);
+                if opened_hyperlink {
+                    self.body.push_str("</w:hyperlink>");
+                }
+            }
+            TagEnd::Image => {
+                if let Some((dest, alt)) = self.image.take() {
+                    self.write_image(&dest, &alt);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn reset_table_column(&mut self) {
+        if let Some(table) = self.table.as_mut() {
+            table.column = 0;
+        }
+    }
+
+    fn ensure_paragraph(&mut self) {
+        if self.paragraph_open {
+            return;
+        }
+        self.paragraph_open = true;
+
+        let mut properties = String::new();
+        let style = if let Some(level) = self.heading {
+            Some(format!("Heading{}", level))
+        } else if self.in_code_block {
+            Some("Code".to_string())
+        } else if self.current_footnote.is_some() {
+            Some("FootnoteText".to_string())
+        } else if self.quote_depth > 0 {
+            Some("Quote".to_string())
+        } else if !self.lists.is_empty() {
+            Some("ListParagraph".to_string())
+        } else {
+            None
+        };
+        if let Some(style) = style {
+            let _ = write!(properties, r#"<w:pStyle w:val="{}"/>"#, style);
+        }
+
+        if let Some(list) = self.lists.last() {
+            if self.item_needs_number {
+                let _ = write!(
+                    properties,
+                    r#"<w:numPr><w:ilvl w:val="{}"/><w:numId w:val="{}"/></w:numPr>"#,
+                    self.lists.len() - 1,
+                    list.num_id
+                );
+                self.item_needs_number = false;
+            } else {
+                let _ = write!(
+                    properties,
+                    r#"<w:ind w:left="{}"/>"#,
+                    720 * self.lists.len()
+                );
+            }
+        }
+
+        if let Some(table) = self.table.as_mut() {
+            table.cell_has_paragraph = true;
+            let alignment = match table.alignments.get(table.column) {
+                Some(Alignment::Center) => Some("center"),
+                Some(Alignment::Right) => Some("right"),
+                _ => None,
+            };
+            if let Some(alignment) = alignment {
+                let _ = write!(properties, r#"<w:jc w:val="{}"/>"#, alignment);
+            }
+        }
+
+        self.body.push_str("<w:p>");
+        if !properties.is_empty() {
+            let _ = write!(self.body, "<w:pPr>{}</w:pPr>", properties);
+        }
+
+        if self.footnote_ref_pending && self.current_footnote.is_some() {
+            self.body.push_str(
+                r#"<w:r><w:rPr><w:rStyle w:val="FootnoteReference"/></w:rPr><w:footnoteRef/></w:r><w:r><w:t xml:space="preserve"> </w:t></w:r>"#,
+            );
+            self.footnote_ref_pending = false;
+        }
+    }
+
+    fn close_paragraph(&mut self) {
+        if self.paragraph_open {
+            self.body.push_str("</w:p>");
+            self.paragraph_open = false;
+        }
+    }
+
+    fn write_run(&mut self, text: &str, code: bool) {
+        if text.is_empty() {
+            return;
+        }
+        self.ensure_paragraph();
+
+        let mut properties = String::new();
+        if code {
+            properties.push_str(r#"<w:rStyle w:val="CodeChar"/>"#);
+        } else if self.links.last() == Some(&true) {
+            properties.push_str(r#"<w:rStyle w:val="Hyperlink"/>"#);
+        }
+        if self.bold > 0 {
+            properties.push_str("<w:b/>");
+        }
+        if self.italic > 0 {
+            properties.push_str("<w:i/>");
+        }
+        if self.strike > 0 {
+            properties.push_str("<w:strike/>");
+        }
+
+        self.body.push_str("<w:r>");
+        if !properties.is_empty() {
+            let _ = write!(self.body, "<w:rPr>{}</w:rPr>", properties);
+        }
+        let _ = write!(
+            self.body,
+            r#"<w:t xml:space="preserve">{}</w:t></w:r>"#,
+            xml_escape(text)
+        );
+    }
+
+    fn write_code_block_text(&mut self, text: &str) {
+        let mut lines = text.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            // The final newline of a code block does not start a new line
+            if lines.peek().is_none() && line.is_empty() {
+                break;
+            }
+            self.ensure_paragraph();
+            if !line.is_empty() {
+                let _ = write!(
+                    self.body,
+                    r#"<w:r><w:t xml:space="preserve">{}</w:t></w:r>"#,
+                    xml_escape(line)
+                );
+            }
+            if lines.peek().is_some() {
+                self.close_paragraph();
+            }
+        }
+    }
+
+    fn write_image(&mut self, dest: &str, alt: &str) {
+        let embedded = self
+            .resolve_local_image(dest)
+            .and_then(|path| match std::fs::read(&path) {
+                Ok(data) => Some((path, data)),
+                Err(e) => {
+                    log(
+                        "DOCX_EXPORT",
+                        &format!("Failed to read image '{}'", path.display()),
+                        Some(&e.to_string()),
+                    );
+                    None
+                }
+            })
+            .and_then(|(path, data)| {
+                let (extension, width, height) = image_info(&data)?;
+                Some((path, data, extension, width, height))
+            });
+
+        let Some((path, data, extension, width, height)) = embedded else {
+            let fallback = if alt.is_empty() { dest } else { alt };
+            self.write_run(&format!("[{}]", fallback), false);
+            return;
+        };
+
+        let mut cx = width as u64 * EMU_PER_PIXEL;
+        let mut cy = height as u64 * EMU_PER_PIXEL;
+        if cx > MAX_IMAGE_WIDTH_EMU {
+            cy = cy * MAX_IMAGE_WIDTH_EMU / cx;
+            cx = MAX_IMAGE_WIDTH_EMU;
+        }
+
+        let file_name = format!("image{}.{}", self.media.len() + 1, extension);
+        let rel_id = self.add_relationship(
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image",
+            &format!("media/{}", file_name),
+            false,
+        );
+        self.media.push(DocxImage {
+            file_name: file_name.clone(),
+            data,
+        });
+
+        self.drawing_count += 1;
+        let id = self.drawing_count;
+        let description = if alt.is_empty() {
+            path.file_name()
+                .map(|n| xml_escape(&n.to_string_lossy()))
+                .unwrap_or_default()
+        } else {
+            xml_escape(alt)
+        };
+
+        self.ensure_paragraph();
+        let _ = write!(
+            self.body,
+            concat!(
+                r#"<w:r><w:drawing><wp:inline distT="0" distB="0" distL="0" distR="0">"#,
+                r#"<wp:extent cx="{cx}" cy="{cy}"/><wp:docPr id="{id}" name="Picture {id}" descr="{descr}"/>"#,
+                r#"<a:graphic xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">"#,
+                r#"<a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture">"#,
+                r#"<pic:pic xmlns:pic="http://schemas.openxmlformats.org/drawingml/2006/picture">"#,
+                r#"<pic:nvPicPr><pic:cNvPr id="{id}" name="{name}"/><pic:cNvPicPr/></pic:nvPicPr>"#,
+                r#"<pic:blipFill><a:blip r:embed="{rel}"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill>"#,
+                r#"<pic:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="{cx}" cy="{cy}"/></a:xfrm>"#,
+                r#"<a:prstGeom prst="rect"><a:avLst/></a:prstGeom></pic:spPr></pic:pic>"#,
+                r#"</a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"#
+            ),
+            cx = cx,
+            cy = cy,
+            id = id,
+            descr = description,
+            name = file_name,
+            rel = rel_id
+        );
+    }
+
+    fn resolve_local_image(&self, dest: &str) -> Option<PathBuf> {
+        if is_external_url(dest) || dest.starts_with("data:") {
+            return None;
+        }
+        let root = self.notes_root.as_ref()?;
+        let relative = dest.replace("%20", " ");
+        let candidate = match self.note_dir {
+            Some(dir) if !relative.starts_with('/') => dir.join(&relative),
+            _ => root.join(relative.trim_start_matches('/')),
+        };
+        let canonical = candidate.canonicalize().ok()?;
+        // Never embed files from outside the notes directory
+        canonical.starts_with(root).then_some(canonical)
+    }
+
+    fn footnote_id(&mut self, label: &str) -> usize {
+        let next_id = self.footnote_ids.len() + 1;
+        *self
+            .footnote_ids
+            .entry(label.to_string())
+            .or_insert(next_id)
+    }
+
+    fn add_relationship(&mut self, rel_type: &'static str, target: &str, external: bool) -> String {
+        // rId1-rId3 are reserved for styles, numbering and footnotes
+        let id = format!("rId{}", self.relationships.len() + 4);
+        self.relationships
+            .push((id.clone(), rel_type, target.to_string(), external));
+        id
+    }
+
+    fn package(mut self, title: &str) -> AppResult<Vec<u8>> {
+        self.close_paragraph();
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        let mut files: Vec<(String, Vec<u8>)> = vec![
+            (
+                "[Content_Types].xml".to_string(),
+                content_types_xml().into_bytes(),
+            ),
+            ("_rels/.rels".to_string(), ROOT_RELS_XML.as_bytes().to_vec()),
+            (
+                "docProps/core.xml".to_string(),
+                core_xml(title).into_bytes(),
+            ),
+            (
+                "word/document.xml".to_string(),
+                self.document_xml().into_bytes(),
+            ),
+            (
+                "word/styles.xml".to_string(),
+                STYLES_XML.as_bytes().to_vec(),
+            ),
+            (
+                "word/numbering.xml".to_string(),
+                self.numbering_xml().into_bytes(),
+            ),
+            (
+                "word/footnotes.xml".to_string(),
+                self.footnotes_xml().into_bytes(),
+            ),
+            (
+                "word/_rels/document.xml.rels".to_string(),
+                self.document_rels_xml().into_bytes(),
+            ),
+        ];
+        for image in std::mem::take(&mut self.media) {
+            files.push((format!("word/media/{}", image.file_name), image.data));
+        }
+
+        for (name, data) in files {
+            zip.start_file(name.as_str(), options)
+                .map_err(|e| AppError::FileWrite(format!("Failed to build DOCX: {}", e)))?;
+            zip.write_all(&data)?;
+        }
+
+        let cursor = zip
+            .finish()
+            .map_err(|e| AppError::FileWrite(format!("Failed to finish DOCX: {}", e)))?;
+        Ok(cursor.into_inner())
+    }
+
+    fn document_xml(&self) -> String {
+        format!(
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+                r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" "#,
+                r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" "#,
+                r#"xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing">"#,
+                r#"<w:body>{}<w:sectPr><w:pgSz w:w="12240" w:h="15840"/>"#,
+                r#"<w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440" w:header="720" w:footer="720" w:gutter="0"/>"#,
+                r#"</w:sectPr></w:body></w:document>"#
+            ),
+            self.body
+        )
+    }
+
+    fn footnotes_xml(&self) -> String {
+        let mut xml = String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<w:footnotes xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" "#,
+            r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" "#,
+            r#"xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing">"#,
+            r#"<w:footnote w:type="separator" w:id="-1"><w:p><w:r><w:separator/></w:r></w:p></w:footnote>"#,
+            r#"<w:footnote w:type="continuationSeparator" w:id="0"><w:p><w:r><w:continuationSeparator/></w:r></w:p></w:footnote>"#
+        ));
+
+        // References without a definition still need a (blank) footnote to keep the document valid
+        let mut ids: Vec<usize> = self.footnote_ids.values().copied().collect();
+        ids.sort_unstable();
+        for id in ids {
+            let body = self
+                .footnotes
+                .iter()
+                .find(|(footnote_id, _)| *footnote_id == id)
+                .map(|(_, body)| body.as_str())
+                .unwrap_or("<w:p/>");
+            let _ = write!(xml, r#"<w:footnote w:id="{}">{}</w:footnote>"#, id, body);
+        }
+
+        xml.push_str("</w:footnotes>");
+        xml
+    }
+
+    fn numbering_xml(&self) -> String {
+        let mut xml = String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#
+        ));
+
+        let bullets = ["\u{2022}", "\u{25E6}", "\u{25AA}"];
+        xml.push_str(
+            r#"<w:abstractNum w:abstractNumId="0"><w:multiLevelType w:val="hybridMultilevel"/>"#,
+        );
+        for level in 0..9 {
+            let _ = write!(
+                xml,
+                r#"<w:lvl w:ilvl="{lvl}"><w:start w:val="1"/><w:numFmt w:val="bullet"/><w:lvlText w:val="{text}"/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="{left}" w:hanging="360"/></w:pPr></w:lvl>"#,
+                lvl = level,
+                text = bullets[level % bullets.len()],
+                left = 720 * (level + 1)
+            );
+        }
+        xml.push_str("</w:abstractNum>");
+
+        let formats = ["decimal", "lowerLetter", "lowerRoman"];
+        xml.push_str(
+            r#"<w:abstractNum w:abstractNumId="1"><w:multiLevelType w:val="hybridMultilevel"/>"#,
+        );
+        for level in 0..9 {
+            let _ = write!(
+                xml,
+                r#"<w:lvl w:ilvl="{lvl}"><w:start w:val="1"/><w:numFmt w:val="{fmt}"/><w:lvlText w:val="%{n}."/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="{left}" w:hanging="360"/></w:pPr></w:lvl>"#,
+                lvl = level,
+                fmt = formats[level % formats.len()],
+                n = level + 1,
+                left = 720 * (level + 1)
+            );
+        }
+        xml.push_str("</w:abstractNum>");
+
+        let _ = write!(
+            xml,
+            r#"<w:num w:numId="{}"><w:abstractNumId w:val="0"/></w:num>"#,
+            BULLET_NUM_ID
+        );
+        // Each ordered list gets its own instance so numbering restarts per list
+        for (num_id, level, start) in &self.ordered_lists {
+            let _ = write!(
+                xml,
+                r#"<w:num w:numId="{}"><w:abstractNumId w:val="1"/><w:lvlOverride w:ilvl="{}"><w:startOverride w:val="{}"/></w:lvlOverride></w:num>"#,
+                num_id, level, start
+            );
+        }
+
+        xml.push_str("</w:numbering>");
+        xml
+    }
+
+    fn document_rels_xml(&self) -> String {
+        let mut xml = String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+            r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>"#,
+            r#"<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/>"#,
+            r#"<Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/footnotes" Target="footnotes.xml"/>"#
+        ));
+        for (id, rel_type, target, external) in &self.relationships {
+            let _ = write!(
+                xml,
+                r#"<Relationship Id="{}" Type="{}" Target="{}"{}/>"#,
+                id,
+                rel_type,
+                xml_escape(target),
+                if *external {
+                    r#" TargetMode="External""#
+                } else {
+                    ""
+                }
+            );
+        }
+        xml.push_str("</Relationships>");
+        xml
+    }
+}
+
+fn heading_level(level: HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+fn is_external_url(url: &str) -> bool {
+    url.contains("://") || url.starts_with("mailto:")
+}
+
+/// Returns `(extension, width, height)` for PNG, JPEG and GIF images.
+fn image_info(data: &[u8]) -> Option<(&'static str, u32, u32)> {
+    if data.len() >= 24 && data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        let width = u32::from_be_bytes(data[16..20].try_into().ok()?);
+        let height = u32::from_be_bytes(data[20..24].try_into().ok()?);
+        return Some(("png", width, height));
+    }
+    if data.len() >= 10 && (data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a")) {
+        let width = u16::from_le_bytes([data[6], data[7]]) as u32;
+        let height = u16::from_le_bytes([data[8], data[9]]) as u32;
+        return Some(("gif", width, height));
+    }
+    if data.starts_with(&[0xFF, 0xD8]) {
+        let mut pos = 2;
+        while pos + 9 < data.len() {
+            if data[pos] != 0xFF {
+                pos += 1;
+                continue;
+            }
+            let marker = data[pos + 1];
+            let length = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+            let is_frame_header =
+                (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+            if is_frame_header {
+                let height = u16::from_be_bytes([data[pos + 5], data[pos + 6]]) as u32;
+                let width = u16::from_be_bytes([data[pos + 7], data[pos + 8]]) as u32;
+                return Some(("jpeg", width, height));
+            }
+            pos += 2 + length;
+        }
+    }
+    None
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            // Control characters are not allowed in XML 1.0
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn content_types_xml() -> String {
+    String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+        r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+        r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+        r#"<Default Extension="png" ContentType="image/png"/>"#,
+        r#"<Default Extension="jpeg" ContentType="image/jpeg"/>"#,
+        r#"<Default Extension="gif" ContentType="image/gif"/>"#,
+        r#"<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>"#,
+        r#"<Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>"#,
+        r#"<Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/>"#,
+        r#"<Override PartName="/word/footnotes.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.footnotes+xml"/>"#,
+        r#"<Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>"#,
+        r#"</Types>"#
+    ))
+}
+
+fn core_xml(title: &str) -> String {
+    format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" "#,
+            r#"xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" "#,
+            r#"xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">"#,
+            r#"<dc:title>{}</dc:title><dc:creator>Symiosis</dc:creator>"#,
+            r#"<dcterms:created xsi:type="dcterms:W3CDTF">{}</dcterms:created>"#,
+            r#"</cp:coreProperties>"#
+        ),
+        xml_escape(title),
+        chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+    )
+}
+
+const ROOT_RELS_XML: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>"#,
+    r#"<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>"#,
+    r#"</Relationships>"#
+);
+
+const STYLES_XML: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#,
+    r#"<w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:eastAsia="Calibri" w:cs="Calibri"/><w:sz w:val="22"/></w:rPr></w:rPrDefault>"#,
+    r#"<w:pPrDefault><w:pPr><w:spacing w:after="120" w:line="276" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults>"#,
+    r#"<w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="360" w:after="120"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:sz w:val="36"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="120"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:sz w:val="30"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="Heading3"><w:name w:val="heading 3"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="80"/><w:outlineLvl w:val="2"/></w:pPr><w:rPr><w:b/><w:sz w:val="26"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="Heading4"><w:name w:val="heading 4"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:outlineLvl w:val="3"/></w:pPr><w:rPr><w:b/><w:sz w:val="24"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="Heading5"><w:name w:val="heading 5"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:outlineLvl w:val="4"/></w:pPr><w:rPr><w:b/><w:i/></w:rPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="Heading6"><w:name w:val="heading 6"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:outlineLvl w:val="5"/></w:pPr><w:rPr><w:i/></w:rPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="Quote"><w:name w:val="Quote"/><w:basedOn w:val="Normal"/><w:pPr><w:ind w:left="720"/><w:pBdr><w:left w:val="single" w:sz="12" w:space="8" w:color="BFBFBF"/></w:pBdr></w:pPr><w:rPr><w:i/><w:color w:val="595959"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="Code"><w:name w:val="Code"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="0" w:line="240" w:lineRule="auto"/><w:shd w:val="clear" w:color="auto" w:fill="F2F2F2"/></w:pPr><w:rPr><w:rFonts w:ascii="Consolas" w:hAnsi="Consolas" w:cs="Consolas"/><w:sz w:val="20"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="ListParagraph"><w:name w:val="List Paragraph"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="60"/></w:pPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="FootnoteText"><w:name w:val="footnote text"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="0"/></w:pPr><w:rPr><w:sz w:val="18"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="character" w:styleId="FootnoteReference"><w:name w:val="footnote reference"/><w:rPr><w:vertAlign w:val="superscript"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="character" w:styleId="CodeChar"><w:name w:val="Code Char"/><w:rPr><w:rFonts w:ascii="Consolas" w:hAnsi="Consolas" w:cs="Consolas"/><w:shd w:val="clear" w:color="auto" w:fill="F2F2F2"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="character" w:styleId="Hyperlink"><w:name w:val="Hyperlink"/><w:rPr><w:color w:val="0563C1"/><w:u w:val="single"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="table" w:styleId="TableGrid"><w:name w:val="Table Grid"/><w:tblPr><w:tblBorders>"#,
+    r#"<w:top w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:left w:val="single" w:sz="4" w:space="0" w:color="auto"/>"#,
+    r#"<w:bottom w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:right w:val="single" w:sz="4" w:space="0" w:color="auto"/>"#,
+    r#"<w:insideH w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="auto"/>"#,
+    r#"</w:tblBorders><w:tblCellMar><w:left w:w="108" w:type="dxa"/><w:right w:w="108" w:type="dxa"/></w:tblCellMar></w:tblPr></w:style>"#,
+    r#"</w:styles>"#
+);
@@ -0,0 +1,36 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlannedAction {
+    Create,
+    /// An existing note is replaced, after a backup
+    Overwrite,
+    Delete,
+    /// Only the database row is removed; the file is already gone
+    RemoveFromIndex,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedChange {
+    pub action: PlannedAction,
+    pub note_name: String,
+}
+
+/// What a destructive command would do, returned instead of doing it when the
+/// command is called with `dry_run`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DryRunReport {
+    pub changes: Vec<PlannedChange>,
+    /// Backup files that would be written, as absolute paths
+    pub backups: Vec<String>,
+}
+
+impl DryRunReport {
+    pub fn plan(&mut self, action: PlannedAction, note_name: &str) {
+        self.changes.push(PlannedChange {
+            action,
+            note_name: note_name.to_string(),
+        });
+    }
+}
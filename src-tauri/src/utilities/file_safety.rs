@@ -2,11 +2,16 @@ use crate::{
     config::get_config_notes_dir,
     core::{AppError, AppResult},
     logging::log,
-    utilities::paths::{get_backup_dir_for_notes_path, get_temp_dir},
+    utilities::{
+        paths::{get_backup_dir_for_notes_path, get_recovery_dir, get_temp_dir},
+        text_encoding::encode_note_text,
+        version_labels::load_version_labels,
+        write_recovery::{recover_temp_writes, temp_target_path, WRITE_TEMP_PREFIX},
+    },
 };
 use std::{
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -18,8 +23,11 @@ pub enum BackupType {
     Rollback,       // For safe_write_note rollback protection
     SaveFailure,    // For failed save operations
     Rename,         // For rename operation safety
-    Delete,         // For delete operation recovery
     ExternalChange, // For watcher-detected external modifications
+    Replace,        // For project-wide find and replace
+    TagRename,      // For workspace-wide tag rename and merge
+    LinkUpdate,     // For wikilinks rewritten after a rename
+    Restore,        // For notes overwritten by a vault archive restore
 }
 
 impl BackupType {
@@ -28,17 +36,17 @@ impl BackupType {
             BackupType::Rollback => "rollback",
             BackupType::SaveFailure => "save_failure",
             BackupType::Rename => "rename_backup",
-            BackupType::Delete => "delete_backup",
             BackupType::ExternalChange => "external_change",
+            BackupType::Replace => "replace_backup",
+            BackupType::TagRename => "tag_backup",
+            BackupType::LinkUpdate => "link_backup",
+            BackupType::Restore => "restore_backup",
         }
     }
 }
 
-pub fn create_versioned_backup(
-    note_path: &PathBuf,
-    backup_type: BackupType,
-    content_override: Option<&str>,
-) -> AppResult<PathBuf> {
+/// Where a backup of `note_path` taken now would be written, without writing it.
+pub fn versioned_backup_path(note_path: &Path, backup_type: &BackupType) -> AppResult<PathBuf> {
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
@@ -49,12 +57,12 @@ pub fn create_versioned_backup(
         .and_then(|s| s.to_str())
         .ok_or_else(|| AppError::InvalidPath("Invalid filename".to_string()))?;
 
-    let backup_filename = generate_backup_filename(note_filename, &backup_type, timestamp);
+    let backup_filename = generate_backup_filename(note_filename, backup_type, timestamp);
 
-    let backup_path = match backup_type {
+    Ok(match backup_type {
         BackupType::Rollback => {
             // For rollback backups, use the existing path structure
-            let mut path = safe_backup_path(note_path)?;
+            let mut path = safe_backup_path(&note_path.to_path_buf())?;
             path.set_file_name(backup_filename);
             path
         }
@@ -63,7 +71,15 @@ pub fn create_versioned_backup(
             let backup_dir = get_backup_dir_for_notes_path(&get_config_notes_dir())?;
             backup_dir.join(backup_filename)
         }
-    };
+    })
+}
+
+pub fn create_versioned_backup(
+    note_path: &PathBuf,
+    backup_type: BackupType,
+    content_override: Option<&str>,
+) -> AppResult<PathBuf> {
+    let backup_path = versioned_backup_path(note_path, &backup_type)?;
 
     if let Some(backup_parent) = backup_path.parent() {
         fs::create_dir_all(backup_parent)?;
@@ -100,9 +116,10 @@ pub fn create_versioned_backup(
 }
 
 pub fn safe_write_note(note_path: &PathBuf, content: &str) -> AppResult<()> {
+    let content = &*encode_note_text(content, note_path);
     let rollback_backup_path = create_rollback_backup_if_exists(note_path)?;
 
-    let temp_path = match create_temp_file_with_content(content) {
+    let temp_path = match create_temp_file_with_content(note_path, content) {
         Ok(path) => path,
         Err(e) => {
             create_save_failure_backup(note_path, content);
@@ -110,16 +127,37 @@ pub fn safe_write_note(note_path: &PathBuf, content: &str) -> AppResult<()> {
         }
     };
 
-    perform_atomic_write_with_rollback(
+    let write_result = perform_atomic_write_with_rollback(
         note_path,
         &temp_path,
         content,
         rollback_backup_path.as_ref(),
-    )?;
+    );
+    cleanup_temp_file(&temp_target_path(&temp_path));
+    write_result?;
     verify_written_content(note_path, content)?;
     Ok(())
 }
 
+/// Atomically replaces a small app-data file (dictionaries, snippets) by writing
+/// a sibling temp file and renaming it over the target.
+pub fn write_app_data_file(path: &Path, content: impl AsRef<[u8]>) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    fs::write(&temp_path, content)
+        .map_err(|e| AppError::FileWrite(format!("Failed to write temp file: {}", e)))?;
+    fs::rename(&temp_path, path).map_err(|e| {
+        cleanup_temp_file(&temp_path);
+        AppError::FileWrite(format!("Failed to save '{}': {}", path.display(), e))
+    })
+}
+
 pub fn safe_backup_path(note_path: &PathBuf) -> AppResult<PathBuf> {
     let notes_dir = get_config_notes_dir();
     let backup_dir = get_backup_dir_for_notes_path(&notes_dir)?;
@@ -136,15 +174,35 @@ pub fn safe_backup_path(note_path: &PathBuf) -> AppResult<PathBuf> {
     Ok(backup_dir.join(relative_path))
 }
 
+/// Removes temp files left by saves a crash interrupted, after registering
+/// their text as recoverable writes. When that fails they are kept for the
+/// next start.
 pub fn cleanup_temp_files() -> AppResult<()> {
     let temp_dir = get_temp_dir()?;
     if temp_dir.exists() {
+        match recover_temp_writes(&temp_dir, &get_recovery_dir()?, &get_config_notes_dir()) {
+            Ok(0) => {}
+            Ok(recovered) => log(
+                "TEMP_CLEANUP",
+                &format!("Recovered {} interrupted saves", recovered),
+                None,
+            ),
+            Err(e) => {
+                log(
+                    "TEMP_CLEANUP",
+                    "Failed to recover interrupted saves, keeping temp files",
+                    Some(&e.to_string()),
+                );
+                return Ok(());
+            }
+        }
+
         if let Ok(entries) = fs::read_dir(&temp_dir) {
             for entry in entries.flatten() {
                 if entry
                     .file_name()
                     .to_string_lossy()
-                    .starts_with("write_temp_")
+                    .starts_with(WRITE_TEMP_PREFIX)
                 {
                     if let Err(e) = fs::remove_file(entry.path()) {
                         log(
@@ -181,13 +239,28 @@ fn prune_old_backups(latest_backup: &PathBuf, max_backups: usize) -> AppResult<(
     let suffix = parts[1];
     let pattern_prefix = format!("{}.{}", base_name, suffix);
 
+    // Labeled backups are kept for good and don't count towards the limit
+    let labels = match load_version_labels(parent) {
+        Ok(labels) => labels,
+        Err(e) => {
+            log(
+                "BACKUP_CLEANUP",
+                "Skipping backup pruning, version labels are unreadable",
+                Some(&e.to_string()),
+            );
+            return Ok(());
+        }
+    };
+
     let mut backups: Vec<_> = fs::read_dir(parent)?
         .filter_map(|entry| entry.ok())
         .filter(|entry| {
             entry
                 .file_name()
                 .to_str()
-                .map(|f| f.starts_with(&pattern_prefix) && f.ends_with(".md"))
+                .map(|f| {
+                    f.starts_with(&pattern_prefix) && f.ends_with(".md") && !labels.contains_key(f)
+                })
                 .unwrap_or(false)
         })
         .collect();
@@ -235,7 +308,7 @@ fn create_rollback_backup_if_exists(note_path: &PathBuf) -> AppResult<Option<Pat
     }
 }
 
-fn create_temp_file_with_content(content: &str) -> AppResult<PathBuf> {
+fn create_temp_file_with_content(note_path: &Path, content: &str) -> AppResult<PathBuf> {
     let temp_dir = get_temp_dir()?;
     fs::create_dir_all(&temp_dir)?;
 
@@ -243,7 +316,19 @@ fn create_temp_file_with_content(content: &str) -> AppResult<PathBuf> {
         .duration_since(UNIX_EPOCH)
         .map(|d| d.as_nanos())
         .unwrap_or(0);
-    let temp_path = temp_dir.join(format!("write_temp_{}.md", timestamp));
+    let temp_path = temp_dir.join(format!("{}{}.md", WRITE_TEMP_PREFIX, timestamp));
+
+    // Only used to name the note when recovering after a crash
+    if let Err(e) = fs::write(
+        temp_target_path(&temp_path),
+        note_path.to_string_lossy().as_bytes(),
+    ) {
+        log(
+            "TEMP_WRITE",
+            &format!("Failed to record temp file target: {:?}", temp_path),
+            Some(&e.to_string()),
+        );
+    }
 
     fs::write(&temp_path, content)
         .map_err(|e| AppError::FileWrite(format!("Failed to write temp file: {}", e)))?;
@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FrontmatterValue {
+    Text(String),
+    List(Vec<String>),
+}
+
+impl FrontmatterValue {
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            FrontmatterValue::Text(text) => Some(text),
+            FrontmatterValue::List(_) => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Vec<String> {
+        match self {
+            FrontmatterValue::Text(text) if text.is_empty() => Vec::new(),
+            FrontmatterValue::Text(text) => vec![text.clone()],
+            FrontmatterValue::List(items) => items.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Frontmatter {
+    pub fields: Vec<(String, FrontmatterValue)>,
+    /// Number of lines occupied by the block, including both `---` fences
+    pub line_count: usize,
+}
+
+impl Frontmatter {
+    pub fn get(&self, key: &str) -> Option<&FrontmatterValue> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value)
+    }
+
+    pub fn get_text(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|value| value.as_text())
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Whether a field is set to `true` or `yes`.
+    pub fn get_flag(&self, key: &str) -> bool {
+        self.get_text(key).is_some_and(|value| {
+            value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("yes")
+        })
+    }
+}
+
+/// Splits a note into its raw front-matter block (without fences) and the body that follows.
+pub fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if trimmed == "---" || trimmed == "..." {
+            let block = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            return Some((block, body));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Title from the `title` front-matter field, falling back to the file stem.
+pub fn note_title(filename: &str, content: &str) -> String {
+    parse_frontmatter(content)
+        .and_then(|fm| fm.get_text("title").map(str::to_string))
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or_else(|| {
+            let stem = crate::utilities::links::strip_note_extension(filename);
+            stem.rsplit('/').next().unwrap_or(stem).to_string()
+        })
+}
+
+pub fn strip_frontmatter(content: &str) -> &str {
+    split_frontmatter(content)
+        .map(|(_, body)| body)
+        .unwrap_or(content)
+}
+
+pub fn parse_frontmatter(content: &str) -> Option<Frontmatter> {
+    let (block, _) = split_frontmatter(content)?;
+    let mut fields: Vec<(String, FrontmatterValue)> = Vec::new();
+
+    for line in block.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            if line.starts_with(' ') || line.starts_with('\t') || line.starts_with('-') {
+                if let Some((_, value)) = fields.last_mut() {
+                    push_list_item(value, item);
+                }
+            }
+            continue;
+        }
+
+        if line.starts_with(' ') || line.starts_with('\t') {
+            // Nested mappings are not supported
+            continue;
+        }
+
+        if let Some((key, raw_value)) = trimmed.split_once(':') {
+            let key = key.trim();
+            if key.is_empty() {
+                continue;
+            }
+            fields.push((key.to_string(), parse_value(raw_value.trim())));
+        }
+    }
+
+    Some(Frontmatter {
+        fields,
+        line_count: block.lines().count() + 2,
+    })
+}
+
+/// Serializes fields back into a fenced block, including the trailing newline.
+pub fn serialize_frontmatter(fields: &[(String, FrontmatterValue)]) -> String {
+    let mut output = String::from("---\n");
+    for (key, value) in fields {
+        match value {
+            FrontmatterValue::Text(text) => {
+                output.push_str(&format!("{}: {}\n", key, quote_if_needed(text)));
+            }
+            FrontmatterValue::List(items) => {
+                let rendered: Vec<String> = items.iter().map(|i| quote_if_needed(i)).collect();
+                output.push_str(&format!("{}: [{}]\n", key, rendered.join(", ")));
+            }
+        }
+    }
+    output.push_str("---\n");
+    output
+}
+
+/// Sets or, with `None`, removes one top-level field, leaving the other lines
+/// of the block (comments, ordering, formatting) as they are. The field is
+/// added at the end of the block, or in a new block when the note has none.
+pub fn set_frontmatter_field(content: &str, key: &str, value: Option<&FrontmatterValue>) -> String {
+    let new_line = value.map(|value| {
+        serialize_frontmatter(&[(key.to_string(), value.clone())])
+            .trim_start_matches("---\n")
+            .trim_end_matches("---\n")
+            .to_string()
+    });
+    let Some((block, body)) = split_frontmatter(content) else {
+        return match value {
+            Some(value) => format!(
+                "{}{}",
+                serialize_frontmatter(&[(key.to_string(), value.clone())]),
+                content
+            ),
+            None => content.to_string(),
+        };
+    };
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut replaced = false;
+    let mut in_field = false;
+    for line in block.split_inclusive('\n') {
+        let is_continuation = line.starts_with([' ', '\t']) || line.trim_start().starts_with("- ");
+        if in_field && is_continuation {
+            continue;
+        }
+        in_field = !is_continuation
+            && line
+                .split_once(':')
+                .is_some_and(|(field, _)| field.trim().eq_ignore_ascii_case(key));
+        if in_field {
+            if let Some(new_line) = new_line.as_ref().filter(|_| !replaced) {
+                lines.push(new_line.clone());
+            }
+            replaced = true;
+            continue;
+        }
+        let mut line = line.to_string();
+        if !line.ends_with('\n') {
+            line.push('\n');
+        }
+        lines.push(line);
+    }
+    if !replaced {
+        match new_line {
+            Some(new_line) => lines.push(new_line),
+            None => return content.to_string(),
+        }
+    }
+
+    if lines.iter().all(|line| line.trim().is_empty()) {
+        return body.to_string();
+    }
+    format!("---\n{}---\n{}", lines.concat(), body)
+}
+
+fn push_list_item(value: &mut FrontmatterValue, item: &str) {
+    let item = unquote(item.trim());
+    match value {
+        FrontmatterValue::List(items) => items.push(item),
+        FrontmatterValue::Text(text) if text.is_empty() => {
+            *value = FrontmatterValue::List(vec![item]);
+        }
+        FrontmatterValue::Text(_) => {}
+    }
+}
+
+fn parse_value(raw: &str) -> FrontmatterValue {
+    if let Some(inner) = raw.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+        let items = inner
+            .split(',')
+            .map(|item| unquote(item.trim()))
+            .filter(|item| !item.is_empty())
+            .collect();
+        return FrontmatterValue::List(items);
+    }
+
+    FrontmatterValue::Text(unquote(strip_inline_comment(raw)))
+}
+
+fn strip_inline_comment(raw: &str) -> &str {
+    if raw.starts_with('"') || raw.starts_with('\'') {
+        return raw;
+    }
+    match raw.find(" #") {
+        Some(index) => raw[..index].trim_end(),
+        None => raw,
+    }
+}
+
+fn unquote(value: &str) -> String {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        value[1..value.len() - 1].replace("\\\"", "\"")
+    } else if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        value[1..value.len() - 1].replace("''", "'")
+    } else {
+        value.to_string()
+    }
+}
+
+fn quote_if_needed(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value.contains(':')
+        || value.contains('#')
+        || value.contains(',')
+        || value.starts_with(['[', '{', '"', '\'', '-', ' '])
+        || value.ends_with(' ');
+
+    if needs_quotes {
+        format!("\"{}\"", value.replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
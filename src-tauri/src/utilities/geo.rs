@@ -0,0 +1,85 @@
+use crate::utilities::frontmatter::{parse_frontmatter, FrontmatterValue};
+
+/// Mean Earth radius used for great-circle distances
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinates {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl Coordinates {
+    /// `None` unless both values are finite and within range.
+    pub fn new(lat: f64, lon: f64) -> Option<Self> {
+        ((-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon))
+            .then_some(Self { lat, lon })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NoteLocation {
+    Coordinates(Coordinates),
+    /// A place name, resolved to coordinates only when a geocoder is set up
+    Place(String),
+}
+
+/// Parses `48.8566, 2.3522` style coordinates.
+pub fn parse_coordinates(text: &str) -> Option<Coordinates> {
+    let (lat, lon) = text.split_once(',')?;
+    Coordinates::new(lat.trim().parse().ok()?, lon.trim().parse().ok()?)
+}
+
+/// The `location:` front matter of a note. Accepts `lat, lon`, `[lat, lon]`,
+/// a place name, or a list of places and coordinate pairs.
+pub fn extract_locations(content: &str) -> Vec<NoteLocation> {
+    let Some(value) = parse_frontmatter(content).and_then(|fm| fm.get("location").cloned()) else {
+        return Vec::new();
+    };
+
+    let items = match value {
+        FrontmatterValue::Text(text) => vec![text],
+        FrontmatterValue::List(items) => {
+            if let [lat, lon] = items.as_slice() {
+                if let Some(coordinates) = parse_coordinates(&format!("{},{}", lat, lon)) {
+                    return vec![NoteLocation::Coordinates(coordinates)];
+                }
+            }
+            items
+        }
+    };
+
+    let mut locations: Vec<NoteLocation> = Vec::new();
+    for item in items {
+        let item = item.trim();
+        if item.is_empty() {
+            continue;
+        }
+        let location = match parse_coordinates(item) {
+            Some(coordinates) => NoteLocation::Coordinates(coordinates),
+            None => NoteLocation::Place(item.to_string()),
+        };
+        if !locations.contains(&location) {
+            locations.push(location);
+        }
+    }
+    locations
+}
+
+/// Lookup key for a place name: trimmed, lowercased, single-spaced.
+pub fn normalize_place(place: &str) -> String {
+    place
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Great-circle distance in kilometres (haversine formula).
+pub fn distance_km(a: Coordinates, b: Coordinates) -> f64 {
+    let (lat1, lat2) = (a.lat.to_radians(), b.lat.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = (b.lon - a.lon).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * h.sqrt().min(1.0).asin()
+}
@@ -0,0 +1,181 @@
+use crate::utilities::{
+    frontmatter::{note_title, parse_frontmatter, serialize_frontmatter, FrontmatterValue},
+    links::{lines_outside_code_blocks, strip_note_extension},
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+pub const HIGHLIGHTS_FOLDER: &str = "Highlights";
+pub const HIGHLIGHTS_TAG: &str = "highlights";
+/// Front-matter key recording which note a highlights note was built from
+pub const HIGHLIGHTS_ORIGIN_KEY: &str = "highlights_of";
+
+static MARK_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"==([^=\n](?:[^\n]*?[^=\n])?)=="));
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HighlightKind {
+    Mark,
+    Quote,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Highlight {
+    pub kind: HighlightKind,
+    pub text: String,
+    /// 1-based line where the passage starts
+    pub line: usize,
+}
+
+/// Collects `==marked==` spans and blockquoted passages outside code blocks.
+/// Consecutive quote lines form a single passage; repeated passages are kept once.
+pub fn find_highlights(content: &str) -> Vec<Highlight> {
+    let body_start = parse_frontmatter(content)
+        .map(|fm| fm.line_count)
+        .unwrap_or(0);
+    let mut highlights: Vec<Highlight> = Vec::new();
+    let mut quote: Option<(usize, Vec<String>)> = None;
+    let mut previous_index = None;
+
+    let push = |highlights: &mut Vec<Highlight>, highlight: Highlight| {
+        if !highlight.text.is_empty() && !highlights.iter().any(|h| h.text == highlight.text) {
+            highlights.push(highlight);
+        }
+    };
+    let flush_quote = |quote: &mut Option<(usize, Vec<String>)>| {
+        quote.take().map(|(line, lines)| Highlight {
+            kind: HighlightKind::Quote,
+            text: lines.join("\n").trim().to_string(),
+            line,
+        })
+    };
+
+    for (index, line) in lines_outside_code_blocks(content) {
+        if index < body_start {
+            continue;
+        }
+        // A skipped code block also ends the current quote
+        if previous_index.is_some_and(|previous| previous + 1 != index) {
+            if let Some(highlight) = flush_quote(&mut quote) {
+                push(&mut highlights, highlight);
+            }
+        }
+        previous_index = Some(index);
+
+        match line.trim_start().strip_prefix('>') {
+            Some(quoted) => {
+                let quoted = quoted.strip_prefix(' ').unwrap_or(quoted).trim_end();
+                quote
+                    .get_or_insert_with(|| (index + 1, Vec::new()))
+                    .1
+                    .push(quoted.to_string());
+            }
+            None => {
+                if let Some(highlight) = flush_quote(&mut quote) {
+                    push(&mut highlights, highlight);
+                }
+                if let Ok(regex) = MARK_REGEX.as_ref() {
+                    for caps in regex.captures_iter(line) {
+                        if let Some(mark) = caps.get(1) {
+                            push(
+                                &mut highlights,
+                                Highlight {
+                                    kind: HighlightKind::Mark,
+                                    text: mark.as_str().trim().to_string(),
+                                    line: index + 1,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if let Some(highlight) = flush_quote(&mut quote) {
+        push(&mut highlights, highlight);
+    }
+
+    highlights
+}
+
+/// Name of the consolidated highlights note for an origin note. `existing` returns
+/// the content of a candidate note if it exists; a note built from another origin is
+/// never reused.
+pub fn highlights_note_name(
+    origin_note: &str,
+    existing: impl Fn(&str) -> Option<String>,
+) -> String {
+    let stem = strip_note_extension(origin_note);
+    let stem = stem.rsplit('/').next().unwrap_or(stem);
+
+    (1..)
+        .map(|n| {
+            if n == 1 {
+                format!("{}/{}.md", HIGHLIGHTS_FOLDER, stem)
+            } else {
+                format!("{}/{} {}.md", HIGHLIGHTS_FOLDER, stem, n)
+            }
+        })
+        .find(|candidate| match existing(candidate) {
+            Some(content) => highlights_origin(&content).as_deref() == Some(origin_note),
+            None => true,
+        })
+        .unwrap_or_else(|| format!("{}/{}.md", HIGHLIGHTS_FOLDER, stem))
+}
+
+/// The note a highlights note was built from, if `content` is one.
+pub fn highlights_origin(content: &str) -> Option<String> {
+    parse_frontmatter(content).and_then(|fm| fm.get_text(HIGHLIGHTS_ORIGIN_KEY).map(str::to_string))
+}
+
+/// Renders the highlights note, linking back to the origin and its web source when known.
+pub fn build_highlights_note(
+    origin_note: &str,
+    origin_content: &str,
+    highlights: &[Highlight],
+) -> String {
+    let title = note_title(origin_note, origin_content);
+    let source = parse_frontmatter(origin_content)
+        .and_then(|fm| fm.get_text("source").map(str::to_string))
+        .filter(|source| !source.is_empty());
+
+    let mut fields = vec![
+        (
+            "title".to_string(),
+            FrontmatterValue::Text(format!("Highlights: {}", title)),
+        ),
+        (
+            HIGHLIGHTS_ORIGIN_KEY.to_string(),
+            FrontmatterValue::Text(origin_note.to_string()),
+        ),
+    ];
+    if let Some(source) = &source {
+        fields.push(("source".to_string(), FrontmatterValue::Text(source.clone())));
+    }
+    fields.push((
+        "tags".to_string(),
+        FrontmatterValue::List(vec![HIGHLIGHTS_TAG.to_string()]),
+    ));
+
+    let mut output = serialize_frontmatter(&fields);
+    output.push_str(&format!("\n# Highlights: {}\n\n", title));
+    output.push_str(&format!("From [[{}]]", strip_note_extension(origin_note)));
+    if let Some(source) = &source {
+        output.push_str(&format!(" · <{}>", source));
+    }
+    output.push_str("\n\n");
+
+    for highlight in highlights {
+        for line in highlight.text.lines() {
+            if line.is_empty() {
+                output.push_str(">\n");
+            } else {
+                output.push_str(&format!("> {}\n", line));
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
@@ -0,0 +1,169 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+/// Tags the Markdown renderer emits plus inline HTML that can't run code. Raw
+/// HTML in notes passes through the renderer, so anything else is reported.
+pub const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "dd",
+    "del",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "section",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// Attributes holding URLs, checked for script schemes
+const URL_ATTRIBUTES: &[&str] = &["href", "src", "action", "formaction", "xlink:href"];
+
+static TAG_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"<\s*(/?)\s*([a-zA-Z][a-zA-Z0-9-]*)([^>]*)>"));
+static ATTRIBUTE_REGEX: Lazy<Result<Regex, regex::Error>> = Lazy::new(|| {
+    Regex::new(r#"([a-zA-Z_:][-a-zA-Z0-9_:.]*)\s*(?:=\s*("[^"]*"|'[^']*'|[^\s"'>]+))?"#)
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HtmlIssueKind {
+    /// A tag outside [`ALLOWED_TAGS`], such as `script` or `iframe`
+    DisallowedTag,
+    /// An `on*` attribute such as `onclick`
+    EventHandler,
+    /// A `javascript:`, `vbscript:` or `data:text/html` URL
+    ScriptUrl,
+    /// An `<input>` other than a task-list checkbox
+    FormControl,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HtmlIssue {
+    pub kind: HtmlIssueKind,
+    /// The offending tag or attribute name
+    pub detail: String,
+    pub count: usize,
+}
+
+/// Scans rendered HTML for content the allowlist doesn't permit, one entry per
+/// distinct kind and tag/attribute.
+pub fn audit_html(html: &str) -> Vec<HtmlIssue> {
+    let (Ok(tag_regex), Ok(attribute_regex)) = (TAG_REGEX.as_ref(), ATTRIBUTE_REGEX.as_ref())
+    else {
+        return Vec::new();
+    };
+
+    let mut issues: Vec<HtmlIssue> = Vec::new();
+    let mut report = |kind: HtmlIssueKind, detail: String| match issues
+        .iter_mut()
+        .find(|issue| issue.kind == kind && issue.detail == detail)
+    {
+        Some(issue) => issue.count += 1,
+        None => issues.push(HtmlIssue {
+            kind,
+            detail,
+            count: 1,
+        }),
+    };
+
+    for caps in tag_regex.captures_iter(html) {
+        let closing = caps.get(1).is_some_and(|m| !m.as_str().is_empty());
+        let tag = caps
+            .get(2)
+            .map(|m| m.as_str().to_lowercase())
+            .unwrap_or_default();
+        if closing {
+            continue;
+        }
+        if !ALLOWED_TAGS.contains(&tag.as_str()) {
+            report(HtmlIssueKind::DisallowedTag, tag);
+            continue;
+        }
+
+        let attributes = caps.get(3).map_or("", |m| m.as_str());
+        let mut input_type = None;
+        for attribute in attribute_regex.captures_iter(attributes) {
+            let name = attribute
+                .get(1)
+                .map(|m| m.as_str().to_lowercase())
+                .unwrap_or_default();
+            let value = attribute
+                .get(2)
+                .map(|m| m.as_str().trim_matches(['"', '\'']))
+                .unwrap_or("");
+
+            if name.starts_with("on") {
+                report(HtmlIssueKind::EventHandler, name);
+            } else if URL_ATTRIBUTES.contains(&name.as_str()) && is_script_url(value) {
+                report(HtmlIssueKind::ScriptUrl, name);
+            } else if name == "type" {
+                input_type = Some(value.to_lowercase());
+            }
+        }
+
+        if tag == "input" && input_type.as_deref() != Some("checkbox") {
+            report(HtmlIssueKind::FormControl, tag);
+        }
+    }
+
+    issues
+}
+
+fn is_script_url(value: &str) -> bool {
+    // Browsers ignore whitespace and control characters inside the scheme
+    let compact: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .take(32)
+        .collect::<String>()
+        .to_lowercase();
+    compact.starts_with("javascript:")
+        || compact.starts_with("vbscript:")
+        || compact.starts_with("data:text/html")
+}
@@ -0,0 +1,146 @@
+use std::fmt::Write;
+
+/// Above this many cells the changed middle section is shown as a full
+/// replacement instead of running the quadratic LCS.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Unified diff of `old` against `new` with `context` unchanged lines around
+/// each change. Empty when the texts have the same lines.
+pub fn unified_diff(old: &str, new: &str, context: usize) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_ops(&old_lines, &new_lines);
+
+    // Old and new line index at the start of each op
+    let mut positions = Vec::with_capacity(ops.len());
+    let (mut old_index, mut new_index) = (0, 0);
+    for (op, _) in &ops {
+        positions.push((old_index, new_index));
+        match op {
+            Op::Equal => {
+                old_index += 1;
+                new_index += 1;
+            }
+            Op::Delete => old_index += 1,
+            Op::Insert => new_index += 1,
+        }
+    }
+
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _))| *op != Op::Equal)
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut diff = String::new();
+    let mut next = 0;
+    while next < changes.len() {
+        let start = changes[next].saturating_sub(context);
+        let mut end = changes[next] + 1;
+        next += 1;
+        // Changes separated by at most twice the context share a hunk
+        while next < changes.len() && changes[next] <= end + 2 * context {
+            end = changes[next] + 1;
+            next += 1;
+        }
+        let end = (end + context).min(ops.len());
+
+        let hunk = &ops[start..end];
+        let (old_start, new_start) = positions[start];
+        let old_count = hunk.iter().filter(|(op, _)| *op != Op::Insert).count();
+        let new_count = hunk.iter().filter(|(op, _)| *op != Op::Delete).count();
+        let _ = writeln!(
+            diff,
+            "@@ -{} +{} @@",
+            hunk_range(old_start, old_count),
+            hunk_range(new_start, new_count)
+        );
+        for (op, line) in hunk {
+            let prefix = match op {
+                Op::Equal => ' ',
+                Op::Delete => '-',
+                Op::Insert => '+',
+            };
+            let _ = writeln!(diff, "{}{}", prefix, line);
+        }
+    }
+    diff
+}
+
+/// `start,count` with a 1-based start; an empty range points at the line
+/// before it, as in `diff -u`.
+fn hunk_range(start: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{},0", start)
+    } else {
+        format!("{},{}", start + 1, count)
+    }
+}
+
+fn diff_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<(Op, &str)> = old[..prefix].iter().map(|l| (Op::Equal, *l)).collect();
+    if old_middle.len().saturating_mul(new_middle.len()) > MAX_LCS_CELLS {
+        ops.extend(old_middle.iter().map(|l| (Op::Delete, *l)));
+        ops.extend(new_middle.iter().map(|l| (Op::Insert, *l)));
+    } else {
+        ops.extend(lcs_ops(old_middle, new_middle));
+    }
+    ops.extend(old[old.len() - suffix..].iter().map(|l| (Op::Equal, *l)));
+    ops
+}
+
+fn lcs_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    let width = new.len() + 1;
+    // lengths[i * width + j] = LCS length of old[i..] and new[j..]
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            ops.push((Op::Equal, old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            ops.push((Op::Delete, old[i]));
+            i += 1;
+        } else {
+            ops.push((Op::Insert, new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|l| (Op::Delete, *l)));
+    ops.extend(new[j..].iter().map(|l| (Op::Insert, *l)));
+    ops
+}
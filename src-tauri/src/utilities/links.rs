@@ -0,0 +1,336 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::ops::Range;
+
+static WIKILINK_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"(!?)\[\[([^\[\]\n]+?)\]\]"));
+
+static MARKDOWN_LINK_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"(!?)\[([^\[\]\n]*)\]\(([^()\s]+)(?:\s+[^()]*)?\)"));
+
+static EXTERNAL_URL_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"(?:https?://|mailto:)[^\s<>()\[\]]+"));
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    Wikilink,
+    Embed,
+    Markdown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NoteLink {
+    pub kind: LinkKind,
+    /// Link target without heading anchor or alias
+    pub target: String,
+    pub heading: Option<String>,
+    pub alias: Option<String>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column of the first character of the link syntax
+    pub column: usize,
+    pub length: usize,
+}
+
+/// Extracts internal note links (wikilinks, embeds and relative markdown links),
+/// skipping fenced code blocks and external URLs.
+pub fn extract_links(content: &str) -> Vec<NoteLink> {
+    let mut links = Vec::new();
+    let (wikilink_regex, markdown_regex) =
+        match (WIKILINK_REGEX.as_ref(), MARKDOWN_LINK_REGEX.as_ref()) {
+            (Ok(wiki), Ok(markdown)) => (wiki, markdown),
+            _ => return links,
+        };
+
+    for (index, line) in lines_outside_code_blocks(content) {
+        for caps in wikilink_regex.captures_iter(line) {
+            let (Some(whole), Some(inner)) = (caps.get(0), caps.get(2)) else {
+                continue;
+            };
+            let is_embed = caps.get(1).map(|m| !m.as_str().is_empty()).unwrap_or(false);
+            let (target, heading, alias) = split_wikilink(inner.as_str());
+            if target.is_empty() && heading.is_none() {
+                continue;
+            }
+
+            links.push(NoteLink {
+                kind: if is_embed {
+                    LinkKind::Embed
+                } else {
+                    LinkKind::Wikilink
+                },
+                target,
+                heading,
+                alias,
+                line: index + 1,
+                column: line[..whole.start()].chars().count() + 1,
+                length: whole.as_str().chars().count(),
+            });
+        }
+
+        for caps in markdown_regex.captures_iter(line) {
+            let (Some(whole), Some(text), Some(href)) = (caps.get(0), caps.get(2), caps.get(3))
+            else {
+                continue;
+            };
+            let is_image = caps.get(1).map(|m| !m.as_str().is_empty()).unwrap_or(false);
+            if is_image || is_external_href(href.as_str()) {
+                continue;
+            }
+
+            let (path, heading) = match href.as_str().split_once('#') {
+                Some((path, anchor)) => (path, Some(anchor.to_string())),
+                None => (href.as_str(), None),
+            };
+            if path.is_empty() {
+                continue;
+            }
+
+            links.push(NoteLink {
+                kind: LinkKind::Markdown,
+                target: decode_percent_spaces(path),
+                heading,
+                alias: Some(text.as_str().to_string()).filter(|t| !t.is_empty()),
+                line: index + 1,
+                column: line[..whole.start()].chars().count() + 1,
+                length: whole.as_str().chars().count(),
+            });
+        }
+    }
+
+    links
+}
+
+/// `![[target#heading]]` embeds in `text`, as their byte range, target and
+/// heading. The target is empty for an embed of a heading in the same note.
+pub fn find_embeds(text: &str) -> Vec<(Range<usize>, String, Option<String>)> {
+    let Ok(wikilink_regex) = WIKILINK_REGEX.as_ref() else {
+        return Vec::new();
+    };
+    wikilink_regex
+        .captures_iter(text)
+        .filter(|caps| &caps[1] == "!")
+        .filter_map(|caps| {
+            let whole = caps.get(0)?;
+            let (target, heading, _) = split_wikilink(&caps[2]);
+            Some((whole.range(), target, heading))
+        })
+        .collect()
+}
+
+/// Replaces every wikilink and embed with the text a reader sees: its alias,
+/// else its target, else the heading it points to.
+pub fn replace_wikilinks_with_text(text: &str) -> String {
+    let Ok(wikilink_regex) = WIKILINK_REGEX.as_ref() else {
+        return text.to_string();
+    };
+    wikilink_regex
+        .replace_all(text, |caps: &regex::Captures| {
+            let (target, heading, alias) = split_wikilink(&caps[2]);
+            alias
+                .filter(|alias| !alias.is_empty())
+                .or(Some(target).filter(|target| !target.is_empty()))
+                .or(heading)
+                .unwrap_or_default()
+        })
+        .to_string()
+}
+
+/// Resolves a link target against the known note filenames. Wikilinks match on
+/// file stem (case-insensitive, with or without folders); markdown links match
+/// on relative path.
+pub fn resolve_link_target(link: &NoteLink, source_note: &str, notes: &[String]) -> Option<String> {
+    match link.kind {
+        LinkKind::Markdown => {
+            let base = std::path::Path::new(source_note)
+                .parent()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let joined = normalize_relative_path(&base, &link.target);
+            notes
+                .iter()
+                .find(|note| {
+                    note.eq_ignore_ascii_case(&joined) || note.eq_ignore_ascii_case(&link.target)
+                })
+                .cloned()
+        }
+        LinkKind::Wikilink | LinkKind::Embed => {
+            if link.target.is_empty() {
+                return Some(source_note.to_string());
+            }
+            resolve_wikilink_name(&link.target, notes)
+        }
+    }
+}
+
+/// `content` with every wikilink and embed that resolves to `old_name`
+/// pointing at `new_name` instead, keeping headings, aliases and code blocks
+/// as they are, and the number of links changed. `notes_before` and
+/// `notes_after` are the note names before and after the rename.
+pub fn rewrite_wikilinks(
+    content: &str,
+    old_name: &str,
+    new_name: &str,
+    notes_before: &[String],
+    notes_after: &[String],
+) -> (String, usize) {
+    let Ok(wikilink_regex) = WIKILINK_REGEX.as_ref() else {
+        return (content.to_string(), 0);
+    };
+    let prose_lines: HashSet<usize> = lines_outside_code_blocks(content)
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut rewritten = String::with_capacity(content.len());
+    let mut count = 0;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        if !prose_lines.contains(&index) {
+            rewritten.push_str(line);
+            continue;
+        }
+        let line = wikilink_regex.replace_all(line, |caps: &regex::Captures| {
+            let inner = &caps[2];
+            let target_end = inner.find(['#', '|']).unwrap_or(inner.len());
+            let target = inner[..target_end].trim();
+            if target.is_empty()
+                || resolve_wikilink_name(target, notes_before).as_deref() != Some(old_name)
+            {
+                return caps[0].to_string();
+            }
+            count += 1;
+            let new_target = renamed_wikilink_target(target, new_name, notes_after);
+            format!("{}[[{}{}]]", &caps[1], new_target, &inner[target_end..])
+        });
+        rewritten.push_str(&line);
+    }
+    (rewritten, count)
+}
+
+/// How a link written as `target` names `new_name`: with a folder and file
+/// extension only where `target` had them, or where the bare name would
+/// resolve to another note.
+fn renamed_wikilink_target(target: &str, new_name: &str, notes_after: &[String]) -> String {
+    let with_extension = strip_note_extension(&target.to_lowercase()).len() < target.len();
+    let path = if with_extension {
+        new_name
+    } else {
+        strip_note_extension(new_name)
+    };
+    let name = path.rsplit('/').next().unwrap_or(path);
+    if !target.contains('/')
+        && resolve_wikilink_name(name, notes_after).as_deref() == Some(new_name)
+    {
+        name.to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+pub fn resolve_wikilink_name(name: &str, notes: &[String]) -> Option<String> {
+    let wanted = name.trim().to_lowercase();
+    let wanted_has_folder = wanted.contains('/');
+
+    notes
+        .iter()
+        .find(|note| {
+            let lower = note.to_lowercase();
+            let without_ext = strip_note_extension(&lower);
+            if wanted_has_folder {
+                without_ext == wanted || lower == wanted
+            } else {
+                let stem = without_ext.rsplit('/').next().unwrap_or(without_ext);
+                stem == wanted || lower == wanted
+            }
+        })
+        .cloned()
+}
+
+pub fn strip_note_extension(filename: &str) -> &str {
+    filename
+        .strip_suffix(".md")
+        .or_else(|| filename.strip_suffix(".markdown"))
+        .or_else(|| filename.strip_suffix(".txt"))
+        .unwrap_or(filename)
+}
+
+/// Number of `http(s)` and `mailto` URLs outside code blocks, whether bare,
+/// autolinked or the target of a markdown link.
+pub fn count_external_links(content: &str) -> usize {
+    let Ok(regex) = EXTERNAL_URL_REGEX.as_ref() else {
+        return 0;
+    };
+    lines_outside_code_blocks(content)
+        .map(|(_, line)| regex.find_iter(line).count())
+        .sum()
+}
+
+/// Yields `(line_index, line)` pairs for lines that are not inside fenced code blocks.
+pub fn lines_outside_code_blocks(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut fence: Option<&str> = None;
+    content.lines().enumerate().filter(move |(_, line)| {
+        let trimmed = line.trim_start();
+        let marker = if trimmed.starts_with("```") {
+            Some("```")
+        } else if trimmed.starts_with("~~~") {
+            Some("~~~")
+        } else {
+            None
+        };
+
+        match (fence, marker) {
+            (None, Some(open)) => {
+                fence = Some(open);
+                false
+            }
+            (Some(open), Some(close)) if open == close => {
+                fence = None;
+                false
+            }
+            (Some(_), _) => false,
+            (None, None) => true,
+        }
+    })
+}
+
+fn split_wikilink(inner: &str) -> (String, Option<String>, Option<String>) {
+    let (reference, alias) = match inner.split_once('|') {
+        Some((reference, alias)) => (reference, Some(alias.trim().to_string())),
+        None => (inner, None),
+    };
+    let (target, heading) = match reference.split_once('#') {
+        Some((target, heading)) => (target, Some(heading.trim().to_string())),
+        None => (reference, None),
+    };
+    (target.trim().to_string(), heading, alias)
+}
+
+fn is_external_href(href: &str) -> bool {
+    href.starts_with('#') || href.contains("://") || href.starts_with("mailto:")
+}
+
+fn decode_percent_spaces(path: &str) -> String {
+    path.replace("%20", " ")
+}
+
+fn normalize_relative_path(base: &str, target: &str) -> String {
+    let mut parts: Vec<&str> = if target.starts_with('/') {
+        Vec::new()
+    } else {
+        base.split('/').filter(|p| !p.is_empty()).collect()
+    };
+
+    for component in target.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            other => parts.push(other),
+        }
+    }
+
+    parts.join("/")
+}
@@ -0,0 +1,147 @@
+//! macOS Services menu entries ("New Symiosis Note from Selection" and
+//! "Append Selection to Symiosis Inbox"), declared under `NSServices` in
+//! `Info.plist`. The selected text is saved like a quick capture and the UI
+//! is told with a `selection-captured` event.
+
+#[cfg(target_os = "macos")]
+use crate::{
+    commands::{capture::append_text_to_note, note_crud::create_untitled_note_with_content},
+    core::{
+        events::{emit_event, SelectionCaptured},
+        state::AppState,
+        AppResult,
+    },
+    logging::log,
+};
+#[cfg(target_os = "macos")]
+use objc2::{
+    define_class, msg_send,
+    rc::Retained,
+    runtime::{AnyObject, NSObject},
+    AllocAnyThread, DefinedClass,
+};
+#[cfg(target_os = "macos")]
+use objc2_app_kit::{NSApplication, NSPasteboard, NSPasteboardTypeString};
+#[cfg(target_os = "macos")]
+use objc2_foundation::{MainThreadMarker, NSString};
+#[cfg(target_os = "macos")]
+use tauri::{AppHandle, Manager};
+
+#[cfg(target_os = "macos")]
+pub struct ServiceProviderIvars {
+    app: AppHandle,
+}
+
+#[cfg(target_os = "macos")]
+define_class!(
+    // SAFETY: NSObject has no subclassing requirements and the provider
+    // doesn't implement Drop.
+    #[unsafe(super(NSObject))]
+    #[name = "SymiosisServiceProvider"]
+    #[ivars = ServiceProviderIvars]
+    pub struct ServiceProvider;
+
+    impl ServiceProvider {
+        #[unsafe(method(newNoteFromSelection:userData:error:))]
+        fn new_note_from_selection(
+            &self,
+            pasteboard: &NSPasteboard,
+            _user_data: Option<&NSString>,
+            _error: *mut *mut NSString,
+        ) {
+            self.capture(pasteboard, true);
+        }
+
+        #[unsafe(method(appendSelectionToInbox:userData:error:))]
+        fn append_selection_to_inbox(
+            &self,
+            pasteboard: &NSPasteboard,
+            _user_data: Option<&NSString>,
+            _error: *mut *mut NSString,
+        ) {
+            self.capture(pasteboard, false);
+        }
+    }
+);
+
+#[cfg(target_os = "macos")]
+impl ServiceProvider {
+    fn new(app: AppHandle) -> Retained<Self> {
+        let this = Self::alloc().set_ivars(ServiceProviderIvars { app });
+        unsafe { msg_send![super(this), init] }
+    }
+
+    /// Saves the selection as a new untitled note, opening the app on it, or
+    /// appends it to the quick capture inbox note.
+    fn capture(&self, pasteboard: &NSPasteboard, create: bool) {
+        let text = unsafe { pasteboard.stringForType(NSPasteboardTypeString) }
+            .map(|text| text.to_string())
+            .unwrap_or_default();
+        if text.trim().is_empty() {
+            return;
+        }
+
+        let app = &self.ivars().app;
+        let app_state = app.state::<AppState>();
+        let saved = || -> AppResult<String> {
+            if create {
+                create_untitled_note_with_content(
+                    None,
+                    &format!("{}\n", text.trim_end()),
+                    &app_state,
+                )
+            } else {
+                append_text_to_note(None, &text, &app_state)
+            }
+        }();
+        match saved {
+            Ok(note_name) => {
+                if create {
+                    if let Some(window) = app.get_webview_window("main") {
+                        crate::utilities::mac_focus::save_current_frontmost_app();
+                        crate::utilities::mac_focus::show_app(window);
+                    }
+                }
+                emit_event(
+                    app,
+                    SelectionCaptured {
+                        note_name,
+                        created: create,
+                    },
+                );
+            }
+            Err(e) => log(
+                "MAC_SERVICES",
+                "Failed to save text from the Services menu",
+                Some(&e.to_string()),
+            ),
+        }
+    }
+}
+
+/// Makes the app answer its Services menu entries. Must run on the main
+/// thread, during setup.
+#[cfg(target_os = "macos")]
+pub fn register_services(app: &AppHandle) {
+    let Some(mtm) = MainThreadMarker::new() else {
+        log(
+            "MAC_SERVICES",
+            "Services menu not registered: setup is not on the main thread",
+            None,
+        );
+        return;
+    };
+    let provider = ServiceProvider::new(app.clone());
+    let provider_object: &AnyObject = &provider;
+    unsafe {
+        NSApplication::sharedApplication(mtm).setServicesProvider(Some(provider_object));
+    }
+    // AppKit doesn't retain the provider; it has to live as long as the app
+    std::mem::forget(provider);
+}
+
+// Stub implementation for non-macOS platforms
+#[cfg(not(target_os = "macos"))]
+pub fn register_services(_app: &tauri::AppHandle) {
+    // No Services menu outside macOS
+}
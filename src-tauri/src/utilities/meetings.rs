@@ -0,0 +1,197 @@
+use crate::utilities::{
+    frontmatter::{note_title, parse_frontmatter},
+    links::lines_outside_code_blocks,
+    mentions::first_handle,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+static HEADING_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"^#{1,6}\s+(.+?)\s*#*\s*$"));
+static LABEL_REGEX: Lazy<Result<Regex, regex::Error>> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^\s*(?:\*\*|__)?(attendees|participants|present|decisions?|decided|action items?|actions|next steps)\s*:\s*(?:\*\*|__)?\s*(.*)$",
+    )
+});
+static LIST_ITEM_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"^\s*(?:[-*+]|\d+[.)])\s+(?:\[([ xX])\]\s+)?(.*)$"));
+static DUE_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"(?:📅\s*|due:\s*)(\d{4}-\d{2}-\d{2})"));
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Attendees,
+    Decisions,
+    Actions,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActionItem {
+    /// First `@mention` in the item, without the `@`
+    pub owner: Option<String>,
+    pub text: String,
+    pub done: bool,
+    pub due: Option<String>,
+    /// 1-based line number
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MeetingNote {
+    pub title: String,
+    pub date: Option<String>,
+    pub attendees: Vec<String>,
+    pub decisions: Vec<String>,
+    pub action_items: Vec<ActionItem>,
+}
+
+/// Reads attendees from front-matter (`attendees`/`participants`) or an Attendees
+/// section, decisions from a Decisions section or `Decision:` lines, and action items
+/// from checkboxes that name an `@owner` or sit under an Action items section.
+pub fn parse_meeting(note_name: &str, content: &str) -> MeetingNote {
+    let frontmatter = parse_frontmatter(content);
+    let mut meeting = MeetingNote {
+        title: note_title(note_name, content),
+        ..MeetingNote::default()
+    };
+
+    if let Some(frontmatter) = &frontmatter {
+        meeting.date = frontmatter
+            .get_text("date")
+            .or_else(|| frontmatter.get_text("created"))
+            .map(str::to_string);
+        if let Some(value) = frontmatter
+            .get("attendees")
+            .or_else(|| frontmatter.get("participants"))
+        {
+            for item in value.as_list() {
+                push_attendees(&mut meeting.attendees, &item);
+            }
+        }
+    }
+
+    let (Ok(heading_regex), Ok(label_regex), Ok(item_regex)) = (
+        HEADING_REGEX.as_ref(),
+        LABEL_REGEX.as_ref(),
+        LIST_ITEM_REGEX.as_ref(),
+    ) else {
+        return meeting;
+    };
+
+    let body_start = frontmatter.map(|fm| fm.line_count).unwrap_or(0);
+    let mut section = Section::Other;
+    for (index, line) in lines_outside_code_blocks(content) {
+        if index < body_start {
+            continue;
+        }
+
+        if let Some(caps) = heading_regex.captures(line) {
+            section = classify_section(caps.get(1).map_or("", |m| m.as_str()));
+            continue;
+        }
+
+        if let Some(caps) = label_regex.captures(line) {
+            section = classify_section(caps.get(1).map_or("", |m| m.as_str()));
+            let rest = caps.get(2).map_or("", |m| m.as_str()).trim();
+            match section {
+                Section::Attendees => push_attendees(&mut meeting.attendees, rest),
+                Section::Decisions if !rest.is_empty() => {
+                    meeting.decisions.push(rest.to_string());
+                    // A one-line `Decision:` doesn't start a list
+                    section = Section::Other;
+                }
+                Section::Actions if !rest.is_empty() => {
+                    meeting
+                        .action_items
+                        .push(action_item(rest, false, index + 1));
+                    section = Section::Other;
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let Some(caps) = item_regex.captures(line) else {
+            continue;
+        };
+        let checkbox = caps.get(1).map(|m| m.as_str());
+        let text = caps.get(2).map_or("", |m| m.as_str()).trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        match section {
+            Section::Attendees => push_attendees(&mut meeting.attendees, text),
+            Section::Decisions => meeting.decisions.push(text.to_string()),
+            Section::Actions => {
+                let done = checkbox.is_some_and(|mark| mark.eq_ignore_ascii_case("x"));
+                meeting
+                    .action_items
+                    .push(action_item(text, done, index + 1));
+            }
+            Section::Other => {
+                if let Some(mark) = checkbox {
+                    let item = action_item(text, mark.eq_ignore_ascii_case("x"), index + 1);
+                    if item.owner.is_some() {
+                        meeting.action_items.push(item);
+                    }
+                }
+            }
+        }
+    }
+
+    meeting
+}
+
+fn classify_section(heading: &str) -> Section {
+    let heading = heading
+        .trim()
+        .trim_matches(|c| c == '*' || c == '_' || c == ':')
+        .to_lowercase();
+    match heading.as_str() {
+        "attendees" | "participants" | "present" | "people" => Section::Attendees,
+        "decisions" | "decision" | "decided" | "outcomes" => Section::Decisions,
+        "action items" | "action item" | "actions" | "next steps" | "todo" | "to do"
+        | "follow-ups" | "follow ups" => Section::Actions,
+        _ => Section::Other,
+    }
+}
+
+fn action_item(text: &str, done: bool, line: usize) -> ActionItem {
+    let owner = first_handle(text);
+    let due = DUE_REGEX
+        .as_ref()
+        .ok()
+        .and_then(|regex| regex.captures(text))
+        .and_then(|caps| caps.get(1))
+        .map(|due| due.as_str().to_string());
+
+    ActionItem {
+        owner,
+        text: text.to_string(),
+        done,
+        due,
+        line,
+    }
+}
+
+/// Accepts comma-separated names, `@handles` and `[[People/Name]]` links.
+fn push_attendees(attendees: &mut Vec<String>, raw: &str) {
+    for name in raw.split([',', ';']) {
+        let name = name.trim().trim_start_matches('@');
+        let name = name
+            .strip_prefix("[[")
+            .and_then(|inner| inner.strip_suffix("]]"))
+            .map(|inner| {
+                let target = inner.split('|').next_back().unwrap_or(inner);
+                target.rsplit('/').next().unwrap_or(target)
+            })
+            .unwrap_or(name)
+            .trim();
+        if !name.is_empty() && !attendees.iter().any(|a| a.eq_ignore_ascii_case(name)) {
+            attendees.push(name.to_string());
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::utilities::links::{extract_links, lines_outside_code_blocks, strip_note_extension};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+/// Folder whose notes act as person pages; links into it count as mentions.
+pub const PEOPLE_FOLDER: &str = "People";
+
+// Requires whitespace or an opening bracket before `@`, so email addresses don't match
+static MENTION_REGEX: Lazy<Result<Regex, regex::Error>> = Lazy::new(|| {
+    Regex::new(r"(?:^|[\s(\[])@([\p{L}\p{N}_][\p{L}\p{N}_.-]*[\p{L}\p{N}_]|[\p{L}\p{N}_])")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MentionKind {
+    Handle,
+    Link,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Mention {
+    /// Normalized key shared by `@ana_lopez` and `[[People/Ana Lopez]]`
+    pub person: String,
+    /// The name as written
+    pub display: String,
+    pub kind: MentionKind,
+    /// 1-based line number
+    pub line: usize,
+}
+
+/// Lowercases and treats `_`, `-` and `.` as spaces so handles and page names agree.
+pub fn normalize_person(name: &str) -> String {
+    name.trim()
+        .trim_start_matches('@')
+        .split(|c: char| c.is_whitespace() || matches!(c, '_' | '-' | '.'))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// First `@handle` in a piece of text, without the `@`.
+pub fn first_handle(text: &str) -> Option<String> {
+    MENTION_REGEX
+        .as_ref()
+        .ok()?
+        .captures(text)?
+        .get(1)
+        .map(|handle| handle.as_str().to_string())
+}
+
+/// Person page a note name refers to, e.g. `People/Ana Lopez.md` -> `Ana Lopez`.
+pub fn person_page_name(target: &str) -> Option<&str> {
+    let target = strip_note_extension(target.trim_start_matches('/'));
+    let (folder, name) = target.split_once('/')?;
+    (folder.eq_ignore_ascii_case(PEOPLE_FOLDER) && !name.is_empty() && !name.contains('/'))
+        .then_some(name)
+}
+
+/// Collects `@handles` and links to `People/` pages outside code blocks.
+pub fn extract_mentions(content: &str) -> Vec<Mention> {
+    let mut mentions = Vec::new();
+
+    if let Ok(regex) = MENTION_REGEX.as_ref() {
+        for (index, line) in lines_outside_code_blocks(content) {
+            for caps in regex.captures_iter(line) {
+                if let Some(handle) = caps.get(1) {
+                    mentions.push(Mention {
+                        person: normalize_person(handle.as_str()),
+                        display: handle.as_str().to_string(),
+                        kind: MentionKind::Handle,
+                        line: index + 1,
+                    });
+                }
+            }
+        }
+    }
+
+    for link in extract_links(content) {
+        if let Some(name) = person_page_name(&link.target) {
+            mentions.push(Mention {
+                person: normalize_person(name),
+                display: name.to_string(),
+                kind: MentionKind::Link,
+                line: link.line,
+            });
+        }
+    }
+
+    mentions.sort_by_key(|mention| mention.line);
+    mentions
+}
@@ -0,0 +1,157 @@
+use crate::core::{AppError, AppResult};
+use crate::logging::log;
+use crate::utilities::paths::get_temp_dir;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// Longest a single mermaid-cli run may take before it's killed
+const MERMAID_CLI_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Tells apart the temporary files of diagrams rendered at the same time
+static CLI_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+static MERMAID_CACHE: Lazy<RwLock<MermaidCache>> =
+    Lazy::new(|| RwLock::new(MermaidCache::default()));
+
+#[derive(Default)]
+struct MermaidCache {
+    cli: Option<PathBuf>,
+    /// Diagram source hash to its SVG, `None` when the CLI failed on it
+    diagrams: HashMap<String, Option<String>>,
+}
+
+/// Whether a fenced code block's info string (` ```mermaid `) marks a diagram
+pub fn is_mermaid_fence(info: &str) -> bool {
+    info.split_whitespace()
+        .next()
+        .is_some_and(|lang| lang.eq_ignore_ascii_case("mermaid"))
+}
+
+/// Sets the `[interface] mermaid_cli` executable diagrams are pre-rendered
+/// with; an empty or missing path leaves them for the front end.
+pub fn configure_mermaid_cli(cli: Option<&str>) {
+    let mut cache = MERMAID_CACHE.write().unwrap_or_else(|e| e.into_inner());
+    cache.cli = cli
+        .map(str::trim)
+        .filter(|cli| !cli.is_empty())
+        .map(PathBuf::from);
+    cache.diagrams.clear();
+}
+
+pub fn mermaid_cli_enabled() -> bool {
+    let cache = MERMAID_CACHE.read().unwrap_or_else(|e| e.into_inner());
+    cache.cli.is_some()
+}
+
+/// A mermaid diagram in its own `<div class="mermaid-diagram">`: the SVG the
+/// CLI made of it as an image, or else its source in a `<pre class="mermaid">`
+/// for mermaid.js to pick up.
+pub fn mermaid_block_html(source: &str) -> String {
+    let inner = match prerendered_svg(source) {
+        Some(svg) => format!(
+            "<img class=\"mermaid-svg\" alt=\"Mermaid diagram\" src=\"data:image/svg+xml;base64,{}\">",
+            STANDARD.encode(svg)
+        ),
+        None => format!(
+            "<pre class=\"mermaid\">{}</pre>",
+            html_escape::encode_text(source)
+        ),
+    };
+    format!("<div class=\"mermaid-diagram\">{}</div>\n", inner)
+}
+
+/// The diagram as SVG when a CLI is configured, rendering each distinct
+/// diagram once. Failures are logged and remembered so the note still shows
+/// its source instead of retrying on every render.
+fn prerendered_svg(source: &str) -> Option<String> {
+    let key = format!("{:x}", Sha256::digest(source.as_bytes()));
+    let cli = {
+        let cache = MERMAID_CACHE.read().unwrap_or_else(|e| e.into_inner());
+        let cli = cache.cli.clone()?;
+        if let Some(svg) = cache.diagrams.get(&key) {
+            return svg.clone();
+        }
+        cli
+    };
+
+    let svg = match run_mermaid_cli(&cli, &key, source) {
+        Ok(svg) => Some(svg),
+        Err(e) => {
+            log(
+                "MERMAID",
+                "Failed to pre-render a mermaid diagram",
+                Some(&e.to_string()),
+            );
+            None
+        }
+    };
+    let mut cache = MERMAID_CACHE.write().unwrap_or_else(|e| e.into_inner());
+    if cache.cli.as_ref() == Some(&cli) {
+        cache.diagrams.insert(key, svg.clone());
+    }
+    svg
+}
+
+fn run_mermaid_cli(cli: &Path, key: &str, source: &str) -> AppResult<String> {
+    let dir = get_temp_dir()?.join("mermaid");
+    fs::create_dir_all(&dir)?;
+    let run = CLI_RUNS.fetch_add(1, Ordering::Relaxed);
+    let input = dir.join(format!("{}-{}.mmd", key, run));
+    let output = dir.join(format!("{}-{}.svg", key, run));
+    fs::write(&input, source)?;
+
+    let result = (|| -> AppResult<String> {
+        let mut child = Command::new(cli)
+            .arg("-i")
+            .arg(&input)
+            .arg("-o")
+            .arg(&output)
+            .args(["-b", "transparent"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| {
+                AppError::ConfigLoad(format!(
+                    "Failed to run mermaid CLI '{}': {}",
+                    cli.display(),
+                    e
+                ))
+            })?;
+
+        let started = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if started.elapsed() > MERMAID_CLI_TIMEOUT {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AppError::FileWrite(format!(
+                    "Mermaid CLI took longer than {}s",
+                    MERMAID_CLI_TIMEOUT.as_secs()
+                )));
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        };
+        if !status.success() {
+            return Err(AppError::FileWrite(format!(
+                "Mermaid CLI exited with {}",
+                status
+            )));
+        }
+        Ok(fs::read_to_string(&output)?)
+    })();
+
+    let _ = fs::remove_file(&input);
+    let _ = fs::remove_file(&output);
+    result
+}
@@ -1,7 +1,33 @@
+pub mod chat_format;
+pub mod citations;
 pub mod config_helpers;
+pub mod dates;
+pub mod docx_export;
+pub mod dry_run;
 pub mod file_safety;
+pub mod frontmatter;
+pub mod geo;
+pub mod highlights;
+pub mod html_audit;
+pub mod line_diff;
+pub mod links;
 pub mod mac_focus;
+pub mod mac_services;
+pub mod meetings;
+pub mod mentions;
+pub mod mermaid;
+pub mod note_lint;
 pub mod note_renderer;
+pub mod note_sections;
 pub mod paths;
+pub mod property_query;
+pub mod references;
+pub mod slides;
 pub mod strings;
+pub mod tags;
+pub mod tasks;
+pub mod text_encoding;
+pub mod time_entries;
 pub mod validation;
+pub mod version_labels;
+pub mod write_recovery;
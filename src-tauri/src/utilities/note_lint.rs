@@ -0,0 +1,205 @@
+use crate::config::LintConfig;
+use crate::utilities::frontmatter::parse_frontmatter;
+use crate::utilities::links::{extract_links, lines_outside_code_blocks, resolve_link_target};
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LintDiagnostic {
+    pub rule: &'static str,
+    pub severity: LintSeverity,
+    pub message: String,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based start column
+    pub column: usize,
+    /// 1-based exclusive end column
+    pub end_column: usize,
+}
+
+pub fn lint_content(
+    note_name: &str,
+    content: &str,
+    config: &LintConfig,
+    known_notes: &[String],
+) -> Vec<LintDiagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if !config.required_frontmatter_fields.is_empty() {
+        check_required_frontmatter(content, config, &mut diagnostics);
+    }
+    if config.check_trailing_whitespace {
+        check_trailing_whitespace(content, &mut diagnostics);
+    }
+    if config.max_line_length > 0 {
+        check_line_length(content, config.max_line_length, &mut diagnostics);
+    }
+    if config.check_duplicate_headings {
+        check_duplicate_headings(content, &mut diagnostics);
+    }
+    if config.check_broken_links {
+        check_broken_links(note_name, content, known_notes, &mut diagnostics);
+    }
+
+    diagnostics.sort_by(|a, b| a.line.cmp(&b.line).then(a.column.cmp(&b.column)));
+    diagnostics
+}
+
+/// Parses ATX headings outside code blocks as `(line_index, level, text)`.
+pub fn parse_atx_headings(content: &str) -> Vec<(usize, usize, String)> {
+    let frontmatter_lines = parse_frontmatter(content)
+        .map(|fm| fm.line_count)
+        .unwrap_or(0);
+
+    lines_outside_code_blocks(content)
+        .filter(|(index, _)| *index >= frontmatter_lines)
+        .filter_map(|(index, line)| {
+            let trimmed = line.trim_start();
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            if level == 0 || level > 6 {
+                return None;
+            }
+            let rest = &trimmed[level..];
+            if !rest.is_empty() && !rest.starts_with(' ') && !rest.starts_with('\t') {
+                return None;
+            }
+            let text = rest.trim().trim_end_matches('#').trim_end().to_string();
+            Some((index, level, text))
+        })
+        .collect()
+}
+
+fn check_required_frontmatter(
+    content: &str,
+    config: &LintConfig,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    let frontmatter = parse_frontmatter(content);
+
+    for field in &config.required_frontmatter_fields {
+        let present = frontmatter
+            .as_ref()
+            .map(|fm| fm.contains_key(field))
+            .unwrap_or(false);
+        if !present {
+            diagnostics.push(LintDiagnostic {
+                rule: "missing-frontmatter-field",
+                severity: LintSeverity::Warning,
+                message: format!("Missing front-matter field '{}'", field),
+                line: 1,
+                column: 1,
+                end_column: first_line_end_column(content),
+            });
+        }
+    }
+}
+
+fn check_trailing_whitespace(content: &str, diagnostics: &mut Vec<LintDiagnostic>) {
+    for (index, line) in content.lines().enumerate() {
+        let trimmed_len = line.trim_end_matches([' ', '\t']).chars().count();
+        let total_len = line.chars().count();
+        let trailing = &line[line.trim_end_matches([' ', '\t']).len()..];
+
+        // Exactly two spaces is a markdown hard line break
+        if total_len == trimmed_len || (trailing == "  " && trimmed_len > 0) {
+            continue;
+        }
+
+        diagnostics.push(LintDiagnostic {
+            rule: "trailing-whitespace",
+            severity: LintSeverity::Info,
+            message: "Trailing whitespace".to_string(),
+            line: index + 1,
+            column: trimmed_len + 1,
+            end_column: total_len + 1,
+        });
+    }
+}
+
+fn check_line_length(content: &str, max_length: usize, diagnostics: &mut Vec<LintDiagnostic>) {
+    for (index, line) in lines_outside_code_blocks(content) {
+        let length = line.chars().count();
+        // Lines without spaces (long URLs, table separators) cannot be wrapped
+        if length <= max_length || !line.trim().contains(' ') {
+            continue;
+        }
+
+        diagnostics.push(LintDiagnostic {
+            rule: "line-too-long",
+            severity: LintSeverity::Info,
+            message: format!("Line is {} characters long (max {})", length, max_length),
+            line: index + 1,
+            column: max_length + 1,
+            end_column: length + 1,
+        });
+    }
+}
+
+fn check_duplicate_headings(content: &str, diagnostics: &mut Vec<LintDiagnostic>) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for (index, _level, text) in parse_atx_headings(content) {
+        if text.is_empty() {
+            continue;
+        }
+        let key = text.to_lowercase();
+        match seen.get(&key) {
+            Some(first_line) => {
+                let line = content.lines().nth(index).unwrap_or_default();
+                diagnostics.push(LintDiagnostic {
+                    rule: "duplicate-heading",
+                    severity: LintSeverity::Warning,
+                    message: format!(
+                        "Duplicate heading '{}' (first defined on line {})",
+                        text,
+                        first_line + 1
+                    ),
+                    line: index + 1,
+                    column: 1,
+                    end_column: line.chars().count() + 1,
+                });
+            }
+            None => {
+                seen.insert(key, index);
+            }
+        }
+    }
+}
+
+fn check_broken_links(
+    note_name: &str,
+    content: &str,
+    known_notes: &[String],
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    for link in extract_links(content) {
+        if resolve_link_target(&link, note_name, known_notes).is_some() {
+            continue;
+        }
+
+        diagnostics.push(LintDiagnostic {
+            rule: "broken-link",
+            severity: LintSeverity::Error,
+            message: format!("Link target '{}' does not exist", link.target),
+            line: link.line,
+            column: link.column,
+            end_column: link.column + link.length,
+        });
+    }
+}
+
+fn first_line_end_column(content: &str) -> usize {
+    content
+        .lines()
+        .next()
+        .map(|line| line.chars().count() + 1)
+        .unwrap_or(1)
+}
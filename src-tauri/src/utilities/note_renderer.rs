@@ -1,8 +1,20 @@
+use crate::config::InterfaceConfig;
 use crate::core::errors::{AppError, AppResult};
+use crate::utilities::{
+    citations::bibliography_fingerprint,
+    frontmatter::strip_frontmatter,
+    links::{find_embeds, replace_wikilinks_with_text},
+    mermaid::{is_mermaid_fence, mermaid_block_html, mermaid_cli_enabled},
+};
 use html_escape;
 use once_cell::sync::Lazy;
-use pulldown_cmark::{html, Options, Parser};
+use pulldown_cmark::{
+    html, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd, TextMergeStream,
+};
 use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
 
 static URL_REGEX: Lazy<Result<Regex, regex::Error>> =
     Lazy::new(|| Regex::new(r#"(?i)\b(https?://[^\s<>"'`()\[\]{}]+)\b"#));
@@ -50,20 +62,102 @@ pub(crate) fn linkify_urls_in_html(html: &str) -> AppResult<String> {
     Ok(result)
 }
 
+/// Bump whenever `render_note` output changes, so `rerender_all_notes` refreshes
+/// stored renders without a `force`.
+pub const RENDERER_VERSION: i64 = 4;
+
+const TABLES: u8 = 1;
+const TASK_LISTS: u8 = 1 << 1;
+const FOOTNOTES: u8 = 1 << 2;
+const STRIKETHROUGH: u8 = 1 << 3;
+
+/// The `[interface]` markdown extensions turned on, as the bits above
+static MARKDOWN_EXTENSIONS: AtomicU8 =
+    AtomicU8::new(TABLES | TASK_LISTS | FOOTNOTES | STRIKETHROUGH);
+
+/// Applies the `[interface]` markdown extension flags to every later render.
+pub fn configure_markdown_extensions(interface: &InterfaceConfig) {
+    let mut extensions = 0;
+    for (enabled, bit) in [
+        (interface.markdown_tables, TABLES),
+        (interface.markdown_task_lists, TASK_LISTS),
+        (interface.markdown_footnotes, FOOTNOTES),
+        (interface.markdown_strikethrough, STRIKETHROUGH),
+    ] {
+        if enabled {
+            extensions |= bit;
+        }
+    }
+    MARKDOWN_EXTENSIONS.store(extensions, Ordering::Relaxed);
+}
+
+/// Identifies what `render_note` produces now: the renderer version, the
+/// extensions turned on, whether mermaid diagrams are pre-rendered and the
+/// bibliography citations resolve against. Stored renders from another version
+/// are stale.
+pub fn render_version() -> i64 {
+    let mermaid = if mermaid_cli_enabled() { 1 << 4 } else { 0 };
+    let bibliography = i64::from(bibliography_fingerprint() >> 1) << 32;
+    bibliography
+        | (RENDERER_VERSION << 5)
+        | mermaid
+        | i64::from(MARKDOWN_EXTENSIONS.load(Ordering::Relaxed))
+}
+
+fn is_markdown_note(filename: &str) -> bool {
+    filename.ends_with(".md") || filename.ends_with(".markdown")
+}
+
+fn markdown_options() -> Options {
+    let extensions = MARKDOWN_EXTENSIONS.load(Ordering::Relaxed);
+    let mut options = Options::empty();
+    for (bit, option) in [
+        (TABLES, Options::ENABLE_TABLES),
+        (TASK_LISTS, Options::ENABLE_TASKLISTS),
+        (FOOTNOTES, Options::ENABLE_FOOTNOTES),
+        (STRIKETHROUGH, Options::ENABLE_STRIKETHROUGH),
+    ] {
+        if extensions & bit != 0 {
+            options.insert(option);
+        }
+    }
+    options.insert(Options::ENABLE_SMART_PUNCTUATION);
+    // Front matter is parsed into a metadata block, which isn't rendered
+    options.insert(Options::ENABLE_YAML_STYLE_METADATA_BLOCKS);
+    options
+}
+
+/// Deepest chain of embeds expanded; embeds below it are left as written
+pub const MAX_EMBED_DEPTH: usize = 4;
+
+const EMBED_OPEN: &str = "<div class=\"note-embed\">";
+
+/// Finds the note an embed's target names, returning its name and content
+pub type EmbedResolver<'a> = dyn Fn(&str) -> Option<(String, String)> + 'a;
+
+/// The note being rendered and the embeds being expanded inside it, as
+/// lowercased note name and heading
+type EmbedChain = Vec<(String, Option<String>)>;
+
 pub fn render_note(filename: &str, content: &str) -> String {
-    if filename.ends_with(".md") || filename.ends_with(".markdown") {
-        let mut options = Options::empty();
-        options.insert(Options::ENABLE_STRIKETHROUGH);
-        options.insert(Options::ENABLE_TABLES);
-        options.insert(Options::ENABLE_FOOTNOTES);
-        options.insert(Options::ENABLE_TASKLISTS);
-        options.insert(Options::ENABLE_SMART_PUNCTUATION);
-
-        let parser = Parser::new_ext(content, options);
-        let mut html_output = String::new();
-        html::push_html(&mut html_output, parser);
-
-        match linkify_urls_in_html(&html_output) {
+    render_note_with_embeds(filename, content, &|_| None)
+}
+
+/// `render_note` with each `![[note]]` or `![[note#heading]]` embed replaced
+/// by the rendered note, or section under that heading, that `resolve` finds.
+/// Embeds that don't resolve, that would embed a note (or section) already
+/// being embedded, or that are nested deeper than [`MAX_EMBED_DEPTH`] are
+/// left as written.
+pub fn render_note_with_embeds(filename: &str, content: &str, resolve: &EmbedResolver) -> String {
+    if is_markdown_note(filename) {
+        let html_output = render_markdown(
+            filename,
+            content,
+            resolve,
+            &mut vec![(filename.to_lowercase(), None)],
+        );
+
+        let html_output = match linkify_urls_in_html(&html_output) {
             Ok(result) => result,
             Err(e) => {
                 crate::logging::log(
@@ -73,7 +167,9 @@ pub fn render_note(filename: &str, content: &str) -> String {
                 );
                 html_output // Return original HTML if linkification fails
             }
-        }
+        };
+
+        crate::utilities::references::render_reference_links(&html_output).unwrap_or(html_output)
     } else {
         let escaped = html_escape::encode_text(content);
         match linkify_urls_in_html(&escaped) {
@@ -89,3 +185,325 @@ pub fn render_note(filename: &str, content: &str) -> String {
         }
     }
 }
+
+/// Markdown to HTML with embeds expanded; links and references are handled
+/// once for the whole note by the caller.
+fn render_markdown(
+    filename: &str,
+    content: &str,
+    resolve: &EmbedResolver,
+    chain: &mut EmbedChain,
+) -> String {
+    let with_citations = crate::utilities::citations::render_citations(content);
+    let content = with_citations.as_deref().unwrap_or(content);
+
+    let mut events = Vec::new();
+    let mut in_code_block = false;
+    // Source of the ```mermaid block being read
+    let mut mermaid: Option<String> = None;
+    for event in TextMergeStream::new(Parser::new_ext(content, markdown_options())) {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(ref info)))
+                if is_mermaid_fence(info) =>
+            {
+                mermaid = Some(String::new());
+                continue;
+            }
+            Event::Text(ref text) if mermaid.is_some() => {
+                mermaid.get_or_insert_with(String::new).push_str(text);
+                continue;
+            }
+            Event::End(TagEnd::CodeBlock) if mermaid.is_some() => {
+                let source = mermaid.take().unwrap_or_default();
+                events.push(Event::Html(mermaid_block_html(&source).into()));
+                continue;
+            }
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            // An embed alone in a paragraph replaces it rather than nest a div in a p
+            Event::End(TagEnd::Paragraph) => {
+                if let [.., Event::Start(Tag::Paragraph), Event::InlineHtml(embed)] =
+                    events.as_slice()
+                {
+                    if embed.starts_with(EMBED_OPEN) {
+                        let embed = Event::Html(embed.clone());
+                        events.truncate(events.len() - 2);
+                        events.push(embed);
+                        continue;
+                    }
+                }
+            }
+            Event::Text(ref text) if !in_code_block && text.contains("![[") => {
+                let mut rest = 0;
+                for (range, target, heading) in find_embeds(text) {
+                    let Some(embed) =
+                        render_embed(filename, content, &target, heading, resolve, chain)
+                    else {
+                        continue;
+                    };
+                    if range.start > rest {
+                        events.push(Event::Text(text[rest..range.start].to_string().into()));
+                    }
+                    events.push(Event::InlineHtml(embed.into()));
+                    rest = range.end;
+                }
+                if rest < text.len() {
+                    events.push(Event::Text(text[rest..].to_string().into()));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        events.push(event);
+    }
+
+    let mut html_output = String::new();
+    html::push_html(&mut html_output, events.into_iter());
+    html_output
+}
+
+/// One embed as `<div class="note-embed">`, or `None` to leave it as written.
+fn render_embed(
+    filename: &str,
+    content: &str,
+    target: &str,
+    heading: Option<String>,
+    resolve: &EmbedResolver,
+    chain: &mut EmbedChain,
+) -> Option<String> {
+    // The chain starts with the note being rendered
+    if chain.len() > MAX_EMBED_DEPTH {
+        return None;
+    }
+    let (name, embedded) = if target.is_empty() {
+        (filename.to_string(), content.to_string())
+    } else {
+        resolve(target)?
+    };
+    let link = (
+        name.to_lowercase(),
+        heading.as_ref().map(|heading| heading.to_lowercase()),
+    );
+    if chain.contains(&link) {
+        return None;
+    }
+    let embedded = match &heading {
+        Some(heading) => heading_section(&embedded, heading)?,
+        None => strip_frontmatter(&embedded),
+    };
+
+    let html_output = if is_markdown_note(&name) {
+        chain.push(link);
+        let html_output = render_markdown(&name, embedded, resolve, chain);
+        chain.pop();
+        html_output
+    } else {
+        format!("<pre>{}</pre>", html_escape::encode_text(embedded))
+    };
+    Some(format!("{}\n{}</div>\n", EMBED_OPEN, html_output))
+}
+
+/// The heading titled `heading` (ignoring case) and everything up to the next
+/// heading of the same or a higher level.
+fn heading_section<'a>(content: &'a str, heading: &str) -> Option<&'a str> {
+    let wanted = heading.trim().to_lowercase();
+    let mut section: Option<(usize, HeadingLevel)> = None;
+    let mut current: Option<(usize, HeadingLevel, String)> = None;
+
+    for (event, range) in Parser::new_ext(content, markdown_options()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                if let Some((start, section_level)) = section {
+                    if level <= section_level {
+                        return Some(content[start..range.start].trim_end());
+                    }
+                }
+                current = Some((range.start, level, String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, _, title)) = current.as_mut() {
+                    title.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((start, level, title)) = current.take() {
+                    if section.is_none() && title.trim().to_lowercase() == wanted {
+                        section = Some((start, level));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    section.map(|(start, _)| content[start..].trim_end())
+}
+
+/// A note as plain text for pasting into forms: front matter, markdown
+/// syntax, HTML and wikilink brackets are removed while paragraphs, list items
+/// and code keep their lines. Other notes are returned as they are.
+pub fn note_plain_text(filename: &str, content: &str) -> String {
+    if !is_markdown_note(filename) {
+        return content.to_string();
+    }
+
+    let mut output = String::new();
+    // Prose waiting for its wikilinks to be replaced; code goes straight out
+    let mut prose = String::new();
+    let flush = |prose: &mut String, output: &mut String| {
+        output.push_str(&replace_wikilinks_with_text(prose));
+        prose.clear();
+    };
+    let block_break = |prose: &mut String, output: &mut String| {
+        flush(prose, output);
+        let trimmed = output.trim_end_matches([' ', '\n']).len();
+        output.truncate(trimmed);
+        if !output.is_empty() {
+            output.push_str("\n\n");
+        }
+    };
+    // Next number of each open list, `None` for bullet lists
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    let mut in_code_block = false;
+
+    for event in Parser::new_ext(strip_frontmatter(content), markdown_options()) {
+        match event {
+            Event::Text(text) if in_code_block => output.push_str(&text),
+            Event::Text(text) => prose.push_str(&text),
+            Event::Code(code) => {
+                flush(&mut prose, &mut output);
+                output.push_str(&code);
+            }
+            Event::SoftBreak | Event::HardBreak => prose.push('\n'),
+            Event::TaskListMarker(checked) => prose.push_str(if checked { "[x] " } else { "[ ] " }),
+            Event::Start(Tag::CodeBlock(_)) => {
+                block_break(&mut prose, &mut output);
+                in_code_block = true;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                block_break(&mut prose, &mut output);
+            }
+            Event::Start(Tag::List(start)) => {
+                if lists.is_empty() {
+                    block_break(&mut prose, &mut output);
+                }
+                lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+                if lists.is_empty() {
+                    block_break(&mut prose, &mut output);
+                }
+            }
+            Event::Start(Tag::Item) => {
+                flush(&mut prose, &mut output);
+                let trimmed = output.trim_end_matches(' ').len();
+                output.truncate(trimmed);
+                if !output.is_empty() && !output.ends_with('\n') {
+                    output.push('\n');
+                }
+                let depth = lists.len().saturating_sub(1);
+                output.push_str(&"  ".repeat(depth));
+                match lists.last_mut() {
+                    Some(Some(number)) => {
+                        output.push_str(&format!("{}. ", number));
+                        *number += 1;
+                    }
+                    _ => output.push_str("• "),
+                }
+            }
+            Event::End(TagEnd::Paragraph)
+            | Event::End(TagEnd::Heading(_))
+            | Event::End(TagEnd::BlockQuote(_))
+            | Event::End(TagEnd::Table)
+                if lists.is_empty() =>
+            {
+                block_break(&mut prose, &mut output);
+            }
+            Event::End(TagEnd::TableCell) => prose.push('\t'),
+            Event::End(TagEnd::TableHead) | Event::End(TagEnd::TableRow) => {
+                let trimmed = prose.trim_end_matches('\t').len();
+                prose.truncate(trimmed);
+                prose.push('\n');
+            }
+            _ => {}
+        }
+    }
+    flush(&mut prose, &mut output);
+    output.trim().to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutlineHeading {
+    pub text: String,
+    pub level: usize,
+    /// 1-based line the heading starts on
+    pub line: usize,
+    /// Anchor id in GitHub style, unique within the note
+    pub anchor: String,
+}
+
+/// Headings of a markdown note in document order, parsed the way
+/// `render_note` parses them. Other notes have no outline.
+pub fn note_outline(filename: &str, content: &str) -> Vec<OutlineHeading> {
+    if !is_markdown_note(filename) {
+        return Vec::new();
+    }
+
+    let mut outline = Vec::new();
+    let mut anchors: HashMap<String, usize> = HashMap::new();
+    let mut current: Option<(usize, usize, String)> = None;
+    for (event, range) in Parser::new_ext(content, markdown_options()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                let line = content[..range.start].matches('\n').count() + 1;
+                current = Some((level as usize, line, String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, _, heading)) = current.as_mut() {
+                    heading.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((level, line, text)) = current.take() {
+                    let text = text.trim().to_string();
+                    outline.push(OutlineHeading {
+                        anchor: unique_anchor(&text, &mut anchors),
+                        text,
+                        level,
+                        line,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    outline
+}
+
+/// Lowercase letters, digits, `-` and `_`, with spaces as `-`. Repeats get
+/// `-1`, `-2`, ... appended, and headings with no such characters are
+/// `section`.
+fn unique_anchor(text: &str, seen: &mut HashMap<String, usize>) -> String {
+    let mut anchor: String = text
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .flat_map(char::to_lowercase)
+        .collect();
+    if anchor.is_empty() {
+        anchor = "section".to_string();
+    }
+
+    let count = seen.entry(anchor.clone()).or_insert(0);
+    let unique = if *count == 0 {
+        anchor
+    } else {
+        format!("{}-{}", anchor, count)
+    };
+    *count += 1;
+    unique
+}
@@ -0,0 +1,80 @@
+use crate::utilities::frontmatter::strip_frontmatter;
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+/// Longest section title kept in a filename
+const MAX_TITLE_CHARS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteSection {
+    /// Plain text of the heading
+    pub title: String,
+    /// Markdown from the heading up to the next heading of the same or a
+    /// higher level
+    pub content: String,
+}
+
+/// Sections of a note starting at each heading of `level` (1 for `#`). Text
+/// before the first such heading, and under higher-level headings between
+/// sections, belongs to no section. Front-matter is ignored.
+pub fn split_sections(content: &str, level: usize) -> Vec<NoteSection> {
+    let body = strip_frontmatter(content);
+    // (start offset, title), `None` title for headings that only end a section
+    let mut boundaries: Vec<(usize, Option<String>)> = Vec::new();
+    let mut heading: Option<(usize, String)> = None;
+
+    for (event, range) in Parser::new(body).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level: found, .. }) if found as usize <= level => {
+                if found as usize == level {
+                    heading = Some((range.start, String::new()));
+                } else {
+                    boundaries.push((range.start, None));
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, title)) = heading.as_mut() {
+                    title.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((start, title)) = heading.take() {
+                    boundaries.push((start, Some(title)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    boundaries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (start, title))| {
+            let title = title.as_ref()?;
+            let end = boundaries.get(i + 1).map_or(body.len(), |next| next.0);
+            Some(NoteSection {
+                title: title.trim().to_string(),
+                content: format!("{}\n", body[*start..end].trim_end()),
+            })
+        })
+        .collect()
+}
+
+/// Filename stem for a section titled `title`: characters that can't appear
+/// in filenames are dropped and whitespace collapsed. `None` if nothing's left.
+pub fn section_file_stem(title: &str) -> Option<String> {
+    let cleaned: String = title
+        .chars()
+        .filter(|c| !matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+        .filter(|c| !c.is_control())
+        .collect();
+    let stem: String = cleaned
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_start_matches('.')
+        .chars()
+        .take(MAX_TITLE_CHARS)
+        .collect();
+    let stem = stem.trim_end().to_string();
+    (!stem.is_empty()).then_some(stem)
+}
@@ -170,3 +170,80 @@ pub fn get_temp_dir() -> AppResult<PathBuf> {
         .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
         .map(|path| path.join("symiosis").join("temp"))
 }
+
+/// Text of saves interrupted by a crash, kept until the user deals with it.
+pub fn get_recovery_dir() -> AppResult<PathBuf> {
+    get_data_dir()
+        .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
+        .map(|path| path.join("symiosis").join("recovered"))
+}
+
+pub fn get_dictionary_path() -> AppResult<PathBuf> {
+    get_data_dir()
+        .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
+        .map(|path| path.join("symiosis").join("dictionary.txt"))
+}
+
+pub fn get_vault_dictionary_path(notes_dir: &std::path::Path) -> AppResult<PathBuf> {
+    let encoded_path = encode_path_for_backup(notes_dir);
+    get_data_dir()
+        .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
+        .map(|path| {
+            path.join("symiosis")
+                .join("dictionaries")
+                .join(format!("{}.txt", encoded_path))
+        })
+}
+
+pub fn get_path_grants_path() -> AppResult<PathBuf> {
+    get_data_dir()
+        .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
+        .map(|path| path.join("symiosis").join("path_grants.json"))
+}
+
+pub fn get_snippets_path() -> AppResult<PathBuf> {
+    get_data_dir()
+        .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
+        .map(|path| path.join("symiosis").join("snippets.json"))
+}
+
+pub fn get_export_templates_dir() -> AppResult<PathBuf> {
+    get_data_dir()
+        .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
+        .map(|path| path.join("symiosis").join("export_templates"))
+}
+
+pub fn get_crdt_dir_for_notes_dir(notes_dir: &std::path::Path) -> AppResult<PathBuf> {
+    let encoded_path = encode_path_for_backup(notes_dir);
+    get_data_dir()
+        .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
+        .map(|path| path.join("symiosis").join("crdt").join(encoded_path))
+}
+
+pub fn get_external_changes_path(notes_dir: &std::path::Path) -> AppResult<PathBuf> {
+    let encoded_path = encode_path_for_backup(notes_dir);
+    get_data_dir()
+        .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
+        .map(|path| {
+            path.join("symiosis")
+                .join("external_changes")
+                .join(format!("{}.json", encoded_path))
+        })
+}
+
+pub fn get_lan_sync_dir() -> AppResult<PathBuf> {
+    get_data_dir()
+        .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
+        .map(|path| path.join("symiosis").join("lan"))
+}
+
+pub fn get_lan_vault_dir(notes_dir: &std::path::Path) -> AppResult<PathBuf> {
+    let encoded_path = encode_path_for_backup(notes_dir);
+    get_lan_sync_dir().map(|path| path.join(encoded_path))
+}
+
+pub fn get_reference_cache_path() -> AppResult<PathBuf> {
+    get_data_dir()
+        .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
+        .map(|path| path.join("symiosis").join("references.json"))
+}
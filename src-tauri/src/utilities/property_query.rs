@@ -0,0 +1,313 @@
+use crate::core::{AppError, AppResult};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// Longest filter accepted, to keep parsing cheap
+const MAX_FILTER_CHARS: usize = 2000;
+/// Deepest nesting of parentheses and `NOT`
+const MAX_DEPTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+/// A parsed `query_notes_by_properties` filter, e.g.
+/// `status = "active" AND (due < 2025-01-01 OR NOT priority)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyFilter {
+    And(Vec<PropertyFilter>),
+    Or(Vec<PropertyFilter>),
+    Not(Box<PropertyFilter>),
+    /// A bare field name: the note sets it to something non-empty
+    Exists(String),
+    Compare {
+        field: String,
+        op: CompareOp,
+        value: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: String,
+    pub descending: bool,
+}
+
+impl PropertyFilter {
+    /// Whether a note with these values (field to list items, fields
+    /// lowercased) matches. A comparison is true when any item satisfies it,
+    /// except `!=`, which needs every item to differ; fields a note doesn't
+    /// set only satisfy `!=`.
+    pub fn matches(&self, values: &BTreeMap<String, Vec<String>>) -> bool {
+        match self {
+            PropertyFilter::And(filters) => filters.iter().all(|f| f.matches(values)),
+            PropertyFilter::Or(filters) => filters.iter().any(|f| f.matches(values)),
+            PropertyFilter::Not(filter) => !filter.matches(values),
+            PropertyFilter::Exists(field) => values
+                .get(field)
+                .is_some_and(|items| items.iter().any(|item| !item.is_empty())),
+            PropertyFilter::Compare { field, op, value } => {
+                let items = values.get(field).map(Vec::as_slice).unwrap_or_default();
+                if *op == CompareOp::NotEq {
+                    return items
+                        .iter()
+                        .all(|item| compare_property_values(item, value) != Ordering::Equal);
+                }
+                items.iter().any(|item| {
+                    let ordering = compare_property_values(item, value);
+                    match op {
+                        CompareOp::Eq => ordering == Ordering::Equal,
+                        CompareOp::Lt => ordering == Ordering::Less,
+                        CompareOp::LtEq => ordering != Ordering::Greater,
+                        CompareOp::Gt => ordering == Ordering::Greater,
+                        CompareOp::GtEq => ordering != Ordering::Less,
+                        CompareOp::NotEq => unreachable!(),
+                    }
+                })
+            }
+        }
+    }
+}
+
+/// Numbers compare numerically and anything else case-insensitively, which
+/// orders ISO dates (`2025-01-01`) chronologically.
+pub fn compare_property_values(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.trim().to_lowercase().cmp(&b.trim().to_lowercase()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Op(CompareOp),
+    /// Quoted text, never a keyword
+    Quoted(String),
+    Word(String),
+}
+
+/// Parses a filter; an empty one matches every note (`None`).
+pub fn parse_property_filter(filter: &str) -> AppResult<Option<PropertyFilter>> {
+    if filter.chars().count() > MAX_FILTER_CHARS {
+        return Err(query_error(&format!(
+            "Filter is longer than {} characters",
+            MAX_FILTER_CHARS
+        )));
+    }
+    let tokens = tokenize(filter)?;
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+    let mut parser = Parser { tokens, pos: 0 };
+    let parsed = parser.or_expr(0)?;
+    match parser.tokens.get(parser.pos) {
+        None => Ok(Some(parsed)),
+        Some(token) => Err(query_error(&format!("Unexpected {}", describe(token)))),
+    }
+}
+
+/// Parses `field [ASC|DESC], ...`; an empty sort keeps the default order.
+pub fn parse_property_sort(sort: &str) -> AppResult<Vec<SortKey>> {
+    sort.split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(|key| {
+            let mut words = key.split_whitespace();
+            let field = words.next().unwrap_or_default().to_lowercase();
+            let descending = match words.next() {
+                None => false,
+                Some(word) if word.eq_ignore_ascii_case("asc") => false,
+                Some(word) if word.eq_ignore_ascii_case("desc") => true,
+                Some(word) => {
+                    return Err(query_error(&format!(
+                        "Expected ASC or DESC after '{}', found '{}'",
+                        field, word
+                    )))
+                }
+            };
+            if let Some(extra) = words.next() {
+                return Err(query_error(&format!("Unexpected '{}' in sort", extra)));
+            }
+            Ok(SortKey { field, descending })
+        })
+        .collect()
+}
+
+fn query_error(message: &str) -> AppError {
+    AppError::SearchQuery(format!("Invalid property query: {}", message))
+}
+
+fn tokenize(filter: &str) -> AppResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = filter.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => text.extend(chars.next()),
+                        Some(next) if next == c => break,
+                        Some(next) => text.push(next),
+                        None => return Err(query_error("Unterminated quoted value")),
+                    }
+                }
+                tokens.push(Token::Quoted(text));
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let or_equal = chars.next_if_eq(&'=').is_some();
+                let op = match (c, or_equal) {
+                    ('=', _) => CompareOp::Eq,
+                    ('!', true) => CompareOp::NotEq,
+                    ('<', false) => CompareOp::Lt,
+                    ('<', true) => CompareOp::LtEq,
+                    ('>', false) => CompareOp::Gt,
+                    ('>', true) => CompareOp::GtEq,
+                    _ => return Err(query_error("Expected '!=' after '!'")),
+                };
+                tokens.push(Token::Op(op));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || "()\"'=!<>".contains(next) {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Open => "'('".to_string(),
+        Token::Close => "')'".to_string(),
+        Token::Op(_) => "comparison".to_string(),
+        Token::Quoted(text) => format!("\"{}\"", text),
+        Token::Word(word) => format!("'{}'", word),
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(
+            self.tokens.get(self.pos),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword)
+        );
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn or_expr(&mut self, depth: usize) -> AppResult<PropertyFilter> {
+        let mut filters = vec![self.and_expr(depth)?];
+        while self.keyword("or") {
+            filters.push(self.and_expr(depth)?);
+        }
+        Ok(if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            PropertyFilter::Or(filters)
+        })
+    }
+
+    fn and_expr(&mut self, depth: usize) -> AppResult<PropertyFilter> {
+        let mut filters = vec![self.unary(depth)?];
+        while self.keyword("and") {
+            filters.push(self.unary(depth)?);
+        }
+        Ok(if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            PropertyFilter::And(filters)
+        })
+    }
+
+    fn unary(&mut self, depth: usize) -> AppResult<PropertyFilter> {
+        if depth > MAX_DEPTH {
+            return Err(query_error("Filter is nested too deeply"));
+        }
+        if self.keyword("not") {
+            return Ok(PropertyFilter::Not(Box::new(self.unary(depth + 1)?)));
+        }
+        if self.tokens.get(self.pos) == Some(&Token::Open) {
+            self.pos += 1;
+            let inner = self.or_expr(depth + 1)?;
+            if self.tokens.get(self.pos) != Some(&Token::Close) {
+                return Err(query_error("Missing ')'"));
+            }
+            self.pos += 1;
+            return Ok(inner);
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> AppResult<PropertyFilter> {
+        let field = match self.tokens.get(self.pos) {
+            Some(Token::Word(word))
+                if !["and", "or", "not"]
+                    .iter()
+                    .any(|keyword| word.eq_ignore_ascii_case(keyword)) =>
+            {
+                word.to_lowercase()
+            }
+            Some(Token::Quoted(text)) => text.trim().to_lowercase(),
+            Some(token) => {
+                return Err(query_error(&format!(
+                    "Expected a field name, found {}",
+                    describe(token)
+                )))
+            }
+            None => return Err(query_error("Expected a field name")),
+        };
+        self.pos += 1;
+
+        let Some(Token::Op(op)) = self.tokens.get(self.pos).cloned() else {
+            return Ok(PropertyFilter::Exists(field));
+        };
+        self.pos += 1;
+        let value = match self.tokens.get(self.pos) {
+            Some(Token::Word(value)) | Some(Token::Quoted(value)) => value.clone(),
+            _ => {
+                return Err(query_error(&format!(
+                    "Expected a value to compare '{}' with",
+                    field
+                )))
+            }
+        };
+        self.pos += 1;
+        Ok(PropertyFilter::Compare { field, op, value })
+    }
+}
@@ -0,0 +1,250 @@
+use crate::core::{AppError, AppResult};
+use crate::logging::log;
+use crate::utilities::citations::{
+    active_bibliography, format_reference, parse_csl_json, BibEntry, Bibliography,
+};
+use crate::utilities::file_safety::write_app_data_file;
+use crate::utilities::paths::get_reference_cache_path;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+static LINK_HREF_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r#"<a href="([^"]*)""#));
+
+/// `10.<registrant>/<suffix>`, matched against the lowercased DOI
+static DOI_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"^10\.\d{4,9}/\S+$"));
+
+/// Parsed reference cache and the file modification time it was read at
+type LoadedReferences = (Option<SystemTime>, Arc<Bibliography>);
+
+static REFERENCE_CACHE: Lazy<RwLock<Option<LoadedReferences>>> = Lazy::new(|| RwLock::new(None));
+
+static REFERENCE_CACHE_WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReferenceLink {
+    /// Normalized DOI: lowercase, since DOIs are case-insensitive, without a
+    /// `doi:` or doi.org prefix
+    Doi(String),
+    /// Citation key from a Better BibTeX `zotero://select/items/@key` link,
+    /// looked up in the `[citations]` bibliography
+    Zotero(String),
+}
+
+/// Accepts `10.1000/xyz`, `doi:10.1000/xyz` and doi.org URLs.
+pub fn normalize_doi(doi: &str) -> Option<String> {
+    let doi = doi.trim();
+    let lower = doi.to_lowercase();
+    let stripped = [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+        "doi:",
+    ]
+    .iter()
+    .find_map(|prefix| lower.strip_prefix(prefix))
+    .unwrap_or(&lower)
+    .trim();
+
+    DOI_REGEX
+        .as_ref()
+        .ok()
+        .filter(|regex| regex.is_match(stripped))
+        .map(|_| stripped.to_string())
+}
+
+pub fn parse_reference_link(href: &str) -> Option<ReferenceLink> {
+    if let Some(key) = href.strip_prefix("zotero://select/items/@") {
+        let key = key.trim_end_matches('/');
+        return (!key.is_empty()).then(|| ReferenceLink::Zotero(key.to_string()));
+    }
+    let lower = href.to_lowercase();
+    if lower.starts_with("doi:") || lower.contains("doi.org/") {
+        return normalize_doi(href).map(ReferenceLink::Doi);
+    }
+    None
+}
+
+/// Cached CSL JSON items, keyed by DOI in their `id`. A missing file is an
+/// empty cache.
+pub fn load_reference_cache(cache_path: &Path) -> AppResult<Vec<serde_json::Value>> {
+    let content = match fs::read_to_string(cache_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(AppError::FileRead(format!(
+                "Failed to read reference cache: {}",
+                e
+            )))
+        }
+    };
+    serde_json::from_str(&content)
+        .map_err(|e| AppError::FileRead(format!("Invalid reference cache: {}", e)))
+}
+
+/// Adds or replaces the CSL JSON `item` for `doi`. The cache stays a valid
+/// CSL JSON file, so it can also be used as a `[citations]` bibliography.
+pub fn cache_reference(
+    cache_path: &Path,
+    doi: &str,
+    mut item: serde_json::Value,
+) -> AppResult<BibEntry> {
+    let object = item
+        .as_object_mut()
+        .ok_or_else(|| AppError::FileRead("Reference metadata is not an object".to_string()))?;
+    object.insert("id".to_string(), serde_json::Value::from(doi));
+
+    let entry = parse_csl_json(&serde_json::Value::Array(vec![item.clone()]).to_string())?
+        .get(doi)
+        .cloned()
+        .ok_or_else(|| AppError::FileRead(format!("No usable metadata for {}", doi)))?;
+
+    let _guard = REFERENCE_CACHE_WRITE_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut items = load_reference_cache(cache_path)?;
+    items.retain(|existing| existing.get("id").and_then(|id| id.as_str()) != Some(doi));
+    items.push(item);
+    let content = serde_json::to_string_pretty(&items)
+        .map_err(|e| AppError::FileWrite(format!("Failed to serialize references: {}", e)))?;
+    write_app_data_file(cache_path, content)?;
+    Ok(entry)
+}
+
+/// The reference cache as a bibliography, re-read when the file changes.
+fn cached_references() -> Option<Arc<Bibliography>> {
+    let path = get_reference_cache_path().ok()?;
+    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+    {
+        let cache = REFERENCE_CACHE.read().unwrap_or_else(|e| e.into_inner());
+        if let Some((loaded_at, references)) = cache.as_ref() {
+            if *loaded_at == modified {
+                return Some(references.clone());
+            }
+        }
+    }
+
+    let references = load_reference_cache(&path)
+        .and_then(|items| parse_csl_json(&serde_json::Value::Array(items).to_string()))
+        .unwrap_or_else(|e| {
+            log(
+                "CITATIONS",
+                "Failed to load reference cache",
+                Some(&e.to_string()),
+            );
+            Bibliography::default()
+        });
+    let references = Arc::new(references);
+    *REFERENCE_CACHE.write().unwrap_or_else(|e| e.into_inner()) =
+        Some((modified, references.clone()));
+    Some(references)
+}
+
+/// Metadata for `link` that is available without a network request.
+pub fn lookup_local_reference(link: &ReferenceLink) -> Option<BibEntry> {
+    match link {
+        ReferenceLink::Doi(doi) => cached_references()?.get(doi).cloned(),
+        ReferenceLink::Zotero(key) => active_bibliography()?.0.get(key).cloned(),
+    }
+}
+
+/// Adds the formatted reference as a `title` tooltip to rendered DOI and
+/// Zotero links whose metadata is known, and points `doi:` links at doi.org.
+pub fn annotate_reference_links(
+    html: &str,
+    lookup: impl Fn(&ReferenceLink) -> Option<BibEntry>,
+) -> Option<String> {
+    if !html.contains("doi") && !html.contains("zotero://") {
+        return None;
+    }
+    let href_regex = LINK_HREF_REGEX.as_ref().ok()?;
+
+    let mut changed = false;
+    let annotated = href_regex.replace_all(html, |caps: &regex::Captures| {
+        let whole = caps.get(0).map(|m| m.as_str()).unwrap_or_default();
+        let href = html_escape::decode_html_entities(caps.get(1).map_or("", |m| m.as_str()));
+        let Some(link) = parse_reference_link(&href) else {
+            return whole.to_string();
+        };
+
+        changed = true;
+        let href = match &link {
+            ReferenceLink::Doi(doi) => format!("https://doi.org/{}", doi),
+            ReferenceLink::Zotero(_) => href.to_string(),
+        };
+        let mut tag = format!(
+            r#"<a class="reference-link" href="{}""#,
+            html_escape::encode_double_quoted_attribute(&href)
+        );
+        if let Some(entry) = lookup(&link) {
+            tag.push_str(&format!(
+                r#" title="{}""#,
+                html_escape::encode_double_quoted_attribute(&format_reference(&entry))
+            ));
+        }
+        tag
+    });
+
+    changed.then(|| annotated.into_owned())
+}
+
+/// `annotate_reference_links` against the local caches, for the renderer.
+pub fn render_reference_links(html: &str) -> Option<String> {
+    annotate_reference_links(html, lookup_local_reference)
+}
+
+/// Adds `- entry` at the end of the `heading` section, creating the section at
+/// the end of the note when it is missing. Unchanged if the entry is present.
+pub fn append_reference_entry(content: &str, heading: &str, entry: &str) -> String {
+    let item = format!("- {}", entry);
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.iter().any(|line| line.trim() == item) {
+        return content.to_string();
+    }
+
+    let heading_level = |line: &str| {
+        let hashes = line.chars().take_while(|c| *c == '#').count();
+        ((1..=6).contains(&hashes) && line[hashes..].starts_with(' ')).then_some(hashes)
+    };
+    let section = lines.iter().enumerate().find_map(|(index, line)| {
+        let level = heading_level(line)?;
+        (line[level..].trim().eq_ignore_ascii_case(heading.trim())).then_some((index, level))
+    });
+
+    let Some((start, level)) = section else {
+        let mut output = content.trim_end().to_string();
+        if !output.is_empty() {
+            output.push_str("\n\n");
+        }
+        output.push_str(&format!("## {}\n\n{}\n", heading.trim(), item));
+        return output;
+    };
+
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| heading_level(line).is_some_and(|l| l <= level))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+    let insert_at = (start + 1..end)
+        .rev()
+        .find(|&index| !lines[index].trim().is_empty())
+        .map_or(start + 1, |index| index + 1);
+
+    let mut output: Vec<&str> = lines[..insert_at].to_vec();
+    if insert_at == start + 1 {
+        output.push("");
+    }
+    output.push(&item);
+    if insert_at < lines.len() && !lines[insert_at].trim().is_empty() {
+        output.push("");
+    }
+    output.extend_from_slice(&lines[insert_at..]);
+    let mut output = output.join("\n");
+    output.push('\n');
+    output
+}
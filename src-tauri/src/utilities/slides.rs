@@ -0,0 +1,81 @@
+use crate::utilities::{frontmatter::strip_frontmatter, note_renderer::render_note};
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag};
+
+/// reveal.js release the exported decks load, pinned so decks keep working
+const REVEAL_VERSION: &str = "5.1.0";
+const REVEAL_CDN: &str = "https://cdn.jsdelivr.net/npm/reveal.js";
+
+/// Markdown of each slide: a new slide starts at every `---` thematic break
+/// (which is dropped) and every level-2 heading. Front matter and empty slides
+/// are left out.
+pub fn split_slides(content: &str) -> Vec<String> {
+    let body = strip_frontmatter(content);
+    // (start, end) of each slide's text
+    let mut slides: Vec<(usize, usize)> = Vec::new();
+    let mut start = 0;
+
+    for (event, range) in Parser::new(body).into_offset_iter() {
+        match event {
+            Event::Rule => {
+                slides.push((start, range.start));
+                start = range.end;
+            }
+            Event::Start(Tag::Heading {
+                level: HeadingLevel::H2,
+                ..
+            }) => {
+                slides.push((start, range.start));
+                start = range.start;
+            }
+            _ => {}
+        }
+    }
+    slides.push((start, body.len()));
+
+    slides
+        .into_iter()
+        .map(|(start, end)| body[start..end].trim())
+        .filter(|slide| !slide.is_empty())
+        .map(|slide| format!("{}\n", slide))
+        .collect()
+}
+
+/// A single HTML file presenting `content` as a reveal.js deck, each slide
+/// rendered like the note itself. reveal.js is loaded from jsDelivr.
+pub fn slides_html(note_name: &str, title: &str, content: &str) -> String {
+    let sections: String = split_slides(content)
+        .iter()
+        .map(|slide| format!("<section>\n{}</section>\n", render_note(note_name, slide)))
+        .collect();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<link rel="stylesheet" href="{cdn}@{version}/dist/reveal.css">
+<link rel="stylesheet" href="{cdn}@{version}/dist/theme/white.css">
+<style>
+.reveal section {{ text-align: left; }}
+.reveal h1, .reveal h2 {{ text-align: center; }}
+.reveal img {{ max-height: 60vh; }}
+</style>
+</head>
+<body>
+<div class="reveal">
+<div class="slides">
+{sections}</div>
+</div>
+<script src="{cdn}@{version}/dist/reveal.js"></script>
+<script>Reveal.initialize({{ hash: true }});</script>
+</body>
+</html>
+"#,
+        title = html_escape::encode_text(title),
+        cdn = REVEAL_CDN,
+        version = REVEAL_VERSION,
+        sections = sections
+    )
+}
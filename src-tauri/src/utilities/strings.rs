@@ -18,6 +18,31 @@ pub fn extract_title_from_content(content: &str) -> Option<String> {
         .filter(|title| !title.is_empty())
 }
 
+/// Longest clipboard text the clipboard search shortcut looks up
+pub const MAX_CLIPBOARD_QUERY_CHARS: usize = 200;
+
+/// Copied text as a search query: whitespace runs become single spaces and
+/// long text is cut after the last whole word within
+/// `MAX_CLIPBOARD_QUERY_CHARS`. `None` when there's nothing to search.
+pub fn clipboard_search_query(text: &str) -> Option<String> {
+    let mut query = String::new();
+    for word in text.split_whitespace() {
+        let separator = usize::from(!query.is_empty());
+        let length = query.chars().count() + separator + word.chars().count();
+        if length > MAX_CLIPBOARD_QUERY_CHARS {
+            if query.is_empty() {
+                query = word.chars().take(MAX_CLIPBOARD_QUERY_CHARS).collect();
+            }
+            break;
+        }
+        if separator == 1 {
+            query.push(' ');
+        }
+        query.push_str(word);
+    }
+    (!query.is_empty()).then_some(query)
+}
+
 pub fn sanitize_fts_query(query: &str) -> String {
     // First pass: remove dangerous characters and special syntax
     let cleaned_chars: String = query
@@ -47,6 +72,16 @@ pub fn sanitize_fts_query(query: &str) -> String {
     filtered_words.join(" ").trim().to_string()
 }
 
+/// Lowercases and strips diacritics, so `Café` and `cafe` compare equal.
+/// Combining marks left by decomposed (NFD) text are dropped as well.
+pub fn fold_search_text(text: &str) -> String {
+    text.chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !('\u{0300}'..='\u{036F}').contains(c))
+        .map(nucleo_matcher::chars::normalize)
+        .collect()
+}
+
 pub fn format_timestamp_for_humans(timestamp: u64) -> String {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -89,3 +124,174 @@ pub fn parse_deleted_backup_filename(filename: &str) -> Option<(String, u64)> {
     }
     None
 }
+
+/// Length of an excerpt when the caller doesn't ask for one
+pub const DEFAULT_EXCERPT_CHARS: usize = 200;
+
+/// Short plain-text summary of a note: the `summary`/`description`/`excerpt`
+/// front-matter field if present, otherwise the leading paragraphs of the body,
+/// cut at a word boundary.
+pub fn note_excerpt(content: &str, max_chars: usize) -> String {
+    use crate::utilities::frontmatter::{parse_frontmatter, strip_frontmatter};
+    use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+    if let Some(frontmatter) = parse_frontmatter(content) {
+        let explicit = ["summary", "description", "excerpt"]
+            .iter()
+            .find_map(|key| frontmatter.get_text(key))
+            .map(str::trim)
+            .filter(|text| !text.is_empty());
+        if let Some(text) = explicit {
+            return truncate_at_word(text, max_chars);
+        }
+    }
+
+    let mut text = String::new();
+    let mut in_paragraph = false;
+    for event in Parser::new(strip_frontmatter(content)) {
+        match event {
+            Event::Start(Tag::Paragraph) => in_paragraph = true,
+            Event::End(TagEnd::Paragraph) => {
+                in_paragraph = false;
+                if text.chars().count() >= max_chars {
+                    break;
+                }
+                text.push(' ');
+            }
+            Event::Text(t) | Event::Code(t) if in_paragraph => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak if in_paragraph => text.push(' '),
+            _ => {}
+        }
+    }
+
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    truncate_at_word(&collapsed, max_chars)
+}
+
+/// Words in the note body, ignoring front-matter and bare markup such as `#` or `-`.
+pub fn word_count(content: &str) -> usize {
+    crate::utilities::frontmatter::strip_frontmatter(content)
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+/// Name of the `n`th copy of a note, next to it: `Plan copy.md`, then
+/// `Plan copy 2.md` and so on.
+pub fn copy_note_name(note_name: &str, n: usize) -> String {
+    let suffix = if n <= 1 {
+        " copy".to_string()
+    } else {
+        format!(" copy {}", n)
+    };
+    let file_start = note_name.rfind('/').map_or(0, |i| i + 1);
+    match note_name[file_start..].rfind('.') {
+        Some(dot) if dot > 0 => {
+            let dot = file_start + dot;
+            format!("{}{}{}", &note_name[..dot], suffix, &note_name[dot..])
+        }
+        _ => format!("{}{}", note_name, suffix),
+    }
+}
+
+/// Name of the `n`th untitled note: `Untitled.md`, then `Untitled 2.md`.
+pub fn untitled_note_name(n: usize) -> String {
+    if n <= 1 {
+        "Untitled.md".to_string()
+    } else {
+        format!("Untitled {}.md", n)
+    }
+}
+
+/// Orders names the way people count: `note2` before `note10`. Letters
+/// compare case-insensitively and runs of digits by their value, with fewer
+/// leading zeros first on ties. Names equal by those rules fall back to
+/// plain ordering so the result is total.
+pub fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    let mut left = a.chars().peekable();
+    let mut right = b.chars().peekable();
+    loop {
+        let (l, r) = match (left.peek(), right.peek()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) => (*l, *r),
+        };
+
+        if l.is_ascii_digit() && r.is_ascii_digit() {
+            let l_digits = take_digits(&mut left);
+            let r_digits = take_digits(&mut right);
+            let l_value = l_digits.trim_start_matches('0');
+            let r_value = r_digits.trim_start_matches('0');
+            let ordering = l_value
+                .len()
+                .cmp(&r_value.len())
+                .then_with(|| l_value.cmp(r_value))
+                .then_with(|| l_digits.len().cmp(&r_digits.len()));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            continue;
+        }
+
+        let ordering = l.to_lowercase().cmp(r.to_lowercase());
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        left.next();
+        right.next();
+    }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        digits.push(c);
+    }
+    digits
+}
+
+fn truncate_at_word(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    let cut = match cut.rfind(char::is_whitespace) {
+        Some(index) if index > 0 => &cut[..index],
+        _ => cut.as_str(),
+    };
+    format!(
+        "{}\u{2026}",
+        cut.trim_end_matches(|c: char| c.is_ascii_punctuation())
+    )
+}
+
+/// Parses dates as written in front-matter: RFC 3339, `YYYY-MM-DD HH:MM[:SS]`
+/// or `YYYY-MM-DD`. Dates without an offset are treated as local time.
+pub fn parse_note_date(value: &str) -> Option<chrono::DateTime<Utc>> {
+    use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    let naive = [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|date| date.with_timezone(&Utc))
+}
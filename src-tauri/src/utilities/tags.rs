@@ -0,0 +1,188 @@
+use crate::utilities::frontmatter::{parse_frontmatter, set_frontmatter_field, FrontmatterValue};
+use crate::utilities::links::lines_outside_code_blocks;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashSet;
+
+static INLINE_TAG_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"(?:^|[\s(\[,;])#([\p{L}\p{N}_][\p{L}\p{N}_/-]*)"));
+
+static TAG_NAME_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"^[\p{L}\p{N}_][\p{L}\p{N}_/-]*$"));
+
+/// Normalizes a tag for comparison and storage: no leading `#`, lowercase,
+/// no trailing slashes.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim()
+        .trim_start_matches('#')
+        .trim_end_matches('/')
+        .to_lowercase()
+}
+
+/// Collects tags from the `tags`/`tag` front-matter field and inline `#tags`
+/// outside code blocks. Purely numeric tags (`#123`) are ignored, matching
+/// how issue references are usually written.
+pub fn extract_tags(content: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let mut push = |raw: &str| {
+        let tag = normalize_tag(raw);
+        if !tag.is_empty() && !tag.chars().all(|c| c.is_ascii_digit()) && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    };
+
+    let frontmatter = parse_frontmatter(content);
+    if let Some(frontmatter) = &frontmatter {
+        if let Some(value) = frontmatter.get("tags").or_else(|| frontmatter.get("tag")) {
+            for item in value.as_list() {
+                for tag in item.split([',', ' ']) {
+                    push(tag);
+                }
+            }
+        }
+    }
+
+    let Ok(regex) = INLINE_TAG_REGEX.as_ref() else {
+        return tags;
+    };
+    let body_start = frontmatter.map(|fm| fm.line_count).unwrap_or(0);
+    for (index, line) in lines_outside_code_blocks(content) {
+        if index < body_start {
+            continue;
+        }
+        for caps in regex.captures_iter(line) {
+            if let Some(tag) = caps.get(1) {
+                push(tag.as_str());
+            }
+        }
+    }
+
+    tags
+}
+
+/// The normalized `tag` preceded by each of its ancestors: `project/alpha`
+/// gives `project` and `project/alpha`.
+pub fn tag_with_ancestors(tag: &str) -> Vec<String> {
+    let mut tags: Vec<String> = tag
+        .char_indices()
+        .filter(|(_, c)| *c == '/')
+        .map(|(index, _)| &tag[..index])
+        .filter(|parent| !parent.is_empty() && !parent.ends_with('/'))
+        .map(str::to_string)
+        .collect();
+    tags.push(tag.to_string());
+    tags
+}
+
+/// Whether `tag` (without `#`) can be written inline and read back by
+/// `extract_tags`.
+pub fn is_valid_tag_name(tag: &str) -> bool {
+    !tag.chars().all(|c| c.is_ascii_digit())
+        && TAG_NAME_REGEX
+            .as_ref()
+            .is_ok_and(|regex| regex.is_match(tag))
+}
+
+/// Whether the normalized `tag` is `parent` or nested under it, as
+/// `project/alpha` is under `project`.
+pub fn tag_within(tag: &str, parent: &str) -> bool {
+    tag.strip_prefix(parent)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// `raw` with the matching source swapped for `target`, keeping nested
+/// segments as written: `Project/Alpha` from `project` to `work` is `work/Alpha`.
+fn retarget_tag(raw: &str, sources: &[String], target: &str) -> Option<String> {
+    let normalized = normalize_tag(raw);
+    if normalized.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let source = sources
+        .iter()
+        .find(|source| tag_within(&normalized, source))?;
+    let raw = raw.trim().trim_start_matches('#');
+    let nested: Vec<&str> = raw
+        .trim_end_matches('/')
+        .split('/')
+        .skip(source.split('/').count())
+        .collect();
+    let retargeted = if nested.is_empty() {
+        target.to_string()
+    } else {
+        format!("{}/{}", target, nested.join("/"))
+    };
+    (retargeted != raw).then_some(retargeted)
+}
+
+/// Rewrites every tag that is one of the normalized `sources`, or nested under
+/// one, to `target`: inline `#tags` outside code blocks and the `tags`/`tag`
+/// front-matter field, where merged duplicates are dropped. Returns the new
+/// content and the number of tags rewritten, or `None` when none matched.
+pub fn retag_content(content: &str, sources: &[String], target: &str) -> Option<(String, usize)> {
+    let mut rewritten = 0;
+    let body_start = parse_frontmatter(content).map_or(0, |fm| fm.line_count);
+    let eligible: HashSet<usize> = lines_outside_code_blocks(content)
+        .map(|(index, _)| index)
+        .filter(|index| *index >= body_start)
+        .collect();
+
+    let mut output = String::with_capacity(content.len());
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let Some(regex) = INLINE_TAG_REGEX
+            .as_ref()
+            .ok()
+            .filter(|_| eligible.contains(&index))
+        else {
+            output.push_str(line);
+            continue;
+        };
+        let replaced = regex.replace_all(line, |caps: &regex::Captures| {
+            let (Some(whole), Some(tag)) = (caps.get(0), caps.get(1)) else {
+                return String::new();
+            };
+            match retarget_tag(tag.as_str(), sources, target) {
+                Some(new_tag) => {
+                    rewritten += 1;
+                    format!("{}{}", &line[whole.start()..tag.start()], new_tag)
+                }
+                None => whole.as_str().to_string(),
+            }
+        });
+        output.push_str(&replaced);
+    }
+
+    let frontmatter_field = parse_frontmatter(&output).and_then(|fm| {
+        fm.fields
+            .into_iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("tags") || key.eq_ignore_ascii_case("tag"))
+    });
+    if let Some((key, value)) = frontmatter_field {
+        let mut tags: Vec<String> = Vec::new();
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut field_rewritten = 0;
+        for item in value.as_list() {
+            for raw in item.split([',', ' ']).filter(|raw| !raw.trim().is_empty()) {
+                let tag = match retarget_tag(raw, sources, target) {
+                    Some(new_tag) => {
+                        field_rewritten += 1;
+                        new_tag
+                    }
+                    None => raw.trim().to_string(),
+                };
+                if seen.insert(normalize_tag(&tag)) {
+                    tags.push(tag);
+                }
+            }
+        }
+        if field_rewritten > 0 {
+            rewritten += field_rewritten;
+            let value = match (value, tags.len()) {
+                (FrontmatterValue::Text(_), 1) => FrontmatterValue::Text(tags.remove(0)),
+                _ => FrontmatterValue::List(tags),
+            };
+            output = set_frontmatter_field(&output, &key, Some(&value));
+        }
+    }
+
+    (rewritten > 0).then_some((output, rewritten))
+}
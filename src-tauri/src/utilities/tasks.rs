@@ -0,0 +1,77 @@
+use crate::utilities::{
+    frontmatter::parse_frontmatter, links::lines_outside_code_blocks, mentions::first_handle,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+static TASK_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"^\s*(?:[-*+]|\d+[.)])\s+\[([ xX])\]\s+(.*)$"));
+static DUE_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"(?:📅\s*|due:\s*)(\d{4}-\d{2}-\d{2})"));
+static DONE_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"(?:✅\s*|done:\s*|@done\()(\d{4}-\d{2}-\d{2})\)?"));
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Task {
+    /// Task text with due/completion markers removed
+    pub text: String,
+    pub done: bool,
+    /// From `📅 2024-06-01` or `due:2024-06-01`
+    pub due: Option<String>,
+    /// From `✅ 2024-06-01`, `done:2024-06-01` or `@done(2024-06-01)`
+    pub completed: Option<String>,
+    /// First `@handle` in the task
+    pub owner: Option<String>,
+    /// 1-based line number
+    pub line: usize,
+}
+
+/// Parses a single Markdown checkbox line.
+pub fn parse_task_line(line: &str, line_number: usize) -> Option<Task> {
+    let caps = TASK_REGEX.as_ref().ok()?.captures(line)?;
+    let done = caps.get(1)?.as_str().eq_ignore_ascii_case("x");
+    let raw = caps.get(2)?.as_str();
+
+    let capture_date = |regex: &Lazy<Result<Regex, regex::Error>>| {
+        regex
+            .as_ref()
+            .ok()
+            .and_then(|regex| regex.captures(raw))
+            .and_then(|caps| caps.get(1))
+            .map(|date| date.as_str().to_string())
+    };
+    let due = capture_date(&DUE_REGEX);
+    let completed = capture_date(&DONE_REGEX);
+
+    let mut text = raw.to_string();
+    for regex in [&DUE_REGEX, &DONE_REGEX] {
+        if let Ok(regex) = regex.as_ref() {
+            text = regex.replace_all(&text, "").to_string();
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return None;
+    }
+
+    Some(Task {
+        owner: first_handle(&text),
+        text,
+        done,
+        due,
+        completed,
+        line: line_number,
+    })
+}
+
+/// Every checkbox task in a note body, skipping front-matter and code blocks.
+pub fn extract_tasks(content: &str) -> Vec<Task> {
+    let body_start = parse_frontmatter(content)
+        .map(|fm| fm.line_count)
+        .unwrap_or(0);
+    lines_outside_code_blocks(content)
+        .filter(|(index, _)| *index >= body_start)
+        .filter_map(|(index, line)| parse_task_line(line, index + 1))
+        .collect()
+}
@@ -0,0 +1,214 @@
+//! Decoding of note files and their line endings. Notes are read into the app
+//! as UTF-8 with LF line endings whatever they were written with, so diffs,
+//! content checks and rendering compare like with like, and saved as UTF-8
+//! with the line endings `[preferences] line_endings` asks for.
+
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Accepted `[preferences] line_endings` values
+pub const LINE_ENDING_MODES: [&str; 3] = ["preserve", "lf", "crlf"];
+pub const DEFAULT_LINE_ENDING_MODE: &str = "preserve";
+
+const MODE_PRESERVE: u8 = 0;
+const MODE_LF: u8 = 1;
+const MODE_CRLF: u8 = 2;
+
+static LINE_ENDING_MODE: AtomicU8 = AtomicU8::new(MODE_PRESERVE);
+
+/// Characters of Windows-1252 bytes 0x80-0x9F. The five bytes it leaves
+/// undefined map to the C1 controls of the same value, so every byte decodes
+/// to its own character and nothing is lost.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextEncoding {
+    Utf8,
+    /// UTF-8 starting with a byte order mark
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    /// Fallback for text that isn't valid UTF-8
+    Windows1252,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineEnding {
+    /// No line breaks at all
+    None,
+    Lf,
+    Crlf,
+    /// More than one kind, or old Mac `\r` breaks
+    Mixed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedText {
+    /// The text with LF line endings
+    pub text: String,
+    pub encoding: TextEncoding,
+    /// Line endings the text had before normalizing
+    pub line_ending: LineEnding,
+}
+
+impl DecodedText {
+    /// Why the file won't be saved back byte for byte, for encodings other
+    /// than UTF-8.
+    pub fn warning(&self) -> Option<String> {
+        match self.encoding {
+            TextEncoding::Utf8 | TextEncoding::Utf8Bom => None,
+            TextEncoding::Utf16Le | TextEncoding::Utf16Be => Some(
+                "File is UTF-16 text; saving it in the app converts it to UTF-8".to_string(),
+            ),
+            TextEncoding::Windows1252 => Some(
+                "File isn't valid UTF-8 and was read as Windows-1252; saving it in the app converts it to UTF-8"
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+/// Sets the `[preferences] line_endings` notes are saved with; unknown values
+/// preserve each note's line endings.
+pub fn configure_line_endings(mode: &str) {
+    let mode = match mode {
+        "lf" => MODE_LF,
+        "crlf" => MODE_CRLF,
+        _ => MODE_PRESERVE,
+    };
+    LINE_ENDING_MODE.store(mode, Ordering::Relaxed);
+}
+
+pub fn detect_line_ending(text: &str) -> LineEnding {
+    let bytes = text.as_bytes();
+    let (mut lf, mut crlf, mut cr) = (0, 0, 0);
+    for (i, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'\n' if i > 0 && bytes[i - 1] == b'\r' => crlf += 1,
+            b'\n' => lf += 1,
+            b'\r' if bytes.get(i + 1) != Some(&b'\n') => cr += 1,
+            _ => {}
+        }
+    }
+    match (lf, crlf, cr) {
+        (0, 0, 0) => LineEnding::None,
+        (_, 0, 0) => LineEnding::Lf,
+        (0, _, 0) => LineEnding::Crlf,
+        _ => LineEnding::Mixed,
+    }
+}
+
+/// `text` with CRLF and lone CR line breaks turned into LF
+pub fn normalize_line_endings(text: &str) -> Cow<'_, str> {
+    if !text.contains('\r') {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n"))
+}
+
+fn decode_windows_1252(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| match byte {
+            0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+            _ => char::from(byte),
+        })
+        .collect()
+}
+
+fn decode_utf16(bytes: &[u8], little_endian: bool) -> Option<String> {
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            if little_endian {
+                u16::from_le_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_be_bytes([pair[0], pair[1]])
+            }
+        })
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+/// Decodes a note file's bytes: UTF-8 (with or without a byte order mark),
+/// UTF-16 with a byte order mark, or else Windows-1252. `None` for binary
+/// files.
+pub fn decode_text(bytes: &[u8]) -> Option<DecodedText> {
+    let (text, encoding) = if let Some(rest) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        match std::str::from_utf8(rest) {
+            Ok(text) => (text.to_string(), TextEncoding::Utf8Bom),
+            Err(_) => (decode_windows_1252(rest), TextEncoding::Windows1252),
+        }
+    } else if let Some(rest) = bytes.strip_prefix(b"\xFF\xFE") {
+        (decode_utf16(rest, true)?, TextEncoding::Utf16Le)
+    } else if let Some(rest) = bytes.strip_prefix(b"\xFE\xFF") {
+        (decode_utf16(rest, false)?, TextEncoding::Utf16Be)
+    } else if bytes.contains(&0) {
+        return None;
+    } else {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => (text.to_string(), TextEncoding::Utf8),
+            Err(_) => (decode_windows_1252(bytes), TextEncoding::Windows1252),
+        }
+    };
+    if text.contains('\0') {
+        return None;
+    }
+
+    let line_ending = detect_line_ending(&text);
+    let text = if line_ending == LineEnding::Lf || line_ending == LineEnding::None {
+        text
+    } else {
+        normalize_line_endings(&text).into_owned()
+    };
+    Some(DecodedText {
+        text,
+        encoding,
+        line_ending,
+    })
+}
+
+/// Reads a note as the app works with it: decoded and with LF line endings.
+/// Binary files fail with `InvalidData`.
+pub fn read_note_text(path: &Path) -> io::Result<String> {
+    decode_text(&fs::read(path)?)
+        .map(|decoded| decoded.text)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "File is not text"))
+}
+
+/// `content` as it should be written to `note_path`: with the configured line
+/// endings, or, when preserving them, CRLF only if the note on disk uses CRLF.
+pub fn encode_note_text<'a>(content: &'a str, note_path: &Path) -> Cow<'a, str> {
+    let crlf = match LINE_ENDING_MODE.load(Ordering::Relaxed) {
+        MODE_LF => false,
+        MODE_CRLF => true,
+        _ => fs::read(note_path)
+            .ok()
+            .and_then(|bytes| decode_text(&bytes))
+            .is_some_and(|decoded| decoded.line_ending == LineEnding::Crlf),
+    };
+    with_line_endings(content, crlf)
+}
+
+/// `content` with every line break turned into CRLF, or into LF
+pub fn with_line_endings(content: &str, crlf: bool) -> Cow<'_, str> {
+    let normalized = normalize_line_endings(content);
+    if !crlf {
+        return normalized;
+    }
+    Cow::Owned(normalized.replace('\n', "\r\n"))
+}
@@ -0,0 +1,75 @@
+use crate::utilities::{
+    frontmatter::parse_frontmatter, links::lines_outside_code_blocks, tags::normalize_tag,
+};
+use chrono::{NaiveDate, NaiveTime};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+static TIME_ENTRY_REGEX: Lazy<Result<Regex, regex::Error>> = Lazy::new(|| {
+    Regex::new(r"⏱\x{FE0F}?\s*(\d{4}-\d{2}-\d{2})\s+(\d{1,2}:\d{2})\s*[-–]\s*(\d{1,2}:\d{2})(.*)$")
+});
+static ENTRY_TAG_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"(?:^|\s)#([\p{L}\p{N}_][\p{L}\p{N}_/-]*)"));
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimeEntry {
+    pub date: String,
+    pub start: String,
+    pub end: String,
+    /// Entries ending before they start are taken to run past midnight
+    pub minutes: i64,
+    pub tags: Vec<String>,
+    pub description: String,
+    /// 1-based line number
+    pub line: usize,
+}
+
+/// Parses `⏱ 2024-06-01 09:00-10:30 #projectX optional description` lines.
+pub fn parse_time_entry(line: &str, line_number: usize) -> Option<TimeEntry> {
+    let caps = TIME_ENTRY_REGEX.as_ref().ok()?.captures(line)?;
+    let date = NaiveDate::parse_from_str(caps.get(1)?.as_str(), "%Y-%m-%d").ok()?;
+    let start = NaiveTime::parse_from_str(caps.get(2)?.as_str(), "%H:%M").ok()?;
+    let end = NaiveTime::parse_from_str(caps.get(3)?.as_str(), "%H:%M").ok()?;
+    let rest = caps.get(4).map_or("", |m| m.as_str());
+
+    let mut minutes = (end - start).num_minutes();
+    if minutes < 0 {
+        minutes += 24 * 60;
+    }
+
+    let mut tags = Vec::new();
+    let mut description = rest.to_string();
+    if let Ok(regex) = ENTRY_TAG_REGEX.as_ref() {
+        for caps in regex.captures_iter(rest) {
+            if let Some(tag) = caps.get(1) {
+                let tag = normalize_tag(tag.as_str());
+                if !tag.is_empty() && !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+        }
+        description = regex.replace_all(rest, " ").to_string();
+    }
+
+    Some(TimeEntry {
+        date: date.to_string(),
+        start: start.format("%H:%M").to_string(),
+        end: end.format("%H:%M").to_string(),
+        minutes,
+        tags,
+        description: description.split_whitespace().collect::<Vec<_>>().join(" "),
+        line: line_number,
+    })
+}
+
+/// Every time entry in a note body, skipping front-matter and code blocks.
+pub fn extract_time_entries(content: &str) -> Vec<TimeEntry> {
+    let body_start = parse_frontmatter(content)
+        .map(|fm| fm.line_count)
+        .unwrap_or(0);
+    lines_outside_code_blocks(content)
+        .filter(|(index, _)| *index >= body_start)
+        .filter_map(|(index, line)| parse_time_entry(line, index + 1))
+        .collect()
+}
@@ -1,9 +1,20 @@
 use crate::config::{
     get_available_markdown_themes, get_available_ui_themes, parse_shortcut, AppConfig,
-    EditorConfig, GeneralConfig, InterfaceConfig, PreferencesConfig, ShortcutsConfig,
+    AutomationConfig, CaptureConfig, CitationsConfig, EditorConfig, EmailConfig, GeneralConfig,
+    GeoConfig, HabitsConfig, InterfaceConfig, LintConfig, MetadataConfig, PreferencesConfig,
+    RemindersConfig, ShortcutsConfig, SyncConfig, TagRule, TemplatesConfig, VaultConfig,
+    WebhooksConfig,
 };
 use crate::core::{AppError, AppResult};
 use crate::logging::log;
+use crate::services::folder_service::normalize_folder_name;
+use crate::services::reminder_service::parse_reminder_time;
+use crate::utilities::config_helpers::{
+    is_https_url, EXPIRED_NOTE_ACTIONS, FRONTMATTER_FIELD_TYPES, FTS_TOKENIZERS, GEOCODERS,
+    MAX_DB_TIMEOUT_MS, MAX_VAULT_NAME_CHARS, NEW_NOTE_LOCATIONS, SMTP_SECURITY_MODES,
+};
+use crate::utilities::frontmatter::FrontmatterValue;
+use crate::utilities::text_encoding::LINE_ENDING_MODES;
 
 pub fn validate_config(config: &AppConfig) -> AppResult<()> {
     validate_notes_directory(&config.notes_directory)?;
@@ -13,6 +24,19 @@ pub fn validate_config(config: &AppConfig) -> AppResult<()> {
     validate_editor_config(&config.editor)?;
     validate_shortcuts_config(&config.shortcuts)?;
     validate_preferences_config(&config.preferences)?;
+    validate_lint_config(&config.lint)?;
+    validate_citations_config(&config.citations)?;
+    validate_sync_config(&config.sync)?;
+    validate_habits_config(&config.habits)?;
+    validate_templates_config(&config.templates)?;
+    validate_metadata_config(&config.metadata)?;
+    validate_capture_config(&config.capture)?;
+    validate_email_config(&config.email)?;
+    validate_webhooks_config(&config.webhooks)?;
+    validate_geo_config(&config.geo)?;
+    validate_automation_config(&config.automation)?;
+    validate_reminders_config(&config.reminders)?;
+    validate_vaults(&config.vaults)?;
     Ok(())
 }
 
@@ -168,6 +192,322 @@ pub fn validate_preferences_config(preferences: &PreferencesConfig) -> AppResult
             "Max search results too large (max: 10000)".to_string(),
         ));
     }
+    if preferences.trash_retention_days > 3650 {
+        return Err(AppError::ConfigLoad(
+            "Trash retention too long (max: 3650 days)".to_string(),
+        ));
+    }
+    if preferences.integrity_check_hours > 720 {
+        return Err(AppError::ConfigLoad(
+            "Integrity check interval too long (max: 720 hours)".to_string(),
+        ));
+    }
+    if preferences.db_timeout_ms > MAX_DB_TIMEOUT_MS as u64 {
+        return Err(AppError::ConfigLoad(format!(
+            "Database timeout too long (max: {} ms)",
+            MAX_DB_TIMEOUT_MS
+        )));
+    }
+    if !FTS_TOKENIZERS.contains(&preferences.fts_tokenizer.as_str()) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid fts_tokenizer '{}'. Must be one of: {}",
+            preferences.fts_tokenizer,
+            FTS_TOKENIZERS.join(", ")
+        )));
+    }
+    if !NEW_NOTE_LOCATIONS.contains(&preferences.new_note_location.as_str()) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid new_note_location '{}'. Must be one of: {}",
+            preferences.new_note_location,
+            NEW_NOTE_LOCATIONS.join(", ")
+        )));
+    }
+    if !EXPIRED_NOTE_ACTIONS.contains(&preferences.expired_notes.as_str()) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid expired_notes '{}'. Must be one of: {}",
+            preferences.expired_notes,
+            EXPIRED_NOTE_ACTIONS.join(", ")
+        )));
+    }
+    normalize_folder_name(&preferences.inbox_folder).map_err(|e| {
+        AppError::ConfigLoad(format!(
+            "Invalid inbox_folder '{}': {}",
+            preferences.inbox_folder, e
+        ))
+    })?;
+    if !LINE_ENDING_MODES.contains(&preferences.line_endings.as_str()) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid line_endings '{}'. Must be one of: {}",
+            preferences.line_endings,
+            LINE_ENDING_MODES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_lint_config(lint: &LintConfig) -> AppResult<()> {
+    if lint.max_line_length > 10000 {
+        return Err(AppError::ConfigLoad(
+            "Max line length too large (max: 10000)".to_string(),
+        ));
+    }
+    if lint
+        .required_frontmatter_fields
+        .iter()
+        .any(|field| field.trim().is_empty() || field.contains(':'))
+    {
+        return Err(AppError::ConfigLoad(
+            "Required front-matter fields must be non-empty names without ':'".to_string(),
+        ));
+    }
+    for (field, field_type) in &lint.field_types {
+        if field.trim().is_empty() || field.contains(':') {
+            return Err(AppError::ConfigLoad(
+                "Typed front-matter fields must be non-empty names without ':'".to_string(),
+            ));
+        }
+        if !FRONTMATTER_FIELD_TYPES.contains(&field_type.as_str()) {
+            return Err(AppError::ConfigLoad(format!(
+                "Invalid type '{}' for front-matter field '{}'. Valid types: {}",
+                field_type,
+                field,
+                FRONTMATTER_FIELD_TYPES.join(", ")
+            )));
+        }
+    }
+    for (name, pattern) in [
+        ("filename_pattern", &lint.filename_pattern),
+        ("field_name_pattern", &lint.field_name_pattern),
+    ] {
+        if let Err(e) = regex::Regex::new(pattern) {
+            return Err(AppError::ConfigLoad(format!(
+                "Invalid {} '{}': {}",
+                name, pattern, e
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_citations_config(citations: &CitationsConfig) -> AppResult<()> {
+    let file = citations.bibliography_file.trim();
+    if !file.is_empty() && !crate::utilities::config_helpers::is_supported_bibliography_file(file) {
+        return Err(AppError::ConfigLoad(format!(
+            "Unsupported bibliography file '{}'. Expected a .bib or .json file",
+            file
+        )));
+    }
+    if citations.references_heading.trim().is_empty() {
+        return Err(AppError::ConfigLoad(
+            "References heading cannot be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_sync_config(sync: &SyncConfig) -> AppResult<()> {
+    if sync.lan_port < 1024 {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid LAN sync port {}. Must be between 1024 and 65535",
+            sync.lan_port
+        )));
+    }
+    if sync.device_name.chars().count() > 63 {
+        return Err(AppError::ConfigLoad(
+            "Device name must be at most 63 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_habits_config(habits: &HabitsConfig) -> AppResult<()> {
+    if habits.habits.iter().any(|habit| habit.trim().is_empty()) {
+        return Err(AppError::ConfigLoad(
+            "Habit names cannot be empty".to_string(),
+        ));
+    }
+    if habits
+        .daily_notes_folder
+        .split('/')
+        .any(|part| part == "..")
+    {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid daily notes folder '{}'",
+            habits.daily_notes_folder
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_templates_config(templates: &TemplatesConfig) -> AppResult<()> {
+    let directory = std::path::Path::new(&templates.directory);
+    if directory.is_relative()
+        && directory
+            .components()
+            .any(|part| part == std::path::Component::ParentDir)
+    {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid templates directory '{}'",
+            templates.directory
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_metadata_config(metadata: &MetadataConfig) -> AppResult<()> {
+    if let Some(field) = metadata
+        .fields
+        .iter()
+        .find(|field| !crate::utilities::config_helpers::is_valid_metadata_field(field))
+    {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid metadata field '{}'",
+            field
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_capture_config(capture: &CaptureConfig) -> AppResult<()> {
+    if !capture.shortcut.trim().is_empty() {
+        validate_shortcut_format(&capture.shortcut)?;
+    }
+    if !capture.clipboard_search_shortcut.trim().is_empty() {
+        validate_shortcut_format(&capture.clipboard_search_shortcut)?;
+    }
+    validate_note_name(&capture.inbox_note)
+}
+
+pub fn validate_email_config(email: &EmailConfig) -> AppResult<()> {
+    if !SMTP_SECURITY_MODES.contains(&email.security.as_str()) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid email security '{}'. Must be one of: {}",
+            email.security,
+            SMTP_SECURITY_MODES.join(", ")
+        )));
+    }
+    if email.smtp_port == 0 {
+        return Err(AppError::ConfigLoad("Invalid SMTP port 0".to_string()));
+    }
+    if !email.from_address.is_empty() && !email.from_address.contains('@') {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid email from_address '{}'",
+            email.from_address
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_webhooks_config(webhooks: &WebhooksConfig) -> AppResult<()> {
+    for (key, url) in [
+        ("slack_url", &webhooks.slack_url),
+        ("discord_url", &webhooks.discord_url),
+    ] {
+        if !url.is_empty() && !is_https_url(url) {
+            return Err(AppError::ConfigLoad(format!(
+                "Invalid webhooks {}: must be an https URL",
+                key
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_geo_config(geo: &GeoConfig) -> AppResult<()> {
+    if !GEOCODERS.contains(&geo.geocoder.as_str()) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid geocoder '{}'. Must be empty or one of: {}",
+            geo.geocoder,
+            GEOCODERS[1..].join(", ")
+        )));
+    }
+    if !geo.geocoder_url.is_empty() && !is_https_url(&geo.geocoder_url) {
+        return Err(AppError::ConfigLoad(
+            "Invalid geo geocoder_url: must be an https URL".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_automation_config(automation: &AutomationConfig) -> AppResult<()> {
+    automation.rules.iter().try_for_each(validate_tag_rule)
+}
+
+pub fn validate_vault(vault: &VaultConfig) -> AppResult<()> {
+    if vault.name.trim().is_empty()
+        || vault.name.chars().any(char::is_control)
+        || vault.name.chars().count() > MAX_VAULT_NAME_CHARS
+    {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid vault name '{}'. Must be a single line of at most {} characters",
+            vault.name, MAX_VAULT_NAME_CHARS
+        )));
+    }
+    if !std::path::Path::new(&vault.path).is_absolute() {
+        return Err(AppError::ConfigLoad(format!(
+            "Vault path '{}' must be absolute",
+            vault.path
+        )));
+    }
+    validate_notes_directory(&vault.path)
+}
+
+pub fn validate_vaults(vaults: &[VaultConfig]) -> AppResult<()> {
+    for (i, vault) in vaults.iter().enumerate() {
+        validate_vault(vault)?;
+        if vaults[..i]
+            .iter()
+            .any(|other| other.name.eq_ignore_ascii_case(&vault.name))
+        {
+            return Err(AppError::ConfigLoad(format!(
+                "Duplicate vault name '{}'",
+                vault.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_reminders_config(reminders: &RemindersConfig) -> AppResult<()> {
+    if parse_reminder_time(&reminders.notify_time).is_none() {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid reminders notify_time '{}'. Must be HH:MM",
+            reminders.notify_time
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_tag_rule(rule: &TagRule) -> AppResult<()> {
+    if !crate::utilities::tags::is_valid_tag_name(&rule.tag) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid automation rule tag '{}'",
+            rule.tag
+        )));
+    }
+    if rule.move_to.is_empty() && rule.append_template.is_empty() {
+        return Err(AppError::ConfigLoad(format!(
+            "Automation rule for '{}' needs move_to or append_template",
+            rule.tag
+        )));
+    }
+    if !rule.move_to.is_empty()
+        && rule
+            .move_to
+            .split('/')
+            .any(|part| part.is_empty() || part.starts_with('.'))
+    {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid automation rule folder '{}'",
+            rule.move_to
+        )));
+    }
+    if rule.append_template.contains(['/', '\\']) || rule.append_template.starts_with('.') {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid automation rule template '{}'",
+            rule.append_template
+        )));
+    }
     Ok(())
 }
 
@@ -292,3 +632,41 @@ pub fn validate_note_name(note_name: &str) -> AppResult<()> {
     }
     Ok(())
 }
+
+/// Checks a front-matter field can be written as one `key: value` line and
+/// read back unchanged.
+pub fn validate_frontmatter_field(key: &str, value: Option<&FrontmatterValue>) -> AppResult<()> {
+    if key.trim().is_empty() {
+        return Err(AppError::validation_error("key", "Key cannot be empty"));
+    }
+    if key != key.trim()
+        || key.starts_with(['#', '-', '[', '{', '"', '\''])
+        || key.contains([':', '\n', '\r'])
+        || key.chars().any(char::is_control)
+    {
+        return Err(AppError::validation_error(
+            "key",
+            &format!("'{}' is not a valid front-matter key", key),
+        ));
+    }
+    if key.len() > 100 {
+        return Err(AppError::validation_error("key", "Key too long"));
+    }
+
+    match value {
+        Some(FrontmatterValue::Text(text)) if text.contains(['\n', '\r']) => Err(
+            AppError::validation_error("value", "Value cannot span several lines"),
+        ),
+        Some(FrontmatterValue::List(items))
+            if items
+                .iter()
+                .any(|item| item.trim().is_empty() || item.contains([',', '\n', '\r'])) =>
+        {
+            Err(AppError::validation_error(
+                "value",
+                "List items must be non-empty and cannot contain commas or line breaks",
+            ))
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::file_safety::write_app_data_file,
+};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Labels of the backups in a backup folder, keyed by backup filename
+pub const VERSION_LABELS_FILE: &str = "version_labels.json";
+const MAX_LABEL_CHARS: usize = 100;
+
+pub fn load_version_labels(backup_dir: &Path) -> AppResult<BTreeMap<String, String>> {
+    let content = match fs::read_to_string(backup_dir.join(VERSION_LABELS_FILE)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => {
+            return Err(AppError::FileRead(format!(
+                "Failed to read version labels: {}",
+                e
+            )))
+        }
+    };
+
+    serde_json::from_str(&content)
+        .map_err(|e| AppError::FileRead(format!("Invalid version labels: {}", e)))
+}
+
+/// Labels `version`, or removes its label when `label` is blank. Labels of
+/// backups that no longer exist are dropped on the way.
+pub fn set_version_label(backup_dir: &Path, version: &str, label: &str) -> AppResult<()> {
+    let label = normalize_version_label(label)?;
+    let mut labels = load_version_labels(backup_dir)?;
+    labels.retain(|filename, _| backup_dir.join(filename).is_file());
+    match label {
+        Some(label) => labels.insert(version.to_string(), label),
+        None => labels.remove(version),
+    };
+
+    let content = serde_json::to_string_pretty(&labels)
+        .map_err(|e| AppError::FileWrite(format!("Failed to serialize version labels: {}", e)))?;
+    write_app_data_file(&backup_dir.join(VERSION_LABELS_FILE), content)
+}
+
+/// A trimmed label, `None` when blank. Rejects multi-line or overlong labels.
+pub fn normalize_version_label(label: &str) -> AppResult<Option<String>> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Ok(None);
+    }
+    if label.chars().any(char::is_control) || label.chars().count() > MAX_LABEL_CHARS {
+        return Err(AppError::validation_error(
+            "label",
+            &format!(
+                "Label must be a single line of at most {} characters",
+                MAX_LABEL_CHARS
+            ),
+        ));
+    }
+    Ok(Some(label.to_string()))
+}
@@ -0,0 +1,127 @@
+use crate::{
+    core::{AppError, AppResult},
+    utilities::file_safety::write_app_data_file,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Prefix of the temp files `safe_write_note` renames over notes
+pub const WRITE_TEMP_PREFIX: &str = "write_temp_";
+const RECOVERY_INDEX_FILE: &str = "index.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoverableWrite {
+    pub id: String,
+    /// Note the interrupted save was writing, when it's known and inside the
+    /// notes directory
+    pub note_name: Option<String>,
+    /// Unix seconds when the save started
+    pub written_at: i64,
+    pub content: String,
+}
+
+/// Sidecar file next to a temp file recording which note it was written for.
+pub fn temp_target_path(temp_path: &Path) -> PathBuf {
+    temp_path.with_extension("target")
+}
+
+pub fn list_recoverable_writes(recovery_dir: &Path) -> AppResult<Vec<RecoverableWrite>> {
+    let content = match fs::read_to_string(recovery_dir.join(RECOVERY_INDEX_FILE)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(AppError::FileRead(format!(
+                "Failed to read recoverable writes: {}",
+                e
+            )))
+        }
+    };
+
+    let mut writes: Vec<RecoverableWrite> = serde_json::from_str(&content)
+        .map_err(|e| AppError::FileRead(format!("Invalid recoverable writes: {}", e)))?;
+    writes.sort_by_key(|write| std::cmp::Reverse(write.written_at));
+    Ok(writes)
+}
+
+fn save_recoverable_writes(recovery_dir: &Path, writes: &[RecoverableWrite]) -> AppResult<()> {
+    let content = serde_json::to_string_pretty(writes).map_err(|e| {
+        AppError::FileWrite(format!("Failed to serialize recoverable writes: {}", e))
+    })?;
+    write_app_data_file(&recovery_dir.join(RECOVERY_INDEX_FILE), content)
+}
+
+pub fn discard_recoverable_write(recovery_dir: &Path, id: &str) -> AppResult<()> {
+    let mut writes = list_recoverable_writes(recovery_dir)?;
+    let before = writes.len();
+    writes.retain(|write| write.id != id);
+    if writes.len() == before {
+        return Err(AppError::FileNotFound(format!(
+            "Recoverable write not found: {}",
+            id
+        )));
+    }
+    save_recoverable_writes(recovery_dir, &writes)
+}
+
+/// Registers the text of temp files left behind by saves that never finished,
+/// so they can be removed without losing it. Empty files and text the note
+/// already holds are skipped. Returns how many writes were registered.
+pub fn recover_temp_writes(
+    temp_dir: &Path,
+    recovery_dir: &Path,
+    notes_dir: &Path,
+) -> AppResult<usize> {
+    let mut writes = list_recoverable_writes(recovery_dir)?;
+    let mut recovered = 0;
+
+    for entry in fs::read_dir(temp_dir)?.flatten() {
+        let filename = entry.file_name().to_string_lossy().to_string();
+        let Some(id) = filename
+            .strip_prefix(WRITE_TEMP_PREFIX)
+            .and_then(|rest| rest.strip_suffix(".md"))
+        else {
+            continue;
+        };
+        if writes.iter().any(|write| write.id == id) {
+            continue;
+        }
+
+        let content = String::from_utf8_lossy(&fs::read(entry.path())?).to_string();
+        let target = fs::read_to_string(temp_target_path(&entry.path()))
+            .ok()
+            .map(PathBuf::from);
+        if content.trim().is_empty()
+            || target
+                .as_ref()
+                .and_then(|target| fs::read_to_string(target).ok())
+                .is_some_and(|current| current == content)
+        {
+            continue;
+        }
+
+        writes.push(RecoverableWrite {
+            id: id.to_string(),
+            note_name: target.and_then(|target| {
+                target
+                    .strip_prefix(notes_dir)
+                    .ok()
+                    .map(|name| name.to_string_lossy().to_string())
+            }),
+            // Temp files are named after the save's start in nanoseconds
+            written_at: id
+                .parse::<u128>()
+                .map(|nanos| (nanos / 1_000_000_000) as i64)
+                .unwrap_or(0),
+            content,
+        });
+        recovered += 1;
+    }
+
+    if recovered > 0 {
+        save_recoverable_writes(recovery_dir, &writes)?;
+    }
+    Ok(recovered)
+}
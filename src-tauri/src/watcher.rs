@@ -4,16 +4,37 @@ use std::path::PathBuf;
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 
 use crate::{
+    commands::tag_rules::apply_tag_rules,
     config::get_config_notes_dir,
+    core::{
+        events::{emit_event, CacheRefreshed},
+        service_health::{ServiceName, SERVICES},
+    },
     database::with_db,
     logging::log,
-    services::note_service::update_note_in_database,
-    utilities::file_safety::{create_versioned_backup, BackupType},
+    services::{
+        external_change_service::{record_external_change, ExternalChangeKind},
+        note_created_service::forget_created,
+        note_service::update_note_in_database,
+        reading_progress_service::forget_reading_progress,
+    },
+    utilities::{
+        file_safety::{create_versioned_backup, BackupType},
+        paths::get_external_changes_path,
+        text_encoding::read_note_text,
+    },
 };
-use std::sync::atomic::{AtomicU32, Ordering};
+use rusqlite::OptionalExtension;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// How often the event loop checks whether it was asked to stop
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Shutdown flag of the running event loop, if any
+static WATCHER_SHUTDOWN: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
 
 struct DebouncedWatcher {
     pending_events: Arc<Mutex<HashMap<PathBuf, Instant>>>,
@@ -76,6 +97,17 @@ impl DebouncedWatcher {
 pub fn setup_notes_watcher(
     app_handle: AppHandle,
     app_state: Arc<crate::core::state::AppState>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let result = start_notes_watcher(app_handle, app_state);
+    if let Err(e) = &result {
+        SERVICES.mark_error(ServiceName::Watcher, &e.to_string());
+    }
+    result
+}
+
+fn start_notes_watcher(
+    app_handle: AppHandle,
+    app_state: Arc<crate::core::state::AppState>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let canonical_notes_dir = setup_canonical_notes_directory()?;
     let debounced_watcher = Arc::new(DebouncedWatcher::new(500));
@@ -84,6 +116,16 @@ pub fn setup_notes_watcher(
     watcher.watch(&canonical_notes_dir, RecursiveMode::Recursive)?;
     log("WATCHER_SETUP", "File watcher started successfully", None);
 
+    let shutdown = Arc::new(AtomicBool::new(false));
+    if let Some(previous) = WATCHER_SHUTDOWN
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .replace(Arc::clone(&shutdown))
+    {
+        previous.store(true, Ordering::Relaxed);
+    }
+    SERVICES.mark_running(ServiceName::Watcher);
+
     spawn_watcher_event_loop(
         app_handle,
         app_state,
@@ -91,11 +133,26 @@ pub fn setup_notes_watcher(
         canonical_notes_dir,
         rx,
         watcher,
+        shutdown,
     );
 
     Ok(())
 }
 
+/// Asks the running event loop to exit; its watcher is dropped within
+/// [`SHUTDOWN_POLL_INTERVAL`].
+pub fn stop_notes_watcher() {
+    if let Some(shutdown) = WATCHER_SHUTDOWN
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+    {
+        shutdown.store(true, Ordering::Relaxed);
+        SERVICES.mark_stopped(ServiceName::Watcher);
+        log("WATCHER_SETUP", "File watcher stopped", None);
+    }
+}
+
 fn setup_canonical_notes_directory() -> Result<PathBuf, Box<dyn std::error::Error>> {
     let notes_dir = get_config_notes_dir();
 
@@ -136,8 +193,9 @@ fn spawn_watcher_event_loop(
     app_state: Arc<crate::core::state::AppState>,
     debounced_watcher: Arc<DebouncedWatcher>,
     canonical_notes_dir: PathBuf,
-    rx: mpsc::Receiver<Event>,
+    rx: WatcherReceiver,
     watcher: RecommendedWatcher,
+    shutdown: Arc<AtomicBool>,
 ) {
     let app_handle_clone = app_handle.clone();
     let debounced_watcher_clone = debounced_watcher.clone();
@@ -147,7 +205,23 @@ fn spawn_watcher_event_loop(
     thread::spawn(move || {
         let _watcher = watcher;
 
-        for event in rx {
+        while !shutdown.load(Ordering::Relaxed) {
+            let event = match rx.recv_timeout(SHUTDOWN_POLL_INTERVAL) {
+                Ok(Ok(event)) => event,
+                Ok(Err(e)) => {
+                    log("WATCHER_ERROR", "File watcher error", Some(&e.to_string()));
+                    SERVICES.mark_error(ServiceName::Watcher, &e.to_string());
+                    continue;
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    log("WATCHER_ERROR", "File watcher channel closed", None);
+                    SERVICES.mark_error(ServiceName::Watcher, "File watcher channel closed");
+                    break;
+                }
+            };
+            SERVICES.record_activity(ServiceName::Watcher);
+
             match event.kind {
                 EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
                     if involves_note_files(&event) {
@@ -237,20 +311,20 @@ fn process_file_event_async(
             None,
         );
 
-        process_file_paths(&paths_to_update, &canonical_dir, &app_state_for_task);
-        emit_cache_refresh_notification(&app_handle_for_refresh);
+        let refreshed = process_file_paths(&paths_to_update, &canonical_dir, &app_state_for_task);
+        emit_event(&app_handle_for_refresh, refreshed);
     });
 }
 
+type WatcherReceiver = mpsc::Receiver<Result<Event, notify::Error>>;
+
 fn create_watcher_and_channel(
-) -> Result<(RecommendedWatcher, mpsc::Receiver<Event>), Box<dyn std::error::Error>> {
+) -> Result<(RecommendedWatcher, WatcherReceiver), Box<dyn std::error::Error>> {
     let (tx, rx) = mpsc::channel();
 
     let watcher = RecommendedWatcher::new(
         move |res: Result<Event, notify::Error>| {
-            if let Ok(event) = res {
-                let _ = tx.send(event);
-            }
+            let _ = tx.send(res);
         },
         Config::default(),
     )?;
@@ -283,99 +357,196 @@ fn get_file_modification_time(path: &PathBuf) -> i64 {
         .unwrap_or(0)
 }
 
-fn create_backup_if_content_changed(
+/// Where external changes are queued for review, or `None` when
+/// `review_external_changes` is off.
+fn external_change_store(app_state: &crate::core::state::AppState) -> Option<PathBuf> {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    if !config.preferences.review_external_changes {
+        return None;
+    }
+    get_external_changes_path(std::path::Path::new(&config.notes_directory)).ok()
+}
+
+fn queue_external_change(
+    store: &std::path::Path,
+    filename: &str,
+    kind: ExternalChangeKind,
+    backup_path: Option<&std::path::Path>,
+) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    if let Err(e) = record_external_change(store, filename, kind, backup_path, now) {
+        log(
+            "EXTERNAL_CHANGE",
+            &format!("Failed to queue external change for {}", filename),
+            Some(&e.to_string()),
+        );
+    }
+}
+
+fn create_external_change_backup(
     path: &PathBuf,
     filename: &str,
-    new_content: &str,
+    old_content: &str,
+) -> Option<PathBuf> {
+    match create_versioned_backup(path, BackupType::ExternalChange, Some(old_content)) {
+        Ok(backup_path) => {
+            log(
+                "FILE_BACKUP",
+                "Created external change backup",
+                Some(&backup_path.display().to_string()),
+            );
+            Some(backup_path)
+        }
+        Err(e) => {
+            log(
+                "FILE_BACKUP",
+                &format!("Failed to create external change backup for {}", filename),
+                Some(&e.to_string()),
+            );
+            None
+        }
+    }
+}
+
+/// The indexed content of `filename`: `Ok(None)` for a note that is not
+/// indexed yet, `Err` when the database could not be read.
+fn indexed_content(
+    filename: &str,
     app_state: &Arc<crate::core::state::AppState>,
-) {
-    let _ = with_db(app_state, |conn| {
+) -> Result<Option<String>, ()> {
+    with_db(app_state, |conn| {
         let mut stmt = conn.prepare("SELECT content FROM notes WHERE filename = ?1")?;
-        match stmt.query_row(rusqlite::params![filename], |row| row.get::<_, String>(0)) {
-            Ok(old_content) => {
-                if old_content != new_content {
-                    match create_versioned_backup(
-                        path,
-                        BackupType::ExternalChange,
-                        Some(&old_content),
-                    ) {
-                        Ok(backup_path) => {
-                            log(
-                                "FILE_BACKUP",
-                                "Created external change backup",
-                                Some(&backup_path.display().to_string()),
-                            );
-                        }
-                        Err(e) => {
-                            log(
-                                "FILE_BACKUP",
-                                &format!(
-                                    "Failed to create external change backup for {}",
-                                    filename
-                                ),
-                                Some(&e.to_string()),
-                            );
-                        }
-                    }
-                }
-            }
-            Err(_) => {}
-        }
-        Ok(())
+        Ok(stmt
+            .query_row(rusqlite::params![filename], |row| row.get::<_, String>(0))
+            .optional()?)
     })
-    .unwrap_or_else(|e| {
+    .map_err(|e| {
         log(
             "FILE_BACKUP",
             "Failed to check for existing content before external change backup",
             Some(&e.to_string()),
         );
-    });
+    })
+}
+
+/// Backs up the indexed content when the file on disk differs from it and
+/// reports what kind of change this was, if any.
+fn create_backup_if_content_changed(
+    path: &PathBuf,
+    filename: &str,
+    new_content: &str,
+    old_content: Option<&str>,
+) -> Option<(ExternalChangeKind, Option<PathBuf>)> {
+    match old_content {
+        Some(old_content) if old_content != new_content => Some((
+            ExternalChangeKind::Modified,
+            create_external_change_backup(path, filename, old_content),
+        )),
+        Some(_) => None,
+        None => Some((ExternalChangeKind::Created, None)),
+    }
 }
 
+/// Indexes the changed file and returns its name, which differs from
+/// `filename` when a tag rule moved it.
 fn process_existing_file(
     path: &PathBuf,
     filename: &str,
     app_state: &Arc<crate::core::state::AppState>,
-) {
+) -> String {
     let modified = get_file_modification_time(path);
 
-    if let Ok(content) = std::fs::read_to_string(path) {
-        create_backup_if_content_changed(path, filename, &content, app_state);
+    let Ok(content) = read_note_text(path) else {
+        return filename.to_string();
+    };
+    let previous = indexed_content(filename, app_state);
+    let change = previous.as_ref().ok().and_then(|old_content| {
+        create_backup_if_content_changed(path, filename, &content, old_content.as_deref())
+    });
 
-        if let Err(e) = update_note_in_database(app_state, filename, &content, modified) {
-            log(
-                "DATABASE_UPDATE",
-                &format!("Failed to update note {}", filename),
-                Some(&e.to_string()),
-            );
+    if let Err(e) = update_note_in_database(app_state, filename, &content, modified) {
+        log(
+            "DATABASE_UPDATE",
+            &format!("Failed to update note {}", filename),
+            Some(&e.to_string()),
+        );
+    }
+
+    // The external editor's save ends its session; in-app saves work again
+    if matches!(change, Some((ExternalChangeKind::Modified, _)))
+        && app_state.external_edits.end(filename)
+    {
+        log(
+            "EXTERNAL_EDIT",
+            &format!("External edit of {} finished", filename),
+            None,
+        );
+    }
+
+    if let (Some((kind, backup_path)), Some(store)) = (&change, external_change_store(app_state)) {
+        queue_external_change(&store, filename, *kind, backup_path.as_deref());
+    }
+
+    if change.is_some() {
+        let previous = previous.ok().flatten();
+        if let Some(plan) = apply_tag_rules(app_state, filename, previous.as_deref(), &content) {
+            return plan.final_name().to_string();
         }
     }
+    filename.to_string()
 }
 
-fn process_deleted_file(filename: &str, app_state: &Arc<crate::core::state::AppState>) {
-    if let Err(e) = crate::database::with_db(app_state, |conn| {
+fn process_deleted_file(
+    path: &PathBuf,
+    filename: &str,
+    app_state: &Arc<crate::core::state::AppState>,
+) {
+    let store = external_change_store(app_state);
+    let result = crate::database::with_db(app_state, |conn| {
+        let old_content: Option<String> = if store.is_some() {
+            conn.query_row(
+                "SELECT content FROM notes WHERE filename = ?1",
+                rusqlite::params![filename],
+                |row| row.get(0),
+            )
+            .optional()?
+        } else {
+            None
+        };
         conn.execute(
             "DELETE FROM notes WHERE filename = ?1",
             rusqlite::params![filename],
         )
         .map_err(|e| format!("Database error: {}", e))?;
-        Ok(())
-    }) {
-        log(
-            "DATABASE_DELETE",
-            &format!("Failed to delete note {}", filename),
-            Some(&e.to_string()),
-        );
-    }
-}
+        forget_created(conn, filename)?;
+        forget_reading_progress(conn, filename)?;
+        Ok(old_content)
+    });
 
-fn emit_cache_refresh_notification(app_handle: &AppHandle) {
-    if let Err(e) = app_handle.emit("cache-refreshed", ()) {
-        log(
-            "UI_EVENT",
-            "Failed to emit cache-refreshed event",
-            Some(&e.to_string()),
-        );
+    app_state.external_edits.end(filename);
+    match result {
+        Ok(old_content) => {
+            // Only notes that were indexed count as deleted
+            if let (Some(store), Some(old_content)) = (store, old_content) {
+                let backup_path = create_external_change_backup(path, filename, &old_content);
+                queue_external_change(
+                    &store,
+                    filename,
+                    ExternalChangeKind::Deleted,
+                    backup_path.as_deref(),
+                );
+            }
+        }
+        Err(e) => {
+            log(
+                "DATABASE_DELETE",
+                &format!("Failed to delete note {}", filename),
+                Some(&e.to_string()),
+            );
+        }
     }
 }
 
@@ -383,7 +554,8 @@ fn process_file_paths(
     paths: &[PathBuf],
     canonical_notes_dir: &PathBuf,
     app_state: &Arc<crate::core::state::AppState>,
-) {
+) -> CacheRefreshed {
+    let mut refreshed = CacheRefreshed::default();
     for path in paths {
         match path.strip_prefix(canonical_notes_dir) {
             Ok(relative) => {
@@ -394,9 +566,14 @@ fn process_file_paths(
                 }
 
                 if path.exists() {
-                    process_existing_file(path, &filename, app_state);
+                    let name = process_existing_file(path, &filename, app_state);
+                    if name != filename {
+                        refreshed.removed.push(filename);
+                    }
+                    refreshed.changed.push(name);
                 } else {
-                    process_deleted_file(&filename, app_state);
+                    process_deleted_file(path, &filename, app_state);
+                    refreshed.removed.push(filename);
                 }
             }
             Err(_) => {
@@ -412,6 +589,7 @@ fn process_file_paths(
             }
         }
     }
+    refreshed
 }
 
 fn handle_periodic_cleanup(debounced_watcher: &Arc<DebouncedWatcher>) {
@@ -23,6 +23,12 @@ import { createSearchActions } from './actions/search.svelte'
 import { createSettingsActions } from './actions/settings.svelte'
 import { createKeyboardActions } from './actions/keyboard.svelte'
 import { setupAppEffects } from './effects/app.svelte'
+import type {
+  ClipboardSearchEvent,
+  DbLoadingErrorEvent,
+  DbLoadingProgressEvent,
+  DbLoadingStartEvent,
+} from '../types/events'
 
 // eslint-disable-next-line @typescript-eslint/no-empty-object-type
 interface AppCoordinatorDeps {}
@@ -336,6 +342,7 @@ export function createAppCoordinator(
     unlistenDbLoadingProgress: () => void
     unlistenDbLoadingComplete: () => void
     unlistenDbLoadingError: () => void
+    unlistenClipboardSearch: () => void
   }> {
     const unlisten = await listen('open-preferences', async () => {
       await settingsActions.openSettingsPane()
@@ -349,17 +356,17 @@ export function createAppCoordinator(
       isFirstRun = true
     })
 
-    const unlistenDbLoadingStart = await listen<string>(
+    const unlistenDbLoadingStart = await listen<DbLoadingStartEvent>(
       'db-loading-start',
       (event) => {
-        progressManager.start(event.payload)
+        progressManager.start(event.payload.message)
       }
     )
 
-    const unlistenDbLoadingProgress = await listen<string>(
+    const unlistenDbLoadingProgress = await listen<DbLoadingProgressEvent>(
       'db-loading-progress',
       (event) => {
-        progressManager.updateProgress(event.payload)
+        progressManager.updateProgress(event.payload.message)
       }
     )
 
@@ -370,10 +377,18 @@ export function createAppCoordinator(
       }
     )
 
-    const unlistenDbLoadingError = await listen<string>(
+    const unlistenDbLoadingError = await listen<DbLoadingErrorEvent>(
       'db-loading-error',
       (event) => {
-        progressManager.setError(event.payload)
+        progressManager.setError(event.payload.message)
+      }
+    )
+
+    const unlistenClipboardSearch = await listen<ClipboardSearchEvent>(
+      'clipboard-search',
+      (event) => {
+        searchManager.showResults(event.payload.query, event.payload.results)
+        focusManager.focusSearch()
       }
     )
 
@@ -385,6 +400,7 @@ export function createAppCoordinator(
       unlistenDbLoadingProgress,
       unlistenDbLoadingComplete,
       unlistenDbLoadingError,
+      unlistenClipboardSearch,
     }
   }
 
@@ -416,6 +432,7 @@ export function createAppCoordinator(
       unlistenDbLoadingProgress: () => void
       unlistenDbLoadingComplete: () => void
       unlistenDbLoadingError: () => void
+    unlistenClipboardSearch: () => void
     },
     cleanupEffects: () => void
   ): () => void {
@@ -433,6 +450,7 @@ export function createAppCoordinator(
       listeners.unlistenDbLoadingProgress()
       listeners.unlistenDbLoadingComplete()
       listeners.unlistenDbLoadingError()
+      listeners.unlistenClipboardSearch()
       configManager.cleanup()
     }
   }
@@ -69,6 +69,10 @@ export function createConfigManager(): ConfigManager {
       markdown_render_theme: 'modern-dark',
       md_render_code_theme: 'gruvbox-dark-medium',
       always_on_top: false,
+      markdown_tables: true,
+      markdown_task_lists: true,
+      markdown_footnotes: true,
+      markdown_strikethrough: true,
     },
     editor: {
       mode: 'basic',
@@ -104,6 +108,18 @@ export function createConfigManager(): ConfigManager {
     },
     preferences: {
       max_search_results: 100,
+      track_rename_history: true,
+      trash_retention_days: 30,
+      review_external_changes: false,
+      fts_tokenizer: 'unicode61',
+      db_timeout_ms: 30000,
+      slow_query_ms: 500,
+      show_linked_mentions: false,
+      new_note_location: 'root',
+      inbox_folder: 'Inbox',
+      line_endings: 'preserve',
+      integrity_check_hours: 24,
+      expired_notes: 'flag',
     },
     isLoading: false,
     error: null,
@@ -31,6 +31,7 @@ interface SearchManager {
   readonly query: string
   setSearchInput(value: string): void
   setFilteredNotes(notes: string[]): void
+  showResults(query: string, notes: string[]): void
   clearSearch(): void
   executeSearch(query: string): Promise<string[]>
   setSearchCompleteCallback(callback: (notes: string[]) => void): void
@@ -123,6 +124,16 @@ export function createSearchManager(deps: SearchManagerDeps): SearchManager {
     state.filteredNotes = notes
   }
 
+  // Shows results searched elsewhere (e.g. the clipboard search shortcut)
+  // without running the query again
+  function showResults(query: string, notes: string[]): void {
+    clearTimeout(state.searchTimeout)
+    state.requestController?.abort()
+    state.searchInput = query
+    state.query = query
+    handleSuccessfulSearch(notes)
+  }
+
   function clearSearch(): void {
     setSearchInput('')
   }
@@ -130,6 +141,7 @@ export function createSearchManager(deps: SearchManagerDeps): SearchManager {
   return {
     setSearchInput,
     setFilteredNotes,
+    showResults,
     clearSearch,
 
     get isLoading(): boolean {
@@ -7,6 +7,42 @@
 import { invoke } from '@tauri-apps/api/core'
 import { notification } from '../utils/notification'
 
+export interface SearchResult {
+  filename: string
+  title: string
+  score: number
+  modified: number
+  /** HTML-escaped excerpt with matched terms in <mark>; null for recent notes */
+  snippet: string | null
+  highlighted_filename: string | null
+}
+
+export type SearchSort =
+  | 'relevance'
+  | 'modified_desc'
+  | 'modified_asc'
+  | 'filename'
+
+export interface SearchScope {
+  folder?: string
+  tag?: string
+}
+
+export interface SearchOptions {
+  offset?: number
+  limit?: number
+  sort?: SearchSort
+  scope?: SearchScope
+}
+
+/** Retries of a search the backend turned away with `Busy:` */
+const SEARCH_BUSY_RETRIES = 3
+const SEARCH_BUSY_RETRY_DELAY_MS = 50
+
+function isBusyError(e: unknown): boolean {
+  return String(e).startsWith('Busy:')
+}
+
 export function createNoteService() {
   const state = $state({
     isLoading: false,
@@ -145,15 +181,39 @@ export function createNoteService() {
     }
   }
 
-  async function search(query: string): Promise<string[]> {
-    try {
-      return await invoke<string[]>('search_notes', { query })
-    } catch (e) {
-      console.error('Failed to search notes:', e)
-      throw e
+  async function searchWithSnippets(
+    query: string,
+    options: SearchOptions = {}
+  ): Promise<SearchResult[]> {
+    for (let attempt = 0; ; attempt++) {
+      try {
+        return await invoke<SearchResult[]>('search_notes', {
+          query,
+          offset: options.offset,
+          limit: options.limit,
+          sort: options.sort,
+          scope: options.scope,
+        })
+      } catch (e) {
+        // Searches over the backend's rate or concurrency limit are retried
+        // after a short wait instead of leaving the results empty
+        if (isBusyError(e) && attempt < SEARCH_BUSY_RETRIES) {
+          await new Promise((resolve) =>
+            setTimeout(resolve, SEARCH_BUSY_RETRY_DELAY_MS * (attempt + 1))
+          )
+          continue
+        }
+        console.error('Failed to search notes:', e)
+        throw e
+      }
     }
   }
 
+  async function search(query: string): Promise<string[]> {
+    const results = await searchWithSnippets(query)
+    return results.map((result) => result.filename)
+  }
+
   async function initializeDatabase(): Promise<{
     success: boolean
     error?: string
@@ -184,6 +244,7 @@ export function createNoteService() {
     save,
 
     search,
+    searchWithSnippets,
 
     openInEditor,
     openFolder,
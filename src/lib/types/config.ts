@@ -14,6 +14,11 @@ export interface InterfaceConfig {
   always_on_top: boolean
   custom_ui_theme_path?: string
   custom_markdown_theme_path?: string
+  markdown_tables: boolean
+  markdown_task_lists: boolean
+  markdown_footnotes: boolean
+  markdown_strikethrough: boolean
+  mermaid_cli?: string
 }
 
 export interface EditorConfig {
@@ -52,4 +57,16 @@ export interface ShortcutsConfig {
 
 export interface PreferencesConfig {
   max_search_results: number
+  track_rename_history: boolean
+  trash_retention_days: number
+  review_external_changes: boolean
+  fts_tokenizer: string
+  db_timeout_ms: number
+  slow_query_ms: number
+  show_linked_mentions: boolean
+  new_note_location: 'inbox' | 'same_folder' | 'root'
+  inbox_folder: string
+  line_endings: 'preserve' | 'lf' | 'crlf'
+  integrity_check_hours: number
+  expired_notes: 'flag' | 'archive'
 }
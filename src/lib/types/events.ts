@@ -0,0 +1,83 @@
+// Payloads of backend events, mirroring src-tauri/src/core/events.rs
+
+export interface CacheRefreshedEvent {
+  changed: string[]
+  removed: string[]
+}
+
+export interface DbLoadingStartEvent {
+  message: string
+}
+
+export interface DbLoadingProgressEvent {
+  message: string
+  fraction: number | null
+}
+
+export interface DbLoadingErrorEvent {
+  message: string
+}
+
+export interface DictionaryUpdatedEvent {
+  words: string[]
+}
+
+export interface ReadingListUpdatedEvent {
+  url: string
+  note_name: string
+}
+
+export interface ReadingListFailedEvent {
+  url: string
+  message: string
+}
+
+export interface RerenderProgressEvent {
+  rendered: number
+  total: number
+  cancelled: boolean
+}
+
+export interface RerenderErrorEvent {
+  message: string
+}
+
+// Only emitted on macOS, from the Services menu
+export interface SelectionCapturedEvent {
+  note_name: string
+  created: boolean
+}
+
+export interface ClipboardSearchEvent {
+  query: string
+  results: string[]
+}
+
+export interface IntegrityAlertsRaisedEvent {
+  note_names: string[]
+}
+
+export interface NoteEditLockChangedEvent {
+  note_name: string
+  window_id: string | null
+}
+
+export interface VaultSwitchedEvent {
+  name: string
+  path: string
+}
+
+export interface NotesExpiredEvent {
+  note_names: string[]
+  archived: boolean
+}
+
+export interface LanPairingRequestedEvent {
+  peer: {
+    device_id: string
+    device_name: string
+    recipient: string
+    paired_at: string
+  }
+  fingerprint: string
+}
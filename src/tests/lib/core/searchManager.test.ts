@@ -201,4 +201,21 @@ describe('searchManager', () => {
       expect(searchManager.filteredNotes).toEqual(notes)
     })
   })
+
+  describe('showResults', () => {
+    it('should show results searched elsewhere without searching again', async () => {
+      vi.useFakeTimers()
+      searchManager.searchInput = 'typed query'
+
+      searchManager.showResults('clipboard text', ['found.md'])
+      await vi.runAllTimersAsync()
+
+      expect(mockNoteService.search).not.toHaveBeenCalled()
+      expect(searchManager.searchInput).toBe('clipboard text')
+      expect(searchManager.query).toBe('clipboard text')
+      expect(searchManager.filteredNotes).toEqual(['found.md'])
+
+      vi.useRealTimers()
+    })
+  })
 })
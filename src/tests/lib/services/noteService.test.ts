@@ -181,6 +181,29 @@ describe('noteService', () => {
     })
   })
 
+  describe('search', () => {
+    it('should retry a search the backend is too busy for', async () => {
+      const results = [{ filename: 'a.md' }]
+      mockInvoke
+        .mockRejectedValueOnce('Busy: search_notes is limited')
+        .mockResolvedValueOnce(results)
+
+      const found = await noteService.searchWithSnippets('query')
+
+      expect(found).toEqual(results)
+      expect(mockInvoke).toHaveBeenCalledTimes(2)
+    })
+
+    it('should not retry other search errors', async () => {
+      mockInvoke.mockRejectedValueOnce('Database error')
+
+      await expect(noteService.searchWithSnippets('query')).rejects.toBe(
+        'Database error'
+      )
+      expect(mockInvoke).toHaveBeenCalledTimes(1)
+    })
+  })
+
   describe('system integration', () => {
     it('should open note in editor', async () => {
       mockInvoke.mockResolvedValueOnce(undefined)